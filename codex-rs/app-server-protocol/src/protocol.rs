@@ -11,6 +11,7 @@ use codex_protocol::config_types::SandboxMode;
 use codex_protocol::config_types::Verbosity;
 use codex_protocol::parse_command::ParsedCommand;
 use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::Checkpoint;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::FileChange;
 use codex_protocol::protocol::ReviewDecision;
@@ -115,6 +116,25 @@ client_request_definitions! {
         params: InterruptConversationParams,
         response: InterruptConversationResponse,
     },
+    /// Capture the conversation's workspace as a checkpoint. The checkpoint is
+    /// reported through the `codex/event/checkpoint_created` notification.
+    CreateCheckpoint {
+        params: CreateCheckpointParams,
+        response: CreateCheckpointResponse,
+    },
+    /// List the checkpoints recorded for a conversation. Like
+    /// `InterruptConversation`, the reply is sent once the conversation
+    /// reports its checkpoints, which requires a conversation listener.
+    ListCheckpoints {
+        params: ListCheckpointsParams,
+        response: ListCheckpointsResponse,
+    },
+    /// Restore the conversation's workspace to an earlier checkpoint. Completion
+    /// is reported through the `codex/event/checkpoint_restored` notification.
+    RestoreCheckpoint {
+        params: RestoreCheckpointParams,
+        response: RestoreCheckpointResponse,
+    },
     AddConversationListener {
         params: AddConversationListenerParams,
         response: AddConversationSubscriptionResponse,
@@ -577,6 +597,40 @@ pub struct InterruptConversationResponse {
 #[serde(rename_all = "camelCase")]
 pub struct SendUserMessageResponse {}
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCheckpointParams {
+    pub conversation_id: ConversationId,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCheckpointResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct ListCheckpointsParams {
+    pub conversation_id: ConversationId,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct ListCheckpointsResponse {
+    /// Checkpoints that can be restored, oldest first.
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreCheckpointParams {
+    pub conversation_id: ConversationId,
+    pub checkpoint_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreCheckpointResponse {}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
 #[serde(rename_all = "camelCase")]
 pub struct AddConversationListenerParams {
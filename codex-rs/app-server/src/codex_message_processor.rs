@@ -12,6 +12,8 @@ use codex_app_server_protocol::ArchiveConversationResponse;
 use codex_app_server_protocol::AuthStatusChangeNotification;
 use codex_app_server_protocol::ClientRequest;
 use codex_app_server_protocol::ConversationSummary;
+use codex_app_server_protocol::CreateCheckpointParams;
+use codex_app_server_protocol::CreateCheckpointResponse;
 use codex_app_server_protocol::ExecCommandApprovalParams;
 use codex_app_server_protocol::ExecCommandApprovalResponse;
 use codex_app_server_protocol::ExecOneOffCommandParams;
@@ -25,6 +27,8 @@ use codex_app_server_protocol::InputItem as WireInputItem;
 use codex_app_server_protocol::InterruptConversationParams;
 use codex_app_server_protocol::InterruptConversationResponse;
 use codex_app_server_protocol::JSONRPCErrorError;
+use codex_app_server_protocol::ListCheckpointsParams;
+use codex_app_server_protocol::ListCheckpointsResponse;
 use codex_app_server_protocol::ListConversationsParams;
 use codex_app_server_protocol::ListConversationsResponse;
 use codex_app_server_protocol::LoginApiKeyParams;
@@ -36,6 +40,8 @@ use codex_app_server_protocol::NewConversationResponse;
 use codex_app_server_protocol::RemoveConversationListenerParams;
 use codex_app_server_protocol::RemoveConversationSubscriptionResponse;
 use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::RestoreCheckpointParams;
+use codex_app_server_protocol::RestoreCheckpointResponse;
 use codex_app_server_protocol::Result as JsonRpcResult;
 use codex_app_server_protocol::ResumeConversationParams;
 use codex_app_server_protocol::SendUserMessageParams;
@@ -58,9 +64,8 @@ use codex_core::NewConversation;
 use codex_core::RolloutRecorder;
 use codex_core::SessionMeta;
 use codex_core::auth::CLIENT_ID;
-use codex_core::auth::get_auth_file;
-use codex_core::auth::login_with_api_key;
-use codex_core::auth::try_read_auth_json;
+use codex_core::auth::load_auth_dot_json;
+use codex_core::auth::login_with_api_key_for_profile;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 use codex_core::config::ConfigToml;
@@ -68,16 +73,19 @@ use codex_core::config::load_config_as_toml;
 use codex_core::config_edit::CONFIG_KEY_EFFORT;
 use codex_core::config_edit::CONFIG_KEY_MODEL;
 use codex_core::config_edit::persist_overrides_and_clear_if_none;
+use codex_core::config_types::McpElicitationPolicy;
 use codex_core::default_client::get_codex_user_agent;
 use codex_core::exec::ExecParams;
 use codex_core::exec_env::create_env;
 use codex_core::get_platform_sandbox;
 use codex_core::git_info::git_diff_to_remote;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
+use codex_core::protocol::ElicitationRequestEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecApprovalRequestEvent;
 use codex_core::protocol::InputItem as CoreInputItem;
+use codex_core::protocol::ListCheckpointsResponseEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
 use codex_login::ServerOptions as LoginServerOptions;
@@ -129,6 +137,9 @@ pub(crate) struct CodexMessageProcessor {
     active_login: Arc<Mutex<Option<ActiveLogin>>>,
     // Queue of pending interrupt requests per conversation. We reply when TurnAborted arrives.
     pending_interrupts: Arc<Mutex<HashMap<ConversationId, Vec<RequestId>>>>,
+    // Queue of pending checkpoint list requests per conversation. We reply
+    // when ListCheckpointsResponse arrives.
+    pending_checkpoint_lists: Arc<Mutex<HashMap<ConversationId, Vec<RequestId>>>>,
     pending_fuzzy_searches: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
 }
 
@@ -149,6 +160,7 @@ impl CodexMessageProcessor {
             conversation_listeners: HashMap::new(),
             active_login: Arc::new(Mutex::new(None)),
             pending_interrupts: Arc::new(Mutex::new(HashMap::new())),
+            pending_checkpoint_lists: Arc::new(Mutex::new(HashMap::new())),
             pending_fuzzy_searches: Arc::new(Mutex::new(HashMap::new())),
         }
     }
@@ -182,6 +194,38 @@ impl CodexMessageProcessor {
             ClientRequest::InterruptConversation { request_id, params } => {
                 self.interrupt_conversation(request_id, params).await;
             }
+            ClientRequest::CreateCheckpoint { request_id, params } => {
+                let CreateCheckpointParams { conversation_id } = params;
+                if self
+                    .submit_checkpoint_op(&request_id, conversation_id, Op::Checkpoint)
+                    .await
+                {
+                    self.outgoing
+                        .send_response(request_id, CreateCheckpointResponse {})
+                        .await;
+                }
+            }
+            ClientRequest::ListCheckpoints { request_id, params } => {
+                self.list_checkpoints(request_id, params).await;
+            }
+            ClientRequest::RestoreCheckpoint { request_id, params } => {
+                let RestoreCheckpointParams {
+                    conversation_id,
+                    checkpoint_id,
+                } = params;
+                if self
+                    .submit_checkpoint_op(
+                        &request_id,
+                        conversation_id,
+                        Op::RestoreCheckpoint { checkpoint_id },
+                    )
+                    .await
+                {
+                    self.outgoing
+                        .send_response(request_id, RestoreCheckpointResponse {})
+                        .await;
+                }
+            }
             ClientRequest::AddConversationListener { request_id, params } => {
                 self.add_conversation_listener(request_id, params).await;
             }
@@ -250,7 +294,12 @@ impl CodexMessageProcessor {
             }
         }
 
-        match login_with_api_key(&self.config.codex_home, &params.api_key) {
+        match login_with_api_key_for_profile(
+            &self.config.codex_home,
+            self.config.auth_profile.as_deref(),
+            self.config.cli_auth_credentials_store_mode,
+            &params.api_key,
+        ) {
             Ok(()) => {
                 self.auth_manager.reload();
                 self.outgoing
@@ -280,6 +329,8 @@ impl CodexMessageProcessor {
 
         let opts = LoginServerOptions {
             open_browser: false,
+            auth_profile: config.auth_profile.clone(),
+            cli_auth_credentials_store_mode: config.cli_auth_credentials_store_mode,
             ..LoginServerOptions::new(config.codex_home.clone(), CLIENT_ID.to_string())
         };
 
@@ -535,11 +586,14 @@ impl CodexMessageProcessor {
     }
 
     async fn get_user_info(&self, request_id: RequestId) {
-        // Read alleged user email from auth.json (best-effort; not verified).
-        let auth_path = get_auth_file(&self.config.codex_home);
-        let alleged_user_email = match try_read_auth_json(&auth_path) {
-            Ok(auth) => auth.tokens.and_then(|t| t.id_token.email),
-            Err(_) => None,
+        // Read alleged user email from the stored credentials (best-effort; not verified).
+        let alleged_user_email = match load_auth_dot_json(
+            &self.config.codex_home,
+            self.config.auth_profile.as_deref(),
+            self.config.cli_auth_credentials_store_mode,
+        ) {
+            Ok(Some(auth)) => auth.tokens.and_then(|t| t.id_token.email),
+            Ok(None) | Err(_) => None,
         };
 
         let response = UserInfoResponse { alleged_user_email };
@@ -1088,6 +1142,64 @@ impl CodexMessageProcessor {
         let _ = conversation.submit(Op::Interrupt).await;
     }
 
+    /// Submit a checkpoint `op` to the conversation. Results are delivered as
+    /// `codex/event/*` notifications. Returns `false` after replying to
+    /// `request_id` with an error if the op could not be submitted.
+    async fn submit_checkpoint_op(
+        &self,
+        request_id: &RequestId,
+        conversation_id: ConversationId,
+        op: Op,
+    ) -> bool {
+        let Ok(conversation) = self
+            .conversation_manager
+            .get_conversation(conversation_id)
+            .await
+        else {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!("conversation not found: {conversation_id}"),
+                data: None,
+            };
+            self.outgoing.send_error(request_id.clone(), error).await;
+            return false;
+        };
+
+        if let Err(err) = conversation.submit(op).await {
+            let error = JSONRPCErrorError {
+                code: INTERNAL_ERROR_CODE,
+                message: format!("failed to submit checkpoint request: {err}"),
+                data: None,
+            };
+            self.outgoing.send_error(request_id.clone(), error).await;
+            return false;
+        }
+        true
+    }
+
+    async fn list_checkpoints(&self, request_id: RequestId, params: ListCheckpointsParams) {
+        let ListCheckpointsParams { conversation_id } = params;
+
+        // Record the pending request so we can reply when the conversation
+        // reports its checkpoints.
+        {
+            let mut map = self.pending_checkpoint_lists.lock().await;
+            map.entry(conversation_id)
+                .or_default()
+                .push(request_id.clone());
+        }
+
+        if !self
+            .submit_checkpoint_op(&request_id, conversation_id, Op::ListCheckpoints)
+            .await
+        {
+            let mut map = self.pending_checkpoint_lists.lock().await;
+            if let Some(pending) = map.get_mut(&conversation_id) {
+                pending.retain(|rid| rid != &request_id);
+            }
+        }
+    }
+
     async fn add_conversation_listener(
         &mut self,
         request_id: RequestId,
@@ -1114,6 +1226,8 @@ impl CodexMessageProcessor {
             .insert(subscription_id, cancel_tx);
         let outgoing_for_task = self.outgoing.clone();
         let pending_interrupts = self.pending_interrupts.clone();
+        let pending_checkpoint_lists = self.pending_checkpoint_lists.clone();
+        let elicitation_policy = self.config.mcp_elicitation_policy;
         tokio::spawn(async move {
             loop {
                 tokio::select! {
@@ -1154,7 +1268,7 @@ impl CodexMessageProcessor {
                         })
                         .await;
 
-                        apply_bespoke_event_handling(event.clone(), conversation_id, conversation.clone(), outgoing_for_task.clone(), pending_interrupts.clone()).await;
+                        apply_bespoke_event_handling(event.clone(), conversation_id, conversation.clone(), outgoing_for_task.clone(), pending_interrupts.clone(), pending_checkpoint_lists.clone(), elicitation_policy).await;
                     }
                 }
             }
@@ -1255,6 +1369,8 @@ async fn apply_bespoke_event_handling(
     conversation: Arc<CodexConversation>,
     outgoing: Arc<OutgoingMessageSender>,
     pending_interrupts: Arc<Mutex<HashMap<ConversationId, Vec<RequestId>>>>,
+    pending_checkpoint_lists: Arc<Mutex<HashMap<ConversationId, Vec<RequestId>>>>,
+    elicitation_policy: McpElicitationPolicy,
 ) {
     let Event { id: event_id, msg } = event;
     match msg {
@@ -1303,6 +1419,23 @@ async fn apply_bespoke_event_handling(
                 on_exec_approval_response(event_id, rx, conversation).await;
             });
         }
+        // App server clients have no way to answer MCP elicitations yet, so
+        // answer them as configured rather than leave the MCP server waiting.
+        EventMsg::ElicitationRequest(ElicitationRequestEvent {
+            server_name, id, ..
+        }) => {
+            if let Err(err) = conversation
+                .submit(Op::ResolveElicitation {
+                    server_name,
+                    request_id: id,
+                    decision: elicitation_policy.action(),
+                    content: None,
+                })
+                .await
+            {
+                error!("failed to answer elicitation: {err}");
+            }
+        }
         // If this is a TurnAborted, reply to any pending interrupt requests.
         EventMsg::TurnAborted(turn_aborted_event) => {
             let pending = {
@@ -1318,6 +1451,16 @@ async fn apply_bespoke_event_handling(
                 }
             }
         }
+        EventMsg::ListCheckpointsResponse(ListCheckpointsResponseEvent { checkpoints }) => {
+            let pending = {
+                let mut map = pending_checkpoint_lists.lock().await;
+                map.remove(&conversation_id).unwrap_or_default()
+            };
+            let response = ListCheckpointsResponse { checkpoints };
+            for rid in pending {
+                outgoing.send_response(rid, response.clone()).await;
+            }
+        }
 
         _ => {}
     }
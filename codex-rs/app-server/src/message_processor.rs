@@ -35,7 +35,12 @@ impl MessageProcessor {
         config: Arc<Config>,
     ) -> Self {
         let outgoing = Arc::new(outgoing);
-        let auth_manager = AuthManager::shared(config.codex_home.clone(), false);
+        let auth_manager = AuthManager::shared_with_profile(
+            config.codex_home.clone(),
+            config.auth_profile.clone(),
+            config.cli_auth_credentials_store_mode,
+            false,
+        );
         let conversation_manager = Arc::new(ConversationManager::new(
             auth_manager.clone(),
             SessionSource::VSCode,
@@ -1,6 +1,8 @@
 mod parser;
 mod seek_sequence;
 mod standalone_executable;
+mod three_way;
+mod transaction;
 
 use std::collections::HashMap;
 use std::path::Path;
@@ -8,7 +10,6 @@ use std::path::PathBuf;
 use std::str::Utf8Error;
 use std::sync::LazyLock;
 
-use anyhow::Context;
 use anyhow::Result;
 pub use parser::Hunk;
 pub use parser::ParseError;
@@ -25,12 +26,21 @@ use tree_sitter::StreamingIterator;
 use tree_sitter_bash::LANGUAGE as BASH;
 
 pub use standalone_executable::main;
+pub use transaction::IncompleteRollback;
 
 /// Detailed instructions for gpt-4.1 on how to use the `apply_patch` tool.
 pub const APPLY_PATCH_TOOL_INSTRUCTIONS: &str = include_str!("../apply_patch_tool_instructions.md");
 
 const APPLY_PATCH_COMMANDS: [&str; 2] = ["apply_patch", "applypatch"];
 
+const SUMMARY_HEADER: &str = "Success. Updated the following files:";
+const INCOMPLETE_ROLLBACK_HEADER: &str =
+    "Rollback incomplete. The following files were left modified:";
+
+/// Exit code of the `apply_patch` executable when a patch failed and rolling
+/// it back left files modified.
+pub const INCOMPLETE_ROLLBACK_EXIT_CODE: i32 = 3;
+
 #[derive(Debug, Error, PartialEq)]
 pub enum ApplyPatchError {
     #[error(transparent)]
@@ -45,6 +55,9 @@ pub enum ApplyPatchError {
         "patch detected without explicit call to apply_patch. Rerun as [\"apply_patch\", \"<patch>\"]"
     )]
     ImplicitInvocation,
+    /// The patch failed and rolling it back left some files modified.
+    #[error("{0}")]
+    IncompleteRollback(IncompleteRollback),
 }
 
 impl From<std::io::Error> for ApplyPatchError {
@@ -127,8 +140,17 @@ pub fn maybe_parse_apply_patch(argv: &[String]) -> MaybeApplyPatch {
 pub enum ApplyPatchFileChange {
     Add {
         content: String,
+        mode: Option<u32>,
+    },
+    AddBinary {
+        contents: Vec<u8>,
+        mode: Option<u32>,
+    },
+    AddSymlink {
+        target: PathBuf,
     },
     Delete {
+        /// Empty for binary files and symlinks.
         content: String,
     },
     Update {
@@ -136,6 +158,8 @@ pub enum ApplyPatchFileChange {
         move_path: Option<PathBuf>,
         /// new_content that will result after the unified_diff is applied.
         new_content: String,
+        /// Permission bits the file will have after the update, if changed.
+        mode: Option<u32>,
     },
 }
 
@@ -179,6 +203,27 @@ impl ApplyPatchAction {
         &self.changes
     }
 
+    /// Returns the paths applying the patch adds, modifies, or deletes. A
+    /// moved file is reported as modified at its destination.
+    pub fn affected_paths(&self) -> AffectedPaths {
+        let mut affected = AffectedPaths::default();
+        for (path, change) in &self.changes {
+            match change {
+                ApplyPatchFileChange::Add { .. }
+                | ApplyPatchFileChange::AddBinary { .. }
+                | ApplyPatchFileChange::AddSymlink { .. } => affected.added.push(path.clone()),
+                ApplyPatchFileChange::Delete { .. } => affected.deleted.push(path.clone()),
+                ApplyPatchFileChange::Update { move_path, .. } => affected
+                    .modified
+                    .push(move_path.as_ref().unwrap_or(path).clone()),
+            }
+        }
+        affected.added.sort();
+        affected.modified.sort();
+        affected.deleted.sort();
+        affected
+    }
+
     /// Should be used exclusively for testing. (Not worth the overhead of
     /// creating a feature flag for this.)
     pub fn new_add_for_test(path: &Path, content: String) -> Self {
@@ -198,7 +243,13 @@ impl ApplyPatchAction {
 + {content}
 *** End Patch"#,
         );
-        let changes = HashMap::from([(path.to_path_buf(), ApplyPatchFileChange::Add { content })]);
+        let changes = HashMap::from([(
+            path.to_path_buf(),
+            ApplyPatchFileChange::Add {
+                content,
+                mode: None,
+            },
+        )]);
         #[expect(clippy::expect_used)]
         Self {
             changes,
@@ -214,6 +265,19 @@ impl ApplyPatchAction {
 /// cwd must be an absolute path so that we can resolve relative paths in the
 /// patch.
 pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApplyPatchVerified {
+    maybe_parse_apply_patch_verified_with_base(argv, cwd, |_| None)
+}
+
+/// Like [`maybe_parse_apply_patch_verified`], but when an `Update File` hunk
+/// no longer matches the file on disk, `base_version` is asked for the
+/// contents of that file as the model last saw them. If the hunk applies to
+/// that version, the result is three-way merged with the current contents and
+/// the returned [`ApplyPatchAction::patch`] is rewritten to match.
+pub fn maybe_parse_apply_patch_verified_with_base(
+    argv: &[String],
+    cwd: &Path,
+    base_version: impl Fn(&Path) -> Option<String>,
+) -> MaybeApplyPatchVerified {
     // Detect a raw patch body passed directly as the command or as the body of a bash -lc
     // script. In these cases, report an explicit error rather than applying the patch.
     match argv {
@@ -252,14 +316,28 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                 })
                 .unwrap_or_else(|| cwd.to_path_buf());
             let mut changes = HashMap::new();
-            for hunk in hunks {
+            let mut resolved_hunks = hunks.clone();
+            let mut merged_with_base = false;
+            for (idx, hunk) in hunks.into_iter().enumerate() {
                 let path = hunk.resolve_path(&effective_cwd);
                 match hunk {
-                    Hunk::AddFile { contents, .. } => {
-                        changes.insert(path, ApplyPatchFileChange::Add { content: contents });
+                    Hunk::AddFile { contents, mode, .. } => {
+                        changes.insert(
+                            path,
+                            ApplyPatchFileChange::Add {
+                                content: contents,
+                                mode,
+                            },
+                        );
+                    }
+                    Hunk::AddBinaryFile { contents, mode, .. } => {
+                        changes.insert(path, ApplyPatchFileChange::AddBinary { contents, mode });
+                    }
+                    Hunk::AddSymlink { target, .. } => {
+                        changes.insert(path, ApplyPatchFileChange::AddSymlink { target });
                     }
                     Hunk::DeleteFile { .. } => {
-                        let content = match std::fs::read_to_string(&path) {
+                        let content = match read_text_for_display(&path) {
                             Ok(content) => content,
                             Err(e) => {
                                 return MaybeApplyPatchVerified::CorrectnessError(
@@ -273,12 +351,57 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                         changes.insert(path, ApplyPatchFileChange::Delete { content });
                     }
                     Hunk::UpdateFile {
-                        move_path, chunks, ..
+                        move_path,
+                        mode,
+                        chunks,
+                        ..
                     } => {
+                        let update = if chunks.is_empty() {
+                            // Mode-only update: the contents are left untouched.
+                            read_text_for_display(&path)
+                                .map(|content| ApplyPatchFileUpdate {
+                                    unified_diff: String::new(),
+                                    content,
+                                })
+                                .map_err(|source| {
+                                    ApplyPatchError::IoError(IoError {
+                                        context: format!("Failed to read {}", path.display()),
+                                        source,
+                                    })
+                                })
+                        } else {
+                            match unified_diff_from_chunks(&path, &chunks) {
+                                Err(ApplyPatchError::ComputeReplacements(message)) => {
+                                    match base_version(&path) {
+                                        Some(base) => {
+                                            match merge_update_with_base(&path, &base, &chunks) {
+                                                Some((update, merged_chunks)) => {
+                                                    if let Hunk::UpdateFile { chunks, .. } =
+                                                        &mut resolved_hunks[idx]
+                                                    {
+                                                        *chunks = merged_chunks;
+                                                    }
+                                                    merged_with_base = true;
+                                                    Ok(update)
+                                                }
+                                                None => Err(ApplyPatchError::ComputeReplacements(
+                                                    format!(
+                                                        "{message}\nA three-way merge against the version of {} you last read also failed; re-read the file before retrying.",
+                                                        path.display()
+                                                    ),
+                                                )),
+                                            }
+                                        }
+                                        None => Err(ApplyPatchError::ComputeReplacements(message)),
+                                    }
+                                }
+                                result => result,
+                            }
+                        };
                         let ApplyPatchFileUpdate {
                             unified_diff,
                             content: contents,
-                        } = match unified_diff_from_chunks(&path, &chunks) {
+                        } = match update {
                             Ok(diff) => diff,
                             Err(e) => {
                                 return MaybeApplyPatchVerified::CorrectnessError(e);
@@ -290,11 +413,17 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                                 unified_diff,
                                 move_path: move_path.map(|p| cwd.join(p)),
                                 new_content: contents,
+                                mode,
                             },
                         );
                     }
                 }
             }
+            let patch = if merged_with_base {
+                parser::format_patch(&resolved_hunks)
+            } else {
+                patch
+            };
             MaybeApplyPatchVerified::Body(ApplyPatchAction {
                 changes,
                 patch,
@@ -468,12 +597,13 @@ pub enum ExtractHeredocError {
     FailedToFindHeredocBody,
 }
 
-/// Applies the patch and prints the result to stdout/stderr.
+/// Applies the patch, prints the result to stdout/stderr, and returns the
+/// paths that were changed.
 pub fn apply_patch(
     patch: &str,
     stdout: &mut impl std::io::Write,
     stderr: &mut impl std::io::Write,
-) -> Result<(), ApplyPatchError> {
+) -> Result<AffectedPaths, ApplyPatchError> {
     let hunks = match parse_patch(patch) {
         Ok(source) => source.hunks,
         Err(e) => {
@@ -496,9 +626,7 @@ pub fn apply_patch(
         }
     };
 
-    apply_hunks(&hunks, stdout, stderr)?;
-
-    Ok(())
+    apply_hunks(&hunks, stdout, stderr)
 }
 
 /// Applies hunks and continues to update stdout/stderr
@@ -506,11 +634,11 @@ pub fn apply_hunks(
     hunks: &[Hunk],
     stdout: &mut impl std::io::Write,
     stderr: &mut impl std::io::Write,
-) -> Result<(), ApplyPatchError> {
+) -> Result<AffectedPaths, ApplyPatchError> {
     let _existing_paths: Vec<&Path> = hunks
         .iter()
         .filter_map(|hunk| match hunk {
-            Hunk::AddFile { .. } => {
+            Hunk::AddFile { .. } | Hunk::AddBinaryFile { .. } | Hunk::AddSymlink { .. } => {
                 // The file is being added, so it doesn't exist yet.
                 None
             }
@@ -537,12 +665,16 @@ pub fn apply_hunks(
     match apply_hunks_to_files(hunks) {
         Ok(affected) => {
             print_summary(&affected, stdout).map_err(ApplyPatchError::from)?;
-            Ok(())
+            Ok(affected)
         }
         Err(err) => {
             let msg = err.to_string();
             writeln!(stderr, "{msg}").map_err(ApplyPatchError::from)?;
-            if let Some(io) = err.downcast_ref::<std::io::Error>() {
+            if let Some(incomplete) = err.downcast_ref::<IncompleteRollback>() {
+                print_incomplete_rollback(&incomplete.left_modified, stdout)
+                    .map_err(ApplyPatchError::from)?;
+                Err(ApplyPatchError::IncompleteRollback(incomplete.clone()))
+            } else if let Some(io) = err.downcast_ref::<std::io::Error>() {
                 Err(ApplyPatchError::from(io))
             } else {
                 Err(ApplyPatchError::IoError(IoError {
@@ -554,77 +686,55 @@ pub fn apply_hunks(
     }
 }
 
-/// Applies each parsed patch hunk to the filesystem.
-/// Returns an error if any of the changes could not be applied.
 /// Tracks file paths affected by applying a patch.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct AffectedPaths {
     pub added: Vec<PathBuf>,
     pub modified: Vec<PathBuf>,
     pub deleted: Vec<PathBuf>,
 }
 
+impl AffectedPaths {
+    /// All affected paths: added, then modified, then deleted.
+    pub fn into_paths(self) -> Vec<PathBuf> {
+        let mut paths = self.added;
+        paths.extend(self.modified);
+        paths.extend(self.deleted);
+        paths
+    }
+}
+
 /// Apply the hunks to the filesystem, returning which files were added, modified, or deleted.
-/// Returns an error if the patch could not be applied.
+/// Returns an error if the patch could not be applied, in which case no files are changed.
 fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
-    if hunks.is_empty() {
-        anyhow::bail!("No files were modified.");
-    }
-
-    let mut added: Vec<PathBuf> = Vec::new();
-    let mut modified: Vec<PathBuf> = Vec::new();
-    let mut deleted: Vec<PathBuf> = Vec::new();
-    for hunk in hunks {
-        match hunk {
-            Hunk::AddFile { path, contents } => {
-                if let Some(parent) = path.parent()
-                    && !parent.as_os_str().is_empty()
-                {
-                    std::fs::create_dir_all(parent).with_context(|| {
-                        format!("Failed to create parent directories for {}", path.display())
-                    })?;
-                }
-                std::fs::write(path, contents)
-                    .with_context(|| format!("Failed to write file {}", path.display()))?;
-                added.push(path.clone());
-            }
-            Hunk::DeleteFile { path } => {
-                std::fs::remove_file(path)
-                    .with_context(|| format!("Failed to delete file {}", path.display()))?;
-                deleted.push(path.clone());
-            }
-            Hunk::UpdateFile {
-                path,
-                move_path,
-                chunks,
-            } => {
-                let AppliedPatch { new_contents, .. } =
-                    derive_new_contents_from_chunks(path, chunks)?;
-                if let Some(dest) = move_path {
-                    if let Some(parent) = dest.parent()
-                        && !parent.as_os_str().is_empty()
-                    {
-                        std::fs::create_dir_all(parent).with_context(|| {
-                            format!("Failed to create parent directories for {}", dest.display())
-                        })?;
-                    }
-                    std::fs::write(dest, new_contents)
-                        .with_context(|| format!("Failed to write file {}", dest.display()))?;
-                    std::fs::remove_file(path)
-                        .with_context(|| format!("Failed to remove original {}", path.display()))?;
-                    modified.push(dest.clone());
-                } else {
-                    std::fs::write(path, new_contents)
-                        .with_context(|| format!("Failed to write file {}", path.display()))?;
-                    modified.push(path.clone());
-                }
-            }
-        }
+    transaction::apply_hunks_transactionally(hunks)
+}
+
+#[cfg(unix)]
+fn create_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn create_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(target, link)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn create_symlink(_target: &Path, _link: &Path) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "symlinks are not supported on this platform",
+    ))
+}
+
+/// Reads `path` as text for display purposes. Symlinks and files that are not
+/// valid UTF-8 yield an empty string rather than an error.
+fn read_text_for_display(path: &Path) -> std::io::Result<String> {
+    if std::fs::symlink_metadata(path)?.file_type().is_symlink() {
+        return Ok(String::new());
     }
-    Ok(AffectedPaths {
-        added,
-        modified,
-        deleted,
-    })
+    Ok(String::from_utf8(std::fs::read(path)?).unwrap_or_default())
 }
 
 struct AppliedPatch {
@@ -648,25 +758,93 @@ fn derive_new_contents_from_chunks(
         }
     };
 
-    let mut original_lines: Vec<String> = original_contents.split('\n').map(String::from).collect();
+    let new_contents = apply_chunks_to_text(&original_contents, path, chunks)?;
+    Ok(AppliedPatch {
+        original_contents,
+        new_contents,
+    })
+}
 
-    // Drop the trailing empty element that results from the final newline so
-    // that line counts match the behaviour of standard `diff`.
-    if original_lines.last().is_some_and(String::is_empty) {
-        original_lines.pop();
-    }
+/// Apply `chunks` to `contents`, which is the text of the file at `path`.
+fn apply_chunks_to_text(
+    contents: &str,
+    path: &Path,
+    chunks: &[UpdateFileChunk],
+) -> std::result::Result<String, ApplyPatchError> {
+    apply_chunks_with_context(contents, path, chunks, ChangeContext::Required)
+}
 
-    let replacements = compute_replacements(&original_lines, path, chunks)?;
+fn apply_chunks_with_context(
+    contents: &str,
+    path: &Path,
+    chunks: &[UpdateFileChunk],
+    change_context: ChangeContext,
+) -> std::result::Result<String, ApplyPatchError> {
+    let original_lines = split_lines(contents);
+    let replacements = compute_replacements(&original_lines, path, chunks, change_context)?;
     let new_lines = apply_replacements(original_lines, &replacements);
     let mut new_lines = new_lines;
     if !new_lines.last().is_some_and(String::is_empty) {
         new_lines.push(String::new());
     }
-    let new_contents = new_lines.join("\n");
-    Ok(AppliedPatch {
-        original_contents,
-        new_contents,
-    })
+    Ok(new_lines.join("\n"))
+}
+
+fn split_lines(contents: &str) -> Vec<String> {
+    let mut lines: Vec<String> = contents.split('\n').map(String::from).collect();
+
+    // Drop the trailing empty element that results from the final newline so
+    // that line counts match the behaviour of standard `diff`.
+    if lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Three-way fallback for an update whose chunks no longer match the file on
+/// disk: apply the chunks to `base` (the version the patch was written
+/// against) and merge that result with the current contents. On success,
+/// returns the update together with a single whole-file chunk that turns the
+/// current contents into the merged result.
+fn merge_update_with_base(
+    path: &Path,
+    base: &str,
+    chunks: &[UpdateFileChunk],
+) -> Option<(ApplyPatchFileUpdate, Vec<UpdateFileChunk>)> {
+    let current = std::fs::read_to_string(path).ok()?;
+    let theirs = apply_chunks_with_context(base, path, chunks, ChangeContext::Hint).ok()?;
+    let mut merged = three_way::merge(base, &current, &theirs)?;
+    if !merged.ends_with('\n') {
+        merged.push('\n');
+    }
+    let unified_diff = TextDiff::from_lines(&current, &merged)
+        .unified_diff()
+        .context_radius(1)
+        .to_string();
+    let chunk = UpdateFileChunk {
+        change_context: None,
+        old_lines: split_lines(&current),
+        new_lines: split_lines(&merged),
+        is_end_of_file: true,
+    };
+    Some((
+        ApplyPatchFileUpdate {
+            unified_diff,
+            content: merged,
+        },
+        vec![chunk],
+    ))
+}
+
+/// How an `@@` context line that cannot be found in the file is treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChangeContext {
+    /// The chunk fails, so it can never land somewhere unrelated.
+    Required,
+    /// The line is only a hint and `old_lines` are searched for from the
+    /// current position. Used for the three-way merge, where the chunks are
+    /// applied to the version of the file the patch was written against.
+    Hint,
 }
 
 /// Compute a list of replacements needed to transform `original_lines` into the
@@ -676,11 +854,12 @@ fn compute_replacements(
     original_lines: &[String],
     path: &Path,
     chunks: &[UpdateFileChunk],
+    change_context: ChangeContext,
 ) -> std::result::Result<Vec<(usize, usize, Vec<String>)>, ApplyPatchError> {
     let mut replacements: Vec<(usize, usize, Vec<String>)> = Vec::new();
     let mut line_index: usize = 0;
 
-    for chunk in chunks {
+    for (chunk_idx, chunk) in chunks.iter().enumerate() {
         // If a chunk has a `change_context`, we use seek_sequence to find it, then
         // adjust our `line_index` to continue from there.
         if let Some(ctx_line) = &chunk.change_context {
@@ -691,13 +870,17 @@ fn compute_replacements(
                 false,
             ) {
                 line_index = idx + 1;
-            } else {
+            } else if change_context == ChangeContext::Required || chunk.old_lines.is_empty() {
                 return Err(ApplyPatchError::ComputeReplacements(format!(
-                    "Failed to find context '{}' in {}",
+                    "Failed to find context '{}' for chunk {} of {} in {}",
                     ctx_line,
+                    chunk_idx + 1,
+                    chunks.len(),
                     path.display()
                 )));
             }
+            // Otherwise the `@@` line is only a hint; fall through and look
+            // for `old_lines` from the current position.
         }
 
         if chunk.old_lines.is_empty() {
@@ -749,11 +932,9 @@ fn compute_replacements(
             replacements.push((start_idx, pattern.len(), new_slice.to_vec()));
             line_index = start_idx + pattern.len();
         } else {
-            return Err(ApplyPatchError::ComputeReplacements(format!(
-                "Failed to find expected lines in {}:\n{}",
-                path.display(),
-                chunk.old_lines.join("\n"),
-            )));
+            return Err(ApplyPatchError::ComputeReplacements(
+                describe_chunk_mismatch(original_lines, path, chunks, chunk_idx, pattern),
+            ));
         }
     }
 
@@ -762,6 +943,41 @@ fn compute_replacements(
     Ok(replacements)
 }
 
+/// Builds the error reported when a chunk's `old_lines` cannot be located: it
+/// names the failing chunk and, when there is one, quotes the region of the
+/// file that most resembles the expected lines so a retry can target it.
+fn describe_chunk_mismatch(
+    original_lines: &[String],
+    path: &Path,
+    chunks: &[UpdateFileChunk],
+    chunk_idx: usize,
+    pattern: &[String],
+) -> String {
+    let chunk = &chunks[chunk_idx];
+    let mut message = format!(
+        "Failed to find expected lines for chunk {} of {} in {}",
+        chunk_idx + 1,
+        chunks.len(),
+        path.display()
+    );
+    if let Some(ctx_line) = &chunk.change_context {
+        message.push_str(&format!(" (after '@@ {ctx_line}')"));
+    }
+    message.push_str(&format!(":\n{}", chunk.old_lines.join("\n")));
+    if let Some(start) = seek_sequence::closest_sequence(original_lines, pattern) {
+        let end = start + pattern.len();
+        message.push_str(&format!(
+            "\nClosest match in the file (lines {}-{}):",
+            start + 1,
+            end
+        ));
+        for (offset, line) in original_lines[start..end].iter().enumerate() {
+            message.push_str(&format!("\n{:>5} | {line}", start + offset + 1));
+        }
+    }
+    message
+}
+
 /// Apply the `(start_index, old_len, new_lines)` replacements to `original_lines`,
 /// returning the modified file contents as a vector of lines.
 fn apply_replacements(
@@ -821,13 +1037,51 @@ pub fn unified_diff_from_chunks_with_context(
     })
 }
 
+/// Lists the files a failed patch left modified because they could not be
+/// rolled back, in the same format as [`print_summary`].
+pub fn print_incomplete_rollback(
+    affected: &AffectedPaths,
+    out: &mut impl std::io::Write,
+) -> std::io::Result<()> {
+    writeln!(out, "{INCOMPLETE_ROLLBACK_HEADER}")?;
+    print_affected_paths(affected, out)
+}
+
+/// Reads back the list written by [`print_incomplete_rollback`] from the
+/// output of the `apply_patch` executable; `None` if it holds no such list.
+pub fn parse_incomplete_rollback(output: &str) -> Option<AffectedPaths> {
+    let mut lines = output.lines();
+    lines.find(|line| *line == INCOMPLETE_ROLLBACK_HEADER)?;
+    let mut affected = AffectedPaths::default();
+    for line in lines {
+        let Some((kind, path)) = line.split_once(' ') else {
+            break;
+        };
+        let paths = match kind {
+            "A" => &mut affected.added,
+            "M" => &mut affected.modified,
+            "D" => &mut affected.deleted,
+            _ => break,
+        };
+        paths.push(PathBuf::from(path));
+    }
+    Some(affected)
+}
+
 /// Print the summary of changes in git-style format.
 /// Write a summary of changes to the given writer.
 pub fn print_summary(
     affected: &AffectedPaths,
     out: &mut impl std::io::Write,
 ) -> std::io::Result<()> {
-    writeln!(out, "Success. Updated the following files:")?;
+    writeln!(out, "{SUMMARY_HEADER}")?;
+    print_affected_paths(affected, out)
+}
+
+fn print_affected_paths(
+    affected: &AffectedPaths,
+    out: &mut impl std::io::Write,
+) -> std::io::Result<()> {
     for path in &affected.added {
         writeln!(out, "A {}", path.display())?;
     }
@@ -879,6 +1133,7 @@ mod tests {
         vec![Hunk::AddFile {
             path: PathBuf::from("foo"),
             contents: "hi\n".to_string(),
+            mode: None,
         }]
     }
 
@@ -940,7 +1195,8 @@ mod tests {
                     hunks,
                     vec![Hunk::AddFile {
                         path: PathBuf::from("foo"),
-                        contents: "hi\n".to_string()
+                        contents: "hi\n".to_string(),
+                        mode: None
                     }]
                 );
             }
@@ -965,7 +1221,8 @@ mod tests {
                     hunks,
                     vec![Hunk::AddFile {
                         path: PathBuf::from("foo"),
-                        contents: "hi\n".to_string()
+                        contents: "hi\n".to_string(),
+                        mode: None
                     }]
                 );
             }
@@ -998,7 +1255,8 @@ PATCH"#,
                     hunks,
                     vec![Hunk::AddFile {
                         path: PathBuf::from("foo"),
-                        contents: "hi\n".to_string()
+                        contents: "hi\n".to_string(),
+                        mode: None
                     }]
                 );
             }
@@ -1176,6 +1434,83 @@ PATCH"#,
         assert_eq!(contents, "line2\n");
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_add_file_with_mode_and_mode_only_update() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let script = dir.path().join("run.sh");
+        let existing = dir.path().join("tool.py");
+        fs::write(&existing, "print('hi')").unwrap();
+        let patch = wrap_patch(&format!(
+            r#"*** Add File: {}
+*** Mode: 755
++echo hi
+*** Update File: {}
+*** Mode: 100755"#,
+            script.display(),
+            existing.display()
+        ));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+
+        let mode_of = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode_of(&script), 0o755);
+        assert_eq!(fs::read_to_string(&script).unwrap(), "echo hi\n");
+        assert_eq!(mode_of(&existing), 0o755);
+        // Mode-only updates must not touch the contents (no newline is added).
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "print('hi')");
+    }
+
+    #[test]
+    fn test_add_binary_file_decodes_base64() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("fixture.bin");
+        let patch = wrap_patch(&format!(
+            "*** Add Binary File: {}\n+AAEC\n+/w==",
+            path.display()
+        ));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), vec![0, 1, 2, 255]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_add_symlink_and_delete_it() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("target.txt"), "x").unwrap();
+        let link = dir.path().join("link");
+        let patch = wrap_patch(&format!(
+            "*** Add Symlink: {} -> target.txt",
+            link.display()
+        ));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+        assert_eq!(fs::read_link(&link).unwrap(), PathBuf::from("target.txt"));
+
+        let argv = vec![
+            "apply_patch".to_string(),
+            wrap_patch(&format!("*** Delete File: {}", link.display())),
+        ];
+        match maybe_parse_apply_patch_verified(&argv, dir.path()) {
+            MaybeApplyPatchVerified::Body(action) => assert_eq!(
+                action.changes().get(&link),
+                Some(&ApplyPatchFileChange::Delete {
+                    content: String::new()
+                })
+            ),
+            result => panic!("expected MaybeApplyPatchVerified::Body got {result:?}"),
+        }
+        apply_patch(&argv[1], &mut stdout, &mut stderr).unwrap();
+        assert!(fs::symlink_metadata(&link).is_err());
+        assert!(dir.path().join("target.txt").exists());
+    }
+
     /// Verify that a single `Update File` hunk with multiple change chunks can update different
     /// parts of a file and that the file is listed only once in the summary.
     #[test]
@@ -1595,6 +1930,7 @@ g
                         .to_string(),
                         move_path: None,
                         new_content: "updated session directory content\n".to_string(),
+                        mode: None,
                     },
                 )]),
                 patch: argv[1].clone(),
@@ -1603,6 +1939,179 @@ g
         );
     }
 
+    #[test]
+    fn test_stale_update_falls_back_to_three_way_merge() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("merge.txt");
+        let base = "a\nb\nc\nd\ne\nf\n";
+        // The first line changed on disk after the model last read the file.
+        fs::write(&path, "A\nb\nc\nd\ne\nf\n").unwrap();
+
+        let argv = vec![
+            "apply_patch".to_string(),
+            wrap_patch(&format!(
+                "*** Update File: {}\n@@\n a\n b\n-c\n+C\n@@\n e\n-f\n+f2",
+                path.display()
+            )),
+        ];
+        assert_matches!(
+            maybe_parse_apply_patch_verified(&argv, dir.path()),
+            MaybeApplyPatchVerified::CorrectnessError(ApplyPatchError::ComputeReplacements(_))
+        );
+
+        let action = match maybe_parse_apply_patch_verified_with_base(&argv, dir.path(), |p| {
+            (p == path).then(|| base.to_string())
+        }) {
+            MaybeApplyPatchVerified::Body(action) => action,
+            other => panic!("expected merged patch, got {other:?}"),
+        };
+        assert_matches!(
+            action.changes.get(&path),
+            Some(ApplyPatchFileChange::Update { new_content, .. })
+                if new_content == "A\nb\nC\nd\ne\nf2\n"
+        );
+
+        // The rewritten patch applies cleanly to the file on disk.
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&action.patch, &mut stdout, &mut stderr).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "A\nb\nC\nd\ne\nf2\n");
+    }
+
+    #[test]
+    fn test_missing_change_context_is_an_error() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("context.txt");
+        // `x = 1` appears in both functions; the `@@` line says which one.
+        fs::write(
+            &path,
+            "fn first() {\n    x = 1\n}\nfn second() {\n    x = 1\n}\n",
+        )
+        .unwrap();
+        let argv = vec![
+            "apply_patch".to_string(),
+            wrap_patch(&format!(
+                "*** Update File: {}\n@@ fn third() {{\n-    x = 1\n+    x = 2",
+                path.display()
+            )),
+        ];
+        let MaybeApplyPatchVerified::CorrectnessError(ApplyPatchError::ComputeReplacements(
+            message,
+        )) = maybe_parse_apply_patch_verified(&argv, dir.path())
+        else {
+            panic!("expected ComputeReplacements error");
+        };
+        assert_eq!(
+            message,
+            format!(
+                "Failed to find context 'fn third() {{' for chunk 1 of 1 in {}",
+                path.display()
+            )
+        );
+    }
+
+    #[test]
+    fn test_mismatch_error_names_chunk_and_closest_match() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("close.txt");
+        fs::write(&path, "fn main() {\n    println!(\"hello\");\n}\n").unwrap();
+        let argv = vec![
+            "apply_patch".to_string(),
+            "*** Begin Patch\n*** Update File: close.txt\n@@ fn main() {\n-    println!(\"helo\");\n+    println!(\"bye\");\n*** End Patch"
+                .to_string(),
+        ];
+        let MaybeApplyPatchVerified::CorrectnessError(ApplyPatchError::ComputeReplacements(
+            message,
+        )) = maybe_parse_apply_patch_verified(&argv, dir.path())
+        else {
+            panic!("expected ComputeReplacements error");
+        };
+        assert_eq!(
+            message,
+            format!(
+                "Failed to find expected lines for chunk 1 of 1 in {} (after '@@ fn main() {{'):\n    println!(\"helo\");\nClosest match in the file (lines 2-2):\n    2 |     println!(\"hello\");",
+                path.display()
+            )
+        );
+    }
+
+    #[test]
+    fn test_failed_patch_leaves_all_files_untouched() {
+        let dir = tempdir().unwrap();
+        let first = dir.path().join("first.txt");
+        let second = dir.path().join("second.txt");
+        fs::write(&first, "one\n").unwrap();
+        fs::write(&second, "two\n").unwrap();
+        let patch = wrap_patch(&format!(
+            "*** Update File: {}\n@@\n-one\n+uno\n*** Add File: {}\n+new\n*** Update File: {}\n@@\n-missing\n+dos",
+            first.display(),
+            dir.path().join("added.txt").display(),
+            second.display()
+        ));
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        assert_matches!(
+            apply_patch(&patch, &mut stdout, &mut stderr),
+            Err(ApplyPatchError::ComputeReplacements(_))
+        );
+        assert_eq!(fs::read_to_string(&first).unwrap(), "one\n");
+        assert_eq!(fs::read_to_string(&second).unwrap(), "two\n");
+        assert!(!dir.path().join("added.txt").exists());
+        assert!(stdout.is_empty());
+    }
+
+    #[test]
+    fn test_apply_patch_returns_affected_paths() {
+        let dir = tempdir().unwrap();
+        let modified = dir.path().join("modified.txt");
+        let deleted = dir.path().join("deleted.txt");
+        let added = dir.path().join("added.txt");
+        fs::write(&modified, "before\n").unwrap();
+        fs::write(&deleted, "gone\n").unwrap();
+        let patch = wrap_patch(&format!(
+            "*** Add File: {}\n+new\n*** Update File: {}\n@@\n-before\n+after\n*** Delete File: {}",
+            added.display(),
+            modified.display(),
+            deleted.display()
+        ));
+        let argv = vec!["apply_patch".to_string(), patch.clone()];
+        let MaybeApplyPatchVerified::Body(action) =
+            maybe_parse_apply_patch_verified(&argv, dir.path())
+        else {
+            panic!("expected a verified patch");
+        };
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let affected = apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+        assert_eq!(
+            affected,
+            AffectedPaths {
+                added: vec![added.clone()],
+                modified: vec![modified.clone()],
+                deleted: vec![deleted.clone()],
+            }
+        );
+        assert_eq!(action.affected_paths(), affected);
+        assert_eq!(affected.into_paths(), vec![added, modified, deleted]);
+    }
+
+    #[test]
+    fn test_incomplete_rollback_list_round_trips() {
+        let left_modified = AffectedPaths {
+            added: vec![PathBuf::from("new.txt")],
+            modified: vec![PathBuf::from("src/has space.rs")],
+            deleted: vec![],
+        };
+        let mut stdout = Vec::new();
+        print_incomplete_rollback(&left_modified, &mut stdout).unwrap();
+
+        let output = String::from_utf8(stdout).unwrap();
+        assert_eq!(parse_incomplete_rollback(&output), Some(left_modified));
+        assert_eq!(parse_incomplete_rollback("Success.\nA new.txt\n"), None);
+    }
+
     #[test]
     fn test_apply_patch_fails_on_write_error() {
         let dir = tempdir().unwrap();
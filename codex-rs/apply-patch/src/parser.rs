@@ -7,33 +7,44 @@
 //! begin_patch: "*** Begin Patch" LF
 //! end_patch: "*** End Patch" LF?
 //!
-//! hunk: add_hunk | delete_hunk | update_hunk
-//! add_hunk: "*** Add File: " filename LF add_line+
+//! hunk: add_hunk | add_binary_hunk | add_symlink_hunk | delete_hunk | update_hunk
+//! add_hunk: "*** Add File: " filename LF change_mode? add_line+
+//! add_binary_hunk: "*** Add Binary File: " filename LF change_mode? add_line+
+//! add_symlink_hunk: "*** Add Symlink: " filename " -> " filename LF
 //! delete_hunk: "*** Delete File: " filename LF
-//! update_hunk: "*** Update File: " filename LF change_move? change?
+//! update_hunk: "*** Update File: " filename LF change_move? change_mode? change?
 //! filename: /(.+)/
 //! add_line: "+" /(.+)/ LF -> line
 //!
 //! change_move: "*** Move to: " filename LF
+//! change_mode: "*** Mode: " /[0-7]+/ LF
 //! change: (change_context | change_line)+ eof_line?
 //! change_context: ("@@" | "@@ " /(.+)/) LF
 //! change_line: ("+" | "-" | " ") /(.+)/ LF
 //! eof_line: "*** End of File" LF
 //!
+//! The lines of an `add_binary_hunk` are concatenated and decoded as standard
+//! base64. An `update_hunk` may consist of only a `change_mode` line.
+//!
 //! The parser below is a little more lenient than the explicit spec and allows for
 //! leading/trailing whitespace around patch markers.
 use crate::ApplyPatchArgs;
 use std::path::Path;
 use std::path::PathBuf;
 
+use base64::Engine;
 use thiserror::Error;
 
 const BEGIN_PATCH_MARKER: &str = "*** Begin Patch";
 const END_PATCH_MARKER: &str = "*** End Patch";
 const ADD_FILE_MARKER: &str = "*** Add File: ";
+const ADD_BINARY_FILE_MARKER: &str = "*** Add Binary File: ";
+const ADD_SYMLINK_MARKER: &str = "*** Add Symlink: ";
+const SYMLINK_TARGET_SEPARATOR: &str = " -> ";
 const DELETE_FILE_MARKER: &str = "*** Delete File: ";
 const UPDATE_FILE_MARKER: &str = "*** Update File: ";
 const MOVE_TO_MARKER: &str = "*** Move to: ";
+const MODE_MARKER: &str = "*** Mode: ";
 const EOF_MARKER: &str = "*** End of File";
 const CHANGE_CONTEXT_MARKER: &str = "@@ ";
 const EMPTY_CHANGE_CONTEXT_MARKER: &str = "@@";
@@ -61,6 +72,19 @@ pub enum Hunk {
     AddFile {
         path: PathBuf,
         contents: String,
+        /// Permission bits (e.g. `0o755`) to apply to the new file.
+        mode: Option<u32>,
+    },
+    AddBinaryFile {
+        path: PathBuf,
+        contents: Vec<u8>,
+        mode: Option<u32>,
+    },
+    AddSymlink {
+        path: PathBuf,
+        /// Target of the link, stored verbatim (it is not resolved against
+        /// the patch's working directory).
+        target: PathBuf,
     },
     DeleteFile {
         path: PathBuf,
@@ -68,9 +92,12 @@ pub enum Hunk {
     UpdateFile {
         path: PathBuf,
         move_path: Option<PathBuf>,
+        /// Permission bits to apply to the updated file.
+        mode: Option<u32>,
 
         /// Chunks should be in order, i.e. the `change_context` of one chunk
-        /// should occur later in the file than the previous chunk.
+        /// should occur later in the file than the previous chunk. Empty when
+        /// the hunk only changes the file mode.
         chunks: Vec<UpdateFileChunk>,
     },
 }
@@ -79,6 +106,8 @@ impl Hunk {
     pub fn resolve_path(&self, cwd: &Path) -> PathBuf {
         match self {
             Hunk::AddFile { path, .. } => cwd.join(path),
+            Hunk::AddBinaryFile { path, .. } => cwd.join(path),
+            Hunk::AddSymlink { path, .. } => cwd.join(path),
             Hunk::DeleteFile { path } => cwd.join(path),
             Hunk::UpdateFile { path, .. } => cwd.join(path),
         }
@@ -247,9 +276,10 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
     let first_line = lines[0].trim();
     if let Some(path) = first_line.strip_prefix(ADD_FILE_MARKER) {
         // Add File
+        let mode = parse_mode_line(lines.get(1), line_number + 1)?;
+        let mut parsed_lines = 1 + usize::from(mode.is_some());
         let mut contents = String::new();
-        let mut parsed_lines = 1;
-        for add_line in &lines[1..] {
+        for add_line in &lines[parsed_lines..] {
             if let Some(line_to_add) = add_line.strip_prefix('+') {
                 contents.push_str(line_to_add);
                 contents.push('\n');
@@ -262,9 +292,54 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
             AddFile {
                 path: PathBuf::from(path),
                 contents,
+                mode,
             },
             parsed_lines,
         ));
+    } else if let Some(path) = first_line.strip_prefix(ADD_BINARY_FILE_MARKER) {
+        // Add Binary File
+        let mode = parse_mode_line(lines.get(1), line_number + 1)?;
+        let mut parsed_lines = 1 + usize::from(mode.is_some());
+        let mut encoded = String::new();
+        for add_line in &lines[parsed_lines..] {
+            if let Some(chunk) = add_line.strip_prefix('+') {
+                encoded.push_str(chunk.trim());
+                parsed_lines += 1;
+            } else {
+                break;
+            }
+        }
+        let contents = base64::engine::general_purpose::STANDARD
+            .decode(encoded.as_bytes())
+            .map_err(|err| InvalidHunkError {
+                message: format!("Invalid base64 contents for binary file '{path}': {err}"),
+                line_number,
+            })?;
+        return Ok((
+            AddBinaryFile {
+                path: PathBuf::from(path),
+                contents,
+                mode,
+            },
+            parsed_lines,
+        ));
+    } else if let Some(spec) = first_line.strip_prefix(ADD_SYMLINK_MARKER) {
+        // Add Symlink
+        let Some((path, target)) = spec.split_once(SYMLINK_TARGET_SEPARATOR) else {
+            return Err(InvalidHunkError {
+                message: format!(
+                    "Symlink hunk must have the form '*** Add Symlink: {{path}} -> {{target}}', got: '{first_line}'"
+                ),
+                line_number,
+            });
+        };
+        return Ok((
+            AddSymlink {
+                path: PathBuf::from(path),
+                target: PathBuf::from(target),
+            },
+            1,
+        ));
     } else if let Some(path) = first_line.strip_prefix(DELETE_FILE_MARKER) {
         // Delete File
         return Ok((
@@ -288,6 +363,13 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
             parsed_lines += 1;
         }
 
+        // Optional: mode line
+        let mode = parse_mode_line(remaining_lines.first(), line_number + parsed_lines)?;
+        if mode.is_some() {
+            remaining_lines = &remaining_lines[1..];
+            parsed_lines += 1;
+        }
+
         let mut chunks = Vec::new();
         // NOTE: we need to know to stop once we reach the next special marker header.
         while !remaining_lines.is_empty() {
@@ -312,7 +394,7 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
             remaining_lines = &remaining_lines[chunk_lines..]
         }
 
-        if chunks.is_empty() {
+        if chunks.is_empty() && mode.is_none() {
             return Err(InvalidHunkError {
                 message: format!("Update file hunk for path '{path}' is empty"),
                 line_number,
@@ -323,6 +405,7 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
             UpdateFile {
                 path: PathBuf::from(path),
                 move_path: move_path.map(PathBuf::from),
+                mode,
                 chunks,
             },
             parsed_lines,
@@ -331,12 +414,33 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
 
     Err(InvalidHunkError {
         message: format!(
-            "'{first_line}' is not a valid hunk header. Valid hunk headers: '*** Add File: {{path}}', '*** Delete File: {{path}}', '*** Update File: {{path}}'"
+            "'{first_line}' is not a valid hunk header. Valid hunk headers: '*** Add File: {{path}}', '*** Add Binary File: {{path}}', '*** Add Symlink: {{path}} -> {{target}}', '*** Delete File: {{path}}', '*** Update File: {{path}}'"
         ),
         line_number,
     })
 }
 
+/// Parses an optional `*** Mode: <octal>` line. Accepts either bare permission
+/// bits (`755`, `0644`) or a git-style regular file mode (`100755`), and
+/// returns only the permission bits.
+fn parse_mode_line(line: Option<&&str>, line_number: usize) -> Result<Option<u32>, ParseError> {
+    let Some(value) = line.and_then(|line| line.trim().strip_prefix(MODE_MARKER)) else {
+        return Ok(None);
+    };
+    let value = value.trim();
+    let invalid = || InvalidHunkError {
+        message: format!(
+            "Invalid file mode '{value}'. Expected octal permissions such as 644 or 755"
+        ),
+        line_number,
+    };
+    let mode = u32::from_str_radix(value, 8).map_err(|_| invalid())?;
+    match mode & !0o7777 {
+        0 | 0o100000 => Ok(Some(mode & 0o7777)),
+        _ => Err(invalid()),
+    }
+}
+
 fn parse_update_file_chunk(
     lines: &[&str],
     line_number: usize,
@@ -430,6 +534,78 @@ fn parse_update_file_chunk(
     Ok((chunk, parsed_lines + start_index))
 }
 
+/// Serializes `hunks` back into the patch format accepted by [`parse_patch`].
+/// Update chunks are written as removals followed by additions, which parses
+/// back into the same `old_lines`/`new_lines`.
+pub(crate) fn format_patch(hunks: &[Hunk]) -> String {
+    let mut out = vec![BEGIN_PATCH_MARKER.to_string()];
+    for hunk in hunks {
+        match hunk {
+            AddFile {
+                path,
+                contents,
+                mode,
+            } => {
+                out.push(format!("{ADD_FILE_MARKER}{}", path.display()));
+                push_mode_line(&mut out, *mode);
+                out.extend(contents.lines().map(|line| format!("+{line}")));
+            }
+            AddBinaryFile {
+                path,
+                contents,
+                mode,
+            } => {
+                out.push(format!("{ADD_BINARY_FILE_MARKER}{}", path.display()));
+                push_mode_line(&mut out, *mode);
+                let encoded = base64::engine::general_purpose::STANDARD.encode(contents);
+                out.extend(
+                    encoded
+                        .as_bytes()
+                        .chunks(76)
+                        .map(|chunk| format!("+{}", String::from_utf8_lossy(chunk))),
+                );
+            }
+            AddSymlink { path, target } => out.push(format!(
+                "{ADD_SYMLINK_MARKER}{}{SYMLINK_TARGET_SEPARATOR}{}",
+                path.display(),
+                target.display()
+            )),
+            DeleteFile { path } => out.push(format!("{DELETE_FILE_MARKER}{}", path.display())),
+            UpdateFile {
+                path,
+                move_path,
+                mode,
+                chunks,
+            } => {
+                out.push(format!("{UPDATE_FILE_MARKER}{}", path.display()));
+                if let Some(move_path) = move_path {
+                    out.push(format!("{MOVE_TO_MARKER}{}", move_path.display()));
+                }
+                push_mode_line(&mut out, *mode);
+                for chunk in chunks {
+                    match &chunk.change_context {
+                        Some(context) => out.push(format!("{CHANGE_CONTEXT_MARKER}{context}")),
+                        None => out.push(EMPTY_CHANGE_CONTEXT_MARKER.to_string()),
+                    }
+                    out.extend(chunk.old_lines.iter().map(|line| format!("-{line}")));
+                    out.extend(chunk.new_lines.iter().map(|line| format!("+{line}")));
+                    if chunk.is_end_of_file {
+                        out.push(EOF_MARKER.to_string());
+                    }
+                }
+            }
+        }
+    }
+    out.push(END_PATCH_MARKER.to_string());
+    out.join("\n")
+}
+
+fn push_mode_line(out: &mut Vec<String>, mode: Option<u32>) {
+    if let Some(mode) = mode {
+        out.push(format!("{MODE_MARKER}{mode:o}"));
+    }
+}
+
 #[test]
 fn test_parse_patch() {
     assert_eq!(
@@ -486,7 +662,8 @@ fn test_parse_patch() {
         vec![
             AddFile {
                 path: PathBuf::from("path/add.py"),
-                contents: "abc\ndef\n".to_string()
+                contents: "abc\ndef\n".to_string(),
+                mode: None
             },
             DeleteFile {
                 path: PathBuf::from("path/delete.py")
@@ -494,6 +671,7 @@ fn test_parse_patch() {
             UpdateFile {
                 path: PathBuf::from("path/update.py"),
                 move_path: Some(PathBuf::from("path/update2.py")),
+                mode: None,
                 chunks: vec![UpdateFileChunk {
                     change_context: Some("def f():".to_string()),
                     old_lines: vec!["    pass".to_string()],
@@ -521,6 +699,7 @@ fn test_parse_patch() {
             UpdateFile {
                 path: PathBuf::from("file.py"),
                 move_path: None,
+                mode: None,
                 chunks: vec![UpdateFileChunk {
                     change_context: None,
                     old_lines: vec![],
@@ -530,7 +709,8 @@ fn test_parse_patch() {
             },
             AddFile {
                 path: PathBuf::from("other.py"),
-                contents: "content\n".to_string()
+                contents: "content\n".to_string(),
+                mode: None
             }
         ]
     );
@@ -551,6 +731,7 @@ fn test_parse_patch() {
         vec![UpdateFile {
             path: PathBuf::from("file2.py"),
             move_path: None,
+            mode: None,
             chunks: vec![UpdateFileChunk {
                 change_context: None,
                 old_lines: vec!["import foo".to_string()],
@@ -571,6 +752,7 @@ fn test_parse_patch_lenient() {
     let expected_patch = vec![UpdateFile {
         path: PathBuf::from("file2.py"),
         move_path: None,
+        mode: None,
         chunks: vec![UpdateFileChunk {
             change_context: None,
             old_lines: vec!["import foo".to_string()],
@@ -653,13 +835,123 @@ fn test_parse_one_hunk() {
         parse_one_hunk(&["bad"], 234),
         Err(InvalidHunkError {
             message: "'bad' is not a valid hunk header. \
-            Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}'".to_string(),
+            Valid hunk headers: '*** Add File: {path}', '*** Add Binary File: {path}', \
+            '*** Add Symlink: {path} -> {target}', '*** Delete File: {path}', '*** Update File: {path}'".to_string(),
             line_number: 234
         })
     );
     // Other edge cases are already covered by tests above/below.
 }
 
+#[test]
+fn test_parse_binary_symlink_and_mode_hunks() {
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n\
+             *** Add File: run.sh\n\
+             *** Mode: 755\n\
+             +echo hi\n\
+             *** Add Binary File: fixture.bin\n\
+             +AAEC\n\
+             +/w==\n\
+             *** Add Symlink: latest -> releases/v1\n\
+             *** Update File: tool.py\n\
+             *** Mode: 100755\n\
+             *** End Patch",
+            ParseMode::Strict
+        )
+        .unwrap()
+        .hunks,
+        vec![
+            AddFile {
+                path: PathBuf::from("run.sh"),
+                contents: "echo hi\n".to_string(),
+                mode: Some(0o755),
+            },
+            AddBinaryFile {
+                path: PathBuf::from("fixture.bin"),
+                contents: vec![0, 1, 2, 255],
+                mode: None,
+            },
+            AddSymlink {
+                path: PathBuf::from("latest"),
+                target: PathBuf::from("releases/v1"),
+            },
+            UpdateFile {
+                path: PathBuf::from("tool.py"),
+                move_path: None,
+                mode: Some(0o755),
+                chunks: Vec::new(),
+            },
+        ]
+    );
+
+    assert_eq!(
+        parse_one_hunk(&["*** Update File: tool.py", "*** Mode: 40755"], 7),
+        Err(InvalidHunkError {
+            message: "Invalid file mode '40755'. Expected octal permissions such as 644 or 755"
+                .to_string(),
+            line_number: 8
+        })
+    );
+    assert_eq!(
+        parse_one_hunk(&["*** Add Binary File: x.bin", "+not base64!"], 3),
+        Err(InvalidHunkError {
+            message:
+                "Invalid base64 contents for binary file 'x.bin': Invalid symbol 32, offset 3."
+                    .to_string(),
+            line_number: 3
+        })
+    );
+    assert_eq!(
+        parse_one_hunk(&["*** Add Symlink: latest"], 5),
+        Err(InvalidHunkError {
+            message: "Symlink hunk must have the form '*** Add Symlink: {path} -> {target}', got: '*** Add Symlink: latest'"
+                .to_string(),
+            line_number: 5
+        })
+    );
+}
+
+#[test]
+fn test_format_patch_round_trips() {
+    let hunks = vec![
+        AddFile {
+            path: PathBuf::from("run.sh"),
+            contents: "echo hi\n".to_string(),
+            mode: Some(0o755),
+        },
+        AddBinaryFile {
+            path: PathBuf::from("fixture.bin"),
+            contents: vec![0, 1, 2, 255],
+            mode: None,
+        },
+        AddSymlink {
+            path: PathBuf::from("latest"),
+            target: PathBuf::from("releases/v1"),
+        },
+        DeleteFile {
+            path: PathBuf::from("old.txt"),
+        },
+        UpdateFile {
+            path: PathBuf::from("lib.py"),
+            move_path: Some(PathBuf::from("src/lib.py")),
+            mode: None,
+            chunks: vec![UpdateFileChunk {
+                change_context: Some("def f():".to_string()),
+                old_lines: vec!["    pass".to_string(), "".to_string()],
+                new_lines: vec!["    return 1".to_string(), "".to_string()],
+                is_end_of_file: true,
+            }],
+        },
+    ];
+    let patch = format_patch(&hunks);
+    assert_eq!(
+        parse_patch_text(&patch, ParseMode::Strict).unwrap().hunks,
+        hunks
+    );
+}
+
 #[test]
 fn test_update_file_chunk() {
     assert_eq!(
@@ -109,8 +109,46 @@ pub(crate) fn seek_sequence(
     None
 }
 
+/// Minimum average per-line similarity for [`closest_sequence`] to report a
+/// candidate region.
+const CLOSEST_MATCH_MIN_SIMILARITY: f32 = 0.6;
+
+/// Upper bound on line comparisons performed by [`closest_sequence`], so that
+/// reporting a failed patch stays cheap on very large files.
+const CLOSEST_MATCH_MAX_COMPARISONS: usize = 200_000;
+
+/// Find the window of `lines` that most resembles `pattern`, scoring each
+/// candidate by the average character-level similarity of its lines. Used to
+/// point at the likely target region when `seek_sequence` finds no match.
+pub(crate) fn closest_sequence(lines: &[String], pattern: &[String]) -> Option<usize> {
+    if pattern.is_empty()
+        || pattern.len() > lines.len()
+        || lines.len().saturating_mul(pattern.len()) > CLOSEST_MATCH_MAX_COMPARISONS
+    {
+        return None;
+    }
+
+    let mut best: Option<(usize, f32)> = None;
+    for i in 0..=lines.len() - pattern.len() {
+        let total: f32 = pattern
+            .iter()
+            .enumerate()
+            .map(|(p_idx, pat)| {
+                similar::TextDiff::from_chars(lines[i + p_idx].trim(), pat.trim()).ratio()
+            })
+            .sum();
+        let score = total / pattern.len() as f32;
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((i, score));
+        }
+    }
+    best.filter(|(_, score)| *score >= CLOSEST_MATCH_MIN_SIMILARITY)
+        .map(|(idx, _)| idx)
+}
+
 #[cfg(test)]
 mod tests {
+    use super::closest_sequence;
     use super::seek_sequence;
     use std::string::ToString;
 
@@ -148,4 +186,13 @@ mod tests {
         // Should not panic – must return None when pattern cannot possibly fit.
         assert_eq!(seek_sequence(&lines, &pattern, 0, false), None);
     }
+
+    #[test]
+    fn test_closest_sequence_prefers_most_similar_window() {
+        let lines = to_vec(&["fn a() {", "    one();", "}", "fn b() {", "    two();", "}"]);
+        let pattern = to_vec(&["fn b() {", "    tow();"]);
+        assert_eq!(closest_sequence(&lines, &pattern), Some(3));
+        let unrelated = to_vec(&["zzzzzzzz", "qqqqqqqq"]);
+        assert_eq!(closest_sequence(&lines, &unrelated), None);
+    }
 }
@@ -0,0 +1,148 @@
+//! Minimal line-based three-way merge used when a patch no longer applies to
+//! the current contents of a file but does apply to the version the patch was
+//! written against.
+
+use similar::Algorithm;
+use similar::DiffOp;
+use similar::capture_diff_slices;
+
+/// Replacement of `base[start..end]` with `lines`.
+#[derive(Debug, PartialEq)]
+struct Edit<'a> {
+    start: usize,
+    end: usize,
+    lines: &'a [&'a str],
+}
+
+/// Merges the changes from `base` to `ours` with the changes from `base` to
+/// `theirs`. Returns `None` when both sides touch the same (or adjacent)
+/// region of `base` in different ways.
+pub(crate) fn merge(base: &str, ours: &str, theirs: &str) -> Option<String> {
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let our_lines: Vec<&str> = ours.split_inclusive('\n').collect();
+    let their_lines: Vec<&str> = theirs.split_inclusive('\n').collect();
+
+    let our_edits = edits(&base_lines, &our_lines);
+    let their_edits = edits(&base_lines, &their_lines);
+
+    let mut merged = String::new();
+    let mut cursor = 0;
+    let mut ours_iter = our_edits.iter().peekable();
+    let mut theirs_iter = their_edits.iter().peekable();
+    loop {
+        let edit = match (ours_iter.peek(), theirs_iter.peek()) {
+            (None, None) => break,
+            (Some(_), None) => ours_iter.next(),
+            (None, Some(_)) => theirs_iter.next(),
+            (Some(ours), Some(theirs)) => {
+                if ours == theirs {
+                    // Both sides made the same change.
+                    theirs_iter.next();
+                    ours_iter.next()
+                } else if conflicts(ours, theirs) {
+                    return None;
+                } else if ours.start <= theirs.start {
+                    ours_iter.next()
+                } else {
+                    theirs_iter.next()
+                }
+            }
+        }?;
+        if edit.start < cursor {
+            return None;
+        }
+        base_lines[cursor..edit.start]
+            .iter()
+            .for_each(|line| merged.push_str(line));
+        edit.lines.iter().for_each(|line| merged.push_str(line));
+        cursor = edit.end;
+    }
+    base_lines[cursor..]
+        .iter()
+        .for_each(|line| merged.push_str(line));
+    Some(merged)
+}
+
+fn edits<'a>(base: &[&str], other: &'a [&'a str]) -> Vec<Edit<'a>> {
+    capture_diff_slices(Algorithm::Myers, base, other)
+        .into_iter()
+        .filter_map(|op| match op {
+            DiffOp::Equal { .. } => None,
+            DiffOp::Delete {
+                old_index, old_len, ..
+            } => Some(Edit {
+                start: old_index,
+                end: old_index + old_len,
+                lines: &[],
+            }),
+            DiffOp::Insert {
+                old_index,
+                new_index,
+                new_len,
+            } => Some(Edit {
+                start: old_index,
+                end: old_index,
+                lines: &other[new_index..new_index + new_len],
+            }),
+            DiffOp::Replace {
+                old_index,
+                old_len,
+                new_index,
+                new_len,
+            } => Some(Edit {
+                start: old_index,
+                end: old_index + old_len,
+                lines: &other[new_index..new_index + new_len],
+            }),
+        })
+        .collect()
+}
+
+/// Edits from different sides conflict when their base ranges overlap or
+/// touch, since the relative order of the results would be ambiguous.
+fn conflicts(a: &Edit<'_>, b: &Edit<'_>) -> bool {
+    a.start <= b.end && b.start <= a.end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::merge;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn merges_disjoint_changes() {
+        let base = "a\nb\nc\nd\ne\n";
+        let ours = "A\nb\nc\nd\ne\n";
+        let theirs = "a\nb\nc\nd\nE\n";
+        assert_eq!(
+            merge(base, ours, theirs),
+            Some("A\nb\nc\nd\nE\n".to_string())
+        );
+    }
+
+    #[test]
+    fn merges_identical_changes_once() {
+        let base = "a\nb\nc\n";
+        let ours = "a\nB\nc\n";
+        assert_eq!(merge(base, ours, ours), Some(ours.to_string()));
+    }
+
+    #[test]
+    fn reports_overlapping_changes_as_conflict() {
+        let base = "a\nb\nc\n";
+        let ours = "a\nB1\nc\n";
+        let theirs = "a\nB2\nc\n";
+        assert_eq!(merge(base, ours, theirs), None);
+    }
+
+    #[test]
+    fn keeps_insertions_on_both_sides() {
+        let base = "a\nb\nc\nd\ne\n";
+        let ours = "a\nnew\nb\nc\nd\ne\n";
+        let theirs = "a\nb\nc\nd\nmore\ne\n";
+        assert_eq!(
+            merge(base, ours, theirs),
+            Some("a\nnew\nb\nc\nd\nmore\ne\n".to_string())
+        );
+    }
+}
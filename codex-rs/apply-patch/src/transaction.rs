@@ -0,0 +1,551 @@
+//! Transactional application of parsed hunks.
+//!
+//! Every hunk is validated and its resulting file contents computed before
+//! anything on disk changes. The new contents are then staged next to their
+//! destinations and renamed into place, keeping a backup of each file they
+//! replace. If any step fails, the files already renamed into place are
+//! reverted from those backups so the patch either applies completely or not
+//! at all.
+
+use std::fs::Permissions;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use anyhow::Context;
+use anyhow::Result;
+
+use crate::AffectedPaths;
+use crate::Hunk;
+use crate::apply_chunks_to_text;
+use crate::create_symlink;
+
+/// Error attached to a failed transaction whose rollback could not restore
+/// every file; `left_modified` lists the paths that remain changed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncompleteRollback {
+    pub left_modified: AffectedPaths,
+}
+
+impl std::fmt::Display for IncompleteRollback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to roll back a partially applied patch")
+    }
+}
+
+impl std::error::Error for IncompleteRollback {}
+
+/// Desired final state of a path once the patch has been applied.
+enum Target {
+    File {
+        contents: Vec<u8>,
+        permissions: Option<Permissions>,
+    },
+    Symlink(PathBuf),
+    Absent,
+}
+
+/// How the path looks on disk before the patch, used to undo the commit.
+enum Undo {
+    /// The path was renamed aside to `backup`.
+    Restore { path: PathBuf, backup: PathBuf },
+    /// The path did not exist before; remove whatever was placed there.
+    Remove { path: PathBuf },
+}
+
+/// Apply `hunks` all-or-nothing, returning which files were added, modified,
+/// or deleted.
+pub(crate) fn apply_hunks_transactionally(hunks: &[Hunk]) -> Result<AffectedPaths> {
+    if hunks.is_empty() {
+        anyhow::bail!("No files were modified.");
+    }
+
+    let (targets, affected) = plan(hunks)?;
+    let mut created_dirs = Vec::new();
+    let staged = match stage(&targets, &mut created_dirs) {
+        Ok(staged) => staged,
+        Err(err) => {
+            remove_created_dirs(&created_dirs);
+            return Err(err);
+        }
+    };
+
+    let mut undo = Vec::new();
+    if let Err(err) = commit(&targets, &staged, &mut undo) {
+        let unrestored = rollback(undo);
+        for path in staged.iter().flatten() {
+            let _ = std::fs::remove_file(path);
+        }
+        remove_created_dirs(&created_dirs);
+        if unrestored.is_empty() {
+            return Err(err);
+        }
+        let left_modified = AffectedPaths {
+            added: filter_paths(&affected.added, &unrestored),
+            modified: filter_paths(&affected.modified, &unrestored),
+            deleted: filter_paths(&affected.deleted, &unrestored),
+        };
+        return Err(err.context(IncompleteRollback { left_modified }));
+    }
+
+    for entry in undo {
+        if let Undo::Restore { backup, .. } = entry {
+            let _ = std::fs::remove_file(backup);
+        }
+    }
+    Ok(affected)
+}
+
+/// Compute the final state of every path touched by `hunks` without writing
+/// anything. Later hunks observe the results of earlier ones.
+fn plan(hunks: &[Hunk]) -> Result<(Vec<(PathBuf, Target)>, AffectedPaths)> {
+    let mut targets: Vec<(PathBuf, Target)> = Vec::new();
+    let mut added = Vec::new();
+    let mut modified = Vec::new();
+    let mut deleted = Vec::new();
+
+    fn set(targets: &mut Vec<(PathBuf, Target)>, path: &Path, target: Target) {
+        match targets.iter_mut().find(|(existing, _)| existing == path) {
+            Some((_, existing)) => *existing = target,
+            None => targets.push((path.to_path_buf(), target)),
+        }
+    }
+
+    for hunk in hunks {
+        match hunk {
+            Hunk::AddFile {
+                path,
+                contents,
+                mode,
+            } => {
+                let permissions = resolve_permissions(*mode, path, &targets);
+                set(
+                    &mut targets,
+                    path,
+                    Target::File {
+                        contents: contents.clone().into_bytes(),
+                        permissions,
+                    },
+                );
+                added.push(path.clone());
+            }
+            Hunk::AddBinaryFile {
+                path,
+                contents,
+                mode,
+            } => {
+                let permissions = resolve_permissions(*mode, path, &targets);
+                set(
+                    &mut targets,
+                    path,
+                    Target::File {
+                        contents: contents.clone(),
+                        permissions,
+                    },
+                );
+                added.push(path.clone());
+            }
+            Hunk::AddSymlink { path, target } => {
+                set(&mut targets, path, Target::Symlink(target.clone()));
+                added.push(path.clone());
+            }
+            Hunk::DeleteFile { path } => {
+                if !exists(path, &targets) {
+                    return Err(std::io::Error::from(std::io::ErrorKind::NotFound))
+                        .with_context(|| format!("Failed to delete file {}", path.display()));
+                }
+                set(&mut targets, path, Target::Absent);
+                deleted.push(path.clone());
+            }
+            Hunk::UpdateFile {
+                path,
+                move_path,
+                mode,
+                chunks,
+            } => {
+                let original = read_for_update(path, &targets)?;
+                // A mode-only update keeps the contents byte-for-byte, which
+                // also makes it usable on binary files.
+                let contents = if chunks.is_empty() {
+                    original
+                } else {
+                    let text = String::from_utf8(original)
+                        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err));
+                    let text = text.with_context(|| {
+                        format!("Failed to read file to update {}", path.display())
+                    })?;
+                    apply_chunks_to_text(&text, path, chunks)?.into_bytes()
+                };
+                let permissions = resolve_permissions(*mode, path, &targets);
+                // Updating a symlink in place edits the file it points to.
+                let resolved;
+                let dest = match move_path {
+                    Some(move_path) => move_path,
+                    None if is_symlink(path, &targets) => {
+                        resolved = std::fs::canonicalize(path).with_context(|| {
+                            format!("Failed to read file to update {}", path.display())
+                        })?;
+                        &resolved
+                    }
+                    None => path,
+                };
+                ensure_writable(dest, &targets)?;
+                if move_path.is_some() {
+                    set(&mut targets, path, Target::Absent);
+                }
+                set(
+                    &mut targets,
+                    dest,
+                    Target::File {
+                        contents,
+                        permissions,
+                    },
+                );
+                modified.push(move_path.as_ref().unwrap_or(path).clone());
+            }
+        }
+    }
+
+    Ok((
+        targets,
+        AffectedPaths {
+            added,
+            modified,
+            deleted,
+        },
+    ))
+}
+
+/// Whether a file exists at `path` as seen by the hunk being planned: the
+/// result of an earlier hunk if there is one, otherwise what is on disk.
+fn exists(path: &Path, targets: &[(PathBuf, Target)]) -> bool {
+    match targets.iter().find(|(existing, _)| existing == path) {
+        Some((_, target)) => !matches!(target, Target::Absent),
+        None => std::fs::symlink_metadata(path).is_ok_and(|metadata| !metadata.is_dir()),
+    }
+}
+
+fn read_for_update(path: &Path, targets: &[(PathBuf, Target)]) -> Result<Vec<u8>> {
+    match targets.iter().find(|(existing, _)| existing == path) {
+        Some((_, Target::File { contents, .. })) => Ok(contents.clone()),
+        Some(_) => Err(std::io::Error::from(std::io::ErrorKind::NotFound))
+            .with_context(|| format!("Failed to read file to update {}", path.display())),
+        None => std::fs::read(path)
+            .with_context(|| format!("Failed to read file to update {}", path.display())),
+    }
+}
+
+fn is_symlink(path: &Path, targets: &[(PathBuf, Target)]) -> bool {
+    match targets.iter().find(|(existing, _)| existing == path) {
+        Some((_, target)) => matches!(target, Target::Symlink(_)),
+        None => std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink()),
+    }
+}
+
+/// Renaming over a file succeeds even when the file itself is read-only, so
+/// check explicitly to keep the semantics of writing to it in place.
+fn ensure_writable(path: &Path, targets: &[(PathBuf, Target)]) -> Result<()> {
+    if targets.iter().any(|(existing, _)| existing == path) {
+        return Ok(());
+    }
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_file() && metadata.permissions().readonly() => {
+            Err(std::io::Error::from(std::io::ErrorKind::PermissionDenied))
+                .with_context(|| format!("Failed to write file {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Permissions for the new contents of `path`: the explicit `mode` if the
+/// patch sets one, otherwise whatever the file currently has.
+fn resolve_permissions(
+    mode: Option<u32>,
+    path: &Path,
+    targets: &[(PathBuf, Target)],
+) -> Option<Permissions> {
+    if let Some(mode) = mode {
+        return permissions_from_mode(mode);
+    }
+    match targets.iter().find(|(existing, _)| existing == path) {
+        Some((_, Target::File { permissions, .. })) => permissions.clone(),
+        Some(_) => None,
+        None => std::fs::metadata(path)
+            .ok()
+            .filter(std::fs::Metadata::is_file)
+            .map(|metadata| metadata.permissions()),
+    }
+}
+
+#[cfg(unix)]
+fn permissions_from_mode(mode: u32) -> Option<Permissions> {
+    use std::os::unix::fs::PermissionsExt;
+
+    Some(Permissions::from_mode(mode))
+}
+
+/// Windows has no notion of Unix permission bits, so mode changes are a no-op.
+#[cfg(not(unix))]
+fn permissions_from_mode(_mode: u32) -> Option<Permissions> {
+    None
+}
+
+/// Write the new contents of every target to a temporary sibling. Returns the
+/// staged path for each target (`None` for deletions).
+fn stage(
+    targets: &[(PathBuf, Target)],
+    created_dirs: &mut Vec<PathBuf>,
+) -> Result<Vec<Option<PathBuf>>> {
+    let mut staged = Vec::with_capacity(targets.len());
+    for (path, target) in targets {
+        match stage_one(path, target, created_dirs) {
+            Ok(staged_path) => staged.push(staged_path),
+            Err(err) => {
+                for path in staged.iter().flatten() {
+                    let _ = std::fs::remove_file(path);
+                }
+                return Err(err);
+            }
+        }
+    }
+    Ok(staged)
+}
+
+fn stage_one(
+    path: &Path,
+    target: &Target,
+    created_dirs: &mut Vec<PathBuf>,
+) -> Result<Option<PathBuf>> {
+    let staged_path = sibling_path(path, "stage");
+    match target {
+        Target::Absent => return Ok(None),
+        Target::File {
+            contents,
+            permissions,
+        } => {
+            create_parent_dirs(path, created_dirs)?;
+            let result = write_new(&staged_path, contents, permissions.as_ref());
+            if result.is_err() {
+                let _ = std::fs::remove_file(&staged_path);
+            }
+            result.with_context(|| format!("Failed to write file {}", path.display()))?;
+        }
+        Target::Symlink(link_target) => {
+            create_parent_dirs(path, created_dirs)?;
+            create_symlink(link_target, &staged_path)
+                .with_context(|| format!("Failed to create symlink {}", path.display()))?;
+        }
+    }
+    Ok(Some(staged_path))
+}
+
+/// Move every staged file into place, recording how to undo each step.
+fn commit(
+    targets: &[(PathBuf, Target)],
+    staged: &[Option<PathBuf>],
+    undo: &mut Vec<Undo>,
+) -> Result<()> {
+    for ((path, target), staged_path) in targets.iter().zip(staged) {
+        if std::fs::symlink_metadata(path).is_ok() {
+            let backup = sibling_path(path, "backup");
+            std::fs::rename(path, &backup).with_context(|| match target {
+                Target::Absent => format!("Failed to delete file {}", path.display()),
+                _ => format!("Failed to replace file {}", path.display()),
+            })?;
+            undo.push(Undo::Restore {
+                path: path.clone(),
+                backup,
+            });
+        } else {
+            undo.push(Undo::Remove { path: path.clone() });
+        }
+        if let Some(staged_path) = staged_path {
+            std::fs::rename(staged_path, path)
+                .with_context(|| format!("Failed to write file {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Revert the committed steps in reverse order. Returns the paths that could
+/// not be restored.
+fn rollback(undo: Vec<Undo>) -> Vec<PathBuf> {
+    let mut unrestored = Vec::new();
+    for entry in undo.into_iter().rev() {
+        match entry {
+            Undo::Restore { path, backup } => {
+                if std::fs::rename(&backup, &path).is_err() {
+                    unrestored.push(path);
+                }
+            }
+            Undo::Remove { path } => {
+                if std::fs::symlink_metadata(&path).is_ok() && std::fs::remove_file(&path).is_err()
+                {
+                    unrestored.push(path);
+                }
+            }
+        }
+    }
+    unrestored
+}
+
+fn filter_paths(paths: &[PathBuf], keep: &[PathBuf]) -> Vec<PathBuf> {
+    paths
+        .iter()
+        .filter(|path| keep.contains(path))
+        .cloned()
+        .collect()
+}
+
+/// Create the missing ancestors of `path`, remembering which ones were
+/// created so they can be removed again on failure.
+fn create_parent_dirs(path: &Path, created_dirs: &mut Vec<PathBuf>) -> Result<()> {
+    let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) else {
+        return Ok(());
+    };
+    let mut missing: Vec<PathBuf> = parent
+        .ancestors()
+        .take_while(|dir| !dir.as_os_str().is_empty() && !dir.exists())
+        .map(Path::to_path_buf)
+        .collect();
+    std::fs::create_dir_all(parent)
+        .with_context(|| format!("Failed to create parent directories for {}", path.display()))?;
+    // `ancestors` yields the deepest directory first; record outermost first
+    // so removal in reverse order empties children before parents.
+    missing.reverse();
+    created_dirs.append(&mut missing);
+    Ok(())
+}
+
+fn remove_created_dirs(created_dirs: &[PathBuf]) {
+    for dir in created_dirs.iter().rev() {
+        let _ = std::fs::remove_dir(dir);
+    }
+}
+
+fn write_new(
+    path: &Path,
+    contents: &[u8],
+    permissions: Option<&Permissions>,
+) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    if let Some(permissions) = permissions {
+        std::fs::set_permissions(path, permissions.clone())?;
+    }
+    Ok(())
+}
+
+/// A hidden, unique path in the same directory as `path`, so that renames
+/// between the two stay on one filesystem.
+fn sibling_path(path: &Path, kind: &str) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(
+        ".{file_name}.{}.{n}.codex-{kind}",
+        std::process::id()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn failure_rolls_back_earlier_files() {
+        let dir = tempdir().unwrap();
+        let first = dir.path().join("first.txt");
+        let second = dir.path().join("nested/second.txt");
+        std::fs::write(&first, "one\n").unwrap();
+        let hunks = vec![
+            Hunk::AddFile {
+                path: second.clone(),
+                contents: "two\n".to_string(),
+                mode: None,
+            },
+            Hunk::DeleteFile {
+                path: first.clone(),
+            },
+            Hunk::DeleteFile {
+                path: dir.path().join("missing.txt"),
+            },
+        ];
+
+        let err = apply_hunks_transactionally(&hunks).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Failed to delete file {}",
+                dir.path().join("missing.txt").display()
+            )
+        );
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "one\n");
+        assert!(!second.exists());
+        assert!(!dir.path().join("nested").exists());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn commit_failure_restores_replaced_files() {
+        let dir = tempdir().unwrap();
+        let first = dir.path().join("first.txt");
+        let second = dir.path().join("second.txt");
+        std::fs::write(&first, "old\n").unwrap();
+        let targets = vec![
+            (
+                first.clone(),
+                Target::File {
+                    contents: b"new\n".to_vec(),
+                    permissions: None,
+                },
+            ),
+            (
+                second.clone(),
+                Target::File {
+                    contents: b"x\n".to_vec(),
+                    permissions: None,
+                },
+            ),
+        ];
+        let staged = stage(&targets, &mut Vec::new()).unwrap();
+        // Losing the second staged file makes the commit fail after the first
+        // file was already renamed into place.
+        std::fs::remove_file(staged[1].as_ref().unwrap()).unwrap();
+        let mut undo = Vec::new();
+        assert!(commit(&targets, &staged, &mut undo).is_err());
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "new\n");
+
+        assert_eq!(rollback(undo), Vec::<PathBuf>::new());
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "old\n");
+        assert!(!second.exists());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn later_hunks_see_earlier_results() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+        let hunks = crate::parse_patch(&format!(
+            "*** Begin Patch\n*** Add File: {0}\n+a\n*** Update File: {0}\n@@\n-a\n+b\n*** End Patch",
+            path.display()
+        ))
+        .unwrap()
+        .hunks;
+
+        apply_hunks_transactionally(&hunks).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "b\n");
+    }
+}
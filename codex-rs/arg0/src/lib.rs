@@ -60,7 +60,10 @@ where
                 let mut stdout = std::io::stdout();
                 let mut stderr = std::io::stderr();
                 match codex_apply_patch::apply_patch(&patch_arg, &mut stdout, &mut stderr) {
-                    Ok(()) => 0,
+                    Ok(_) => 0,
+                    Err(codex_apply_patch::ApplyPatchError::IncompleteRollback(_)) => {
+                        codex_apply_patch::INCOMPLETE_ROLLBACK_EXIT_CODE
+                    }
                     Err(_) => 1,
                 }
             }
@@ -1,5 +1,5 @@
 use codex_core::CodexAuth;
-use std::path::Path;
+use codex_core::config::Config;
 use std::sync::LazyLock;
 use std::sync::RwLock;
 
@@ -17,9 +17,13 @@ pub fn set_chatgpt_token_data(value: TokenData) {
     }
 }
 
-/// Initialize the ChatGPT token from auth.json file
-pub async fn init_chatgpt_token_from_auth(codex_home: &Path) -> std::io::Result<()> {
-    let auth = CodexAuth::from_codex_home(codex_home)?;
+/// Initialize the ChatGPT token from the stored credentials
+pub async fn init_chatgpt_token_from_auth(config: &Config) -> std::io::Result<()> {
+    let auth = CodexAuth::from_codex_home_for_profile(
+        &config.codex_home,
+        config.auth_profile.as_deref(),
+        config.cli_auth_credentials_store_mode,
+    )?;
     if let Some(auth) = auth {
         let token_data = auth.get_token_data().await?;
         set_chatgpt_token_data(token_data);
@@ -1,11 +1,18 @@
 use codex_app_server_protocol::AuthMode;
 use codex_common::CliConfigOverrides;
 use codex_core::CodexAuth;
+use codex_core::auth::AuthCredentialsStoreMode;
 use codex_core::auth::CLIENT_ID;
-use codex_core::auth::login_with_api_key;
-use codex_core::auth::logout;
+use codex_core::auth::DEFAULT_AUTH_PROFILE;
+use codex_core::auth::list_auth_profiles;
+use codex_core::auth::load_auth_dot_json;
+use codex_core::auth::login_with_api_key_for_profile;
+use codex_core::auth::logout_profile;
+use codex_core::auth::validate_auth_profile_name;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
+use codex_core::config_edit::CONFIG_KEY_AUTH;
+use codex_core::config_edit::persist_overrides_and_clear_if_none;
 use codex_login::ServerOptions;
 use codex_login::run_device_code_login;
 use codex_login::run_login_server;
@@ -13,8 +20,14 @@ use std::io::IsTerminal;
 use std::io::Read;
 use std::path::PathBuf;
 
-pub async fn login_with_chatgpt(codex_home: PathBuf) -> std::io::Result<()> {
-    let opts = ServerOptions::new(codex_home, CLIENT_ID.to_string());
+pub async fn login_with_chatgpt(
+    codex_home: PathBuf,
+    auth_profile: Option<String>,
+    cli_auth_credentials_store_mode: AuthCredentialsStoreMode,
+) -> std::io::Result<()> {
+    let mut opts = ServerOptions::new(codex_home, CLIENT_ID.to_string());
+    opts.auth_profile = auth_profile;
+    opts.cli_auth_credentials_store_mode = cli_auth_credentials_store_mode;
     let server = run_login_server(opts)?;
 
     eprintln!(
@@ -25,12 +38,22 @@ pub async fn login_with_chatgpt(codex_home: PathBuf) -> std::io::Result<()> {
     server.block_until_done().await
 }
 
-pub async fn run_login_with_chatgpt(cli_config_overrides: CliConfigOverrides) -> ! {
+pub async fn run_login_with_chatgpt(
+    cli_config_overrides: CliConfigOverrides,
+    auth_profile: Option<String>,
+) -> ! {
     let config = load_config_or_exit(cli_config_overrides).await;
+    let auth_profile = target_auth_profile_or_exit(auth_profile, &config);
 
-    match login_with_chatgpt(config.codex_home).await {
+    match login_with_chatgpt(
+        config.codex_home,
+        auth_profile.clone(),
+        config.cli_auth_credentials_store_mode,
+    )
+    .await
+    {
         Ok(_) => {
-            eprintln!("Successfully logged in");
+            print_login_success(auth_profile.as_deref(), config.auth_profile.as_deref());
             std::process::exit(0);
         }
         Err(e) => {
@@ -43,12 +66,19 @@ pub async fn run_login_with_chatgpt(cli_config_overrides: CliConfigOverrides) ->
 pub async fn run_login_with_api_key(
     cli_config_overrides: CliConfigOverrides,
     api_key: String,
+    auth_profile: Option<String>,
 ) -> ! {
     let config = load_config_or_exit(cli_config_overrides).await;
+    let auth_profile = target_auth_profile_or_exit(auth_profile, &config);
 
-    match login_with_api_key(&config.codex_home, &api_key) {
+    match login_with_api_key_for_profile(
+        &config.codex_home,
+        auth_profile.as_deref(),
+        config.cli_auth_credentials_store_mode,
+        &api_key,
+    ) {
         Ok(_) => {
-            eprintln!("Successfully logged in");
+            print_login_success(auth_profile.as_deref(), config.auth_profile.as_deref());
             std::process::exit(0);
         }
         Err(e) => {
@@ -90,18 +120,22 @@ pub async fn run_login_with_device_code(
     cli_config_overrides: CliConfigOverrides,
     issuer_base_url: Option<String>,
     client_id: Option<String>,
+    auth_profile: Option<String>,
 ) -> ! {
     let config = load_config_or_exit(cli_config_overrides).await;
+    let auth_profile = target_auth_profile_or_exit(auth_profile, &config);
     let mut opts = ServerOptions::new(
         config.codex_home,
         client_id.unwrap_or(CLIENT_ID.to_string()),
     );
+    opts.auth_profile = auth_profile.clone();
+    opts.cli_auth_credentials_store_mode = config.cli_auth_credentials_store_mode;
     if let Some(iss) = issuer_base_url {
         opts.issuer = iss;
     }
     match run_device_code_login(opts).await {
         Ok(()) => {
-            eprintln!("Successfully logged in");
+            print_login_success(auth_profile.as_deref(), config.auth_profile.as_deref());
             std::process::exit(0);
         }
         Err(e) => {
@@ -113,12 +147,23 @@ pub async fn run_login_with_device_code(
 
 pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
     let config = load_config_or_exit(cli_config_overrides).await;
+    let auth_profile = config.auth_profile.as_deref();
+    let profile_suffix = auth_profile
+        .map(|name| format!(" (auth profile `{name}`)"))
+        .unwrap_or_default();
 
-    match CodexAuth::from_codex_home(&config.codex_home) {
+    match CodexAuth::from_codex_home_for_profile(
+        &config.codex_home,
+        auth_profile,
+        config.cli_auth_credentials_store_mode,
+    ) {
         Ok(Some(auth)) => match auth.mode {
             AuthMode::ApiKey => match auth.get_token().await {
                 Ok(api_key) => {
-                    eprintln!("Logged in using an API key - {}", safe_format_key(&api_key));
+                    eprintln!(
+                        "Logged in using an API key - {}{profile_suffix}",
+                        safe_format_key(&api_key)
+                    );
                     std::process::exit(0);
                 }
                 Err(e) => {
@@ -127,7 +172,12 @@ pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
                 }
             },
             AuthMode::ChatGPT => {
-                eprintln!("Logged in using ChatGPT");
+                match auth.get_account_email() {
+                    Some(email) => {
+                        eprintln!("Logged in using ChatGPT as {email}{profile_suffix}")
+                    }
+                    None => eprintln!("Logged in using ChatGPT{profile_suffix}"),
+                }
                 std::process::exit(0);
             }
         },
@@ -142,10 +192,18 @@ pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
     }
 }
 
-pub async fn run_logout(cli_config_overrides: CliConfigOverrides) -> ! {
+pub async fn run_logout(
+    cli_config_overrides: CliConfigOverrides,
+    auth_profile: Option<String>,
+) -> ! {
     let config = load_config_or_exit(cli_config_overrides).await;
+    let auth_profile = target_auth_profile_or_exit(auth_profile, &config);
 
-    match logout(&config.codex_home) {
+    match logout_profile(
+        &config.codex_home,
+        auth_profile.as_deref(),
+        config.cli_auth_credentials_store_mode,
+    ) {
         Ok(true) => {
             eprintln!("Successfully logged out");
             std::process::exit(0);
@@ -161,6 +219,95 @@ pub async fn run_logout(cli_config_overrides: CliConfigOverrides) -> ! {
     }
 }
 
+/// Makes `name` the active auth profile by setting `auth` in config.toml.
+pub async fn run_login_switch(cli_config_overrides: CliConfigOverrides, name: String) -> ! {
+    let config = load_config_or_exit(cli_config_overrides).await;
+    let auth_profile = parse_auth_profile_or_exit(Some(name));
+    let name = auth_profile.as_deref().unwrap_or(DEFAULT_AUTH_PROFILE);
+
+    let stored = load_auth_dot_json(
+        &config.codex_home,
+        auth_profile.as_deref(),
+        config.cli_auth_credentials_store_mode,
+    );
+    if !matches!(stored, Ok(Some(_))) {
+        eprintln!(
+            "No credentials stored for auth profile `{name}`. Run `codex login --profile {name}` first."
+        );
+        std::process::exit(1);
+    }
+
+    if let Err(e) = persist_overrides_and_clear_if_none(
+        &config.codex_home,
+        None,
+        &[(&[CONFIG_KEY_AUTH], auth_profile.as_deref())],
+    )
+    .await
+    {
+        eprintln!("Error updating config.toml: {e}");
+        std::process::exit(1);
+    }
+
+    eprintln!("Switched to auth profile `{name}`");
+    std::process::exit(0);
+}
+
+/// Lists stored auth profiles, marking the active one.
+pub async fn run_login_list(cli_config_overrides: CliConfigOverrides) -> ! {
+    let config = load_config_or_exit(cli_config_overrides).await;
+    let active = config
+        .auth_profile
+        .as_deref()
+        .unwrap_or(DEFAULT_AUTH_PROFILE);
+
+    match list_auth_profiles(&config.codex_home) {
+        Ok(names) if names.is_empty() => {
+            eprintln!("No stored credentials. Run `codex login` to add some.");
+            std::process::exit(0);
+        }
+        Ok(names) => {
+            for name in names {
+                let marker = if name == active { "*" } else { " " };
+                println!("{marker} {name}");
+            }
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("Error listing auth profiles: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// The auth profile `codex login` and `codex logout` act on: `--profile` if
+/// given, otherwise the active one, so that both commands affect the
+/// credentials the session uses.
+fn target_auth_profile_or_exit(auth_profile: Option<String>, config: &Config) -> Option<String> {
+    match auth_profile {
+        Some(name) => parse_auth_profile_or_exit(Some(name)),
+        None => config.auth_profile.clone(),
+    }
+}
+
+/// Validates a `--profile` value; the default profile maps to `None`.
+fn parse_auth_profile_or_exit(auth_profile: Option<String>) -> Option<String> {
+    let name = auth_profile.filter(|name| name != DEFAULT_AUTH_PROFILE)?;
+    if let Err(e) = validate_auth_profile_name(&name) {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+    Some(name)
+}
+
+fn print_login_success(auth_profile: Option<&str>, active_profile: Option<&str>) {
+    match auth_profile {
+        Some(name) if Some(name) != active_profile => eprintln!(
+            "Successfully logged in to auth profile `{name}`. Run `codex login switch {name}` to use it."
+        ),
+        _ => eprintln!("Successfully logged in"),
+    }
+}
+
 async fn load_config_or_exit(cli_config_overrides: CliConfigOverrides) -> Config {
     let cli_overrides = match cli_config_overrides.parse_overrides() {
         Ok(v) => v,
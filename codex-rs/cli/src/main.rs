@@ -8,7 +8,9 @@ use codex_chatgpt::apply_command::run_apply_command;
 use codex_cli::LandlockCommand;
 use codex_cli::SeatbeltCommand;
 use codex_cli::login::read_api_key_from_stdin;
+use codex_cli::login::run_login_list;
 use codex_cli::login::run_login_status;
+use codex_cli::login::run_login_switch;
 use codex_cli::login::run_login_with_api_key;
 use codex_cli::login::run_login_with_chatgpt;
 use codex_cli::login::run_login_with_device_code;
@@ -25,8 +27,10 @@ use std::path::PathBuf;
 use supports_color::Stream;
 
 mod mcp_cmd;
+mod oss_cmd;
 
 use crate::mcp_cmd::McpCli;
+use crate::oss_cmd::OssCli;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 
@@ -79,6 +83,9 @@ enum Subcommand {
     /// [experimental] Run the app server.
     AppServer,
 
+    /// Manage models in the local Ollama instance used by `--oss`.
+    Oss(OssCli),
+
     /// Generate shell completion scripts.
     Completion(CompletionCommand),
 
@@ -172,6 +179,11 @@ struct LoginCommand {
     #[arg(long = "device-auth")]
     use_device_code: bool,
 
+    /// Store the credentials under this auth profile instead of the active
+    /// one; `default` selects `auth.json`.
+    #[arg(long = "profile", value_name = "NAME")]
+    auth_profile: Option<String>,
+
     /// EXPERIMENTAL: Use custom OAuth issuer base URL (advanced)
     /// Override the OAuth issuer base URL (advanced)
     #[arg(long = "experimental_issuer", value_name = "URL", hide = true)]
@@ -189,12 +201,26 @@ struct LoginCommand {
 enum LoginSubcommand {
     /// Show login status.
     Status,
+
+    /// List stored auth profiles.
+    List,
+
+    /// Use the credentials of another auth profile.
+    Switch {
+        /// Auth profile to switch to; `default` selects `auth.json`.
+        #[arg(value_name = "NAME")]
+        name: String,
+    },
 }
 
 #[derive(Debug, Parser)]
 struct LogoutCommand {
     #[clap(skip)]
     config_overrides: CliConfigOverrides,
+
+    /// Remove the credentials of this auth profile instead of the active one.
+    #[arg(long = "profile", value_name = "NAME")]
+    auth_profile: Option<String>,
 }
 
 #[derive(Debug, Parser)]
@@ -377,6 +403,10 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
         Some(Subcommand::AppServer) => {
             codex_app_server::run_main(codex_linux_sandbox_exe, root_config_overrides).await?;
         }
+        Some(Subcommand::Oss(mut oss_cli)) => {
+            prepend_config_flags(&mut oss_cli.config_overrides, root_config_overrides.clone());
+            oss_cli.run().await?;
+        }
         Some(Subcommand::Resume(ResumeCommand {
             session_id,
             last,
@@ -401,12 +431,19 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 Some(LoginSubcommand::Status) => {
                     run_login_status(login_cli.config_overrides).await;
                 }
+                Some(LoginSubcommand::List) => {
+                    run_login_list(login_cli.config_overrides).await;
+                }
+                Some(LoginSubcommand::Switch { name }) => {
+                    run_login_switch(login_cli.config_overrides, name).await;
+                }
                 None => {
                     if login_cli.use_device_code {
                         run_login_with_device_code(
                             login_cli.config_overrides,
                             login_cli.issuer_base_url,
                             login_cli.client_id,
+                            login_cli.auth_profile,
                         )
                         .await;
                     } else if login_cli.api_key.is_some() {
@@ -416,9 +453,15 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                         std::process::exit(1);
                     } else if login_cli.with_api_key {
                         let api_key = read_api_key_from_stdin();
-                        run_login_with_api_key(login_cli.config_overrides, api_key).await;
+                        run_login_with_api_key(
+                            login_cli.config_overrides,
+                            api_key,
+                            login_cli.auth_profile,
+                        )
+                        .await;
                     } else {
-                        run_login_with_chatgpt(login_cli.config_overrides).await;
+                        run_login_with_chatgpt(login_cli.config_overrides, login_cli.auth_profile)
+                            .await;
                     }
                 }
             }
@@ -428,7 +471,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 &mut logout_cli.config_overrides,
                 root_config_overrides.clone(),
             );
-            run_logout(logout_cli.config_overrides).await;
+            run_logout(logout_cli.config_overrides, logout_cli.auth_profile).await;
         }
         Some(Subcommand::Completion(completion_cli)) => {
             print_completion(completion_cli);
@@ -16,6 +16,7 @@ use codex_core::config_types::McpServerConfig;
 use codex_core::config_types::McpServerTransportConfig;
 use codex_core::features::Feature;
 use codex_core::mcp::auth::compute_auth_statuses;
+use codex_core::mcp::health::check_server_health;
 use codex_core::protocol::McpAuthStatus;
 use codex_rmcp_client::delete_oauth_tokens;
 use codex_rmcp_client::perform_oauth_login;
@@ -66,6 +67,10 @@ pub struct ListArgs {
     /// Output the configured servers as JSON.
     #[arg(long)]
     pub json: bool,
+
+    /// Start each enabled server and report whether it is healthy.
+    #[arg(long)]
+    pub health: bool,
 }
 
 #[derive(Debug, clap::Parser)]
@@ -253,6 +258,8 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
         enabled: true,
         startup_timeout_sec: None,
         tool_timeout_sec: None,
+        tool_policy: Default::default(),
+        sampling_token_budget: None,
     };
 
     servers.insert(name.clone(), new_entry);
@@ -393,6 +400,20 @@ async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) ->
         config.mcp_oauth_credentials_store_mode,
     )
     .await;
+    let health = if list_args.health {
+        Some(check_server_health(&config).await)
+    } else {
+        None
+    };
+    let status_display = |name: &str, cfg: &McpServerConfig| {
+        if !cfg.enabled {
+            return "disabled".to_string();
+        }
+        match health.as_ref().and_then(|health| health.get(name)) {
+            Some(status) => status.health.to_string(),
+            None => "enabled".to_string(),
+        }
+    };
 
     if list_args.json {
         let json_entries: Vec<_> = entries
@@ -433,7 +454,7 @@ async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) ->
                     }
                 };
 
-                serde_json::json!({
+                let mut entry = serde_json::json!({
                     "name": name,
                     "enabled": cfg.enabled,
                     "transport": transport,
@@ -444,7 +465,11 @@ async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) ->
                         .tool_timeout_sec
                         .map(|timeout| timeout.as_secs_f64()),
                     "auth_status": auth_status,
-                })
+                });
+                if let Some(health) = &health {
+                    entry["health"] = serde_json::json!(health.get(name.as_str()));
+                }
+                entry
             })
             .collect();
         let output = serde_json::to_string_pretty(&json_entries)?;
@@ -480,11 +505,7 @@ async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) ->
                     .map(|path| path.display().to_string())
                     .filter(|value| !value.is_empty())
                     .unwrap_or_else(|| "-".to_string());
-                let status = if cfg.enabled {
-                    "enabled".to_string()
-                } else {
-                    "disabled".to_string()
-                };
+                let status = status_display(name, cfg);
                 let auth_status = auth_statuses
                     .get(name.as_str())
                     .copied()
@@ -505,11 +526,7 @@ async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) ->
                 bearer_token_env_var,
                 ..
             } => {
-                let status = if cfg.enabled {
-                    "enabled".to_string()
-                } else {
-                    "disabled".to_string()
-                };
+                let status = status_display(name, cfg);
                 let auth_status = auth_statuses
                     .get(name.as_str())
                     .copied()
@@ -630,6 +647,20 @@ async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) ->
         }
     }
 
+    if let Some(health) = &health {
+        let mut errors: Vec<_> = health
+            .iter()
+            .filter_map(|(name, status)| status.error.as_ref().map(|error| (name, error)))
+            .collect();
+        if !errors.is_empty() {
+            errors.sort();
+            println!();
+            for (name, error) in errors {
+                println!("{name}: {error}");
+            }
+        }
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,217 @@
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_ollama::CliProgressReporter;
+use codex_ollama::OllamaClient;
+use codex_ollama::format_model_size;
+
+/// Manage models in the local Ollama instance used by `--oss`.
+///
+/// Subcommands:
+/// - `list` — list downloaded models (with `--json`)
+/// - `pull` — download a model
+/// - `rm`   — delete a downloaded model
+/// - `show` — show details for a model, including its context length
+#[derive(Debug, clap::Parser)]
+pub struct OssCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: OssSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum OssSubcommand {
+    /// List models downloaded to the local Ollama instance.
+    #[clap(visible_alias = "ls")]
+    List(ListArgs),
+
+    /// Download a model into the local Ollama instance.
+    Pull(ModelArgs),
+
+    /// Delete a downloaded model.
+    #[clap(visible_alias = "remove")]
+    Rm(ModelArgs),
+
+    /// Show details for a downloaded model.
+    Show(ShowArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ListArgs {
+    /// Output the models as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ModelArgs {
+    /// Model name, e.g. `gpt-oss:20b`.
+    pub model: String,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ShowArgs {
+    /// Model name, e.g. `gpt-oss:20b`.
+    pub model: String,
+
+    /// Output the details as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl OssCli {
+    pub async fn run(self) -> Result<()> {
+        let OssCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        let overrides = config_overrides.parse_overrides().map_err(|e| anyhow!(e))?;
+        let config = Config::load_with_cli_overrides(overrides, ConfigOverrides::default())
+            .await
+            .context("failed to load configuration")?;
+        let client = OllamaClient::try_from_oss_provider(&config).await?;
+
+        match subcommand {
+            OssSubcommand::List(args) => run_list(&client, args).await?,
+            OssSubcommand::Pull(ModelArgs { model }) => {
+                let mut reporter = CliProgressReporter::new();
+                client.pull_with_reporter(&model, &mut reporter).await?;
+                println!("Pulled {model}.");
+            }
+            OssSubcommand::Rm(ModelArgs { model }) => {
+                client.delete_model(&model).await?;
+                println!("Deleted {model}.");
+            }
+            OssSubcommand::Show(args) => run_show(&client, args).await?,
+        }
+
+        Ok(())
+    }
+}
+
+async fn run_list(client: &OllamaClient, list_args: ListArgs) -> Result<()> {
+    let mut models = client.list_models().await?;
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if list_args.json {
+        let json_entries: Vec<_> = models
+            .iter()
+            .map(|model| {
+                serde_json::json!({
+                    "name": model.name,
+                    "size": model.size,
+                    "modified_at": model.modified_at,
+                    "parameter_size": model.parameter_size,
+                    "quantization_level": model.quantization_level,
+                })
+            })
+            .collect();
+        let output = serde_json::to_string_pretty(&json_entries)?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    if models.is_empty() {
+        println!("No models downloaded yet. Try `codex oss pull <MODEL>`.");
+        return Ok(());
+    }
+
+    let rows: Vec<[String; 4]> = models
+        .iter()
+        .map(|model| {
+            [
+                model.name.clone(),
+                model
+                    .size
+                    .map(format_model_size)
+                    .unwrap_or_else(|| "-".to_string()),
+                model
+                    .parameter_size
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+                model
+                    .quantization_level
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+    let mut widths = [
+        "Name".len(),
+        "Size".len(),
+        "Parameters".len(),
+        "Quantization".len(),
+    ];
+    for row in &rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.len());
+        }
+    }
+
+    println!(
+        "{name:<name_w$}  {size:<size_w$}  {params:<params_w$}  {quant:<quant_w$}",
+        name = "Name",
+        size = "Size",
+        params = "Parameters",
+        quant = "Quantization",
+        name_w = widths[0],
+        size_w = widths[1],
+        params_w = widths[2],
+        quant_w = widths[3],
+    );
+    for row in &rows {
+        println!(
+            "{name:<name_w$}  {size:<size_w$}  {params:<params_w$}  {quant:<quant_w$}",
+            name = row[0],
+            size = row[1],
+            params = row[2],
+            quant = row[3],
+            name_w = widths[0],
+            size_w = widths[1],
+            params_w = widths[2],
+            quant_w = widths[3],
+        );
+    }
+
+    Ok(())
+}
+
+async fn run_show(client: &OllamaClient, show_args: ShowArgs) -> Result<()> {
+    let details = client.show_model(&show_args.model).await?;
+
+    if show_args.json {
+        let output = serde_json::to_string_pretty(&serde_json::json!({
+            "name": details.name,
+            "family": details.family,
+            "parameter_size": details.parameter_size,
+            "quantization_level": details.quantization_level,
+            "context_length": details.context_length,
+            "capabilities": details.capabilities,
+        }))?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+    println!("{}", details.name);
+    println!("  family: {}", or_dash(details.family));
+    println!("  parameters: {}", or_dash(details.parameter_size));
+    println!("  quantization: {}", or_dash(details.quantization_level));
+    println!(
+        "  context length: {}",
+        or_dash(details.context_length.map(|len| len.to_string()))
+    );
+    if details.capabilities.is_empty() {
+        println!("  capabilities: -");
+    } else {
+        println!("  capabilities: {}", details.capabilities.join(", "));
+    }
+
+    Ok(())
+}
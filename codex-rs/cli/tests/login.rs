@@ -0,0 +1,63 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+#[test]
+fn login_profiles_are_stored_and_switched() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    codex_command(codex_home.path())?
+        .args(["login", "--with-api-key"])
+        .write_stdin("sk-personal-0123456789")
+        .assert()
+        .success();
+    codex_command(codex_home.path())?
+        .args(["login", "--with-api-key", "--profile", "work"])
+        .write_stdin("sk-work-0123456789")
+        .assert()
+        .success()
+        .stderr(contains("Run `codex login switch work` to use it."));
+    assert!(codex_home.path().join("auth.work.json").exists());
+
+    codex_command(codex_home.path())?
+        .args(["login", "list"])
+        .assert()
+        .success()
+        .stdout("* default\n  work\n");
+
+    codex_command(codex_home.path())?
+        .args(["login", "switch", "work"])
+        .assert()
+        .success();
+    let config = std::fs::read_to_string(codex_home.path().join("config.toml"))?;
+    assert!(config.contains("auth = \"work\""), "{config}");
+
+    codex_command(codex_home.path())?
+        .args(["login", "status"])
+        .assert()
+        .success()
+        .stderr(contains("sk-work-***56789 (auth profile `work`)"));
+
+    codex_command(codex_home.path())?
+        .args(["login", "switch", "missing"])
+        .assert()
+        .failure()
+        .stderr(contains("No credentials stored for auth profile `missing`"));
+
+    codex_command(codex_home.path())?
+        .args(["login", "switch", "default"])
+        .assert()
+        .success();
+    let config = std::fs::read_to_string(codex_home.path().join("config.toml"))?;
+    assert!(!config.contains("auth ="), "{config}");
+
+    Ok(())
+}
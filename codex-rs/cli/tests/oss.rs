@@ -0,0 +1,107 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+use tempfile::TempDir;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::body_json;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+fn codex_command(codex_home: &Path, ollama: &MockServer) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home);
+    cmd.env("CODEX_OSS_BASE_URL", format!("{}/v1", ollama.uri()));
+    Ok(cmd)
+}
+
+async fn start_ollama() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/tags"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "models": [{
+                "name": "gpt-oss:20b",
+                "size": 13780173839u64,
+                "details": {"parameter_size": "20.9B", "quantization_level": "MXFP4"}
+            }]
+        })))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn list_renders_local_models() -> Result<()> {
+    if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
+        return Ok(());
+    }
+
+    let codex_home = TempDir::new()?;
+    let ollama = start_ollama().await;
+
+    let mut list = codex_command(codex_home.path(), &ollama)?;
+    list.args(["oss", "list"])
+        .assert()
+        .success()
+        .stdout(contains("gpt-oss:20b  14 GB  20.9B       MXFP4"));
+
+    let mut list_json = codex_command(codex_home.path(), &ollama)?;
+    let output = list_json.args(["oss", "list", "--json"]).output()?;
+    assert!(output.status.success());
+    let parsed: JsonValue = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        parsed,
+        json!([{
+            "name": "gpt-oss:20b",
+            "size": 13780173839u64,
+            "modified_at": null,
+            "parameter_size": "20.9B",
+            "quantization_level": "MXFP4",
+        }])
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn rm_deletes_model_and_reports_missing_ones() -> Result<()> {
+    if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
+        return Ok(());
+    }
+
+    let codex_home = TempDir::new()?;
+    let ollama = start_ollama().await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/delete"))
+        .and(body_json(json!({"model": "gpt-oss:20b"})))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&ollama)
+        .await;
+
+    let mut rm = codex_command(codex_home.path(), &ollama)?;
+    rm.args(["oss", "rm", "gpt-oss:20b"])
+        .assert()
+        .success()
+        .stdout(contains("Deleted gpt-oss:20b."));
+
+    let mut rm_missing = codex_command(codex_home.path(), &ollama)?;
+    rm_missing
+        .args(["oss", "rm", "mistral"])
+        .assert()
+        .failure()
+        .stderr(contains("model 'mistral' not found"));
+
+    Ok(())
+}
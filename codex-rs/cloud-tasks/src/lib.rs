@@ -56,11 +56,7 @@ async fn init_backend(user_agent_suffix: &str) -> anyhow::Result<BackendContext>
     };
     append_error_log(format!("startup: base_url={base_url} path_style={style}"));
 
-    let auth = match codex_core::config::find_codex_home()
-        .ok()
-        .map(|home| codex_login::AuthManager::new(home, false))
-        .and_then(|am| am.auth())
-    {
+    let auth = match util::load_auth_manager().await.and_then(|am| am.auth()) {
         Some(auth) => auth,
         None => {
             eprintln!(
@@ -12,7 +12,7 @@ pub fn create_config_summary_entries(config: &Config) -> Vec<(&'static str, Stri
         ("approval", config.approval_policy.to_string()),
         ("sandbox", summarize_sandbox_policy(&config.sandbox_policy)),
     ];
-    if config.model_provider.wire_api == WireApi::Responses
+    if config.model_provider.wire_api != WireApi::Chat
         && config.model_family.supports_reasoning_summaries
     {
         entries.push((
@@ -22,10 +22,14 @@ pub fn create_config_summary_entries(config: &Config) -> Vec<(&'static str, Stri
                 .map(|effort| effort.to_string())
                 .unwrap_or_else(|| "none".to_string()),
         ));
-        entries.push((
-            "reasoning summaries",
-            config.model_reasoning_summary.to_string(),
-        ));
+        // Native wire APIs map the effort to a thinking budget but have no
+        // summary setting.
+        if config.model_provider.wire_api == WireApi::Responses {
+            entries.push((
+                "reasoning summaries",
+                config.model_reasoning_summary.to_string(),
+            ));
+        }
     }
 
     entries
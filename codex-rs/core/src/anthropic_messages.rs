@@ -0,0 +1,573 @@
+//! Streaming adapter for Anthropic's Messages API (`wire_api = "anthropic"`).
+//!
+//! History items map onto content blocks: assistant text, `thinking` /
+//! `redacted_thinking` (kept verbatim with their signatures so extended
+//! thinking survives tool use), `tool_use` and `tool_result`. Consecutive
+//! items with the same role share one message, which is how parallel tool
+//! calls and their results are expressed. The system prompt and the newest
+//! message carry `cache_control` breakpoints so the prefix is cached.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::time::Duration;
+
+use codex_otel::otel_event_manager::OtelEventManager;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde_json::Value;
+use serde_json::json;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use tracing::trace;
+
+use crate::client_common::ResponseEvent;
+use crate::client_common::ResponseStream;
+use crate::error::CodexErr;
+use crate::error::Result;
+use crate::protocol::TokenUsage;
+use crate::wire_stream::ByteStream;
+use crate::wire_stream::MessageList;
+use crate::wire_stream::NativeRequest;
+use crate::wire_stream::WireTarget;
+use crate::wire_stream::function_tools;
+use crate::wire_stream::parse_data_url;
+use crate::wire_stream::stream_request;
+use crate::wire_stream::thinking_budget;
+use crate::wire_stream::tool_arguments;
+
+pub(crate) async fn stream_anthropic_messages(
+    request: NativeRequest<'_>,
+    target: WireTarget<'_>,
+) -> Result<ResponseStream> {
+    let payload = build_messages_request(&request)?;
+    stream_request(target, payload, process_anthropic_sse).await
+}
+
+fn build_messages_request(request: &NativeRequest<'_>) -> Result<Value> {
+    let prompt = request.prompt;
+    if prompt.output_schema.is_some() {
+        return Err(CodexErr::UnsupportedOperation(
+            "output_schema is not supported for the Anthropic Messages API".to_string(),
+        ));
+    }
+
+    let mut messages = MessageList::new("content");
+    // Results for calls that are not part of the history (e.g. a
+    // `local_shell` call made by an OpenAI model) would be rejected.
+    let mut call_ids = HashSet::new();
+    for item in prompt.get_formatted_input() {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                let role = if role == "assistant" {
+                    "assistant"
+                } else {
+                    "user"
+                };
+                for content_item in content {
+                    if let Some(block) = content_block(content_item) {
+                        messages.push(role, block);
+                    }
+                }
+            }
+            ResponseItem::Reasoning {
+                summary,
+                content,
+                encrypted_content: Some(signature),
+                ..
+            } => {
+                let text = reasoning_text(&summary, content.as_deref());
+                let block = if text.is_none() {
+                    json!({"type": "redacted_thinking", "data": signature})
+                } else {
+                    json!({
+                        "type": "thinking",
+                        "thinking": text.unwrap_or_default(),
+                        "signature": signature,
+                    })
+                };
+                messages.push("assistant", block);
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                messages.push(
+                    "assistant",
+                    json!({
+                        "type": "tool_use",
+                        "id": call_id,
+                        "name": name,
+                        "input": tool_arguments(&arguments),
+                    }),
+                );
+                call_ids.insert(call_id);
+            }
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => {
+                messages.push(
+                    "assistant",
+                    json!({
+                        "type": "tool_use",
+                        "id": call_id,
+                        "name": name,
+                        "input": {"input": input},
+                    }),
+                );
+                call_ids.insert(call_id);
+            }
+            ResponseItem::FunctionCallOutput { call_id, output } => {
+                if call_ids.contains(&call_id) {
+                    let mut block = json!({
+                        "type": "tool_result",
+                        "tool_use_id": call_id,
+                        "content": output.content,
+                    });
+                    if output.success == Some(false) {
+                        block["is_error"] = json!(true);
+                    }
+                    messages.push("user", block);
+                }
+            }
+            ResponseItem::CustomToolCallOutput { call_id, output } => {
+                if call_ids.contains(&call_id) {
+                    messages.push(
+                        "user",
+                        json!({
+                            "type": "tool_result",
+                            "tool_use_id": call_id,
+                            "content": output,
+                        }),
+                    );
+                }
+            }
+            ResponseItem::Reasoning { .. }
+            | ResponseItem::LocalShellCall { .. }
+            | ResponseItem::WebSearchCall { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+    let mut messages = messages.into_inner();
+
+    // Cache everything up to and including the newest message.
+    if let Some(block) = messages
+        .last_mut()
+        .and_then(|message| message["content"].as_array_mut())
+        .and_then(|blocks| blocks.last_mut())
+        && !matches!(
+            block["type"].as_str(),
+            Some("thinking" | "redacted_thinking")
+        )
+    {
+        block["cache_control"] = json!({"type": "ephemeral"});
+    }
+
+    let mut payload = json!({
+        "model": request.model_family.slug,
+        "max_tokens": request.max_output_tokens,
+        "system": [{
+            "type": "text",
+            "text": prompt.get_full_instructions(request.model_family),
+            "cache_control": {"type": "ephemeral"},
+        }],
+        "messages": messages,
+        "stream": true,
+    });
+
+    let tools: Vec<Value> = function_tools(&prompt.tools)?
+        .into_iter()
+        .map(|tool| {
+            json!({
+                "name": tool["name"],
+                "description": tool["description"],
+                "input_schema": tool["parameters"],
+            })
+        })
+        .collect();
+    if !tools.is_empty() {
+        payload["tools"] = json!(tools);
+        payload["tool_choice"] = json!({
+            "type": "auto",
+            "disable_parallel_tool_use": !prompt.parallel_tool_calls,
+        });
+    }
+
+    if let Some(budget_tokens) = thinking_budget(request) {
+        payload["thinking"] = json!({"type": "enabled", "budget_tokens": budget_tokens});
+    }
+
+    Ok(payload)
+}
+
+fn content_block(item: ContentItem) -> Option<Value> {
+    match item {
+        ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            (!text.is_empty()).then(|| json!({"type": "text", "text": text}))
+        }
+        ContentItem::InputImage { image_url } => Some(match parse_data_url(&image_url) {
+            Some((media_type, data)) => json!({
+                "type": "image",
+                "source": {"type": "base64", "media_type": media_type, "data": data},
+            }),
+            None => json!({
+                "type": "image",
+                "source": {"type": "url", "url": image_url},
+            }),
+        }),
+    }
+}
+
+/// The visible text of a reasoning item, if it has any.
+fn reasoning_text(
+    summary: &[ReasoningItemReasoningSummary],
+    content: Option<&[ReasoningItemContent]>,
+) -> Option<String> {
+    let mut text = String::new();
+    for ReasoningItemReasoningSummary::SummaryText { text: part } in summary {
+        text.push_str(part);
+    }
+    for item in content.unwrap_or_default() {
+        match item {
+            ReasoningItemContent::ReasoningText { text: part }
+            | ReasoningItemContent::Text { text: part } => text.push_str(part),
+        }
+    }
+    (!text.is_empty()).then_some(text)
+}
+
+/// A content block being streamed, keyed by its index in the message.
+enum Block {
+    Text(String),
+    ToolUse {
+        id: String,
+        name: String,
+        input_json: String,
+    },
+    Thinking {
+        text: String,
+        signature: String,
+    },
+    RedactedThinking(String),
+}
+
+impl Block {
+    fn start(block: &Value) -> Option<Self> {
+        let text = |key: &str| block[key].as_str().unwrap_or_default().to_string();
+        match block["type"].as_str()? {
+            "text" => Some(Block::Text(text("text"))),
+            "tool_use" => Some(Block::ToolUse {
+                id: text("id"),
+                name: text("name"),
+                input_json: String::new(),
+            }),
+            "thinking" => Some(Block::Thinking {
+                text: text("thinking"),
+                signature: text("signature"),
+            }),
+            "redacted_thinking" => Some(Block::RedactedThinking(text("data"))),
+            _ => None,
+        }
+    }
+
+    fn into_item(self) -> ResponseItem {
+        match self {
+            Block::Text(text) => ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText { text }],
+            },
+            Block::ToolUse {
+                id,
+                name,
+                input_json,
+            } => ResponseItem::FunctionCall {
+                id: None,
+                name,
+                arguments: if input_json.is_empty() {
+                    "{}".to_string()
+                } else {
+                    input_json
+                },
+                call_id: id,
+            },
+            Block::Thinking { text, signature } => ResponseItem::Reasoning {
+                id: String::new(),
+                summary: vec![ReasoningItemReasoningSummary::SummaryText { text }],
+                content: None,
+                encrypted_content: Some(signature),
+            },
+            Block::RedactedThinking(data) => ResponseItem::Reasoning {
+                id: String::new(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some(data),
+            },
+        }
+    }
+}
+
+fn token_usage(usage: &Value, output_tokens: u64) -> TokenUsage {
+    let count = |key: &str| usage[key].as_u64().unwrap_or_default();
+    // Anthropic reports cache reads and writes separately from `input_tokens`.
+    let input_tokens = count("input_tokens")
+        + count("cache_creation_input_tokens")
+        + count("cache_read_input_tokens");
+    TokenUsage {
+        input_tokens,
+        cached_input_tokens: count("cache_read_input_tokens"),
+        output_tokens,
+        reasoning_output_tokens: 0,
+        total_tokens: input_tokens + output_tokens,
+    }
+}
+
+/// Maps the Messages API event stream onto [`ResponseEvent`]s: text and
+/// thinking deltas are forwarded as they arrive, and every finished content
+/// block becomes an `OutputItemDone`.
+async fn process_anthropic_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent>>,
+    idle_timeout: Duration,
+    otel_event_manager: OtelEventManager,
+) {
+    let mut stream = stream.eventsource();
+    let mut blocks: HashMap<u64, Block> = HashMap::new();
+    let mut response_id = String::new();
+    let mut usage = Value::Null;
+    let mut output_tokens = 0;
+
+    loop {
+        let start = std::time::Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        let duration = start.elapsed();
+        otel_event_manager.log_sse_event(&response, duration);
+
+        let sse = match response {
+            Ok(Some(Ok(ev))) => ev,
+            Ok(Some(Err(e))) => {
+                let _ = tx_event
+                    .send(Err(CodexErr::Stream(e.to_string(), None)))
+                    .await;
+                return;
+            }
+            Ok(None) => {
+                let _ = tx_event
+                    .send(Err(CodexErr::Stream(
+                        "stream closed before message_stop".into(),
+                        None,
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(CodexErr::Stream(
+                        "idle timeout waiting for SSE".into(),
+                        None,
+                    )))
+                    .await;
+                return;
+            }
+        };
+
+        let event: Value = match serde_json::from_str(&sse.data) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        trace!("anthropic received SSE event: {event:?}");
+
+        let index = event["index"].as_u64().unwrap_or_default();
+        match event["type"].as_str().unwrap_or_default() {
+            "message_start" => {
+                let message = &event["message"];
+                response_id = message["id"].as_str().unwrap_or_default().to_string();
+                usage = message["usage"].clone();
+                output_tokens = usage["output_tokens"].as_u64().unwrap_or_default();
+                let _ = tx_event.send(Ok(ResponseEvent::Created)).await;
+            }
+            "content_block_start" => {
+                if let Some(block) = Block::start(&event["content_block"]) {
+                    blocks.insert(index, block);
+                }
+            }
+            "content_block_delta" => {
+                let delta = &event["delta"];
+                let text = |key: &str| delta[key].as_str().unwrap_or_default().to_string();
+                let forwarded = match (blocks.get_mut(&index), delta["type"].as_str()) {
+                    (Some(Block::Text(buffer)), Some("text_delta")) => {
+                        let text = text("text");
+                        buffer.push_str(&text);
+                        Some(ResponseEvent::OutputTextDelta(text))
+                    }
+                    (Some(Block::ToolUse { input_json, .. }), Some("input_json_delta")) => {
+                        input_json.push_str(&text("partial_json"));
+                        None
+                    }
+                    (Some(Block::Thinking { text: buffer, .. }), Some("thinking_delta")) => {
+                        let text = text("thinking");
+                        buffer.push_str(&text);
+                        Some(ResponseEvent::ReasoningSummaryDelta(text))
+                    }
+                    (Some(Block::Thinking { signature, .. }), Some("signature_delta")) => {
+                        signature.push_str(&text("signature"));
+                        None
+                    }
+                    _ => None,
+                };
+                if let Some(forwarded) = forwarded {
+                    let _ = tx_event.send(Ok(forwarded)).await;
+                }
+            }
+            "content_block_stop" => {
+                if let Some(block) = blocks.remove(&index) {
+                    let _ = tx_event
+                        .send(Ok(ResponseEvent::OutputItemDone(block.into_item())))
+                        .await;
+                }
+            }
+            "message_delta" => {
+                if let Some(tokens) = event["usage"]["output_tokens"].as_u64() {
+                    output_tokens = tokens;
+                }
+            }
+            "message_stop" => {
+                let _ = tx_event
+                    .send(Ok(ResponseEvent::Completed {
+                        response_id,
+                        token_usage: Some(token_usage(&usage, output_tokens)),
+                    }))
+                    .await;
+                return;
+            }
+            "error" => {
+                let message = event["error"]["message"]
+                    .as_str()
+                    .unwrap_or("error event from the Anthropic API")
+                    .to_string();
+                let _ = tx_event.send(Err(CodexErr::Stream(message, None))).await;
+                return;
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client_common::Prompt;
+    use crate::model_family::find_family_for_model;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn user_message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn parallel_tool_calls_share_one_message() {
+        let Some(model_family) = find_family_for_model("claude-sonnet-4-5") else {
+            panic!("claude-sonnet-4-5 should have a model family");
+        };
+        let prompt = Prompt {
+            input: vec![
+                user_message("list both dirs"),
+                ResponseItem::Reasoning {
+                    id: String::new(),
+                    summary: vec![ReasoningItemReasoningSummary::SummaryText {
+                        text: "Two listings.".to_string(),
+                    }],
+                    content: None,
+                    encrypted_content: Some("sig".to_string()),
+                },
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: "shell".to_string(),
+                    arguments: r#"{"command":["ls","a"]}"#.to_string(),
+                    call_id: "toolu_1".to_string(),
+                },
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: "shell".to_string(),
+                    arguments: r#"{"command":["ls","b"]}"#.to_string(),
+                    call_id: "toolu_2".to_string(),
+                },
+                ResponseItem::FunctionCallOutput {
+                    call_id: "toolu_1".to_string(),
+                    output: FunctionCallOutputPayload {
+                        content: "a.txt".to_string(),
+                        success: Some(true),
+                    },
+                },
+                ResponseItem::FunctionCallOutput {
+                    call_id: "toolu_2".to_string(),
+                    output: FunctionCallOutputPayload {
+                        content: "no such dir".to_string(),
+                        success: Some(false),
+                    },
+                },
+                ResponseItem::FunctionCallOutput {
+                    call_id: "call_from_another_model".to_string(),
+                    output: FunctionCallOutputPayload {
+                        content: "dropped".to_string(),
+                        success: None,
+                    },
+                },
+            ],
+            ..Default::default()
+        };
+
+        let payload = build_messages_request(&NativeRequest {
+            prompt: &prompt,
+            model_family: &model_family,
+            effort: None,
+            max_output_tokens: 64_000,
+        })
+        .expect("payload");
+
+        assert_eq!(
+            payload["messages"],
+            json!([
+                {"role": "user", "content": [{"type": "text", "text": "list both dirs"}]},
+                {"role": "assistant", "content": [
+                    {"type": "thinking", "thinking": "Two listings.", "signature": "sig"},
+                    {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {"command": ["ls", "a"]}},
+                    {"type": "tool_use", "id": "toolu_2", "name": "shell", "input": {"command": ["ls", "b"]}},
+                ]},
+                {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "toolu_1", "content": "a.txt"},
+                    {
+                        "type": "tool_result",
+                        "tool_use_id": "toolu_2",
+                        "content": "no such dir",
+                        "is_error": true,
+                        "cache_control": {"type": "ephemeral"},
+                    },
+                ]},
+            ])
+        );
+        assert_eq!(
+            payload["thinking"],
+            json!({"type": "enabled", "budget_tokens": 8_192})
+        );
+        assert_eq!(
+            payload["system"][0]["cache_control"],
+            json!({"type": "ephemeral"})
+        );
+    }
+}
@@ -3,12 +3,6 @@ use chrono::Utc;
 use serde::Deserialize;
 use serde::Serialize;
 use std::env;
-use std::fs::File;
-use std::fs::OpenOptions;
-use std::io::Read;
-use std::io::Write;
-#[cfg(unix)]
-use std::os::unix::fs::OpenOptionsExt;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -17,17 +11,25 @@ use std::time::Duration;
 
 use codex_app_server_protocol::AuthMode;
 
+use crate::auth_storage::AuthStorageBackend;
+use crate::auth_storage::FileAuthStorage;
+use crate::auth_storage::create_auth_storage;
+use crate::auth_storage::list_keyring_auth_profiles;
 use crate::token_data::PlanType;
 use crate::token_data::TokenData;
 use crate::token_data::parse_id_token;
 
+pub use crate::auth_storage::AuthCredentialsStoreMode;
+pub use crate::auth_storage::try_read_auth_json;
+pub use crate::auth_storage::write_auth_json;
+
 #[derive(Debug, Clone)]
 pub struct CodexAuth {
     pub mode: AuthMode,
 
     pub(crate) api_key: Option<String>,
     pub(crate) auth_dot_json: Arc<Mutex<Option<AuthDotJson>>>,
-    pub(crate) auth_file: PathBuf,
+    pub(crate) storage: Arc<dyn AuthStorageBackend>,
     pub(crate) client: reqwest::Client,
 }
 
@@ -49,7 +51,7 @@ impl CodexAuth {
             .map_err(std::io::Error::other)?;
 
         let updated = update_tokens(
-            &self.auth_file,
+            self.storage.as_ref(),
             refresh_response.id_token,
             refresh_response.access_token,
             refresh_response.refresh_token,
@@ -73,7 +75,17 @@ impl CodexAuth {
 
     /// Loads the available auth information from the auth.json.
     pub fn from_codex_home(codex_home: &Path) -> std::io::Result<Option<CodexAuth>> {
-        load_auth(codex_home, false)
+        Self::from_codex_home_for_profile(codex_home, None, AuthCredentialsStoreMode::default())
+    }
+
+    /// Loads the credentials stored under the named auth profile; `None`
+    /// reads the default credentials.
+    pub fn from_codex_home_for_profile(
+        codex_home: &Path,
+        auth_profile: Option<&str>,
+        store_mode: AuthCredentialsStoreMode,
+    ) -> std::io::Result<Option<CodexAuth>> {
+        load_auth(codex_home, auth_profile, store_mode, false)
     }
 
     pub async fn get_token_data(&self) -> Result<TokenData, std::io::Error> {
@@ -96,7 +108,7 @@ impl CodexAuth {
                     .map_err(std::io::Error::other)?;
 
                     let updated_auth_dot_json = update_tokens(
-                        &self.auth_file,
+                        self.storage.as_ref(),
                         refresh_response.id_token,
                         refresh_response.access_token,
                         refresh_response.refresh_token,
@@ -170,7 +182,7 @@ impl CodexAuth {
         Self {
             api_key: None,
             mode: AuthMode::ChatGPT,
-            auth_file: PathBuf::new(),
+            storage: Arc::new(FileAuthStorage::new(PathBuf::new())),
             auth_dot_json,
             client: crate::default_client::create_client(),
         }
@@ -180,7 +192,7 @@ impl CodexAuth {
         Self {
             api_key: Some(api_key.to_owned()),
             mode: AuthMode::ApiKey,
-            auth_file: PathBuf::new(),
+            storage: Arc::new(FileAuthStorage::new(PathBuf::new())),
             auth_dot_json: Arc::new(Mutex::new(None)),
             client,
         }
@@ -208,33 +220,134 @@ pub fn read_codex_api_key_from_env() -> Option<String> {
         .filter(|value| !value.is_empty())
 }
 
+/// Name that refers to the credentials in `auth.json`.
+pub const DEFAULT_AUTH_PROFILE: &str = "default";
+
 pub fn get_auth_file(codex_home: &Path) -> PathBuf {
     codex_home.join("auth.json")
 }
 
+/// Credentials file for a named auth profile, stored next to `auth.json` as
+/// `auth.<name>.json`. `None` and [`DEFAULT_AUTH_PROFILE`] map to `auth.json`.
+pub fn get_profile_auth_file(codex_home: &Path, auth_profile: Option<&str>) -> PathBuf {
+    match auth_profile {
+        Some(name) if name != DEFAULT_AUTH_PROFILE => codex_home.join(format!("auth.{name}.json")),
+        _ => get_auth_file(codex_home),
+    }
+}
+
+/// Auth profile names become part of a filename, so they are limited to
+/// ASCII letters, digits, `-` and `_`.
+pub fn validate_auth_profile_name(name: &str) -> std::io::Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("invalid auth profile name `{name}`: use only letters, digits, `-` and `_`"),
+        ))
+    }
+}
+
+/// Names of the auth profiles with stored credentials, in files or in the
+/// keyring, sorted, with [`DEFAULT_AUTH_PROFILE`] first.
+pub fn list_auth_profiles(codex_home: &Path) -> std::io::Result<Vec<String>> {
+    let mut names = list_keyring_auth_profiles(codex_home)?;
+    let entries = match std::fs::read_dir(codex_home) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(names),
+        Err(err) => return Err(err),
+    };
+    for entry in entries {
+        let file_name = entry?.file_name();
+        let Some(name) = file_name
+            .to_str()
+            .and_then(|file_name| file_name.strip_prefix("auth."))
+            .and_then(|rest| rest.strip_suffix(".json"))
+        else {
+            continue;
+        };
+        if name != DEFAULT_AUTH_PROFILE && validate_auth_profile_name(name).is_ok() {
+            names.push(name.to_string());
+        }
+    }
+    if get_auth_file(codex_home).exists() {
+        names.push(DEFAULT_AUTH_PROFILE.to_string());
+    }
+    names.sort_by_key(|name| (name != DEFAULT_AUTH_PROFILE, name.clone()));
+    names.dedup();
+    Ok(names)
+}
+
 /// Delete the auth.json file inside `codex_home` if it exists. Returns `Ok(true)`
 /// if a file was removed, `Ok(false)` if no auth file was present.
 pub fn logout(codex_home: &Path) -> std::io::Result<bool> {
-    let auth_file = get_auth_file(codex_home);
-    match std::fs::remove_file(&auth_file) {
-        Ok(_) => Ok(true),
-        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
-        Err(err) => Err(err),
-    }
+    logout_profile(codex_home, None, AuthCredentialsStoreMode::default())
+}
+
+/// Like [`logout`], for the named auth profile and credentials store.
+pub fn logout_profile(
+    codex_home: &Path,
+    auth_profile: Option<&str>,
+    store_mode: AuthCredentialsStoreMode,
+) -> std::io::Result<bool> {
+    create_auth_storage(codex_home, auth_profile, store_mode).delete()
 }
 
 /// Writes an `auth.json` that contains only the API key.
 pub fn login_with_api_key(codex_home: &Path, api_key: &str) -> std::io::Result<()> {
+    login_with_api_key_for_profile(
+        codex_home,
+        None,
+        AuthCredentialsStoreMode::default(),
+        api_key,
+    )
+}
+
+/// Like [`login_with_api_key`], for the named auth profile and credentials
+/// store.
+pub fn login_with_api_key_for_profile(
+    codex_home: &Path,
+    auth_profile: Option<&str>,
+    store_mode: AuthCredentialsStoreMode,
+    api_key: &str,
+) -> std::io::Result<()> {
     let auth_dot_json = AuthDotJson {
         openai_api_key: Some(api_key.to_string()),
         tokens: None,
         last_refresh: None,
     };
-    write_auth_json(&get_auth_file(codex_home), &auth_dot_json)
+    save_auth(codex_home, auth_profile, store_mode, &auth_dot_json)
+}
+
+/// Reads the stored credentials of an auth profile, `None` when there are
+/// none.
+pub fn load_auth_dot_json(
+    codex_home: &Path,
+    auth_profile: Option<&str>,
+    store_mode: AuthCredentialsStoreMode,
+) -> std::io::Result<Option<AuthDotJson>> {
+    create_auth_storage(codex_home, auth_profile, store_mode).load()
+}
+
+/// Replaces the stored credentials of an auth profile.
+pub fn save_auth(
+    codex_home: &Path,
+    auth_profile: Option<&str>,
+    store_mode: AuthCredentialsStoreMode,
+    auth_dot_json: &AuthDotJson,
+) -> std::io::Result<()> {
+    create_auth_storage(codex_home, auth_profile, store_mode).save(auth_dot_json)
 }
 
 fn load_auth(
     codex_home: &Path,
+    auth_profile: Option<&str>,
+    store_mode: AuthCredentialsStoreMode,
     enable_codex_api_key_env: bool,
 ) -> std::io::Result<Option<CodexAuth>> {
     if enable_codex_api_key_env && let Some(api_key) = read_codex_api_key_from_env() {
@@ -245,13 +358,10 @@ fn load_auth(
         )));
     }
 
-    let auth_file = get_auth_file(codex_home);
+    let storage = create_auth_storage(codex_home, auth_profile, store_mode);
     let client = crate::default_client::create_client();
-    let auth_dot_json = match try_read_auth_json(&auth_file) {
-        Ok(auth) => auth,
-        Err(e) => {
-            return Err(e);
-        }
+    let Some(auth_dot_json) = storage.load()? else {
+        return Ok(None);
     };
 
     let AuthDotJson {
@@ -268,7 +378,7 @@ fn load_auth(
     Ok(Some(CodexAuth {
         api_key: None,
         mode: AuthMode::ChatGPT,
-        auth_file,
+        storage,
         auth_dot_json: Arc::new(Mutex::new(Some(AuthDotJson {
             openai_api_key: None,
             tokens,
@@ -278,41 +388,15 @@ fn load_auth(
     }))
 }
 
-/// Attempt to read and refresh the `auth.json` file in the given `CODEX_HOME` directory.
-/// Returns the full AuthDotJson structure after refreshing if necessary.
-pub fn try_read_auth_json(auth_file: &Path) -> std::io::Result<AuthDotJson> {
-    let mut file = File::open(auth_file)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
-    let auth_dot_json: AuthDotJson = serde_json::from_str(&contents)?;
-
-    Ok(auth_dot_json)
-}
-
-pub fn write_auth_json(auth_file: &Path, auth_dot_json: &AuthDotJson) -> std::io::Result<()> {
-    if let Some(parent) = auth_file.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
-    let json_data = serde_json::to_string_pretty(auth_dot_json)?;
-    let mut options = OpenOptions::new();
-    options.truncate(true).write(true).create(true);
-    #[cfg(unix)]
-    {
-        options.mode(0o600);
-    }
-    let mut file = options.open(auth_file)?;
-    file.write_all(json_data.as_bytes())?;
-    file.flush()?;
-    Ok(())
-}
-
 async fn update_tokens(
-    auth_file: &Path,
+    storage: &dyn AuthStorageBackend,
     id_token: String,
     access_token: Option<String>,
     refresh_token: Option<String>,
 ) -> std::io::Result<AuthDotJson> {
-    let mut auth_dot_json = try_read_auth_json(auth_file)?;
+    let mut auth_dot_json = storage
+        .load()?
+        .ok_or_else(|| std::io::Error::other("Stored credentials are missing."))?;
 
     let tokens = auth_dot_json.tokens.get_or_insert_with(TokenData::default);
     tokens.id_token = parse_id_token(&id_token).map_err(std::io::Error::other)?;
@@ -323,7 +407,7 @@ async fn update_tokens(
         tokens.refresh_token = refresh_token;
     }
     auth_dot_json.last_refresh = Some(Utc::now());
-    write_auth_json(auth_file, &auth_dot_json)?;
+    storage.save(&auth_dot_json)?;
     Ok(auth_dot_json)
 }
 
@@ -476,9 +560,15 @@ mod tests {
             api_key,
             mode,
             auth_dot_json,
-            auth_file: _,
             ..
-        } = super::load_auth(codex_home.path(), false).unwrap().unwrap();
+        } = super::load_auth(
+            codex_home.path(),
+            None,
+            AuthCredentialsStoreMode::File,
+            false,
+        )
+        .unwrap()
+        .unwrap();
         assert_eq!(None, api_key);
         assert_eq!(AuthMode::ChatGPT, mode);
 
@@ -517,7 +607,9 @@ mod tests {
         )
         .unwrap();
 
-        let auth = super::load_auth(dir.path(), false).unwrap().unwrap();
+        let auth = super::load_auth(dir.path(), None, AuthCredentialsStoreMode::File, false)
+            .unwrap()
+            .unwrap();
         assert_eq!(auth.mode, AuthMode::ApiKey);
         assert_eq!(auth.api_key, Some("sk-test-key".to_string()));
 
@@ -540,6 +632,48 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn auth_profiles_are_stored_side_by_side() -> Result<(), std::io::Error> {
+        let dir = tempdir()?;
+        assert_eq!(list_auth_profiles(dir.path())?, Vec::<String>::new());
+
+        login_with_api_key(dir.path(), "sk-personal")?;
+        let file_mode = AuthCredentialsStoreMode::File;
+        login_with_api_key_for_profile(dir.path(), Some("work"), file_mode, "sk-work")?;
+        login_with_api_key_for_profile(dir.path(), Some("ci"), file_mode, "sk-ci")?;
+        assert!(dir.path().join("auth.work.json").exists());
+        assert_eq!(
+            list_auth_profiles(dir.path())?,
+            vec!["default".to_string(), "ci".to_string(), "work".to_string()]
+        );
+
+        let work = CodexAuth::from_codex_home_for_profile(dir.path(), Some("work"), file_mode)?
+            .expect("work credentials");
+        assert_eq!(work.api_key.as_deref(), Some("sk-work"));
+        let default =
+            CodexAuth::from_codex_home_for_profile(dir.path(), Some("default"), file_mode)?
+                .expect("default credentials");
+        assert_eq!(default.api_key.as_deref(), Some("sk-personal"));
+
+        let manager = AuthManager::new_with_profile(
+            dir.path().to_path_buf(),
+            Some("work".to_string()),
+            file_mode,
+            false,
+        );
+        assert_eq!(manager.auth_profile(), Some("work"));
+        assert!(manager.logout()?);
+        assert!(manager.auth().is_none());
+        assert_eq!(
+            list_auth_profiles(dir.path())?,
+            vec!["default".to_string(), "ci".to_string()]
+        );
+
+        assert!(validate_auth_profile_name("work-2").is_ok());
+        assert!(validate_auth_profile_name("../evil").is_err());
+        Ok(())
+    }
+
     struct AuthFileParams {
         openai_api_key: Option<String>,
         chatgpt_plan_type: String,
@@ -599,6 +733,8 @@ mod tests {
 #[derive(Debug)]
 pub struct AuthManager {
     codex_home: PathBuf,
+    auth_profile: Option<String>,
+    store_mode: AuthCredentialsStoreMode,
     inner: RwLock<CachedAuth>,
     enable_codex_api_key_env: bool,
 }
@@ -609,11 +745,34 @@ impl AuthManager {
     /// simply return `None` in that case so callers can treat it as an
     /// unauthenticated state.
     pub fn new(codex_home: PathBuf, enable_codex_api_key_env: bool) -> Self {
-        let auth = load_auth(&codex_home, enable_codex_api_key_env)
-            .ok()
-            .flatten();
+        Self::new_with_profile(
+            codex_home,
+            None,
+            AuthCredentialsStoreMode::default(),
+            enable_codex_api_key_env,
+        )
+    }
+
+    /// Like [`AuthManager::new`], reading the credentials of the named auth
+    /// profile from the given credentials store.
+    pub fn new_with_profile(
+        codex_home: PathBuf,
+        auth_profile: Option<String>,
+        store_mode: AuthCredentialsStoreMode,
+        enable_codex_api_key_env: bool,
+    ) -> Self {
+        let auth = load_auth(
+            &codex_home,
+            auth_profile.as_deref(),
+            store_mode,
+            enable_codex_api_key_env,
+        )
+        .ok()
+        .flatten();
         Self {
             codex_home,
+            auth_profile,
+            store_mode,
             inner: RwLock::new(CachedAuth { auth }),
             enable_codex_api_key_env,
         }
@@ -624,6 +783,8 @@ impl AuthManager {
         let cached = CachedAuth { auth: Some(auth) };
         Arc::new(Self {
             codex_home: PathBuf::new(),
+            auth_profile: None,
+            store_mode: AuthCredentialsStoreMode::default(),
             inner: RwLock::new(cached),
             enable_codex_api_key_env: false,
         })
@@ -634,12 +795,27 @@ impl AuthManager {
         self.inner.read().ok().and_then(|c| c.auth.clone())
     }
 
+    /// Name of the auth profile in use; `None` for the default `auth.json`.
+    pub fn auth_profile(&self) -> Option<&str> {
+        self.auth_profile.as_deref()
+    }
+
+    /// Where the credentials are stored.
+    pub fn credentials_store_mode(&self) -> AuthCredentialsStoreMode {
+        self.store_mode
+    }
+
     /// Force a reload of the auth information from auth.json. Returns
     /// whether the auth value changed.
     pub fn reload(&self) -> bool {
-        let new_auth = load_auth(&self.codex_home, self.enable_codex_api_key_env)
-            .ok()
-            .flatten();
+        let new_auth = load_auth(
+            &self.codex_home,
+            self.auth_profile.as_deref(),
+            self.store_mode,
+            self.enable_codex_api_key_env,
+        )
+        .ok()
+        .flatten();
         if let Ok(mut guard) = self.inner.write() {
             let changed = !AuthManager::auths_equal(&guard.auth, &new_auth);
             guard.auth = new_auth;
@@ -662,6 +838,21 @@ impl AuthManager {
         Arc::new(Self::new(codex_home, enable_codex_api_key_env))
     }
 
+    /// `Arc` wrapper around [`AuthManager::new_with_profile`].
+    pub fn shared_with_profile(
+        codex_home: PathBuf,
+        auth_profile: Option<String>,
+        store_mode: AuthCredentialsStoreMode,
+        enable_codex_api_key_env: bool,
+    ) -> Arc<Self> {
+        Arc::new(Self::new_with_profile(
+            codex_home,
+            auth_profile,
+            store_mode,
+            enable_codex_api_key_env,
+        ))
+    }
+
     /// Attempt to refresh the current auth token (if any). On success, reload
     /// the auth state from disk so other components observe refreshed token.
     pub async fn refresh_token(&self) -> std::io::Result<Option<String>> {
@@ -679,12 +870,16 @@ impl AuthManager {
         }
     }
 
-    /// Log out by deleting the on‑disk auth.json (if present). Returns Ok(true)
-    /// if a file was removed, Ok(false) if no auth file existed. On success,
+    /// Log out by deleting the stored credentials (if present). Returns Ok(true)
+    /// if something was removed, Ok(false) if no credentials existed. On success,
     /// reloads the in‑memory auth cache so callers immediately observe the
     /// unauthenticated state.
     pub fn logout(&self) -> std::io::Result<bool> {
-        let removed = super::auth::logout(&self.codex_home)?;
+        let removed = logout_profile(
+            &self.codex_home,
+            self.auth_profile.as_deref(),
+            self.store_mode,
+        )?;
         // Always reload to clear any cached auth (even if file absent).
         self.reload();
         Ok(removed)
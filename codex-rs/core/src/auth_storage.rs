@@ -0,0 +1,562 @@
+//! Where the CLI keeps the credentials from `codex login`.
+//!
+//! Credentials are stored per auth profile, either as `auth.json` /
+//! `auth.<name>.json` in `CODEX_HOME` or in the OS keyring through the same
+//! [`KeyringStore`] the MCP OAuth tokens use. The keyring backends migrate an
+//! existing credentials file into the keyring the first time it is read and
+//! then delete the plaintext copy.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Read;
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_rmcp_client::CredentialStoreError;
+use codex_rmcp_client::DefaultKeyringStore;
+use codex_rmcp_client::KeyringStore;
+use serde::Deserialize;
+use serde::Serialize;
+use sha1::Digest;
+use sha1::Sha1;
+use tracing::warn;
+
+use crate::auth::AuthDotJson;
+use crate::auth::DEFAULT_AUTH_PROFILE;
+use crate::auth::get_profile_auth_file;
+
+const KEYRING_SERVICE: &str = "Codex Auth";
+
+/// Lists the auth profiles whose credentials live in the keyring, which
+/// cannot be enumerated. Holds names only.
+const KEYRING_PROFILES_FILE: &str = "keyring_auth_profiles.json";
+
+/// Determine where Codex should store and read the CLI credentials.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthCredentialsStoreMode {
+    /// CODEX_HOME/auth.json, readable by other applications running as the
+    /// same user.
+    #[default]
+    File,
+    /// Keyring when available, otherwise fail.
+    Keyring,
+    /// `Keyring` when available; otherwise, `File`.
+    Auto,
+}
+
+/// Reads and writes the credentials of one auth profile.
+pub trait AuthStorageBackend: fmt::Debug + Send + Sync {
+    fn load(&self) -> std::io::Result<Option<AuthDotJson>>;
+    fn save(&self, auth: &AuthDotJson) -> std::io::Result<()>;
+    /// Returns whether anything was removed.
+    fn delete(&self) -> std::io::Result<bool>;
+}
+
+/// Backend for `auth_profile` in `codex_home` using the platform keyring.
+pub fn create_auth_storage(
+    codex_home: &Path,
+    auth_profile: Option<&str>,
+    mode: AuthCredentialsStoreMode,
+) -> Arc<dyn AuthStorageBackend> {
+    create_auth_storage_with_keyring(
+        codex_home,
+        auth_profile,
+        mode,
+        Arc::new(DefaultKeyringStore),
+    )
+}
+
+pub(crate) fn create_auth_storage_with_keyring(
+    codex_home: &Path,
+    auth_profile: Option<&str>,
+    mode: AuthCredentialsStoreMode,
+    keyring_store: Arc<dyn KeyringStore + Send + Sync>,
+) -> Arc<dyn AuthStorageBackend> {
+    let file = FileAuthStorage::new(get_profile_auth_file(codex_home, auth_profile));
+    match mode {
+        AuthCredentialsStoreMode::File => Arc::new(file),
+        AuthCredentialsStoreMode::Keyring => Arc::new(KeyringAuthStorage::new(
+            codex_home,
+            auth_profile,
+            keyring_store,
+        )),
+        AuthCredentialsStoreMode::Auto => Arc::new(AutoAuthStorage {
+            keyring: KeyringAuthStorage::new(codex_home, auth_profile, keyring_store),
+            file,
+        }),
+    }
+}
+
+/// Stores the credentials as JSON in `auth_file`.
+#[derive(Debug, Clone)]
+pub struct FileAuthStorage {
+    auth_file: PathBuf,
+}
+
+impl FileAuthStorage {
+    pub fn new(auth_file: PathBuf) -> Self {
+        Self { auth_file }
+    }
+}
+
+impl AuthStorageBackend for FileAuthStorage {
+    fn load(&self) -> std::io::Result<Option<AuthDotJson>> {
+        match try_read_auth_json(&self.auth_file) {
+            Ok(auth) => Ok(Some(auth)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn save(&self, auth: &AuthDotJson) -> std::io::Result<()> {
+        write_auth_json(&self.auth_file, auth)
+    }
+
+    fn delete(&self) -> std::io::Result<bool> {
+        match std::fs::remove_file(&self.auth_file) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Stores the credentials in the keyring, importing the profile's credentials
+/// file on first load.
+pub struct KeyringAuthStorage {
+    codex_home: PathBuf,
+    profile: String,
+    account: String,
+    file: FileAuthStorage,
+    keyring_store: Arc<dyn KeyringStore + Send + Sync>,
+}
+
+impl fmt::Debug for KeyringAuthStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyringAuthStorage")
+            .field("profile", &self.profile)
+            .field("account", &self.account)
+            .finish_non_exhaustive()
+    }
+}
+
+impl KeyringAuthStorage {
+    fn new(
+        codex_home: &Path,
+        auth_profile: Option<&str>,
+        keyring_store: Arc<dyn KeyringStore + Send + Sync>,
+    ) -> Self {
+        let profile = auth_profile.unwrap_or(DEFAULT_AUTH_PROFILE).to_string();
+        Self {
+            codex_home: codex_home.to_path_buf(),
+            account: keyring_account(codex_home, &profile),
+            profile,
+            file: FileAuthStorage::new(get_profile_auth_file(codex_home, auth_profile)),
+            keyring_store,
+        }
+    }
+
+    fn save_to_keyring(&self, auth: &AuthDotJson) -> std::io::Result<()> {
+        let serialized = serde_json::to_string(auth)?;
+        self.keyring_store
+            .save(KEYRING_SERVICE, &self.account, &serialized)
+            .map_err(|err| keyring_error("save", err))?;
+        update_keyring_profiles(&self.codex_home, &self.profile, true)
+    }
+
+    /// Removes the keyring entry only, leaving the credentials file alone.
+    fn delete_from_keyring(&self) -> std::io::Result<bool> {
+        let removed = self
+            .keyring_store
+            .delete(KEYRING_SERVICE, &self.account)
+            .map_err(|err| keyring_error("delete", err))?;
+        update_keyring_profiles(&self.codex_home, &self.profile, false)?;
+        Ok(removed)
+    }
+
+    /// Moves the plaintext credentials file, if any, into the keyring.
+    fn migrate_file(&self) -> std::io::Result<Option<AuthDotJson>> {
+        let Some(auth) = self.file.load()? else {
+            return Ok(None);
+        };
+        self.save_to_keyring(&auth)?;
+        if let Err(err) = self.file.delete() {
+            warn!(
+                "moved credentials of auth profile `{}` to the keyring but could not delete the file: {err}",
+                self.profile
+            );
+        }
+        Ok(Some(auth))
+    }
+}
+
+impl AuthStorageBackend for KeyringAuthStorage {
+    fn load(&self) -> std::io::Result<Option<AuthDotJson>> {
+        let stored = self
+            .keyring_store
+            .load(KEYRING_SERVICE, &self.account)
+            .map_err(|err| keyring_error("read", err))?;
+        match stored {
+            Some(serialized) => Ok(Some(serde_json::from_str(&serialized)?)),
+            None => self.migrate_file(),
+        }
+    }
+
+    fn save(&self, auth: &AuthDotJson) -> std::io::Result<()> {
+        self.save_to_keyring(auth)?;
+        // Do not leave an outdated plaintext copy behind.
+        self.file.delete()?;
+        Ok(())
+    }
+
+    fn delete(&self) -> std::io::Result<bool> {
+        let removed_from_keyring = self.delete_from_keyring()?;
+        let removed_file = self.file.delete()?;
+        Ok(removed_from_keyring || removed_file)
+    }
+}
+
+/// Uses the keyring and falls back to the credentials file when the keyring
+/// is unavailable.
+///
+/// A credentials file next to a keyring entry was written by such a fallback
+/// after the entry, unless the entry was refreshed later; the newer one wins
+/// and is moved into the keyring.
+#[derive(Debug)]
+pub struct AutoAuthStorage {
+    keyring: KeyringAuthStorage,
+    file: FileAuthStorage,
+}
+
+impl AuthStorageBackend for AutoAuthStorage {
+    fn load(&self) -> std::io::Result<Option<AuthDotJson>> {
+        let stored = match self.keyring.load() {
+            Ok(stored) => stored,
+            Err(err) => {
+                warn!("{err}; falling back to the credentials file");
+                return self.file.load();
+            }
+        };
+        match (stored, self.file.load()?) {
+            (Some(stored), Some(file)) if file.last_refresh >= stored.last_refresh => {
+                if let Err(err) = self.keyring.save(&file) {
+                    warn!("could not move newer credentials file into the keyring: {err}");
+                }
+                Ok(Some(file))
+            }
+            (stored, _) => Ok(stored),
+        }
+    }
+
+    fn save(&self, auth: &AuthDotJson) -> std::io::Result<()> {
+        match self.keyring.save(auth) {
+            Ok(()) => Ok(()),
+            Err(err) => {
+                warn!("{err}; falling back to the credentials file");
+                self.file.save(auth)?;
+                // Best effort: an outdated keyring entry would otherwise be
+                // read instead of the file.
+                if let Err(err) = self.keyring.delete_from_keyring() {
+                    warn!("could not remove outdated credentials from the keyring: {err}");
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn delete(&self) -> std::io::Result<bool> {
+        let removed_from_keyring = match self.keyring.delete() {
+            Ok(removed) => removed,
+            Err(err) => {
+                warn!("{err}");
+                false
+            }
+        };
+        let removed_file = self.file.delete()?;
+        Ok(removed_from_keyring || removed_file)
+    }
+}
+
+/// Names of the auth profiles stored in the keyring for `codex_home`.
+pub(crate) fn list_keyring_auth_profiles(codex_home: &Path) -> std::io::Result<Vec<String>> {
+    Ok(read_keyring_profiles(codex_home)?.into_iter().collect())
+}
+
+/// Attempt to read and refresh the `auth.json` file in the given `CODEX_HOME` directory.
+/// Returns the full AuthDotJson structure after refreshing if necessary.
+pub fn try_read_auth_json(auth_file: &Path) -> std::io::Result<AuthDotJson> {
+    let mut file = File::open(auth_file)?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+    let auth_dot_json: AuthDotJson = serde_json::from_str(&contents)?;
+
+    Ok(auth_dot_json)
+}
+
+pub fn write_auth_json(auth_file: &Path, auth_dot_json: &AuthDotJson) -> std::io::Result<()> {
+    if let Some(parent) = auth_file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json_data = serde_json::to_string_pretty(auth_dot_json)?;
+    let mut options = OpenOptions::new();
+    options.truncate(true).write(true).create(true);
+    #[cfg(unix)]
+    {
+        options.mode(0o600);
+    }
+    let mut file = options.open(auth_file)?;
+    file.write_all(json_data.as_bytes())?;
+    file.flush()?;
+    Ok(())
+}
+
+/// Keyring account for a profile. Includes a digest of `codex_home` so that
+/// separate `CODEX_HOME`s do not share credentials.
+fn keyring_account(codex_home: &Path, profile: &str) -> String {
+    let codex_home = dunce::canonicalize(codex_home).unwrap_or_else(|_| codex_home.to_path_buf());
+    let mut hasher = Sha1::new();
+    hasher.update(codex_home.to_string_lossy().as_bytes());
+    let digest = format!("{:x}", hasher.finalize());
+    format!("cli|{profile}|{}", &digest[..16])
+}
+
+fn keyring_error(action: &str, err: CredentialStoreError) -> std::io::Error {
+    std::io::Error::other(format!(
+        "failed to {action} credentials in the keyring: {}",
+        err.message()
+    ))
+}
+
+fn read_keyring_profiles(codex_home: &Path) -> std::io::Result<BTreeSet<String>> {
+    match std::fs::read_to_string(codex_home.join(KEYRING_PROFILES_FILE)) {
+        Ok(contents) => Ok(serde_json::from_str(&contents)?),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(BTreeSet::new()),
+        Err(err) => Err(err),
+    }
+}
+
+fn update_keyring_profiles(codex_home: &Path, profile: &str, present: bool) -> std::io::Result<()> {
+    let mut profiles = read_keyring_profiles(codex_home)?;
+    let changed = if present {
+        profiles.insert(profile.to_string())
+    } else {
+        profiles.remove(profile)
+    };
+    if !changed {
+        return Ok(());
+    }
+    std::fs::create_dir_all(codex_home)?;
+    std::fs::write(
+        codex_home.join(KEYRING_PROFILES_FILE),
+        serde_json::to_string_pretty(&profiles)?,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono::Utc;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering;
+    use tempfile::tempdir;
+
+    /// In-memory keyring; `unavailable` simulates a missing keyring.
+    #[derive(Default)]
+    struct MemoryKeyringStore {
+        entries: Mutex<HashMap<(String, String), String>>,
+        unavailable: AtomicBool,
+    }
+
+    impl MemoryKeyringStore {
+        fn check(&self) -> Result<(), CredentialStoreError> {
+            if self.unavailable.load(Ordering::SeqCst) {
+                Err(CredentialStoreError::new(anyhow::anyhow!(
+                    "keyring unavailable"
+                )))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    impl KeyringStore for MemoryKeyringStore {
+        fn load(
+            &self,
+            service: &str,
+            account: &str,
+        ) -> Result<Option<String>, CredentialStoreError> {
+            self.check()?;
+            let entries = self.entries.lock().unwrap();
+            Ok(entries
+                .get(&(service.to_string(), account.to_string()))
+                .cloned())
+        }
+
+        fn save(
+            &self,
+            service: &str,
+            account: &str,
+            value: &str,
+        ) -> Result<(), CredentialStoreError> {
+            self.check()?;
+            let mut entries = self.entries.lock().unwrap();
+            entries.insert(
+                (service.to_string(), account.to_string()),
+                value.to_string(),
+            );
+            Ok(())
+        }
+
+        fn delete(&self, service: &str, account: &str) -> Result<bool, CredentialStoreError> {
+            self.check()?;
+            let mut entries = self.entries.lock().unwrap();
+            Ok(entries
+                .remove(&(service.to_string(), account.to_string()))
+                .is_some())
+        }
+    }
+
+    fn api_key_auth(api_key: &str) -> AuthDotJson {
+        AuthDotJson {
+            openai_api_key: Some(api_key.to_string()),
+            tokens: None,
+            last_refresh: None,
+        }
+    }
+
+    #[test]
+    fn keyring_mode_migrates_existing_auth_json() -> std::io::Result<()> {
+        let codex_home = tempdir()?;
+        let auth_file = codex_home.path().join("auth.json");
+        write_auth_json(&auth_file, &api_key_auth("sk-file"))?;
+
+        let keyring = Arc::new(MemoryKeyringStore::default());
+        let storage = create_auth_storage_with_keyring(
+            codex_home.path(),
+            None,
+            AuthCredentialsStoreMode::Keyring,
+            keyring.clone(),
+        );
+        assert_eq!(storage.load()?, Some(api_key_auth("sk-file")));
+        assert!(!auth_file.exists());
+        assert_eq!(keyring.entries.lock().unwrap().len(), 1);
+        assert_eq!(
+            list_keyring_auth_profiles(codex_home.path())?,
+            vec!["default".to_string()]
+        );
+        // Served from the keyring from now on.
+        assert_eq!(storage.load()?, Some(api_key_auth("sk-file")));
+
+        storage.save(&api_key_auth("sk-new"))?;
+        assert_eq!(storage.load()?, Some(api_key_auth("sk-new")));
+        assert!(!auth_file.exists());
+
+        assert!(storage.delete()?);
+        assert_eq!(storage.load()?, None);
+        assert!(list_keyring_auth_profiles(codex_home.path())?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn profiles_and_codex_homes_use_separate_keyring_entries() -> std::io::Result<()> {
+        let first_home = tempdir()?;
+        let second_home = tempdir()?;
+        let keyring = Arc::new(MemoryKeyringStore::default());
+        let storage = |home: &Path, profile: Option<&str>| {
+            create_auth_storage_with_keyring(
+                home,
+                profile,
+                AuthCredentialsStoreMode::Keyring,
+                keyring.clone(),
+            )
+        };
+
+        storage(first_home.path(), None).save(&api_key_auth("sk-default"))?;
+        storage(first_home.path(), Some("work")).save(&api_key_auth("sk-work"))?;
+
+        assert_eq!(
+            storage(first_home.path(), Some("work")).load()?,
+            Some(api_key_auth("sk-work"))
+        );
+        assert_eq!(
+            storage(first_home.path(), None).load()?,
+            Some(api_key_auth("sk-default"))
+        );
+        assert_eq!(storage(second_home.path(), None).load()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn auto_mode_falls_back_to_file_without_keyring() -> std::io::Result<()> {
+        let codex_home = tempdir()?;
+        let keyring = Arc::new(MemoryKeyringStore {
+            unavailable: AtomicBool::new(true),
+            ..Default::default()
+        });
+        let storage = create_auth_storage_with_keyring(
+            codex_home.path(),
+            None,
+            AuthCredentialsStoreMode::Auto,
+            keyring.clone(),
+        );
+        storage.save(&api_key_auth("sk-file"))?;
+        assert!(codex_home.path().join("auth.json").exists());
+        assert_eq!(storage.load()?, Some(api_key_auth("sk-file")));
+
+        let strict = create_auth_storage_with_keyring(
+            codex_home.path(),
+            None,
+            AuthCredentialsStoreMode::Keyring,
+            keyring,
+        );
+        assert!(strict.load().is_err());
+
+        assert!(storage.delete()?);
+        assert!(!codex_home.path().join("auth.json").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn auto_mode_prefers_a_file_saved_while_the_keyring_was_down() -> std::io::Result<()> {
+        let codex_home = tempdir()?;
+        let auth_file = codex_home.path().join("auth.json");
+        let keyring = Arc::new(MemoryKeyringStore::default());
+        let storage = create_auth_storage_with_keyring(
+            codex_home.path(),
+            None,
+            AuthCredentialsStoreMode::Auto,
+            keyring.clone(),
+        );
+        let refreshed_at = |hour| AuthDotJson {
+            last_refresh: Some(Utc.with_ymd_and_hms(2025, 1, 1, hour, 0, 0).unwrap()),
+            ..api_key_auth("sk-test")
+        };
+
+        storage.save(&refreshed_at(1))?;
+        assert!(!auth_file.exists());
+
+        // The keyring fails, so the refreshed token only reaches the file and
+        // the old entry cannot be removed either.
+        keyring.unavailable.store(true, Ordering::SeqCst);
+        storage.save(&refreshed_at(2))?;
+        assert!(auth_file.exists());
+
+        keyring.unavailable.store(false, Ordering::SeqCst);
+        assert_eq!(storage.load()?, Some(refreshed_at(2)));
+        // The newer credentials moved into the keyring.
+        assert!(!auth_file.exists());
+        assert_eq!(storage.load()?, Some(refreshed_at(2)));
+        Ok(())
+    }
+}
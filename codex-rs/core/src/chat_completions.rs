@@ -11,7 +11,9 @@ use crate::error::Result;
 use crate::error::RetryLimitReachedError;
 use crate::error::UnexpectedResponseError;
 use crate::model_family::ModelFamily;
+use crate::model_fixtures::Fixture;
 use crate::openai_tools::create_tools_json_for_chat_completions_api;
+use crate::rate_limit_governor::RateLimitPacer;
 use crate::util::backoff;
 use bytes::Bytes;
 use codex_otel::otel_event_manager::OtelEventManager;
@@ -22,13 +24,16 @@ use eventsource_stream::Eventsource;
 use futures::Stream;
 use futures::StreamExt;
 use futures::TryStreamExt;
+use futures::future::Either;
 use reqwest::StatusCode;
 use serde_json::json;
+use std::path::Path;
 use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
 use tokio::sync::mpsc;
 use tokio::time::timeout;
+use tracing::Instrument;
 use tracing::debug;
 use tracing::trace;
 
@@ -39,6 +44,8 @@ pub(crate) async fn stream_chat_completions(
     client: &reqwest::Client,
     provider: &ModelProviderInfo,
     otel_event_manager: &OtelEventManager,
+    cwd: &Path,
+    pacer: RateLimitPacer,
 ) -> Result<ResponseStream> {
     if prompt.output_schema.is_some() {
         return Err(CodexErr::UnsupportedOperation(
@@ -279,37 +286,62 @@ pub(crate) async fn stream_chat_completions(
     }
 
     let tools_json = create_tools_json_for_chat_completions_api(&prompt.tools)?;
-    let payload = json!({
+    let mut payload = json!({
         "model": model_family.slug,
         "messages": messages,
         "stream": true,
         "tools": tools_json,
     });
+    if let Some(max_output_tokens) = prompt.max_output_tokens {
+        payload["max_tokens"] = json!(max_output_tokens);
+    }
 
     debug!(
         "POST to {}: {}",
-        provider.get_full_url(&None),
+        provider.get_full_url(&None, &model_family.slug),
         serde_json::to_string_pretty(&payload).unwrap_or_default()
     );
 
+    let fixture = provider
+        .fixtures
+        .as_ref()
+        .map(|fixtures| Fixture::for_request(fixtures, &payload, cwd));
+    if let Some(fixture) = fixture.as_ref().filter(|fixture| fixture.is_replay()) {
+        let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent>>(1600);
+        tokio::spawn(process_chat_sse(
+            fixture.replay().await?,
+            tx_event,
+            provider.stream_idle_timeout(),
+            otel_event_manager.clone(),
+        ));
+        return Ok(ResponseStream { rx_event });
+    }
+
     let mut attempt = 0;
     let max_retries = provider.request_max_retries();
     loop {
         attempt += 1;
+        pacer.acquire().await;
 
-        let req_builder = provider.create_request_builder(client, &None).await?;
+        let req_builder = provider
+            .create_request_builder(client, &None, &model_family.slug)
+            .await?;
 
+        // `attempt` counts from 1 here; the telemetry attempt is 0-based.
+        let request_span = otel_event_manager.model_request_span(attempt - 1);
         let res = otel_event_manager
-            .log_request(attempt, || {
+            .log_request(attempt - 1, || {
                 req_builder
                     .header(reqwest::header::ACCEPT, "text/event-stream")
                     .json(&payload)
                     .send()
             })
+            .instrument(request_span.clone())
             .await;
 
         match res {
             Ok(resp) if resp.status().is_success() => {
+                pacer.observe_headers(resp.headers());
                 let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent>>(1600);
                 let stream = resp.bytes_stream().map_err(|e| {
                     CodexErr::ResponseStreamFailed(ResponseStreamFailed {
@@ -317,12 +349,19 @@ pub(crate) async fn stream_chat_completions(
                         request_id: None,
                     })
                 });
-                tokio::spawn(process_chat_sse(
-                    stream,
-                    tx_event,
-                    provider.stream_idle_timeout(),
-                    otel_event_manager.clone(),
-                ));
+                let stream = match &fixture {
+                    Some(fixture) => Either::Left(fixture.record(stream)),
+                    None => Either::Right(stream),
+                };
+                tokio::spawn(
+                    process_chat_sse(
+                        stream,
+                        tx_event,
+                        provider.stream_idle_timeout(),
+                        otel_event_manager.clone(),
+                    )
+                    .instrument(request_span),
+                );
                 return Ok(ResponseStream { rx_event });
             }
             Ok(res) => {
@@ -352,6 +391,10 @@ pub(crate) async fn stream_chat_completions(
                 let delay = retry_after_secs
                     .map(|s| Duration::from_millis(s * 1_000))
                     .unwrap_or_else(|| backoff(attempt));
+                if status == StatusCode::TOO_MANY_REQUESTS {
+                    pacer.observe_headers(res.headers());
+                    pacer.block_for(delay);
+                }
                 tokio::time::sleep(delay).await;
             }
             Err(e) => {
@@ -13,6 +13,7 @@ use bytes::Bytes;
 use codex_app_server_protocol::AuthMode;
 use codex_protocol::ConversationId;
 use eventsource_stream::Eventsource;
+use futures::future::Either;
 use futures::prelude::*;
 use regex_lite::Regex;
 use reqwest::StatusCode;
@@ -20,13 +21,17 @@ use reqwest::header::HeaderMap;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value;
+use sha1::Digest;
+use sha1::Sha1;
 use tokio::sync::mpsc;
 use tokio::time::timeout;
 use tokio_util::io::ReaderStream;
+use tracing::Instrument;
 use tracing::debug;
 use tracing::trace;
 use tracing::warn;
 
+use crate::anthropic_messages::stream_anthropic_messages;
 use crate::chat_completions::AggregateStreamExt;
 use crate::chat_completions::stream_chat_completions;
 use crate::client_common::Prompt;
@@ -41,7 +46,9 @@ use crate::error::CodexErr;
 use crate::error::Result;
 use crate::error::UsageLimitReachedError;
 use crate::flags::CODEX_RS_SSE_FIXTURE;
+use crate::gemini::stream_gemini;
 use crate::model_family::ModelFamily;
+use crate::model_fixtures::Fixture;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::WireApi;
 use crate::openai_model_info::get_model_info;
@@ -49,15 +56,24 @@ use crate::openai_tools::create_tools_json_for_responses_api;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::RateLimitWindow;
 use crate::protocol::TokenUsage;
+use crate::rate_limit_governor::RateLimitGovernor;
+use crate::rate_limit_governor::RateLimitKey;
+use crate::rate_limit_governor::RateLimitPacer;
+use crate::rate_limit_governor::governor_for;
 use crate::state::TaskKind;
 use crate::token_data::PlanType;
 use crate::util::backoff;
+use crate::wire_stream::NativeRequest;
+use crate::wire_stream::WireTarget;
 use codex_otel::otel_event_manager::OtelEventManager;
 use codex_protocol::config_types::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
 use codex_protocol::models::ResponseItem;
 use std::sync::Arc;
 
+/// Output limit sent to native wire APIs when the model's is unknown.
+const DEFAULT_NATIVE_MAX_OUTPUT_TOKENS: u64 = 8_192;
+
 #[derive(Debug, Deserialize)]
 struct ErrorResponse {
     error: Error,
@@ -144,6 +160,8 @@ impl ModelClient {
                     &self.client,
                     &self.provider,
                     &self.otel_event_manager,
+                    &self.config.cwd,
+                    self.rate_limit_pacer(),
                 )
                 .await?;
 
@@ -172,6 +190,35 @@ impl ModelClient {
 
                 Ok(ResponseStream { rx_event: rx })
             }
+            WireApi::Anthropic => {
+                stream_anthropic_messages(self.native_request(prompt), self.wire_target()).await
+            }
+            WireApi::Gemini => stream_gemini(self.native_request(prompt), self.wire_target()).await,
+        }
+    }
+
+    fn native_request<'a>(&'a self, prompt: &'a Prompt) -> NativeRequest<'a> {
+        NativeRequest {
+            prompt,
+            model_family: &self.config.model_family,
+            effort: self.effort,
+            // Native APIs require an explicit output limit.
+            max_output_tokens: self
+                .config
+                .model_max_output_tokens
+                .unwrap_or(DEFAULT_NATIVE_MAX_OUTPUT_TOKENS)
+                .min(prompt.max_output_tokens.unwrap_or(u64::MAX)),
+        }
+    }
+
+    fn wire_target(&self) -> WireTarget<'_> {
+        WireTarget {
+            client: &self.client,
+            provider: &self.provider,
+            otel_event_manager: &self.otel_event_manager,
+            cwd: &self.config.cwd,
+            model: &self.config.model,
+            pacer: self.rate_limit_pacer(),
         }
     }
 
@@ -249,6 +296,7 @@ impl ModelClient {
             include,
             prompt_cache_key: Some(self.conversation_id.to_string()),
             text,
+            max_output_tokens: prompt.max_output_tokens,
         };
 
         let mut payload_json = serde_json::to_value(&payload)?;
@@ -256,10 +304,34 @@ impl ModelClient {
             attach_item_ids(&mut payload_json, &input_with_instructions);
         }
 
+        let fixture = self
+            .provider
+            .fixtures
+            .as_ref()
+            .map(|fixtures| Fixture::for_request(fixtures, &payload_json, &self.config.cwd));
+        if let Some(fixture) = fixture.as_ref().filter(|fixture| fixture.is_replay()) {
+            let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent>>(1600);
+            tokio::spawn(process_sse(
+                fixture.replay().await?,
+                tx_event,
+                self.provider.stream_idle_timeout(),
+                self.otel_event_manager.clone(),
+            ));
+            return Ok(ResponseStream { rx_event });
+        }
+
+        let governor = self.rate_limit_governor();
         let max_attempts = self.provider.request_max_retries();
         for attempt in 0..=max_attempts {
+            governor.acquire(&self.config.rate_limit_pacing).await;
             match self
-                .attempt_stream_responses(attempt, &payload_json, &auth_manager, task_kind)
+                .attempt_stream_responses(
+                    attempt,
+                    &payload_json,
+                    &auth_manager,
+                    task_kind,
+                    fixture.as_ref(),
+                )
                 .await
             {
                 Ok(stream) => {
@@ -273,7 +345,12 @@ impl ModelClient {
                         return Err(retryable_attempt_error.into_error());
                     }
 
-                    tokio::time::sleep(retryable_attempt_error.delay(attempt)).await;
+                    let delay = retryable_attempt_error.delay(attempt);
+                    if retryable_attempt_error.is_rate_limited() {
+                        // Hold back the other conversations sharing this quota too.
+                        governor.block_for(delay);
+                    }
+                    tokio::time::sleep(delay).await;
                 }
             }
         }
@@ -288,19 +365,20 @@ impl ModelClient {
         payload_json: &Value,
         auth_manager: &Option<Arc<AuthManager>>,
         task_kind: TaskKind,
+        fixture: Option<&Fixture>,
     ) -> std::result::Result<ResponseStream, StreamAttemptError> {
         // Always fetch the latest auth in case a prior attempt refreshed the token.
         let auth = auth_manager.as_ref().and_then(|m| m.auth());
 
         trace!(
             "POST to {}: {:?}",
-            self.provider.get_full_url(&auth),
+            self.provider.get_full_url(&auth, &self.config.model),
             serde_json::to_string(payload_json)
         );
 
         let mut req_builder = self
             .provider
-            .create_request_builder(&self.client, &auth)
+            .create_request_builder(&self.client, &auth, &self.config.model)
             .await
             .map_err(StreamAttemptError::Fatal)?;
 
@@ -320,9 +398,11 @@ impl ModelClient {
             req_builder = req_builder.header("chatgpt-account-id", account_id);
         }
 
+        let request_span = self.otel_event_manager.model_request_span(attempt);
         let res = self
             .otel_event_manager
             .log_request(attempt, || req_builder.send())
+            .instrument(request_span.clone())
             .await;
 
         let mut request_id = None;
@@ -343,7 +423,11 @@ impl ModelClient {
             Ok(resp) if resp.status().is_success() => {
                 let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent>>(1600);
 
-                if let Some(snapshot) = parse_rate_limit_snapshot(resp.headers())
+                let snapshot = parse_rate_limit_snapshot(resp.headers());
+                if let Some(snapshot) = snapshot.as_ref() {
+                    self.rate_limit_governor().observe(snapshot);
+                }
+                if let Some(snapshot) = snapshot
                     && tx_event
                         .send(Ok(ResponseEvent::RateLimits(snapshot)))
                         .await
@@ -359,12 +443,19 @@ impl ModelClient {
                         request_id: request_id.clone(),
                     })
                 });
-                tokio::spawn(process_sse(
-                    stream,
-                    tx_event,
-                    self.provider.stream_idle_timeout(),
-                    self.otel_event_manager.clone(),
-                ));
+                let stream = match fixture {
+                    Some(fixture) => Either::Left(fixture.record(stream)),
+                    None => Either::Right(stream),
+                };
+                tokio::spawn(
+                    process_sse(
+                        stream,
+                        tx_event,
+                        self.provider.stream_idle_timeout(),
+                        self.otel_event_manager.clone(),
+                    )
+                    .instrument(request_span),
+                );
 
                 Ok(ResponseStream { rx_event })
             }
@@ -410,6 +501,9 @@ impl ModelClient {
 
                 if status == StatusCode::TOO_MANY_REQUESTS {
                     let rate_limit_snapshot = parse_rate_limit_snapshot(res.headers());
+                    if let Some(snapshot) = rate_limit_snapshot.as_ref() {
+                        self.rate_limit_governor().observe(snapshot);
+                    }
                     let body = res.json::<ErrorResponse>().await.ok();
                     if let Some(ErrorResponse { error }) = body {
                         if error.r#type.as_deref() == Some("usage_limit_reached") {
@@ -448,6 +542,15 @@ impl ModelClient {
         self.provider.clone()
     }
 
+    pub(crate) fn get_config(&self) -> Arc<Config> {
+        Arc::clone(&self.config)
+    }
+
+    /// Returns the key of the provider in the `model_providers` map.
+    pub fn get_model_provider_id(&self) -> String {
+        self.config.model_provider_id.clone()
+    }
+
     pub fn get_otel_event_manager(&self) -> OtelEventManager {
         self.otel_event_manager.clone()
     }
@@ -475,6 +578,47 @@ impl ModelClient {
     pub fn get_auth_manager(&self) -> Option<Arc<AuthManager>> {
         self.auth_manager.clone()
     }
+
+    /// Governor shared by every client using the same provider and account.
+    pub(crate) fn rate_limit_governor(&self) -> Arc<RateLimitGovernor> {
+        let account = match self.provider.api_key() {
+            Ok(Some(api_key)) => Some(api_key_fingerprint(&api_key)),
+            _ => self
+                .auth_manager
+                .as_ref()
+                .and_then(|manager| manager.auth())
+                .and_then(|auth| match auth.mode {
+                    AuthMode::ChatGPT => auth.get_account_id(),
+                    AuthMode::ApiKey => auth.api_key.as_deref().map(api_key_fingerprint),
+                }),
+        };
+        governor_for(RateLimitKey {
+            provider: self.config.model_provider_id.clone(),
+            account: account.unwrap_or_default(),
+        })
+    }
+
+    /// The shared governor with this client's pacing settings, for the
+    /// request loops that live outside this file.
+    fn rate_limit_pacer(&self) -> RateLimitPacer {
+        RateLimitPacer {
+            governor: self.rate_limit_governor(),
+            pacing: self.config.rate_limit_pacing,
+        }
+    }
+
+    /// How long the next request will be held back by rate-limit pacing.
+    pub(crate) fn rate_limit_delay(&self) -> Option<Duration> {
+        self.rate_limit_governor()
+            .pending_delay(&self.config.rate_limit_pacing)
+    }
+}
+
+/// Identifies an API key without keeping the key itself around.
+fn api_key_fingerprint(api_key: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(api_key.as_bytes());
+    format!("{:x}", hasher.finalize())
 }
 
 enum StreamAttemptError {
@@ -504,6 +648,13 @@ impl StreamAttemptError {
         }
     }
 
+    fn is_rate_limited(&self) -> bool {
+        matches!(
+            self,
+            Self::RetryableHttpError { status, .. } if *status == StatusCode::TOO_MANY_REQUESTS
+        )
+    }
+
     fn into_error(self) -> CodexErr {
         match self {
             Self::RetryableHttpError {
@@ -600,7 +751,7 @@ fn attach_item_ids(payload_json: &mut Value, original_items: &[ResponseItem]) {
     }
 }
 
-fn parse_rate_limit_snapshot(headers: &HeaderMap) -> Option<RateLimitSnapshot> {
+pub(crate) fn parse_rate_limit_snapshot(headers: &HeaderMap) -> Option<RateLimitSnapshot> {
     let primary = parse_rate_limit_window(
         headers,
         "x-codex-primary-used-percent",
@@ -1095,6 +1246,7 @@ mod tests {
             stream_max_retries: Some(0),
             stream_idle_timeout_ms: Some(1000),
             requires_openai_auth: false,
+            fixtures: None,
         };
 
         let otel_event_manager = otel_event_manager();
@@ -1158,6 +1310,7 @@ mod tests {
             stream_max_retries: Some(0),
             stream_idle_timeout_ms: Some(1000),
             requires_openai_auth: false,
+            fixtures: None,
         };
 
         let otel_event_manager = otel_event_manager();
@@ -1194,6 +1347,7 @@ mod tests {
             stream_max_retries: Some(0),
             stream_idle_timeout_ms: Some(1000),
             requires_openai_auth: false,
+            fixtures: None,
         };
 
         let otel_event_manager = otel_event_manager();
@@ -1232,6 +1386,7 @@ mod tests {
             stream_max_retries: Some(0),
             stream_idle_timeout_ms: Some(1000),
             requires_openai_auth: false,
+            fixtures: None,
         };
 
         let otel_event_manager = otel_event_manager();
@@ -1266,6 +1421,7 @@ mod tests {
             stream_max_retries: Some(0),
             stream_idle_timeout_ms: Some(1000),
             requires_openai_auth: false,
+            fixtures: None,
         };
 
         let otel_event_manager = otel_event_manager();
@@ -1369,6 +1525,7 @@ mod tests {
                 stream_max_retries: Some(0),
                 stream_idle_timeout_ms: Some(1000),
                 requires_openai_auth: false,
+                fixtures: None,
             };
 
             let otel_event_manager = otel_event_manager();
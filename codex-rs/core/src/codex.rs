@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -29,12 +30,15 @@ use futures::future::BoxFuture;
 use futures::prelude::*;
 use futures::stream::FuturesOrdered;
 use mcp_types::CallToolResult;
+use mcp_types::ElicitResult;
 use mcp_types::ListResourceTemplatesRequestParams;
 use mcp_types::ListResourceTemplatesResult;
 use mcp_types::ListResourcesRequestParams;
 use mcp_types::ListResourcesResult;
 use mcp_types::ReadResourceRequestParams;
 use mcp_types::ReadResourceResult;
+use mcp_types::RequestId;
+use mcp_types::Tool;
 use serde_json;
 use serde_json::Value;
 use tokio::sync::Mutex;
@@ -54,6 +58,7 @@ use crate::client_common::ResponseEvent;
 use crate::config::Config;
 use crate::config_types::ShellEnvironmentPolicy;
 use crate::conversation_history::ConversationHistory;
+use crate::cost_tracker::CostTracker;
 use crate::environment_context::EnvironmentContext;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
@@ -68,8 +73,9 @@ use crate::executor::ExecutorConfig;
 use crate::executor::normalize_exec_result;
 use crate::mcp::auth::compute_auth_statuses;
 use crate::mcp_connection_manager::McpConnectionManager;
-use crate::model_family::find_family_for_model;
-use crate::openai_model_info::get_model_info;
+use crate::model_failover::failover_reason;
+use crate::model_failover::translate_history;
+use crate::model_family::derive_default_model_family;
 use crate::openai_tools::ToolsConfig;
 use crate::openai_tools::ToolsConfigParams;
 use crate::parse_command::parse_command;
@@ -81,6 +87,8 @@ use crate::protocol::AgentReasoningSectionBreakEvent;
 use crate::protocol::ApplyPatchApprovalRequestEvent;
 use crate::protocol::AskForApproval;
 use crate::protocol::BackgroundEventEvent;
+use crate::protocol::BudgetWarningEvent;
+use crate::protocol::ElicitationAction;
 use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
@@ -112,6 +120,7 @@ use crate::state::TaskKind;
 use crate::tasks::CompactTask;
 use crate::tasks::RegularTask;
 use crate::tasks::ReviewTask;
+use crate::tasks::build_review_prompt;
 use crate::tools::ToolRouter;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::format_exec_output_str;
@@ -132,7 +141,11 @@ use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::InitialHistory;
 
+mod checkpoint;
 pub mod compact;
+mod mcp_prompts;
+mod mcp_sampling;
+mod read_versions;
 use self::compact::build_compacted_history;
 use self::compact::collect_user_messages;
 
@@ -382,6 +395,7 @@ impl Session {
                 .features
                 .enabled(crate::features::Feature::RmcpClient),
             config.mcp_oauth_credentials_store_mode,
+            tx_event.clone(),
         );
         let default_shell_fut = shell::default_user_shell();
         let history_meta_fut = crate::message_history::history_metadata(&config);
@@ -513,12 +527,16 @@ impl Session {
             rollout: Mutex::new(Some(rollout_recorder)),
             user_shell: default_shell,
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
+            auto_checkpoints: config
+                .features
+                .enabled(crate::features::Feature::Checkpoints),
             executor: Executor::new(ExecutorConfig::new(
                 turn_context.sandbox_policy.clone(),
                 turn_context.cwd.clone(),
                 config.codex_linux_sandbox_exe.clone(),
             )),
             delegate_adapter,
+            cost_tracker: CostTracker::new(&config, &turn_context.cwd),
         };
 
         let sess = Arc::new(Session {
@@ -597,6 +615,8 @@ impl Session {
                     self.record_into_history(&reconstructed_history).await;
                 }
 
+                self.restore_checkpoints_from_rollout(&rollout_items).await;
+
                 // If persisting, persist all rollout items as-is (recorder filters)
                 if persist && !rollout_items.is_empty() {
                     self.persist_rollout_items(&rollout_items).await;
@@ -836,7 +856,23 @@ impl Session {
                 );
             }
         }
+        let budget_warnings = match token_usage {
+            Some(token_usage) => {
+                self.services
+                    .cost_tracker
+                    .record(&turn_context.client.get_model(), token_usage)
+                    .await
+            }
+            None => Vec::new(),
+        };
         self.send_token_count_event(sub_id).await;
+        for message in budget_warnings {
+            self.send_event(Event {
+                id: sub_id.to_string(),
+                msg: EventMsg::BudgetWarning(BudgetWarningEvent { message }),
+            })
+            .await;
+        }
     }
 
     async fn update_rate_limits(&self, sub_id: &str, new_rate_limits: RateLimitSnapshot) {
@@ -854,7 +890,11 @@ impl Session {
         };
         let event = Event {
             id: sub_id.to_string(),
-            msg: EventMsg::TokenCount(TokenCountEvent { info, rate_limits }),
+            msg: EventMsg::TokenCount(TokenCountEvent {
+                info,
+                rate_limits,
+                cost: self.services.cost_tracker.summary(),
+            }),
         };
         self.send_event(event).await;
     }
@@ -910,6 +950,7 @@ impl Session {
             Some(ApplyPatchCommandContext {
                 user_explicitly_approved_this_action,
                 changes,
+                ..
             }) => {
                 {
                     let mut tracker = turn_diff_tracker.lock().await;
@@ -942,7 +983,7 @@ impl Session {
         sub_id: &str,
         call_id: &str,
         output: &ExecToolCallOutput,
-        is_apply_patch: bool,
+        apply_patch: Option<&ApplyPatchCommandContext>,
     ) {
         let ExecToolCallOutput {
             stdout,
@@ -959,12 +1000,33 @@ impl Session {
         let formatted_output = format_exec_output_str(output);
         let aggregated_output: String = aggregated_output.text.clone();
 
-        let msg = if is_apply_patch {
+        let is_apply_patch = apply_patch.is_some();
+        let msg = if let Some(ApplyPatchCommandContext { affected, cwd, .. }) = apply_patch {
+            // A failed patch is rolled back, so nothing was committed unless
+            // the rollback itself failed; then the patch lists the files it
+            // left modified.
+            let committed = match *exit_code {
+                0 => affected.clone().into_paths(),
+                codex_apply_patch::INCOMPLETE_ROLLBACK_EXIT_CODE => {
+                    match codex_apply_patch::parse_incomplete_rollback(&stdout) {
+                        Some(left_modified) => left_modified
+                            .into_paths()
+                            .into_iter()
+                            .map(|path| cwd.join(path))
+                            .collect(),
+                        // Without the list, any of the patch's paths may
+                        // have been left modified.
+                        None => affected.clone().into_paths(),
+                    }
+                }
+                _ => Vec::new(),
+            };
             EventMsg::PatchApplyEnd(PatchApplyEndEvent {
                 call_id: call_id.to_string(),
                 stdout,
                 stderr,
                 success: *exit_code == 0,
+                committed,
             })
         } else {
             EventMsg::ExecCommandEnd(ExecCommandEndEvent {
@@ -1012,7 +1074,7 @@ impl Session {
         approval_policy: AskForApproval,
     ) -> Result<ExecToolCallOutput, ExecError> {
         let PreparedExec { context, request } = prepared;
-        let is_apply_patch = context.apply_patch.is_some();
+        let apply_patch = context.apply_patch.clone();
         let sub_id = context.sub_id.clone();
         let call_id = context.call_id.clone();
 
@@ -1046,7 +1108,7 @@ impl Session {
             &sub_id,
             &call_id,
             borrowed,
-            is_apply_patch,
+            apply_patch.as_ref(),
         )
         .await;
 
@@ -1068,6 +1130,113 @@ impl Session {
         self.send_event(event).await;
     }
 
+    /// Switches to the next `model_fallbacks` entry after `reason` made the
+    /// current provider give up. Returns the turn context to retry with, or
+    /// `None` once the chain is exhausted.
+    async fn fail_over(
+        &self,
+        sub_id: &str,
+        turn_context: &TurnContext,
+        reason: &str,
+    ) -> Option<Arc<TurnContext>> {
+        let config = turn_context.client.get_config();
+        let current_provider_id = turn_context.client.get_model_provider_id();
+        let current_model = turn_context.client.get_model();
+        let (index, fallback) = {
+            let state = self.state.lock().await;
+            config
+                .model_fallbacks
+                .iter()
+                .enumerate()
+                .skip(state.fallbacks_used)
+                .find(|(_, fallback)| {
+                    fallback.model_provider_id != current_provider_id
+                        || fallback.model != current_model
+                })
+                .map(|(index, fallback)| (index, fallback.clone()))?
+        };
+
+        let model_family = config
+            .find_model_family(&fallback.model)
+            .unwrap_or_else(|| derive_default_model_family(&fallback.model));
+        let limits = config.model_limits(&model_family);
+        let mut fallback_config = (*config).clone();
+        fallback_config.model = fallback.model.clone();
+        fallback_config.model_family = model_family.clone();
+        fallback_config.model_context_window = limits.context_window;
+        fallback_config.model_max_output_tokens = limits.max_output_tokens;
+        fallback_config.model_auto_compact_token_limit = limits.auto_compact_token_limit;
+        fallback_config.model_provider_id = fallback.model_provider_id.clone();
+        fallback_config.model_provider = fallback.model_provider.clone();
+
+        let otel_event_manager = turn_context
+            .client
+            .get_otel_event_manager()
+            .with_model(fallback.model.as_str(), model_family.slug.as_str());
+        let client = ModelClient::new(
+            Arc::new(fallback_config),
+            turn_context.client.get_auth_manager(),
+            otel_event_manager,
+            fallback.model_provider,
+            turn_context.client.get_reasoning_effort(),
+            turn_context.client.get_reasoning_summary(),
+            self.conversation_id,
+        );
+        let delegate_enabled = config.include_delegate_tool && self.delegate_adapter().is_some();
+        let next = Arc::new(TurnContext {
+            client,
+            tools_config: ToolsConfig::new(&ToolsConfigParams {
+                model_family: &model_family,
+                features: &config.features,
+                include_delegate_tool: delegate_enabled,
+            }),
+            cwd: turn_context.cwd.clone(),
+            base_instructions: turn_context.base_instructions.clone(),
+            user_instructions: turn_context.user_instructions.clone(),
+            approval_policy: turn_context.approval_policy,
+            sandbox_policy: turn_context.sandbox_policy.clone(),
+            shell_environment_policy: turn_context.shell_environment_policy.clone(),
+            is_review_mode: turn_context.is_review_mode,
+            final_output_json_schema: turn_context.final_output_json_schema.clone(),
+        });
+
+        {
+            let mut state = self.state.lock().await;
+            state.fallbacks_used = index + 1;
+            // Review threads keep their own history and never become the
+            // session's persistent context.
+            if !turn_context.is_review_mode {
+                if fallback.model_provider_id != current_provider_id {
+                    let history = state.history_snapshot();
+                    state.replace_history(translate_history(
+                        history,
+                        model_family.uses_local_shell_tool,
+                    ));
+                }
+                state.failover_turn_context = Some(Arc::clone(&next));
+            }
+        }
+
+        self.notify_background_event(
+            sub_id,
+            format!(
+                "{current_model} ({current_provider_id}): {reason}; switched to {} ({})",
+                fallback.model, fallback.model_provider_id
+            ),
+        )
+        .await;
+        Some(next)
+    }
+
+    /// Takes the turn context installed by the latest [`Session::fail_over`].
+    async fn take_failover_turn_context(&self) -> Option<Arc<TurnContext>> {
+        self.state.lock().await.failover_turn_context.take()
+    }
+
+    async fn has_failed_over(&self) -> bool {
+        self.state.lock().await.fallbacks_used > 0
+    }
+
     async fn notify_stream_error(&self, sub_id: &str, message: impl Into<String>) {
         let event = Event {
             id: sub_id.to_string(),
@@ -1157,6 +1326,40 @@ impl Session {
             .await
     }
 
+    /// Whether a call to `tool` on `server` must be approved by the user,
+    /// taking approvals granted for the rest of the session into account.
+    pub(crate) async fn mcp_tool_requires_approval(&self, server: &str, tool: &str) -> bool {
+        if !self
+            .services
+            .mcp_connection_manager
+            .tool_requires_approval(server, tool)
+        {
+            return false;
+        }
+        let state = self.state.lock().await;
+        !state
+            .approved_mcp_tools
+            .contains(&(server.to_string(), tool.to_string()))
+    }
+
+    pub(crate) async fn approve_mcp_tool_for_session(&self, server: &str, tool: &str) {
+        let mut state = self.state.lock().await;
+        state
+            .approved_mcp_tools
+            .insert((server.to_string(), tool.to_string()));
+    }
+
+    pub fn resolve_elicitation(
+        &self,
+        server_name: &str,
+        id: RequestId,
+        response: ElicitResult,
+    ) -> anyhow::Result<()> {
+        self.services
+            .mcp_connection_manager
+            .resolve_elicitation(server_name, id, response)
+    }
+
     pub(crate) fn parse_mcp_tool_name(&self, tool_name: &str) -> Option<(String, String)> {
         self.services
             .mcp_connection_manager
@@ -1226,9 +1429,33 @@ async fn submission_loop(
 ) {
     // Wrap once to avoid cloning TurnContext for each task.
     let mut turn_context = Arc::new(turn_context);
+    let mut rx_sampling = sess
+        .services
+        .mcp_connection_manager
+        .take_sampling_requests();
     // To break out of this loop, send Op::Shutdown.
-    while let Ok(sub) = rx_sub.recv().await {
+    loop {
+        let sub = tokio::select! {
+            sub = rx_sub.recv() => match sub {
+                Ok(sub) => sub,
+                Err(_) => break,
+            },
+            Some(request) = mcp_sampling::next_sampling_request(&mut rx_sampling) => {
+                // Sampling runs alongside any task, with the current settings.
+                let sess = Arc::clone(&sess);
+                let turn_context = Arc::clone(&turn_context);
+                tokio::spawn(async move {
+                    sess.handle_sampling_request(&turn_context, request).await;
+                });
+                continue;
+            }
+        };
         debug!(?sub, "Submission");
+        // A task that switched to a `model_fallbacks` entry keeps it for
+        // the rest of the session.
+        if let Some(failed_over) = sess.take_failover_turn_context().await {
+            turn_context = failed_over;
+        }
         match sub.op {
             Op::Interrupt => {
                 sess.interrupt_task().await;
@@ -1247,8 +1474,9 @@ async fn submission_loop(
 
                 // Effective model + family
                 let (effective_model, effective_family) = if let Some(ref m) = model {
-                    let fam =
-                        find_family_for_model(m).unwrap_or_else(|| config.model_family.clone());
+                    let fam = config
+                        .find_model_family(m)
+                        .unwrap_or_else(|| config.model_family.clone());
                     (m.clone(), fam)
                 } else {
                     (prev.client.get_model(), prev.client.get_model_family())
@@ -1262,10 +1490,13 @@ async fn submission_loop(
 
                 // Build updated config for the client
                 let mut updated_config = (*config).clone();
+                updated_config.model_provider_id = prev.client.get_model_provider_id();
+                updated_config.model_provider = provider.clone();
                 updated_config.model = effective_model.clone();
                 updated_config.model_family = effective_family.clone();
-                if let Some(model_info) = get_model_info(&effective_family) {
-                    updated_config.model_context_window = Some(model_info.context_window);
+                if let Some(context_window) = config.model_limits(&effective_family).context_window
+                {
+                    updated_config.model_context_window = Some(context_window);
                 }
 
                 let otel_event_manager = prev.client.get_otel_event_manager().with_model(
@@ -1357,16 +1588,28 @@ async fn submission_loop(
                     let provider = turn_context.client.get_provider();
                     let auth_manager = turn_context.client.get_auth_manager();
 
+                    // After switching to a fallback, keep its model unless
+                    // the client asked for a different one.
+                    let model = if model == config.model && sess.has_failed_over().await {
+                        turn_context.client.get_model()
+                    } else {
+                        model
+                    };
+
                     // Derive a model family for the requested model; fall back to the session's.
-                    let model_family = find_family_for_model(&model)
+                    let model_family = config
+                        .find_model_family(&model)
                         .unwrap_or_else(|| config.model_family.clone());
 
                     // Create a per‑turn Config clone with the requested model/family.
                     let mut per_turn_config = (*config).clone();
+                    per_turn_config.model_provider_id = turn_context.client.get_model_provider_id();
+                    per_turn_config.model_provider = provider.clone();
                     per_turn_config.model = model.clone();
                     per_turn_config.model_family = model_family.clone();
-                    if let Some(model_info) = get_model_info(&model_family) {
-                        per_turn_config.model_context_window = Some(model_info.context_window);
+                    if let Some(context_window) = config.model_limits(&model_family).context_window
+                    {
+                        per_turn_config.model_context_window = Some(context_window);
                     }
 
                     let otel_event_manager =
@@ -1445,6 +1688,23 @@ async fn submission_loop(
                 }
                 other => sess.notify_approval(&id, other).await,
             },
+            Op::ResolveElicitation {
+                server_name,
+                request_id,
+                decision,
+                content,
+            } => {
+                let response = ElicitResult {
+                    action: decision.to_string(),
+                    content: match decision {
+                        ElicitationAction::Accept => content,
+                        ElicitationAction::Decline | ElicitationAction::Cancel => None,
+                    },
+                };
+                if let Err(err) = sess.resolve_elicitation(&server_name, request_id, response) {
+                    warn!("failed to resolve elicitation request: {err:#}");
+                }
+            }
             Op::AddToHistory { text } => {
                 let id = sess.conversation_id;
                 let config = config.clone();
@@ -1512,28 +1772,77 @@ async fn submission_loop(
                             resources,
                             resource_templates,
                             auth_statuses,
+                            server_statuses: sess.services.mcp_connection_manager.server_statuses(),
                         },
                     ),
                 };
                 sess.send_event(event).await;
             }
             Op::ListCustomPrompts => {
+                let sess = Arc::clone(&sess);
                 let sub_id = sub.id.clone();
 
-                let custom_prompts: Vec<CustomPrompt> =
-                    if let Some(dir) = crate::custom_prompts::default_prompts_dir() {
-                        crate::custom_prompts::discover_prompts_in(&dir).await
-                    } else {
-                        Vec::new()
+                // Listing MCP prompts can wait on slow servers; keep the loop
+                // free to handle interrupts and approvals meanwhile.
+                tokio::spawn(async move {
+                    let custom_prompts: Vec<CustomPrompt> =
+                        if let Some(dir) = crate::custom_prompts::default_prompts_dir() {
+                            crate::custom_prompts::discover_prompts_in(&dir).await
+                        } else {
+                            Vec::new()
+                        };
+
+                    let mcp_prompts = sess
+                        .services
+                        .mcp_connection_manager
+                        .list_all_prompts()
+                        .await;
+
+                    let event = Event {
+                        id: sub_id,
+                        msg: EventMsg::ListCustomPromptsResponse(ListCustomPromptsResponseEvent {
+                            custom_prompts,
+                            mcp_prompts,
+                        }),
                     };
+                    sess.send_event(event).await;
+                });
+            }
+            Op::RunMcpPrompt {
+                server_name,
+                prompt_name,
+                arguments,
+            } => {
+                let sess = Arc::clone(&sess);
+                let turn_context = Arc::clone(&turn_context);
+                let sub_id = sub.id.clone();
 
-                let event = Event {
-                    id: sub_id,
-                    msg: EventMsg::ListCustomPromptsResponse(ListCustomPromptsResponseEvent {
-                        custom_prompts,
-                    }),
-                };
-                sess.send_event(event).await;
+                tokio::spawn(async move {
+                    let items = match sess
+                        .mcp_prompt_input(&server_name, &prompt_name, arguments)
+                        .await
+                    {
+                        Ok(items) => items,
+                        Err(err) => {
+                            let event = Event {
+                                id: sub_id,
+                                msg: EventMsg::Error(ErrorEvent {
+                                    message: format!("{err:#}"),
+                                }),
+                            };
+                            sess.send_event(event).await;
+                            return;
+                        }
+                    };
+                    turn_context
+                        .client
+                        .get_otel_event_manager()
+                        .user_prompt(&items);
+                    if let Err(items) = sess.inject_input(items).await {
+                        sess.spawn_task(turn_context, sub_id, items, RegularTask)
+                            .await;
+                    }
+                });
             }
             Op::Compact => {
                 // Attempt to inject input into current task
@@ -1614,6 +1923,15 @@ async fn submission_loop(
                 )
                 .await;
             }
+            Op::Checkpoint => {
+                sess.create_checkpoint(&turn_context, &sub.id).await;
+            }
+            Op::ListCheckpoints => {
+                sess.list_checkpoints(&sub.id).await;
+            }
+            Op::RestoreCheckpoint { checkpoint_id } => {
+                sess.restore_checkpoint(&sub.id, &checkpoint_id).await;
+            }
             _ => {
                 // Ignore unknown ops; enum is non_exhaustive to allow extensions.
             }
@@ -1631,7 +1949,8 @@ async fn spawn_review_thread(
     review_request: ReviewRequest,
 ) {
     let model = config.review_model.clone();
-    let review_model_family = find_family_for_model(&model)
+    let review_model_family = config
+        .find_model_family(&model)
         .unwrap_or_else(|| parent_turn_context.client.get_model_family());
     // For reviews, disable plan, web_search, view_image regardless of global settings.
     let mut review_features = config.features.clone();
@@ -1646,7 +1965,17 @@ async fn spawn_review_thread(
     });
 
     let base_instructions = REVIEW_PROMPT.to_string();
-    let review_prompt = review_request.prompt.clone();
+    let review_prompt = match build_review_prompt(&parent_turn_context.cwd, &review_request).await {
+        Ok(prompt) => prompt,
+        Err(message) => {
+            sess.send_event(Event {
+                id: sub_id,
+                msg: EventMsg::Error(ErrorEvent { message }),
+            })
+            .await;
+            return;
+        }
+    };
     let provider = parent_turn_context.client.get_provider();
     let auth_manager = parent_turn_context.client.get_auth_manager();
     let model_family = review_model_family.clone();
@@ -1657,8 +1986,8 @@ async fn spawn_review_thread(
     per_turn_config.model_family = model_family.clone();
     per_turn_config.model_reasoning_effort = Some(ReasoningEffortConfig::Low);
     per_turn_config.model_reasoning_summary = ReasoningSummaryConfig::Detailed;
-    if let Some(model_info) = get_model_info(&model_family) {
-        per_turn_config.model_context_window = Some(model_info.context_window);
+    if let Some(context_window) = config.model_limits(&model_family).context_window {
+        per_turn_config.model_context_window = Some(context_window);
     }
 
     let otel_event_manager = parent_turn_context
@@ -1730,7 +2059,7 @@ async fn spawn_review_thread(
 /// user_instructions. Emits ExitedReviewMode upon final review message.
 pub(crate) async fn run_task(
     sess: Arc<Session>,
-    turn_context: Arc<TurnContext>,
+    mut turn_context: Arc<TurnContext>,
     sub_id: String,
     input: Vec<InputItem>,
     task_kind: TaskKind,
@@ -1769,6 +2098,15 @@ pub(crate) async fn run_task(
     let mut auto_compact_recently_attempted = false;
 
     loop {
+        if let Some(message) = sess.services.cost_tracker.hard_budget_exceeded().await {
+            let event = Event {
+                id: sub_id.clone(),
+                msg: EventMsg::Error(ErrorEvent { message }),
+            };
+            sess.send_event(event).await;
+            break;
+        }
+
         // Note that pending_input would be something like a message the user
         // submitted through the UI while the model was running. Though the UI
         // may support this, the model might not.
@@ -1814,7 +2152,7 @@ pub(crate) async fn run_task(
             .collect();
         match run_turn(
             Arc::clone(&sess),
-            Arc::clone(&turn_context),
+            &mut turn_context,
             Arc::clone(&turn_diff_tracker),
             sub_id.clone(),
             turn_input,
@@ -2044,21 +2382,63 @@ fn parse_review_output_event(text: &str) -> ReviewOutputEvent {
     }
 }
 
+/// Runs one model turn, retrying stream failures and, once the provider's
+/// retry budget is spent, moving on to the next `model_fallbacks` entry.
+/// `turn_context` is replaced when that happens.
 async fn run_turn(
     sess: Arc<Session>,
-    turn_context: Arc<TurnContext>,
+    turn_context: &mut Arc<TurnContext>,
     turn_diff_tracker: SharedTurnDiffTracker,
     sub_id: String,
-    input: Vec<ResponseItem>,
+    mut input: Vec<ResponseItem>,
     task_kind: TaskKind,
     cancellation_token: CancellationToken,
 ) -> CodexResult<TurnRunResult> {
     let mcp_tools = sess.services.mcp_connection_manager.list_all_tools();
+    loop {
+        let err = match run_turn_with_retries(
+            Arc::clone(&sess),
+            Arc::clone(turn_context),
+            Arc::clone(&turn_diff_tracker),
+            &sub_id,
+            input.clone(),
+            mcp_tools.clone(),
+            task_kind,
+            &cancellation_token,
+        )
+        .await
+        {
+            Ok(output) => return Ok(output),
+            Err(err) => err,
+        };
+        let Some(reason) = failover_reason(&err) else {
+            return Err(err);
+        };
+        let Some(next) = sess.fail_over(&sub_id, turn_context, reason).await else {
+            return Err(err);
+        };
+        if next.client.get_model_provider_id() != turn_context.client.get_model_provider_id() {
+            input = translate_history(input, next.client.get_model_family().uses_local_shell_tool);
+        }
+        *turn_context = next;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_turn_with_retries(
+    sess: Arc<Session>,
+    turn_context: Arc<TurnContext>,
+    turn_diff_tracker: SharedTurnDiffTracker,
+    sub_id: &str,
+    input: Vec<ResponseItem>,
+    mcp_tools: HashMap<String, Tool>,
+    task_kind: TaskKind,
+    cancellation_token: &CancellationToken,
+) -> CodexResult<TurnRunResult> {
     let router = Arc::new(ToolRouter::from_config(
         &turn_context.tools_config,
         Some(mcp_tools),
     ));
-
     let model_supports_parallel = turn_context
         .client
         .get_model_family()
@@ -2070,6 +2450,7 @@ async fn run_turn(
         parallel_tool_calls,
         base_instructions_override: turn_context.base_instructions.clone(),
         output_schema: turn_context.final_output_json_schema.clone(),
+        max_output_tokens: None,
     };
 
     let mut retries = 0;
@@ -2079,7 +2460,7 @@ async fn run_turn(
             Arc::clone(&sess),
             Arc::clone(&turn_context),
             Arc::clone(&turn_diff_tracker),
-            &sub_id,
+            sub_id,
             &prompt,
             task_kind,
             cancellation_token.child_token(),
@@ -2091,14 +2472,16 @@ async fn run_turn(
             Err(CodexErr::Interrupted) => return Err(CodexErr::Interrupted),
             Err(CodexErr::EnvVar(var)) => return Err(CodexErr::EnvVar(var)),
             Err(e @ CodexErr::Fatal(_)) => return Err(e),
+            // A replay miss stays a miss; retrying only delays the failure.
+            Err(e @ CodexErr::FixtureNotFound(_)) => return Err(e),
             Err(e @ CodexErr::ContextWindowExceeded) => {
-                sess.set_total_tokens_full(&sub_id, &turn_context).await;
+                sess.set_total_tokens_full(sub_id, &turn_context).await;
                 return Err(e);
             }
             Err(CodexErr::UsageLimitReached(e)) => {
                 let rate_limits = e.rate_limits.clone();
                 if let Some(rate_limits) = rate_limits {
-                    sess.update_rate_limits(&sub_id, rate_limits).await;
+                    sess.update_rate_limits(sub_id, rate_limits).await;
                 }
                 return Err(CodexErr::UsageLimitReached(e));
             }
@@ -2109,7 +2492,12 @@ async fn run_turn(
                 if retries < max_retries {
                     retries += 1;
                     let delay = match e {
-                        CodexErr::Stream(_, Some(delay)) => delay,
+                        CodexErr::Stream(_, Some(delay)) => {
+                            // Rate limited mid-stream: hold back the other
+                            // conversations sharing this quota as well.
+                            turn_context.client.rate_limit_governor().block_for(delay);
+                            delay
+                        }
                         _ => backoff(retries),
                     };
                     warn!(
@@ -2120,7 +2508,7 @@ async fn run_turn(
                     // user understands what is happening instead of staring
                     // at a seemingly frozen screen.
                     sess.notify_stream_error(
-                        &sub_id,
+                        sub_id,
                         format!("Re-connecting... {retries}/{max_retries}"),
                     )
                     .await;
@@ -2221,10 +2609,21 @@ async fn try_run_turn(
         approval_policy: turn_context.approval_policy,
         sandbox_policy: turn_context.sandbox_policy.clone(),
         model: turn_context.client.get_model(),
+        model_provider: Some(turn_context.client.get_model_provider_id()),
         effort: turn_context.client.get_reasoning_effort(),
         summary: turn_context.client.get_reasoning_summary(),
     });
     sess.persist_rollout_items(&[rollout_item]).await;
+    if let Some(delay) = turn_context.client.rate_limit_delay() {
+        sess.notify_background_event(
+            sub_id,
+            format!(
+                "Close to the rate limit; waiting {}s before the next request.",
+                delay.as_secs().max(1)
+            ),
+        )
+        .await;
+    }
     let mut stream = turn_context
         .client
         .clone()
@@ -2898,12 +3297,16 @@ mod tests {
             rollout: Mutex::new(None),
             user_shell: shell::Shell::Unknown,
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
+            auto_checkpoints: config
+                .features
+                .enabled(crate::features::Feature::Checkpoints),
             executor: Executor::new(ExecutorConfig::new(
                 turn_context.sandbox_policy.clone(),
                 turn_context.cwd.clone(),
                 None,
             )),
             delegate_adapter: None,
+            cost_tracker: CostTracker::new(&config, &turn_context.cwd),
         };
         let session = Session {
             conversation_id,
@@ -2968,12 +3371,16 @@ mod tests {
             rollout: Mutex::new(None),
             user_shell: shell::Shell::Unknown,
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
+            auto_checkpoints: config
+                .features
+                .enabled(crate::features::Feature::Checkpoints),
             executor: Executor::new(ExecutorConfig::new(
                 config.sandbox_policy.clone(),
                 config.cwd.clone(),
                 None,
             )),
             delegate_adapter: None,
+            cost_tracker: CostTracker::new(&config, &config.cwd),
         };
         let session = Arc::new(Session {
             conversation_id,
@@ -3285,7 +3692,6 @@ mod tests {
         use crate::protocol::AskForApproval;
         use crate::protocol::SandboxPolicy;
         use crate::turn_diff_tracker::TurnDiffTracker;
-        use std::collections::HashMap;
 
         let (session, mut turn_context_raw) = make_session_and_context();
         // Ensure policy is NOT OnRequest so the early rejection path triggers
@@ -0,0 +1,176 @@
+//! Workspace checkpoints backed by git ghost commits.
+//!
+//! A checkpoint captures the working tree (including untracked files) in a
+//! commit that is not reachable from any ref, so it never shows up in the
+//! user's history. Checkpoints are announced with
+//! [`EventMsg::CheckpointCreated`], which is persisted in the rollout so that
+//! resumed sessions can still restore them.
+
+use std::path::PathBuf;
+
+use chrono::SecondsFormat;
+use chrono::Utc;
+use codex_git_tooling::CreateGhostCommitOptions;
+use codex_git_tooling::GitToolingError;
+use codex_git_tooling::create_ghost_commit;
+use codex_git_tooling::restore_to_commit;
+use codex_protocol::protocol::Checkpoint;
+use codex_protocol::protocol::CheckpointCreatedEvent;
+use codex_protocol::protocol::CheckpointRestoredEvent;
+use codex_protocol::protocol::ListCheckpointsResponseEvent;
+use codex_protocol::protocol::RolloutItem;
+use tracing::warn;
+
+use super::Session;
+use super::TurnContext;
+use crate::protocol::ErrorEvent;
+use crate::protocol::Event;
+use crate::protocol::EventMsg;
+
+/// Maximum number of checkpoints kept per session; older ones are forgotten.
+const MAX_TRACKED_CHECKPOINTS: usize = 50;
+
+impl Session {
+    /// Capture a checkpoint ahead of a regular turn when automatic
+    /// checkpoints are enabled. Failures disable automatic checkpoints for the
+    /// rest of the session instead of failing the turn.
+    pub(crate) async fn capture_turn_checkpoint(&self, turn_context: &TurnContext, sub_id: &str) {
+        if !self.services.auto_checkpoints || self.state.lock().await.checkpoints_disabled {
+            return;
+        }
+
+        match take_checkpoint(turn_context.cwd.clone(), sub_id.to_string()).await {
+            Ok(checkpoint) => self.on_checkpoint_created(sub_id, checkpoint).await,
+            Err(err) => {
+                warn!("failed to create checkpoint: {err}");
+                self.state.lock().await.checkpoints_disabled = true;
+                let message = match err {
+                    GitToolingError::NotAGitRepository { .. } => {
+                        "Checkpoints disabled: current directory is not a Git repository."
+                            .to_string()
+                    }
+                    err => format!("Checkpoints disabled after error: {err}"),
+                };
+                self.notify_background_event(sub_id, message).await;
+            }
+        }
+    }
+
+    /// Handle an explicit `Op::Checkpoint` request.
+    pub(crate) async fn create_checkpoint(&self, turn_context: &TurnContext, sub_id: &str) {
+        match take_checkpoint(turn_context.cwd.clone(), sub_id.to_string()).await {
+            Ok(checkpoint) => self.on_checkpoint_created(sub_id, checkpoint).await,
+            Err(err) => {
+                self.send_checkpoint_error(sub_id, format!("Failed to create checkpoint: {err}"))
+                    .await;
+            }
+        }
+    }
+
+    /// Handle an `Op::ListCheckpoints` request.
+    pub(crate) async fn list_checkpoints(&self, sub_id: &str) {
+        let checkpoints = self.state.lock().await.checkpoints();
+        let event = Event {
+            id: sub_id.to_string(),
+            msg: EventMsg::ListCheckpointsResponse(ListCheckpointsResponseEvent { checkpoints }),
+        };
+        self.send_event(event).await;
+    }
+
+    /// Handle an `Op::RestoreCheckpoint` request. Any recorded checkpoint can
+    /// be restored, not only the most recent one. The restored checkpoint and
+    /// every later one are forgotten, since they describe discarded states.
+    pub(crate) async fn restore_checkpoint(&self, sub_id: &str, checkpoint_id: &str) {
+        if self.active_turn.lock().await.is_some() {
+            self.send_checkpoint_error(
+                sub_id,
+                "Cannot restore a checkpoint while a turn is running.".to_string(),
+            )
+            .await;
+            return;
+        }
+
+        let Some(checkpoint) = self.state.lock().await.find_checkpoint(checkpoint_id) else {
+            self.send_checkpoint_error(sub_id, format!("Unknown checkpoint: {checkpoint_id}"))
+                .await;
+            return;
+        };
+
+        let cwd = checkpoint.cwd.clone();
+        let commit_id = checkpoint.id.clone();
+        let result =
+            tokio::task::spawn_blocking(move || restore_to_commit(cwd.as_path(), &commit_id))
+                .await
+                .unwrap_or_else(|err| Err(GitToolingError::Io(std::io::Error::other(err))));
+        match result {
+            Ok(()) => {
+                self.state
+                    .lock()
+                    .await
+                    .forget_checkpoints_from(&checkpoint.id);
+                let event = Event {
+                    id: sub_id.to_string(),
+                    msg: EventMsg::CheckpointRestored(CheckpointRestoredEvent { checkpoint }),
+                };
+                self.send_event(event).await;
+            }
+            Err(err) => {
+                self.send_checkpoint_error(sub_id, format!("Failed to restore checkpoint: {err}"))
+                    .await;
+            }
+        }
+    }
+
+    /// Seed the in-memory checkpoint list from a resumed or forked rollout.
+    pub(crate) async fn restore_checkpoints_from_rollout(&self, rollout_items: &[RolloutItem]) {
+        let mut state = self.state.lock().await;
+        for item in rollout_items {
+            match item {
+                RolloutItem::EventMsg(EventMsg::CheckpointCreated(event)) => {
+                    state.record_checkpoint(event.checkpoint.clone(), MAX_TRACKED_CHECKPOINTS);
+                }
+                RolloutItem::EventMsg(EventMsg::CheckpointRestored(event)) => {
+                    state.forget_checkpoints_from(&event.checkpoint.id);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    async fn on_checkpoint_created(&self, sub_id: &str, checkpoint: Checkpoint) {
+        self.state
+            .lock()
+            .await
+            .record_checkpoint(checkpoint.clone(), MAX_TRACKED_CHECKPOINTS);
+        let event = Event {
+            id: sub_id.to_string(),
+            msg: EventMsg::CheckpointCreated(CheckpointCreatedEvent { checkpoint }),
+        };
+        self.send_event(event).await;
+    }
+
+    async fn send_checkpoint_error(&self, sub_id: &str, message: String) {
+        let event = Event {
+            id: sub_id.to_string(),
+            msg: EventMsg::Error(ErrorEvent { message }),
+        };
+        self.send_event(event).await;
+    }
+}
+
+/// Create a ghost commit for `cwd` on a blocking thread.
+async fn take_checkpoint(cwd: PathBuf, turn_id: String) -> Result<Checkpoint, GitToolingError> {
+    tokio::task::spawn_blocking(move || {
+        let options = CreateGhostCommitOptions::new(cwd.as_path());
+        let commit = create_ghost_commit(&options)?;
+        Ok(Checkpoint {
+            id: commit.id().to_string(),
+            parent: commit.parent().map(str::to_string),
+            turn_id,
+            cwd,
+            created_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        })
+    })
+    .await
+    .map_err(|err| GitToolingError::Io(std::io::Error::other(err)))?
+}
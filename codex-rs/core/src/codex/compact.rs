@@ -84,6 +84,7 @@ async fn run_compact_task_inner(
         approval_policy: turn_context.approval_policy,
         sandbox_policy: turn_context.sandbox_policy.clone(),
         model: turn_context.client.get_model(),
+        model_provider: Some(turn_context.client.get_model_provider_id()),
         effort: turn_context.client.get_reasoning_effort(),
         summary: turn_context.client.get_reasoning_summary(),
     });
@@ -132,7 +133,7 @@ async fn run_compact_task_inner(
                 return;
             }
             Err(e) => {
-                if retries < max_retries {
+                if retries < max_retries && !matches!(e, CodexErr::FixtureNotFound(_)) {
                     retries += 1;
                     let delay = backoff(retries);
                     sess.notify_stream_error(
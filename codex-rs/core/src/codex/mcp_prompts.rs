@@ -0,0 +1,132 @@
+//! Expands MCP prompts (`prompts/get`) into user input.
+//!
+//! MCP prompts are exposed in the TUI as `/server:prompt` slash commands.
+//! Whatever the server returns is submitted as the user's turn, regardless of
+//! the role the server assigned to each message.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use mcp_types::ContentBlock;
+use mcp_types::EmbeddedResourceResource;
+use mcp_types::PromptMessage;
+
+use super::Session;
+use crate::protocol::InputItem;
+
+impl Session {
+    /// Fetch `prompt_name` from `server_name` and convert its messages into
+    /// input items for a user turn.
+    pub(crate) async fn mcp_prompt_input(
+        &self,
+        server_name: &str,
+        prompt_name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<Vec<InputItem>> {
+        let result = self
+            .services
+            .mcp_connection_manager
+            .get_prompt(server_name, prompt_name, arguments)
+            .await?;
+        Ok(prompt_messages_to_input(result.messages))
+    }
+}
+
+fn prompt_messages_to_input(messages: Vec<PromptMessage>) -> Vec<InputItem> {
+    messages
+        .into_iter()
+        .filter_map(|message| match message.content {
+            ContentBlock::TextContent(text) => Some(InputItem::Text { text: text.text }),
+            ContentBlock::ImageContent(image) => Some(InputItem::Image {
+                image_url: format!("data:{};base64,{}", image.mime_type, image.data),
+            }),
+            ContentBlock::EmbeddedResource(embedded) => match embedded.resource {
+                EmbeddedResourceResource::TextResourceContents(resource) => Some(InputItem::Text {
+                    text: resource.text,
+                }),
+                EmbeddedResourceResource::BlobResourceContents(resource) => Some(InputItem::Text {
+                    text: format!("<binary resource {}>", resource.uri),
+                }),
+            },
+            ContentBlock::ResourceLink(link) => Some(InputItem::Text { text: link.uri }),
+            ContentBlock::AudioContent(_) => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_types::AudioContent;
+    use mcp_types::EmbeddedResource;
+    use mcp_types::ImageContent;
+    use mcp_types::Role;
+    use mcp_types::TextContent;
+    use mcp_types::TextResourceContents;
+    use pretty_assertions::assert_eq;
+
+    fn message(role: Role, content: ContentBlock) -> PromptMessage {
+        PromptMessage { content, role }
+    }
+
+    #[test]
+    fn converts_prompt_messages_to_user_input() {
+        let messages = vec![
+            message(
+                Role::User,
+                ContentBlock::TextContent(TextContent {
+                    annotations: None,
+                    text: "Review this file".to_string(),
+                    r#type: "text".to_string(),
+                }),
+            ),
+            message(
+                Role::Assistant,
+                ContentBlock::EmbeddedResource(EmbeddedResource {
+                    annotations: None,
+                    resource: EmbeddedResourceResource::TextResourceContents(
+                        TextResourceContents {
+                            mime_type: None,
+                            text: "fn main() {}".to_string(),
+                            uri: "file:///main.rs".to_string(),
+                        },
+                    ),
+                    r#type: "resource".to_string(),
+                }),
+            ),
+            message(
+                Role::User,
+                ContentBlock::ImageContent(ImageContent {
+                    annotations: None,
+                    data: "AAAA".to_string(),
+                    mime_type: "image/png".to_string(),
+                    r#type: "image".to_string(),
+                }),
+            ),
+            message(
+                Role::User,
+                ContentBlock::AudioContent(AudioContent {
+                    annotations: None,
+                    data: "AAAA".to_string(),
+                    mime_type: "audio/wav".to_string(),
+                    r#type: "audio".to_string(),
+                }),
+            ),
+        ];
+
+        assert_eq!(
+            prompt_messages_to_input(messages),
+            vec![
+                InputItem::Text {
+                    text: "Review this file".to_string(),
+                },
+                InputItem::Text {
+                    text: "fn main() {}".to_string(),
+                },
+                InputItem::Image {
+                    image_url: "data:image/png;base64,AAAA".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,488 @@
+//! Answers MCP `sampling/createMessage` requests with the session's model.
+//!
+//! Only servers with a `sampling_token_budget` are offered sampling. Every
+//! request must be approved by the user and is charged against the server's
+//! budget; the tokens also count towards the session's token usage.
+//!
+//! Requests run concurrently, so each one reserves its worst case (estimated
+//! input plus its output limit) up front and settles the real usage once the
+//! response completes.
+
+use anyhow::Result;
+use anyhow::bail;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use futures::prelude::*;
+use mcp_types::CreateMessageRequestParams;
+use mcp_types::CreateMessageResult;
+use mcp_types::CreateMessageResultContent;
+use mcp_types::Role;
+use mcp_types::SamplingMessageContent;
+use mcp_types::TextContent;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use super::Session;
+use super::TurnContext;
+use crate::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::mcp_connection_manager::SamplingRequest;
+use crate::protocol::AskForApproval;
+use crate::protocol::ReviewDecision;
+
+/// Instructions used when the server does not send a system prompt.
+const DEFAULT_SAMPLING_INSTRUCTIONS: &str =
+    "You are answering a request from a tool. Reply to the conversation below.";
+
+/// MCP's name for a response that ended normally.
+const STOP_REASON_END_TURN: &str = "endTurn";
+
+/// MCP's name for a response cut off by its output limit.
+const STOP_REASON_MAX_TOKENS: &str = "maxTokens";
+
+/// Rough token cost of one image in a sampling request.
+const IMAGE_TOKEN_ESTIMATE: u64 = 1_000;
+
+/// Budget set aside for one sampling request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SamplingReservation {
+    /// Budget left before this request.
+    remaining: u64,
+    /// Output limit to send with the request.
+    max_output_tokens: u64,
+    /// Tokens held back: estimated input plus `max_output_tokens`.
+    reserved: u64,
+}
+
+/// The next queued sampling request; never resolves when sampling is not
+/// available.
+pub(super) async fn next_sampling_request(
+    rx_sampling: &mut Option<mpsc::UnboundedReceiver<SamplingRequest>>,
+) -> Option<SamplingRequest> {
+    match rx_sampling {
+        Some(rx_sampling) => rx_sampling.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+impl Session {
+    /// Answer `request` and report the outcome back to the server.
+    pub(crate) async fn handle_sampling_request(
+        &self,
+        turn_context: &TurnContext,
+        request: SamplingRequest,
+    ) {
+        let SamplingRequest {
+            server_name,
+            params,
+            token_budget,
+            responder,
+        } = request;
+        let result = self
+            .sample(turn_context, &server_name, params, token_budget)
+            .await;
+        if let Err(err) = &result {
+            warn!("sampling request from MCP server `{server_name}` failed: {err:#}");
+        }
+        let _ = responder.send(result);
+    }
+
+    async fn sample(
+        &self,
+        turn_context: &TurnContext,
+        server_name: &str,
+        params: CreateMessageRequestParams,
+        token_budget: u64,
+    ) -> Result<CreateMessageResult> {
+        let input_tokens = estimate_input_tokens(&params);
+        let reservation = self
+            .reserve_sampling_tokens(server_name, input_tokens, params.max_tokens, token_budget)
+            .await?;
+
+        if turn_context.approval_policy == AskForApproval::Never {
+            self.settle_sampling_tokens(server_name, reservation.reserved, 0)
+                .await;
+            bail!("sampling requires approval, but the approval policy does not allow asking");
+        }
+
+        let sub_id = self.next_internal_sub_id();
+        let decision = self
+            .request_command_approval(
+                sub_id.clone(),
+                sub_id.clone(),
+                vec![
+                    "mcp".to_string(),
+                    "sample".to_string(),
+                    server_name.to_string(),
+                ],
+                turn_context.cwd.clone(),
+                Some(sampling_approval_reason(
+                    server_name,
+                    &params,
+                    reservation.remaining,
+                )),
+            )
+            .await;
+        if !matches!(
+            decision,
+            ReviewDecision::Approved | ReviewDecision::ApprovedForSession
+        ) {
+            self.settle_sampling_tokens(server_name, reservation.reserved, 0)
+                .await;
+            bail!("sampling request rejected by user");
+        }
+
+        // From here on tokens may have been spent, so a failed request keeps
+        // its reservation.
+        let max_output_tokens = reservation.max_output_tokens;
+        let mut prompt = sampling_prompt(params);
+        prompt.max_output_tokens = Some(max_output_tokens);
+        let mut stream = turn_context.client.clone().stream(&prompt).await?;
+        let mut text = Vec::new();
+        let token_usage = loop {
+            let Some(event) = stream.next().await else {
+                bail!("stream closed before response.completed");
+            };
+            match event? {
+                ResponseEvent::OutputItemDone(ResponseItem::Message { role, content, .. })
+                    if role == "assistant" =>
+                {
+                    text.extend(content.into_iter().filter_map(|item| match item {
+                        ContentItem::OutputText { text } => Some(text),
+                        _ => None,
+                    }));
+                }
+                ResponseEvent::RateLimits(snapshot) => {
+                    self.update_rate_limits(&sub_id, snapshot).await;
+                }
+                ResponseEvent::Completed { token_usage, .. } => break token_usage,
+                _ => {}
+            }
+        };
+
+        let hit_output_limit = token_usage
+            .as_ref()
+            .is_some_and(|usage| usage.output_tokens >= max_output_tokens);
+        if let Some(usage) = &token_usage {
+            self.settle_sampling_tokens(server_name, reservation.reserved, usage.total_tokens)
+                .await;
+        }
+        self.update_token_usage_info(&sub_id, turn_context, token_usage.as_ref())
+            .await;
+
+        Ok(CreateMessageResult {
+            content: CreateMessageResultContent::TextContent(TextContent {
+                annotations: None,
+                text: text.join("\n"),
+                r#type: "text".to_string(),
+            }),
+            model: turn_context.client.get_model(),
+            role: Role::Assistant,
+            stop_reason: Some(
+                if hit_output_limit {
+                    STOP_REASON_MAX_TOKENS
+                } else {
+                    STOP_REASON_END_TURN
+                }
+                .to_string(),
+            ),
+        })
+    }
+
+    /// Set aside budget for a request with `input_tokens` of input asking
+    /// for up to `max_tokens` of output, capping the output to what is left.
+    async fn reserve_sampling_tokens(
+        &self,
+        server_name: &str,
+        input_tokens: u64,
+        max_tokens: i64,
+        token_budget: u64,
+    ) -> Result<SamplingReservation> {
+        let mut state = self.state.lock().await;
+        let used = state
+            .sampling_tokens_used
+            .entry(server_name.to_string())
+            .or_default();
+        let remaining = token_budget.saturating_sub(*used);
+        if remaining == 0 {
+            bail!(
+                "MCP server `{server_name}` has used its sampling budget of {token_budget} tokens"
+            );
+        }
+        let max_output_tokens =
+            sampling_output_limit(max_tokens, remaining.saturating_sub(input_tokens));
+        if max_output_tokens == 0 {
+            bail!(
+                "sampling request needs about {input_tokens} input tokens plus at least one output token, but MCP server `{server_name}` has {remaining} tokens left"
+            );
+        }
+        let reserved = input_tokens + max_output_tokens;
+        *used += reserved;
+        Ok(SamplingReservation {
+            remaining,
+            max_output_tokens,
+            reserved,
+        })
+    }
+
+    /// Replace a reservation of `reserved` tokens with the `spent` ones.
+    async fn settle_sampling_tokens(&self, server_name: &str, reserved: u64, spent: u64) {
+        let mut state = self.state.lock().await;
+        let used = state
+            .sampling_tokens_used
+            .entry(server_name.to_string())
+            .or_default();
+        *used = used.saturating_sub(reserved).saturating_add(spent);
+    }
+}
+
+/// Rough input size of a request, at about four bytes per token.
+fn estimate_input_tokens(params: &CreateMessageRequestParams) -> u64 {
+    let text_bytes: usize = params
+        .messages
+        .iter()
+        .filter_map(|message| match &message.content {
+            SamplingMessageContent::TextContent(text) => Some(text.text.len()),
+            _ => None,
+        })
+        .sum::<usize>()
+        + params
+            .system_prompt
+            .as_deref()
+            .unwrap_or(DEFAULT_SAMPLING_INSTRUCTIONS)
+            .len();
+    let images = params
+        .messages
+        .iter()
+        .filter(|message| matches!(message.content, SamplingMessageContent::ImageContent(_)))
+        .count();
+    (text_bytes as u64).div_ceil(4) + images as u64 * IMAGE_TOKEN_ESTIMATE
+}
+
+/// Output limit for a request: what the server asked for, but never more
+/// than is left of its budget.
+fn sampling_output_limit(max_tokens: i64, remaining_budget: u64) -> u64 {
+    u64::try_from(max_tokens)
+        .unwrap_or_default()
+        .min(remaining_budget)
+}
+
+fn sampling_approval_reason(
+    server_name: &str,
+    params: &CreateMessageRequestParams,
+    remaining_tokens: u64,
+) -> String {
+    let request = params
+        .messages
+        .iter()
+        .rev()
+        .find_map(|message| match &message.content {
+            SamplingMessageContent::TextContent(text) => Some(text.text.as_str()),
+            _ => None,
+        })
+        .unwrap_or_default();
+    format!(
+        "MCP server `{server_name}` wants to use the model ({remaining_tokens} tokens left in its budget): {request}"
+    )
+}
+
+/// Turn the server's messages into a prompt without tools. Audio is not
+/// supported by the model and is dropped.
+fn sampling_prompt(params: CreateMessageRequestParams) -> Prompt {
+    let input = params
+        .messages
+        .into_iter()
+        .filter_map(|message| {
+            let (role, content) = match (message.role, message.content) {
+                (Role::User, SamplingMessageContent::TextContent(text)) => {
+                    ("user", ContentItem::InputText { text: text.text })
+                }
+                (Role::Assistant, SamplingMessageContent::TextContent(text)) => {
+                    ("assistant", ContentItem::OutputText { text: text.text })
+                }
+                (Role::User, SamplingMessageContent::ImageContent(image)) => (
+                    "user",
+                    ContentItem::InputImage {
+                        image_url: format!("data:{};base64,{}", image.mime_type, image.data),
+                    },
+                ),
+                (Role::Assistant, SamplingMessageContent::ImageContent(_))
+                | (_, SamplingMessageContent::AudioContent(_)) => return None,
+            };
+            Some(ResponseItem::Message {
+                id: None,
+                role: role.to_string(),
+                content: vec![content],
+            })
+        })
+        .collect();
+    Prompt {
+        input,
+        base_instructions_override: Some(
+            params
+                .system_prompt
+                .unwrap_or_else(|| DEFAULT_SAMPLING_INSTRUCTIONS.to_string()),
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codex::make_session_and_context;
+    use mcp_types::AudioContent;
+    use mcp_types::SamplingMessage;
+    use pretty_assertions::assert_eq;
+    use std::sync::Arc;
+
+    fn text(role: Role, text: &str) -> SamplingMessage {
+        SamplingMessage {
+            content: SamplingMessageContent::TextContent(TextContent {
+                annotations: None,
+                text: text.to_string(),
+                r#type: "text".to_string(),
+            }),
+            role,
+        }
+    }
+
+    fn params(messages: Vec<SamplingMessage>) -> CreateMessageRequestParams {
+        CreateMessageRequestParams {
+            include_context: None,
+            max_tokens: 100,
+            messages,
+            metadata: None,
+            model_preferences: None,
+            stop_sequences: None,
+            system_prompt: None,
+            temperature: None,
+        }
+    }
+
+    #[test]
+    fn sampling_prompt_keeps_roles_and_drops_audio() {
+        let prompt = sampling_prompt(params(vec![
+            text(Role::User, "Summarize this"),
+            text(Role::Assistant, "Sure"),
+            SamplingMessage {
+                content: SamplingMessageContent::AudioContent(AudioContent {
+                    annotations: None,
+                    data: "AAAA".to_string(),
+                    mime_type: "audio/wav".to_string(),
+                    r#type: "audio".to_string(),
+                }),
+                role: Role::User,
+            },
+        ]));
+
+        assert_eq!(
+            prompt.input,
+            vec![
+                ResponseItem::Message {
+                    id: None,
+                    role: "user".to_string(),
+                    content: vec![ContentItem::InputText {
+                        text: "Summarize this".to_string(),
+                    }],
+                },
+                ResponseItem::Message {
+                    id: None,
+                    role: "assistant".to_string(),
+                    content: vec![ContentItem::OutputText {
+                        text: "Sure".to_string(),
+                    }],
+                },
+            ]
+        );
+        assert_eq!(
+            prompt.base_instructions_override.as_deref(),
+            Some(DEFAULT_SAMPLING_INSTRUCTIONS)
+        );
+        assert!(prompt.tools.is_empty());
+    }
+
+    #[test]
+    fn output_limit_is_capped_by_the_remaining_budget() {
+        // A request over budget is truncated to what is left.
+        assert_eq!(sampling_output_limit(4_000, 1_500), 1_500);
+        assert_eq!(sampling_output_limit(100, 1_500), 100);
+        // Nonsensical limits leave nothing to generate and are rejected.
+        assert_eq!(sampling_output_limit(-1, 1_500), 0);
+    }
+
+    #[test]
+    fn input_estimate_counts_text_and_instructions() {
+        let mut request = params(vec![text(Role::User, &"x".repeat(400))]);
+        request.system_prompt = Some("y".repeat(40));
+        assert_eq!(estimate_input_tokens(&request), 110);
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_cannot_overspend_the_budget() {
+        let (session, _turn_context) = make_session_and_context();
+        let session = Arc::new(session);
+        let budget = 1_000;
+        let reserve = move |session: Arc<Session>| async move {
+            session
+                .reserve_sampling_tokens("docs", 100, 600, budget)
+                .await
+        };
+
+        let (first, second) = tokio::join!(
+            tokio::spawn(reserve(Arc::clone(&session))),
+            tokio::spawn(reserve(Arc::clone(&session)))
+        );
+        let mut reservations: Vec<SamplingReservation> = [first, second]
+            .into_iter()
+            .map(|joined| joined.expect("task panicked").expect("budget left"))
+            .collect();
+        reservations.sort_by_key(|reservation| std::cmp::Reverse(reservation.reserved));
+
+        // The second request only gets what the first one left over.
+        assert_eq!(
+            reservations,
+            vec![
+                SamplingReservation {
+                    remaining: 1_000,
+                    max_output_tokens: 600,
+                    reserved: 700,
+                },
+                SamplingReservation {
+                    remaining: 300,
+                    max_output_tokens: 200,
+                    reserved: 300,
+                },
+            ]
+        );
+        let error = reserve(Arc::clone(&session))
+            .await
+            .expect_err("budget is fully reserved");
+        assert!(error.to_string().contains("used its sampling budget"));
+
+        // Settling the real usage frees what was not spent.
+        session.settle_sampling_tokens("docs", 700, 250).await;
+        let reservation = reserve(Arc::clone(&session))
+            .await
+            .expect("unspent tokens are available again");
+        assert_eq!(reservation.remaining, 450);
+        assert_eq!(reservation.max_output_tokens, 350);
+    }
+
+    #[test]
+    fn approval_reason_quotes_the_latest_text_message() {
+        let reason = sampling_approval_reason(
+            "docs",
+            &params(vec![
+                text(Role::User, "first"),
+                text(Role::User, "Summarize the README"),
+            ]),
+            1_500,
+        );
+
+        assert_eq!(
+            reason,
+            "MCP server `docs` wants to use the model (1500 tokens left in its budget): Summarize the README"
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Tracks the contents of files as the model last saw them.
+//!
+//! When an `apply_patch` call no longer matches a file because it changed
+//! after the model read it, the recorded version serves as the merge base for
+//! a three-way merge instead of failing the call outright.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use indexmap::IndexMap;
+
+use super::Session;
+use crate::parse_command::parse_command;
+use codex_protocol::parse_command::ParsedCommand;
+
+/// Maximum number of file versions kept per session.
+const MAX_TRACKED_READ_VERSIONS: usize = 64;
+
+/// Files larger than this are not tracked.
+const MAX_READ_VERSION_BYTES: u64 = 1024 * 1024;
+
+impl Session {
+    /// Remember the current contents of `path` as the version the model has
+    /// seen. Missing, oversized and non-UTF-8 files are ignored.
+    pub(crate) async fn record_read_version(&self, path: &Path) {
+        match tokio::fs::metadata(path).await {
+            Ok(metadata) if metadata.is_file() && metadata.len() <= MAX_READ_VERSION_BYTES => {}
+            _ => return,
+        }
+        let Ok(contents) = tokio::fs::read_to_string(path).await else {
+            return;
+        };
+        let mut state = self.state.lock().await;
+        state.record_read_version(
+            path.to_path_buf(),
+            Arc::from(contents),
+            MAX_TRACKED_READ_VERSIONS,
+        );
+    }
+
+    /// Record every file read by `command` when it consists solely of reads
+    /// (e.g. `cat`, `sed -n`, `head`).
+    pub(crate) async fn record_read_versions_for_command(&self, command: &[String], cwd: &Path) {
+        let parsed = parse_command(command);
+        let paths: Option<Vec<PathBuf>> = parsed
+            .iter()
+            .map(|cmd| match cmd {
+                ParsedCommand::Read { path, .. } => Some(cwd.join(path)),
+                _ => None,
+            })
+            .collect();
+        for path in paths.unwrap_or_default() {
+            self.record_read_version(&path).await;
+        }
+    }
+
+    pub(crate) async fn read_versions(&self) -> IndexMap<PathBuf, Arc<str>> {
+        self.state.lock().await.read_versions()
+    }
+}
@@ -1,22 +1,33 @@
+use crate::auth::DEFAULT_AUTH_PROFILE;
+use crate::auth::validate_auth_profile_name;
+use crate::auth_storage::AuthCredentialsStoreMode;
 use crate::config_loader::LoadedConfigLayers;
 pub use crate::config_loader::load_config_as_toml;
 use crate::config_loader::load_config_layers_with_overrides;
 use crate::config_loader::merge_toml_values;
 use crate::config_profile::ConfigProfile;
+use crate::config_types::Budgets;
 use crate::config_types::DEFAULT_OTEL_ENVIRONMENT;
 use crate::config_types::History;
+use crate::config_types::McpElicitationPolicy;
 use crate::config_types::McpServerConfig;
 use crate::config_types::McpServerTransportConfig;
+use crate::config_types::McpToolApproval;
+use crate::config_types::ModelFallbackToml;
+use crate::config_types::ModelFamilyToml;
+use crate::config_types::ModelPricing;
 use crate::config_types::Notice;
 use crate::config_types::Notifications;
 use crate::config_types::OtelConfig;
 use crate::config_types::OtelConfigToml;
 use crate::config_types::OtelExporterKind;
+use crate::config_types::RateLimitPacing;
 use crate::config_types::ReasoningSummaryFormat;
 use crate::config_types::SandboxWorkspaceWrite;
 use crate::config_types::ShellEnvironmentPolicy;
 use crate::config_types::ShellEnvironmentPolicyToml;
 use crate::config_types::Tui;
+use crate::config_types::TuiKeymap;
 use crate::config_types::UriBasedFileOpener;
 use crate::features::Feature;
 use crate::features::FeatureOverrides;
@@ -24,15 +35,19 @@ use crate::features::Features;
 use crate::features::FeaturesToml;
 use crate::git_info::resolve_root_git_project_for_trust;
 use crate::model_family::ModelFamily;
+use crate::model_family::ModelFamilyOverride;
 use crate::model_family::derive_default_model_family;
-use crate::model_family::find_family_for_model;
+use crate::model_family::find_family_override;
+use crate::model_family::find_family_with_overrides;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::built_in_model_providers;
-use crate::openai_model_info::get_model_info;
+use crate::openai_model_info::ModelLimits;
+use crate::openai_model_info::model_limits;
 use crate::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use crate::project_doc::LOCAL_PROJECT_DOC_FILENAME;
 use crate::protocol::AskForApproval;
 use crate::protocol::SandboxPolicy;
+use crate::tools::spec::EXPERIMENTAL_SUPPORTED_TOOLS;
 use anyhow::Context;
 use codex_app_server_protocol::Tools;
 use codex_app_server_protocol::UserSavedConfig;
@@ -89,6 +104,15 @@ impl Default for MultiAgentConfig {
     }
 }
 
+/// A `model_fallbacks` entry resolved against the `model_providers` map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelFallback {
+    /// Key into the model_providers map.
+    pub model_provider_id: String,
+    pub model_provider: ModelProviderInfo,
+    pub model: String,
+}
+
 /// Application configuration loaded from disk and merged with overrides.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Config {
@@ -100,6 +124,9 @@ pub struct Config {
 
     pub model_family: ModelFamily,
 
+    /// User-defined `[model_families]` overrides, keyed by model slug prefix.
+    pub model_families: HashMap<String, ModelFamilyOverride>,
+
     /// Size of the context window for the model, in tokens.
     pub model_context_window: Option<u64>,
 
@@ -115,6 +142,23 @@ pub struct Config {
     /// Info needed to make an API request to the model.
     pub model_provider: ModelProviderInfo,
 
+    /// Providers and models to switch to, in order, when the current one
+    /// keeps failing or runs out of rate limit.
+    pub model_fallbacks: Vec<ModelFallback>,
+
+    /// User-defined `[model_pricing]` entries, keyed by model slug (or slug
+    /// prefix). They take precedence over the built-in price table.
+    pub model_pricing: HashMap<String, ModelPricing>,
+
+    /// Spending limits from `[budget]`.
+    pub budget: Budgets,
+
+    /// Client-side pacing based on provider rate-limit headers.
+    pub rate_limit_pacing: RateLimitPacing,
+
+    /// Named credentials to use instead of `auth.json`, from `auth`.
+    pub auth_profile: Option<String>,
+
     /// Approval policy for executing commands.
     pub approval_policy: AskForApproval,
 
@@ -167,6 +211,10 @@ pub struct Config {
     /// and turn completions when not focused.
     pub tui_notifications: Notifications,
 
+    /// `[tui.keymap]` preset and per-action key binding overrides. Parsed and
+    /// validated by the TUI.
+    pub tui_keymap: TuiKeymap,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
@@ -184,6 +232,17 @@ pub struct Config {
     /// auto (default): keyring if available, otherwise file.
     pub mcp_oauth_credentials_store_mode: OAuthCredentialsStoreMode,
 
+    /// How MCP elicitation requests are answered when there is no user to
+    /// ask (`codex exec` and app server clients).
+    pub mcp_elicitation_policy: McpElicitationPolicy,
+
+    /// Where `codex login` credentials are stored.
+    /// file (default): CODEX_HOME/auth.json
+    /// keyring: Use an OS-specific keyring service; existing auth.json files
+    ///          are moved into it when first read.
+    /// auto: keyring if available, otherwise file.
+    pub cli_auth_credentials_store_mode: AuthCredentialsStoreMode,
+
     /// Combined provider map (defaults merged with user-defined overrides).
     pub model_providers: HashMap<String, ModelProviderInfo>,
 
@@ -500,6 +559,43 @@ pub fn write_global_mcp_servers(
                 entry["tool_timeout_sec"] = toml_edit::value(timeout.as_secs_f64());
             }
 
+            if let Some(budget) = config.sampling_token_budget {
+                entry["sampling_token_budget"] =
+                    toml_edit::value(i64::try_from(budget).unwrap_or(i64::MAX));
+            }
+
+            let tool_policy = &config.tool_policy;
+            if let Some(patterns) = &tool_policy.enabled_tools {
+                entry["enabled_tools"] =
+                    TomlItem::Value(patterns.iter().collect::<TomlArray>().into());
+            }
+            if let Some(patterns) = &tool_policy.disabled_tools {
+                entry["disabled_tools"] =
+                    TomlItem::Value(patterns.iter().collect::<TomlArray>().into());
+            }
+            if let Some(approval) = tool_policy.approval {
+                entry["approval"] = toml_edit::value(mcp_tool_approval_str(approval));
+            }
+            let mut tools: Vec<_> = tool_policy
+                .tools
+                .iter()
+                .filter_map(|(tool, cfg)| cfg.approval.map(|approval| (tool, approval)))
+                .collect();
+            if !tools.is_empty() {
+                tools.sort_by(|(a, _), (b, _)| a.cmp(b));
+                let mut tools_table = TomlTable::new();
+                tools_table.set_implicit(true);
+                for (tool, approval) in tools {
+                    let mut tool_table = TomlTable::new();
+                    tool_table.insert(
+                        "approval",
+                        toml_edit::value(mcp_tool_approval_str(approval)),
+                    );
+                    tools_table.insert(tool, TomlItem::Table(tool_table));
+                }
+                entry["tools"] = TomlItem::Table(tools_table);
+            }
+
             doc["mcp_servers"][name.as_str()] = TomlItem::Table(entry);
         }
     }
@@ -512,6 +608,14 @@ pub fn write_global_mcp_servers(
     Ok(())
 }
 
+fn mcp_tool_approval_str(approval: McpToolApproval) -> &'static str {
+    match approval {
+        McpToolApproval::Never => "never",
+        McpToolApproval::Always => "always",
+        McpToolApproval::Destructive => "destructive",
+    }
+}
+
 fn set_project_trusted_inner(doc: &mut DocumentMut, project_path: &Path) -> anyhow::Result<()> {
     // Ensure we render a human-friendly structure:
     //
@@ -839,6 +943,10 @@ pub struct ConfigToml {
     /// Provider to use from the model_providers map.
     pub model_provider: Option<String>,
 
+    /// Ordered fallback chain of providers (and optionally models) from the
+    /// model_providers map.
+    pub model_fallbacks: Option<Vec<ModelFallbackToml>>,
+
     /// Size of the context window for the model, in tokens.
     pub model_context_window: Option<u64>,
 
@@ -879,10 +987,42 @@ pub struct ConfigToml {
     #[serde(default)]
     pub mcp_oauth_credentials_store: Option<OAuthCredentialsStoreMode>,
 
+    /// How MCP elicitation requests are answered when there is no user to
+    /// ask: `decline` (default) or `cancel`.
+    #[serde(default)]
+    pub mcp_elicitation_policy: Option<McpElicitationPolicy>,
+
+    /// Preferred backend for storing the credentials from `codex login`.
+    /// file (default): Use auth.json in the Codex home directory.
+    /// keyring: Use the OS-specific keyring service.
+    /// auto: Use the OS-specific keyring service if available, otherwise use a file.
+    #[serde(default)]
+    pub cli_auth_credentials_store: Option<AuthCredentialsStoreMode>,
+
     /// User-defined provider entries that extend/override the built-in list.
     #[serde(default)]
     pub model_providers: HashMap<String, ModelProviderInfo>,
 
+    /// Capability and limit overrides for models, keyed by model slug (or
+    /// slug prefix).
+    #[serde(default)]
+    pub model_families: HashMap<String, ModelFamilyToml>,
+
+    /// Prices in USD per million tokens, keyed by model slug (or slug
+    /// prefix), used to estimate spend.
+    #[serde(default)]
+    pub model_pricing: HashMap<String, ModelPricing>,
+
+    /// Soft and hard spending limits per session, project and day.
+    pub budget: Option<Budgets>,
+
+    /// Delay requests before the provider's rate limits are hit.
+    pub rate_limit_pacing: Option<RateLimitPacing>,
+
+    /// Auth profile whose credentials are used, e.g. `auth = "work"` for
+    /// `auth.work.json`.
+    pub auth: Option<String>,
+
     /// Maximum number of bytes to include from an AGENTS.md project doc file.
     pub project_doc_max_bytes: Option<usize>,
 
@@ -1291,8 +1431,27 @@ impl Config {
             .or(cfg.model)
             .unwrap_or_else(default_model);
 
-        let mut model_family =
-            find_family_for_model(&model).unwrap_or_else(|| derive_default_model_family(&model));
+        let model_fallbacks = Self::load_model_fallbacks(
+            config_profile.model_fallbacks.or(cfg.model_fallbacks),
+            &model_providers,
+            &model,
+        )?;
+
+        let model_families = Self::load_model_families(cfg.model_families, &resolved_cwd)?;
+        let model_pricing = Self::load_model_pricing(cfg.model_pricing)?;
+        let budget = Self::load_budget(config_profile.budget.or(cfg.budget).unwrap_or_default())?;
+        let rate_limit_pacing =
+            Self::load_rate_limit_pacing(cfg.rate_limit_pacing.unwrap_or_default())?;
+        let auth_profile = match config_profile.auth.or(cfg.auth) {
+            Some(name) if name == DEFAULT_AUTH_PROFILE => None,
+            Some(name) => {
+                validate_auth_profile_name(&name)?;
+                Some(name)
+            }
+            None => None,
+        };
+        let mut model_family = find_family_with_overrides(&model, &model_families)
+            .unwrap_or_else(|| derive_default_model_family(&model));
 
         if let Some(supports_reasoning_summaries) = cfg.model_supports_reasoning_summaries {
             model_family.supports_reasoning_summaries = supports_reasoning_summaries;
@@ -1301,20 +1460,14 @@ impl Config {
             model_family.reasoning_summary_format = model_reasoning_summary_format;
         }
 
-        let openai_model_info = get_model_info(&model_family);
-        let model_context_window = cfg
-            .model_context_window
-            .or_else(|| openai_model_info.as_ref().map(|info| info.context_window));
-        let model_max_output_tokens = cfg.model_max_output_tokens.or_else(|| {
-            openai_model_info
-                .as_ref()
-                .map(|info| info.max_output_tokens)
-        });
-        let model_auto_compact_token_limit = cfg.model_auto_compact_token_limit.or_else(|| {
-            openai_model_info
-                .as_ref()
-                .and_then(|info| info.auto_compact_token_limit)
-        });
+        let model_limits = model_limits(&model_family, &model_families);
+        let model_context_window = cfg.model_context_window.or(model_limits.context_window);
+        let model_max_output_tokens = cfg
+            .model_max_output_tokens
+            .or(model_limits.max_output_tokens);
+        let model_auto_compact_token_limit = cfg
+            .model_auto_compact_token_limit
+            .or(model_limits.auto_compact_token_limit);
 
         // Load base instructions override from a file if specified. If the
         // path is relative, resolve it against the effective cwd so the
@@ -1340,11 +1493,17 @@ impl Config {
             model,
             review_model,
             model_family,
+            model_families,
             model_context_window,
             model_max_output_tokens,
             model_auto_compact_token_limit,
             model_provider_id,
             model_provider,
+            model_fallbacks,
+            model_pricing,
+            budget,
+            rate_limit_pacing,
+            auth_profile,
             cwd: resolved_cwd,
             approval_policy,
             sandbox_policy,
@@ -1357,6 +1516,8 @@ impl Config {
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
             mcp_oauth_credentials_store_mode: cfg.mcp_oauth_credentials_store.unwrap_or_default(),
+            mcp_elicitation_policy: cfg.mcp_elicitation_policy.unwrap_or_default(),
+            cli_auth_credentials_store_mode: cfg.cli_auth_credentials_store.unwrap_or_default(),
             model_providers,
             project_doc_max_bytes: cfg.project_doc_max_bytes.unwrap_or(PROJECT_DOC_MAX_BYTES),
             project_doc_fallback_filenames: cfg
@@ -1414,6 +1575,11 @@ impl Config {
                 .as_ref()
                 .map(|t| t.notifications.clone())
                 .unwrap_or_default(),
+            tui_keymap: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.keymap.clone())
+                .unwrap_or_default(),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1446,6 +1612,177 @@ impl Config {
         None
     }
 
+    /// Resolves each `model_fallbacks` entry against `model_providers`.
+    fn load_model_fallbacks(
+        fallbacks: Option<Vec<ModelFallbackToml>>,
+        model_providers: &HashMap<String, ModelProviderInfo>,
+        model: &str,
+    ) -> std::io::Result<Vec<ModelFallback>> {
+        fallbacks
+            .unwrap_or_default()
+            .into_iter()
+            .map(|fallback| {
+                let model_provider = model_providers
+                    .get(&fallback.model_provider)
+                    .ok_or_else(|| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::NotFound,
+                            format!(
+                                "Model provider `{}` in model_fallbacks not found",
+                                fallback.model_provider
+                            ),
+                        )
+                    })?
+                    .clone();
+                Ok(ModelFallback {
+                    model_provider_id: fallback.model_provider,
+                    model_provider,
+                    model: fallback.model.unwrap_or_else(|| model.to_string()),
+                })
+            })
+            .collect()
+    }
+
+    /// Rejects `[model_pricing]` entries with negative or non-finite prices.
+    fn load_model_pricing(
+        pricing: HashMap<String, ModelPricing>,
+    ) -> std::io::Result<HashMap<String, ModelPricing>> {
+        for (slug, price) in &pricing {
+            let prices = [Some(price.input), price.cached_input, Some(price.output)];
+            if prices
+                .into_iter()
+                .flatten()
+                .any(|value| !value.is_finite() || value < 0.0)
+            {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("invalid [model_pricing.{slug}]: prices must be non-negative numbers"),
+                ));
+            }
+        }
+        Ok(pricing)
+    }
+
+    /// Rejects `[budget]` limits that are not positive numbers.
+    fn load_budget(budget: Budgets) -> std::io::Result<Budgets> {
+        let scopes = [
+            ("session", budget.session),
+            ("project", budget.project),
+            ("daily", budget.daily),
+        ];
+        for (scope, limit) in scopes {
+            if [limit.soft, limit.hard]
+                .into_iter()
+                .flatten()
+                .any(|value| !value.is_finite() || value <= 0.0)
+            {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("invalid [budget.{scope}]: limits must be positive numbers"),
+                ));
+            }
+        }
+        Ok(budget)
+    }
+
+    /// Rejects a `[rate_limit_pacing]` threshold outside (0, 100].
+    fn load_rate_limit_pacing(pacing: RateLimitPacing) -> std::io::Result<RateLimitPacing> {
+        let threshold = pacing.threshold_percent;
+        if !threshold.is_finite() || threshold <= 0.0 || threshold > 100.0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "invalid [rate_limit_pacing]: threshold_percent must be greater than 0 and at most 100",
+            ));
+        }
+        Ok(pacing)
+    }
+
+    /// Validates the `[model_families]` tables and reads their base
+    /// instructions files.
+    fn load_model_families(
+        families: HashMap<String, ModelFamilyToml>,
+        cwd: &Path,
+    ) -> std::io::Result<HashMap<String, ModelFamilyOverride>> {
+        let invalid = |slug: &str, message: String| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid [model_families.{slug}]: {message}"),
+            )
+        };
+
+        let mut overrides = HashMap::with_capacity(families.len());
+        for (slug, settings) in families {
+            if slug.trim().is_empty() {
+                return Err(invalid(&slug, "model slug must not be empty".to_string()));
+            }
+            if settings.context_window == Some(0) {
+                return Err(invalid(
+                    &slug,
+                    "context_window must be positive".to_string(),
+                ));
+            }
+            if settings.max_output_tokens == Some(0) {
+                return Err(invalid(
+                    &slug,
+                    "max_output_tokens must be positive".to_string(),
+                ));
+            }
+            if settings
+                .auto_compact_token_limit
+                .is_some_and(|limit| limit <= 0)
+            {
+                return Err(invalid(
+                    &slug,
+                    "auto_compact_token_limit must be positive".to_string(),
+                ));
+            }
+            if let Some(tool) = settings
+                .experimental_supported_tools
+                .iter()
+                .flatten()
+                .find(|tool| !EXPERIMENTAL_SUPPORTED_TOOLS.contains(&tool.as_str()))
+            {
+                return Err(invalid(
+                    &slug,
+                    format!(
+                        "unknown tool `{tool}` in experimental_supported_tools (expected one of: {})",
+                        EXPERIMENTAL_SUPPORTED_TOOLS.join(", ")
+                    ),
+                ));
+            }
+            let base_instructions =
+                Self::get_base_instructions(settings.base_instructions_file.as_ref(), cwd)
+                    .map_err(|err| invalid(&slug, err.to_string()))?;
+
+            overrides.insert(
+                slug,
+                ModelFamilyOverride {
+                    settings,
+                    base_instructions,
+                },
+            );
+        }
+        Ok(overrides)
+    }
+
+    /// Model family for `model`, taking `[model_families]` into account.
+    /// Returns `None` when neither a built-in family nor an override matches.
+    pub fn find_model_family(&self, model: &str) -> Option<ModelFamily> {
+        find_family_with_overrides(model, &self.model_families)
+    }
+
+    /// Token limits for `model_family` from `[model_families]` or the
+    /// built-in model metadata.
+    pub(crate) fn model_limits(&self, model_family: &ModelFamily) -> ModelLimits {
+        model_limits(model_family, &self.model_families)
+    }
+
+    /// The `[model_families]` table applied to the session's model, with its
+    /// key.
+    pub fn model_family_override(&self) -> Option<(&str, &ModelFamilyOverride)> {
+        find_family_override(&self.model_families, &self.model)
+    }
+
     fn get_base_instructions(
         path: Option<&PathBuf>,
         cwd: &Path,
@@ -1468,7 +1805,7 @@ impl Config {
             std::io::Error::new(
                 e.kind(),
                 format!(
-                    "failed to read experimental instructions file {}: {e}",
+                    "failed to read instructions file {}: {e}",
                     full_path.display()
                 ),
             )
@@ -1478,10 +1815,7 @@ impl Config {
         if s.is_empty() {
             Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
-                format!(
-                    "experimental instructions file is empty: {}",
-                    full_path.display()
-                ),
+                format!("instructions file is empty: {}", full_path.display()),
             ))
         } else {
             Ok(Some(s))
@@ -1553,9 +1887,15 @@ pub fn log_dir(cfg: &Config) -> std::io::Result<PathBuf> {
 
 #[cfg(test)]
 mod tests {
+    use crate::config_types::BudgetLimit;
     use crate::config_types::HistoryPersistence;
+    use crate::config_types::KeymapBindings;
+    use crate::config_types::KeymapPreset;
+    use crate::config_types::McpToolConfig;
+    use crate::config_types::McpToolPolicy;
     use crate::config_types::Notifications;
     use crate::features::Feature;
+    use crate::model_family::find_family_for_model;
 
     use super::*;
     use pretty_assertions::assert_eq;
@@ -1608,6 +1948,37 @@ persistence = "none"
         assert_eq!(tui.notifications, Notifications::Enabled(true));
     }
 
+    #[test]
+    fn tui_keymap_parses_preset_and_bindings() {
+        let cfg = r#"
+[tui.keymap]
+preset = "vim"
+show_git_graph = "alt-g"
+pager_close = ["q", "ctrl-c"]
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("keymap should deserialize");
+        let keymap = parsed
+            .tui
+            .expect("config should include tui section")
+            .keymap;
+
+        assert_eq!(keymap.preset, KeymapPreset::Vim);
+        assert_eq!(
+            keymap.bindings,
+            BTreeMap::from([
+                (
+                    "pager_close".to_string(),
+                    KeymapBindings::Many(vec!["q".to_string(), "ctrl-c".to_string()]),
+                ),
+                (
+                    "show_git_graph".to_string(),
+                    KeymapBindings::One("alt-g".to_string()),
+                ),
+            ])
+        );
+    }
+
     #[test]
     fn test_sandbox_config_parsing() {
         let sandbox_full_access = r#"
@@ -1715,6 +2086,283 @@ approve_all = true
         Ok(())
     }
 
+    #[test]
+    fn model_family_table_overrides_capabilities_and_limits() -> std::io::Result<()> {
+        let temp_dir = TempDir::new()?;
+        std::fs::write(
+            temp_dir.path().join("llama.md"),
+            "You are a coding agent.\n",
+        )?;
+        let cfg = r#"
+model = "llama-3.3-70b-instruct"
+
+[model_families.llama-3]
+family = "llama-3"
+supports_parallel_tool_calls = true
+apply_patch_tool_type = "function"
+base_instructions_file = "llama.md"
+experimental_supported_tools = ["read_file"]
+context_window = 128000
+auto_compact_token_limit = 100000
+
+[model_families."llama-3.3-70b-instruct"]
+max_output_tokens = 4096
+"#;
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("model_families should parse");
+        let config = Config::load_from_base_config_with_overrides(
+            parsed,
+            ConfigOverrides {
+                cwd: Some(temp_dir.path().to_path_buf()),
+                ..Default::default()
+            },
+            temp_dir.path().to_path_buf(),
+        )?;
+
+        // The exact key wins over the shorter prefix.
+        let Some((key, family_override)) = config.model_family_override() else {
+            panic!("override should apply to the configured model");
+        };
+        assert_eq!(key, "llama-3.3-70b-instruct");
+        assert_eq!(
+            family_override.overridden_fields(),
+            vec!["max_output_tokens"]
+        );
+        assert_eq!(config.model_family.family, "llama-3.3-70b-instruct");
+        assert_eq!(config.model_max_output_tokens, Some(4_096));
+        assert_eq!(config.model_context_window, None);
+
+        let Some(family) = config.find_model_family("llama-3.1-8b") else {
+            panic!("prefix override should produce a family");
+        };
+        assert_eq!(family.family, "llama-3");
+        assert!(family.supports_parallel_tool_calls);
+        assert_eq!(
+            family.apply_patch_tool_type,
+            Some(crate::tools::handlers::apply_patch::ApplyPatchToolType::Function)
+        );
+        assert_eq!(family.base_instructions, "You are a coding agent.");
+        assert_eq!(family.experimental_supported_tools, vec!["read_file"]);
+        assert_eq!(
+            config.model_limits(&family),
+            ModelLimits {
+                context_window: Some(128_000),
+                max_output_tokens: None,
+                auto_compact_token_limit: Some(100_000),
+            }
+        );
+
+        assert_eq!(config.find_model_family("mistral-large"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn model_family_table_is_validated() {
+        let load = |table: &str| {
+            let parsed = toml::from_str::<ConfigToml>(table).expect("TOML should parse");
+            let temp_dir = TempDir::new().expect("tempdir");
+            Config::load_from_base_config_with_overrides(
+                parsed,
+                ConfigOverrides::default(),
+                temp_dir.path().to_path_buf(),
+            )
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+        };
+
+        assert_eq!(
+            load("[model_families.local]\ncontext_window = 0\n"),
+            Err("invalid [model_families.local]: context_window must be positive".to_string())
+        );
+        let unknown_tool =
+            load("[model_families.local]\nexperimental_supported_tools = [\"rm\"]\n")
+                .expect_err("unknown tool should be rejected");
+        assert!(unknown_tool.contains("unknown tool `rm`"), "{unknown_tool}");
+        let missing_file =
+            load("[model_families.local]\nbase_instructions_file = \"/nonexistent/prompt.md\"\n")
+                .expect_err("missing instructions file should be rejected");
+        assert!(
+            missing_file
+                .starts_with("invalid [model_families.local]: failed to read instructions file"),
+            "{missing_file}"
+        );
+        assert!(
+            toml::from_str::<ConfigToml>("[model_families.local]\ncontext_size = 1\n").is_err()
+        );
+    }
+
+    #[test]
+    fn model_fallbacks_resolve_providers_and_default_model() -> std::io::Result<()> {
+        let cfg = r#"
+model = "gpt-5-codex"
+model_fallbacks = [
+    { model_provider = "openai" },
+    { model_provider = "oss", model = "gpt-oss:20b" },
+]
+"#;
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("model_fallbacks should parse");
+        let temp_dir = TempDir::new()?;
+        let config = Config::load_from_base_config_with_overrides(
+            parsed,
+            ConfigOverrides::default(),
+            temp_dir.path().to_path_buf(),
+        )?;
+
+        let chain: Vec<(&str, &str)> = config
+            .model_fallbacks
+            .iter()
+            .map(|fallback| (fallback.model_provider_id.as_str(), fallback.model.as_str()))
+            .collect();
+        assert_eq!(
+            chain,
+            vec![("openai", "gpt-5-codex"), ("oss", "gpt-oss:20b")]
+        );
+        assert_eq!(
+            config.model_fallbacks[1].model_provider,
+            config.model_providers["oss"]
+        );
+
+        let parsed =
+            toml::from_str::<ConfigToml>("model_fallbacks = [{ model_provider = \"missing\" }]\n")
+                .expect("model_fallbacks should parse");
+        let err = Config::load_from_base_config_with_overrides(
+            parsed,
+            ConfigOverrides::default(),
+            temp_dir.path().to_path_buf(),
+        )
+        .expect_err("unknown provider should be rejected");
+        assert_eq!(
+            err.to_string(),
+            "Model provider `missing` in model_fallbacks not found"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn model_pricing_and_budget_are_loaded_and_validated() -> std::io::Result<()> {
+        let cfg = r#"
+[model_pricing.llama]
+input = 0.2
+output = 0.6
+
+[budget.session]
+soft = 1.5
+hard = 5
+
+[budget.daily]
+hard = 20
+"#;
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("budget should parse");
+        let temp_dir = TempDir::new()?;
+        let config = Config::load_from_base_config_with_overrides(
+            parsed,
+            ConfigOverrides::default(),
+            temp_dir.path().to_path_buf(),
+        )?;
+        assert_eq!(
+            config.model_pricing["llama"],
+            ModelPricing {
+                input: 0.2,
+                cached_input: None,
+                output: 0.6,
+            }
+        );
+        assert_eq!(
+            config.budget,
+            Budgets {
+                session: BudgetLimit {
+                    soft: Some(1.5),
+                    hard: Some(5.0),
+                },
+                project: BudgetLimit::default(),
+                daily: BudgetLimit {
+                    soft: None,
+                    hard: Some(20.0),
+                },
+            }
+        );
+
+        let load = |cfg: &str| {
+            let parsed = toml::from_str::<ConfigToml>(cfg).expect("config should parse");
+            Config::load_from_base_config_with_overrides(
+                parsed,
+                ConfigOverrides::default(),
+                temp_dir.path().to_path_buf(),
+            )
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+        };
+        assert_eq!(
+            load("[budget.project]\nhard = 0\n"),
+            Err("invalid [budget.project]: limits must be positive numbers".to_string())
+        );
+        assert_eq!(
+            load("[model_pricing.llama]\ninput = -1\noutput = 1\n"),
+            Err("invalid [model_pricing.llama]: prices must be non-negative numbers".to_string())
+        );
+        assert!(toml::from_str::<ConfigToml>("[budget.weekly]\nhard = 1\n").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn rate_limit_pacing_defaults_and_validation() -> std::io::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let load = |cfg: &str| {
+            let parsed = toml::from_str::<ConfigToml>(cfg).expect("config should parse");
+            Config::load_from_base_config_with_overrides(
+                parsed,
+                ConfigOverrides::default(),
+                temp_dir.path().to_path_buf(),
+            )
+            .map(|config| config.rate_limit_pacing)
+            .map_err(|err| err.to_string())
+        };
+
+        assert_eq!(load(""), Ok(RateLimitPacing::default()));
+        assert_eq!(
+            load("[rate_limit_pacing]\nthreshold_percent = 75\n"),
+            Ok(RateLimitPacing {
+                threshold_percent: 75.0,
+                ..RateLimitPacing::default()
+            })
+        );
+        assert_eq!(
+            load("[rate_limit_pacing]\nthreshold_percent = 120\n"),
+            Err(
+                "invalid [rate_limit_pacing]: threshold_percent must be greater than 0 and at most 100"
+                    .to_string()
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn auth_profile_binding_prefers_active_profile() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let load = |cfg: &str| {
+            let parsed = toml::from_str::<ConfigToml>(cfg).expect("config should parse");
+            Config::load_from_base_config_with_overrides(
+                parsed,
+                ConfigOverrides::default(),
+                codex_home.path().to_path_buf(),
+            )
+            .map(|config| config.auth_profile)
+            .map_err(|err| err.to_string())
+        };
+
+        assert_eq!(load(""), Ok(None));
+        assert_eq!(load("auth = \"default\"\n"), Ok(None));
+        assert_eq!(
+            load("auth = \"personal\"\nprofile = \"work\"\n[profiles.work]\nauth = \"work\"\n"),
+            Ok(Some("work".to_string()))
+        );
+        assert_eq!(
+            load("auth = \"personal\"\n"),
+            Ok(Some("personal".to_string()))
+        );
+        assert!(load("auth = \"../work\"\n").is_err());
+        Ok(())
+    }
+
     #[test]
     fn config_defaults_to_auto_oauth_store_mode() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -1734,6 +2382,28 @@ approve_all = true
         Ok(())
     }
 
+    #[test]
+    fn cli_auth_credentials_store_defaults_to_file() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let load = |toml: &str| -> std::io::Result<AuthCredentialsStoreMode> {
+            let cfg: ConfigToml = toml::from_str(toml).map_err(std::io::Error::other)?;
+            Config::load_from_base_config_with_overrides(
+                cfg,
+                ConfigOverrides::default(),
+                codex_home.path().to_path_buf(),
+            )
+            .map(|config| config.cli_auth_credentials_store_mode)
+        };
+
+        assert_eq!(load("")?, AuthCredentialsStoreMode::File);
+        assert_eq!(
+            load("cli_auth_credentials_store = \"keyring\"\n")?,
+            AuthCredentialsStoreMode::Keyring
+        );
+        assert!(load("cli_auth_credentials_store = \"vault\"\n").is_err());
+        Ok(())
+    }
+
     #[test]
     fn profile_legacy_toggles_override_base() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -1881,6 +2551,29 @@ approve_all = true
         Ok(())
     }
 
+    #[test]
+    fn mcp_elicitation_policy_defaults_to_decline() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let load = |toml: &str| {
+            let cfg: ConfigToml = toml::from_str(toml).expect("parse config");
+            Config::load_from_base_config_with_overrides(
+                cfg,
+                ConfigOverrides::default(),
+                codex_home.path().to_path_buf(),
+            )
+        };
+
+        assert_eq!(
+            load("")?.mcp_elicitation_policy,
+            McpElicitationPolicy::Decline
+        );
+        assert_eq!(
+            load("mcp_elicitation_policy = \"cancel\"")?.mcp_elicitation_policy,
+            McpElicitationPolicy::Cancel
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn load_global_mcp_servers_returns_empty_if_missing() -> anyhow::Result<()> {
         let codex_home = TempDir::new()?;
@@ -1909,6 +2602,8 @@ approve_all = true
                 enabled: true,
                 startup_timeout_sec: Some(Duration::from_secs(3)),
                 tool_timeout_sec: Some(Duration::from_secs(5)),
+                tool_policy: Default::default(),
+                sampling_token_budget: Some(5_000),
             },
         );
 
@@ -1935,6 +2630,7 @@ approve_all = true
         }
         assert_eq!(docs.startup_timeout_sec, Some(Duration::from_secs(3)));
         assert_eq!(docs.tool_timeout_sec, Some(Duration::from_secs(5)));
+        assert_eq!(docs.sampling_token_budget, Some(5_000));
         assert!(docs.enabled);
 
         let empty = BTreeMap::new();
@@ -1945,6 +2641,63 @@ approve_all = true
         Ok(())
     }
 
+    #[tokio::test]
+    async fn write_global_mcp_servers_serializes_tool_policy() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+
+        let tool_policy = McpToolPolicy {
+            enabled_tools: Some(vec!["list_*".to_string()]),
+            disabled_tools: Some(vec!["list_secrets".to_string()]),
+            approval: Some(McpToolApproval::Destructive),
+            tools: HashMap::from([(
+                "list_repos".to_string(),
+                McpToolConfig {
+                    approval: Some(McpToolApproval::Always),
+                },
+            )]),
+        };
+        let servers = BTreeMap::from([(
+            "docs".to_string(),
+            McpServerConfig {
+                transport: McpServerTransportConfig::Stdio {
+                    command: "docs-server".to_string(),
+                    args: Vec::new(),
+                    env: None,
+                    env_vars: Vec::new(),
+                    cwd: None,
+                },
+                enabled: true,
+                startup_timeout_sec: None,
+                tool_timeout_sec: None,
+                tool_policy: tool_policy.clone(),
+                sampling_token_budget: None,
+            },
+        )]);
+
+        write_global_mcp_servers(codex_home.path(), &servers)?;
+
+        let config_path = codex_home.path().join(CONFIG_TOML_FILE);
+        let serialized = std::fs::read_to_string(&config_path)?;
+        assert_eq!(
+            serialized,
+            r#"[mcp_servers.docs]
+command = "docs-server"
+enabled_tools = ["list_*"]
+disabled_tools = ["list_secrets"]
+approval = "destructive"
+
+[mcp_servers.docs.tools.list_repos]
+approval = "always"
+"#
+        );
+
+        let loaded = load_global_mcp_servers(codex_home.path()).await?;
+        let docs = loaded.get("docs").expect("docs entry");
+        assert_eq!(docs.tool_policy, tool_policy);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn managed_config_wins_over_cli_overrides() -> anyhow::Result<()> {
         let codex_home = TempDir::new()?;
@@ -2045,6 +2798,8 @@ bearer_token = "secret"
                 enabled: true,
                 startup_timeout_sec: None,
                 tool_timeout_sec: None,
+                tool_policy: Default::default(),
+                sampling_token_budget: None,
             },
         )]);
 
@@ -2107,6 +2862,8 @@ ZIG_VAR = "3"
                 enabled: true,
                 startup_timeout_sec: None,
                 tool_timeout_sec: None,
+                tool_policy: Default::default(),
+                sampling_token_budget: None,
             },
         )]);
 
@@ -2149,6 +2906,8 @@ ZIG_VAR = "3"
                 enabled: true,
                 startup_timeout_sec: None,
                 tool_timeout_sec: None,
+                tool_policy: Default::default(),
+                sampling_token_budget: None,
             },
         )]);
 
@@ -2190,6 +2949,8 @@ ZIG_VAR = "3"
                 enabled: true,
                 startup_timeout_sec: Some(Duration::from_secs(2)),
                 tool_timeout_sec: None,
+                tool_policy: Default::default(),
+                sampling_token_budget: None,
             },
         )]);
 
@@ -2247,6 +3008,8 @@ startup_timeout_sec = 2.0
                 enabled: true,
                 startup_timeout_sec: Some(Duration::from_secs(2)),
                 tool_timeout_sec: None,
+                tool_policy: Default::default(),
+                sampling_token_budget: None,
             },
         )]);
         write_global_mcp_servers(codex_home.path(), &servers)?;
@@ -2316,6 +3079,8 @@ X-Auth = "DOCS_AUTH"
                 enabled: true,
                 startup_timeout_sec: Some(Duration::from_secs(2)),
                 tool_timeout_sec: None,
+                tool_policy: Default::default(),
+                sampling_token_budget: None,
             },
         )]);
 
@@ -2337,6 +3102,8 @@ X-Auth = "DOCS_AUTH"
                 enabled: true,
                 startup_timeout_sec: None,
                 tool_timeout_sec: None,
+                tool_policy: Default::default(),
+                sampling_token_budget: None,
             },
         );
         write_global_mcp_servers(codex_home.path(), &servers)?;
@@ -2396,6 +3163,8 @@ url = "https://example.com/mcp"
                     enabled: true,
                     startup_timeout_sec: Some(Duration::from_secs(2)),
                     tool_timeout_sec: None,
+                    tool_policy: Default::default(),
+                    sampling_token_budget: None,
                 },
             ),
             (
@@ -2411,6 +3180,8 @@ url = "https://example.com/mcp"
                     enabled: true,
                     startup_timeout_sec: None,
                     tool_timeout_sec: None,
+                    tool_policy: Default::default(),
+                    sampling_token_budget: None,
                 },
             ),
         ]);
@@ -2485,6 +3256,8 @@ url = "https://example.com/mcp"
                 enabled: false,
                 startup_timeout_sec: None,
                 tool_timeout_sec: None,
+                tool_policy: Default::default(),
+                sampling_token_budget: None,
             },
         )]);
 
@@ -2733,6 +3506,7 @@ model_verbosity = "high"
             stream_max_retries: Some(10),
             stream_idle_timeout_ms: Some(300_000),
             requires_openai_auth: false,
+            fixtures: None,
         };
         let model_provider_map = {
             let mut model_provider_map = built_in_model_providers();
@@ -2789,11 +3563,17 @@ model_verbosity = "high"
                 model: "o3".to_string(),
                 review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
                 model_family: find_family_for_model("o3").expect("known model slug"),
+                model_families: HashMap::new(),
                 model_context_window: Some(200_000),
                 model_max_output_tokens: Some(100_000),
                 model_auto_compact_token_limit: None,
                 model_provider_id: "openai".to_string(),
                 model_provider: fixture.openai_provider.clone(),
+                model_fallbacks: Vec::new(),
+                model_pricing: HashMap::new(),
+                budget: Budgets::default(),
+                rate_limit_pacing: RateLimitPacing::default(),
+                auth_profile: None,
                 approval_policy: AskForApproval::Never,
                 sandbox_policy: SandboxPolicy::new_read_only_policy(),
                 did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -2803,6 +3583,8 @@ model_verbosity = "high"
                 cwd: fixture.cwd(),
                 mcp_servers: HashMap::new(),
                 mcp_oauth_credentials_store_mode: Default::default(),
+                mcp_elicitation_policy: McpElicitationPolicy::default(),
+                cli_auth_credentials_store_mode: Default::default(),
                 model_providers: fixture.model_provider_map.clone(),
                 project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
                 project_doc_fallback_filenames: Vec::new(),
@@ -2833,6 +3615,7 @@ model_verbosity = "high"
                 notices: Default::default(),
                 disable_paste_burst: false,
                 tui_notifications: Default::default(),
+                tui_keymap: Default::default(),
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -2858,11 +3641,17 @@ model_verbosity = "high"
             model: "gpt-3.5-turbo".to_string(),
             review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
             model_family: find_family_for_model("gpt-3.5-turbo").expect("known model slug"),
+            model_families: HashMap::new(),
             model_context_window: Some(16_385),
             model_max_output_tokens: Some(4_096),
             model_auto_compact_token_limit: None,
             model_provider_id: "openai-chat-completions".to_string(),
             model_provider: fixture.openai_chat_completions_provider.clone(),
+            model_fallbacks: Vec::new(),
+            model_pricing: HashMap::new(),
+            budget: Budgets::default(),
+            rate_limit_pacing: RateLimitPacing::default(),
+            auth_profile: None,
             approval_policy: AskForApproval::UnlessTrusted,
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -2872,6 +3661,8 @@ model_verbosity = "high"
             cwd: fixture.cwd(),
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
+            mcp_elicitation_policy: McpElicitationPolicy::default(),
+            cli_auth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
@@ -2902,6 +3693,7 @@ model_verbosity = "high"
             notices: Default::default(),
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_keymap: Default::default(),
             otel: OtelConfig::default(),
         };
 
@@ -2942,11 +3734,17 @@ model_verbosity = "high"
             model: "o3".to_string(),
             review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
             model_family: find_family_for_model("o3").expect("known model slug"),
+            model_families: HashMap::new(),
             model_context_window: Some(200_000),
             model_max_output_tokens: Some(100_000),
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            model_fallbacks: Vec::new(),
+            model_pricing: HashMap::new(),
+            budget: Budgets::default(),
+            rate_limit_pacing: RateLimitPacing::default(),
+            auth_profile: None,
             approval_policy: AskForApproval::OnFailure,
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -2956,6 +3754,8 @@ model_verbosity = "high"
             cwd: fixture.cwd(),
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
+            mcp_elicitation_policy: McpElicitationPolicy::default(),
+            cli_auth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
@@ -2986,6 +3786,7 @@ model_verbosity = "high"
             notices: Default::default(),
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_keymap: Default::default(),
             otel: OtelConfig::default(),
         };
 
@@ -3012,11 +3813,17 @@ model_verbosity = "high"
             model: "gpt-5".to_string(),
             review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
             model_family: find_family_for_model("gpt-5").expect("known model slug"),
+            model_families: HashMap::new(),
             model_context_window: Some(272_000),
             model_max_output_tokens: Some(128_000),
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            model_fallbacks: Vec::new(),
+            model_pricing: HashMap::new(),
+            budget: Budgets::default(),
+            rate_limit_pacing: RateLimitPacing::default(),
+            auth_profile: None,
             approval_policy: AskForApproval::OnFailure,
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3026,6 +3833,8 @@ model_verbosity = "high"
             cwd: fixture.cwd(),
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
+            mcp_elicitation_policy: McpElicitationPolicy::default(),
+            cli_auth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
@@ -3056,6 +3865,7 @@ model_verbosity = "high"
             notices: Default::default(),
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_keymap: Default::default(),
             otel: OtelConfig::default(),
         };
 
@@ -1,6 +1,8 @@
 use serde::Deserialize;
 use std::path::PathBuf;
 
+use crate::config_types::Budgets;
+use crate::config_types::ModelFallbackToml;
 use crate::protocol::AskForApproval;
 use codex_protocol::config_types::ReasoningEffort;
 use codex_protocol::config_types::ReasoningSummary;
@@ -14,6 +16,13 @@ pub struct ConfigProfile {
     /// The key in the `model_providers` map identifying the
     /// [`ModelProviderInfo`] to use.
     pub model_provider: Option<String>,
+    /// Providers and models to switch to, in order, when the current one
+    /// keeps failing or runs out of rate limit.
+    pub model_fallbacks: Option<Vec<ModelFallbackToml>>,
+    /// Spending limits that replace the top-level `[budget]` table.
+    pub budget: Option<Budgets>,
+    /// Auth profile whose credentials this profile uses.
+    pub auth: Option<String>,
     pub approval_policy: Option<AskForApproval>,
     pub model_reasoning_effort: Option<ReasoningEffort>,
     pub model_reasoning_summary: Option<ReasoningSummary>,
@@ -3,7 +3,9 @@
 // Note this file should generally be restricted to simple struct/enum
 // definitions that do not contain business logic.
 
+use codex_protocol::protocol::ElicitationAction;
 use serde::Deserializer;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -35,6 +37,78 @@ pub struct McpServerConfig {
     /// Default timeout for MCP tool calls initiated via this server.
     #[serde(default, with = "option_duration_secs")]
     pub tool_timeout_sec: Option<Duration>,
+
+    /// Which of the server's tools are exposed to the model, and which of
+    /// them need the user's approval before they run.
+    #[serde(flatten)]
+    pub tool_policy: McpToolPolicy,
+
+    /// Tokens the server may spend on the session's model through MCP
+    /// sampling. Sampling is not offered to the server when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling_token_budget: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct McpToolPolicy {
+    /// Glob patterns; when set, only tools matching one of them are exposed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled_tools: Option<Vec<String>>,
+
+    /// Glob patterns for tools that are never exposed. Takes precedence over
+    /// `enabled_tools`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled_tools: Option<Vec<String>>,
+
+    /// Approval requirement for tools without an entry in `tools`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval: Option<McpToolApproval>,
+
+    /// Per-tool settings, keyed by the tool name the server reports.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tools: HashMap<String, McpToolConfig>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct McpToolConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval: Option<McpToolApproval>,
+}
+
+/// When a call to an MCP tool must be approved by the user.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum McpToolApproval {
+    /// Run without asking.
+    #[default]
+    Never,
+    /// Ask before every call.
+    Always,
+    /// Ask for tools that may be destructive: any tool the server does not
+    /// annotate as read-only or non-destructive.
+    Destructive,
+}
+
+/// How MCP elicitation requests are answered when there is no user to ask,
+/// as in `codex exec` or for app server clients.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum McpElicitationPolicy {
+    /// Decline the request; the server may continue without the values.
+    #[default]
+    Decline,
+    /// Cancel the request; the server should abort the operation.
+    Cancel,
+}
+
+impl McpElicitationPolicy {
+    /// The answer given on the user's behalf.
+    pub fn action(self) -> ElicitationAction {
+        match self {
+            McpElicitationPolicy::Decline => ElicitationAction::Decline,
+            McpElicitationPolicy::Cancel => ElicitationAction::Cancel,
+        }
+    }
 }
 
 impl<'de> Deserialize<'de> for McpServerConfig {
@@ -72,6 +146,16 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             tool_timeout_sec: Option<Duration>,
             #[serde(default)]
             enabled: Option<bool>,
+            #[serde(default)]
+            enabled_tools: Option<Vec<String>>,
+            #[serde(default)]
+            disabled_tools: Option<Vec<String>>,
+            #[serde(default)]
+            approval: Option<McpToolApproval>,
+            #[serde(default)]
+            tools: HashMap<String, McpToolConfig>,
+            #[serde(default)]
+            sampling_token_budget: Option<u64>,
         }
 
         let raw = RawMcpServerConfig::deserialize(deserializer)?;
@@ -141,6 +225,11 @@ impl<'de> Deserialize<'de> for McpServerConfig {
                 tool_timeout_sec: _,
                 startup_timeout_ms: _,
                 enabled: _,
+                enabled_tools: _,
+                disabled_tools: _,
+                approval: _,
+                tools: _,
+                sampling_token_budget: _,
             } => {
                 throw_if_set("streamable_http", "command", command.as_ref())?;
                 throw_if_set("streamable_http", "args", args.as_ref())?;
@@ -163,6 +252,13 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             startup_timeout_sec,
             tool_timeout_sec: raw.tool_timeout_sec,
             enabled: raw.enabled.unwrap_or_else(default_enabled),
+            tool_policy: McpToolPolicy {
+                enabled_tools: raw.enabled_tools,
+                disabled_tools: raw.disabled_tools,
+                approval: raw.approval,
+                tools: raw.tools,
+            },
+            sampling_token_budget: raw.sampling_token_budget,
         })
     }
 }
@@ -359,6 +455,57 @@ pub struct Tui {
     /// Defaults to `false`.
     #[serde(default)]
     pub notifications: Notifications,
+
+    /// Key bindings for TUI actions, see [`TuiKeymap`].
+    #[serde(default)]
+    pub keymap: TuiKeymap,
+}
+
+/// The `[tui.keymap]` table: a preset plus per-action overrides.
+///
+/// ```toml
+/// [tui.keymap]
+/// preset = "vim"
+/// show_git_graph = "alt-g"
+/// pager_close = ["q", "ctrl-c"]
+/// ```
+///
+/// Action names and chords are validated by the TUI at startup; an empty
+/// list unbinds the action.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct TuiKeymap {
+    #[serde(default)]
+    pub preset: KeymapPreset,
+
+    #[serde(flatten)]
+    pub bindings: BTreeMap<String, KeymapBindings>,
+}
+
+/// Built-in keymaps the `[tui.keymap]` overrides are applied on top of.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum KeymapPreset {
+    #[default]
+    Default,
+    Vim,
+    Emacs,
+}
+
+/// One key chord (`"ctrl-t"`) or several alternatives (`["up", "k"]`).
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum KeymapBindings {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeymapBindings {
+    pub fn chords(&self) -> &[String] {
+        match self {
+            KeymapBindings::One(chord) => std::slice::from_ref(chord),
+            KeymapBindings::Many(chords) => chords,
+        }
+    }
 }
 
 /// Settings for notices we display to users via the tui and app-server clients
@@ -502,6 +649,116 @@ pub enum ReasoningSummaryFormat {
     Experimental,
 }
 
+/// `apply_patch` tool variant for a `[model_families.<slug>]` table;
+/// `"none"` removes the tool.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplyPatchToolTypeToml {
+    Freeform,
+    Function,
+    #[serde(rename = "none")]
+    Disabled,
+}
+
+/// A `[model_families.<slug>]` table. It applies to the model named `<slug>`,
+/// or else to models whose name starts with `<slug>` (the longest matching
+/// key wins). Unset fields keep the values of the built-in family.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ModelFamilyToml {
+    /// Family name shown in the UI and reported in telemetry.
+    pub family: Option<String>,
+    pub needs_special_apply_patch_instructions: Option<bool>,
+    pub supports_reasoning_summaries: Option<bool>,
+    pub reasoning_summary_format: Option<ReasoningSummaryFormat>,
+    pub uses_local_shell_tool: Option<bool>,
+    pub supports_parallel_tool_calls: Option<bool>,
+    pub apply_patch_tool_type: Option<ApplyPatchToolTypeToml>,
+    /// File whose contents replace the built-in base instructions. Relative
+    /// paths are resolved against the working directory.
+    pub base_instructions_file: Option<PathBuf>,
+    /// Extra tools to expose, e.g. `["read_file", "list_dir"]`.
+    pub experimental_supported_tools: Option<Vec<String>>,
+    /// Size of the context window, in tokens.
+    pub context_window: Option<u64>,
+    /// Maximum number of output tokens.
+    pub max_output_tokens: Option<u64>,
+    /// Token usage threshold triggering auto-compaction of conversation history.
+    pub auto_compact_token_limit: Option<i64>,
+}
+
+/// An entry of a `model_fallbacks` chain: the provider (a key in
+/// `model_providers`) and model to switch to when the current one keeps
+/// failing. `model` defaults to the session's model.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ModelFallbackToml {
+    pub model_provider: String,
+    pub model: Option<String>,
+}
+
+/// Price of a model in USD per million tokens, from `[model_pricing.<slug>]`.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ModelPricing {
+    /// Price of uncached input tokens.
+    pub input: f64,
+    /// Price of cached input tokens. Defaults to `input`.
+    pub cached_input: Option<f64>,
+    /// Price of output tokens, including reasoning tokens.
+    pub output: f64,
+}
+
+/// Soft and hard spending limits in USD for one `[budget]` scope.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BudgetLimit {
+    /// Warn once the estimated spend reaches this amount.
+    pub soft: Option<f64>,
+    /// Stop running turns once the estimated spend reaches this amount.
+    pub hard: Option<f64>,
+}
+
+/// Spending limits from the `[budget]` table.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Budgets {
+    /// Spend of a single session.
+    #[serde(default)]
+    pub session: BudgetLimit,
+    /// Spend of all sessions in the same project (git root or cwd).
+    #[serde(default)]
+    pub project: BudgetLimit,
+    /// Spend of all sessions on the current local day.
+    #[serde(default)]
+    pub daily: BudgetLimit,
+}
+
+/// Client-side request pacing from the `[rate_limit_pacing]` table, driven by
+/// the rate-limit headers returned by the provider.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitPacing {
+    /// When false, requests are sent as soon as they are ready.
+    pub enabled: bool,
+    /// Usage of a rate-limit window, in percent, above which request starts
+    /// are queued and spread out until the window resets.
+    pub threshold_percent: f64,
+    /// Longest wait applied before a request. Longer delays are clamped to
+    /// this value.
+    pub max_wait_secs: u64,
+}
+
+impl Default for RateLimitPacing {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold_percent: 90.0,
+            max_wait_secs: 300,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -757,4 +1014,35 @@ mod tests {
             "unexpected error: {err}"
         );
     }
+
+    #[test]
+    fn deserialize_server_config_with_tool_policy() {
+        let cfg: McpServerConfig = toml::from_str(
+            r#"
+            command = "echo"
+            enabled_tools = ["list_*", "get_*"]
+            disabled_tools = ["get_secret"]
+            approval = "destructive"
+
+            [tools.list_repos]
+            approval = "always"
+        "#,
+        )
+        .expect("should deserialize tool policy");
+
+        assert_eq!(
+            cfg.tool_policy,
+            McpToolPolicy {
+                enabled_tools: Some(vec!["list_*".to_string(), "get_*".to_string()]),
+                disabled_tools: Some(vec!["get_secret".to_string()]),
+                approval: Some(McpToolApproval::Destructive),
+                tools: HashMap::from([(
+                    "list_repos".to_string(),
+                    McpToolConfig {
+                        approval: Some(McpToolApproval::Always),
+                    },
+                )]),
+            }
+        );
+    }
 }
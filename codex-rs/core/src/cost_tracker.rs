@@ -0,0 +1,379 @@
+//! Estimated spend per session, project and day, and enforcement of the
+//! `[budget]` limits.
+//!
+//! Project and daily totals are shared by all sessions through
+//! `~/.codex/costs.json`:
+//!
+//! ````text
+//! {"projects":{"/path/to/repo":12.5},"days":{"2025-10-18":3.25}}
+//! ````
+//!
+//! The file is rewritten under an advisory lock after every priced response.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs::OpenOptions;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::config_types::BudgetLimit;
+use crate::config_types::Budgets;
+use crate::config_types::ModelPricing;
+use crate::git_info::resolve_root_git_project_for_trust;
+use crate::model_pricing::estimate_cost_usd;
+use crate::model_pricing::find_model_pricing;
+use crate::protocol::CostSummary;
+use crate::protocol::TokenUsage;
+
+/// Filename that stores the cost ledger inside `~/.codex`.
+pub(crate) const COSTS_FILENAME: &str = "costs.json";
+
+const MAX_RETRIES: usize = 10;
+const RETRY_SLEEP: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CostLedger {
+    #[serde(default)]
+    projects: BTreeMap<String, f64>,
+    #[serde(default)]
+    days: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum BudgetScope {
+    Session,
+    Project,
+    Daily,
+}
+
+impl BudgetScope {
+    const ALL: [BudgetScope; 3] = [Self::Session, Self::Project, Self::Daily];
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::Session => "session",
+            Self::Project => "project",
+            Self::Daily => "daily",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Session => "Session",
+            Self::Project => "Project",
+            Self::Daily => "Daily",
+        }
+    }
+
+    fn limit(self, budgets: &Budgets) -> BudgetLimit {
+        match self {
+            Self::Session => budgets.session,
+            Self::Project => budgets.project,
+            Self::Daily => budgets.daily,
+        }
+    }
+
+    fn spent(self, summary: &CostSummary) -> f64 {
+        match self {
+            Self::Session => summary.session_usd,
+            Self::Project => summary.project_usd,
+            Self::Daily => summary.daily_usd,
+        }
+    }
+}
+
+#[derive(Default)]
+struct CostState {
+    /// `None` until the session priced a response or read the ledger.
+    summary: Option<CostSummary>,
+    /// Scopes whose soft limit was already reported.
+    warned: HashSet<BudgetScope>,
+}
+
+/// Prices the session's token usage, records it in the shared ledger and
+/// checks the result against `[budget]`.
+pub(crate) struct CostTracker {
+    pricing: HashMap<String, ModelPricing>,
+    budgets: Budgets,
+    ledger_path: PathBuf,
+    project: String,
+    state: Mutex<CostState>,
+}
+
+impl CostTracker {
+    pub(crate) fn new(config: &Config, cwd: &Path) -> Self {
+        let project = resolve_root_git_project_for_trust(cwd).unwrap_or_else(|| cwd.to_path_buf());
+        Self {
+            pricing: config.model_pricing.clone(),
+            budgets: config.budget,
+            ledger_path: config.codex_home.join(COSTS_FILENAME),
+            project: project.to_string_lossy().into_owned(),
+            state: Mutex::new(CostState::default()),
+        }
+    }
+
+    /// Latest spend estimate, if any.
+    pub(crate) fn summary(&self) -> Option<CostSummary> {
+        self.state.lock().ok().and_then(|state| state.summary)
+    }
+
+    /// Adds the cost of one response by `model` and returns a warning for
+    /// every soft limit crossed for the first time.
+    pub(crate) async fn record(&self, model: &str, usage: &TokenUsage) -> Vec<String> {
+        let Some(pricing) = find_model_pricing(model, &self.pricing) else {
+            return Vec::new();
+        };
+        let usd = estimate_cost_usd(&pricing, usage);
+        let totals = self.update_ledger(usd).await;
+
+        let Ok(mut state) = self.state.lock() else {
+            return Vec::new();
+        };
+        let previous = state.summary.unwrap_or_default();
+        let (project_usd, daily_usd) =
+            totals.unwrap_or((previous.project_usd + usd, previous.daily_usd + usd));
+        let summary = CostSummary {
+            session_usd: previous.session_usd + usd,
+            project_usd,
+            daily_usd,
+        };
+        state.summary = Some(summary);
+
+        let mut warnings = Vec::new();
+        for scope in BudgetScope::ALL {
+            let spent = scope.spent(&summary);
+            if let Some(soft) = scope.limit(&self.budgets).soft
+                && spent >= soft
+                && state.warned.insert(scope)
+            {
+                warnings.push(format!(
+                    "{} spend is ${spent:.2}, above the soft budget of ${soft:.2} (`budget.{}.soft`).",
+                    scope.title(),
+                    scope.key()
+                ));
+            }
+        }
+        warnings
+    }
+
+    /// Error message when a hard limit is reached and no further turns
+    /// should run. Re-reads the ledger so spend from other sessions counts.
+    pub(crate) async fn hard_budget_exceeded(&self) -> Option<String> {
+        let hard_limits: Vec<(BudgetScope, f64)> = BudgetScope::ALL
+            .into_iter()
+            .filter_map(|scope| scope.limit(&self.budgets).hard.map(|hard| (scope, hard)))
+            .collect();
+        if hard_limits.is_empty() {
+            return None;
+        }
+
+        let totals = self.update_ledger(0.0).await;
+        let summary = {
+            let mut state = self.state.lock().ok()?;
+            let mut summary = state.summary.unwrap_or_default();
+            if let Some((project_usd, daily_usd)) = totals {
+                summary.project_usd = project_usd;
+                summary.daily_usd = daily_usd;
+            }
+            state.summary = Some(summary);
+            summary
+        };
+
+        hard_limits.into_iter().find_map(|(scope, hard)| {
+            let spent = scope.spent(&summary);
+            (spent >= hard).then(|| {
+                format!(
+                    "{} budget exceeded: estimated spend ${spent:.2} reached the hard limit of ${hard:.2}. Raise `budget.{}.hard` in config.toml to continue.",
+                    scope.title(),
+                    scope.key()
+                )
+            })
+        })
+    }
+
+    /// Adds `usd` to the ledger and returns the project and daily totals.
+    /// Failures are logged and reported as `None`.
+    async fn update_ledger(&self, usd: f64) -> Option<(f64, f64)> {
+        let path = self.ledger_path.clone();
+        let project = self.project.clone();
+        let day = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let result =
+            tokio::task::spawn_blocking(move || update_ledger_file(&path, &project, &day, usd))
+                .await;
+        match result {
+            Ok(Ok(totals)) => Some(totals),
+            Ok(Err(err)) => {
+                warn!("failed to update {COSTS_FILENAME}: {err}");
+                None
+            }
+            Err(err) => {
+                warn!("failed to update {COSTS_FILENAME}: {err}");
+                None
+            }
+        }
+    }
+}
+
+/// Blocking read-modify-write of the ledger under an exclusive lock. A zero
+/// `usd` only reads the totals.
+fn update_ledger_file(
+    path: &Path,
+    project: &str,
+    day: &str,
+    usd: f64,
+) -> std::io::Result<(f64, f64)> {
+    if usd == 0.0 && !path.exists() {
+        return Ok((0.0, 0.0));
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)?;
+
+    for _ in 0..MAX_RETRIES {
+        match file.try_lock() {
+            Ok(()) => {
+                let mut contents = String::new();
+                file.read_to_string(&mut contents)?;
+                let mut ledger: CostLedger = if contents.trim().is_empty() {
+                    CostLedger::default()
+                } else {
+                    serde_json::from_str(&contents).map_err(std::io::Error::other)?
+                };
+
+                let project_usd = ledger.projects.entry(project.to_string()).or_default();
+                *project_usd += usd;
+                let project_usd = *project_usd;
+                let daily_usd = ledger.days.entry(day.to_string()).or_default();
+                *daily_usd += usd;
+                let daily_usd = *daily_usd;
+
+                if usd != 0.0 {
+                    let json = serde_json::to_string(&ledger).map_err(std::io::Error::other)?;
+                    file.set_len(0)?;
+                    file.seek(SeekFrom::Start(0))?;
+                    file.write_all(json.as_bytes())?;
+                    file.flush()?;
+                }
+                return Ok((project_usd, daily_usd));
+            }
+            Err(std::fs::TryLockError::WouldBlock) => std::thread::sleep(RETRY_SLEEP),
+            Err(std::fs::TryLockError::Error(err)) => return Err(err),
+        }
+    }
+
+    Err(std::io::Error::new(
+        std::io::ErrorKind::WouldBlock,
+        "could not acquire exclusive lock on cost ledger after multiple attempts",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn tracker(codex_home: &TempDir, budgets: Budgets) -> CostTracker {
+        CostTracker {
+            pricing: HashMap::from([(
+                "test-model".to_string(),
+                ModelPricing {
+                    input: 1.0,
+                    cached_input: None,
+                    output: 2.0,
+                },
+            )]),
+            budgets,
+            ledger_path: codex_home.path().join(COSTS_FILENAME),
+            project: "/repo".to_string(),
+            state: Mutex::new(CostState::default()),
+        }
+    }
+
+    fn usage(input_tokens: u64, output_tokens: u64) -> TokenUsage {
+        TokenUsage {
+            input_tokens,
+            output_tokens,
+            total_tokens: input_tokens + output_tokens,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn records_spend_and_enforces_budgets() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let budgets = Budgets {
+            session: BudgetLimit {
+                soft: Some(1.0),
+                hard: Some(3.0),
+            },
+            ..Default::default()
+        };
+        let first = tracker(&codex_home, budgets);
+
+        // Unpriced models are not accounted.
+        assert!(first.record("gpt-oss:20b", &usage(1, 1)).await.is_empty());
+        assert_eq!(first.summary(), None);
+        assert_eq!(first.hard_budget_exceeded().await, None);
+
+        let warnings = first.record("test-model", &usage(1_000_000, 500_000)).await;
+        assert_eq!(
+            warnings,
+            vec![
+                "Session spend is $2.00, above the soft budget of $1.00 (`budget.session.soft`)."
+                    .to_string()
+            ]
+        );
+        assert!(first.record("test-model", &usage(0, 0)).await.is_empty());
+        assert_eq!(first.hard_budget_exceeded().await, None);
+
+        first.record("test-model", &usage(1_000_000, 0)).await;
+        assert_eq!(
+            first.hard_budget_exceeded().await,
+            Some(
+                "Session budget exceeded: estimated spend $3.00 reached the hard limit of $3.00. Raise `budget.session.hard` in config.toml to continue."
+                    .to_string()
+            )
+        );
+
+        // A new session starts from zero but sees the shared project total.
+        let second = tracker(
+            &codex_home,
+            Budgets {
+                project: BudgetLimit {
+                    soft: None,
+                    hard: Some(2.5),
+                },
+                ..Default::default()
+            },
+        );
+        assert!(
+            second
+                .hard_budget_exceeded()
+                .await
+                .is_some_and(|message| message.starts_with("Project budget exceeded"))
+        );
+        let summary = second.summary().expect("ledger was read");
+        assert_eq!(summary.session_usd, 0.0);
+        assert!((summary.project_usd - 3.0).abs() < 1e-9);
+        assert!((summary.daily_usd - 3.0).abs() < 1e-9);
+    }
+}
@@ -8,6 +8,7 @@ use codex_protocol::protocol::RateLimitSnapshot;
 use reqwest::StatusCode;
 use serde_json;
 use std::io;
+use std::path::PathBuf;
 use std::time::Duration;
 use thiserror::Error;
 use tokio::task::JoinError;
@@ -127,6 +128,10 @@ pub enum CodexErr {
     #[error("unsupported operation: {0}")]
     UnsupportedOperation(String),
 
+    /// A provider in fixture replay mode has no recorded exchange matching the request.
+    #[error("no recorded model fixture matches this request (expected {})", .0.display())]
+    FixtureNotFound(PathBuf),
+
     #[error("Fatal error: {0}")]
     Fatal(String),
 
@@ -43,6 +43,8 @@ pub enum Feature {
     WebSearchRequest,
     /// Automatically approve all approval requests from the harness.
     ApproveAll,
+    /// Capture a workspace checkpoint before every user turn.
+    Checkpoints,
 }
 
 impl Feature {
@@ -255,4 +257,10 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::Experimental,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::Checkpoints,
+        key: "checkpoints",
+        stage: Stage::Beta,
+        default_enabled: false,
+    },
 ];
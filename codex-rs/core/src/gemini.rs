@@ -0,0 +1,515 @@
+//! Streaming adapter for the Gemini API (`wire_api = "gemini"`).
+//!
+//! History items map onto `contents` parts: text, inline images,
+//! `functionCall` and `functionResponse`. Gemini attaches an opaque
+//! `thoughtSignature` to the part that follows its thinking and expects it
+//! back on that same part; it is stored as the `encrypted_content` of a
+//! reasoning item placed right before the item it belongs to.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use codex_otel::otel_event_manager::OtelEventManager;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde_json::Value;
+use serde_json::json;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use tracing::trace;
+
+use crate::client_common::ResponseEvent;
+use crate::client_common::ResponseStream;
+use crate::error::CodexErr;
+use crate::error::Result;
+use crate::protocol::TokenUsage;
+use crate::wire_stream::ByteStream;
+use crate::wire_stream::MessageList;
+use crate::wire_stream::NativeRequest;
+use crate::wire_stream::WireTarget;
+use crate::wire_stream::function_tools;
+use crate::wire_stream::parse_data_url;
+use crate::wire_stream::stream_request;
+use crate::wire_stream::thinking_budget;
+use crate::wire_stream::tool_arguments;
+
+/// Prefix of call ids made up for function calls that Gemini sent without
+/// one. They are not sent back to the API.
+const GENERATED_CALL_ID_PREFIX: &str = "gemini_call_";
+
+pub(crate) async fn stream_gemini(
+    request: NativeRequest<'_>,
+    target: WireTarget<'_>,
+) -> Result<ResponseStream> {
+    let payload = build_gemini_request(&request)?;
+    stream_request(target, payload, process_gemini_sse).await
+}
+
+fn build_gemini_request(request: &NativeRequest<'_>) -> Result<Value> {
+    let prompt = request.prompt;
+    if prompt.output_schema.is_some() {
+        return Err(CodexErr::UnsupportedOperation(
+            "output_schema is not supported for the Gemini API".to_string(),
+        ));
+    }
+
+    let mut contents = Contents::default();
+    // `functionResponse` parts are matched to their call by name.
+    let mut call_names: HashMap<String, String> = HashMap::new();
+    for item in prompt.get_formatted_input() {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                let role = if role == "assistant" { "model" } else { "user" };
+                for content_item in content {
+                    if let Some(part) = content_part(content_item) {
+                        contents.push(role, part);
+                    }
+                }
+            }
+            ResponseItem::Reasoning {
+                encrypted_content: Some(signature),
+                ..
+            } => contents.pending_signature = Some(signature),
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                contents.push(
+                    "model",
+                    function_call_part(&call_id, &name, tool_arguments(&arguments)),
+                );
+                call_names.insert(call_id, name);
+            }
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => {
+                contents.push(
+                    "model",
+                    function_call_part(&call_id, &name, json!({"input": input})),
+                );
+                call_names.insert(call_id, name);
+            }
+            ResponseItem::FunctionCallOutput { call_id, output } => {
+                if let Some(name) = call_names.get(&call_id) {
+                    let response = if output.success == Some(false) {
+                        json!({"error": output.content})
+                    } else {
+                        json!({"content": output.content})
+                    };
+                    contents.push("user", function_response_part(&call_id, name, response));
+                }
+            }
+            ResponseItem::CustomToolCallOutput { call_id, output } => {
+                if let Some(name) = call_names.get(&call_id) {
+                    contents.push(
+                        "user",
+                        function_response_part(&call_id, name, json!({"content": output})),
+                    );
+                }
+            }
+            ResponseItem::Reasoning { .. }
+            | ResponseItem::LocalShellCall { .. }
+            | ResponseItem::WebSearchCall { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+
+    let mut generation_config = json!({"maxOutputTokens": request.max_output_tokens});
+    if let Some(budget) = thinking_budget(request) {
+        generation_config["thinkingConfig"] = json!({
+            "thinkingBudget": budget,
+            "includeThoughts": true,
+        });
+    }
+
+    let mut payload = json!({
+        "systemInstruction": {
+            "parts": [{"text": prompt.get_full_instructions(request.model_family)}],
+        },
+        "contents": contents.messages.into_inner(),
+        "generationConfig": generation_config,
+    });
+
+    let declarations: Vec<Value> = function_tools(&prompt.tools)?
+        .into_iter()
+        .map(|tool| {
+            json!({
+                "name": tool["name"],
+                "description": tool["description"],
+                "parametersJsonSchema": tool["parameters"],
+            })
+        })
+        .collect();
+    if !declarations.is_empty() {
+        payload["tools"] = json!([{"functionDeclarations": declarations}]);
+    }
+
+    Ok(payload)
+}
+
+/// `contents` under construction, plus the thought signature waiting for the
+/// next model part.
+struct Contents {
+    messages: MessageList,
+    pending_signature: Option<String>,
+}
+
+impl Default for Contents {
+    fn default() -> Self {
+        Self {
+            messages: MessageList::new("parts"),
+            pending_signature: None,
+        }
+    }
+}
+
+impl Contents {
+    fn push(&mut self, role: &str, mut part: Value) {
+        // A signature only belongs to the model part right after it.
+        if let Some(signature) = self.pending_signature.take()
+            && role == "model"
+        {
+            part["thoughtSignature"] = json!(signature);
+        }
+        self.messages.push(role, part);
+    }
+}
+
+fn content_part(item: ContentItem) -> Option<Value> {
+    match item {
+        ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            (!text.is_empty()).then(|| json!({"text": text}))
+        }
+        ContentItem::InputImage { image_url } => Some(match parse_data_url(&image_url) {
+            Some((mime_type, data)) => json!({
+                "inlineData": {"mimeType": mime_type, "data": data},
+            }),
+            None => json!({"fileData": {"fileUri": image_url}}),
+        }),
+    }
+}
+
+fn function_call_part(call_id: &str, name: &str, args: Value) -> Value {
+    let mut call = json!({"name": name, "args": args});
+    if !call_id.starts_with(GENERATED_CALL_ID_PREFIX) {
+        call["id"] = json!(call_id);
+    }
+    json!({"functionCall": call})
+}
+
+fn function_response_part(call_id: &str, name: &str, response: Value) -> Value {
+    let mut function_response = json!({"name": name, "response": response});
+    if !call_id.starts_with(GENERATED_CALL_ID_PREFIX) {
+        function_response["id"] = json!(call_id);
+    }
+    json!({"functionResponse": function_response})
+}
+
+/// Output of the current model turn that has not been emitted yet.
+#[derive(Default)]
+struct PendingOutput {
+    thoughts: String,
+    text: String,
+    /// Signature carried by a thought or text part.
+    signature: Option<String>,
+}
+
+impl PendingOutput {
+    /// Items for the buffered thoughts and text, in history order.
+    fn take_items(&mut self) -> Vec<ResponseItem> {
+        let PendingOutput {
+            thoughts,
+            text,
+            signature,
+        } = std::mem::take(self);
+        let mut items = Vec::new();
+        if !thoughts.is_empty() || signature.is_some() {
+            items.push(reasoning_item(thoughts, signature));
+        }
+        if !text.is_empty() {
+            items.push(ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText { text }],
+            });
+        }
+        items
+    }
+}
+
+fn reasoning_item(thoughts: String, signature: Option<String>) -> ResponseItem {
+    let summary = if thoughts.is_empty() {
+        Vec::new()
+    } else {
+        vec![ReasoningItemReasoningSummary::SummaryText { text: thoughts }]
+    };
+    ResponseItem::Reasoning {
+        id: String::new(),
+        summary,
+        content: None,
+        encrypted_content: signature,
+    }
+}
+
+fn token_usage(usage: &Value) -> TokenUsage {
+    let count = |key: &str| usage[key].as_u64().unwrap_or_default();
+    let reasoning_output_tokens = count("thoughtsTokenCount");
+    TokenUsage {
+        input_tokens: count("promptTokenCount"),
+        cached_input_tokens: count("cachedContentTokenCount"),
+        output_tokens: count("candidatesTokenCount") + reasoning_output_tokens,
+        reasoning_output_tokens,
+        total_tokens: count("totalTokenCount"),
+    }
+}
+
+/// Maps `streamGenerateContent` chunks onto [`ResponseEvent`]s. Text and
+/// thoughts are forwarded as deltas and emitted as items once a function call
+/// or the end of the turn follows them.
+async fn process_gemini_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent>>,
+    idle_timeout: Duration,
+    otel_event_manager: OtelEventManager,
+) {
+    let mut stream = stream.eventsource();
+    let mut pending = PendingOutput::default();
+    let mut response_id = String::new();
+    let mut usage = Value::Null;
+    let mut finished = false;
+    let mut created = false;
+
+    loop {
+        let start = std::time::Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        let duration = start.elapsed();
+        otel_event_manager.log_sse_event(&response, duration);
+
+        let sse = match response {
+            Ok(Some(Ok(ev))) => ev,
+            Ok(Some(Err(e))) => {
+                let _ = tx_event
+                    .send(Err(CodexErr::Stream(e.to_string(), None)))
+                    .await;
+                return;
+            }
+            Ok(None) => {
+                // Gemini ends the turn by closing the stream after a chunk
+                // with a `finishReason`.
+                if !finished {
+                    let _ = tx_event
+                        .send(Err(CodexErr::Stream(
+                            "stream closed before finishReason".into(),
+                            None,
+                        )))
+                        .await;
+                    return;
+                }
+                for item in pending.take_items() {
+                    let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone(item))).await;
+                }
+                let _ = tx_event
+                    .send(Ok(ResponseEvent::Completed {
+                        response_id,
+                        token_usage: (!usage.is_null()).then(|| token_usage(&usage)),
+                    }))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(CodexErr::Stream(
+                        "idle timeout waiting for SSE".into(),
+                        None,
+                    )))
+                    .await;
+                return;
+            }
+        };
+
+        let chunk: Value = match serde_json::from_str(&sse.data) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        trace!("gemini received SSE chunk: {chunk:?}");
+
+        if let Some(message) = chunk["error"]["message"].as_str() {
+            let _ = tx_event
+                .send(Err(CodexErr::Stream(message.to_string(), None)))
+                .await;
+            return;
+        }
+        if let Some(reason) = chunk["promptFeedback"]["blockReason"].as_str() {
+            let _ = tx_event
+                .send(Err(CodexErr::Stream(
+                    format!("prompt blocked by Gemini: {reason}"),
+                    None,
+                )))
+                .await;
+            return;
+        }
+
+        if !created {
+            created = true;
+            let _ = tx_event.send(Ok(ResponseEvent::Created)).await;
+        }
+        if let Some(id) = chunk["responseId"].as_str() {
+            response_id = id.to_string();
+        }
+        if chunk["usageMetadata"].is_object() {
+            usage = chunk["usageMetadata"].clone();
+        }
+
+        let candidate = &chunk["candidates"][0];
+        for part in candidate["content"]["parts"]
+            .as_array()
+            .into_iter()
+            .flatten()
+        {
+            let signature = part["thoughtSignature"].as_str().map(str::to_string);
+            if let Some(call) = part.get("functionCall") {
+                for item in pending.take_items() {
+                    let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone(item))).await;
+                }
+                if let Some(signature) = signature {
+                    let _ = tx_event
+                        .send(Ok(ResponseEvent::OutputItemDone(reasoning_item(
+                            String::new(),
+                            Some(signature),
+                        ))))
+                        .await;
+                }
+                let call_id = match call["id"].as_str() {
+                    Some(id) if !id.is_empty() => id.to_string(),
+                    _ => format!("{GENERATED_CALL_ID_PREFIX}{}", uuid::Uuid::new_v4()),
+                };
+                let item = ResponseItem::FunctionCall {
+                    id: None,
+                    name: call["name"].as_str().unwrap_or_default().to_string(),
+                    arguments: call
+                        .get("args")
+                        .map(Value::to_string)
+                        .unwrap_or_else(|| "{}".to_string()),
+                    call_id,
+                };
+                let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone(item))).await;
+                continue;
+            }
+
+            if signature.is_some() {
+                pending.signature = signature;
+            }
+            let Some(text) = part["text"].as_str().filter(|text| !text.is_empty()) else {
+                continue;
+            };
+            if part["thought"].as_bool() == Some(true) {
+                pending.thoughts.push_str(text);
+                let _ = tx_event
+                    .send(Ok(ResponseEvent::ReasoningSummaryDelta(text.to_string())))
+                    .await;
+            } else {
+                pending.text.push_str(text);
+                let _ = tx_event
+                    .send(Ok(ResponseEvent::OutputTextDelta(text.to_string())))
+                    .await;
+            }
+        }
+        if candidate["finishReason"].is_string() {
+            finished = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client_common::Prompt;
+    use crate::model_family::find_family_for_model;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn thought_signatures_and_parallel_calls_round_trip() {
+        let Some(model_family) = find_family_for_model("gemini-2.5-pro") else {
+            panic!("gemini-2.5-pro should have a model family");
+        };
+        let generated_id = format!("{GENERATED_CALL_ID_PREFIX}1");
+        let prompt = Prompt {
+            input: vec![
+                ResponseItem::Message {
+                    id: None,
+                    role: "user".to_string(),
+                    content: vec![ContentItem::InputText {
+                        text: "read both files".to_string(),
+                    }],
+                },
+                reasoning_item("Reading.".to_string(), Some("sig".to_string())),
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: "read_file".to_string(),
+                    arguments: r#"{"path":"a"}"#.to_string(),
+                    call_id: generated_id.clone(),
+                },
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: "read_file".to_string(),
+                    arguments: r#"{"path":"b"}"#.to_string(),
+                    call_id: "call-b".to_string(),
+                },
+                ResponseItem::FunctionCallOutput {
+                    call_id: generated_id,
+                    output: FunctionCallOutputPayload {
+                        content: "A".to_string(),
+                        success: Some(true),
+                    },
+                },
+                ResponseItem::FunctionCallOutput {
+                    call_id: "call-b".to_string(),
+                    output: FunctionCallOutputPayload {
+                        content: "missing".to_string(),
+                        success: Some(false),
+                    },
+                },
+            ],
+            ..Default::default()
+        };
+
+        let payload = build_gemini_request(&NativeRequest {
+            prompt: &prompt,
+            model_family: &model_family,
+            effort: None,
+            max_output_tokens: 65_536,
+        })
+        .expect("payload");
+
+        assert_eq!(
+            payload["contents"],
+            json!([
+                {"role": "user", "parts": [{"text": "read both files"}]},
+                {"role": "model", "parts": [
+                    {"functionCall": {"name": "read_file", "args": {"path": "a"}}, "thoughtSignature": "sig"},
+                    {"functionCall": {"name": "read_file", "args": {"path": "b"}, "id": "call-b"}},
+                ]},
+                {"role": "user", "parts": [
+                    {"functionResponse": {"name": "read_file", "response": {"content": "A"}}},
+                    {"functionResponse": {"name": "read_file", "response": {"error": "missing"}, "id": "call-b"}},
+                ]},
+            ])
+        );
+        assert_eq!(
+            payload["generationConfig"],
+            json!({
+                "maxOutputTokens": 65_536,
+                "thinkingConfig": {"thinkingBudget": 8_192, "includeThoughts": true},
+            })
+        );
+    }
+}
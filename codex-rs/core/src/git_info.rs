@@ -4,6 +4,7 @@ use std::path::PathBuf;
 
 use codex_app_server_protocol::GitSha;
 use codex_protocol::protocol::GitInfo;
+use codex_protocol::protocol::ReviewTarget;
 use futures::future::join_all;
 use serde::Deserialize;
 use serde::Serialize;
@@ -513,6 +514,92 @@ async fn diff_against_sha(cwd: &Path, sha: &GitSha) -> Option<String> {
     Some(diff)
 }
 
+/// Compute the diff a review of `target` should look at. Returns `None` if not
+/// in a git repository or if any git command fails (e.g. an unknown revision).
+pub async fn review_target_diff(cwd: &Path, target: &ReviewTarget) -> Option<String> {
+    match target {
+        ReviewTarget::UncommittedChanges => diff_against_sha(cwd, &GitSha::new("HEAD")).await,
+        ReviewTarget::Commit { sha } => {
+            let sha = resolve_commit(cwd, sha).await?;
+            let output = run_git_command_with_timeout(
+                &[
+                    "show",
+                    "--no-textconv",
+                    "--no-ext-diff",
+                    "--format=",
+                    &sha.0,
+                ],
+                cwd,
+            )
+            .await?;
+            if !output.status.success() {
+                return None;
+            }
+            String::from_utf8(output.stdout).ok()
+        }
+        ReviewTarget::Range { base, head } => {
+            let base = resolve_commit(cwd, base).await?;
+            let head = resolve_commit(cwd, head).await?;
+            let range = format!("{}..{}", base.0, head.0);
+            let output = run_git_command_with_timeout(
+                &["diff", "--no-textconv", "--no-ext-diff", &range],
+                cwd,
+            )
+            .await?;
+            if !output.status.success() {
+                return None;
+            }
+            String::from_utf8(output.stdout).ok()
+        }
+        ReviewTarget::BaseBranch { branch } => {
+            // Prefer the branch's upstream so a stale local branch does not
+            // inflate the diff.
+            let upstream = format!("{branch}@{{upstream}}");
+            let mut merge_base = None;
+            for candidate in [upstream.as_str(), branch.as_str()] {
+                let Some(candidate) = resolve_commit(cwd, candidate).await else {
+                    continue;
+                };
+                if let Some(output) =
+                    run_git_command_with_timeout(&["merge-base", "HEAD", &candidate.0], cwd).await
+                    && output.status.success()
+                    && let Ok(sha) = String::from_utf8(output.stdout)
+                {
+                    merge_base = Some(GitSha::new(sha.trim()));
+                    break;
+                }
+            }
+            diff_against_sha(cwd, &merge_base?).await
+        }
+    }
+}
+
+/// Resolves a caller-supplied revision to a commit SHA, so later git
+/// invocations only ever see a hex object name. Anything that looks like an
+/// option is rejected rather than handed to git.
+async fn resolve_commit(cwd: &Path, rev: &str) -> Option<GitSha> {
+    if rev.is_empty() || rev.starts_with('-') {
+        return None;
+    }
+    let spec = format!("{rev}^{{commit}}");
+    let output = run_git_command_with_timeout(
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            "--end-of-options",
+            &spec,
+        ],
+        cwd,
+    )
+    .await?;
+    if !output.status.success() {
+        return None;
+    }
+    let sha = String::from_utf8(output.stdout).ok()?;
+    Some(GitSha::new(sha.trim()))
+}
+
 /// Resolve the path that should be used for trust checks. Similar to
 /// `[get_git_repo_root]`, but resolves to the root of the main
 /// repository. Handles worktrees.
@@ -762,6 +849,115 @@ mod tests {
         (repo_path, branch)
     }
 
+    #[tokio::test]
+    async fn test_review_target_diff_covers_each_target() {
+        skip_if_sandbox!();
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let repo_path = create_test_git_repo(&temp_dir).await;
+        let base_branch = current_branch_name(&repo_path)
+            .await
+            .expect("current branch");
+
+        Command::new("git")
+            .args(["checkout", "-b", "feature"])
+            .current_dir(&repo_path)
+            .output()
+            .await
+            .expect("git checkout");
+        fs::write(repo_path.join("feature.txt"), "feature\n").unwrap();
+        Command::new("git")
+            .args(["add", "feature.txt"])
+            .current_dir(&repo_path)
+            .output()
+            .await
+            .expect("git add");
+        Command::new("git")
+            .args(["commit", "-m", "add feature"])
+            .current_dir(&repo_path)
+            .output()
+            .await
+            .expect("git commit");
+        fs::write(repo_path.join("untracked.txt"), "wip\n").unwrap();
+
+        let commit = recent_commits(&repo_path, 1).await.remove(0).sha;
+        let commit_diff = review_target_diff(&repo_path, &ReviewTarget::Commit { sha: commit })
+            .await
+            .expect("commit diff");
+        assert!(commit_diff.contains("+++ b/feature.txt"));
+        assert!(!commit_diff.contains("untracked.txt"));
+
+        let range_diff = review_target_diff(
+            &repo_path,
+            &ReviewTarget::Range {
+                base: base_branch.clone(),
+                head: "HEAD".to_string(),
+            },
+        )
+        .await
+        .expect("range diff");
+        assert_eq!(range_diff, commit_diff);
+
+        let uncommitted = review_target_diff(&repo_path, &ReviewTarget::UncommittedChanges)
+            .await
+            .expect("uncommitted diff");
+        assert!(uncommitted.contains("untracked.txt"));
+        assert!(!uncommitted.contains("feature.txt"));
+
+        let branch_diff = review_target_diff(
+            &repo_path,
+            &ReviewTarget::BaseBranch {
+                branch: base_branch,
+            },
+        )
+        .await
+        .expect("branch diff");
+        assert!(branch_diff.contains("feature.txt"));
+        assert!(branch_diff.contains("untracked.txt"));
+
+        assert!(
+            review_target_diff(
+                &repo_path,
+                &ReviewTarget::Commit {
+                    sha: "does-not-exist".to_string()
+                }
+            )
+            .await
+            .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_review_target_diff_rejects_option_like_refs() {
+        skip_if_sandbox!();
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let repo_path = create_test_git_repo(&temp_dir).await;
+        let injected_output = temp_dir.path().join("injected.txt");
+        let injected = format!("--output={}", injected_output.display());
+
+        let targets = [
+            ReviewTarget::Commit {
+                sha: injected.clone(),
+            },
+            ReviewTarget::Range {
+                base: injected.clone(),
+                head: "HEAD".to_string(),
+            },
+            ReviewTarget::Range {
+                base: "HEAD".to_string(),
+                head: injected.clone(),
+            },
+            ReviewTarget::BaseBranch {
+                branch: injected.clone(),
+            },
+        ];
+        for target in &targets {
+            assert_eq!(review_target_diff(&repo_path, target).await, None);
+        }
+        assert!(!injected_output.exists());
+    }
+
     #[tokio::test]
     async fn test_collect_git_info_non_git_directory() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
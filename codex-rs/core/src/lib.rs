@@ -5,8 +5,10 @@
 // the TUI or the tracing stack).
 #![deny(clippy::print_stdout, clippy::print_stderr)]
 
+mod anthropic_messages;
 mod apply_patch;
 pub mod auth;
+pub mod auth_storage;
 pub mod bash;
 mod chat_completions;
 mod client;
@@ -21,6 +23,7 @@ pub mod config_loader;
 pub mod config_profile;
 pub mod config_types;
 mod conversation_history;
+mod cost_tracker;
 pub mod custom_prompts;
 pub mod delegate_tool;
 mod environment_context;
@@ -31,19 +34,27 @@ pub mod exec_env;
 pub mod executor;
 pub mod features;
 mod flags;
+mod gemini;
 pub mod git_info;
 pub mod landlock;
 pub mod mcp;
 mod mcp_connection_manager;
 mod mcp_tool_call;
 mod message_history;
+mod model_failover;
+mod model_fixtures;
+mod model_pricing;
 mod model_provider_info;
 pub mod parse_command;
+mod rate_limit_governor;
 pub mod token_data;
 mod truncate;
 mod unified_exec;
 mod user_instructions;
+mod wire_stream;
 pub use model_provider_info::BUILT_IN_OSS_MODEL_PROVIDER_ID;
+pub use model_provider_info::FixtureMode;
+pub use model_provider_info::ModelFixtures;
 pub use model_provider_info::ModelProviderInfo;
 pub use model_provider_info::WireApi;
 pub use model_provider_info::built_in_model_providers;
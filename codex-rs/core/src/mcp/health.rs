@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use codex_protocol::protocol::McpServerHealth;
+use codex_protocol::protocol::McpServerStatus;
+
+use crate::config::Config;
+use crate::features::Feature;
+use crate::mcp_connection_manager::McpConnectionManager;
+
+/// Start every enabled MCP server in `config`, report whether it came up and
+/// shut it down again. Disabled servers are not included.
+pub async fn check_server_health(config: &Config) -> HashMap<String, McpServerStatus> {
+    // Keep the receiver alive so that status events are not reported as
+    // send failures while the servers are up.
+    let (tx_event, _rx_event) = async_channel::unbounded();
+    let (manager, errors) = match McpConnectionManager::new(
+        config.mcp_servers.clone(),
+        config.features.enabled(Feature::RmcpClient),
+        config.mcp_oauth_credentials_store_mode,
+        tx_event,
+    )
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            let error = format!("{err:#}");
+            return config
+                .mcp_servers
+                .iter()
+                .filter(|(_, cfg)| cfg.enabled)
+                .map(|(name, _)| (name.clone(), failed(error.clone())))
+                .collect();
+        }
+    };
+
+    let mut statuses = manager.server_statuses();
+    for (server_name, err) in errors {
+        statuses.insert(server_name, failed(format!("{err:#}")));
+    }
+    statuses
+}
+
+fn failed(error: String) -> McpServerStatus {
+    McpServerStatus {
+        health: McpServerHealth::Failed,
+        restarts: 0,
+        error: Some(error),
+    }
+}
@@ -0,0 +1,151 @@
+//! Applies the per-server `enabled_tools`/`disabled_tools` globs and the
+//! per-tool `approval` setting from [`McpToolPolicy`].
+
+use mcp_types::Tool;
+use wildmatch::WildMatch;
+
+use crate::config_types::McpToolApproval;
+use crate::config_types::McpToolPolicy;
+
+/// Whether `tool_name` may be exposed to the model. `disabled_tools` wins
+/// over `enabled_tools`.
+pub fn is_tool_enabled(policy: &McpToolPolicy, tool_name: &str) -> bool {
+    let matches_any = |patterns: &[String]| {
+        patterns
+            .iter()
+            .any(|p| WildMatch::new(p).matches(tool_name))
+    };
+    if policy.disabled_tools.as_deref().is_some_and(matches_any) {
+        return false;
+    }
+    policy.enabled_tools.as_deref().is_none_or(matches_any)
+}
+
+/// The approval setting for `tool_name`: its own entry, then the server-wide
+/// default, then [`McpToolApproval::Never`].
+pub fn tool_approval(policy: &McpToolPolicy, tool_name: &str) -> McpToolApproval {
+    policy
+        .tools
+        .get(tool_name)
+        .and_then(|tool| tool.approval)
+        .or(policy.approval)
+        .unwrap_or_default()
+}
+
+/// Whether calling `tool_name` needs the user's approval first. `tool` is
+/// the listed definition, if known; a tool that is not (yet) listed is
+/// treated as destructive.
+pub fn requires_approval(policy: &McpToolPolicy, tool_name: &str, tool: Option<&Tool>) -> bool {
+    match tool_approval(policy, tool_name) {
+        McpToolApproval::Never => false,
+        McpToolApproval::Always => true,
+        McpToolApproval::Destructive => tool.is_none_or(is_destructive),
+    }
+}
+
+/// Per the MCP spec, `destructiveHint` defaults to true unless the tool is
+/// marked read-only, so only an explicit opt-out counts as safe.
+fn is_destructive(tool: &Tool) -> bool {
+    tool.annotations.as_ref().is_none_or(|annotations| {
+        annotations.read_only_hint != Some(true) && annotations.destructive_hint != Some(false)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_types::McpToolConfig;
+    use mcp_types::ToolAnnotations;
+    use mcp_types::ToolInputSchema;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    fn tool(name: &str, destructive_hint: Option<bool>, read_only_hint: Option<bool>) -> Tool {
+        let annotated = destructive_hint.is_some() || read_only_hint.is_some();
+        Tool {
+            annotations: annotated.then_some(ToolAnnotations {
+                destructive_hint,
+                idempotent_hint: None,
+                open_world_hint: None,
+                read_only_hint,
+                title: None,
+            }),
+            description: None,
+            input_schema: ToolInputSchema {
+                properties: None,
+                required: None,
+                r#type: "object".to_string(),
+            },
+            name: name.to_string(),
+            output_schema: None,
+            title: None,
+        }
+    }
+
+    #[test]
+    fn disabled_tools_take_precedence_over_enabled_tools() {
+        let policy = McpToolPolicy {
+            enabled_tools: Some(vec!["get_*".to_string(), "list_*".to_string()]),
+            disabled_tools: Some(vec!["get_secret*".to_string()]),
+            ..Default::default()
+        };
+
+        let enabled: Vec<&str> = ["get_issue", "list_repos", "get_secret_key", "delete_repo"]
+            .into_iter()
+            .filter(|name| is_tool_enabled(&policy, name))
+            .collect();
+        assert_eq!(enabled, vec!["get_issue", "list_repos"]);
+        assert!(is_tool_enabled(&McpToolPolicy::default(), "anything"));
+    }
+
+    #[test]
+    fn approval_follows_tool_then_server_setting() {
+        let policy = McpToolPolicy {
+            approval: Some(McpToolApproval::Destructive),
+            tools: HashMap::from([(
+                "deploy".to_string(),
+                McpToolConfig {
+                    approval: Some(McpToolApproval::Always),
+                },
+            )]),
+            ..Default::default()
+        };
+
+        let check =
+            |policy: &McpToolPolicy, tool: Tool| requires_approval(policy, &tool.name, Some(&tool));
+        assert!(check(&policy, tool("deploy", Some(false), None)));
+        assert!(check(&policy, tool("drop_table", Some(true), None)));
+        assert!(!check(&policy, tool("select", Some(false), None)));
+        assert!(!check(&policy, tool("select", None, Some(true))));
+        assert!(!check(
+            &McpToolPolicy::default(),
+            tool("drop_table", Some(true), None)
+        ));
+    }
+
+    #[test]
+    fn unannotated_and_unknown_tools_count_as_destructive() {
+        let destructive = McpToolPolicy {
+            approval: Some(McpToolApproval::Destructive),
+            ..Default::default()
+        };
+        let unannotated = tool("write_file", None, None);
+        assert!(requires_approval(
+            &destructive,
+            "write_file",
+            Some(&unannotated)
+        ));
+        assert!(requires_approval(&destructive, "not_listed", None));
+
+        let always = McpToolPolicy {
+            approval: Some(McpToolApproval::Always),
+            ..Default::default()
+        };
+        assert!(requires_approval(&always, "not_listed", None));
+        assert!(!requires_approval(
+            &McpToolPolicy::default(),
+            "not_listed",
+            None
+        ));
+    }
+}
@@ -12,22 +12,41 @@ use std::env;
 use std::ffi::OsString;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+use std::sync::RwLock;
+use std::sync::Weak;
 use std::time::Duration;
 
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
+use async_channel::Sender;
 use codex_mcp_client::McpClient;
+use codex_rmcp_client::CreateMessage;
+use codex_rmcp_client::ListChangedKind;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
+use codex_rmcp_client::OnListChanged;
 use codex_rmcp_client::RmcpClient;
+use codex_rmcp_client::SendElicitation;
+use futures::FutureExt;
+use futures::future::BoxFuture;
 use mcp_types::ClientCapabilities;
+use mcp_types::CreateMessageRequestParams;
+use mcp_types::CreateMessageResult;
+use mcp_types::ElicitResult;
+use mcp_types::GetPromptRequestParams;
+use mcp_types::GetPromptResult;
 use mcp_types::Implementation;
+use mcp_types::ListPromptsRequestParams;
+use mcp_types::ListPromptsResult;
 use mcp_types::ListResourceTemplatesRequestParams;
 use mcp_types::ListResourceTemplatesResult;
 use mcp_types::ListResourcesRequestParams;
 use mcp_types::ListResourcesResult;
+use mcp_types::Prompt;
 use mcp_types::ReadResourceRequestParams;
 use mcp_types::ReadResourceResult;
+use mcp_types::RequestId;
 use mcp_types::Resource;
 use mcp_types::ResourceTemplate;
 use mcp_types::Tool;
@@ -35,12 +54,26 @@ use mcp_types::Tool;
 use serde_json::json;
 use sha1::Digest;
 use sha1::Sha1;
+use tokio::sync::Notify;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
 use tokio::task::JoinSet;
 use tracing::info;
 use tracing::warn;
 
 use crate::config_types::McpServerConfig;
 use crate::config_types::McpServerTransportConfig;
+use crate::config_types::McpToolPolicy;
+use crate::mcp::tool_policy::is_tool_enabled;
+use crate::mcp::tool_policy::requires_approval;
+use crate::protocol::ElicitationRequestEvent;
+use crate::protocol::Event;
+use crate::protocol::EventMsg;
+use crate::protocol::McpListChangedEvent;
+use crate::protocol::McpListKind;
+use crate::protocol::McpServerHealth;
+use crate::protocol::McpServerStatus;
+use crate::protocol::McpServerStatusEvent;
 
 /// Delimiter used to separate the server name from the tool name in a fully
 /// qualified tool name.
@@ -60,6 +93,128 @@ const DEFAULT_TOOL_TIMEOUT: Duration = Duration::from_secs(60);
 /// spawned successfully.
 pub type ClientStartErrors = HashMap<String, anyhow::Error>;
 
+/// Event id used for [`EventMsg::ElicitationRequest`]. Elicitations are
+/// initiated by the server, so they do not belong to any submission.
+const ELICITATION_EVENT_ID: &str = "mcp_elicitation";
+
+/// Event id used for [`EventMsg::McpListChanged`].
+const LIST_CHANGED_EVENT_ID: &str = "mcp_list_changed";
+
+/// Event id used for [`EventMsg::McpServerStatus`].
+const SERVER_STATUS_EVENT_ID: &str = "mcp_server_status";
+
+/// How often a supervisor checks that its server is still connected.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Delay before the first restart attempt; doubled after every failure.
+const INITIAL_RESTART_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Restart attempts before a server is reported as failed for good.
+const MAX_RESTART_ATTEMPTS: u32 = 5;
+
+type SharedTools = Arc<RwLock<HashMap<String, ToolInfo>>>;
+
+/// A `sampling/createMessage` request from an MCP server, waiting to be
+/// answered with a completion from the session's model.
+pub(crate) struct SamplingRequest {
+    pub(crate) server_name: String,
+    pub(crate) params: CreateMessageRequestParams,
+    /// The server's `sampling_token_budget` for the whole session.
+    pub(crate) token_budget: u64,
+    pub(crate) responder: oneshot::Sender<Result<CreateMessageResult>>,
+}
+
+/// Build the sampling callback for a server: requests are queued on
+/// `tx_sampling` for the session to answer.
+fn make_create_message(
+    server_name: String,
+    token_budget: u64,
+    tx_sampling: mpsc::UnboundedSender<SamplingRequest>,
+) -> CreateMessage {
+    Box::new(move |_id, params| {
+        let (responder, rx) = oneshot::channel();
+        let queued = tx_sampling.send(SamplingRequest {
+            server_name: server_name.clone(),
+            params,
+            token_budget,
+            responder,
+        });
+        async move {
+            queued.map_err(|_| anyhow!("sampling is no longer available"))?;
+            rx.await.context("sampling request was dropped")?
+        }
+        .boxed()
+    })
+}
+
+type ResponderMap = HashMap<(String, RequestId), oneshot::Sender<ElicitResult>>;
+
+/// Tracks elicitation requests that were surfaced to the user and are waiting
+/// for an answer, keyed by server name and the server-assigned request id.
+#[derive(Clone, Default)]
+struct ElicitationRequestManager {
+    requests: Arc<StdMutex<ResponderMap>>,
+}
+
+impl ElicitationRequestManager {
+    /// Build the callback handed to the client for `server_name`: it emits an
+    /// [`EventMsg::ElicitationRequest`] and waits for [`Self::resolve`].
+    fn make_sender(&self, server_name: String, tx_event: Sender<Event>) -> SendElicitation {
+        let requests = self.requests.clone();
+        Box::new(move |id, elicitation| {
+            let requests = requests.clone();
+            let tx_event = tx_event.clone();
+            let server_name = server_name.clone();
+            async move {
+                let (tx, rx) = oneshot::channel();
+                {
+                    let mut lock = requests
+                        .lock()
+                        .map_err(|_| anyhow!("elicitation request lock poisoned"))?;
+                    lock.insert((server_name.clone(), id.clone()), tx);
+                }
+                tx_event
+                    .send(Event {
+                        id: ELICITATION_EVENT_ID.to_string(),
+                        msg: EventMsg::ElicitationRequest(ElicitationRequestEvent {
+                            server_name,
+                            id,
+                            message: elicitation.message,
+                            requested_schema: elicitation.requested_schema,
+                        }),
+                    })
+                    .await
+                    .context("failed to surface elicitation request")?;
+                rx.await.context("elicitation request was dropped")
+            }
+            .boxed()
+        })
+    }
+
+    fn resolve(&self, server_name: &str, id: RequestId, response: ElicitResult) -> Result<()> {
+        let responder = self
+            .requests
+            .lock()
+            .map_err(|_| anyhow!("elicitation request lock poisoned"))?
+            .remove(&(server_name.to_string(), id.clone()))
+            .ok_or_else(|| {
+                anyhow!("no pending elicitation {id:?} for MCP server '{server_name}'")
+            })?;
+        responder
+            .send(response)
+            .map_err(|_| anyhow!("MCP server '{server_name}' is no longer waiting for {id:?}"))
+    }
+
+    /// Drop every pending responder, failing the requests still waiting for
+    /// an answer.
+    fn clear(&self) {
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.clear();
+        }
+    }
+}
+
 fn qualify_tools(tools: Vec<ToolInfo>) -> HashMap<String, ToolInfo> {
     let mut used_names = HashSet::new();
     let mut qualified_tools = HashMap::new();
@@ -92,16 +247,110 @@ fn qualify_tools(tools: Vec<ToolInfo>) -> HashMap<String, ToolInfo> {
     qualified_tools
 }
 
+#[derive(Clone)]
 struct ToolInfo {
     server_name: String,
     tool_name: String,
     tool: Tool,
 }
 
+/// Spawns a server and runs `initialize`, yielding a ready client. Called
+/// again by the supervisor whenever the server has to be restarted.
+type ClientFactory = Arc<dyn Fn() -> BoxFuture<'static, Result<McpClientAdapter>> + Send + Sync>;
+
+/// A configured server. The client is swapped out by the supervisor whenever
+/// the server is restarted.
 struct ManagedClient {
-    client: McpClientAdapter,
+    server_name: String,
+    client: RwLock<Option<McpClientAdapter>>,
+    status: StdMutex<McpServerStatus>,
+    factory: ClientFactory,
+    /// Wakes the supervisor so it checks the server's health right away.
+    health_check: Arc<Notify>,
     startup_timeout: Duration,
     tool_timeout: Option<Duration>,
+    tool_policy: McpToolPolicy,
+}
+
+impl ManagedClient {
+    fn new(
+        server_name: String,
+        factory: ClientFactory,
+        startup_timeout: Duration,
+        tool_timeout: Option<Duration>,
+        tool_policy: McpToolPolicy,
+    ) -> Self {
+        Self {
+            server_name,
+            client: RwLock::new(None),
+            status: StdMutex::new(McpServerStatus {
+                health: McpServerHealth::Ready,
+                restarts: 0,
+                error: None,
+            }),
+            factory,
+            health_check: Arc::new(Notify::new()),
+            startup_timeout,
+            tool_timeout,
+            tool_policy,
+        }
+    }
+
+    /// The live client, or an error while the server is down.
+    fn client(&self) -> Result<McpClientAdapter> {
+        self.current_client().ok_or_else(|| {
+            let status = self.status();
+            match status.error {
+                Some(error) => anyhow!(
+                    "MCP server '{}' is {}: {error}",
+                    self.server_name,
+                    status.health
+                ),
+                None => anyhow!("MCP server '{}' is {}", self.server_name, status.health),
+            }
+        })
+    }
+
+    fn current_client(&self) -> Option<McpClientAdapter> {
+        self.client
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
+    fn set_client(&self, client: Option<McpClientAdapter>) {
+        *self
+            .client
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = client;
+    }
+
+    fn status(&self) -> McpServerStatus {
+        self.status
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Update the health and return the new status.
+    fn set_health(&self, health: McpServerHealth, error: Option<String>) -> McpServerStatus {
+        let mut status = self
+            .status
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        status.health = health;
+        status.error = error;
+        status.clone()
+    }
+
+    /// Pass `result` through, asking the supervisor to check on the server
+    /// when the request failed: its transport may be gone.
+    fn observe<T>(&self, result: Result<T>) -> Result<T> {
+        if result.is_err() {
+            self.health_check.notify_one();
+        }
+        result
+    }
 }
 
 #[derive(Clone)]
@@ -121,11 +370,22 @@ impl McpClientAdapter {
         cwd: Option<PathBuf>,
         params: mcp_types::InitializeRequestParams,
         startup_timeout: Duration,
+        send_elicitation: SendElicitation,
+        create_message: Option<CreateMessage>,
+        on_list_changed: OnListChanged,
     ) -> Result<Self> {
         if use_rmcp_client {
             let client =
                 Arc::new(RmcpClient::new_stdio_client(program, args, env, &env_vars, cwd).await?);
-            client.initialize(params, Some(startup_timeout)).await?;
+            client
+                .initialize(
+                    params,
+                    Some(startup_timeout),
+                    send_elicitation,
+                    create_message,
+                    on_list_changed,
+                )
+                .await?;
             Ok(McpClientAdapter::Rmcp(client))
         } else {
             let client =
@@ -145,6 +405,9 @@ impl McpClientAdapter {
         params: mcp_types::InitializeRequestParams,
         startup_timeout: Duration,
         store_mode: OAuthCredentialsStoreMode,
+        send_elicitation: SendElicitation,
+        create_message: Option<CreateMessage>,
+        on_list_changed: OnListChanged,
     ) -> Result<Self> {
         let client = Arc::new(
             RmcpClient::new_streamable_http_client(
@@ -157,7 +420,15 @@ impl McpClientAdapter {
             )
             .await?,
         );
-        client.initialize(params, Some(startup_timeout)).await?;
+        client
+            .initialize(
+                params,
+                Some(startup_timeout),
+                send_elicitation,
+                create_message,
+                on_list_changed,
+            )
+            .await?;
         Ok(McpClientAdapter::Rmcp(client))
     }
 
@@ -172,6 +443,34 @@ impl McpClientAdapter {
         }
     }
 
+    /// List every tool the server exposes, following `next_cursor` until the
+    /// last page.
+    async fn list_all_tools(&self, timeout: Option<Duration>) -> Result<Vec<Tool>> {
+        let mut collected = Vec::new();
+        let mut cursor: Option<String> = None;
+        // Every cursor seen so far, so a server that cycles through pages
+        // (A -> B -> A) cannot keep us paging forever.
+        let mut seen_cursors = HashSet::new();
+        loop {
+            let params = cursor
+                .as_ref()
+                .map(|next| mcp_types::ListToolsRequestParams {
+                    cursor: Some(next.clone()),
+                });
+            let response = self.list_tools(params, timeout).await?;
+            collected.extend(response.tools);
+            match response.next_cursor {
+                Some(next) => {
+                    if !seen_cursors.insert(next.clone()) {
+                        return Err(anyhow!("tools/list returned duplicate cursor {next}"));
+                    }
+                    cursor = Some(next);
+                }
+                None => return Ok(collected),
+            }
+        }
+    }
+
     async fn list_resources(
         &self,
         params: Option<mcp_types::ListResourcesRequestParams>,
@@ -213,6 +512,51 @@ impl McpClientAdapter {
         }
     }
 
+    async fn list_prompts(
+        &self,
+        params: Option<mcp_types::ListPromptsRequestParams>,
+        timeout: Option<Duration>,
+    ) -> Result<mcp_types::ListPromptsResult> {
+        match self {
+            McpClientAdapter::Legacy(_) => Ok(ListPromptsResult {
+                next_cursor: None,
+                prompts: Vec::new(),
+            }),
+            McpClientAdapter::Rmcp(client) => client.list_prompts(params, timeout).await,
+        }
+    }
+
+    /// Whether the server advertised the `prompts` capability.
+    async fn supports_prompts(&self) -> bool {
+        match self {
+            McpClientAdapter::Legacy(_) => false,
+            McpClientAdapter::Rmcp(client) => client
+                .server_capabilities()
+                .await
+                .is_ok_and(|capabilities| capabilities.prompts.is_some()),
+        }
+    }
+
+    async fn get_prompt(
+        &self,
+        params: mcp_types::GetPromptRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<mcp_types::GetPromptResult> {
+        match self {
+            McpClientAdapter::Legacy(_) => Err(anyhow!(
+                "prompts/get is not supported by legacy MCP clients"
+            )),
+            McpClientAdapter::Rmcp(client) => client.get_prompt(params, timeout).await,
+        }
+    }
+
+    async fn is_closed(&self) -> bool {
+        match self {
+            McpClientAdapter::Legacy(client) => client.is_closed(),
+            McpClientAdapter::Rmcp(client) => client.is_transport_closed().await,
+        }
+    }
+
     async fn call_tool(
         &self,
         name: String,
@@ -233,10 +577,18 @@ pub(crate) struct McpConnectionManager {
     ///
     /// The server name originates from the keys of the `mcp_servers` map in
     /// the user configuration.
-    clients: HashMap<String, ManagedClient>,
+    clients: Arc<HashMap<String, Arc<ManagedClient>>>,
+
+    /// Fully qualified tool name -> tool instance. Refreshed in the
+    /// background when a server announces that its tools changed.
+    tools: SharedTools,
+
+    /// Elicitation requests awaiting an answer from the user.
+    elicitation_requests: ElicitationRequestManager,
 
-    /// Fully qualified tool name -> tool instance.
-    tools: HashMap<String, ToolInfo>,
+    /// Sampling requests from servers with a `sampling_token_budget`. Taken
+    /// once by the session, which answers them.
+    sampling_requests: StdMutex<Option<mpsc::UnboundedReceiver<SamplingRequest>>>,
 }
 
 impl McpConnectionManager {
@@ -248,10 +600,15 @@ impl McpConnectionManager {
     ///
     /// Servers that fail to start are reported in `ClientStartErrors`: the
     /// user should be informed about these errors.
+    ///
+    /// Elicitation requests from the servers are forwarded on `tx_event` and
+    /// answered via [`Self::resolve_elicitation`]. List change notifications
+    /// are reported on `tx_event` as well, after re-listing.
     pub async fn new(
         mcp_servers: HashMap<String, McpServerConfig>,
         use_rmcp_client: bool,
         store_mode: OAuthCredentialsStoreMode,
+        tx_event: Sender<Event>,
     ) -> Result<(Self, ClientStartErrors)> {
         // Early exit if no servers are configured.
         if mcp_servers.is_empty() {
@@ -261,6 +618,9 @@ impl McpConnectionManager {
         // Launch all configured servers concurrently.
         let mut join_set = JoinSet::new();
         let mut errors = ClientStartErrors::new();
+        let elicitation_requests = ElicitationRequestManager::default();
+        let (tx_list_changed, rx_list_changed) = mpsc::unbounded_channel();
+        let (tx_sampling, rx_sampling) = mpsc::unbounded_channel();
 
         for (server_name, cfg) in mcp_servers {
             // Validate server name before spawning
@@ -278,89 +638,39 @@ impl McpConnectionManager {
 
             let startup_timeout = cfg.startup_timeout_sec.unwrap_or(DEFAULT_STARTUP_TIMEOUT);
             let tool_timeout = cfg.tool_timeout_sec.unwrap_or(DEFAULT_TOOL_TIMEOUT);
+            let tool_policy = cfg.tool_policy;
 
-            let resolved_bearer_token = match &cfg.transport {
-                McpServerTransportConfig::StreamableHttp {
-                    bearer_token_env_var,
-                    ..
-                } => resolve_bearer_token(&server_name, bearer_token_env_var.as_deref()),
-                _ => Ok(None),
-            };
+            let factory = client_factory(
+                server_name.clone(),
+                cfg.transport,
+                use_rmcp_client,
+                store_mode,
+                startup_timeout,
+                cfg.sampling_token_budget,
+                elicitation_requests.clone(),
+                tx_event.clone(),
+                tx_list_changed.clone(),
+                tx_sampling.clone(),
+            );
 
             join_set.spawn(async move {
-                let McpServerConfig { transport, .. } = cfg;
-                let params = mcp_types::InitializeRequestParams {
-                    capabilities: ClientCapabilities {
-                        experimental: None,
-                        roots: None,
-                        sampling: None,
-                        // https://modelcontextprotocol.io/specification/2025-06-18/client/elicitation#capabilities
-                        // indicates this should be an empty object.
-                        elicitation: Some(json!({})),
-                    },
-                    client_info: Implementation {
-                        name: "codex-mcp-client".to_owned(),
-                        version: env!("CARGO_PKG_VERSION").to_owned(),
-                        title: Some("Codex".into()),
-                        // This field is used by Codex when it is an MCP
-                        // server: it should not be used when Codex is
-                        // an MCP client.
-                        user_agent: None,
-                    },
-                    protocol_version: mcp_types::MCP_SCHEMA_VERSION.to_owned(),
-                };
-
-                let client = match transport {
-                    McpServerTransportConfig::Stdio {
-                        command,
-                        args,
-                        env,
-                        env_vars,
-                        cwd,
-                    } => {
-                        let command_os: OsString = command.into();
-                        let args_os: Vec<OsString> = args.into_iter().map(Into::into).collect();
-                        McpClientAdapter::new_stdio_client(
-                            use_rmcp_client,
-                            command_os,
-                            args_os,
-                            env,
-                            env_vars,
-                            cwd,
-                            params,
-                            startup_timeout,
-                        )
-                        .await
-                    }
-                    McpServerTransportConfig::StreamableHttp {
-                        url,
-                        http_headers,
-                        env_http_headers,
-                        ..
-                    } => {
-                        McpClientAdapter::new_streamable_http_client(
-                            server_name.clone(),
-                            url,
-                            resolved_bearer_token.unwrap_or_default(),
-                            http_headers,
-                            env_http_headers,
-                            params,
-                            startup_timeout,
-                            store_mode,
-                        )
-                        .await
-                    }
-                }
-                .map(|c| (c, startup_timeout));
-
-                ((server_name, tool_timeout), client)
+                let client = factory().await;
+                let managed = ManagedClient::new(
+                    server_name.clone(),
+                    factory,
+                    startup_timeout,
+                    Some(tool_timeout),
+                    tool_policy,
+                );
+                (server_name, managed, client)
             });
         }
 
-        let mut clients: HashMap<String, ManagedClient> = HashMap::with_capacity(join_set.len());
+        let mut clients: HashMap<String, Arc<ManagedClient>> =
+            HashMap::with_capacity(join_set.len());
 
         while let Some(res) = join_set.join_next().await {
-            let ((server_name, tool_timeout), client_res) = match res {
+            let (server_name, managed, client_res) = match res {
                 Ok(result) => result,
                 Err(e) => {
                     warn!("Task panic when starting MCP server: {e:#}");
@@ -369,20 +679,15 @@ impl McpConnectionManager {
             };
 
             match client_res {
-                Ok((client, startup_timeout)) => {
-                    clients.insert(
-                        server_name,
-                        ManagedClient {
-                            client,
-                            startup_timeout,
-                            tool_timeout: Some(tool_timeout),
-                        },
-                    );
-                }
+                Ok(client) => managed.set_client(Some(client)),
                 Err(e) => {
-                    errors.insert(server_name, e);
+                    // The supervisor keeps trying to start the server.
+                    managed.set_health(McpServerHealth::Restarting, Some(format!("{e:#}")));
+                    managed.health_check.notify_one();
+                    errors.insert(server_name.clone(), e);
                 }
             }
+            clients.insert(server_name, Arc::new(managed));
         }
 
         let all_tools = match list_all_tools(&clients).await {
@@ -393,15 +698,43 @@ impl McpConnectionManager {
             }
         };
 
-        let tools = qualify_tools(all_tools);
+        let clients = Arc::new(clients);
+        let tools = Arc::new(RwLock::new(qualify_tools(all_tools)));
+
+        for managed in clients.values() {
+            tokio::spawn(supervise(
+                Arc::downgrade(managed),
+                Arc::clone(&managed.health_check),
+                Arc::clone(&tools),
+                tx_event.clone(),
+            ));
+        }
 
-        Ok((Self { clients, tools }, errors))
+        // Only the per-server callbacks keep the channel open, so the refresh
+        // task ends once the clients are dropped.
+        drop(tx_list_changed);
+        tokio::spawn(refresh_on_list_changed(
+            rx_list_changed,
+            Arc::downgrade(&clients),
+            Arc::clone(&tools),
+            tx_event,
+        ));
+
+        Ok((
+            Self {
+                clients,
+                tools,
+                elicitation_requests,
+                sampling_requests: StdMutex::new(Some(rx_sampling)),
+            },
+            errors,
+        ))
     }
 
     /// Returns a single map that contains all tools. Each key is the
     /// fully-qualified name for the tool.
     pub fn list_all_tools(&self) -> HashMap<String, Tool> {
-        self.tools
+        read_tools(&self.tools)
             .iter()
             .map(|(name, tool)| (name.clone(), tool.tool.clone()))
             .collect()
@@ -412,9 +745,11 @@ impl McpConnectionManager {
     pub async fn list_all_resources(&self) -> HashMap<String, Vec<Resource>> {
         let mut join_set = JoinSet::new();
 
-        for (server_name, managed_client) in &self.clients {
+        for (server_name, managed_client) in self.clients.iter() {
             let server_name_cloned = server_name.clone();
-            let client_clone = managed_client.client.clone();
+            let Some(client_clone) = managed_client.current_client() else {
+                continue;
+            };
             let timeout = managed_client.tool_timeout;
 
             join_set.spawn(async move {
@@ -472,9 +807,11 @@ impl McpConnectionManager {
     pub async fn list_all_resource_templates(&self) -> HashMap<String, Vec<ResourceTemplate>> {
         let mut join_set = JoinSet::new();
 
-        for (server_name, managed_client) in &self.clients {
+        for (server_name, managed_client) in self.clients.iter() {
             let server_name_cloned = server_name.clone();
-            let client_clone = managed_client.client.clone();
+            let Some(client_clone) = managed_client.current_client() else {
+                continue;
+            };
             let timeout = managed_client.tool_timeout;
 
             join_set.spawn(async move {
@@ -534,6 +871,73 @@ impl McpConnectionManager {
         aggregated
     }
 
+    /// Returns a single map that contains all prompts. Each key is the
+    /// server name and the value is a vector of prompts.
+    pub async fn list_all_prompts(&self) -> HashMap<String, Vec<Prompt>> {
+        let mut join_set = JoinSet::new();
+
+        for (server_name, managed_client) in self.clients.iter() {
+            let server_name_cloned = server_name.clone();
+            let Some(client_clone) = managed_client.current_client() else {
+                continue;
+            };
+            let timeout = managed_client.tool_timeout;
+
+            join_set.spawn(async move {
+                let mut collected: Vec<Prompt> = Vec::new();
+                if !client_clone.supports_prompts().await {
+                    return (server_name_cloned, Ok(collected));
+                }
+                let mut cursor: Option<String> = None;
+
+                loop {
+                    let params = cursor.as_ref().map(|next| ListPromptsRequestParams {
+                        cursor: Some(next.clone()),
+                    });
+                    let response = match client_clone.list_prompts(params, timeout).await {
+                        Ok(result) => result,
+                        Err(err) => return (server_name_cloned, Err(err)),
+                    };
+
+                    collected.extend(response.prompts);
+
+                    match response.next_cursor {
+                        Some(next) => {
+                            if cursor.as_ref() == Some(&next) {
+                                return (
+                                    server_name_cloned,
+                                    Err(anyhow!("prompts/list returned duplicate cursor")),
+                                );
+                            }
+                            cursor = Some(next);
+                        }
+                        None => return (server_name_cloned, Ok(collected)),
+                    }
+                }
+            });
+        }
+
+        let mut aggregated: HashMap<String, Vec<Prompt>> = HashMap::new();
+
+        while let Some(join_res) = join_set.join_next().await {
+            match join_res {
+                Ok((server_name, Ok(prompts))) => {
+                    if !prompts.is_empty() {
+                        aggregated.insert(server_name, prompts);
+                    }
+                }
+                Ok((server_name, Err(err))) => {
+                    warn!("Failed to list prompts for MCP server '{server_name}': {err:#}");
+                }
+                Err(err) => {
+                    warn!("Task panic when listing prompts for MCP server: {err:#}");
+                }
+            }
+        }
+
+        aggregated
+    }
+
     /// Invoke the tool indicated by the (server, tool) pair.
     pub async fn call_tool(
         &self,
@@ -545,15 +949,34 @@ impl McpConnectionManager {
             .clients
             .get(server)
             .ok_or_else(|| anyhow!("unknown MCP server '{server}'"))?;
-        let client = &managed.client;
+        if !is_tool_enabled(&managed.tool_policy, tool) {
+            return Err(anyhow!(
+                "tool `{tool}` is disabled for MCP server '{server}'"
+            ));
+        }
+        let client = managed.client()?;
         let timeout = managed.tool_timeout;
 
-        client
-            .call_tool(tool.to_string(), arguments, timeout)
-            .await
+        managed
+            .observe(client.call_tool(tool.to_string(), arguments, timeout).await)
             .with_context(|| format!("tool call failed for `{server}/{tool}`"))
     }
 
+    /// Deliver the user's answer to a pending elicitation request.
+    pub fn resolve_elicitation(
+        &self,
+        server_name: &str,
+        id: RequestId,
+        response: ElicitResult,
+    ) -> Result<()> {
+        self.elicitation_requests.resolve(server_name, id, response)
+    }
+
+    /// Fail all elicitation requests that are still waiting for an answer.
+    pub fn drop_pending_elicitations(&self) {
+        self.elicitation_requests.clear();
+    }
+
     /// List resources from the specified server.
     pub async fn list_resources(
         &self,
@@ -564,12 +987,11 @@ impl McpConnectionManager {
             .clients
             .get(server)
             .ok_or_else(|| anyhow!("unknown MCP server '{server}'"))?;
-        let client = managed.client.clone();
+        let client = managed.client()?;
         let timeout = managed.tool_timeout;
 
-        client
-            .list_resources(params, timeout)
-            .await
+        managed
+            .observe(client.list_resources(params, timeout).await)
             .with_context(|| format!("resources/list failed for `{server}`"))
     }
 
@@ -583,15 +1005,40 @@ impl McpConnectionManager {
             .clients
             .get(server)
             .ok_or_else(|| anyhow!("unknown MCP server '{server}'"))?;
-        let client = managed.client.clone();
+        let client = managed.client()?;
         let timeout = managed.tool_timeout;
 
-        client
-            .list_resource_templates(params, timeout)
-            .await
+        managed
+            .observe(client.list_resource_templates(params, timeout).await)
             .with_context(|| format!("resources/templates/list failed for `{server}`"))
     }
 
+    /// Fetch a prompt from the specified server, expanded with `arguments`.
+    pub async fn get_prompt(
+        &self,
+        server: &str,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<GetPromptResult> {
+        let managed = self
+            .clients
+            .get(server)
+            .ok_or_else(|| anyhow!("unknown MCP server '{server}'"))?;
+        let client = managed.client()?;
+        if !client.supports_prompts().await {
+            return Err(anyhow!("MCP server '{server}' does not support prompts"));
+        }
+        let timeout = managed.tool_timeout;
+        let params = GetPromptRequestParams {
+            arguments: (!arguments.is_empty()).then(|| json!(arguments)),
+            name: name.to_string(),
+        };
+
+        managed
+            .observe(client.get_prompt(params, timeout).await)
+            .with_context(|| format!("prompts/get failed for `{server}/{name}`"))
+    }
+
     /// Read a resource from the specified server.
     pub async fn read_resource(
         &self,
@@ -602,23 +1049,402 @@ impl McpConnectionManager {
             .clients
             .get(server)
             .ok_or_else(|| anyhow!("unknown MCP server '{server}'"))?;
-        let client = managed.client.clone();
+        let client = managed.client()?;
         let timeout = managed.tool_timeout;
         let uri = params.uri.clone();
 
-        client
-            .read_resource(params, timeout)
-            .await
+        managed
+            .observe(client.read_resource(params, timeout).await)
             .with_context(|| format!("resources/read failed for `{server}` ({uri})"))
     }
 
+    /// Hand out the queue of sampling requests; `None` after the first call.
+    pub(crate) fn take_sampling_requests(
+        &self,
+    ) -> Option<mpsc::UnboundedReceiver<SamplingRequest>> {
+        self.sampling_requests
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take()
+    }
+
+    /// Health of every server Codex tried to start, keyed by server name.
+    pub fn server_statuses(&self) -> HashMap<String, McpServerStatus> {
+        self.clients
+            .iter()
+            .map(|(server_name, managed)| (server_name.clone(), managed.status()))
+            .collect()
+    }
+
+    /// Whether the user must approve a call to `tool` on `server` first, per
+    /// the server's `approval` settings and the tool's annotations. Fails
+    /// closed: an unknown server always needs approval, and a tool missing
+    /// from the listing (e.g. mid-refresh) is treated as destructive.
+    pub fn tool_requires_approval(&self, server: &str, tool: &str) -> bool {
+        let Some(managed) = self.clients.get(server) else {
+            return true;
+        };
+        let tools = read_tools(&self.tools);
+        let listed = tools
+            .values()
+            .find(|info| info.server_name == server && info.tool_name == tool)
+            .map(|info| &info.tool);
+        requires_approval(&managed.tool_policy, tool, listed)
+    }
+
     pub fn parse_tool_name(&self, tool_name: &str) -> Option<(String, String)> {
-        self.tools
+        read_tools(&self.tools)
             .get(tool_name)
             .map(|tool| (tool.server_name.clone(), tool.tool_name.clone()))
     }
 }
 
+fn read_tools(tools: &SharedTools) -> std::sync::RwLockReadGuard<'_, HashMap<String, ToolInfo>> {
+    tools
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Drop the tools the user disabled for a server.
+fn enabled_tools(tool_policy: &McpToolPolicy, tools: Vec<Tool>) -> Vec<Tool> {
+    tools
+        .into_iter()
+        .filter(|tool| is_tool_enabled(tool_policy, &tool.name))
+        .collect()
+}
+
+/// Replace the tools of `server_name` with `server_tools`, re-qualifying the
+/// whole map so names stay unique across servers.
+fn replace_server_tools(tools: &SharedTools, server_name: &str, server_tools: Vec<Tool>) {
+    let mut guard = tools
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let mut all_tools: Vec<ToolInfo> = guard
+        .values()
+        .filter(|tool| tool.server_name != server_name)
+        .cloned()
+        .collect();
+    all_tools.extend(server_tools.into_iter().map(|tool| ToolInfo {
+        server_name: server_name.to_string(),
+        tool_name: tool.name.clone(),
+        tool,
+    }));
+    *guard = qualify_tools(all_tools);
+}
+
+/// Re-list whatever a server announced as changed, so that the next turn
+/// sees the server's current tools, and let the user know about it.
+async fn refresh_on_list_changed(
+    mut rx_list_changed: mpsc::UnboundedReceiver<(String, ListChangedKind)>,
+    clients: Weak<HashMap<String, Arc<ManagedClient>>>,
+    tools: SharedTools,
+    tx_event: Sender<Event>,
+) {
+    while let Some((server_name, kind)) = rx_list_changed.recv().await {
+        let Some(managed) = clients
+            .upgrade()
+            .and_then(|clients| clients.get(&server_name).cloned())
+        else {
+            continue;
+        };
+        let Some(client) = managed.current_client() else {
+            continue;
+        };
+        let (kind, count) = match kind {
+            ListChangedKind::Tools => {
+                match client.list_all_tools(Some(managed.startup_timeout)).await {
+                    Ok(result) => {
+                        let server_tools = enabled_tools(&managed.tool_policy, result);
+                        let count = server_tools.len();
+                        replace_server_tools(&tools, &server_name, server_tools);
+                        info!("MCP server `{server_name}` now exposes {count} tools");
+                        (McpListKind::Tools, Some(count))
+                    }
+                    Err(err) => {
+                        warn!("Failed to re-list tools for MCP server '{server_name}': {err:#}");
+                        continue;
+                    }
+                }
+            }
+            ListChangedKind::Resources => {
+                let count = client
+                    .list_resources(None, managed.tool_timeout)
+                    .await
+                    .map(|result| result.resources.len())
+                    .ok();
+                (McpListKind::Resources, count)
+            }
+            ListChangedKind::Prompts => {
+                let count = client
+                    .list_prompts(None, managed.tool_timeout)
+                    .await
+                    .map(|result| result.prompts.len())
+                    .ok();
+                (McpListKind::Prompts, count)
+            }
+        };
+        let event = Event {
+            id: LIST_CHANGED_EVENT_ID.to_string(),
+            msg: EventMsg::McpListChanged(McpListChangedEvent {
+                server_name,
+                kind,
+                count,
+            }),
+        };
+        if tx_event.send(event).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Build the [`ClientFactory`] for one server. Every client gets fresh
+/// callbacks that forward elicitations, sampling requests and list change
+/// notifications.
+#[allow(clippy::too_many_arguments)]
+fn client_factory(
+    server_name: String,
+    transport: McpServerTransportConfig,
+    use_rmcp_client: bool,
+    store_mode: OAuthCredentialsStoreMode,
+    startup_timeout: Duration,
+    sampling_token_budget: Option<u64>,
+    elicitation_requests: ElicitationRequestManager,
+    tx_event: Sender<Event>,
+    tx_list_changed: mpsc::UnboundedSender<(String, ListChangedKind)>,
+    tx_sampling: mpsc::UnboundedSender<SamplingRequest>,
+) -> ClientFactory {
+    // The legacy client cannot answer requests from the server.
+    let supports_sampling =
+        use_rmcp_client || matches!(transport, McpServerTransportConfig::StreamableHttp { .. });
+    let sampling_token_budget = sampling_token_budget.filter(|_| supports_sampling);
+    Arc::new(move || {
+        let send_elicitation =
+            elicitation_requests.make_sender(server_name.clone(), tx_event.clone());
+        let create_message = sampling_token_budget.map(|token_budget| {
+            make_create_message(server_name.clone(), token_budget, tx_sampling.clone())
+        });
+        let on_list_changed: OnListChanged = {
+            let tx_list_changed = tx_list_changed.clone();
+            let server_name = server_name.clone();
+            Box::new(move |kind| {
+                let _ = tx_list_changed.send((server_name.clone(), kind));
+            })
+        };
+        start_client(
+            server_name.clone(),
+            transport.clone(),
+            use_rmcp_client,
+            store_mode,
+            startup_timeout,
+            send_elicitation,
+            create_message,
+            on_list_changed,
+        )
+        .boxed()
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn start_client(
+    server_name: String,
+    transport: McpServerTransportConfig,
+    use_rmcp_client: bool,
+    store_mode: OAuthCredentialsStoreMode,
+    startup_timeout: Duration,
+    send_elicitation: SendElicitation,
+    create_message: Option<CreateMessage>,
+    on_list_changed: OnListChanged,
+) -> Result<McpClientAdapter> {
+    let params = mcp_types::InitializeRequestParams {
+        capabilities: ClientCapabilities {
+            experimental: None,
+            roots: None,
+            // Only offered to servers with a `sampling_token_budget`.
+            sampling: create_message.as_ref().map(|_| json!({})),
+            // https://modelcontextprotocol.io/specification/2025-06-18/client/elicitation#capabilities
+            // indicates this should be an empty object.
+            elicitation: Some(json!({})),
+        },
+        client_info: Implementation {
+            name: "codex-mcp-client".to_owned(),
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            title: Some("Codex".into()),
+            // This field is used by Codex when it is an MCP
+            // server: it should not be used when Codex is
+            // an MCP client.
+            user_agent: None,
+        },
+        protocol_version: mcp_types::MCP_SCHEMA_VERSION.to_owned(),
+    };
+
+    match transport {
+        McpServerTransportConfig::Stdio {
+            command,
+            args,
+            env,
+            env_vars,
+            cwd,
+        } => {
+            let command_os: OsString = command.into();
+            let args_os: Vec<OsString> = args.into_iter().map(Into::into).collect();
+            McpClientAdapter::new_stdio_client(
+                use_rmcp_client,
+                command_os,
+                args_os,
+                env,
+                env_vars,
+                cwd,
+                params,
+                startup_timeout,
+                send_elicitation,
+                create_message,
+                on_list_changed,
+            )
+            .await
+        }
+        McpServerTransportConfig::StreamableHttp {
+            url,
+            bearer_token_env_var,
+            http_headers,
+            env_http_headers,
+        } => {
+            let resolved_bearer_token =
+                resolve_bearer_token(&server_name, bearer_token_env_var.as_deref());
+            McpClientAdapter::new_streamable_http_client(
+                server_name,
+                url,
+                resolved_bearer_token.unwrap_or_default(),
+                http_headers,
+                env_http_headers,
+                params,
+                startup_timeout,
+                store_mode,
+                send_elicitation,
+                create_message,
+                on_list_changed,
+            )
+            .await
+        }
+    }
+}
+
+/// Watch one server and restart it when its transport is gone. Runs until
+/// the manager is dropped.
+async fn supervise(
+    managed: Weak<ManagedClient>,
+    health_check: Arc<Notify>,
+    tools: SharedTools,
+    tx_event: Sender<Event>,
+) {
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(HEALTH_CHECK_INTERVAL) => {}
+            _ = health_check.notified() => {}
+        }
+        let Some(current) = managed.upgrade() else {
+            return;
+        };
+        if current.status().health == McpServerHealth::Failed {
+            // We gave up on this server.
+            continue;
+        }
+        let is_healthy = match current.current_client() {
+            Some(client) => !client.is_closed().await,
+            None => false,
+        };
+        drop(current);
+        if !is_healthy {
+            restart(&managed, &tools, &tx_event).await;
+        }
+    }
+}
+
+/// Restart the server with exponential backoff, re-running `initialize` and
+/// re-listing its tools. Reports every change of health on `tx_event`.
+async fn restart(managed: &Weak<ManagedClient>, tools: &SharedTools, tx_event: &Sender<Event>) {
+    let mut last_error = None;
+    for attempt in 0..MAX_RESTART_ATTEMPTS {
+        let Some(current) = managed.upgrade() else {
+            return;
+        };
+        current.set_client(None);
+        let error = last_error.take().or_else(|| current.status().error);
+        let status = current.set_health(McpServerHealth::Restarting, error);
+        let server_name = current.server_name.clone();
+        let factory = Arc::clone(&current.factory);
+        let startup_timeout = current.startup_timeout;
+        let tool_policy = current.tool_policy.clone();
+        drop(current);
+        send_server_status(tx_event, &server_name, status).await;
+
+        tokio::time::sleep(restart_backoff(attempt)).await;
+        let client = match factory().await {
+            Ok(client) => client,
+            Err(err) => {
+                warn!("Failed to restart MCP server '{server_name}': {err:#}");
+                last_error = Some(format!("{err:#}"));
+                continue;
+            }
+        };
+        match client.list_all_tools(Some(startup_timeout)).await {
+            Ok(result) => {
+                let server_tools = enabled_tools(&tool_policy, result);
+                replace_server_tools(tools, &server_name, server_tools);
+            }
+            Err(err) => {
+                warn!("Failed to list tools for MCP server '{server_name}': {err:#}");
+            }
+        }
+
+        let Some(current) = managed.upgrade() else {
+            return;
+        };
+        current.set_client(Some(client));
+        let status = {
+            let mut status = current
+                .status
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            status.health = McpServerHealth::Ready;
+            status.restarts += 1;
+            status.error = None;
+            status.clone()
+        };
+        drop(current);
+        info!("Restarted MCP server '{server_name}'");
+        send_server_status(tx_event, &server_name, status).await;
+        return;
+    }
+
+    let Some(current) = managed.upgrade() else {
+        return;
+    };
+    let status = current.set_health(McpServerHealth::Failed, last_error);
+    let server_name = current.server_name.clone();
+    drop(current);
+    warn!("Giving up on MCP server '{server_name}' after {MAX_RESTART_ATTEMPTS} restart attempts");
+    send_server_status(tx_event, &server_name, status).await;
+}
+
+fn restart_backoff(attempt: u32) -> Duration {
+    INITIAL_RESTART_BACKOFF
+        .saturating_mul(1 << attempt.min(16))
+        .min(MAX_RESTART_BACKOFF)
+}
+
+async fn send_server_status(tx_event: &Sender<Event>, server_name: &str, status: McpServerStatus) {
+    let event = Event {
+        id: SERVER_STATUS_EVENT_ID.to_string(),
+        msg: EventMsg::McpServerStatus(McpServerStatusEvent {
+            server_name: server_name.to_string(),
+            status,
+        }),
+    };
+    if let Err(err) = tx_event.send(event).await {
+        warn!("failed to send MCP server status event: {err}");
+    }
+}
+
 fn resolve_bearer_token(
     server_name: &str,
     bearer_token_env_var: Option<&str>,
@@ -648,7 +1474,7 @@ fn resolve_bearer_token(
 
 /// Query every server for its available tools and return a single map that
 /// contains all tools. Each key is the fully-qualified name for the tool.
-async fn list_all_tools(clients: &HashMap<String, ManagedClient>) -> Result<Vec<ToolInfo>> {
+async fn list_all_tools(clients: &HashMap<String, Arc<ManagedClient>>) -> Result<Vec<ToolInfo>> {
     let mut join_set = JoinSet::new();
 
     // Spawn one task per server so we can query them concurrently. This
@@ -656,10 +1482,16 @@ async fn list_all_tools(clients: &HashMap<String, ManagedClient>) -> Result<Vec<
     // the cumulative latency.
     for (server_name, managed_client) in clients {
         let server_name_cloned = server_name.clone();
-        let client_clone = managed_client.client.clone();
+        let Some(client_clone) = managed_client.current_client() else {
+            continue;
+        };
         let startup_timeout = managed_client.startup_timeout;
+        let tool_policy = managed_client.tool_policy.clone();
         join_set.spawn(async move {
-            let res = client_clone.list_tools(None, Some(startup_timeout)).await;
+            let res = client_clone
+                .list_all_tools(Some(startup_timeout))
+                .await
+                .map(|result| enabled_tools(&tool_policy, result));
             (server_name_cloned, res)
         });
     }
@@ -681,7 +1513,7 @@ async fn list_all_tools(clients: &HashMap<String, ManagedClient>) -> Result<Vec<
             continue;
         };
 
-        for tool in list_result.tools {
+        for tool in list_result {
             let tool_info = ToolInfo {
                 server_name: server_name.clone(),
                 tool_name: tool.name.clone(),
@@ -711,6 +1543,8 @@ fn is_valid_mcp_server_name(server_name: &str) -> bool {
 mod tests {
     use super::*;
     use mcp_types::ToolInputSchema;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
 
     fn create_test_tool(server_name: &str, tool_name: &str) -> ToolInfo {
         ToolInfo {
@@ -793,4 +1627,185 @@ mod tests {
             "my_server__yet_another_e1c3987bd9c50b826cbe1687966f79f0c602d19ca"
         );
     }
+
+    #[test]
+    fn replace_server_tools_only_touches_that_server() {
+        let tools: SharedTools = Arc::new(RwLock::new(qualify_tools(vec![
+            create_test_tool("server1", "old_tool"),
+            create_test_tool("server2", "other_tool"),
+        ])));
+
+        let new_tools = ["new_tool", "another_tool"]
+            .into_iter()
+            .map(|name| create_test_tool("server1", name).tool)
+            .collect();
+        replace_server_tools(&tools, "server1", new_tools);
+
+        let mut keys: Vec<String> = read_tools(&tools).keys().cloned().collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                "server1__another_tool".to_string(),
+                "server1__new_tool".to_string(),
+                "server2__other_tool".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn elicitation_is_surfaced_and_resolved() {
+        let (tx_event, rx_event) = async_channel::unbounded();
+        let manager = ElicitationRequestManager::default();
+        let send = manager.make_sender("docs".to_string(), tx_event);
+
+        let pending = tokio::spawn(send(
+            RequestId::Integer(7),
+            mcp_types::ElicitRequestParams {
+                message: "Which branch?".to_string(),
+                requested_schema: mcp_types::ElicitRequestParamsRequestedSchema {
+                    properties: json!({ "branch": { "type": "string" } }),
+                    required: Some(vec!["branch".to_string()]),
+                    r#type: "object".to_string(),
+                },
+            },
+        ));
+
+        let event = rx_event.recv().await.expect("elicitation event");
+        let EventMsg::ElicitationRequest(request) = event.msg else {
+            panic!("unexpected event: {:?}", event.msg);
+        };
+        assert_eq!(request.server_name, "docs");
+        assert_eq!(request.message, "Which branch?");
+
+        let answer = ElicitResult {
+            action: "accept".to_string(),
+            content: Some(json!({ "branch": "main" })),
+        };
+        manager
+            .resolve("docs", request.id.clone(), answer.clone())
+            .expect("resolve pending elicitation");
+        assert_eq!(pending.await.expect("join").expect("response"), answer);
+
+        assert!(manager.resolve("docs", request.id, answer).is_err());
+    }
+
+    #[tokio::test]
+    async fn cleared_elicitation_fails_the_pending_request() {
+        let (tx_event, rx_event) = async_channel::unbounded();
+        let manager = ElicitationRequestManager::default();
+        let send = manager.make_sender("docs".to_string(), tx_event);
+
+        let pending = tokio::spawn(send(
+            RequestId::Integer(8),
+            mcp_types::ElicitRequestParams {
+                message: "Which branch?".to_string(),
+                requested_schema: mcp_types::ElicitRequestParamsRequestedSchema {
+                    properties: json!({ "branch": { "type": "string" } }),
+                    required: None,
+                    r#type: "object".to_string(),
+                },
+            },
+        ));
+        let event = rx_event.recv().await.expect("elicitation event");
+        let EventMsg::ElicitationRequest(request) = event.msg else {
+            panic!("unexpected event: {:?}", event.msg);
+        };
+
+        manager.clear();
+
+        assert!(pending.await.expect("join").is_err());
+        let answer = ElicitResult {
+            action: "decline".to_string(),
+            content: None,
+        };
+        assert!(manager.resolve("docs", request.id, answer).is_err());
+    }
+
+    #[test]
+    fn restart_backoff_doubles_up_to_the_cap() {
+        let delays: Vec<u64> = (0..7).map(|n| restart_backoff(n).as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 30, 30]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn supervisor_gives_up_after_repeated_restart_failures() {
+        let (tx_event, rx_event) = async_channel::unbounded();
+        let factory: ClientFactory =
+            Arc::new(|| async { Err::<McpClientAdapter, _>(anyhow!("boom")) }.boxed());
+        let managed = Arc::new(ManagedClient::new(
+            "flaky".to_string(),
+            factory,
+            DEFAULT_STARTUP_TIMEOUT,
+            None,
+            McpToolPolicy::default(),
+        ));
+        let tools: SharedTools = Arc::default();
+
+        restart(&Arc::downgrade(&managed), &tools, &tx_event).await;
+
+        let failed = McpServerStatus {
+            health: McpServerHealth::Failed,
+            restarts: 0,
+            error: Some("boom".to_string()),
+        };
+        assert_eq!(managed.status(), failed);
+        assert!(managed.client().is_err());
+
+        drop(tx_event);
+        let mut healths = Vec::new();
+        while let Ok(event) = rx_event.recv().await {
+            let EventMsg::McpServerStatus(ev) = event.msg else {
+                panic!("unexpected event: {:?}", event.msg);
+            };
+            healths.push(ev.status.health);
+        }
+        let mut expected = vec![McpServerHealth::Restarting; MAX_RESTART_ATTEMPTS as usize];
+        expected.push(McpServerHealth::Failed);
+        assert_eq!(healths, expected);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn supervisor_stops_restarting_after_max_attempts() {
+        let (tx_event, _rx_event) = async_channel::unbounded();
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let factory: ClientFactory = {
+            let attempts = Arc::clone(&attempts);
+            Arc::new(move || {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                async { Err::<McpClientAdapter, _>(anyhow!("boom")) }.boxed()
+            })
+        };
+        let managed = Arc::new(ManagedClient::new(
+            "flaky".to_string(),
+            factory,
+            DEFAULT_STARTUP_TIMEOUT,
+            None,
+            McpToolPolicy::default(),
+        ));
+        let tools: SharedTools = Arc::default();
+        let supervisor = tokio::spawn(supervise(
+            Arc::downgrade(&managed),
+            Arc::clone(&managed.health_check),
+            tools,
+            tx_event,
+        ));
+
+        // Far longer than every backoff combined, with extra health checks
+        // that must not start another round of restarts.
+        for _ in 0..20 {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            managed.health_check.notify_one();
+        }
+
+        assert_eq!(
+            attempts.load(Ordering::SeqCst),
+            MAX_RESTART_ATTEMPTS as usize
+        );
+        assert_eq!(managed.status().health, McpServerHealth::Failed);
+
+        drop(managed);
+        tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+        assert!(supervisor.is_finished());
+    }
 }
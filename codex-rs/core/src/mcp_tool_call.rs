@@ -3,11 +3,15 @@ use std::time::Instant;
 use tracing::error;
 
 use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::protocol::AskForApproval;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::McpInvocation;
 use crate::protocol::McpToolCallBeginEvent;
 use crate::protocol::McpToolCallEndEvent;
+use crate::protocol::ReviewDecision;
+use codex_otel::otel_event_manager::ToolDecisionSource;
 use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ResponseInputItem;
 
@@ -15,6 +19,7 @@ use codex_protocol::models::ResponseInputItem;
 /// `McpToolCallBegin` and `McpToolCallEnd` events to the `Session`.
 pub(crate) async fn handle_mcp_tool_call(
     sess: &Session,
+    turn_context: &TurnContext,
     sub_id: &str,
     call_id: String,
     server: String,
@@ -47,6 +52,19 @@ pub(crate) async fn handle_mcp_tool_call(
         arguments: arguments_value.clone(),
     };
 
+    if sess.mcp_tool_requires_approval(&server, &tool_name).await
+        && let Err(message) =
+            request_mcp_tool_approval(sess, turn_context, sub_id, &call_id, &invocation).await
+    {
+        return ResponseInputItem::FunctionCallOutput {
+            call_id,
+            output: FunctionCallOutputPayload {
+                content: message,
+                success: Some(false),
+            },
+        };
+    }
+
     let tool_call_begin_event = EventMsg::McpToolCallBegin(McpToolCallBeginEvent {
         call_id: call_id.clone(),
         invocation: invocation.clone(),
@@ -71,6 +89,65 @@ pub(crate) async fn handle_mcp_tool_call(
     ResponseInputItem::McpToolCallOutput { call_id, result }
 }
 
+/// Ask the user to approve the call through the same overlay used for shell
+/// commands. Returns the message for the model when the call may not run.
+async fn request_mcp_tool_approval(
+    sess: &Session,
+    turn_context: &TurnContext,
+    sub_id: &str,
+    call_id: &str,
+    invocation: &McpInvocation,
+) -> Result<(), String> {
+    let McpInvocation {
+        server,
+        tool,
+        arguments,
+    } = invocation;
+    if turn_context.approval_policy == AskForApproval::Never {
+        return Err(format!(
+            "MCP tool `{tool}` on server `{server}` requires approval, but the approval policy does not allow asking for it"
+        ));
+    }
+
+    let mut command = vec![
+        "mcp".to_string(),
+        "call".to_string(),
+        server.clone(),
+        tool.clone(),
+    ];
+    if let Some(arguments) = arguments {
+        command.push(arguments.to_string());
+    }
+    let decision = sess
+        .request_command_approval(
+            sub_id.to_string(),
+            call_id.to_string(),
+            command,
+            turn_context.cwd.clone(),
+            Some(format!(
+                "MCP server `{server}` requires approval for `{tool}`"
+            )),
+        )
+        .await;
+    turn_context.client.get_otel_event_manager().tool_decision(
+        tool,
+        call_id,
+        decision,
+        ToolDecisionSource::User,
+    );
+
+    match decision {
+        ReviewDecision::Approved => Ok(()),
+        ReviewDecision::ApprovedForSession => {
+            sess.approve_mcp_tool_for_session(server, tool).await;
+            Ok(())
+        }
+        ReviewDecision::Denied | ReviewDecision::Abort => {
+            Err("MCP tool call rejected by user".to_string())
+        }
+    }
+}
+
 async fn notify_mcp_tool_call_event(sess: &Session, sub_id: &str, event: EventMsg) {
     sess.send_event(Event {
         id: sub_id.to_string(),
@@ -0,0 +1,141 @@
+//! Helpers for switching a session to the next `model_fallbacks` entry when
+//! its current provider keeps failing.
+
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use reqwest::StatusCode;
+use serde_json::json;
+
+use crate::error::CodexErr;
+
+/// Why `err`, once the turn's retry budget is spent, should move the session
+/// to the next fallback. `None` for errors another provider would not fix.
+pub(crate) fn failover_reason(err: &CodexErr) -> Option<&'static str> {
+    match err {
+        CodexErr::UsageLimitReached(_) => Some("usage limit reached"),
+        CodexErr::RetryLimit(err) if err.status == StatusCode::TOO_MANY_REQUESTS => {
+            Some("rate limited")
+        }
+        CodexErr::RetryLimit(_) | CodexErr::InternalServerError => Some("server errors"),
+        CodexErr::ConnectionFailed(_) | CodexErr::Timeout => Some("connection failed"),
+        CodexErr::Stream(..) | CodexErr::ResponseStreamFailed(_) => Some("stream disconnected"),
+        _ => None,
+    }
+}
+
+/// Rewrites history recorded against one provider so another can accept it.
+///
+/// Reasoning items are dropped: their encrypted content (or signature) is
+/// only valid for the provider that produced it. `local_shell` calls become
+/// `shell` function calls unless the new model uses the local shell tool.
+pub(crate) fn translate_history(
+    items: Vec<ResponseItem>,
+    uses_local_shell_tool: bool,
+) -> Vec<ResponseItem> {
+    items
+        .into_iter()
+        .filter_map(|item| match item {
+            ResponseItem::Reasoning { .. } => None,
+            ResponseItem::LocalShellCall {
+                id,
+                call_id,
+                action: LocalShellAction::Exec(action),
+                ..
+            } if !uses_local_shell_tool => {
+                let call_id = call_id.or(id)?;
+                let arguments = json!({
+                    "command": action.command,
+                    "workdir": action.working_directory,
+                    "timeout_ms": action.timeout_ms,
+                });
+                Some(ResponseItem::FunctionCall {
+                    id: None,
+                    name: "shell".to_string(),
+                    arguments: arguments.to_string(),
+                    call_id,
+                })
+            }
+            item => Some(item),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ContentItem;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use codex_protocol::models::LocalShellExecAction;
+    use codex_protocol::models::LocalShellStatus;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn translate_history_drops_reasoning_and_rewrites_local_shell_calls() {
+        let message = ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: "list files".to_string(),
+            }],
+        };
+        let output = ResponseItem::FunctionCallOutput {
+            call_id: "call-1".to_string(),
+            output: FunctionCallOutputPayload {
+                content: "a.txt".to_string(),
+                success: Some(true),
+            },
+        };
+        let local_shell_call = ResponseItem::LocalShellCall {
+            id: None,
+            call_id: Some("call-1".to_string()),
+            status: LocalShellStatus::Completed,
+            action: LocalShellAction::Exec(LocalShellExecAction {
+                command: vec!["ls".to_string()],
+                timeout_ms: Some(1_000),
+                working_directory: Some("/repo".to_string()),
+                env: None,
+                user: None,
+            }),
+        };
+        let history = vec![
+            message.clone(),
+            ResponseItem::Reasoning {
+                id: "rs-1".to_string(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some("opaque".to_string()),
+            },
+            local_shell_call.clone(),
+            output.clone(),
+        ];
+
+        assert_eq!(
+            translate_history(history.clone(), false),
+            vec![
+                message.clone(),
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: "shell".to_string(),
+                    arguments: r#"{"command":["ls"],"workdir":"/repo","timeout_ms":1000}"#
+                        .to_string(),
+                    call_id: "call-1".to_string(),
+                },
+                output.clone(),
+            ]
+        );
+        assert_eq!(
+            translate_history(history, true),
+            vec![message, local_shell_call, output]
+        );
+    }
+
+    #[test]
+    fn failover_reason_skips_errors_another_provider_would_not_fix() {
+        assert_eq!(
+            failover_reason(&CodexErr::Timeout),
+            Some("connection failed")
+        );
+        assert_eq!(failover_reason(&CodexErr::ContextWindowExceeded), None);
+        assert_eq!(failover_reason(&CodexErr::Interrupted), None);
+    }
+}
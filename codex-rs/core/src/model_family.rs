@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+
+use crate::config_types::ApplyPatchToolTypeToml;
+use crate::config_types::ModelFamilyToml;
 use crate::config_types::ReasoningSummaryFormat;
 use crate::tools::handlers::apply_patch::ApplyPatchToolType;
 
@@ -158,6 +162,41 @@ pub fn find_family_for_model(mut slug: &str) -> Option<ModelFamily> {
             supports_reasoning_summaries: true,
             needs_special_apply_patch_instructions: true,
         )
+
+    // Anthropic models served through `wire_api = "anthropic"`. Extended
+    // thinking is available from Claude 3.7 Sonnet onwards.
+    } else if slug.starts_with("claude-3-7")
+        || slug.starts_with("claude-sonnet-4")
+        || slug.starts_with("claude-opus-4")
+        || slug.starts_with("claude-haiku-4")
+    {
+        model_family!(
+            slug, slug,
+            supports_reasoning_summaries: true,
+            supports_parallel_tool_calls: true,
+            apply_patch_tool_type: Some(ApplyPatchToolType::Function),
+        )
+    } else if slug.starts_with("claude-") {
+        model_family!(
+            slug, slug,
+            supports_parallel_tool_calls: true,
+            apply_patch_tool_type: Some(ApplyPatchToolType::Function),
+        )
+
+    // Google models served through `wire_api = "gemini"`.
+    } else if slug.starts_with("gemini-2.5") || slug.starts_with("gemini-3") {
+        model_family!(
+            slug, slug,
+            supports_reasoning_summaries: true,
+            supports_parallel_tool_calls: true,
+            apply_patch_tool_type: Some(ApplyPatchToolType::Function),
+        )
+    } else if slug.starts_with("gemini-") {
+        model_family!(
+            slug, slug,
+            supports_parallel_tool_calls: true,
+            apply_patch_tool_type: Some(ApplyPatchToolType::Function),
+        )
     } else {
         None
     }
@@ -177,3 +216,129 @@ pub fn derive_default_model_family(model: &str) -> ModelFamily {
         experimental_supported_tools: Vec::new(),
     }
 }
+
+/// A validated `[model_families.<slug>]` table from config.toml.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelFamilyOverride {
+    pub settings: ModelFamilyToml,
+    /// Contents of `settings.base_instructions_file`.
+    pub base_instructions: Option<String>,
+}
+
+impl ModelFamilyOverride {
+    fn apply(&self, family: &mut ModelFamily) {
+        let settings = &self.settings;
+        if let Some(name) = &settings.family {
+            family.family = name.clone();
+        }
+        if let Some(value) = settings.needs_special_apply_patch_instructions {
+            family.needs_special_apply_patch_instructions = value;
+        }
+        if let Some(value) = settings.supports_reasoning_summaries {
+            family.supports_reasoning_summaries = value;
+        }
+        if let Some(value) = &settings.reasoning_summary_format {
+            family.reasoning_summary_format = value.clone();
+        }
+        if let Some(value) = settings.uses_local_shell_tool {
+            family.uses_local_shell_tool = value;
+        }
+        if let Some(value) = settings.supports_parallel_tool_calls {
+            family.supports_parallel_tool_calls = value;
+        }
+        if let Some(value) = settings.apply_patch_tool_type {
+            family.apply_patch_tool_type = match value {
+                ApplyPatchToolTypeToml::Freeform => Some(ApplyPatchToolType::Freeform),
+                ApplyPatchToolTypeToml::Function => Some(ApplyPatchToolType::Function),
+                ApplyPatchToolTypeToml::Disabled => None,
+            };
+        }
+        if let Some(value) = &self.base_instructions {
+            family.base_instructions = value.clone();
+        }
+        if let Some(value) = &settings.experimental_supported_tools {
+            family.experimental_supported_tools = value.clone();
+        }
+    }
+
+    /// Names of the fields this table sets, in declaration order.
+    pub fn overridden_fields(&self) -> Vec<&'static str> {
+        let settings = &self.settings;
+        [
+            ("family", settings.family.is_some()),
+            (
+                "needs_special_apply_patch_instructions",
+                settings.needs_special_apply_patch_instructions.is_some(),
+            ),
+            (
+                "supports_reasoning_summaries",
+                settings.supports_reasoning_summaries.is_some(),
+            ),
+            (
+                "reasoning_summary_format",
+                settings.reasoning_summary_format.is_some(),
+            ),
+            (
+                "uses_local_shell_tool",
+                settings.uses_local_shell_tool.is_some(),
+            ),
+            (
+                "supports_parallel_tool_calls",
+                settings.supports_parallel_tool_calls.is_some(),
+            ),
+            (
+                "apply_patch_tool_type",
+                settings.apply_patch_tool_type.is_some(),
+            ),
+            ("base_instructions_file", self.base_instructions.is_some()),
+            (
+                "experimental_supported_tools",
+                settings.experimental_supported_tools.is_some(),
+            ),
+            ("context_window", settings.context_window.is_some()),
+            ("max_output_tokens", settings.max_output_tokens.is_some()),
+            (
+                "auto_compact_token_limit",
+                settings.auto_compact_token_limit.is_some(),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, set)| set.then_some(name))
+        .collect()
+    }
+}
+
+/// The `[model_families]` entry that applies to `slug`: an exact match, or
+/// else the longest key that `slug` starts with.
+pub fn find_family_override<'a>(
+    overrides: &'a HashMap<String, ModelFamilyOverride>,
+    slug: &str,
+) -> Option<(&'a str, &'a ModelFamilyOverride)> {
+    if let Some((key, family_override)) = overrides.get_key_value(slug) {
+        return Some((key.as_str(), family_override));
+    }
+    overrides
+        .iter()
+        .filter(|(key, _)| slug.starts_with(key.as_str()))
+        .max_by_key(|(key, _)| key.len())
+        .map(|(key, family_override)| (key.as_str(), family_override))
+}
+
+/// Like [`find_family_for_model`], with any matching `[model_families]`
+/// override applied. Models that only have an override start from
+/// [`derive_default_model_family`].
+pub fn find_family_with_overrides(
+    slug: &str,
+    overrides: &HashMap<String, ModelFamilyOverride>,
+) -> Option<ModelFamily> {
+    let family_override = find_family_override(overrides, slug);
+    let mut family = match (find_family_for_model(slug), family_override) {
+        (Some(family), _) => family,
+        (None, Some(_)) => derive_default_model_family(slug),
+        (None, None) => return None,
+    };
+    if let Some((_, family_override)) = family_override {
+        family_override.apply(&mut family);
+    }
+    Some(family)
+}
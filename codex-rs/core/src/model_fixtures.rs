@@ -0,0 +1,338 @@
+//! Record/replay of model exchanges for providers configured with
+//! `fixtures = { mode = "record" | "replay", dir = "..." }`.
+//!
+//! Each exchange is stored as two files named after a hash of the normalized
+//! request: `<hash>.json` holds the normalized request (for humans and diffs)
+//! and `<hash>.sse` holds the raw SSE response body. Normalization drops
+//! values that change from run to run, so a replayed session matches the
+//! recording as long as it sends the same content.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use bytes::Bytes;
+use futures::Stream;
+use futures::StreamExt;
+use futures::channel::mpsc;
+use serde_json::Value;
+use sha1::Digest;
+use sha1::Sha1;
+use tracing::debug;
+use tracing::warn;
+
+use crate::error::CodexErr;
+use crate::error::Result;
+use crate::model_provider_info::FixtureMode;
+use crate::model_provider_info::ModelFixtures;
+
+/// Request fields whose values differ between otherwise identical sessions.
+const VOLATILE_KEYS: &[&str] = &["prompt_cache_key"];
+
+/// Placeholder that replaces the session's working directory in requests.
+const CWD_PLACEHOLDER: &str = "<cwd>";
+
+/// Markers of a finished response in each wire API's SSE body: Responses,
+/// Chat Completions, Anthropic Messages and Gemini, respectively.
+const COMPLETION_MARKERS: &[&str] = &[
+    "response.completed",
+    "data: [DONE]",
+    "message_stop",
+    "\"finishReason\"",
+];
+
+/// The fixture files for one request.
+pub(crate) struct Fixture {
+    mode: FixtureMode,
+    request: Value,
+    request_path: PathBuf,
+    response_path: PathBuf,
+}
+
+impl Fixture {
+    pub(crate) fn for_request(fixtures: &ModelFixtures, payload: &Value, cwd: &Path) -> Self {
+        let request = normalize_request(payload, cwd);
+        let key = fixture_key(&request);
+        let dir = cwd.join(&fixtures.dir);
+        Self {
+            mode: fixtures.mode,
+            request,
+            request_path: dir.join(format!("{key}.json")),
+            response_path: dir.join(format!("{key}.sse")),
+        }
+    }
+
+    pub(crate) fn is_replay(&self) -> bool {
+        self.mode == FixtureMode::Replay
+    }
+
+    /// Serves the recorded SSE body for this request.
+    pub(crate) async fn replay(
+        &self,
+    ) -> Result<impl Stream<Item = Result<Bytes>> + Unpin + Send + 'static> {
+        let body = match tokio::fs::read(&self.response_path).await {
+            Ok(body) => body,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Err(CodexErr::FixtureNotFound(self.response_path.clone()));
+            }
+            Err(err) => return Err(err.into()),
+        };
+        debug!(path = %self.response_path.display(), "Replaying model fixture");
+        Ok(futures::stream::iter([Ok(Bytes::from(body))]))
+    }
+
+    /// Passes `stream` through unchanged and saves the exchange once the
+    /// response has been read. Nothing is written if the stream fails or ends
+    /// before the response completed, e.g. because the consumer stopped
+    /// reading early.
+    pub(crate) fn record<S>(
+        &self,
+        mut stream: S,
+    ) -> impl Stream<Item = Result<Bytes>> + Unpin + Send + 'static
+    where
+        S: Stream<Item = Result<Bytes>> + Unpin + Send + 'static,
+    {
+        let (tx, rx) = mpsc::unbounded();
+        let request = self.request.clone();
+        let request_path = self.request_path.clone();
+        let response_path = self.response_path.clone();
+        tokio::spawn(async move {
+            let mut body = Vec::new();
+            while let Some(chunk) = stream.next().await {
+                match &chunk {
+                    Ok(bytes) => body.extend_from_slice(bytes),
+                    Err(err) => {
+                        warn!("not recording model fixture after stream error: {err}");
+                        let _ = tx.unbounded_send(chunk);
+                        return;
+                    }
+                }
+                // The consumer stops reading once the response is complete.
+                if tx.unbounded_send(chunk).is_err() {
+                    break;
+                }
+            }
+            if !is_complete_response(&body) {
+                warn!(
+                    "not recording model fixture {}: the response did not complete",
+                    response_path.display()
+                );
+                return;
+            }
+            if let Err(err) = write_fixture(&request, &request_path, &body, &response_path).await {
+                warn!(
+                    "failed to record model fixture {}: {err}",
+                    response_path.display()
+                );
+            }
+        });
+        rx
+    }
+}
+
+fn is_complete_response(body: &[u8]) -> bool {
+    let body = String::from_utf8_lossy(body);
+    COMPLETION_MARKERS
+        .iter()
+        .any(|marker| body.contains(marker))
+}
+
+async fn write_fixture(
+    request: &Value,
+    request_path: &Path,
+    body: &[u8],
+    response_path: &Path,
+) -> std::io::Result<()> {
+    if let Some(dir) = request_path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    let mut request_json = serde_json::to_string_pretty(request)?;
+    request_json.push('\n');
+    tokio::fs::write(request_path, request_json).await?;
+    tokio::fs::write(response_path, body).await
+}
+
+/// Returns `payload` with object keys sorted, volatile fields removed and the
+/// working directory replaced by a placeholder. A filesystem root is left
+/// alone: replacing it would rewrite every path separator.
+fn normalize_request(payload: &Value, cwd: &Path) -> Value {
+    let cwd = if cwd.parent().is_some() {
+        cwd.to_string_lossy()
+    } else {
+        "".into()
+    };
+    normalize_value(payload, &cwd)
+}
+
+fn normalize_value(value: &Value, cwd: &str) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map
+                .iter()
+                .filter(|(key, _)| !VOLATILE_KEYS.contains(&key.as_str()))
+                .collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.clone(), normalize_value(value, cwd)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| normalize_value(item, cwd))
+                .collect(),
+        ),
+        Value::String(text) if !cwd.is_empty() => Value::String(text.replace(cwd, CWD_PLACEHOLDER)),
+        other => other.clone(),
+    }
+}
+
+fn fixture_key(request: &Value) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(request.to_string().as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn normalization_ignores_volatile_values() {
+        let first = json!({
+            "model": "gpt-5",
+            "prompt_cache_key": "11111111-1111-1111-1111-111111111111",
+            "input": [{"type": "message", "content": "<cwd>/tmp/one</cwd>"}],
+        });
+        let second = json!({
+            "input": [{"content": "<cwd>/tmp/two</cwd>", "type": "message"}],
+            "prompt_cache_key": "22222222-2222-2222-2222-222222222222",
+            "model": "gpt-5",
+        });
+
+        let first = normalize_request(&first, Path::new("/tmp/one"));
+        let second = normalize_request(&second, Path::new("/tmp/two"));
+        assert_eq!(first, second);
+        assert_eq!(fixture_key(&first), fixture_key(&second));
+        assert_eq!(
+            first,
+            json!({
+                "input": [{"content": "<cwd><cwd></cwd>", "type": "message"}],
+                "model": "gpt-5",
+            })
+        );
+    }
+
+    #[test]
+    fn root_cwd_is_not_normalized() {
+        let payload = json!({"input": "see /etc/hosts"});
+        assert_eq!(normalize_request(&payload, Path::new("/")), payload);
+    }
+
+    #[tokio::test]
+    async fn recorded_exchange_is_replayed() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let payload = json!({"model": "gpt-5", "input": "hello"});
+        let record = Fixture::for_request(
+            &ModelFixtures {
+                mode: FixtureMode::Record,
+                dir: dir.path().to_path_buf(),
+            },
+            &payload,
+            dir.path(),
+        );
+        let body = "event: response.completed\ndata: {}\n\n";
+        let upstream =
+            futures::stream::iter([Ok(Bytes::from(&body[..24])), Ok(Bytes::from(&body[24..]))]);
+        let passed_through: Vec<Bytes> = record
+            .record(upstream)
+            .map(|chunk| chunk.map_err(anyhow::Error::from))
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<anyhow::Result<_>>()?;
+        assert_eq!(passed_through.concat(), body.as_bytes());
+
+        let replay = Fixture::for_request(
+            &ModelFixtures {
+                mode: FixtureMode::Replay,
+                dir: dir.path().to_path_buf(),
+            },
+            &payload,
+            dir.path(),
+        );
+        assert!(replay.is_replay());
+        // The recording task writes the files after the stream ends.
+        let mut replayed = None;
+        for _ in 0..50 {
+            match replay.replay().await {
+                Ok(stream) => {
+                    replayed = Some(stream.collect::<Vec<_>>().await);
+                    break;
+                }
+                Err(_) => tokio::time::sleep(std::time::Duration::from_millis(10)).await,
+            }
+        }
+        let replayed: Vec<Bytes> = replayed
+            .ok_or_else(|| anyhow::anyhow!("fixture was not recorded"))?
+            .into_iter()
+            .collect::<Result<_>>()?;
+        assert_eq!(replayed.concat(), body.as_bytes());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn truncated_response_is_not_recorded() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let fixtures = ModelFixtures {
+            mode: FixtureMode::Record,
+            dir: dir.path().to_path_buf(),
+        };
+        let payload = json!({"model": "gpt-5", "input": "hello"});
+        let record = Fixture::for_request(&fixtures, &payload, dir.path());
+        let (upstream, rx_upstream) = mpsc::unbounded::<Result<Bytes>>();
+        let mut passed_through = record.record(rx_upstream);
+
+        // The consumer stops after the first event, before the response
+        // has completed.
+        let send = |chunk: &'static str| {
+            upstream
+                .unbounded_send(Ok(Bytes::from(chunk)))
+                .expect("upstream is open");
+        };
+        send("event: response.created\ndata: {}\n\n");
+        assert!(passed_through.next().await.is_some());
+        drop(passed_through);
+        send("event: response.output_text.delta\ndata: {}\n\n");
+        send("event: response.completed\ndata: {}\n\n");
+        upstream.close_channel();
+
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert!(!record.response_path.exists());
+        assert!(!record.request_path.exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn replay_without_recording_is_an_error() {
+        let dir = TempDir::new().expect("tempdir");
+        let fixture = Fixture::for_request(
+            &ModelFixtures {
+                mode: FixtureMode::Replay,
+                dir: PathBuf::from("fixtures"),
+            },
+            &json!({"model": "gpt-5"}),
+            dir.path(),
+        );
+        let err = fixture.replay().await.err().expect("missing fixture");
+        assert!(matches!(
+            err,
+            CodexErr::FixtureNotFound(path) if path.starts_with(dir.path().join("fixtures"))
+        ));
+    }
+}
@@ -0,0 +1,161 @@
+//! Model prices and cost estimates derived from token usage.
+
+use std::collections::HashMap;
+
+use crate::config_types::ModelPricing;
+use crate::protocol::TokenUsage;
+
+const fn price(input: f64, cached_input: f64, output: f64) -> ModelPricing {
+    ModelPricing {
+        input,
+        cached_input: Some(cached_input),
+        output,
+    }
+}
+
+/// Price for models without a cached-input discount.
+const fn uncached_price(input: f64, output: f64) -> ModelPricing {
+    ModelPricing {
+        input,
+        cached_input: None,
+        output,
+    }
+}
+
+/// Built-in prices in USD per million tokens, matched by slug prefix. Prices
+/// change over time; `[model_pricing]` in config.toml takes precedence.
+const BUILT_IN_PRICING: &[(&str, ModelPricing)] = &[
+    // https://platform.openai.com/docs/pricing
+    ("gpt-5", price(1.25, 0.125, 10.0)),
+    ("gpt-5-codex", price(1.25, 0.125, 10.0)),
+    ("gpt-5-mini", price(0.25, 0.025, 2.0)),
+    ("gpt-5-nano", price(0.05, 0.005, 0.4)),
+    ("gpt-5-pro", uncached_price(15.0, 120.0)),
+    ("codex-mini-latest", price(1.5, 0.375, 6.0)),
+    ("gpt-4.1", price(2.0, 0.5, 8.0)),
+    ("gpt-4.1-mini", price(0.4, 0.1, 1.6)),
+    ("gpt-4.1-nano", price(0.1, 0.025, 0.4)),
+    ("gpt-4o", price(2.5, 1.25, 10.0)),
+    ("gpt-4o-mini", price(0.15, 0.075, 0.6)),
+    ("o3", price(2.0, 0.5, 8.0)),
+    ("o3-mini", price(1.1, 0.55, 4.4)),
+    ("o3-pro", uncached_price(20.0, 80.0)),
+    ("o4-mini", price(1.1, 0.275, 4.4)),
+    // https://www.anthropic.com/pricing#api
+    ("claude-opus-4", price(15.0, 1.5, 75.0)),
+    ("claude-sonnet-4", price(3.0, 0.3, 15.0)),
+    ("claude-haiku-4", price(1.0, 0.1, 5.0)),
+    // https://ai.google.dev/gemini-api/docs/pricing
+    ("gemini-2.5-pro", price(1.25, 0.31, 10.0)),
+    ("gemini-2.5-flash", price(0.3, 0.075, 2.5)),
+    ("gemini-2.5-flash-lite", price(0.1, 0.025, 0.4)),
+];
+
+/// Price for `model`: `[model_pricing]` entries first, then the built-in
+/// table. Both match by slug prefix and prefer the longest match. Returns
+/// `None` for models without a known price, e.g. local models.
+pub(crate) fn find_model_pricing(
+    model: &str,
+    overrides: &HashMap<String, ModelPricing>,
+) -> Option<ModelPricing> {
+    longest_prefix_match(
+        model,
+        overrides
+            .iter()
+            .map(|(slug, pricing)| (slug.as_str(), *pricing)),
+    )
+    .or_else(|| longest_prefix_match(model, BUILT_IN_PRICING.iter().copied()))
+}
+
+fn longest_prefix_match<'a>(
+    model: &str,
+    entries: impl Iterator<Item = (&'a str, ModelPricing)>,
+) -> Option<ModelPricing> {
+    entries
+        .filter(|(slug, _)| model.starts_with(slug))
+        .max_by_key(|(slug, _)| slug.len())
+        .map(|(_, pricing)| pricing)
+}
+
+/// Estimated cost of `usage` in USD. Reasoning tokens are part of
+/// `output_tokens` and billed as output.
+pub(crate) fn estimate_cost_usd(pricing: &ModelPricing, usage: &TokenUsage) -> f64 {
+    let cached_input = usage.cached_input_tokens.min(usage.input_tokens);
+    let uncached_input = usage.input_tokens - cached_input;
+    let cached_input_price = pricing.cached_input.unwrap_or(pricing.input);
+    (uncached_input as f64 * pricing.input
+        + cached_input as f64 * cached_input_price
+        + usage.output_tokens as f64 * pricing.output)
+        / 1_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn prefers_overrides_and_longest_prefix() {
+        let overrides = HashMap::from([(
+            "gpt-5-codex".to_string(),
+            ModelPricing {
+                input: 1.0,
+                cached_input: None,
+                output: 2.0,
+            },
+        )]);
+
+        assert_eq!(
+            find_model_pricing("gpt-5-mini-2025-08-07", &overrides),
+            Some(price(0.25, 0.025, 2.0))
+        );
+        assert_eq!(
+            find_model_pricing("gpt-5-codex", &overrides),
+            Some(ModelPricing {
+                input: 1.0,
+                cached_input: None,
+                output: 2.0,
+            })
+        );
+        assert_eq!(find_model_pricing("gpt-oss:20b", &overrides), None);
+    }
+
+    #[test]
+    fn pro_models_are_not_priced_as_their_base_model() {
+        let overrides = HashMap::new();
+        assert_eq!(
+            find_model_pricing("gpt-5-pro-2025-10-06", &overrides),
+            Some(uncached_price(15.0, 120.0))
+        );
+        assert_eq!(
+            find_model_pricing("o3-pro", &overrides),
+            Some(uncached_price(20.0, 80.0))
+        );
+        assert_eq!(
+            find_model_pricing("o3-2025-04-16", &overrides),
+            Some(price(2.0, 0.5, 8.0))
+        );
+    }
+
+    #[test]
+    fn estimates_cost_with_cached_input() {
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            cached_input_tokens: 400_000,
+            output_tokens: 100_000,
+            reasoning_output_tokens: 50_000,
+            total_tokens: 1_100_000,
+        };
+
+        let cost = estimate_cost_usd(&price(1.25, 0.125, 10.0), &usage);
+        // 0.6M * 1.25 + 0.4M * 0.125 + 0.1M * 10
+        assert!((cost - 1.8).abs() < 1e-9, "{cost}");
+
+        let uncached = ModelPricing {
+            input: 2.0,
+            cached_input: None,
+            output: 0.0,
+        };
+        assert!((estimate_cost_usd(&uncached, &usage) - 2.0).abs() < 1e-9);
+    }
+}
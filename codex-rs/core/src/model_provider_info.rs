@@ -11,6 +11,7 @@ use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::env::VarError;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use crate::error::EnvVarError;
@@ -37,8 +38,18 @@ pub enum WireApi {
     /// Regular Chat Completions compatible with `/v1/chat/completions`.
     #[default]
     Chat,
+
+    /// Anthropic's Messages API at `/v1/messages`.
+    Anthropic,
+
+    /// Google's Gemini API at `/v1beta/models/{model}:streamGenerateContent`.
+    Gemini,
 }
 
+/// Version header required by the Anthropic Messages API.
+const ANTHROPIC_VERSION_HEADER: &str = "anthropic-version";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
 /// Serializable representation of a provider definition.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct ModelProviderInfo {
@@ -86,13 +97,39 @@ pub struct ModelProviderInfo {
     /// and API key (if needed) comes from the "env_key" environment variable.
     #[serde(default)]
     pub requires_openai_auth: bool,
+
+    /// Record every model exchange to a fixture directory, or replay recorded
+    /// exchanges instead of contacting the provider.
+    pub fixtures: Option<ModelFixtures>,
+}
+
+/// Whether a provider records exchanges to its fixture directory or serves
+/// them back from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FixtureMode {
+    /// Send requests to the provider and save each request/SSE response pair.
+    Record,
+
+    /// Answer requests from the saved pairs without any network access.
+    Replay,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelFixtures {
+    pub mode: FixtureMode,
+
+    /// Directory holding the fixtures. Relative paths are resolved against
+    /// the session's working directory.
+    pub dir: PathBuf,
 }
 
 impl ModelProviderInfo {
-    /// Construct a `POST` RequestBuilder for the given URL using the provided
+    /// Construct a `POST` RequestBuilder for the given model using the provided
     /// reqwest Client applying:
     ///   • provider-specific headers (static + env based)
-    ///   • Bearer auth header when an API key is available.
+    ///   • Bearer auth header when an API key is available (`x-api-key` for
+    ///     Anthropic, `x-goog-api-key` for Gemini).
     ///   • Auth token for OAuth.
     ///
     /// If the provider declares an `env_key` but the variable is missing/empty, returns an [`Err`] identical to the
@@ -101,6 +138,7 @@ impl ModelProviderInfo {
         &'a self,
         client: &'a reqwest::Client,
         auth: &Option<CodexAuth>,
+        model: &str,
     ) -> crate::error::Result<reqwest::RequestBuilder> {
         let effective_auth = match self.api_key() {
             Ok(Some(key)) => Some(CodexAuth::from_api_key(&key)),
@@ -114,17 +152,38 @@ impl ModelProviderInfo {
             }
         };
 
-        let url = self.get_full_url(&effective_auth);
+        let url = self.get_full_url(&effective_auth, model);
 
         let mut builder = client.post(url);
 
         if let Some(auth) = effective_auth.as_ref() {
-            builder = builder.bearer_auth(auth.get_token().await?);
+            let token = auth.get_token().await?;
+            builder = match self.wire_api {
+                WireApi::Responses | WireApi::Chat => builder.bearer_auth(token),
+                WireApi::Anthropic => builder.header("x-api-key", token),
+                WireApi::Gemini => builder.header("x-goog-api-key", token),
+            };
+        }
+
+        if self.wire_api == WireApi::Anthropic && !self.has_http_header(ANTHROPIC_VERSION_HEADER) {
+            builder = builder.header(ANTHROPIC_VERSION_HEADER, ANTHROPIC_VERSION);
         }
 
         Ok(self.apply_http_headers(builder))
     }
 
+    fn has_http_header(&self, name: &str) -> bool {
+        self.http_headers
+            .iter()
+            .flat_map(|headers| headers.keys())
+            .chain(
+                self.env_http_headers
+                    .iter()
+                    .flat_map(|headers| headers.keys()),
+            )
+            .any(|header| header.eq_ignore_ascii_case(name))
+    }
+
     fn get_query_string(&self) -> String {
         self.query_params
             .as_ref()
@@ -138,8 +197,12 @@ impl ModelProviderInfo {
             })
     }
 
-    pub(crate) fn get_full_url(&self, auth: &Option<CodexAuth>) -> String {
-        let default_base_url = if matches!(
+    pub(crate) fn get_full_url(&self, auth: &Option<CodexAuth>, model: &str) -> String {
+        let default_base_url = if self.wire_api == WireApi::Anthropic {
+            "https://api.anthropic.com/v1"
+        } else if self.wire_api == WireApi::Gemini {
+            "https://generativelanguage.googleapis.com/v1beta"
+        } else if matches!(
             auth,
             Some(CodexAuth {
                 mode: AuthMode::ChatGPT,
@@ -159,6 +222,11 @@ impl ModelProviderInfo {
         match self.wire_api {
             WireApi::Responses => format!("{base_url}/responses{query_string}"),
             WireApi::Chat => format!("{base_url}/chat/completions{query_string}"),
+            WireApi::Anthropic => format!("{base_url}/messages{query_string}"),
+            WireApi::Gemini => {
+                let extra_params = query_string.replacen('?', "&", 1);
+                format!("{base_url}/models/{model}:streamGenerateContent?alt=sse{extra_params}")
+            }
         }
     }
 
@@ -297,6 +365,7 @@ pub fn built_in_model_providers() -> HashMap<String, ModelProviderInfo> {
                 stream_max_retries: None,
                 stream_idle_timeout_ms: None,
                 requires_openai_auth: true,
+                fixtures: None,
             },
         ),
         (BUILT_IN_OSS_MODEL_PROVIDER_ID, create_oss_provider()),
@@ -341,6 +410,7 @@ pub fn create_oss_provider_with_base_url(base_url: &str) -> ModelProviderInfo {
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
         requires_openai_auth: false,
+        fixtures: None,
     }
 }
 
@@ -380,6 +450,7 @@ base_url = "http://localhost:11434/v1"
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
+            fixtures: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -409,6 +480,7 @@ query_params = { api-version = "2025-04-01-preview" }
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
+            fixtures: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -441,6 +513,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
+            fixtures: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -463,6 +536,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 stream_max_retries: None,
                 stream_idle_timeout_ms: None,
                 requires_openai_auth: false,
+                fixtures: None,
             }
         }
 
@@ -495,6 +569,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
+            fixtures: None,
         };
         assert!(named_provider.is_azure_responses_endpoint());
 
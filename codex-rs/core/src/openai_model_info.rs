@@ -1,10 +1,11 @@
+use std::collections::HashMap;
+
 use crate::model_family::ModelFamily;
+use crate::model_family::ModelFamilyOverride;
+use crate::model_family::find_family_override;
 
-/// Metadata about a model, particularly OpenAI models.
-/// We may want to consider including details like the pricing for
-/// input tokens, output tokens, etc., though users will need to be able to
-/// override this in config.toml, as this information can get out of date.
-/// Though this would help present more accurate pricing information in the UI.
+/// Metadata about a model, particularly OpenAI models. Prices live in
+/// [`crate::model_pricing`].
 #[derive(Debug)]
 pub(crate) struct ModelInfo {
     /// Size of the context window in tokens. This is the maximum size of the input context.
@@ -72,6 +73,51 @@ pub(crate) fn get_model_info(model_family: &ModelFamily) -> Option<ModelInfo> {
 
         _ if slug.starts_with("codex-") => Some(ModelInfo::new(272_000, 128_000)),
 
+        // https://docs.anthropic.com/en/docs/about-claude/models/overview
+        _ if slug.starts_with("claude-opus-4") => Some(ModelInfo::new(200_000, 32_000)),
+        _ if slug.starts_with("claude-sonnet-4") || slug.starts_with("claude-haiku-4") => {
+            Some(ModelInfo::new(200_000, 64_000))
+        }
+        _ if slug.starts_with("claude-3-7-") => Some(ModelInfo::new(200_000, 64_000)),
+        _ if slug.starts_with("claude-3-5-") => Some(ModelInfo::new(200_000, 8_192)),
+        _ if slug.starts_with("claude-3-") => Some(ModelInfo::new(200_000, 4_096)),
+        // Unknown Claude models get the smallest output limit any of them has,
+        // since Anthropic rejects requests whose `max_tokens` is too large.
+        _ if slug.starts_with("claude-") => Some(ModelInfo::new(200_000, 4_096)),
+
+        // https://ai.google.dev/gemini-api/docs/models
+        _ if slug.starts_with("gemini-2.5") => Some(ModelInfo::new(1_048_576, 65_536)),
+
         _ => None,
     }
 }
+
+/// Token limits for a model after applying `[model_families]` overrides.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ModelLimits {
+    pub(crate) context_window: Option<u64>,
+    pub(crate) max_output_tokens: Option<u64>,
+    pub(crate) auto_compact_token_limit: Option<i64>,
+}
+
+/// Limits for `model_family`: each value comes from the matching
+/// `[model_families]` table if it sets it, else from [`get_model_info`].
+pub(crate) fn model_limits(
+    model_family: &ModelFamily,
+    overrides: &HashMap<String, ModelFamilyOverride>,
+) -> ModelLimits {
+    let info = get_model_info(model_family);
+    let settings = find_family_override(overrides, &model_family.slug)
+        .map(|(_, family_override)| &family_override.settings);
+    ModelLimits {
+        context_window: settings
+            .and_then(|settings| settings.context_window)
+            .or_else(|| info.as_ref().map(|info| info.context_window)),
+        max_output_tokens: settings
+            .and_then(|settings| settings.max_output_tokens)
+            .or_else(|| info.as_ref().map(|info| info.max_output_tokens)),
+        auto_compact_token_limit: settings
+            .and_then(|settings| settings.auto_compact_token_limit)
+            .or_else(|| info.as_ref().and_then(|info| info.auto_compact_token_limit)),
+    }
+}
@@ -5,6 +5,7 @@ use crate::default_client::originator;
 use codex_otel::config::OtelExporter;
 use codex_otel::config::OtelHttpProtocol;
 use codex_otel::config::OtelSettings;
+use codex_otel::otel_event_manager::METRICS_TARGET;
 use codex_otel::otel_provider::OtelProvider;
 use std::error::Error;
 
@@ -55,7 +56,14 @@ pub fn build_provider(
 }
 
 /// Filter predicate for exporting only Codex-owned events via OTEL.
-/// Keeps events that originated from codex_otel module
+/// Keeps events that originated from codex_otel module, except the
+/// metric-only events which are exported through the trace layer.
 pub fn codex_export_filter(meta: &tracing::Metadata<'_>) -> bool {
+    meta.target().starts_with("codex_otel") && meta.target() != METRICS_TARGET
+}
+
+/// Filter predicate for the OTEL trace and metrics layer. Keeps the spans and
+/// metric events that originated from codex_otel module.
+pub fn codex_trace_filter(meta: &tracing::Metadata<'_>) -> bool {
     meta.target().starts_with("codex_otel")
 }
@@ -0,0 +1,294 @@
+//! Client-side pacing driven by the rate-limit headers providers send back.
+//!
+//! Every `ModelClient` talking to the same provider with the same account
+//! shares one [`RateLimitGovernor`], so the primary conversation, delegates
+//! and TUMIX agents running in this process pace against a single view of
+//! the limits. Before each request the governor decides how long to wait:
+//!
+//! - after a `429` with `Retry-After`, every request waits until then;
+//! - while a window is used up, requests wait for it to reset;
+//! - above `threshold_percent`, request starts are queued and spread over
+//!   the time left in the window, one per remaining percentage point.
+//!
+//! No single wait exceeds `max_wait_secs`: longer delays, such as the gap
+//! between requests on a nearly used-up weekly window, are clamped to it.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+use reqwest::header::HeaderMap;
+
+use crate::client::parse_rate_limit_snapshot;
+use crate::config_types::RateLimitPacing;
+use crate::protocol::RateLimitSnapshot;
+
+/// Identifies the quota a request draws from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct RateLimitKey {
+    /// Key of the provider in `model_providers`.
+    pub provider: String,
+    /// ChatGPT account id or API key fingerprint; empty when unauthenticated.
+    pub account: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct WindowState {
+    used_percent: f64,
+    resets_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct GovernorState {
+    /// Windows from the latest snapshot that report a reset time.
+    windows: Vec<WindowState>,
+    /// Set from `Retry-After` on `429` responses.
+    blocked_until: Option<Instant>,
+    /// Earliest start of the next paced request.
+    next_start: Option<Instant>,
+}
+
+impl GovernorState {
+    /// Delay before a request issued at `now`. With `reserve`, the request
+    /// also claims its slot in the pacing queue.
+    fn schedule(
+        &mut self,
+        pacing: &RateLimitPacing,
+        now: Instant,
+        reserve: bool,
+    ) -> Option<Duration> {
+        if !pacing.enabled {
+            return None;
+        }
+        self.windows.retain(|window| window.resets_at > now);
+        if self.blocked_until.is_some_and(|until| until <= now) {
+            self.blocked_until = None;
+        }
+
+        let max_wait = Duration::from_secs(pacing.max_wait_secs);
+        let mut start = self.blocked_until.unwrap_or(now);
+        let mut spacing = Duration::ZERO;
+        for window in &self.windows {
+            if window.used_percent >= 100.0 {
+                start = start.max(window.resets_at);
+            } else if window.used_percent >= pacing.threshold_percent {
+                let headroom = (100.0 - window.used_percent).max(1.0);
+                let remaining = window.resets_at.saturating_duration_since(now);
+                spacing = spacing.max(remaining.div_f64(headroom));
+            }
+        }
+        let spacing = spacing.min(max_wait);
+        if !spacing.is_zero()
+            && let Some(next_start) = self.next_start
+        {
+            start = start.max(next_start);
+        }
+        let start = start.min(now + max_wait);
+
+        if reserve && !spacing.is_zero() {
+            self.next_start = Some(start + spacing);
+        }
+        let wait = start.saturating_duration_since(now);
+        (!wait.is_zero()).then_some(wait)
+    }
+}
+
+/// Shared pacing state for one [`RateLimitKey`].
+#[derive(Debug, Default)]
+pub(crate) struct RateLimitGovernor {
+    state: Mutex<GovernorState>,
+}
+
+/// Returns the process-wide governor for `key`.
+pub(crate) fn governor_for(key: RateLimitKey) -> Arc<RateLimitGovernor> {
+    static GOVERNORS: OnceLock<Mutex<HashMap<RateLimitKey, Arc<RateLimitGovernor>>>> =
+        OnceLock::new();
+    let Ok(mut governors) = GOVERNORS.get_or_init(Mutex::default).lock() else {
+        return Arc::default();
+    };
+    Arc::clone(governors.entry(key).or_default())
+}
+
+impl RateLimitGovernor {
+    /// Replaces the known windows with those from `snapshot`.
+    pub(crate) fn observe(&self, snapshot: &RateLimitSnapshot) {
+        let now = Instant::now();
+        let windows: Vec<WindowState> = [&snapshot.primary, &snapshot.secondary]
+            .into_iter()
+            .flatten()
+            .filter_map(|window| {
+                window.resets_in_seconds.map(|seconds| WindowState {
+                    used_percent: window.used_percent,
+                    resets_at: now + Duration::from_secs(seconds),
+                })
+            })
+            .collect();
+        if let Ok(mut state) = self.state.lock() {
+            state.windows = windows;
+        }
+    }
+
+    /// Holds back every request for `delay`, e.g. after a `429`.
+    pub(crate) fn block_for(&self, delay: Duration) {
+        let until = Instant::now() + delay;
+        if let Ok(mut state) = self.state.lock() {
+            state.blocked_until = Some(state.blocked_until.map_or(until, |b| b.max(until)));
+        }
+    }
+
+    /// How long the next request would wait, without claiming a slot.
+    pub(crate) fn pending_delay(&self, pacing: &RateLimitPacing) -> Option<Duration> {
+        let mut state = self.state.lock().ok()?;
+        state.schedule(pacing, Instant::now(), false)
+    }
+
+    /// Claims a slot and waits until the request may be sent.
+    pub(crate) async fn acquire(&self, pacing: &RateLimitPacing) {
+        let delay = match self.state.lock() {
+            Ok(mut state) => state.schedule(pacing, Instant::now(), true),
+            Err(_) => None,
+        };
+        if let Some(delay) = delay {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// A governor together with the pacing to apply, handed to the request loops
+/// of every wire API.
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimitPacer {
+    pub(crate) governor: Arc<RateLimitGovernor>,
+    pub(crate) pacing: RateLimitPacing,
+}
+
+impl RateLimitPacer {
+    /// Waits until the next attempt may be sent.
+    pub(crate) async fn acquire(&self) {
+        self.governor.acquire(&self.pacing).await;
+    }
+
+    /// Records the rate-limit windows reported in response `headers`.
+    /// Responses without rate-limit headers leave the known windows alone.
+    pub(crate) fn observe_headers(&self, headers: &HeaderMap) -> Option<RateLimitSnapshot> {
+        let snapshot = parse_rate_limit_snapshot(headers)
+            .filter(|snapshot| snapshot.primary.is_some() || snapshot.secondary.is_some())?;
+        self.governor.observe(&snapshot);
+        Some(snapshot)
+    }
+
+    /// Holds back every request sharing this quota after a `429`.
+    pub(crate) fn block_for(&self, delay: Duration) {
+        self.governor.block_for(delay);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn window(used_percent: f64, resets_at: Instant) -> WindowState {
+        WindowState {
+            used_percent,
+            resets_at,
+        }
+    }
+
+    #[test]
+    fn waits_for_exhausted_window_and_retry_after() {
+        let pacing = RateLimitPacing::default();
+        let now = Instant::now();
+        let mut state = GovernorState {
+            windows: vec![window(50.0, now + Duration::from_secs(600))],
+            ..Default::default()
+        };
+        assert_eq!(state.schedule(&pacing, now, true), None);
+
+        state.windows = vec![window(100.0, now + Duration::from_secs(30))];
+        assert_eq!(
+            state.schedule(&pacing, now, true),
+            Some(Duration::from_secs(30))
+        );
+        // The window has reset.
+        assert_eq!(
+            state.schedule(&pacing, now + Duration::from_secs(31), true),
+            None
+        );
+
+        state.blocked_until = Some(now + Duration::from_secs(5));
+        assert_eq!(
+            state.schedule(&pacing, now, true),
+            Some(Duration::from_secs(5))
+        );
+
+        // Longer waits are clamped to `max_wait_secs`.
+        state.blocked_until = None;
+        state.windows = vec![window(100.0, now + Duration::from_secs(3_600))];
+        assert_eq!(
+            state.schedule(&pacing, now, true),
+            Some(Duration::from_secs(pacing.max_wait_secs))
+        );
+    }
+
+    #[test]
+    fn spaces_requests_above_threshold() {
+        let pacing = RateLimitPacing::default();
+        let now = Instant::now();
+        // 5 points of headroom over 100s: one request every 20s.
+        let mut state = GovernorState {
+            windows: vec![window(95.0, now + Duration::from_secs(100))],
+            ..Default::default()
+        };
+
+        assert_eq!(state.schedule(&pacing, now, false), None);
+        assert_eq!(state.schedule(&pacing, now, true), None);
+        assert_eq!(
+            state.schedule(&pacing, now, false),
+            Some(Duration::from_secs(20))
+        );
+        assert_eq!(
+            state.schedule(&pacing, now, true),
+            Some(Duration::from_secs(20))
+        );
+        assert_eq!(
+            state.schedule(&pacing, now, true),
+            Some(Duration::from_secs(40))
+        );
+
+        // A five-hour window at 95% would space requests an hour apart.
+        let mut state = GovernorState {
+            windows: vec![window(95.0, now + Duration::from_secs(5 * 3_600))],
+            ..Default::default()
+        };
+        let max_wait = Duration::from_secs(pacing.max_wait_secs);
+        assert_eq!(state.schedule(&pacing, now, true), None);
+        assert_eq!(state.schedule(&pacing, now, true), Some(max_wait));
+        assert_eq!(state.schedule(&pacing, now, true), Some(max_wait));
+
+        let disabled = RateLimitPacing {
+            enabled: false,
+            ..pacing
+        };
+        assert_eq!(state.schedule(&disabled, now, true), None);
+    }
+
+    #[test]
+    fn governors_are_shared_per_key() {
+        let key = |account: &str| RateLimitKey {
+            provider: "governor-test".to_string(),
+            account: account.to_string(),
+        };
+        assert!(Arc::ptr_eq(
+            &governor_for(key("a")),
+            &governor_for(key("a"))
+        ));
+        assert!(!Arc::ptr_eq(
+            &governor_for(key("a")),
+            &governor_for(key("b"))
+        ));
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::Component;
 use std::path::Path;
@@ -12,6 +13,7 @@ use crate::command_safety::is_dangerous_command::command_might_be_dangerous;
 use crate::command_safety::is_safe_command::is_known_safe_command;
 use crate::protocol::AskForApproval;
 use crate::protocol::SandboxPolicy;
+use crate::protocol::WritableRoot;
 
 #[derive(Debug, PartialEq)]
 pub enum SafetyCheck {
@@ -48,6 +50,20 @@ pub fn assess_patch_safety(
         }
     }
 
+    // The sandbox follows symlinks, so a link created by this patch (or one
+    // already on disk) can redirect a write that looks like it lands in the
+    // workspace to somewhere outside of it. Never auto-approve those.
+    if patch_escapes_writable_paths_via_symlinks(action, sandbox_policy, cwd) {
+        return if policy == AskForApproval::Never {
+            SafetyCheck::Reject {
+                reason: "symlink points outside of the project; rejected by user approval settings"
+                    .to_string(),
+            }
+        } else {
+            SafetyCheck::AskUser
+        };
+    }
+
     // Even though the patch *appears* to be constrained to writable paths, it
     // is possible that paths in the patch are hard links to files outside the
     // writable roots, so we should still run `apply_patch` in a sandbox in that
@@ -227,22 +243,6 @@ fn is_write_patch_constrained_to_writable_paths(
         SandboxPolicy::WorkspaceWrite { .. } => sandbox_policy.get_writable_roots_with_cwd(cwd),
     };
 
-    // Normalize a path by removing `.` and resolving `..` without touching the
-    // filesystem (works even if the file does not exist).
-    fn normalize(path: &Path) -> Option<PathBuf> {
-        let mut out = PathBuf::new();
-        for comp in path.components() {
-            match comp {
-                Component::ParentDir => {
-                    out.pop();
-                }
-                Component::CurDir => { /* skip */ }
-                other => out.push(other.as_os_str()),
-            }
-        }
-        Some(out)
-    }
-
     // Determine whether `path` is inside **any** writable root. Both `path`
     // and roots are converted to absolute, normalized forms before the
     // prefix check.
@@ -264,7 +264,10 @@ fn is_write_patch_constrained_to_writable_paths(
 
     for (path, change) in action.changes() {
         match change {
-            ApplyPatchFileChange::Add { .. } | ApplyPatchFileChange::Delete { .. } => {
+            ApplyPatchFileChange::Add { .. }
+            | ApplyPatchFileChange::AddBinary { .. }
+            | ApplyPatchFileChange::AddSymlink { .. }
+            | ApplyPatchFileChange::Delete { .. } => {
                 if !is_path_writable(path) {
                     return false;
                 }
@@ -285,6 +288,151 @@ fn is_write_patch_constrained_to_writable_paths(
     true
 }
 
+/// Returns `true` when a write in `action` would land outside the writable
+/// roots once symlinks are followed: either the target of a symlink the patch
+/// creates, or a path that goes through a symlinked directory (already on
+/// disk or created earlier in the same patch).
+fn patch_escapes_writable_paths_via_symlinks(
+    action: &ApplyPatchAction,
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+) -> bool {
+    let writable_roots = match sandbox_policy {
+        SandboxPolicy::ReadOnly | SandboxPolicy::DangerFullAccess => return false,
+        SandboxPolicy::WorkspaceWrite { .. } => sandbox_policy.get_writable_roots_with_cwd(cwd),
+    };
+    let no_links = HashMap::new();
+    let resolve_root =
+        |path: &Path| resolve_symlinks(path, &no_links).unwrap_or_else(|| path.to_path_buf());
+    let writable_roots: Vec<WritableRoot> = writable_roots
+        .iter()
+        .map(|writable_root| WritableRoot {
+            root: resolve_root(&writable_root.root),
+            read_only_subpaths: writable_root
+                .read_only_subpaths
+                .iter()
+                .map(|subpath| resolve_root(subpath))
+                .collect(),
+        })
+        .collect();
+
+    let absolute = |p: &Path| {
+        let abs = if p.is_absolute() {
+            p.to_path_buf()
+        } else {
+            cwd.join(p)
+        };
+        normalize(&abs).unwrap_or(abs)
+    };
+    let pending_links: HashMap<PathBuf, PathBuf> = action
+        .changes()
+        .iter()
+        .filter_map(|(path, change)| match change {
+            ApplyPatchFileChange::AddSymlink { target } => Some((absolute(path), target.clone())),
+            _ => None,
+        })
+        .collect();
+
+    // With `follow_last` unset, only the directories leading to `p` are
+    // resolved, for changes that act on a link rather than on its target.
+    let escapes = |p: &Path, follow_last: bool| {
+        let abs = absolute(p);
+        let resolved = match (follow_last, abs.parent(), abs.file_name()) {
+            (false, Some(parent), Some(name)) => {
+                resolve_symlinks(parent, &pending_links).map(|parent| parent.join(name))
+            }
+            _ => resolve_symlinks(&abs, &pending_links),
+        };
+        match resolved {
+            Some(resolved) => !writable_roots
+                .iter()
+                .any(|writable_root| writable_root.is_path_writable(&resolved)),
+            // A symlink loop cannot be reasoned about; let the user decide.
+            None => true,
+        }
+    };
+
+    action.changes().iter().any(|(path, change)| match change {
+        // Deleting a link removes the link itself, not what it points to.
+        ApplyPatchFileChange::Delete { .. } => escapes(path, false),
+        // `path` is in `pending_links`, so following it checks the target.
+        ApplyPatchFileChange::AddSymlink { .. } => escapes(path, false) || escapes(path, true),
+        ApplyPatchFileChange::Update {
+            move_path: Some(dest),
+            ..
+        } => escapes(path, true) || escapes(dest, true),
+        _ => escapes(path, true),
+    })
+}
+
+/// Normalize a path by removing `.` and resolving `..` without touching the
+/// filesystem (works even if the file does not exist).
+fn normalize(path: &Path) -> Option<PathBuf> {
+    let mut out = PathBuf::new();
+    for comp in path.components() {
+        match comp {
+            Component::ParentDir => {
+                out.pop();
+            }
+            Component::CurDir => { /* skip */ }
+            other => out.push(other.as_os_str()),
+        }
+    }
+    Some(out)
+}
+
+/// Follows every symlink along the absolute `path`, including the links in
+/// `pending_links` that a patch is about to create, and returns the path a
+/// write would actually reach. Components that do not exist yet are kept
+/// as-is. Returns `None` when the links form a cycle.
+fn resolve_symlinks(path: &Path, pending_links: &HashMap<PathBuf, PathBuf>) -> Option<PathBuf> {
+    // Same limit as Linux's MAXSYMLINKS.
+    const MAX_LINK_HOPS: usize = 40;
+
+    let mut hops = 0;
+    let mut resolved = PathBuf::new();
+    let mut remaining: Vec<PathBuf> = path
+        .components()
+        .rev()
+        .map(|comp| PathBuf::from(comp.as_os_str()))
+        .collect();
+    while let Some(next) = remaining.pop() {
+        match next.components().next() {
+            Some(Component::ParentDir) => {
+                resolved.pop();
+            }
+            Some(Component::CurDir) | None => {}
+            Some(Component::Normal(name)) => {
+                let candidate = resolved.join(name);
+                let link = pending_links
+                    .get(&candidate)
+                    .cloned()
+                    .or_else(|| std::fs::read_link(&candidate).ok());
+                match link {
+                    Some(target) => {
+                        hops += 1;
+                        if hops > MAX_LINK_HOPS {
+                            return None;
+                        }
+                        // A relative target is resolved against the link's
+                        // directory, which is what `resolved` still holds.
+                        remaining.extend(
+                            target
+                                .components()
+                                .rev()
+                                .map(|comp| PathBuf::from(comp.as_os_str())),
+                        );
+                    }
+                    None => resolved = candidate,
+                }
+            }
+            // Pushing a root (or prefix) replaces whatever was resolved so far.
+            Some(other) => resolved.push(other.as_os_str()),
+        }
+    }
+    Some(resolved)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -340,6 +488,73 @@ mod tests {
         ));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn symlink_pointing_outside_writable_roots_asks_user() {
+        let tmp = TempDir::new().unwrap();
+        let cwd = tmp.path().join("workspace");
+        let outside = tmp.path().join("outside");
+        std::fs::create_dir_all(&cwd).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+        let parse = |patch: &str| {
+            let argv = vec!["apply_patch".to_string(), patch.to_string()];
+            match codex_apply_patch::maybe_parse_apply_patch_verified(&argv, &cwd) {
+                codex_apply_patch::MaybeApplyPatchVerified::Body(action) => action,
+                other => panic!("expected a verified patch, got {other:?}"),
+            }
+        };
+
+        // A new link pointing out of the workspace, then a write through it.
+        let through_new_link = parse(
+            "*** Begin Patch\n*** Add Symlink: escape -> ../outside\n*** Add File: escape/evil.txt\n+pwned\n*** End Patch",
+        );
+        assert_eq!(
+            assess_patch_safety(&through_new_link, AskForApproval::OnRequest, &policy, &cwd),
+            SafetyCheck::AskUser
+        );
+
+        // A link that only points out of the workspace.
+        let link_only =
+            parse("*** Begin Patch\n*** Add Symlink: escape -> ../outside\n*** End Patch");
+        assert_eq!(
+            assess_patch_safety(&link_only, AskForApproval::OnFailure, &policy, &cwd),
+            SafetyCheck::AskUser
+        );
+
+        // A write through a symlinked directory that already exists on disk.
+        std::os::unix::fs::symlink(&outside, cwd.join("existing")).unwrap();
+        let through_existing_link =
+            parse("*** Begin Patch\n*** Add File: existing/evil.txt\n+pwned\n*** End Patch");
+        assert_eq!(
+            assess_patch_safety(
+                &through_existing_link,
+                AskForApproval::OnRequest,
+                &policy,
+                &cwd
+            ),
+            SafetyCheck::AskUser
+        );
+        assert!(matches!(
+            assess_patch_safety(&through_existing_link, AskForApproval::Never, &policy, &cwd),
+            SafetyCheck::Reject { .. }
+        ));
+
+        // Links that stay inside the workspace are still constrained.
+        let inside_link =
+            parse("*** Begin Patch\n*** Add Symlink: alias -> existing_dir\n*** End Patch");
+        assert!(!patch_escapes_writable_paths_via_symlinks(
+            &inside_link,
+            &policy,
+            &cwd
+        ));
+    }
+
     #[test]
     fn test_request_escalated_privileges() {
         // Should not be a trusted command
@@ -1,4 +1,5 @@
 use crate::RolloutRecorder;
+use crate::cost_tracker::CostTracker;
 use crate::delegate_tool::DelegateToolAdapter;
 use crate::exec_command::ExecSessionManager;
 use crate::executor::Executor;
@@ -16,6 +17,8 @@ pub(crate) struct SessionServices {
     pub(crate) rollout: Mutex<Option<RolloutRecorder>>,
     pub(crate) user_shell: crate::shell::Shell,
     pub(crate) show_raw_agent_reasoning: bool,
+    pub(crate) auto_checkpoints: bool,
     pub(crate) executor: Executor,
     pub(crate) delegate_adapter: Option<Arc<dyn DelegateToolAdapter>>,
+    pub(crate) cost_tracker: CostTracker,
 }
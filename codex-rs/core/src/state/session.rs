@@ -1,7 +1,15 @@
 //! Session-wide mutable state.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
 use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::Checkpoint;
+use indexmap::IndexMap;
 
+use crate::codex::TurnContext;
 use crate::conversation_history::ConversationHistory;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
@@ -13,6 +21,19 @@ pub(crate) struct SessionState {
     pub(crate) history: ConversationHistory,
     pub(crate) token_info: Option<TokenUsageInfo>,
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
+    pub(crate) checkpoints: Vec<Checkpoint>,
+    pub(crate) checkpoints_disabled: bool,
+    /// Contents of files as the model last saw them, oldest first.
+    pub(crate) read_versions: IndexMap<PathBuf, Arc<str>>,
+    /// `(server, tool)` pairs the user approved for the rest of the session.
+    pub(crate) approved_mcp_tools: HashSet<(String, String)>,
+    /// Tokens each MCP server has spent on sampling requests.
+    pub(crate) sampling_tokens_used: HashMap<String, u64>,
+    /// Number of `model_fallbacks` entries the session has moved past.
+    pub(crate) fallbacks_used: usize,
+    /// Turn context from the latest switch to a fallback, not yet picked up
+    /// by the submission loop.
+    pub(crate) failover_turn_context: Option<Arc<TurnContext>>,
 }
 
 impl SessionState {
@@ -73,5 +94,57 @@ impl SessionState {
         }
     }
 
+    // Checkpoint helpers
+    pub(crate) fn record_checkpoint(&mut self, checkpoint: Checkpoint, max_tracked: usize) {
+        self.checkpoints.push(checkpoint);
+        if self.checkpoints.len() > max_tracked {
+            let excess = self.checkpoints.len() - max_tracked;
+            self.checkpoints.drain(..excess);
+        }
+    }
+
+    pub(crate) fn find_checkpoint(&self, checkpoint_id: &str) -> Option<Checkpoint> {
+        self.checkpoints
+            .iter()
+            .find(|checkpoint| checkpoint.id == checkpoint_id)
+            .cloned()
+    }
+
+    pub(crate) fn checkpoints(&self) -> Vec<Checkpoint> {
+        self.checkpoints.clone()
+    }
+
+    /// Forget `checkpoint_id` and every checkpoint taken after it.
+    pub(crate) fn forget_checkpoints_from(&mut self, checkpoint_id: &str) {
+        if let Some(pos) = self
+            .checkpoints
+            .iter()
+            .position(|checkpoint| checkpoint.id == checkpoint_id)
+        {
+            self.checkpoints.truncate(pos);
+        }
+    }
+
+    // Read version helpers
+    pub(crate) fn record_read_version(
+        &mut self,
+        path: PathBuf,
+        contents: Arc<str>,
+        max_tracked: usize,
+    ) {
+        // Re-inserting moves the entry to the back so eviction drops the
+        // least recently read file.
+        self.read_versions.shift_remove(&path);
+        self.read_versions.insert(path, contents);
+        if self.read_versions.len() > max_tracked {
+            let excess = self.read_versions.len() - max_tracked;
+            self.read_versions.drain(..excess);
+        }
+    }
+
+    pub(crate) fn read_versions(&self) -> IndexMap<PathBuf, Arc<str>> {
+        self.read_versions.clone()
+    }
+
     // Pending input/approval moved to TurnState.
 }
@@ -10,6 +10,7 @@ use tokio::select;
 use tokio::sync::Notify;
 use tokio_util::sync::CancellationToken;
 use tokio_util::task::AbortOnDropHandle;
+use tracing::Instrument;
 use tracing::trace;
 use tracing::warn;
 
@@ -28,6 +29,7 @@ use crate::state::TaskKind;
 pub(crate) use compact::CompactTask;
 pub(crate) use regular::RegularTask;
 pub(crate) use review::ReviewTask;
+pub(crate) use review::build_review_prompt;
 
 const GRACEFULL_INTERRUPTION_TIMEOUT_MS: u64 = 100;
 
@@ -82,30 +84,37 @@ impl Session {
         let done = Arc::new(Notify::new());
 
         let done_clone = Arc::clone(&done);
+        let turn_span = turn_context
+            .client
+            .get_otel_event_manager()
+            .turn_span(&sub_id, task_kind.header_value());
         let handle = {
             let session_ctx = Arc::new(SessionTaskContext::new(Arc::clone(self)));
             let ctx = Arc::clone(&turn_context);
             let task_for_run = Arc::clone(&task);
             let sub_clone = sub_id.clone();
             let task_cancellation_token = cancellation_token.child_token();
-            tokio::spawn(async move {
-                let last_agent_message = task_for_run
-                    .run(
-                        Arc::clone(&session_ctx),
-                        ctx,
-                        sub_clone.clone(),
-                        input,
-                        task_cancellation_token.child_token(),
-                    )
-                    .await;
-
-                if !task_cancellation_token.is_cancelled() {
-                    // Emit completion uniformly from spawn site so all tasks share the same lifecycle.
-                    let sess = session_ctx.clone_session();
-                    sess.on_task_finished(sub_clone, last_agent_message).await;
+            tokio::spawn(
+                async move {
+                    let last_agent_message = task_for_run
+                        .run(
+                            Arc::clone(&session_ctx),
+                            ctx,
+                            sub_clone.clone(),
+                            input,
+                            task_cancellation_token.child_token(),
+                        )
+                        .await;
+
+                    if !task_cancellation_token.is_cancelled() {
+                        // Emit completion uniformly from spawn site so all tasks share the same lifecycle.
+                        let sess = session_ctx.clone_session();
+                        sess.on_task_finished(sub_clone, last_agent_message).await;
+                    }
+                    done_clone.notify_waiters();
                 }
-                done_clone.notify_waiters();
-            })
+                .instrument(turn_span),
+            )
         };
 
         let running_task = RunningTask {
@@ -119,9 +128,17 @@ impl Session {
     }
 
     pub async fn abort_all_tasks(self: &Arc<Self>, reason: TurnAbortReason) {
-        for (sub_id, task) in self.take_all_running_tasks().await {
+        let tasks = self.take_all_running_tasks().await;
+        if tasks.is_empty() {
+            return;
+        }
+        for (sub_id, task) in tasks {
             self.handle_task_abort(sub_id, task, reason.clone()).await;
         }
+        // Elicitations raised during the aborted turn will never be answered.
+        self.services
+            .mcp_connection_manager
+            .drop_pending_elicitations();
     }
 
     pub async fn on_task_finished(
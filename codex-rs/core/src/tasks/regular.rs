@@ -29,6 +29,7 @@ impl SessionTask for RegularTask {
         cancellation_token: CancellationToken,
     ) -> Option<String> {
         let sess = session.clone_session();
+        sess.capture_turn_checkpoint(&ctx, &sub_id).await;
         run_task(
             sess,
             ctx,
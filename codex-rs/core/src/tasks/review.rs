@@ -1,11 +1,15 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use codex_protocol::protocol::ReviewRequest;
+use codex_utils_string::take_bytes_at_char_boundary;
 use tokio_util::sync::CancellationToken;
 
 use crate::codex::TurnContext;
 use crate::codex::exit_review_mode;
 use crate::codex::run_task;
+use crate::git_info::review_target_diff;
 use crate::protocol::InputItem;
 use crate::state::TaskKind;
 
@@ -45,3 +49,34 @@ impl SessionTask for ReviewTask {
         exit_review_mode(session.clone_session(), sub_id.to_string(), None).await;
     }
 }
+
+/// Diffs larger than this are truncated before being embedded in the prompt.
+const MAX_REVIEW_DIFF_BYTES: usize = 256 * 1024;
+
+/// Build the initial prompt for a review. When the request names a
+/// [`codex_protocol::protocol::ReviewTarget`], the corresponding diff is computed here and appended so
+/// the model does not have to discover it with shell commands.
+pub(crate) async fn build_review_prompt(
+    cwd: &Path,
+    review_request: &ReviewRequest,
+) -> Result<String, String> {
+    let Some(target) = &review_request.target else {
+        return Ok(review_request.prompt.clone());
+    };
+    let Some(diff) = review_target_diff(cwd, target).await else {
+        return Err(format!("Failed to compute the diff for {target}."));
+    };
+    if diff.trim().is_empty() {
+        return Err(format!("There are no changes to review for {target}."));
+    }
+
+    let mut diff_for_prompt = take_bytes_at_char_boundary(&diff, MAX_REVIEW_DIFF_BYTES).to_string();
+    if diff_for_prompt.len() < diff.len() {
+        diff_for_prompt
+            .push_str("\n[... diff truncated; inspect the remaining files directly ...]");
+    }
+    Ok(format!(
+        "{}\n\nThe diff for {target} is below.\n\n```diff\n{diff_for_prompt}\n```",
+        review_request.prompt
+    ))
+}
@@ -134,13 +134,16 @@ Your patch language is a stripped‑down, file‑oriented diff format designed t
 
 Within that envelope, you get a sequence of file operations.
 You MUST include a header to specify the action you are taking.
-Each operation starts with one of three headers:
+Each operation starts with one of these headers:
 
 *** Add File: <path> - create a new file. Every following line is a + line (the initial contents).
-*** Delete File: <path> - remove an existing file. Nothing follows.
+*** Add Binary File: <path> - create a new binary file. Every following line is a + line holding base64 data.
+*** Add Symlink: <path> -> <target> - create a symbolic link. Nothing follows.
+*** Delete File: <path> - remove an existing file or symlink. Nothing follows.
 *** Update File: <path> - patch an existing file in place (optionally with a rename).
 
-May be immediately followed by *** Move to: <new path> if you want to rename the file.
+An Add File, Add Binary File or Update File header may be followed by *** Mode: <octal> (e.g. *** Mode: 755) to set the file's permissions; an Update File that only changes the mode needs no hunks.
+An Update File header may be immediately followed by *** Move to: <new path> if you want to rename the file.
 Then one or more “hunks”, each introduced by @@ (optionally followed by a hunk header).
 Within a hunk each line starts with:
 
@@ -166,11 +169,14 @@ The full grammar definition is below:
 Patch := Begin { FileOp } End
 Begin := "*** Begin Patch" NEWLINE
 End := "*** End Patch" NEWLINE
-FileOp := AddFile | DeleteFile | UpdateFile
-AddFile := "*** Add File: " path NEWLINE { "+" line NEWLINE }
+FileOp := AddFile | AddBinaryFile | AddSymlink | DeleteFile | UpdateFile
+AddFile := "*** Add File: " path NEWLINE [ Mode ] { "+" line NEWLINE }
+AddBinaryFile := "*** Add Binary File: " path NEWLINE [ Mode ] { "+" base64 NEWLINE }
+AddSymlink := "*** Add Symlink: " path " -> " target NEWLINE
 DeleteFile := "*** Delete File: " path NEWLINE
-UpdateFile := "*** Update File: " path NEWLINE [ MoveTo ] { Hunk }
+UpdateFile := "*** Update File: " path NEWLINE [ MoveTo ] [ Mode ] { Hunk }
 MoveTo := "*** Move to: " newPath NEWLINE
+Mode := "*** Mode: " octal NEWLINE
 Hunk := "@@" [ header ] NEWLINE { HunkLine } [ "*** End of File" NEWLINE ]
 HunkLine := (" " | "-" | "+") text NEWLINE
 
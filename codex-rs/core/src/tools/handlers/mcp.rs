@@ -19,6 +19,7 @@ impl ToolHandler for McpHandler {
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
         let ToolInvocation {
             session,
+            turn,
             sub_id,
             call_id,
             payload,
@@ -43,6 +44,7 @@ impl ToolHandler for McpHandler {
 
         let response = handle_mcp_tool_call(
             session.as_ref(),
+            turn.as_ref(),
             &sub_id,
             call_id.clone(),
             server,
@@ -23,13 +23,15 @@ use crate::function_tool::FunctionCallError;
 use crate::tools::context::ApplyPatchCommandContext;
 use crate::tools::context::ExecCommandContext;
 use crate::tools::context::SharedTurnDiffTracker;
+use codex_apply_patch::ApplyPatchFileChange;
 use codex_apply_patch::MaybeApplyPatchVerified;
-use codex_apply_patch::maybe_parse_apply_patch_verified;
+use codex_apply_patch::maybe_parse_apply_patch_verified_with_base;
 use codex_protocol::protocol::AskForApproval;
 use codex_utils_string::take_bytes_at_char_boundary;
 use codex_utils_string::take_last_bytes_at_char_boundary;
 pub use router::ToolRouter;
 use serde::Serialize;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tracing::trace;
 
@@ -67,38 +69,43 @@ pub(crate) async fn handle_container_exec_with_params(
         )));
     }
 
-    // check if this was a patch, and apply it if so
-    let apply_patch_exec = match maybe_parse_apply_patch_verified(&params.command, &params.cwd) {
-        MaybeApplyPatchVerified::Body(changes) => {
-            match apply_patch::apply_patch(
-                sess.as_ref(),
-                turn_context.as_ref(),
-                &sub_id,
-                &call_id,
-                changes,
-            )
-            .await
-            {
-                InternalApplyPatchInvocation::Output(item) => return item,
-                InternalApplyPatchInvocation::DelegateToExec(apply_patch_exec) => {
-                    Some(apply_patch_exec)
+    // check if this was a patch, and apply it if so. Files the model read
+    // earlier serve as merge bases when the patch no longer matches the disk.
+    let read_versions = sess.read_versions().await;
+    let apply_patch_exec =
+        match maybe_parse_apply_patch_verified_with_base(&params.command, &params.cwd, |path| {
+            read_versions.get(path).map(ToString::to_string)
+        }) {
+            MaybeApplyPatchVerified::Body(changes) => {
+                match apply_patch::apply_patch(
+                    sess.as_ref(),
+                    turn_context.as_ref(),
+                    &sub_id,
+                    &call_id,
+                    changes,
+                )
+                .await
+                {
+                    InternalApplyPatchInvocation::Output(item) => return item,
+                    InternalApplyPatchInvocation::DelegateToExec(apply_patch_exec) => {
+                        Some(apply_patch_exec)
+                    }
                 }
             }
-        }
-        MaybeApplyPatchVerified::CorrectnessError(parse_error) => {
-            // It looks like an invocation of `apply_patch`, but we
-            // could not resolve it into a patch that would apply
-            // cleanly. Return to model for resample.
-            return Err(FunctionCallError::RespondToModel(format!(
-                "apply_patch verification failed: {parse_error}"
-            )));
-        }
-        MaybeApplyPatchVerified::ShellParseError(error) => {
-            trace!("Failed to parse shell command, {error:?}");
-            None
-        }
-        MaybeApplyPatchVerified::NotApplyPatch => None,
-    };
+            MaybeApplyPatchVerified::CorrectnessError(parse_error) => {
+                // It looks like an invocation of `apply_patch`, but we
+                // could not resolve it into a patch that would apply
+                // cleanly. Return to model for resample.
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "apply_patch verification failed: {parse_error}"
+                )));
+            }
+            MaybeApplyPatchVerified::ShellParseError(error) => {
+                trace!("Failed to parse shell command, {error:?}");
+                None
+            }
+            MaybeApplyPatchVerified::NotApplyPatch => None,
+        };
 
     let command_for_display = if let Some(exec) = apply_patch_exec.as_ref() {
         vec!["apply_patch".to_string(), exec.action.patch.clone()]
@@ -118,12 +125,35 @@ pub(crate) async fn handle_container_exec_with_params(
              }| ApplyPatchCommandContext {
                 user_explicitly_approved_this_action: *user_explicitly_approved_this_action,
                 changes: convert_apply_patch_to_protocol(action),
+                affected: action.affected_paths(),
+                cwd: action.cwd.clone(),
             },
         ),
         tool_name: tool_name.to_string(),
         otel_event_manager,
     };
 
+    // Files whose contents the model knows once the command succeeds.
+    let seen_paths: Vec<PathBuf> = apply_patch_exec
+        .as_ref()
+        .map(|exec| {
+            exec.action
+                .changes()
+                .iter()
+                .filter_map(|(path, change)| match change {
+                    ApplyPatchFileChange::Add { .. } => Some(path.clone()),
+                    ApplyPatchFileChange::Update { move_path, .. } => {
+                        Some(move_path.clone().unwrap_or_else(|| path.clone()))
+                    }
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let is_apply_patch = apply_patch_exec.is_some();
+    let command = params.command.clone();
+    let cwd = params.cwd.clone();
+
     let mode = match apply_patch_exec {
         Some(exec) => ExecutionMode::ApplyPatch(exec),
         None => ExecutionMode::Shell,
@@ -161,6 +191,13 @@ pub(crate) async fn handle_container_exec_with_params(
             let ExecToolCallOutput { exit_code, .. } = &output;
             let content = format_exec_output_apply_patch(&output);
             if *exit_code == 0 {
+                if is_apply_patch {
+                    for path in &seen_paths {
+                        sess.record_read_version(path).await;
+                    }
+                } else {
+                    sess.record_read_versions_for_command(&command, &cwd).await;
+                }
                 Ok(content)
             } else {
                 Err(FunctionCallError::RespondToModel(content))
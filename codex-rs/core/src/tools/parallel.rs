@@ -3,6 +3,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio_util::either::Either;
 use tokio_util::task::AbortOnDropHandle;
+use tracing::Instrument;
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
@@ -54,17 +55,20 @@ impl ToolCallRuntime {
         let lock = Arc::clone(&self.parallel_execution);
 
         let handle: AbortOnDropHandle<Result<ResponseInputItem, FunctionCallError>> =
-            AbortOnDropHandle::new(tokio::spawn(async move {
-                let _guard = if supports_parallel {
-                    Either::Left(lock.read().await)
-                } else {
-                    Either::Right(lock.write().await)
-                };
+            AbortOnDropHandle::new(tokio::spawn(
+                async move {
+                    let _guard = if supports_parallel {
+                        Either::Left(lock.read().await)
+                    } else {
+                        Either::Right(lock.write().await)
+                    };
 
-                router
-                    .dispatch_tool_call(session, turn, tracker, sub_id, call)
-                    .await
-            }));
+                    router
+                        .dispatch_tool_call(session, turn, tracker, sub_id, call)
+                        .await
+                }
+                .instrument(tracing::Span::current()),
+            ));
 
         async move {
             match handle.await {
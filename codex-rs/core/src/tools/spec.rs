@@ -22,6 +22,10 @@ pub enum ConfigShellToolType {
     Streamable,
 }
 
+/// Tools a model family can opt into through `experimental_supported_tools`.
+pub(crate) const EXPERIMENTAL_SUPPORTED_TOOLS: &[&str] =
+    &["grep_files", "list_dir", "read_file", "test_sync_tool"];
+
 #[derive(Debug, Clone)]
 pub(crate) struct ToolsConfig {
     pub shell_type: ConfigShellToolType,
@@ -295,7 +295,8 @@ impl TurnDiffTracker {
         };
 
         // Fast path: identical bytes or both missing.
-        if left_bytes == right_bytes.as_deref() {
+        let same_bytes = left_bytes == right_bytes.as_deref();
+        if same_bytes && (right_bytes.is_none() || baseline_mode == current_mode) {
             return aggregated;
         }
 
@@ -311,6 +312,10 @@ impl TurnDiffTracker {
         } else if baseline_mode != current_mode {
             aggregated.push_str(&format!("old mode {baseline_mode}\n"));
             aggregated.push_str(&format!("new mode {current_mode}\n"));
+            if same_bytes {
+                // Pure mode change: git emits no index line or hunks.
+                return aggregated;
+            }
         }
 
         let left_text = left_bytes.and_then(|b| std::str::from_utf8(b).ok());
@@ -515,6 +520,7 @@ mod tests {
             file.clone(),
             FileChange::Add {
                 content: "foo\n".to_string(),
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&add_changes);
@@ -545,6 +551,7 @@ index {ZERO_OID}..{right_oid}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: None,
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&update_changes);
@@ -620,6 +627,7 @@ index {left_oid}..{ZERO_OID}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: Some(dest.clone()),
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&mv_changes);
@@ -660,6 +668,7 @@ index {left_oid}..{right_oid}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: Some(dest.clone()),
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&mv_changes);
@@ -682,6 +691,7 @@ index {left_oid}..{right_oid}
             FileChange::Update {
                 unified_diff: "".into(),
                 move_path: Some(dest.clone()),
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&mv);
@@ -722,6 +732,7 @@ index {ZERO_OID}..{right_oid}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: None,
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&update_a);
@@ -802,6 +813,7 @@ index {left_oid_b}..{ZERO_OID}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: None,
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&update_changes);
@@ -826,6 +838,38 @@ Binary files differ
         assert_eq!(diff, expected);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn mode_only_update_emits_mode_header() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("run.sh");
+        fs::write(&file, "echo hi\n").unwrap();
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let mut acc = TurnDiffTracker::new();
+        let update_changes = HashMap::from([(
+            file.clone(),
+            FileChange::Update {
+                unified_diff: "".to_owned(),
+                move_path: None,
+                mode: Some(0o755),
+            },
+        )]);
+        acc.on_patch_begin(&update_changes);
+
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let diff = acc.get_unified_diff().unwrap().unwrap();
+        let diff = normalize_diff_for_test(&diff, dir.path());
+        let expected = r#"diff --git a/<TMP>/run.sh b/<TMP>/run.sh
+old mode 100644
+new mode 100755
+"#;
+        assert_eq!(diff, expected);
+    }
+
     #[test]
     fn filenames_with_spaces_add_and_update() {
         let mut acc = TurnDiffTracker::new();
@@ -838,6 +882,7 @@ Binary files differ
             file.clone(),
             FileChange::Add {
                 content: "foo\n".to_string(),
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&add_changes);
@@ -868,6 +913,7 @@ index {ZERO_OID}..{right_oid}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: None,
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&update_changes);
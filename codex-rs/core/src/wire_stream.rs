@@ -0,0 +1,315 @@
+//! Plumbing shared by the native wire adapters in `anthropic_messages.rs` and
+//! `gemini.rs`: sending the streaming request with retries, fixture
+//! record/replay, and the helpers both use to translate a [`Prompt`].
+
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::time::Duration;
+
+use bytes::Bytes;
+use codex_otel::otel_event_manager::OtelEventManager;
+use codex_protocol::config_types::ReasoningEffort as ReasoningEffortConfig;
+use futures::Stream;
+use futures::TryStreamExt;
+use reqwest::StatusCode;
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tracing::Instrument;
+use tracing::debug;
+
+use crate::ModelProviderInfo;
+use crate::client_common::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::client_common::ResponseStream;
+use crate::client_common::tools::ToolSpec;
+use crate::error::CodexErr;
+use crate::error::ConnectionFailedError;
+use crate::error::ResponseStreamFailed;
+use crate::error::Result;
+use crate::error::RetryLimitReachedError;
+use crate::error::UnexpectedResponseError;
+use crate::model_family::ModelFamily;
+use crate::model_fixtures::Fixture;
+use crate::openai_tools::create_tools_json_for_responses_api;
+use crate::rate_limit_governor::RateLimitPacer;
+use crate::util::backoff;
+
+/// Raw SSE body handed to an adapter's event processor.
+pub(crate) type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>;
+
+/// Smallest thinking budget the native APIs accept.
+const MIN_THINKING_BUDGET: u64 = 1_024;
+
+/// What to ask the model for.
+pub(crate) struct NativeRequest<'a> {
+    pub(crate) prompt: &'a Prompt,
+    pub(crate) model_family: &'a ModelFamily,
+    pub(crate) effort: Option<ReasoningEffortConfig>,
+    pub(crate) max_output_tokens: u64,
+}
+
+/// Where and how to send it.
+pub(crate) struct WireTarget<'a> {
+    pub(crate) client: &'a reqwest::Client,
+    pub(crate) provider: &'a ModelProviderInfo,
+    pub(crate) otel_event_manager: &'a OtelEventManager,
+    pub(crate) cwd: &'a Path,
+    pub(crate) model: &'a str,
+    pub(crate) pacer: RateLimitPacer,
+}
+
+/// Posts `payload` and spawns `process` on the SSE body. Rate limits, server
+/// errors and connection failures are retried up to the provider's
+/// `request_max_retries`, and every attempt is paced by the shared
+/// rate-limit governor.
+pub(crate) async fn stream_request<P, Fut>(
+    target: WireTarget<'_>,
+    payload: Value,
+    process: P,
+) -> Result<ResponseStream>
+where
+    P: FnOnce(ByteStream, mpsc::Sender<Result<ResponseEvent>>, Duration, OtelEventManager) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let WireTarget {
+        client,
+        provider,
+        otel_event_manager,
+        cwd,
+        model,
+        pacer,
+    } = target;
+
+    debug!(
+        "POST to {}: {}",
+        provider.get_full_url(&None, model),
+        serde_json::to_string_pretty(&payload).unwrap_or_default()
+    );
+
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent>>(1600);
+    let fixture = provider
+        .fixtures
+        .as_ref()
+        .map(|fixtures| Fixture::for_request(fixtures, &payload, cwd));
+    if let Some(fixture) = fixture.as_ref().filter(|fixture| fixture.is_replay()) {
+        let stream: ByteStream = Box::pin(fixture.replay().await?);
+        tokio::spawn(process(
+            stream,
+            tx_event,
+            provider.stream_idle_timeout(),
+            otel_event_manager.clone(),
+        ));
+        return Ok(ResponseStream { rx_event });
+    }
+
+    let max_retries = provider.request_max_retries();
+    let mut attempt = 0;
+    loop {
+        pacer.acquire().await;
+        let req_builder = provider
+            .create_request_builder(client, &None, model)
+            .await?;
+
+        let request_span = otel_event_manager.model_request_span(attempt);
+        let res = otel_event_manager
+            .log_request(attempt, || {
+                req_builder
+                    .header(reqwest::header::ACCEPT, "text/event-stream")
+                    .json(&payload)
+                    .send()
+            })
+            .instrument(request_span.clone())
+            .await;
+
+        match res {
+            Ok(resp) if resp.status().is_success() => {
+                pacer.observe_headers(resp.headers());
+                let stream = resp.bytes_stream().map_err(|e| {
+                    CodexErr::ResponseStreamFailed(ResponseStreamFailed {
+                        source: e,
+                        request_id: None,
+                    })
+                });
+                let stream: ByteStream = match &fixture {
+                    Some(fixture) => Box::pin(fixture.record(stream)),
+                    None => Box::pin(stream),
+                };
+                tokio::spawn(
+                    process(
+                        stream,
+                        tx_event,
+                        provider.stream_idle_timeout(),
+                        otel_event_manager.clone(),
+                    )
+                    .instrument(request_span),
+                );
+                return Ok(ResponseStream { rx_event });
+            }
+            Ok(res) => {
+                let status = res.status();
+                if !(status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()) {
+                    let body = (res.text().await).unwrap_or_default();
+                    return Err(CodexErr::UnexpectedStatus(UnexpectedResponseError {
+                        status,
+                        body,
+                        request_id: None,
+                    }));
+                }
+
+                if attempt >= max_retries {
+                    return Err(CodexErr::RetryLimit(RetryLimitReachedError {
+                        status,
+                        request_id: None,
+                    }));
+                }
+
+                let retry_after_secs = res
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|s| s.parse::<u64>().ok());
+                let delay = retry_after_secs
+                    .map(Duration::from_secs)
+                    .unwrap_or_else(|| backoff(attempt + 1));
+                if status == StatusCode::TOO_MANY_REQUESTS {
+                    pacer.observe_headers(res.headers());
+                    pacer.block_for(delay);
+                }
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => {
+                if attempt >= max_retries {
+                    return Err(CodexErr::ConnectionFailed(ConnectionFailedError {
+                        source: e,
+                    }));
+                }
+                tokio::time::sleep(backoff(attempt + 1)).await;
+            }
+        }
+        attempt += 1;
+    }
+}
+
+/// Messages under construction. A block with the same role as the previous
+/// message is appended to it, so parallel tool calls and their results end up
+/// in a single turn as the native APIs expect.
+pub(crate) struct MessageList {
+    /// Name of the field holding a message's blocks.
+    key: &'static str,
+    messages: Vec<Value>,
+}
+
+impl MessageList {
+    pub(crate) fn new(key: &'static str) -> Self {
+        Self {
+            key,
+            messages: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, role: &str, block: Value) {
+        if let Some(last) = self.messages.last_mut()
+            && last["role"] == role
+            && let Some(blocks) = last[self.key].as_array_mut()
+        {
+            blocks.push(block);
+            return;
+        }
+        let mut message = serde_json::Map::new();
+        message.insert("role".to_string(), Value::String(role.to_string()));
+        message.insert(self.key.to_string(), Value::Array(vec![block]));
+        self.messages.push(Value::Object(message));
+    }
+
+    pub(crate) fn into_inner(self) -> Vec<Value> {
+        self.messages
+    }
+}
+
+/// The function tools of `tools` in Responses API shape (`name`,
+/// `description`, `parameters`). Other tool kinds have no native equivalent
+/// and are skipped.
+pub(crate) fn function_tools(tools: &[ToolSpec]) -> Result<Vec<Value>> {
+    Ok(create_tools_json_for_responses_api(tools)?
+        .into_iter()
+        .filter(|tool| tool.get("type").and_then(Value::as_str) == Some("function"))
+        .collect())
+}
+
+/// Parses the JSON arguments of a function call; native APIs take an object.
+pub(crate) fn tool_arguments(arguments: &str) -> Value {
+    match serde_json::from_str::<Value>(arguments) {
+        Ok(value @ Value::Object(_)) => value,
+        _ => Value::Object(serde_json::Map::new()),
+    }
+}
+
+/// Splits a `data:<mime>;base64,<data>` URL into its media type and payload.
+pub(crate) fn parse_data_url(url: &str) -> Option<(&str, &str)> {
+    let rest = url.strip_prefix("data:")?;
+    let (media_type, data) = rest.split_once(";base64,")?;
+    Some((media_type, data))
+}
+
+/// Thinking budget for `effort`, or `None` when thinking should stay off:
+/// the model does not reason, the user asked for minimal effort, or the
+/// output limit leaves no room for a budget.
+pub(crate) fn thinking_budget(request: &NativeRequest<'_>) -> Option<u64> {
+    if !request.model_family.supports_reasoning_summaries {
+        return None;
+    }
+    let budget = match request.effort.unwrap_or_default() {
+        ReasoningEffortConfig::Minimal => return None,
+        ReasoningEffortConfig::Low => 2_048,
+        ReasoningEffortConfig::Medium => 8_192,
+        ReasoningEffortConfig::High => 24_576,
+    };
+    // The budget has to leave room for the visible answer.
+    let budget = budget.min(request.max_output_tokens / 2);
+    (budget >= MIN_THINKING_BUDGET).then_some(budget)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_family::find_family_for_model;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn thinking_budget_follows_effort_and_output_limit() {
+        let prompt = Prompt::default();
+        let Some(model_family) = find_family_for_model("claude-sonnet-4-5") else {
+            panic!("claude-sonnet-4-5 should have a model family");
+        };
+        let budget = |effort, max_output_tokens| {
+            thinking_budget(&NativeRequest {
+                prompt: &prompt,
+                model_family: &model_family,
+                effort,
+                max_output_tokens,
+            })
+        };
+
+        assert_eq!(budget(None, 64_000), Some(8_192));
+        assert_eq!(
+            budget(Some(ReasoningEffortConfig::High), 64_000),
+            Some(24_576)
+        );
+        assert_eq!(
+            budget(Some(ReasoningEffortConfig::High), 8_192),
+            Some(4_096)
+        );
+        assert_eq!(budget(Some(ReasoningEffortConfig::Minimal), 64_000), None);
+        assert_eq!(budget(Some(ReasoningEffortConfig::Low), 1_024), None);
+    }
+
+    #[test]
+    fn data_urls_are_split() {
+        assert_eq!(
+            parse_data_url("data:image/png;base64,AAAA"),
+            Some(("image/png", "AAAA"))
+        );
+        assert_eq!(parse_data_url("https://example.com/cat.png"), None);
+    }
+}
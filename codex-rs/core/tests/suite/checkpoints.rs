@@ -0,0 +1,165 @@
+#![allow(clippy::expect_used)]
+#![cfg(not(target_os = "windows"))]
+
+use std::path::Path;
+use std::process::Command;
+
+use codex_core::features::Feature;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::InputItem;
+use codex_core::protocol::Op;
+use core_test_support::responses;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use wiremock::matchers::any;
+
+fn git(repo: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .current_dir(repo)
+        .args(args)
+        .status()
+        .expect("git command");
+    assert!(status.success(), "git command failed: {args:?}");
+}
+
+fn init_repo(repo: &Path) {
+    git(repo, &["init", "--initial-branch=main"]);
+    git(repo, &["config", "core.autocrlf", "false"]);
+    std::fs::write(repo.join("tracked.txt"), "v1\n").expect("write tracked file");
+    git(repo, &["add", "tracked.txt"]);
+    git(
+        repo,
+        &[
+            "-c",
+            "user.name=Tester",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "init",
+        ],
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn restore_checkpoint_reverts_workspace() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let TestCodex { codex, cwd, .. } = test_codex().build(&server).await?;
+    init_repo(cwd.path());
+
+    std::fs::write(cwd.path().join("tracked.txt"), "v2\n")?;
+    codex.submit(Op::Checkpoint).await?;
+    let EventMsg::CheckpointCreated(created) =
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::CheckpointCreated(_))).await
+    else {
+        unreachable!("wait_for_event returned a different event");
+    };
+
+    std::fs::write(cwd.path().join("tracked.txt"), "v3\n")?;
+    std::fs::write(cwd.path().join("untracked.txt"), "new\n")?;
+
+    codex.submit(Op::ListCheckpoints).await?;
+    let EventMsg::ListCheckpointsResponse(listed) = wait_for_event(&codex, |ev| {
+        matches!(ev, EventMsg::ListCheckpointsResponse(_))
+    })
+    .await
+    else {
+        unreachable!("wait_for_event returned a different event");
+    };
+    assert_eq!(listed.checkpoints, vec![created.checkpoint.clone()]);
+
+    codex
+        .submit(Op::RestoreCheckpoint {
+            checkpoint_id: created.checkpoint.id.clone(),
+        })
+        .await?;
+    let EventMsg::CheckpointRestored(restored) =
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::CheckpointRestored(_))).await
+    else {
+        unreachable!("wait_for_event returned a different event");
+    };
+
+    assert_eq!(restored.checkpoint, created.checkpoint);
+    assert_eq!(
+        std::fs::read_to_string(cwd.path().join("tracked.txt"))?,
+        "v2\n"
+    );
+
+    // The restored checkpoint is consumed, like an entry on an undo stack.
+    codex.submit(Op::ListCheckpoints).await?;
+    let EventMsg::ListCheckpointsResponse(listed) = wait_for_event(&codex, |ev| {
+        matches!(ev, EventMsg::ListCheckpointsResponse(_))
+    })
+    .await
+    else {
+        unreachable!("wait_for_event returned a different event");
+    };
+    assert_eq!(listed.checkpoints, Vec::new());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn restore_unknown_checkpoint_reports_error() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let TestCodex { codex, .. } = test_codex().build(&server).await?;
+
+    codex
+        .submit(Op::RestoreCheckpoint {
+            checkpoint_id: "does-not-exist".to_string(),
+        })
+        .await?;
+    let EventMsg::Error(error) =
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::Error(_))).await
+    else {
+        unreachable!("wait_for_event returned a different event");
+    };
+    assert_eq!(error.message, "Unknown checkpoint: does-not-exist");
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn checkpoints_feature_captures_before_each_turn() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    responses::mount_sse_once_match(
+        &server,
+        any(),
+        sse(vec![ev_assistant_message("m1", "Done"), ev_completed("r1")]),
+    )
+    .await;
+
+    let TestCodex { codex, cwd, .. } = test_codex()
+        .with_config(|config| config.features.enable(Feature::Checkpoints))
+        .build(&server)
+        .await?;
+    init_repo(cwd.path());
+
+    let sub_id = codex
+        .submit(Op::UserInput {
+            items: vec![InputItem::Text {
+                text: "hello".into(),
+            }],
+        })
+        .await?;
+
+    let EventMsg::CheckpointCreated(created) =
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::CheckpointCreated(_))).await
+    else {
+        unreachable!("wait_for_event returned a different event");
+    };
+    assert_eq!(created.checkpoint.turn_id, sub_id);
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+    Ok(())
+}
@@ -2,6 +2,7 @@
 
 #[cfg(not(target_os = "windows"))]
 mod abort_tasks;
+mod checkpoints;
 mod cli_stream;
 mod client;
 mod compact;
@@ -14,10 +15,13 @@ mod grep_files;
 mod json_result;
 mod list_dir;
 mod live_cli;
+mod model_fixtures;
 mod model_overrides;
 mod model_tools;
+mod native_wire_api;
 mod otel;
 mod prompt_caching;
+mod rate_limit_pacing;
 mod read_file;
 mod review;
 mod rmcp_client;
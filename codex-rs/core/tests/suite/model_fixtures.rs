@@ -0,0 +1,113 @@
+//! Verifies that a provider in `record` mode saves its exchanges and that a
+//! provider in `replay` mode serves them back without contacting the server.
+
+use std::path::Path;
+use std::time::Duration;
+
+use codex_core::FixtureMode;
+use codex_core::ModelFixtures;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::InputItem;
+use codex_core::protocol::Op;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use wiremock::MockServer;
+
+async fn run_turn(
+    server: &MockServer,
+    fixtures_dir: &Path,
+    mode: FixtureMode,
+) -> anyhow::Result<Option<String>> {
+    let fixtures = ModelFixtures {
+        mode,
+        dir: fixtures_dir.to_path_buf(),
+    };
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(move |config| {
+            config.model_provider.fixtures = Some(fixtures);
+        })
+        .build(server)
+        .await?;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![InputItem::Text {
+                text: "hello".into(),
+            }],
+        })
+        .await?;
+
+    match wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await {
+        EventMsg::TaskComplete(complete) => Ok(complete.last_agent_message),
+        other => anyhow::bail!("unexpected event: {other:?}"),
+    }
+}
+
+fn recorded_fixtures(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    path.extension()
+                        .map(|ext| ext.to_string_lossy().into_owned())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn replay_serves_recorded_exchange_without_network() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let fixtures_dir = TempDir::new()?;
+
+    let server = start_mock_server().await;
+    mount_sse_once(
+        &server,
+        sse(vec![
+            ev_assistant_message("msg-1", "recorded answer"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    let recorded = run_turn(&server, fixtures_dir.path(), FixtureMode::Record).await?;
+    assert_eq!(recorded.as_deref(), Some("recorded answer"));
+
+    // The exchange is written once the response stream has been consumed.
+    let mut fixtures = Vec::new();
+    for _ in 0..100 {
+        fixtures = recorded_fixtures(fixtures_dir.path());
+        if fixtures.len() == 2 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(fixtures, vec!["json".to_string(), "sse".to_string()]);
+
+    let offline_server = start_mock_server().await;
+    let replayed = run_turn(&offline_server, fixtures_dir.path(), FixtureMode::Replay).await?;
+    assert_eq!(replayed, recorded);
+    assert_eq!(
+        offline_server
+            .received_requests()
+            .await
+            .map(|requests| requests.len()),
+        Some(0)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,342 @@
+//! Streams turns through the native Anthropic Messages and Gemini wire APIs
+//! against mock SSE servers.
+
+use std::sync::Arc;
+
+use codex_app_server_protocol::AuthMode;
+use codex_core::ContentItem;
+use codex_core::ModelClient;
+use codex_core::ModelProviderInfo;
+use codex_core::Prompt;
+use codex_core::ResponseEvent;
+use codex_core::ResponseItem;
+use codex_core::WireApi;
+use codex_core::model_family::find_family_for_model;
+use codex_core::protocol::TokenUsage;
+use codex_otel::otel_event_manager::OtelEventManager;
+use codex_protocol::ConversationId;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use core_test_support::load_default_config_for_test;
+use core_test_support::responses::sse_response;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use futures::StreamExt;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use tempfile::TempDir;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+async fn run_turn(
+    server: &MockServer,
+    wire_api: WireApi,
+    base_url: String,
+    model: &str,
+) -> anyhow::Result<Vec<ResponseEvent>> {
+    let provider = ModelProviderInfo {
+        name: "native".into(),
+        base_url: Some(base_url),
+        env_key: None,
+        env_key_instructions: None,
+        wire_api,
+        query_params: None,
+        http_headers: None,
+        env_http_headers: None,
+        request_max_retries: Some(0),
+        stream_max_retries: Some(0),
+        stream_idle_timeout_ms: Some(5_000),
+        requires_openai_auth: false,
+        fixtures: None,
+    };
+
+    let codex_home = TempDir::new()?;
+    let mut config = load_default_config_for_test(&codex_home);
+    config.model = model.to_string();
+    config.model_family = find_family_for_model(model)
+        .ok_or_else(|| anyhow::anyhow!("no model family for {model}"))?;
+    config.model_max_output_tokens = Some(32_000);
+    config.model_provider_id = provider.name.clone();
+    config.model_provider = provider.clone();
+    let effort = config.model_reasoning_effort;
+    let summary = config.model_reasoning_summary;
+    let config = Arc::new(config);
+
+    let conversation_id = ConversationId::new();
+    let otel_event_manager = OtelEventManager::new(
+        conversation_id,
+        config.model.as_str(),
+        config.model_family.slug.as_str(),
+        None,
+        Some("test@test.com".to_string()),
+        Some(AuthMode::ApiKey),
+        false,
+        "test".to_string(),
+    );
+    let client = ModelClient::new(
+        Arc::clone(&config),
+        None,
+        otel_event_manager,
+        provider,
+        effort,
+        summary,
+        conversation_id,
+    );
+
+    let mut prompt = Prompt::default();
+    prompt.input = vec![ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText {
+            text: "list both dirs".to_string(),
+        }],
+    }];
+
+    let mut stream = client.stream(&prompt).await?;
+    let mut events = Vec::new();
+    while let Some(event) = stream.next().await {
+        events.push(event?);
+    }
+    assert_eq!(
+        server
+            .received_requests()
+            .await
+            .map(|requests| requests.len()),
+        Some(1)
+    );
+    Ok(events)
+}
+
+async fn request_body(server: &MockServer) -> anyhow::Result<Value> {
+    let requests = server.received_requests().await.unwrap_or_default();
+    let request = requests
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("no request received"))?;
+    Ok(request.body_json()?)
+}
+
+/// Messages API events always carry a `data` line, even `message_stop`.
+fn anthropic_sse(events: Vec<Value>) -> String {
+    events
+        .iter()
+        .map(|event| {
+            format!(
+                "event: {}\ndata: {event}\n\n",
+                event["type"].as_str().unwrap_or_default()
+            )
+        })
+        .collect()
+}
+
+fn done_items(events: &[ResponseEvent]) -> Vec<ResponseItem> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            ResponseEvent::OutputItemDone(item) => Some(item.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn call_ids(items: &[ResponseItem]) -> Vec<(String, String)> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            ResponseItem::FunctionCall {
+                call_id, arguments, ..
+            } => Some((call_id.clone(), arguments.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `(input, cached input, output, reasoning output, total)` tokens reported
+/// with `Completed`.
+fn completed_usage(events: &[ResponseEvent]) -> Option<(u64, u64, u64, u64, u64)> {
+    events.iter().find_map(|event| match event {
+        ResponseEvent::Completed {
+            token_usage: Some(usage),
+            ..
+        } => Some(usage_counts(usage)),
+        _ => None,
+    })
+}
+
+fn usage_counts(usage: &TokenUsage) -> (u64, u64, u64, u64, u64) {
+    (
+        usage.input_tokens,
+        usage.cached_input_tokens,
+        usage.output_tokens,
+        usage.reasoning_output_tokens,
+        usage.total_tokens,
+    )
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn anthropic_streams_thinking_and_parallel_tool_use() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let body = anthropic_sse(vec![
+        json!({"type": "message_start", "message": {
+            "id": "msg_1",
+            "usage": {"input_tokens": 10, "cache_read_input_tokens": 90, "output_tokens": 1},
+        }}),
+        json!({"type": "content_block_start", "index": 0, "content_block": {"type": "thinking", "thinking": ""}}),
+        json!({"type": "content_block_delta", "index": 0, "delta": {"type": "thinking_delta", "thinking": "Two listings."}}),
+        json!({"type": "content_block_delta", "index": 0, "delta": {"type": "signature_delta", "signature": "sig-1"}}),
+        json!({"type": "content_block_stop", "index": 0}),
+        json!({"type": "content_block_start", "index": 1, "content_block": {"type": "tool_use", "id": "toolu_a", "name": "shell", "input": {}}}),
+        json!({"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": "{\"command\":"}}),
+        json!({"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": "[\"ls\",\"a\"]}"}}),
+        json!({"type": "content_block_stop", "index": 1}),
+        json!({"type": "content_block_start", "index": 2, "content_block": {"type": "tool_use", "id": "toolu_b", "name": "shell", "input": {}}}),
+        json!({"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "{\"command\":[\"ls\",\"b\"]}"}}),
+        json!({"type": "content_block_stop", "index": 2}),
+        json!({"type": "message_delta", "delta": {"stop_reason": "tool_use"}, "usage": {"output_tokens": 42}}),
+        json!({"type": "message_stop"}),
+    ]);
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(sse_response(body))
+        .mount(&server)
+        .await;
+
+    let events = run_turn(
+        &server,
+        WireApi::Anthropic,
+        format!("{}/v1", server.uri()),
+        "claude-sonnet-4-5",
+    )
+    .await?;
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert_eq!(
+        requests
+            .first()
+            .and_then(|request| request.headers.get("anthropic-version"))
+            .and_then(|value| value.to_str().ok()),
+        Some("2023-06-01")
+    );
+    let body = request_body(&server).await?;
+    assert_eq!(body["model"], json!("claude-sonnet-4-5"));
+    assert_eq!(body["max_tokens"], json!(32_000));
+    assert_eq!(body["stream"], json!(true));
+    assert!(body["thinking"]["budget_tokens"].is_u64());
+    assert_eq!(
+        body["messages"][0]["content"][0]["cache_control"],
+        json!({"type": "ephemeral"})
+    );
+
+    assert!(events.iter().any(|event| matches!(
+        event,
+        ResponseEvent::ReasoningSummaryDelta(delta) if delta == "Two listings."
+    )));
+    let items = done_items(&events);
+    assert!(matches!(
+        &items[0],
+        ResponseItem::Reasoning { summary, encrypted_content: Some(signature), .. }
+            if signature == "sig-1"
+                && summary == &vec![ReasoningItemReasoningSummary::SummaryText {
+                    text: "Two listings.".to_string(),
+                }]
+    ));
+    assert_eq!(
+        call_ids(&items),
+        vec![
+            (
+                "toolu_a".to_string(),
+                r#"{"command":["ls","a"]}"#.to_string()
+            ),
+            (
+                "toolu_b".to_string(),
+                r#"{"command":["ls","b"]}"#.to_string()
+            ),
+        ]
+    );
+    assert_eq!(completed_usage(&events), Some((100, 90, 42, 0, 142)));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn gemini_streams_thoughts_and_parallel_function_calls() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let chunks = [
+        json!({"responseId": "resp-1", "candidates": [{"content": {"role": "model", "parts": [
+            {"text": "Two listings.", "thought": true},
+        ]}}]}),
+        json!({"candidates": [{"content": {"role": "model", "parts": [
+            {"functionCall": {"name": "shell", "args": {"command": ["ls", "a"]}}, "thoughtSignature": "sig-1"},
+            {"functionCall": {"name": "shell", "args": {"command": ["ls", "b"]}}},
+        ]}, "finishReason": "STOP"}], "usageMetadata": {
+            "promptTokenCount": 100,
+            "cachedContentTokenCount": 60,
+            "candidatesTokenCount": 20,
+            "thoughtsTokenCount": 30,
+            "totalTokenCount": 150,
+        }}),
+    ];
+    let body: String = chunks
+        .iter()
+        .map(|chunk| format!("data: {chunk}\r\n\r\n"))
+        .collect();
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-2.5-pro:streamGenerateContent"))
+        .respond_with(sse_response(body))
+        .mount(&server)
+        .await;
+
+    let events = run_turn(
+        &server,
+        WireApi::Gemini,
+        format!("{}/v1beta", server.uri()),
+        "gemini-2.5-pro",
+    )
+    .await?;
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert_eq!(
+        requests.first().and_then(|request| request.url.query()),
+        Some("alt=sse")
+    );
+    let body = request_body(&server).await?;
+    assert_eq!(
+        body["contents"][0]["parts"],
+        json!([{"text": "list both dirs"}])
+    );
+    assert_eq!(
+        body["generationConfig"]["thinkingConfig"]["includeThoughts"],
+        json!(true)
+    );
+
+    let items = done_items(&events);
+    assert!(matches!(
+        &items[0],
+        ResponseItem::Reasoning { encrypted_content: None, summary, .. } if !summary.is_empty()
+    ));
+    assert!(matches!(
+        &items[1],
+        ResponseItem::Reasoning { encrypted_content: Some(signature), .. } if signature == "sig-1"
+    ));
+    let calls = call_ids(&items);
+    assert_eq!(calls.len(), 2);
+    assert_ne!(calls[0].0, calls[1].0);
+    assert_eq!(
+        calls
+            .iter()
+            .map(|(_, arguments)| serde_json::from_str::<Value>(arguments).ok())
+            .collect::<Vec<_>>(),
+        vec![
+            Some(json!({"command": ["ls", "a"]})),
+            Some(json!({"command": ["ls", "b"]})),
+        ]
+    );
+    assert_eq!(completed_usage(&events), Some((100, 60, 50, 30, 150)));
+
+    Ok(())
+}
@@ -87,6 +87,50 @@ async fn responses_api_emits_api_request_event() {
     });
 }
 
+#[tokio::test]
+#[traced_test]
+async fn responses_api_records_request_metric_inside_turn_span() {
+    let server = start_mock_server().await;
+
+    mount_sse_once(&server, sse(vec![ev_completed("done")])).await;
+
+    let TestCodex { codex, .. } = test_codex().build(&server).await.unwrap();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![InputItem::Text {
+                text: "hello".into(),
+            }],
+        })
+        .await
+        .unwrap();
+
+    wait_for_event_with_timeout(
+        &codex,
+        |ev| matches!(ev, EventMsg::TaskComplete(_)),
+        Duration::from_secs(5),
+    )
+    .await;
+
+    retry_logs_assert!(|lines: &[&str]| {
+        let line = lines
+            .iter()
+            .find(|line| line.contains("histogram.codex.api_request.duration_ms"))
+            .ok_or_else(|| "missing api_request duration metric".to_string())?;
+
+        for span in ["codex.session{", "codex.turn{", "codex.model_request{"] {
+            if !line.contains(span) {
+                return Err(format!("metric not recorded inside {span}"));
+            }
+        }
+        if !line.contains("monotonic_counter.codex.api_request.retries=0") {
+            return Err("first attempt counted as a retry".to_string());
+        }
+
+        Ok(())
+    });
+}
+
 #[tokio::test]
 #[traced_test]
 async fn process_sse_emits_tracing_for_output_item() {
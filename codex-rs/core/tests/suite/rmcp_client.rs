@@ -15,6 +15,7 @@ use codex_core::features::Feature;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::InputItem;
+use codex_core::protocol::McpServerHealth;
 use codex_core::protocol::Op;
 use codex_core::protocol::SandboxPolicy;
 use codex_protocol::config_types::ReasoningSummary;
@@ -94,6 +95,8 @@ async fn stdio_server_round_trip() -> anyhow::Result<()> {
                     enabled: true,
                     startup_timeout_sec: Some(Duration::from_secs(10)),
                     tool_timeout_sec: None,
+                    tool_policy: Default::default(),
+                    sampling_token_budget: None,
                 },
             );
         })
@@ -230,6 +233,8 @@ async fn stdio_server_propagates_whitelisted_env_vars() -> anyhow::Result<()> {
                     enabled: true,
                     startup_timeout_sec: Some(Duration::from_secs(10)),
                     tool_timeout_sec: None,
+                    tool_policy: Default::default(),
+                    sampling_token_budget: None,
                 },
             );
         })
@@ -309,6 +314,104 @@ async fn stdio_server_propagates_whitelisted_env_vars() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[serial(mcp_test_value)]
+async fn stdio_server_is_restarted_after_it_dies() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+
+    let server_name = "rmcp_restart";
+    let tool_name = format!("{server_name}__echo");
+
+    let rmcp_test_server_bin = CargoBuild::new()
+        .package("codex-rmcp-client")
+        .bin("test_stdio_server")
+        .run()?
+        .path()
+        .to_string_lossy()
+        .into_owned();
+    let pid_dir = tempdir()?;
+    let pid_file = pid_dir.path().join("server.pid");
+    let script = format!(
+        "echo $$ > '{}'; exec '{}'",
+        pid_file.display(),
+        rmcp_test_server_bin
+    );
+
+    let fixture = test_codex()
+        .with_config(move |config| {
+            config.features.enable(Feature::RmcpClient);
+            config.mcp_servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: "sh".to_string(),
+                        args: vec!["-c".to_string(), script],
+                        env: None,
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                    enabled: true,
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    tool_policy: Default::default(),
+                    sampling_token_budget: None,
+                },
+            );
+        })
+        .build(&server)
+        .await?;
+
+    let pid = fs::read_to_string(&pid_file)?.trim().to_string();
+    let status = std::process::Command::new("kill")
+        .args(["-9", &pid])
+        .status()?;
+    assert!(status.success(), "failed to kill MCP server {pid}");
+
+    // The supervisor polls every few seconds and backs off before restarting.
+    let ready_event = wait_for_event_with_timeout(
+        &fixture.codex,
+        |ev| {
+            matches!(
+                ev,
+                EventMsg::McpServerStatus(ev)
+                    if ev.server_name == server_name
+                        && ev.status.health == McpServerHealth::Ready
+            )
+        },
+        Duration::from_secs(20),
+    )
+    .await;
+    let EventMsg::McpServerStatus(ready) = ready_event else {
+        unreachable!("event guard guarantees McpServerStatus");
+    };
+    assert_eq!(ready.status.restarts, 1);
+    let new_pid = fs::read_to_string(&pid_file)?.trim().to_string();
+    assert_ne!(new_pid, pid, "server should run in a new process");
+
+    fixture.codex.submit(Op::ListMcpTools).await?;
+    let list_event = wait_for_event(&fixture.codex, |ev| {
+        matches!(ev, EventMsg::McpListToolsResponse(_))
+    })
+    .await;
+    let EventMsg::McpListToolsResponse(list) = list_event else {
+        unreachable!("event guard guarantees McpListToolsResponse");
+    };
+    assert!(
+        list.tools.contains_key(&tool_name),
+        "tools should be re-listed after the restart: {:?}",
+        list.tools.keys().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        list.server_statuses.get(server_name).map(|s| s.health),
+        Some(McpServerHealth::Ready)
+    );
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn streamable_http_tool_call_round_trip() -> anyhow::Result<()> {
     skip_if_no_network!(Ok(()));
@@ -381,6 +484,8 @@ async fn streamable_http_tool_call_round_trip() -> anyhow::Result<()> {
                     enabled: true,
                     startup_timeout_sec: Some(Duration::from_secs(10)),
                     tool_timeout_sec: None,
+                    tool_policy: Default::default(),
+                    sampling_token_budget: None,
                 },
             );
         })
@@ -564,6 +669,8 @@ async fn streamable_http_with_oauth_round_trip() -> anyhow::Result<()> {
                     enabled: true,
                     startup_timeout_sec: Some(Duration::from_secs(10)),
                     tool_timeout_sec: None,
+                    tool_policy: Default::default(),
+                    sampling_token_budget: None,
                 },
             );
         })
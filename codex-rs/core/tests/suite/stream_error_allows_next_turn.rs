@@ -74,6 +74,7 @@ async fn continue_after_stream_error() {
         stream_max_retries: Some(1),
         stream_idle_timeout_ms: Some(2_000),
         requires_openai_auth: false,
+        fixtures: None,
     };
 
     let TestCodex { codex, .. } = test_codex()
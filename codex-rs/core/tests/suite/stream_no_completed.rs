@@ -82,6 +82,7 @@ async fn retries_on_early_close() {
         stream_max_retries: Some(1),
         stream_idle_timeout_ms: Some(2000),
         requires_openai_auth: false,
+        fixtures: None,
     };
 
     let TestCodex { codex, .. } = test_codex()
@@ -1,6 +1,7 @@
 use clap::Parser;
 use clap::ValueEnum;
 use codex_common::CliConfigOverrides;
+use codex_protocol::protocol::ReviewSeverity;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -99,6 +100,50 @@ pub enum Command {
     /// The original session file is NOT modified.
     #[clap(name = "resume-clone")]
     ResumeClone(ResumeCloneArgs),
+
+    /// Review code changes and report prioritized findings. Exits with code 2
+    /// when a finding at or above `--fail-on` is reported.
+    Review(ReviewArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct ReviewArgs {
+    /// Review the current branch against its merge-base with this branch (PR style).
+    #[arg(long = "base", value_name = "BRANCH", conflicts_with_all = ["commit", "range"])]
+    pub base: Option<String>,
+
+    /// Review the changes introduced by a single commit.
+    #[arg(long = "commit", value_name = "SHA", conflicts_with = "range")]
+    pub commit: Option<String>,
+
+    /// Review the changes between two revisions, given as `BASE..HEAD`.
+    #[arg(long = "range", value_name = "BASE..HEAD")]
+    pub range: Option<String>,
+
+    /// Minimum severity that makes the command exit with code 2.
+    #[arg(long = "fail-on", value_enum, default_value_t = FailOn::High)]
+    pub fail_on: FailOn,
+
+    /// Print events to stdout as JSONL.
+    #[arg(long = "json", default_value_t = false)]
+    pub json: bool,
+
+    /// Additional review instructions. Defaults to reviewing uncommitted
+    /// changes when no target is given.
+    #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
+    pub prompt: Option<String>,
+}
+
+/// Severity threshold for `codex exec review --fail-on`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum FailOn {
+    Critical,
+    High,
+    Medium,
+    Low,
+    /// Never fail because of findings.
+    Never,
 }
 
 #[derive(Parser, Debug)]
@@ -137,3 +182,16 @@ pub enum Color {
     #[default]
     Auto,
 }
+
+impl FailOn {
+    /// The lowest severity that fails the run, or `None` for [`FailOn::Never`].
+    pub fn threshold(self) -> Option<ReviewSeverity> {
+        match self {
+            FailOn::Critical => Some(ReviewSeverity::Critical),
+            FailOn::High => Some(ReviewSeverity::High),
+            FailOn::Medium => Some(ReviewSeverity::Medium),
+            FailOn::Low => Some(ReviewSeverity::Low),
+            FailOn::Never => None,
+        }
+    }
+}
@@ -4,11 +4,16 @@ use codex_core::config::Config;
 use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::BudgetWarningEvent;
+use codex_core::protocol::CheckpointCreatedEvent;
+use codex_core::protocol::CheckpointRestoredEvent;
+use codex_core::protocol::ElicitationRequestEvent;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecCommandBeginEvent;
 use codex_core::protocol::ExecCommandEndEvent;
+use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::McpToolCallBeginEvent;
@@ -22,6 +27,7 @@ use codex_core::protocol::TurnAbortReason;
 use codex_core::protocol::TurnDiffEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
+use codex_core::review_format::format_review_findings_block;
 use codex_protocol::num_format::format_with_separators;
 use owo_colors::OwoColorize;
 use owo_colors::Style;
@@ -167,6 +173,10 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::StreamError(StreamErrorEvent { message }) => {
                 ts_msg!(self, "{}", message.style(self.dimmed));
             }
+            EventMsg::BudgetWarning(BudgetWarningEvent { message }) => {
+                let prefix = "WARNING:".style(self.magenta);
+                ts_msg!(self, "{prefix} {message}");
+            }
             EventMsg::TaskStarted(_) => {
                 // Ignore.
             }
@@ -312,17 +322,36 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 // it's easy to scan in the terminal output.
                 for (path, change) in changes.iter() {
                     match change {
-                        FileChange::Add { content } => {
+                        FileChange::Add { content, mode } => {
                             let header = format!(
-                                "{} {}",
+                                "{} {}{}",
                                 format_file_change(change),
-                                path.to_string_lossy()
+                                path.to_string_lossy(),
+                                format_mode_suffix(*mode)
                             );
                             eprintln!("{}", header.style(self.magenta));
                             for line in content.lines() {
                                 eprintln!("{}", line.style(self.green));
                             }
                         }
+                        FileChange::AddBinary { size, mode } => {
+                            let header = format!(
+                                "{} {} (binary, {size} bytes){}",
+                                format_file_change(change),
+                                path.to_string_lossy(),
+                                format_mode_suffix(*mode)
+                            );
+                            eprintln!("{}", header.style(self.magenta));
+                        }
+                        FileChange::AddSymlink { target } => {
+                            let header = format!(
+                                "{} {} -> {}",
+                                format_file_change(change),
+                                path.to_string_lossy(),
+                                target.to_string_lossy()
+                            );
+                            eprintln!("{}", header.style(self.magenta));
+                        }
                         FileChange::Delete { content } => {
                             let header = format!(
                                 "{} {}",
@@ -337,16 +366,23 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                         FileChange::Update {
                             unified_diff,
                             move_path,
+                            mode,
                         } => {
                             let header = if let Some(dest) = move_path {
                                 format!(
-                                    "{} {} -> {}",
+                                    "{} {} -> {}{}",
                                     format_file_change(change),
                                     path.to_string_lossy(),
-                                    dest.to_string_lossy()
+                                    dest.to_string_lossy(),
+                                    format_mode_suffix(*mode)
                                 )
                             } else {
-                                format!("{} {}", format_file_change(change), path.to_string_lossy())
+                                format!(
+                                    "{} {}{}",
+                                    format_file_change(change),
+                                    path.to_string_lossy(),
+                                    format_mode_suffix(*mode)
+                                )
                             };
                             eprintln!("{}", header.style(self.magenta));
 
@@ -373,7 +409,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 stdout,
                 stderr,
                 success,
-                ..
+                committed,
             }) => {
                 let patch_begin = self.call_id_to_patch.remove(&call_id);
 
@@ -402,6 +438,16 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 for line in output.lines() {
                     eprintln!("{}", line.style(self.dimmed));
                 }
+                // A failed patch is rolled back, so anything committed here
+                // is a file that could not be restored.
+                if !success {
+                    for path in committed {
+                        eprintln!(
+                            "{}",
+                            format!("left modified: {}", path.display()).style(self.red)
+                        );
+                    }
+                }
             }
             EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => {
                 ts_msg!(
@@ -417,6 +463,18 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::ApplyPatchApprovalRequest(_) => {
                 // Should we exit?
             }
+            EventMsg::ElicitationRequest(ElicitationRequestEvent {
+                server_name,
+                message,
+                ..
+            }) => {
+                ts_msg!(
+                    self,
+                    "{} {}",
+                    format!("{server_name} requested input (declined):").style(self.magenta),
+                    message
+                );
+            }
             EventMsg::AgentReasoning(agent_reasoning_event) => {
                 if self.show_agent_reasoning {
                     ts_msg!(
@@ -487,6 +545,12 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::McpListToolsResponse(_) => {
                 // Currently ignored in exec output.
             }
+            EventMsg::McpListChanged(_) => {
+                // Currently ignored in exec output.
+            }
+            EventMsg::McpServerStatus(ev) => {
+                ts_msg!(self, "{}", ev.to_string().style(self.dimmed));
+            }
             EventMsg::ListCustomPromptsResponse(_) => {
                 // Currently ignored in exec output.
             }
@@ -512,8 +576,40 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::ShutdownComplete => return CodexStatus::Shutdown,
             EventMsg::ConversationPath(_) => {}
             EventMsg::UserMessage(_) => {}
-            EventMsg::EnteredReviewMode(_) => {}
-            EventMsg::ExitedReviewMode(_) => {}
+            EventMsg::EnteredReviewMode(review_request) => {
+                ts_msg!(
+                    self,
+                    "{} {}",
+                    "reviewing".style(self.magenta),
+                    review_request.user_facing_hint
+                );
+            }
+            EventMsg::ExitedReviewMode(ExitedReviewModeEvent { review_output }) => {
+                if let Some(output) = review_output {
+                    if output.findings.is_empty() {
+                        ts_msg!(self, "{}", "review found no issues".style(self.green));
+                    } else {
+                        eprintln!("{}", format_review_findings_block(&output.findings, None));
+                    }
+                    if !output.overall_explanation.is_empty() {
+                        eprintln!("\n{}", output.overall_explanation);
+                    }
+                }
+            }
+            EventMsg::CheckpointCreated(CheckpointCreatedEvent { checkpoint }) => {
+                let short_id: String = checkpoint.id.chars().take(8).collect();
+                ts_msg!(self, "{} {}", "checkpoint".style(self.dimmed), short_id);
+            }
+            EventMsg::CheckpointRestored(CheckpointRestoredEvent { checkpoint }) => {
+                let short_id: String = checkpoint.id.chars().take(8).collect();
+                ts_msg!(
+                    self,
+                    "{} {}",
+                    "restored checkpoint".style(self.magenta),
+                    short_id
+                );
+            }
+            EventMsg::ListCheckpointsResponse(_) => {}
             EventMsg::AgentMessageDelta(_) => {}
             EventMsg::AgentReasoningDelta(_) => {}
             EventMsg::AgentReasoningRawContentDelta(_) => {}
@@ -551,7 +647,9 @@ fn escape_command(command: &[String]) -> String {
 
 fn format_file_change(change: &FileChange) -> &'static str {
     match change {
-        FileChange::Add { .. } => "A",
+        FileChange::Add { .. } | FileChange::AddBinary { .. } | FileChange::AddSymlink { .. } => {
+            "A"
+        }
         FileChange::Delete { .. } => "D",
         FileChange::Update {
             move_path: Some(_), ..
@@ -562,6 +660,11 @@ fn format_file_change(change: &FileChange) -> &'static str {
     }
 }
 
+fn format_mode_suffix(mode: Option<u32>) -> String {
+    mode.map(|mode| format!(" (mode {mode:o})"))
+        .unwrap_or_default()
+}
+
 fn format_mcp_invocation(invocation: &McpInvocation) -> String {
     // Build fully-qualified tool name: server.tool
     let fq_tool_name = format!("{}.{}", invocation.server, invocation.tool);
@@ -18,6 +18,9 @@ use crate::exec_events::McpToolCallStatus;
 use crate::exec_events::PatchApplyStatus;
 use crate::exec_events::PatchChangeKind;
 use crate::exec_events::ReasoningItem;
+use crate::exec_events::ReviewFindingItem;
+use crate::exec_events::ReviewItem;
+use crate::exec_events::ReviewSeverity;
 use crate::exec_events::ThreadErrorEvent;
 use crate::exec_events::ThreadEvent;
 use crate::exec_events::ThreadItem;
@@ -37,11 +40,13 @@ use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecCommandBeginEvent;
 use codex_core::protocol::ExecCommandEndEvent;
+use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
+use codex_core::protocol::ReviewSeverity as CoreReviewSeverity;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_core::protocol::TaskStartedEvent;
@@ -110,6 +115,7 @@ impl EventProcessorWithJsonOutput {
             EventMsg::PatchApplyEnd(ev) => self.handle_patch_apply_end(ev),
             EventMsg::WebSearchBegin(_) => Vec::new(),
             EventMsg::WebSearchEnd(ev) => self.handle_web_search_end(ev),
+            EventMsg::ExitedReviewMode(ev) => self.handle_exited_review_mode(ev),
             EventMsg::TokenCount(ev) => {
                 if let Some(info) = &ev.info {
                     self.last_total_token_usage = Some(info.total_token_usage.clone());
@@ -158,6 +164,43 @@ impl EventProcessorWithJsonOutput {
         vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })]
     }
 
+    fn handle_exited_review_mode(&self, ev: &ExitedReviewModeEvent) -> Vec<ThreadEvent> {
+        let Some(output) = &ev.review_output else {
+            return Vec::new();
+        };
+        let findings = output
+            .findings
+            .iter()
+            .map(|finding| ReviewFindingItem {
+                title: finding.title.clone(),
+                body: finding.body.clone(),
+                file: finding
+                    .code_location
+                    .absolute_file_path
+                    .to_string_lossy()
+                    .into_owned(),
+                line: finding.code_location.line_range.start,
+                end_line: finding.code_location.line_range.end,
+                severity: match finding.severity() {
+                    CoreReviewSeverity::Critical => ReviewSeverity::Critical,
+                    CoreReviewSeverity::High => ReviewSeverity::High,
+                    CoreReviewSeverity::Medium => ReviewSeverity::Medium,
+                    CoreReviewSeverity::Low => ReviewSeverity::Low,
+                },
+            })
+            .collect();
+        let item = ThreadItem {
+            id: self.get_next_item_id(),
+            details: ThreadItemDetails::Review(ReviewItem {
+                findings,
+                overall_correctness: output.overall_correctness.clone(),
+                overall_explanation: output.overall_explanation.clone(),
+            }),
+        };
+
+        vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })]
+    }
+
     fn handle_agent_message(&self, payload: &AgentMessageEvent) -> Vec<ThreadEvent> {
         let item = ThreadItem {
             id: self.get_next_item_id(),
@@ -285,7 +328,9 @@ impl EventProcessorWithJsonOutput {
 
     fn map_change_kind(&self, kind: &FileChange) -> PatchChangeKind {
         match kind {
-            FileChange::Add { .. } => PatchChangeKind::Add,
+            FileChange::Add { .. }
+            | FileChange::AddBinary { .. }
+            | FileChange::AddSymlink { .. } => PatchChangeKind::Add,
             FileChange::Delete { .. } => PatchChangeKind::Delete,
             FileChange::Update { .. } => PatchChangeKind::Update,
         }
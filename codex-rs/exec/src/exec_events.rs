@@ -119,6 +119,9 @@ pub enum ThreadItemDetails {
     TodoList(TodoListItem),
     /// Describes a non-fatal error surfaced as an item.
     Error(ErrorItem),
+    /// Findings reported by `codex exec review`. Emitted as a completed item
+    /// once the review finishes.
+    Review(ReviewItem),
 }
 
 /// Response from the agent.
@@ -215,6 +218,35 @@ pub struct ErrorItem {
     pub message: String,
 }
 
+/// The outcome of a code review.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
+pub struct ReviewItem {
+    pub findings: Vec<ReviewFindingItem>,
+    pub overall_correctness: String,
+    pub overall_explanation: String,
+}
+
+/// A single review finding, located by file and line.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
+pub struct ReviewFindingItem {
+    pub title: String,
+    pub body: String,
+    pub file: String,
+    pub line: u32,
+    pub end_line: u32,
+    pub severity: ReviewSeverity,
+}
+
+/// Severity of a review finding, derived from its `[P0]`..`[P3]` priority.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewSeverity {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
 /// An item in agent's to-do list.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
 pub struct TodoItem {
@@ -22,8 +22,13 @@ use codex_core::git_info::get_git_repo_root;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
+use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::InputItem;
 use codex_core::protocol::Op;
+use codex_core::protocol::ReviewFinding;
+use codex_core::protocol::ReviewRequest;
+use codex_core::protocol::ReviewSeverity;
+use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SessionSource;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_ollama::DEFAULT_OSS_MODEL;
@@ -43,6 +48,7 @@ use tracing_subscriber::EnvFilter;
 use tracing_subscriber::prelude::*;
 
 use crate::cli::Command as ExecCommand;
+use crate::cli::FailOn;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
 use codex_core::default_client::set_default_originator;
@@ -81,10 +87,35 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         // when the Resume subcommand did not provide its own prompt.
         Some(ExecCommand::Resume(args)) => args.prompt.clone().or(prompt),
         Some(ExecCommand::ResumeClone(args)) => args.prompt.clone().or(prompt),
+        Some(ExecCommand::Review(args)) => args.prompt.clone().or(prompt),
         None => prompt,
     };
 
+    // Reviews describe their own target, so the prompt is optional and never
+    // read from stdin.
+    let review_request = match &command {
+        Some(ExecCommand::Review(args)) => {
+            match build_review_request(args, prompt_arg.as_deref()) {
+                Ok(request) => Some(request),
+                Err(err) => {
+                    eprintln!("{err}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        _ => None,
+    };
+    let (review_json, fail_on) = match &command {
+        Some(ExecCommand::Review(args)) => (args.json, args.fail_on),
+        _ => (false, FailOn::Never),
+    };
+    let json_mode = json_mode || review_json;
+
     let prompt = match prompt_arg {
+        _ if review_request.is_some() => review_request
+            .as_ref()
+            .map(|request| request.prompt.clone())
+            .unwrap_or_default(),
         Some(p) if p != "-" => p,
         // Either `-` was passed or no positional arg.
         maybe_dash => {
@@ -213,10 +244,17 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         let otel_layer = OpenTelemetryTracingBridge::new(&provider.logger).with_filter(
             tracing_subscriber::filter::filter_fn(codex_core::otel_init::codex_export_filter),
         );
+        let trace_layer =
+            provider
+                .tracing_layer()
+                .with_filter(tracing_subscriber::filter::filter_fn(
+                    codex_core::otel_init::codex_trace_filter,
+                ));
 
         let _ = tracing_subscriber::registry()
             .with(fmt_layer)
             .with(otel_layer)
+            .with(trace_layer)
             .try_init();
     } else {
         let _ = tracing_subscriber::registry().with(fmt_layer).try_init();
@@ -249,7 +287,12 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         std::process::exit(1);
     }
 
-    let auth_manager = AuthManager::shared(config.codex_home.clone(), true);
+    let auth_manager = AuthManager::shared_with_profile(
+        config.codex_home.clone(),
+        config.auth_profile.clone(),
+        config.cli_auth_credentials_store_mode,
+        true,
+    );
     let conversation_manager = ConversationManager::new(auth_manager.clone(), SessionSource::Exec);
 
     // Handle resume subcommand by resolving a rollout path and using explicit resume API.
@@ -387,29 +430,65 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         }
     }
 
-    // Send the prompt.
-    let items: Vec<InputItem> = vec![InputItem::Text { text: prompt }];
-    let initial_prompt_task_id = conversation
-        .submit(Op::UserTurn {
-            items,
-            cwd: default_cwd,
-            approval_policy: default_approval_policy,
-            sandbox_policy: default_sandbox_policy,
-            model: default_model,
-            effort: default_effort,
-            summary: default_summary,
-            final_output_json_schema: output_schema,
-        })
-        .await?;
+    // Send the prompt, or start the review.
+    let is_review = review_request.is_some();
+    let initial_prompt_task_id = if let Some(review_request) = review_request {
+        conversation.submit(Op::Review { review_request }).await?
+    } else {
+        let items: Vec<InputItem> = vec![InputItem::Text { text: prompt }];
+        conversation
+            .submit(Op::UserTurn {
+                items,
+                cwd: default_cwd,
+                approval_policy: default_approval_policy,
+                sandbox_policy: default_sandbox_policy,
+                model: default_model,
+                effort: default_effort,
+                summary: default_summary,
+                final_output_json_schema: output_schema,
+            })
+            .await?
+    };
     info!("Sent prompt with event ID: {initial_prompt_task_id}");
 
     // Run the loop until the task is complete.
     // Track whether a fatal error was reported by the server so we can
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
+    let mut review_started = false;
+    let mut worst_finding: Option<ReviewSeverity> = None;
     while let Some(event) = rx.recv().await {
-        if matches!(event.msg, EventMsg::Error(_)) {
-            error_seen = true;
+        match &event.msg {
+            EventMsg::Error(_) => {
+                error_seen = true;
+                // A review that fails to start (e.g. an unknown revision)
+                // never produces a task to wait for.
+                if is_review && !review_started {
+                    conversation.submit(Op::Shutdown).await?;
+                }
+            }
+            EventMsg::EnteredReviewMode(_) => review_started = true,
+            // There is nobody to answer MCP elicitations in a non-interactive
+            // run, so answer them as configured.
+            EventMsg::ElicitationRequest(ev) => {
+                if let Err(e) = conversation
+                    .submit(Op::ResolveElicitation {
+                        server_name: ev.server_name.clone(),
+                        request_id: ev.id.clone(),
+                        decision: config.mcp_elicitation_policy.action(),
+                        content: None,
+                    })
+                    .await
+                {
+                    error!("failed to answer elicitation: {e}");
+                }
+            }
+            EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
+                review_output: Some(output),
+            }) => {
+                worst_finding = output.findings.iter().map(ReviewFinding::severity).max();
+            }
+            _ => {}
         }
         // Auto-approve requests when the approve_all feature is enabled.
         if approve_all_enabled {
@@ -454,10 +533,53 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     if error_seen {
         std::process::exit(1);
     }
+    if let (Some(threshold), Some(worst)) = (fail_on.threshold(), worst_finding)
+        && worst >= threshold
+    {
+        std::process::exit(2);
+    }
 
     Ok(())
 }
 
+/// Translate `codex exec review` arguments into a [`ReviewRequest`].
+fn build_review_request(
+    args: &crate::cli::ReviewArgs,
+    instructions: Option<&str>,
+) -> anyhow::Result<ReviewRequest> {
+    let target = if let Some(branch) = &args.base {
+        ReviewTarget::BaseBranch {
+            branch: branch.clone(),
+        }
+    } else if let Some(sha) = &args.commit {
+        ReviewTarget::Commit { sha: sha.clone() }
+    } else if let Some(range) = &args.range {
+        match range.split_once("..") {
+            Some((base, head))
+                if !base.is_empty() && !head.is_empty() && !head.starts_with('.') =>
+            {
+                ReviewTarget::Range {
+                    base: base.to_string(),
+                    head: head.to_string(),
+                }
+            }
+            _ => anyhow::bail!("--range must have the form BASE..HEAD, got '{range}'"),
+        }
+    } else {
+        ReviewTarget::UncommittedChanges
+    };
+
+    let mut prompt = format!("Review the {target} and provide prioritized, actionable findings.");
+    if let Some(instructions) = instructions.map(str::trim).filter(|s| !s.is_empty()) {
+        prompt.push_str(&format!("\n\nAdditional instructions: {instructions}"));
+    }
+    Ok(ReviewRequest {
+        prompt,
+        user_facing_hint: target.to_string(),
+        target: Some(target),
+    })
+}
+
 async fn resolve_resume_path(
     config: &Config,
     args: &crate::cli::ResumeArgs,
@@ -5,12 +5,17 @@ use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecCommandBeginEvent;
 use codex_core::protocol::ExecCommandEndEvent;
+use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
+use codex_core::protocol::ReviewCodeLocation;
+use codex_core::protocol::ReviewFinding;
+use codex_core::protocol::ReviewLineRange;
+use codex_core::protocol::ReviewOutputEvent;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol::WebSearchEndEvent;
 use codex_exec::event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
@@ -25,6 +30,9 @@ use codex_exec::exec_events::McpToolCallStatus;
 use codex_exec::exec_events::PatchApplyStatus;
 use codex_exec::exec_events::PatchChangeKind;
 use codex_exec::exec_events::ReasoningItem;
+use codex_exec::exec_events::ReviewFindingItem;
+use codex_exec::exec_events::ReviewItem;
+use codex_exec::exec_events::ReviewSeverity;
 use codex_exec::exec_events::ThreadErrorEvent;
 use codex_exec::exec_events::ThreadEvent;
 use codex_exec::exec_events::ThreadItem;
@@ -116,6 +124,52 @@ fn web_search_end_emits_item_completed() {
     );
 }
 
+#[test]
+fn exited_review_mode_emits_review_item_with_severity() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let out = ep.collect_thread_events(&event(
+        "r1",
+        EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
+            review_output: Some(ReviewOutputEvent {
+                findings: vec![ReviewFinding {
+                    title: "[P1] Off by one".to_string(),
+                    body: "The loop skips the last element.".to_string(),
+                    confidence_score: 0.8,
+                    priority: 1,
+                    code_location: ReviewCodeLocation {
+                        absolute_file_path: PathBuf::from("/repo/src/lib.rs"),
+                        line_range: ReviewLineRange { start: 10, end: 12 },
+                    },
+                }],
+                overall_correctness: "patch is incorrect".to_string(),
+                overall_explanation: "One bug.".to_string(),
+                overall_confidence_score: 0.8,
+            }),
+        }),
+    ));
+
+    assert_eq!(
+        out,
+        vec![ThreadEvent::ItemCompleted(ItemCompletedEvent {
+            item: ThreadItem {
+                id: "item_0".to_string(),
+                details: ThreadItemDetails::Review(ReviewItem {
+                    findings: vec![ReviewFindingItem {
+                        title: "[P1] Off by one".to_string(),
+                        body: "The loop skips the last element.".to_string(),
+                        file: "/repo/src/lib.rs".to_string(),
+                        line: 10,
+                        end_line: 12,
+                        severity: ReviewSeverity::High,
+                    }],
+                    overall_correctness: "patch is incorrect".to_string(),
+                    overall_explanation: "One bug.".to_string(),
+                }),
+            },
+        })]
+    );
+}
+
 #[test]
 fn plan_update_emits_todo_list_started_updated_and_completed() {
     let mut ep = EventProcessorWithJsonOutput::new(None);
@@ -644,6 +698,7 @@ fn patch_apply_success_produces_item_completed_patchapply() {
         PathBuf::from("a/added.txt"),
         FileChange::Add {
             content: "+hello".to_string(),
+            mode: None,
         },
     );
     changes.insert(
@@ -657,6 +712,7 @@ fn patch_apply_success_produces_item_completed_patchapply() {
         FileChange::Update {
             unified_diff: "--- c/modified.txt\n+++ c/modified.txt\n@@\n-old\n+new\n".to_string(),
             move_path: Some(PathBuf::from("c/renamed.txt")),
+            mode: None,
         },
     );
 
@@ -680,6 +736,7 @@ fn patch_apply_success_produces_item_completed_patchapply() {
             stdout: "applied 3 changes".to_string(),
             stderr: String::new(),
             success: true,
+            committed: Vec::new(),
         }),
     );
     let out_end = ep.collect_thread_events(&end);
@@ -726,6 +783,7 @@ fn patch_apply_failure_produces_item_completed_patchapply_failed() {
         FileChange::Update {
             unified_diff: "--- file.txt\n+++ file.txt\n@@\n-old\n+new\n".to_string(),
             move_path: None,
+            mode: None,
         },
     );
 
@@ -748,6 +806,7 @@ fn patch_apply_failure_produces_item_completed_patchapply_failed() {
             stdout: String::new(),
             stderr: "failed to apply".to_string(),
             success: false,
+            committed: Vec::new(),
         }),
     );
     let out_end = ep.collect_thread_events(&end);
@@ -792,6 +851,7 @@ fn task_complete_produces_turn_completed_with_usage() {
         EventMsg::TokenCount(codex_core::protocol::TokenCountEvent {
             info: Some(info),
             rate_limits: None,
+            cost: None,
         }),
     );
     assert!(ep.collect_thread_events(&token_count_event).is_empty());
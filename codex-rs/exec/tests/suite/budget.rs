@@ -0,0 +1,90 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use predicates::str::contains;
+
+/// A session that reaches its hard budget stops before the next model request
+/// and `codex-exec` exits non-zero.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exits_non_zero_when_session_budget_is_exceeded() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    // 1M uncached input tokens of gpt-5 cost $1.25. The tool call would
+    // normally trigger a follow-up request.
+    let server = responses::start_mock_server().await;
+    responses::mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_response_created("resp1"),
+            responses::ev_function_call("call-1", "unsupported_tool", "{}"),
+            responses::ev_completed_with_tokens("resp1", 1_000_000),
+        ]),
+    )
+    .await;
+    responses::mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_assistant_message("m1", "done"),
+            responses::ev_completed("resp2"),
+        ]),
+    )
+    .await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("-m")
+        .arg("gpt-5")
+        .arg("-c")
+        .arg("budget.session.hard=1")
+        .arg("do something")
+        .assert()
+        .code(1)
+        .stderr(contains(
+            "Session budget exceeded: estimated spend $1.25 reached the hard limit of $1.00.",
+        ));
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert_eq!(requests.len(), 1);
+
+    let ledger: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(
+        test.home_path().join("costs.json"),
+    )?)?;
+    let daily_total: f64 = ledger["days"]
+        .as_object()
+        .expect("days map")
+        .values()
+        .filter_map(serde_json::Value::as_f64)
+        .sum();
+    assert!((daily_total - 1.25).abs() < 1e-9, "{ledger}");
+
+    Ok(())
+}
+
+/// Spend recorded by earlier sessions counts against the daily budget, so
+/// no request is made once it is used up.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn refuses_to_start_when_daily_budget_is_used_up() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    std::fs::write(
+        test.home_path().join("costs.json"),
+        serde_json::json!({ "days": { today: 7.5 } }).to_string(),
+    )?;
+
+    let server = responses::start_mock_server().await;
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("-c")
+        .arg("budget.daily.hard=5")
+        .arg("do something")
+        .assert()
+        .code(1)
+        .stderr(contains("Daily budget exceeded"));
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert!(requests.is_empty());
+
+    Ok(())
+}
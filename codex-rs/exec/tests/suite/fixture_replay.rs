@@ -0,0 +1,59 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use std::time::Duration;
+use std::time::Instant;
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+
+/// A replay-mode provider with no recorded response fails the turn at once
+/// instead of retrying, and never reaches the network.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn missing_fixture_fails_without_retrying() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let server = responses::start_mock_server().await;
+    std::fs::write(
+        test.home_path().join("config.toml"),
+        format!(
+            r#"
+model_provider = "replay"
+
+[model_providers.replay]
+name = "replay"
+base_url = "{}/v1"
+wire_api = "responses"
+stream_max_retries = 5
+fixtures = {{ mode = "replay", dir = "fixtures" }}
+"#,
+            server.uri()
+        ),
+    )?;
+
+    let started = Instant::now();
+    let output = test
+        .cmd()
+        .arg("--skip-git-repo-check")
+        .arg("tell me something")
+        .output()?;
+    let elapsed = started.elapsed();
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("no recorded model fixture matches this request"),
+        "stderr: {stderr}"
+    );
+    assert!(!stderr.contains("Re-connecting"), "stderr: {stderr}");
+    // Five retries with backoff would take several seconds.
+    assert!(elapsed < Duration::from_secs(3), "took {elapsed:?}");
+    assert!(
+        server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .is_empty()
+    );
+
+    Ok(())
+}
@@ -2,6 +2,8 @@
 mod apply_patch;
 mod approve_all;
 mod auth_env;
+mod budget;
+mod fixture_replay;
 mod originator;
 mod output_schema;
 mod resume;
@@ -188,6 +188,8 @@ pub async fn run_device_code_login(opts: ServerOptions) -> std::io::Result<()> {
 
     crate::server::persist_tokens_async(
         &opts.codex_home,
+        opts.auth_profile.as_deref(),
+        opts.cli_auth_credentials_store_mode,
         None,
         tokens.id_token,
         tokens.access_token,
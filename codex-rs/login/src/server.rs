@@ -14,8 +14,9 @@ use crate::pkce::PkceCodes;
 use crate::pkce::generate_pkce;
 use base64::Engine;
 use chrono::Utc;
+use codex_core::auth::AuthCredentialsStoreMode;
 use codex_core::auth::AuthDotJson;
-use codex_core::auth::get_auth_file;
+use codex_core::auth::save_auth;
 use codex_core::default_client::originator;
 use codex_core::token_data::TokenData;
 use codex_core::token_data::parse_id_token;
@@ -33,6 +34,11 @@ const DEFAULT_PORT: u16 = 1455;
 #[derive(Debug, Clone)]
 pub struct ServerOptions {
     pub codex_home: PathBuf,
+    /// Named auth profile to store the credentials under; `None` writes
+    /// `auth.json`.
+    pub auth_profile: Option<String>,
+    /// Where the credentials are stored.
+    pub cli_auth_credentials_store_mode: AuthCredentialsStoreMode,
     pub client_id: String,
     pub issuer: String,
     pub port: u16,
@@ -44,6 +50,8 @@ impl ServerOptions {
     pub fn new(codex_home: PathBuf, client_id: String) -> Self {
         Self {
             codex_home,
+            auth_profile: None,
+            cli_auth_credentials_store_mode: AuthCredentialsStoreMode::default(),
             client_id,
             issuer: DEFAULT_ISSUER.to_string(),
             port: DEFAULT_PORT,
@@ -246,6 +254,8 @@ async fn process_request(
                         .ok();
                     if let Err(err) = persist_tokens_async(
                         &opts.codex_home,
+                        opts.auth_profile.as_deref(),
+                        opts.cli_auth_credentials_store_mode,
                         api_key.clone(),
                         tokens.id_token.clone(),
                         tokens.access_token.clone(),
@@ -499,6 +509,8 @@ pub(crate) async fn exchange_code_for_tokens(
 
 pub(crate) async fn persist_tokens_async(
     codex_home: &Path,
+    auth_profile: Option<&str>,
+    store_mode: AuthCredentialsStoreMode,
     api_key: Option<String>,
     id_token: String,
     access_token: String,
@@ -506,14 +518,8 @@ pub(crate) async fn persist_tokens_async(
 ) -> io::Result<()> {
     // Reuse existing synchronous logic but run it off the async runtime.
     let codex_home = codex_home.to_path_buf();
+    let auth_profile = auth_profile.map(str::to_string);
     tokio::task::spawn_blocking(move || {
-        let auth_file = get_auth_file(&codex_home);
-        if let Some(parent) = auth_file.parent()
-            && !parent.exists()
-        {
-            std::fs::create_dir_all(parent).map_err(io::Error::other)?;
-        }
-
         let mut tokens = TokenData {
             id_token: parse_id_token(&id_token).map_err(io::Error::other)?,
             access_token,
@@ -531,7 +537,7 @@ pub(crate) async fn persist_tokens_async(
             tokens: Some(tokens),
             last_refresh: Some(Utc::now()),
         };
-        codex_core::auth::write_auth_json(&auth_file, &auth)
+        save_auth(&codex_home, auth_profile.as_deref(), store_mode, &auth)
     })
     .await
     .map_err(|e| io::Error::other(format!("persist task failed: {e}")))?
@@ -47,6 +47,7 @@ use tokio::process::Command;
 use tokio::sync::Mutex;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
 use tokio::time;
 use tracing::debug;
 use tracing::error;
@@ -77,6 +78,9 @@ pub struct McpClient {
 
     /// Monotonically increasing counter used to generate request IDs.
     id_counter: AtomicI64,
+
+    /// Reader task; it finishes once the server closes its STDOUT.
+    reader_handle: JoinHandle<()>,
 }
 
 impl McpClient {
@@ -178,20 +182,28 @@ impl McpClient {
             })
         };
 
-        // We intentionally *detach* the tasks. They will keep running in the
-        // background as long as their respective resources (channels/stdin/
+        // We intentionally *detach* the writer task. Both tasks keep running in
+        // the background as long as their respective resources (channels/stdin/
         // stdout) are alive. Dropping `McpClient` cancels the tasks due to
-        // dropped resources.
-        let _ = (writer_handle, reader_handle);
+        // dropped resources. The reader handle is kept so we can tell when the
+        // server has gone away.
+        drop(writer_handle);
 
         Ok(Self {
             child,
             outgoing_tx,
             pending,
             id_counter: AtomicI64::new(1),
+            reader_handle,
         })
     }
 
+    /// Returns true once the connection to the server is gone, i.e. the server
+    /// closed its STDOUT or can no longer be written to.
+    pub fn is_closed(&self) -> bool {
+        self.reader_handle.is_finished() || self.outgoing_tx.is_closed()
+    }
+
     /// Send an arbitrary MCP request and await the typed result.
     ///
     /// If `timeout` is `None` the call waits indefinitely. If `Some(duration)`
@@ -222,6 +222,95 @@ pub(crate) fn create_tool_for_codex_tool_call_reply_param() -> Tool {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexListSessionsParam {
+    /// Also list sessions recorded in CODEX_HOME that can be passed to
+    /// `codex-resume`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_recorded: Option<bool>,
+}
+
+/// Parameters shared by the tools that act on a single running session.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexSessionParam {
+    /// The conversation id for this Codex session.
+    pub conversation_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexResumeParam {
+    /// The conversation id of a session recorded in CODEX_HOME.
+    pub conversation_id: String,
+
+    /// Optional prompt to continue the conversation with. Without it, the
+    /// session is only loaded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+}
+
+/// Builds the input schema for a tool that accepts `T` as its arguments.
+fn create_tool_input_schema<T: JsonSchema>() -> ToolInputSchema {
+    let schema = SchemaSettings::draft2019_09()
+        .with(|s| {
+            s.inline_subschemas = true;
+            s.option_add_null_type = false;
+        })
+        .into_generator()
+        .into_root_schema_for::<T>();
+
+    #[expect(clippy::expect_used)]
+    let schema_value = serde_json::to_value(&schema).expect("tool schema should serialise to JSON");
+
+    serde_json::from_value::<ToolInputSchema>(schema_value).unwrap_or_else(|e| {
+        panic!("failed to create Tool from schema: {e}");
+    })
+}
+
+fn create_tool<T: JsonSchema>(name: &str, title: &str, description: &str) -> Tool {
+    Tool {
+        name: name.to_string(),
+        title: Some(title.to_string()),
+        input_schema: create_tool_input_schema::<T>(),
+        output_schema: None,
+        description: Some(description.to_string()),
+        annotations: None,
+    }
+}
+
+/// Builds the `Tool` definitions for the session management tools.
+pub(crate) fn create_session_tools() -> Vec<Tool> {
+    vec![
+        create_tool::<CodexListSessionsParam>(
+            "codex-list-sessions",
+            "Codex List Sessions",
+            "List the Codex sessions running in this server and, optionally, the sessions recorded on disk.",
+        ),
+        create_tool::<CodexSessionParam>(
+            "codex-interrupt",
+            "Codex Interrupt",
+            "Interrupt the turn that is running in a Codex session.",
+        ),
+        create_tool::<CodexSessionParam>(
+            "codex-get-diff",
+            "Codex Get Diff",
+            "Get the unified diff of the changes made during the latest turn of a Codex session.",
+        ),
+        create_tool::<CodexResumeParam>(
+            "codex-resume",
+            "Codex Resume",
+            "Resume a recorded Codex session, optionally continuing it with a prompt.",
+        ),
+        create_tool::<CodexSessionParam>(
+            "codex-status",
+            "Codex Status",
+            "Get the state, model, token usage and last message of a Codex session.",
+        ),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -334,4 +423,36 @@ mod tests {
         });
         assert_eq!(expected_tool_json, tool_json);
     }
+
+    #[test]
+    fn verify_codex_resume_json_schema() {
+        let tools = create_session_tools();
+        let tool = tools
+            .iter()
+            .find(|tool| tool.name == "codex-resume")
+            .expect("codex-resume tool");
+        let tool_json = serde_json::to_value(tool).expect("tool serializes");
+        let expected_tool_json = serde_json::json!({
+          "description": "Resume a recorded Codex session, optionally continuing it with a prompt.",
+          "inputSchema": {
+            "properties": {
+              "conversationId": {
+                "description": "The conversation id of a session recorded in CODEX_HOME.",
+                "type": "string"
+              },
+              "prompt": {
+                "description": "Optional prompt to continue the conversation with. Without it, the session is only loaded.",
+                "type": "string"
+              },
+            },
+            "required": [
+              "conversationId",
+            ],
+            "type": "object",
+          },
+          "name": "codex-resume",
+          "title": "Codex Resume",
+        });
+        assert_eq!(expected_tool_json, tool_json);
+    }
 }
@@ -6,9 +6,12 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::exec_approval::handle_exec_approval_request;
+use crate::mcp_elicitation::handle_mcp_elicitation_request;
 use crate::outgoing_message::OutgoingMessageSender;
 use crate::outgoing_message::OutgoingNotificationMeta;
 use crate::patch_approval::handle_patch_approval_request;
+use crate::progress::ProgressReporter;
+use crate::session_registry::SessionRegistry;
 use codex_core::CodexConversation;
 use codex_core::ConversationManager;
 use codex_core::NewConversation;
@@ -36,13 +39,16 @@ pub(crate) const INVALID_PARAMS_ERROR_CODE: i64 = -32602;
 ///
 /// On completion (success or error) the function sends the appropriate
 /// `tools/call` response so the LLM can continue the conversation.
-pub async fn run_codex_tool_session(
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run_codex_tool_session(
     id: RequestId,
     initial_prompt: String,
     config: CodexConfig,
     outgoing: Arc<OutgoingMessageSender>,
     conversation_manager: Arc<ConversationManager>,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
+    sessions: Arc<SessionRegistry>,
+    progress: Option<ProgressReporter>,
 ) {
     let NewConversation {
         conversation_id,
@@ -65,6 +71,8 @@ pub async fn run_codex_tool_session(
         }
     };
 
+    sessions.register(&session_configured).await;
+
     let session_configured_event = Event {
         // Use a fake id value for now.
         id: "".to_string(),
@@ -106,20 +114,26 @@ pub async fn run_codex_tool_session(
 
     run_codex_tool_session_inner(
         conversation,
+        conversation_id,
         outgoing,
         id,
         running_requests_id_to_codex_uuid,
+        sessions,
+        progress,
     )
     .await;
 }
 
-pub async fn run_codex_tool_session_reply(
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run_codex_tool_session_reply(
     conversation: Arc<CodexConversation>,
     outgoing: Arc<OutgoingMessageSender>,
     request_id: RequestId,
     prompt: String,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
     conversation_id: ConversationId,
+    sessions: Arc<SessionRegistry>,
+    progress: Option<ProgressReporter>,
 ) {
     running_requests_id_to_codex_uuid
         .lock()
@@ -142,18 +156,24 @@ pub async fn run_codex_tool_session_reply(
 
     run_codex_tool_session_inner(
         conversation,
+        conversation_id,
         outgoing,
         request_id,
         running_requests_id_to_codex_uuid,
+        sessions,
+        progress,
     )
     .await;
 }
 
 async fn run_codex_tool_session_inner(
     codex: Arc<CodexConversation>,
+    conversation_id: ConversationId,
     outgoing: Arc<OutgoingMessageSender>,
     request_id: RequestId,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
+    sessions: Arc<SessionRegistry>,
+    mut progress: Option<ProgressReporter>,
 ) {
     let request_id_str = match &request_id {
         RequestId::String(s) => s.clone(),
@@ -171,6 +191,10 @@ async fn run_codex_tool_session_inner(
                         Some(OutgoingNotificationMeta::new(Some(request_id.clone()))),
                     )
                     .await;
+                sessions.record_event(conversation_id, &event.msg).await;
+                if let Some(progress) = progress.as_mut() {
+                    progress.report(&event.msg).await;
+                }
 
                 match event.msg {
                     EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
@@ -222,6 +246,16 @@ async fn run_codex_tool_session_inner(
                         .await;
                         continue;
                     }
+                    EventMsg::ElicitationRequest(ev) => {
+                        handle_mcp_elicitation_request(
+                            ev,
+                            outgoing.clone(),
+                            codex.clone(),
+                            request_id_str.clone(),
+                        )
+                        .await;
+                        continue;
+                    }
                     EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message }) => {
                         let text = match last_agent_message {
                             Some(msg) => msg,
@@ -244,6 +278,28 @@ async fn run_codex_tool_session_inner(
                             .remove(&request_id);
                         break;
                     }
+                    EventMsg::TurnAborted(_) => {
+                        // Cancelled requests are unregistered before the
+                        // interrupt is sent and must not get a response.
+                        let registered = running_requests_id_to_codex_uuid
+                            .lock()
+                            .await
+                            .remove(&request_id)
+                            .is_some();
+                        if registered {
+                            let result = CallToolResult {
+                                content: vec![ContentBlock::TextContent(TextContent {
+                                    r#type: "text".to_string(),
+                                    text: "Codex turn was interrupted".to_string(),
+                                    annotations: None,
+                                })],
+                                is_error: Some(true),
+                                structured_content: None,
+                            };
+                            outgoing.send_response(request_id.clone(), result).await;
+                        }
+                        break;
+                    }
                     EventMsg::SessionConfigured(_) => {
                         tracing::error!("unexpected SessionConfigured event");
                     }
@@ -265,12 +321,15 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::McpToolCallBegin(_)
                     | EventMsg::McpToolCallEnd(_)
                     | EventMsg::McpListToolsResponse(_)
+                    | EventMsg::McpListChanged(_)
+                    | EventMsg::McpServerStatus(_)
                     | EventMsg::ListCustomPromptsResponse(_)
                     | EventMsg::ExecCommandBegin(_)
                     | EventMsg::ExecCommandOutputDelta(_)
                     | EventMsg::ExecCommandEnd(_)
                     | EventMsg::BackgroundEvent(_)
                     | EventMsg::StreamError(_)
+                    | EventMsg::BudgetWarning(_)
                     | EventMsg::PatchApplyBegin(_)
                     | EventMsg::PatchApplyEnd(_)
                     | EventMsg::TurnDiff(_)
@@ -278,13 +337,15 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::WebSearchEnd(_)
                     | EventMsg::GetHistoryEntryResponse(_)
                     | EventMsg::PlanUpdate(_)
-                    | EventMsg::TurnAborted(_)
                     | EventMsg::ConversationPath(_)
                     | EventMsg::UserMessage(_)
                     | EventMsg::ShutdownComplete
                     | EventMsg::ViewImageToolCall(_)
                     | EventMsg::EnteredReviewMode(_)
-                    | EventMsg::ExitedReviewMode(_) => {
+                    | EventMsg::ExitedReviewMode(_)
+                    | EventMsg::CheckpointCreated(_)
+                    | EventMsg::ListCheckpointsResponse(_)
+                    | EventMsg::CheckpointRestored(_) => {
                         // For now, we do not do anything extra for these
                         // events. Note that
                         // send(codex_event_to_notification(&event)) above has
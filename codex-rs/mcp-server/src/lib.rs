@@ -24,14 +24,20 @@ mod codex_tool_config;
 mod codex_tool_runner;
 mod error_code;
 mod exec_approval;
+mod mcp_elicitation;
 pub(crate) mod message_processor;
 mod outgoing_message;
 mod patch_approval;
+mod progress;
+mod session_registry;
 
 use crate::message_processor::MessageProcessor;
 use crate::outgoing_message::OutgoingMessage;
 use crate::outgoing_message::OutgoingMessageSender;
 
+pub use crate::codex_tool_config::CodexListSessionsParam;
+pub use crate::codex_tool_config::CodexResumeParam;
+pub use crate::codex_tool_config::CodexSessionParam;
 pub use crate::codex_tool_config::CodexToolCallParam;
 pub use crate::codex_tool_config::CodexToolCallReplyParam;
 pub use crate::exec_approval::ExecApprovalElicitRequestParams;
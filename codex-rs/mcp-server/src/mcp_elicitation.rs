@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use codex_core::CodexConversation;
+use codex_core::protocol::ElicitationAction;
+use codex_core::protocol::ElicitationRequestEvent;
+use codex_core::protocol::Op;
+use mcp_types::ElicitRequest;
+use mcp_types::ElicitRequestParamsRequestedSchema;
+use mcp_types::ModelContextProtocolRequest;
+use mcp_types::RequestId;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::error;
+
+use crate::outgoing_message::OutgoingMessageSender;
+
+/// Conforms to [`mcp_types::ElicitRequestParams`] so that it can be used as the
+/// `params` field of an [`ElicitRequest`].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct McpElicitRequestParams {
+    pub message: String,
+
+    #[serde(rename = "requestedSchema")]
+    pub requested_schema: ElicitRequestParamsRequestedSchema,
+
+    // These are additional fields the client can use to
+    // correlate the request with the codex tool call.
+    pub codex_elicitation: String,
+    pub codex_mcp_tool_call_id: String,
+    pub codex_mcp_server: String,
+}
+
+/// Conforms to [`mcp_types::ElicitResult`].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct McpElicitResponse {
+    pub action: ElicitationAction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<serde_json::Value>,
+}
+
+/// Relay an elicitation issued by one of Codex's own MCP servers to the client
+/// of the Codex MCP server, which is in a better position to ask the user.
+pub(crate) async fn handle_mcp_elicitation_request(
+    event: ElicitationRequestEvent,
+    outgoing: Arc<OutgoingMessageSender>,
+    codex: Arc<CodexConversation>,
+    tool_call_id: String,
+) {
+    let ElicitationRequestEvent {
+        server_name,
+        id,
+        message,
+        requested_schema,
+    } = event;
+    let params = McpElicitRequestParams {
+        message,
+        requested_schema,
+        codex_elicitation: "mcp-elicitation".to_string(),
+        codex_mcp_tool_call_id: tool_call_id,
+        codex_mcp_server: server_name.clone(),
+    };
+    let params_json = match serde_json::to_value(&params) {
+        Ok(value) => value,
+        Err(err) => {
+            error!("Failed to serialize McpElicitRequestParams: {err}");
+            resolve(&codex, server_name, id, decline()).await;
+            return;
+        }
+    };
+
+    let on_response = outgoing
+        .send_request(ElicitRequest::METHOD, Some(params_json))
+        .await;
+
+    // Listen for the response on a separate task so we don't block the main agent loop.
+    tokio::spawn(async move {
+        let response = match on_response.await {
+            Ok(value) => serde_json::from_value::<McpElicitResponse>(value).unwrap_or_else(|err| {
+                error!("failed to deserialize McpElicitResponse: {err}");
+                decline()
+            }),
+            Err(err) => {
+                error!("request failed: {err:?}");
+                decline()
+            }
+        };
+        resolve(&codex, server_name, id, response).await;
+    });
+}
+
+fn decline() -> McpElicitResponse {
+    McpElicitResponse {
+        action: ElicitationAction::Decline,
+        content: None,
+    }
+}
+
+async fn resolve(
+    codex: &CodexConversation,
+    server_name: String,
+    request_id: RequestId,
+    response: McpElicitResponse,
+) {
+    if let Err(err) = codex
+        .submit(Op::ResolveElicitation {
+            server_name,
+            request_id,
+            decision: response.action,
+            content: response.content,
+        })
+        .await
+    {
+        error!("failed to submit ResolveElicitation: {err}");
+    }
+}
@@ -1,20 +1,35 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::codex_tool_config::CodexListSessionsParam;
+use crate::codex_tool_config::CodexResumeParam;
+use crate::codex_tool_config::CodexSessionParam;
 use crate::codex_tool_config::CodexToolCallParam;
 use crate::codex_tool_config::CodexToolCallReplyParam;
+use crate::codex_tool_config::create_session_tools;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_param;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_reply_param;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::outgoing_message::OutgoingNotificationMeta;
+use crate::progress::ProgressReporter;
+use crate::progress::progress_token;
+use crate::session_registry::SessionRegistry;
 use codex_protocol::ConversationId;
+use codex_protocol::protocol::SessionMeta;
 use codex_protocol::protocol::SessionSource;
 
 use codex_core::AuthManager;
 use codex_core::ConversationManager;
+use codex_core::NewConversation;
+use codex_core::RolloutRecorder;
 use codex_core::config::Config;
 use codex_core::default_client::USER_AGENT_SUFFIX;
 use codex_core::default_client::get_codex_user_agent;
+use codex_core::find_conversation_path_by_id_str;
+use codex_core::protocol::Event;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
 use codex_core::protocol::Submission;
 use mcp_types::CallToolRequestParams;
 use mcp_types::CallToolResult;
@@ -27,10 +42,13 @@ use mcp_types::JSONRPCRequest;
 use mcp_types::JSONRPCResponse;
 use mcp_types::ListToolsResult;
 use mcp_types::ModelContextProtocolRequest;
+use mcp_types::ProgressToken;
 use mcp_types::RequestId;
 use mcp_types::ServerCapabilitiesTools;
 use mcp_types::ServerNotification;
 use mcp_types::TextContent;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use serde_json::json;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -40,10 +58,16 @@ pub(crate) struct MessageProcessor {
     outgoing: Arc<OutgoingMessageSender>,
     initialized: bool,
     codex_linux_sandbox_exe: Option<PathBuf>,
+    config: Arc<Config>,
+    auth_manager: Arc<AuthManager>,
     conversation_manager: Arc<ConversationManager>,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
+    sessions: Arc<SessionRegistry>,
 }
 
+/// Number of recorded sessions returned by `codex-list-sessions`.
+const RECORDED_SESSIONS_PAGE_SIZE: usize = 25;
+
 impl MessageProcessor {
     /// Create a new `MessageProcessor`, retaining a handle to the outgoing
     /// `Sender` so handlers can enqueue messages to be written to stdout.
@@ -53,21 +77,32 @@ impl MessageProcessor {
         config: Arc<Config>,
     ) -> Self {
         let outgoing = Arc::new(outgoing);
-        let auth_manager = AuthManager::shared(config.codex_home.clone(), false);
-        let conversation_manager =
-            Arc::new(ConversationManager::new(auth_manager, SessionSource::Mcp));
+        let auth_manager = AuthManager::shared_with_profile(
+            config.codex_home.clone(),
+            config.auth_profile.clone(),
+            config.cli_auth_credentials_store_mode,
+            false,
+        );
+        let conversation_manager = Arc::new(ConversationManager::new(
+            auth_manager.clone(),
+            SessionSource::Mcp,
+        ));
         Self {
             outgoing,
             initialized: false,
             codex_linux_sandbox_exe,
+            config,
+            auth_manager,
             conversation_manager,
             running_requests_id_to_codex_uuid: Arc::new(Mutex::new(HashMap::new())),
+            sessions: Arc::new(SessionRegistry::default()),
         }
     }
 
     pub(crate) async fn process_request(&mut self, request: JSONRPCRequest) {
         // Hold on to the ID so we can respond.
         let request_id = request.id.clone();
+        let progress_token = progress_token(request.params.as_ref());
 
         let client_request = match McpClientRequest::try_from(request) {
             Ok(client_request) => client_request,
@@ -110,7 +145,8 @@ impl MessageProcessor {
                 self.handle_list_tools(request_id, params).await;
             }
             McpClientRequest::CallToolRequest(params) => {
-                self.handle_call_tool(request_id, params).await;
+                self.handle_call_tool(request_id, params, progress_token)
+                    .await;
             }
             McpClientRequest::SetLevelRequest(params) => {
                 self.handle_set_level(params);
@@ -299,10 +335,13 @@ impl MessageProcessor {
     ) {
         tracing::trace!("tools/list -> {params:?}");
         let result = ListToolsResult {
-            tools: vec![
+            tools: [
                 create_tool_for_codex_tool_call_param(),
                 create_tool_for_codex_tool_call_reply_param(),
-            ],
+            ]
+            .into_iter()
+            .chain(create_session_tools())
+            .collect(),
             next_cursor: None,
         };
 
@@ -314,16 +353,24 @@ impl MessageProcessor {
         &self,
         id: RequestId,
         params: <mcp_types::CallToolRequest as mcp_types::ModelContextProtocolRequest>::Params,
+        progress_token: Option<ProgressToken>,
     ) {
         tracing::info!("tools/call -> params: {:?}", params);
         let CallToolRequestParams { name, arguments } = params;
+        let progress =
+            progress_token.map(|token| ProgressReporter::new(self.outgoing.clone(), token));
 
         match name.as_str() {
-            "codex" => self.handle_tool_call_codex(id, arguments).await,
+            "codex" => self.handle_tool_call_codex(id, arguments, progress).await,
             "codex-reply" => {
-                self.handle_tool_call_codex_session_reply(id, arguments)
+                self.handle_tool_call_codex_session_reply(id, arguments, progress)
                     .await
             }
+            "codex-list-sessions" => self.handle_tool_call_list_sessions(id, arguments).await,
+            "codex-interrupt" => self.handle_tool_call_interrupt(id, arguments).await,
+            "codex-get-diff" => self.handle_tool_call_get_diff(id, arguments).await,
+            "codex-resume" => self.handle_tool_call_resume(id, arguments, progress).await,
+            "codex-status" => self.handle_tool_call_status(id, arguments).await,
             _ => {
                 let result = CallToolResult {
                     content: vec![ContentBlock::TextContent(TextContent {
@@ -339,7 +386,13 @@ impl MessageProcessor {
             }
         }
     }
-    async fn handle_tool_call_codex(&self, id: RequestId, arguments: Option<serde_json::Value>) {
+
+    async fn handle_tool_call_codex(
+        &self,
+        id: RequestId,
+        arguments: Option<serde_json::Value>,
+        progress: Option<ProgressReporter>,
+    ) {
         let (initial_prompt, config): (String, Config) = match arguments {
             Some(json_val) => match serde_json::from_value::<CodexToolCallParam>(json_val) {
                 Ok(tool_cfg) => match tool_cfg
@@ -401,6 +454,7 @@ impl MessageProcessor {
         let outgoing = self.outgoing.clone();
         let conversation_manager = self.conversation_manager.clone();
         let running_requests_id_to_codex_uuid = self.running_requests_id_to_codex_uuid.clone();
+        let sessions = self.sessions.clone();
 
         // Spawn an async task to handle the Codex session so that we do not
         // block the synchronous message-processing loop.
@@ -413,6 +467,8 @@ impl MessageProcessor {
                 outgoing,
                 conversation_manager,
                 running_requests_id_to_codex_uuid,
+                sessions,
+                progress,
             )
             .await;
         });
@@ -422,6 +478,7 @@ impl MessageProcessor {
         &self,
         request_id: RequestId,
         arguments: Option<serde_json::Value>,
+        progress: Option<ProgressReporter>,
     ) {
         tracing::info!("tools/call -> params: {:?}", arguments);
 
@@ -516,6 +573,7 @@ impl MessageProcessor {
             let outgoing = outgoing.clone();
             let prompt = prompt.clone();
             let running_requests_id_to_codex_uuid = running_requests_id_to_codex_uuid.clone();
+            let sessions = self.sessions.clone();
 
             async move {
                 crate::codex_tool_runner::run_codex_tool_session_reply(
@@ -525,12 +583,280 @@ impl MessageProcessor {
                     prompt,
                     running_requests_id_to_codex_uuid,
                     conversation_id,
+                    sessions,
+                    progress,
                 )
                 .await;
             }
         });
     }
 
+    async fn handle_tool_call_list_sessions(
+        &self,
+        request_id: RequestId,
+        arguments: Option<serde_json::Value>,
+    ) {
+        let CodexListSessionsParam { include_recorded } =
+            match parse_tool_arguments(arguments.unwrap_or_else(|| json!({}))) {
+                Ok(params) => params,
+                Err(result) => {
+                    self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+                        .await;
+                    return;
+                }
+            };
+
+        let mut result = json!({ "sessions": self.sessions.list().await });
+        if include_recorded.unwrap_or(false) {
+            match RolloutRecorder::list_conversations(
+                &self.config.codex_home,
+                RECORDED_SESSIONS_PAGE_SIZE,
+                None,
+                &[],
+            )
+            .await
+            {
+                Ok(page) => {
+                    let recorded: Vec<serde_json::Value> = page
+                        .items
+                        .into_iter()
+                        .filter_map(|item| {
+                            let meta =
+                                serde_json::from_value::<SessionMeta>(item.head.first()?.clone())
+                                    .ok()?;
+                            Some(json!({
+                                "conversationId": meta.id,
+                                "rolloutPath": item.path,
+                                "createdAt": item.created_at,
+                                "updatedAt": item.updated_at,
+                            }))
+                        })
+                        .collect();
+                    result["recorded"] = json!(recorded);
+                }
+                Err(e) => {
+                    let result = tool_error(format!("Failed to list recorded sessions: {e}"));
+                    self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+                        .await;
+                    return;
+                }
+            }
+        }
+
+        self.send_response::<mcp_types::CallToolRequest>(request_id, tool_json_result(result))
+            .await;
+    }
+
+    async fn handle_tool_call_interrupt(
+        &self,
+        request_id: RequestId,
+        arguments: Option<serde_json::Value>,
+    ) {
+        let conversation_id = match self.parse_session_arguments(arguments).await {
+            Ok(conversation_id) => conversation_id,
+            Err(result) => {
+                self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+                    .await;
+                return;
+            }
+        };
+
+        let result = match self
+            .conversation_manager
+            .get_conversation(conversation_id)
+            .await
+        {
+            Ok(conversation) => match conversation.submit(Op::Interrupt).await {
+                Ok(_) => tool_json_result(json!({ "conversationId": conversation_id })),
+                Err(e) => tool_error(format!("Failed to interrupt Codex session: {e}")),
+            },
+            Err(_) => tool_error(format!(
+                "Session not found for conversation_id: {conversation_id}"
+            )),
+        };
+        self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+            .await;
+    }
+
+    async fn handle_tool_call_get_diff(
+        &self,
+        request_id: RequestId,
+        arguments: Option<serde_json::Value>,
+    ) {
+        let result = match self.parse_session_arguments(arguments).await {
+            Ok(conversation_id) => {
+                let diff = self
+                    .sessions
+                    .status(conversation_id)
+                    .await
+                    .and_then(|status| status.turn_diff);
+                CallToolResult {
+                    content: vec![ContentBlock::TextContent(TextContent {
+                        r#type: "text".to_string(),
+                        text: diff
+                            .clone()
+                            .unwrap_or_else(|| "No changes in the latest turn.".to_string()),
+                        annotations: None,
+                    })],
+                    is_error: None,
+                    structured_content: Some(json!({
+                        "conversationId": conversation_id,
+                        "diff": diff,
+                    })),
+                }
+            }
+            Err(result) => result,
+        };
+        self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+            .await;
+    }
+
+    async fn handle_tool_call_status(
+        &self,
+        request_id: RequestId,
+        arguments: Option<serde_json::Value>,
+    ) {
+        let result = match self.parse_session_arguments(arguments).await {
+            Ok(conversation_id) => match self.sessions.status(conversation_id).await {
+                Some(status) => tool_json_result(status),
+                None => tool_error(format!(
+                    "Session not found for conversation_id: {conversation_id}"
+                )),
+            },
+            Err(result) => result,
+        };
+        self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+            .await;
+    }
+
+    async fn handle_tool_call_resume(
+        &self,
+        request_id: RequestId,
+        arguments: Option<serde_json::Value>,
+        progress: Option<ProgressReporter>,
+    ) {
+        let CodexResumeParam {
+            conversation_id,
+            prompt,
+        } = match arguments.map(parse_tool_arguments) {
+            Some(Ok(params)) => params,
+            Some(Err(result)) => {
+                self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+                    .await;
+                return;
+            }
+            None => {
+                let result = tool_error(
+                    "Missing arguments for codex-resume tool-call; the `conversationId` field is required.".to_string(),
+                );
+                self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+                    .await;
+                return;
+            }
+        };
+
+        let rollout_path =
+            match find_conversation_path_by_id_str(&self.config.codex_home, &conversation_id).await
+            {
+                Ok(Some(path)) => path,
+                Ok(None) => {
+                    let result = tool_error(format!(
+                        "No recorded session found for conversation_id: {conversation_id}"
+                    ));
+                    self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+                        .await;
+                    return;
+                }
+                Err(e) => {
+                    let result = tool_error(format!("Failed to look up recorded session: {e}"));
+                    self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+                        .await;
+                    return;
+                }
+            };
+
+        let NewConversation {
+            conversation_id,
+            conversation,
+            session_configured,
+        } = match self
+            .conversation_manager
+            .resume_conversation_from_rollout(
+                self.config.as_ref().clone(),
+                rollout_path,
+                self.auth_manager.clone(),
+            )
+            .await
+        {
+            Ok(new_conversation) => new_conversation,
+            Err(e) => {
+                let result = tool_error(format!("Failed to resume Codex session: {e}"));
+                self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+                    .await;
+                return;
+            }
+        };
+        self.sessions.register(&session_configured).await;
+        self.outgoing
+            .send_event_as_notification(
+                &Event {
+                    id: "".to_string(),
+                    msg: EventMsg::SessionConfigured(session_configured),
+                },
+                Some(OutgoingNotificationMeta::new(Some(request_id.clone()))),
+            )
+            .await;
+
+        let Some(prompt) = prompt else {
+            let result = match self.sessions.status(conversation_id).await {
+                Some(status) => tool_json_result(status),
+                None => tool_json_result(json!({ "conversationId": conversation_id })),
+            };
+            self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+                .await;
+            return;
+        };
+
+        let outgoing = self.outgoing.clone();
+        let running_requests_id_to_codex_uuid = self.running_requests_id_to_codex_uuid.clone();
+        let sessions = self.sessions.clone();
+        tokio::spawn(async move {
+            crate::codex_tool_runner::run_codex_tool_session_reply(
+                conversation,
+                outgoing,
+                request_id,
+                prompt,
+                running_requests_id_to_codex_uuid,
+                conversation_id,
+                sessions,
+                progress,
+            )
+            .await;
+        });
+    }
+
+    /// Parses the arguments of a tool that acts on a session started by this
+    /// server.
+    async fn parse_session_arguments(
+        &self,
+        arguments: Option<serde_json::Value>,
+    ) -> Result<ConversationId, CallToolResult> {
+        let Some(arguments) = arguments else {
+            return Err(tool_error(
+                "Missing arguments; the `conversationId` field is required.".to_string(),
+            ));
+        };
+        let CodexSessionParam { conversation_id } = parse_tool_arguments(arguments)?;
+        let conversation_id = ConversationId::from_string(&conversation_id)
+            .map_err(|e| tool_error(format!("Failed to parse conversation_id: {e}")))?;
+        if self.sessions.status(conversation_id).await.is_none() {
+            return Err(tool_error(format!(
+                "Session not found for conversation_id: {conversation_id}"
+            )));
+        }
+        Ok(conversation_id)
+    }
+
     fn handle_set_level(
         &self,
         params: <mcp_types::SetLevelRequest as mcp_types::ModelContextProtocolRequest>::Params,
@@ -560,11 +886,12 @@ impl MessageProcessor {
             RequestId::Integer(i) => i.to_string(),
         };
 
-        // Obtain the conversation id while holding the first lock, then release.
+        // Unregister the id first so the interrupted tool call does not send a
+        // response for the cancelled request.
         let conversation_id = {
-            let map_guard = self.running_requests_id_to_codex_uuid.lock().await;
-            match map_guard.get(&request_id) {
-                Some(id) => *id,
+            let mut map_guard = self.running_requests_id_to_codex_uuid.lock().await;
+            match map_guard.remove(&request_id) {
+                Some(id) => id,
                 None => {
                     tracing::warn!("Session not found for request_id: {}", request_id_string);
                     return;
@@ -595,13 +922,7 @@ impl MessageProcessor {
             .await;
         if let Err(e) = err {
             tracing::error!("Failed to submit interrupt to Codex: {e}");
-            return;
         }
-        // unregister the id so we don't keep it in the map
-        self.running_requests_id_to_codex_uuid
-            .lock()
-            .await
-            .remove(&request_id);
     }
 
     fn handle_progress_notification(
@@ -649,3 +970,36 @@ impl MessageProcessor {
         tracing::info!("notifications/message -> params: {:?}", params);
     }
 }
+
+fn parse_tool_arguments<T: DeserializeOwned>(
+    arguments: serde_json::Value,
+) -> Result<T, CallToolResult> {
+    serde_json::from_value(arguments)
+        .map_err(|e| tool_error(format!("Failed to parse tool arguments: {e}")))
+}
+
+fn tool_error(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::TextContent(TextContent {
+            r#type: "text".to_string(),
+            text,
+            annotations: None,
+        })],
+        is_error: Some(true),
+        structured_content: None,
+    }
+}
+
+/// A successful result carrying `value` both as text and as structured content.
+fn tool_json_result<T: Serialize>(value: T) -> CallToolResult {
+    let value = serde_json::to_value(value).unwrap_or_default();
+    CallToolResult {
+        content: vec![ContentBlock::TextContent(TextContent {
+            r#type: "text".to_string(),
+            text: value.to_string(),
+            annotations: None,
+        })],
+        is_error: None,
+        structured_content: Some(value),
+    }
+}
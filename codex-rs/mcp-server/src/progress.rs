@@ -0,0 +1,155 @@
+//! Streams MCP progress notifications for `tools/call` requests that include a
+//! `progressToken` in their `_meta`.
+
+use std::sync::Arc;
+
+use codex_core::protocol::EventMsg;
+use mcp_types::ModelContextProtocolNotification;
+use mcp_types::ProgressNotification;
+use mcp_types::ProgressNotificationParams;
+use mcp_types::ProgressToken;
+
+use crate::outgoing_message::OutgoingMessageSender;
+use crate::outgoing_message::OutgoingNotification;
+
+/// Returns the `_meta.progressToken` of a request's params, if any.
+pub(crate) fn progress_token(params: Option<&serde_json::Value>) -> Option<ProgressToken> {
+    let token = params?.get("_meta")?.get("progressToken")?;
+    serde_json::from_value(token.clone()).ok()
+}
+
+pub(crate) struct ProgressReporter {
+    outgoing: Arc<OutgoingMessageSender>,
+    token: ProgressToken,
+    progress: f64,
+}
+
+impl ProgressReporter {
+    pub(crate) fn new(outgoing: Arc<OutgoingMessageSender>, token: ProgressToken) -> Self {
+        Self {
+            outgoing,
+            token,
+            progress: 0.0,
+        }
+    }
+
+    /// Sends a progress notification if `msg` is a step worth reporting. The
+    /// total is unknown, so `progress` simply counts the steps so far.
+    pub(crate) async fn report(&mut self, msg: &EventMsg) {
+        let Some(message) = progress_message(msg) else {
+            return;
+        };
+        self.progress += 1.0;
+        let params = ProgressNotificationParams {
+            message: Some(message),
+            progress: self.progress,
+            progress_token: self.token.clone(),
+            total: None,
+        };
+        self.outgoing
+            .send_notification(OutgoingNotification {
+                method: ProgressNotification::METHOD.to_string(),
+                params: serde_json::to_value(params).ok(),
+            })
+            .await;
+    }
+}
+
+fn progress_message(msg: &EventMsg) -> Option<String> {
+    match msg {
+        EventMsg::TaskStarted(_) => Some("Turn started".to_string()),
+        EventMsg::AgentMessage(ev) => Some(ev.message.clone()),
+        EventMsg::ExecCommandBegin(ev) => {
+            let command = shlex::try_join(ev.command.iter().map(String::as_str))
+                .unwrap_or_else(|_| ev.command.join(" "));
+            Some(format!("Running `{command}`"))
+        }
+        EventMsg::PatchApplyBegin(ev) => Some(match ev.changes.len() {
+            1 => "Editing 1 file".to_string(),
+            n => format!("Editing {n} files"),
+        }),
+        EventMsg::McpToolCallBegin(ev) => Some(format!(
+            "Calling {}.{}",
+            ev.invocation.server, ev.invocation.tool
+        )),
+        EventMsg::WebSearchBegin(_) => Some("Searching the web".to_string()),
+        EventMsg::ExecApprovalRequest(_)
+        | EventMsg::ApplyPatchApprovalRequest(_)
+        | EventMsg::ElicitationRequest(_) => Some("Waiting for approval".to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::ExecCommandBeginEvent;
+    use codex_core::protocol::TaskStartedEvent;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::path::PathBuf;
+    use tokio::sync::mpsc;
+
+    use crate::outgoing_message::OutgoingMessage;
+
+    #[test]
+    fn progress_token_is_read_from_meta() {
+        assert_eq!(
+            progress_token(Some(
+                &json!({"name": "codex", "_meta": {"progressToken": 7}})
+            )),
+            Some(ProgressToken::Integer(7))
+        );
+        assert_eq!(progress_token(Some(&json!({"name": "codex"}))), None);
+        assert_eq!(progress_token(None), None);
+    }
+
+    #[tokio::test]
+    async fn reports_steps_with_increasing_progress() {
+        let (tx, mut rx) = mpsc::unbounded_channel::<OutgoingMessage>();
+        let outgoing = Arc::new(OutgoingMessageSender::new(tx));
+        let mut reporter =
+            ProgressReporter::new(outgoing, ProgressToken::String("tok".to_string()));
+
+        reporter
+            .report(&EventMsg::ExecCommandBegin(ExecCommandBeginEvent {
+                call_id: "call-1".to_string(),
+                command: vec!["ls".to_string(), "-la".to_string()],
+                cwd: PathBuf::from("/tmp"),
+                parsed_cmd: Vec::new(),
+            }))
+            .await;
+        reporter.report(&EventMsg::ShutdownComplete).await;
+        reporter
+            .report(&EventMsg::TaskStarted(TaskStartedEvent {
+                model_context_window: None,
+            }))
+            .await;
+
+        let mut notifications = Vec::new();
+        while let Ok(OutgoingMessage::Notification(notification)) = rx.try_recv() {
+            notifications.push(notification);
+        }
+        assert_eq!(
+            notifications,
+            vec![
+                OutgoingNotification {
+                    method: "notifications/progress".to_string(),
+                    params: Some(json!({
+                        "message": "Running `ls -la`",
+                        "progress": 1.0,
+                        "progressToken": "tok",
+                    })),
+                },
+                OutgoingNotification {
+                    method: "notifications/progress".to_string(),
+                    params: Some(json!({
+                        "message": "Turn started",
+                        "progress": 2.0,
+                        "progressToken": "tok",
+                    })),
+                },
+            ]
+        );
+    }
+}
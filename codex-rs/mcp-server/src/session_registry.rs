@@ -0,0 +1,233 @@
+//! Keeps track of the Codex sessions started by this server so the
+//! `codex-list-sessions`, `codex-status` and `codex-get-diff` tools can answer
+//! without interrupting a running turn.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::TokenUsage;
+use codex_protocol::ConversationId;
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum SessionState {
+    Idle,
+    Running,
+    WaitingForApproval,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionStatus {
+    pub conversation_id: ConversationId,
+    pub model: String,
+    pub rollout_path: PathBuf,
+    pub state: SessionState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_agent_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_usage: Option<TokenUsage>,
+    /// Diff of the latest turn; returned by `codex-get-diff` only.
+    #[serde(skip)]
+    pub turn_diff: Option<String>,
+}
+
+impl SessionStatus {
+    fn new(session_configured: &SessionConfiguredEvent) -> Self {
+        Self {
+            conversation_id: session_configured.session_id,
+            model: session_configured.model.clone(),
+            rollout_path: session_configured.rollout_path.clone(),
+            state: SessionState::Idle,
+            last_agent_message: None,
+            token_usage: None,
+            turn_diff: None,
+        }
+    }
+
+    fn apply(&mut self, msg: &EventMsg) {
+        match msg {
+            EventMsg::TaskStarted(_) => {
+                self.state = SessionState::Running;
+                self.turn_diff = None;
+            }
+            EventMsg::TaskComplete(ev) => {
+                self.state = SessionState::Idle;
+                if let Some(message) = &ev.last_agent_message {
+                    self.last_agent_message = Some(message.clone());
+                }
+            }
+            EventMsg::TurnAborted(_) | EventMsg::Error(_) => {
+                self.state = SessionState::Idle;
+            }
+            EventMsg::ExecApprovalRequest(_)
+            | EventMsg::ApplyPatchApprovalRequest(_)
+            | EventMsg::ElicitationRequest(_) => {
+                self.state = SessionState::WaitingForApproval;
+            }
+            EventMsg::AgentMessage(ev) => {
+                self.last_agent_message = Some(ev.message.clone());
+            }
+            EventMsg::TurnDiff(ev) => {
+                self.turn_diff = Some(ev.unified_diff.clone());
+            }
+            EventMsg::TokenCount(ev) => {
+                if let Some(info) = &ev.info {
+                    self.token_usage = Some(info.total_token_usage.clone());
+                }
+            }
+            _ => {}
+        }
+
+        // Any other progress means the pending request has been answered.
+        if self.state == SessionState::WaitingForApproval
+            && !matches!(
+                msg,
+                EventMsg::ExecApprovalRequest(_)
+                    | EventMsg::ApplyPatchApprovalRequest(_)
+                    | EventMsg::ElicitationRequest(_)
+                    | EventMsg::TokenCount(_)
+            )
+        {
+            self.state = SessionState::Running;
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct SessionRegistry {
+    sessions: Mutex<HashMap<ConversationId, SessionStatus>>,
+}
+
+impl SessionRegistry {
+    pub(crate) async fn register(&self, session_configured: &SessionConfiguredEvent) {
+        self.sessions.lock().await.insert(
+            session_configured.session_id,
+            SessionStatus::new(session_configured),
+        );
+    }
+
+    pub(crate) async fn record_event(&self, conversation_id: ConversationId, msg: &EventMsg) {
+        if let Some(status) = self.sessions.lock().await.get_mut(&conversation_id) {
+            status.apply(msg);
+        }
+    }
+
+    pub(crate) async fn status(&self, conversation_id: ConversationId) -> Option<SessionStatus> {
+        self.sessions.lock().await.get(&conversation_id).cloned()
+    }
+
+    pub(crate) async fn list(&self) -> Vec<SessionStatus> {
+        let mut sessions: Vec<SessionStatus> =
+            self.sessions.lock().await.values().cloned().collect();
+        sessions.sort_by_key(|status| status.conversation_id.to_string());
+        sessions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::AgentMessageEvent;
+    use codex_core::protocol::ExecApprovalRequestEvent;
+    use codex_core::protocol::ExecCommandBeginEvent;
+    use codex_core::protocol::TaskCompleteEvent;
+    use codex_core::protocol::TaskStartedEvent;
+    use codex_core::protocol::TurnDiffEvent;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn tracks_turn_state_diff_and_last_message() {
+        let conversation_id = ConversationId::new();
+        let registry = SessionRegistry::default();
+        registry
+            .register(&SessionConfiguredEvent {
+                session_id: conversation_id,
+                model: "gpt-5".to_string(),
+                reasoning_effort: None,
+                history_log_id: 0,
+                history_entry_count: 0,
+                initial_messages: None,
+                rollout_path: PathBuf::from("rollout.jsonl"),
+            })
+            .await;
+
+        let state = |status: Option<SessionStatus>| status.map(|status| status.state);
+        registry
+            .record_event(
+                conversation_id,
+                &EventMsg::TaskStarted(TaskStartedEvent {
+                    model_context_window: None,
+                }),
+            )
+            .await;
+        registry
+            .record_event(
+                conversation_id,
+                &EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+                    call_id: "call-1".to_string(),
+                    command: vec!["touch".to_string(), "a".to_string()],
+                    cwd: PathBuf::from("/tmp"),
+                    reason: None,
+                    parsed_cmd: Vec::new(),
+                }),
+            )
+            .await;
+        assert_eq!(
+            state(registry.status(conversation_id).await),
+            Some(SessionState::WaitingForApproval)
+        );
+
+        registry
+            .record_event(
+                conversation_id,
+                &EventMsg::ExecCommandBegin(ExecCommandBeginEvent {
+                    call_id: "call-1".to_string(),
+                    command: vec!["touch".to_string(), "a".to_string()],
+                    cwd: PathBuf::from("/tmp"),
+                    parsed_cmd: Vec::new(),
+                }),
+            )
+            .await;
+        assert_eq!(
+            state(registry.status(conversation_id).await),
+            Some(SessionState::Running)
+        );
+
+        for msg in [
+            EventMsg::TurnDiff(TurnDiffEvent {
+                unified_diff: "diff --git a/a b/a\n".to_string(),
+            }),
+            EventMsg::AgentMessage(AgentMessageEvent {
+                message: "Created a".to_string(),
+            }),
+            EventMsg::TaskComplete(TaskCompleteEvent {
+                last_agent_message: None,
+            }),
+        ] {
+            registry.record_event(conversation_id, &msg).await;
+        }
+
+        let status = registry
+            .status(conversation_id)
+            .await
+            .expect("session is registered");
+        assert_eq!(
+            (
+                status.state,
+                status.last_agent_message.as_deref(),
+                status.turn_diff.as_deref(),
+            ),
+            (
+                SessionState::Idle,
+                Some("Created a"),
+                Some("diff --git a/a b/a\n"),
+            )
+        );
+        assert_eq!(registry.list().await.len(), 1);
+    }
+}
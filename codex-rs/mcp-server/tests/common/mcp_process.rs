@@ -200,6 +200,25 @@ impl McpProcess {
         .await
     }
 
+    /// Sends a `tools/call` request for `name`. When `progress_token` is set,
+    /// it is passed in `_meta` to ask for progress notifications.
+    pub async fn send_tool_call(
+        &mut self,
+        name: &str,
+        arguments: serde_json::Value,
+        progress_token: Option<i64>,
+    ) -> anyhow::Result<i64> {
+        let mut params = serde_json::to_value(CallToolRequestParams {
+            name: name.to_string(),
+            arguments: Some(arguments),
+        })?;
+        if let Some(progress_token) = progress_token {
+            params["_meta"] = json!({ "progressToken": progress_token });
+        }
+        self.send_request(mcp_types::CallToolRequest::METHOD, Some(params))
+            .await
+    }
+
     async fn send_request(
         &mut self,
         method: &str,
@@ -297,6 +316,35 @@ impl McpProcess {
         }
     }
 
+    /// Reads messages until a notification with `method` is observed.
+    pub async fn read_stream_until_notification(
+        &mut self,
+        method: &str,
+    ) -> anyhow::Result<JSONRPCNotification> {
+        eprintln!("in read_stream_until_notification({method})");
+
+        loop {
+            let message = self.read_jsonrpc_message().await?;
+            match message {
+                JSONRPCMessage::Notification(notification) => {
+                    if notification.method == method {
+                        return Ok(notification);
+                    }
+                    eprintln!("ignoring notification: {notification:?}");
+                }
+                JSONRPCMessage::Request(_) => {
+                    anyhow::bail!("unexpected JSONRPCMessage::Request: {message:?}");
+                }
+                JSONRPCMessage::Error(_) => {
+                    anyhow::bail!("unexpected JSONRPCMessage::Error: {message:?}");
+                }
+                JSONRPCMessage::Response(_) => {
+                    anyhow::bail!("unexpected JSONRPCMessage::Response: {message:?}");
+                }
+            }
+        }
+    }
+
     /// Reads notifications until a legacy TaskComplete event is observed:
     /// Method "codex/event" with params.msg.type == "task_complete".
     pub async fn read_stream_until_legacy_task_complete_notification(
@@ -0,0 +1,150 @@
+use std::path::Path;
+
+use codex_mcp_server::CodexToolCallParam;
+use core_test_support::skip_if_no_network;
+use mcp_test_support::McpProcess;
+use mcp_test_support::create_final_assistant_message_sse_response;
+use mcp_test_support::create_mock_chat_completions_server;
+use mcp_types::RequestId;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(20);
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_session_tools_report_progress_and_status() {
+    skip_if_no_network!();
+
+    if let Err(err) = session_tools_report_progress_and_status().await {
+        panic!("failure: {err}");
+    }
+}
+
+async fn session_tools_report_progress_and_status() -> anyhow::Result<()> {
+    let server =
+        create_mock_chat_completions_server(vec![create_final_assistant_message_sse_response(
+            "All done",
+        )?])
+        .await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+    let mut mcp_process = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp_process.initialize()).await??;
+
+    let codex_request_id = mcp_process
+        .send_tool_call(
+            "codex",
+            serde_json::to_value(CodexToolCallParam {
+                prompt: "Say you are done".to_string(),
+                ..Default::default()
+            })?,
+            Some(42),
+        )
+        .await?;
+
+    let progress = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_notification("notifications/progress"),
+    )
+    .await??;
+    assert_eq!(
+        progress.params,
+        Some(json!({
+            "message": "Turn started",
+            "progress": 1.0,
+            "progressToken": 42,
+        }))
+    );
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Integer(codex_request_id)),
+    )
+    .await??;
+
+    let list_request_id = mcp_process
+        .send_tool_call("codex-list-sessions", json!({}), None)
+        .await?;
+    let list_response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Integer(list_request_id)),
+    )
+    .await??;
+    let sessions = &list_response.result["structuredContent"]["sessions"];
+    assert_eq!(sessions.as_array().map(Vec::len), Some(1));
+    let conversation_id = sessions[0]["conversationId"].clone();
+
+    let status_request_id = mcp_process
+        .send_tool_call(
+            "codex-status",
+            json!({ "conversationId": conversation_id }),
+            None,
+        )
+        .await?;
+    let status_response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Integer(status_request_id)),
+    )
+    .await??;
+    let status = &status_response.result["structuredContent"];
+    assert_eq!(status["state"], json!("idle"));
+    assert_eq!(status["model"], json!("mock-model"));
+    assert_eq!(status["lastAgentMessage"], json!("All done"));
+
+    let diff_request_id = mcp_process
+        .send_tool_call(
+            "codex-get-diff",
+            json!({ "conversationId": conversation_id }),
+            None,
+        )
+        .await?;
+    let diff_response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Integer(diff_request_id)),
+    )
+    .await??;
+    assert_eq!(
+        diff_response.result["structuredContent"],
+        json!({ "conversationId": conversation_id, "diff": null })
+    );
+
+    let missing_request_id = mcp_process
+        .send_tool_call(
+            "codex-interrupt",
+            json!({ "conversationId": "67e55044-10b1-426f-9247-bb680e5fe0c8" }),
+            None,
+        )
+        .await?;
+    let missing_response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Integer(missing_request_id)),
+    )
+    .await??;
+    assert_eq!(missing_response.result["isError"], json!(true));
+
+    Ok(())
+}
+
+fn create_config_toml(codex_home: &Path, server_uri: &str) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_policy = "read-only"
+
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "chat"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
@@ -5,6 +5,8 @@ use serde_json::Value as JsonValue;
 use std::collections::VecDeque;
 use std::io;
 
+use crate::parser::local_models_from_value;
+use crate::parser::model_details_from_value;
 use crate::parser::pull_events_from_value;
 use crate::pull::PullEvent;
 use crate::pull::PullProgressReporter;
@@ -17,6 +19,28 @@ use codex_core::config::Config;
 
 const OLLAMA_CONNECTION_ERROR: &str = "No running Ollama server detected. Start it with: `ollama serve` (after installing). Install instructions: https://github.com/ollama/ollama?tab=readme-ov-file#ollama";
 
+/// A model available in the local Ollama instance, as listed by `/api/tags`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalModel {
+    pub name: String,
+    /// Size on disk in bytes.
+    pub size: Option<u64>,
+    pub modified_at: Option<String>,
+    pub parameter_size: Option<String>,
+    pub quantization_level: Option<String>,
+}
+
+/// Details about a single model, as reported by `/api/show`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelDetails {
+    pub name: String,
+    pub family: Option<String>,
+    pub parameter_size: Option<String>,
+    pub quantization_level: Option<String>,
+    pub context_length: Option<u64>,
+    pub capabilities: Vec<String>,
+}
+
 /// Client for interacting with a local Ollama instance.
 pub struct OllamaClient {
     client: reqwest::Client,
@@ -100,6 +124,13 @@ impl OllamaClient {
 
     /// Return the list of model names known to the local Ollama instance.
     pub async fn fetch_models(&self) -> io::Result<Vec<String>> {
+        let models = self.list_models().await?;
+        Ok(models.into_iter().map(|model| model.name).collect())
+    }
+
+    /// Return the models known to the local Ollama instance along with their
+    /// size and quantization.
+    pub async fn list_models(&self) -> io::Result<Vec<LocalModel>> {
         let tags_url = format!("{}/api/tags", self.host_root.trim_end_matches('/'));
         let resp = self
             .client
@@ -111,17 +142,58 @@ impl OllamaClient {
             return Ok(Vec::new());
         }
         let val = resp.json::<JsonValue>().await.map_err(io::Error::other)?;
-        let names = val
-            .get("models")
-            .and_then(|m| m.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| v.get("name").and_then(|n| n.as_str()))
-                    .map(str::to_string)
-                    .collect::<Vec<_>>()
-            })
-            .unwrap_or_default();
-        Ok(names)
+        Ok(local_models_from_value(&val))
+    }
+
+    /// Return details for a local model, including its context length.
+    pub async fn show_model(&self, model: &str) -> io::Result<ModelDetails> {
+        let url = format!("{}/api/show", self.host_root.trim_end_matches('/'));
+        let resp = self
+            .client
+            .post(url)
+            .json(&serde_json::json!({"model": model}))
+            .send()
+            .await
+            .map_err(io::Error::other)?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("model '{model}' not found"),
+            ));
+        }
+        if !resp.status().is_success() {
+            return Err(io::Error::other(format!(
+                "failed to show model: HTTP {}",
+                resp.status()
+            )));
+        }
+        let val = resp.json::<JsonValue>().await.map_err(io::Error::other)?;
+        Ok(model_details_from_value(model, &val))
+    }
+
+    /// Delete a model from the local Ollama instance.
+    pub async fn delete_model(&self, model: &str) -> io::Result<()> {
+        let url = format!("{}/api/delete", self.host_root.trim_end_matches('/'));
+        let resp = self
+            .client
+            .delete(url)
+            .json(&serde_json::json!({"model": model}))
+            .send()
+            .await
+            .map_err(io::Error::other)?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("model '{model}' not found"),
+            ));
+        }
+        if !resp.status().is_success() {
+            return Err(io::Error::other(format!(
+                "failed to delete model: HTTP {}",
+                resp.status()
+            )));
+        }
+        Ok(())
     }
 
     /// Start a model pull and emit streaming events. The returned stream ends when
@@ -187,7 +259,7 @@ impl OllamaClient {
     pub async fn pull_with_reporter(
         &self,
         model: &str,
-        reporter: &mut dyn PullProgressReporter,
+        reporter: &mut (dyn PullProgressReporter + Send),
     ) -> io::Result<()> {
         reporter.on_event(&PullEvent::Status(format!("Pulling model {model}...")))?;
         let mut stream = self.pull_model_stream(model).await?;
@@ -268,6 +340,57 @@ mod tests {
         assert!(models.contains(&"mistral".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_show_and_delete_model() {
+        if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
+            tracing::info!(
+                "{} is set; skipping test_show_and_delete_model",
+                codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR
+            );
+            return;
+        }
+
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/api/show"))
+            .and(wiremock::matchers::body_json(
+                serde_json::json!({"model": "llama3.2:3b"}),
+            ))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "details": {"family": "llama", "parameter_size": "3.2B"},
+                    "model_info": {"llama.context_length": 131072}
+                })),
+            )
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("DELETE"))
+            .and(wiremock::matchers::path("/api/delete"))
+            .and(wiremock::matchers::body_json(
+                serde_json::json!({"model": "llama3.2:3b"}),
+            ))
+            .respond_with(wiremock::ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let client = OllamaClient::from_host_root(server.uri());
+        let details = client.show_model("llama3.2:3b").await.expect("show model");
+        assert_eq!(details.context_length, Some(131_072));
+        assert_eq!(details.parameter_size.as_deref(), Some("3.2B"));
+        client
+            .delete_model("llama3.2:3b")
+            .await
+            .expect("delete model");
+
+        // Unmatched requests get a 404 from the mock server.
+        let err = client
+            .delete_model("mistral")
+            .await
+            .expect_err("missing model");
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert_eq!(err.to_string(), "model 'mistral' not found");
+    }
+
     #[tokio::test]
     async fn test_probe_server_happy_path_openai_compat_and_native() {
         if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
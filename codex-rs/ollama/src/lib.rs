@@ -3,16 +3,39 @@ mod parser;
 mod pull;
 mod url;
 
+pub use client::LocalModel;
+pub use client::ModelDetails;
 pub use client::OllamaClient;
 use codex_core::config::Config;
 pub use pull::CliProgressReporter;
 pub use pull::PullEvent;
 pub use pull::PullProgressReporter;
-pub use pull::TuiProgressReporter;
 
 /// Default OSS model to use when `--oss` is passed without an explicit `-m`.
 pub const DEFAULT_OSS_MODEL: &str = "gpt-oss:20b";
 
+/// Format a model size in bytes the way `ollama list` does, e.g. `13 GB`.
+pub fn format_model_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1000 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64;
+    let mut unit = UNITS[0];
+    for candidate in UNITS {
+        value /= 1000.0;
+        unit = candidate;
+        if value < 1000.0 {
+            break;
+        }
+    }
+    if value < 10.0 {
+        format!("{value:.1} {unit}")
+    } else {
+        format!("{value:.0} {unit}")
+    }
+}
+
 /// Prepare the local OSS environment when `--oss` is selected.
 ///
 /// - Ensures a local Ollama server is reachable.
@@ -42,3 +65,16 @@ pub async fn ensure_oss_ready(config: &Config) -> std::io::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_model_size() {
+        assert_eq!(format_model_size(512), "512 B");
+        assert_eq!(format_model_size(2_019_393_189), "2.0 GB");
+        assert_eq!(format_model_size(13_780_173_839), "14 GB");
+        assert_eq!(format_model_size(65_000_000_000_000), "65 TB");
+    }
+}
@@ -1,5 +1,7 @@
 use serde_json::Value as JsonValue;
 
+use crate::client::LocalModel;
+use crate::client::ModelDetails;
 use crate::pull::PullEvent;
 
 // Convert a single JSON object representing a pull update into one or more events.
@@ -28,6 +30,69 @@ pub(crate) fn pull_events_from_value(value: &JsonValue) -> Vec<PullEvent> {
     events
 }
 
+/// Parse the `models` array of an `/api/tags` response.
+pub(crate) fn local_models_from_value(value: &JsonValue) -> Vec<LocalModel> {
+    value
+        .get("models")
+        .and_then(|m| m.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| {
+                    let name = v.get("name").and_then(|n| n.as_str())?;
+                    let details = v.get("details");
+                    Some(LocalModel {
+                        name: name.to_string(),
+                        size: v.get("size").and_then(JsonValue::as_u64),
+                        modified_at: string_field(Some(v), "modified_at"),
+                        parameter_size: string_field(details, "parameter_size"),
+                        quantization_level: string_field(details, "quantization_level"),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse an `/api/show` response. The context length lives under an
+/// architecture-specific key such as `llama.context_length`.
+pub(crate) fn model_details_from_value(name: &str, value: &JsonValue) -> ModelDetails {
+    let details = value.get("details");
+    let context_length = value
+        .get("model_info")
+        .and_then(|info| info.as_object())
+        .and_then(|info| {
+            info.iter()
+                .find(|(key, _)| key.ends_with(".context_length"))
+                .and_then(|(_, v)| v.as_u64())
+        });
+    let capabilities = value
+        .get("capabilities")
+        .and_then(|c| c.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|c| c.as_str())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    ModelDetails {
+        name: name.to_string(),
+        family: string_field(details, "family"),
+        parameter_size: string_field(details, "parameter_size"),
+        quantization_level: string_field(details, "quantization_level"),
+        context_length,
+        capabilities,
+    }
+}
+
+fn string_field(value: Option<&JsonValue>, key: &str) -> Option<String> {
+    value
+        .and_then(|v| v.get(key))
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
@@ -72,4 +137,36 @@ mod tests {
             } if digest == "sha256:def" && total.is_none() && completed == &Some(42)
         );
     }
+
+    #[test]
+    fn test_local_models_and_details_decoders() {
+        let tags: JsonValue = serde_json::json!({
+            "models": [
+                {
+                    "name": "gpt-oss:20b",
+                    "size": 13780173839u64,
+                    "modified_at": "2025-08-05T10:00:00Z",
+                    "details": {"parameter_size": "20.9B", "quantization_level": "MXFP4"}
+                },
+                {"model": "missing-name"}
+            ]
+        });
+        let models = local_models_from_value(&tags);
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].name, "gpt-oss:20b");
+        assert_eq!(models[0].size, Some(13_780_173_839));
+        assert_eq!(models[0].parameter_size.as_deref(), Some("20.9B"));
+        assert_eq!(models[0].quantization_level.as_deref(), Some("MXFP4"));
+
+        let show: JsonValue = serde_json::json!({
+            "details": {"family": "gptoss", "parameter_size": "20.9B"},
+            "model_info": {"general.architecture": "gptoss", "gptoss.context_length": 131072},
+            "capabilities": ["completion", "tools"]
+        });
+        let details = model_details_from_value("gpt-oss:20b", &show);
+        assert_eq!(details.family.as_deref(), Some("gptoss"));
+        assert_eq!(details.context_length, Some(131_072));
+        assert_eq!(details.quantization_level, None);
+        assert_eq!(details.capabilities, vec!["completion", "tools"]);
+    }
 }
@@ -134,14 +134,3 @@ impl PullProgressReporter for CliProgressReporter {
         }
     }
 }
-
-/// For now the TUI reporter delegates to the CLI reporter. This keeps UI and
-/// CLI behavior aligned until a dedicated TUI integration is implemented.
-#[derive(Default)]
-pub struct TuiProgressReporter(CliProgressReporter);
-
-impl PullProgressReporter for TuiProgressReporter {
-    fn on_event(&mut self, event: &PullEvent) -> io::Result<()> {
-        self.0.on_event(event)
-    }
-}
@@ -20,6 +20,12 @@ use std::time::Duration;
 use std::time::Instant;
 use strum_macros::Display;
 use tokio::time::error::Elapsed;
+use tracing::Instrument;
+use tracing::Span;
+
+/// Target of the events that only carry metrics. They are picked up by the
+/// metrics layer and should not be exported as log records.
+pub const METRICS_TARGET: &str = "codex_otel::metrics";
 
 #[derive(Debug, Clone, Serialize, Display)]
 #[serde(rename_all = "snake_case")]
@@ -44,6 +50,8 @@ pub struct OtelEventMetadata {
 #[derive(Debug, Clone)]
 pub struct OtelEventManager {
     metadata: OtelEventMetadata,
+    /// Root span of the conversation; turns are recorded as its children.
+    session_span: Span,
 }
 
 impl OtelEventManager {
@@ -58,7 +66,15 @@ impl OtelEventManager {
         log_user_prompts: bool,
         terminal_type: String,
     ) -> OtelEventManager {
+        let session_span = tracing::info_span!(
+            "codex.session",
+            conversation.id = %conversation_id,
+            app.version = %env!("CARGO_PKG_VERSION"),
+            terminal.type = %terminal_type,
+            model = %model,
+        );
         Self {
+            session_span,
             metadata: OtelEventMetadata {
                 conversation_id,
                 auth_mode: auth_mode.map(|m| m.to_string()),
@@ -80,6 +96,46 @@ impl OtelEventManager {
         manager
     }
 
+    /// Span for one turn of the conversation, recorded as a child of the
+    /// session span.
+    pub fn turn_span(&self, sub_id: &str, kind: &str) -> Span {
+        tracing::info_span!(
+            parent: &self.session_span,
+            "codex.turn",
+            conversation.id = %self.metadata.conversation_id,
+            turn.id = %sub_id,
+            turn.kind = %kind,
+            model = %self.metadata.model,
+            slug = %self.metadata.slug,
+        )
+    }
+
+    /// Span for one attempt at a model request, from sending it until the
+    /// response stream ends. Its parent is the current span, usually the turn.
+    pub fn model_request_span(&self, attempt: u64) -> Span {
+        tracing::info_span!(
+            "codex.model_request",
+            conversation.id = %self.metadata.conversation_id,
+            model = %self.metadata.model,
+            slug = %self.metadata.slug,
+            attempt = attempt,
+            http.response.status_code = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            input_token_count = tracing::field::Empty,
+            output_token_count = tracing::field::Empty,
+        )
+    }
+
+    fn tool_call_span(&self, tool_name: &str, call_id: &str) -> Span {
+        tracing::info_span!(
+            "codex.tool_call",
+            conversation.id = %self.metadata.conversation_id,
+            tool_name = %tool_name,
+            call_id = %call_id,
+            success = tracing::field::Empty,
+        )
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn conversation_starts(
         &self,
@@ -133,6 +189,22 @@ impl OtelEventManager {
             Err(error) => (error.status().map(|s| s.as_u16()), Some(error.to_string())),
         };
 
+        let span = Span::current();
+        if let Some(status) = status {
+            span.record("http.response.status_code", status);
+        }
+        if let Some(error) = &error {
+            span.record("error.message", error.as_str());
+        }
+        tracing::event!(
+            target: METRICS_TARGET,
+            tracing::Level::INFO,
+            histogram.codex.api_request.duration_ms = duration_ms(duration),
+            monotonic_counter.codex.api_request.retries = u64::from(attempt > 0),
+            model = %self.metadata.model,
+            http.response.status_code = status,
+        );
+
         tracing::event!(
             tracing::Level::INFO,
             event.name = "codex.api_request",
@@ -287,6 +359,19 @@ impl OtelEventManager {
         reasoning_token_count: Option<u64>,
         tool_token_count: u64,
     ) {
+        let span = Span::current();
+        span.record("input_token_count", input_token_count);
+        span.record("output_token_count", output_token_count);
+        tracing::event!(
+            target: METRICS_TARGET,
+            tracing::Level::INFO,
+            monotonic_counter.codex.tokens.input = input_token_count,
+            monotonic_counter.codex.tokens.output = output_token_count,
+            monotonic_counter.codex.tokens.cached = cached_token_count.unwrap_or_default(),
+            monotonic_counter.codex.tokens.reasoning = reasoning_token_count.unwrap_or_default(),
+            model = %self.metadata.model,
+        );
+
         tracing::event!(
             tracing::Level::INFO,
             event.name = "codex.sse_event",
@@ -347,6 +432,15 @@ impl OtelEventManager {
         decision: ReviewDecision,
         source: ToolDecisionSource,
     ) {
+        tracing::event!(
+            target: METRICS_TARGET,
+            tracing::Level::INFO,
+            monotonic_counter.codex.tool_decision = 1_u64,
+            tool_name = %tool_name,
+            decision = %decision.to_string().to_lowercase(),
+            source = %source.to_string(),
+        );
+
         tracing::event!(
             tracing::Level::INFO,
             event.name = "codex.tool_decision",
@@ -378,8 +472,9 @@ impl OtelEventManager {
         Fut: Future<Output = Result<(String, bool), E>>,
         E: Display,
     {
+        let span = self.tool_call_span(tool_name, call_id);
         let start = Instant::now();
-        let result = f().await;
+        let result = f().instrument(span.clone()).await;
         let duration = start.elapsed();
 
         let (output, success) = match &result {
@@ -388,6 +483,8 @@ impl OtelEventManager {
         };
 
         let success_str = if success { "true" } else { "false" };
+        span.record("success", success);
+        self.tool_duration(tool_name, duration, success);
 
         tracing::event!(
             tracing::Level::INFO,
@@ -443,6 +540,7 @@ impl OtelEventManager {
         output: &str,
     ) {
         let success_str = if success { "true" } else { "false" };
+        self.tool_duration(tool_name, duration, success);
 
         tracing::event!(
             tracing::Level::INFO,
@@ -464,6 +562,20 @@ impl OtelEventManager {
             output = %output,
         );
     }
+
+    fn tool_duration(&self, tool_name: &str, duration: Duration, success: bool) {
+        tracing::event!(
+            target: METRICS_TARGET,
+            tracing::Level::INFO,
+            histogram.codex.tool_call.duration_ms = duration_ms(duration),
+            tool_name = %tool_name,
+            success = success,
+        );
+    }
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
 }
 
 fn timestamp() -> String {
@@ -2,32 +2,57 @@ use crate::config::OtelExporter;
 use crate::config::OtelHttpProtocol;
 use crate::config::OtelSettings;
 use opentelemetry::KeyValue;
+use opentelemetry::trace::TracerProvider as _;
 use opentelemetry_otlp::LogExporter;
+use opentelemetry_otlp::MetricExporter;
 use opentelemetry_otlp::Protocol;
+use opentelemetry_otlp::SpanExporter;
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_otlp::WithHttpConfig;
 use opentelemetry_otlp::WithTonicConfig;
 use opentelemetry_sdk::Resource;
 use opentelemetry_sdk::logs::SdkLoggerProvider;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::trace::SdkTracerProvider;
 use opentelemetry_semantic_conventions as semconv;
 use reqwest::header::HeaderMap;
 use reqwest::header::HeaderName;
 use reqwest::header::HeaderValue;
 use std::error::Error;
 use tonic::metadata::MetadataMap;
+use tracing::Subscriber;
 use tracing::debug;
+use tracing_opentelemetry::MetricsLayer;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::Layer;
+use tracing_subscriber::registry::LookupSpan;
 
 const ENV_ATTRIBUTE: &str = "env";
+const TRACER_NAME: &str = "codex";
 
 pub struct OtelProvider {
     pub logger: SdkLoggerProvider,
+    pub tracer: SdkTracerProvider,
+    pub meter: SdkMeterProvider,
 }
 
 impl OtelProvider {
     pub fn shutdown(&self) {
+        let _ = self.tracer.shutdown();
+        let _ = self.meter.shutdown();
         let _ = self.logger.shutdown();
     }
 
+    /// Layer that exports Codex spans as traces and metric events as OTLP
+    /// metrics. Install it next to the log bridge with the same filter.
+    pub fn tracing_layer<S>(&self) -> impl Layer<S> + Send + Sync + 'static
+    where
+        S: Subscriber + for<'span> LookupSpan<'span> + Send + Sync,
+    {
+        OpenTelemetryLayer::new(self.tracer.tracer(TRACER_NAME))
+            .and_then(MetricsLayer::new(self.meter.clone()))
+    }
+
     pub fn from(settings: &OtelSettings) -> Result<Option<Self>, Box<dyn Error>> {
         let resource = Resource::builder()
             .with_service_name(settings.service_name.clone())
@@ -40,7 +65,9 @@ impl OtelProvider {
             ])
             .build();
 
-        let mut builder = SdkLoggerProvider::builder().with_resource(resource);
+        let mut logger = SdkLoggerProvider::builder().with_resource(resource.clone());
+        let mut tracer = SdkTracerProvider::builder().with_resource(resource.clone());
+        let mut meter = SdkMeterProvider::builder().with_resource(resource);
 
         match &settings.exporter {
             OtelExporter::None => {
@@ -50,22 +77,39 @@ impl OtelProvider {
             OtelExporter::OtlpGrpc { endpoint, headers } => {
                 debug!("Using OTLP Grpc exporter: {}", endpoint);
 
-                let mut header_map = HeaderMap::new();
-                for (key, value) in headers {
-                    if let Ok(name) = HeaderName::from_bytes(key.as_bytes())
-                        && let Ok(val) = HeaderValue::from_str(value)
-                    {
-                        header_map.insert(name, val);
+                let metadata = || {
+                    let mut header_map = HeaderMap::new();
+                    for (key, value) in headers {
+                        if let Ok(name) = HeaderName::from_bytes(key.as_bytes())
+                            && let Ok(val) = HeaderValue::from_str(value)
+                        {
+                            header_map.insert(name, val);
+                        }
                     }
-                }
-
-                let exporter = LogExporter::builder()
-                    .with_tonic()
-                    .with_endpoint(endpoint)
-                    .with_metadata(MetadataMap::from_headers(header_map))
-                    .build()?;
+                    MetadataMap::from_headers(header_map)
+                };
 
-                builder = builder.with_batch_exporter(exporter);
+                logger = logger.with_batch_exporter(
+                    LogExporter::builder()
+                        .with_tonic()
+                        .with_endpoint(endpoint)
+                        .with_metadata(metadata())
+                        .build()?,
+                );
+                tracer = tracer.with_batch_exporter(
+                    SpanExporter::builder()
+                        .with_tonic()
+                        .with_endpoint(endpoint)
+                        .with_metadata(metadata())
+                        .build()?,
+                );
+                meter = meter.with_periodic_exporter(
+                    MetricExporter::builder()
+                        .with_tonic()
+                        .with_endpoint(endpoint)
+                        .with_metadata(metadata())
+                        .build()?,
+                );
             }
             OtelExporter::OtlpHttp {
                 endpoint,
@@ -79,25 +123,71 @@ impl OtelProvider {
                     OtelHttpProtocol::Json => Protocol::HttpJson,
                 };
 
-                let exporter = LogExporter::builder()
-                    .with_http()
-                    .with_endpoint(endpoint)
-                    .with_protocol(protocol)
-                    .with_headers(headers.clone())
-                    .build()?;
-
-                builder = builder.with_batch_exporter(exporter);
+                logger = logger.with_batch_exporter(
+                    LogExporter::builder()
+                        .with_http()
+                        .with_endpoint(endpoint)
+                        .with_protocol(protocol)
+                        .with_headers(headers.clone())
+                        .build()?,
+                );
+                tracer = tracer.with_batch_exporter(
+                    SpanExporter::builder()
+                        .with_http()
+                        .with_endpoint(http_signal_endpoint(endpoint, "traces"))
+                        .with_protocol(protocol)
+                        .with_headers(headers.clone())
+                        .build()?,
+                );
+                meter = meter.with_periodic_exporter(
+                    MetricExporter::builder()
+                        .with_http()
+                        .with_endpoint(http_signal_endpoint(endpoint, "metrics"))
+                        .with_protocol(protocol)
+                        .with_headers(headers.clone())
+                        .build()?,
+                );
             }
         }
 
         Ok(Some(Self {
-            logger: builder.build(),
+            logger: logger.build(),
+            tracer: tracer.build(),
+            meter: meter.build(),
         }))
     }
 }
 
+/// The configured OTLP/HTTP endpoint is the logs endpoint. Traces and metrics
+/// go to the sibling `/v1/traces` and `/v1/metrics` paths.
+fn http_signal_endpoint(logs_endpoint: &str, signal: &str) -> String {
+    let base = logs_endpoint
+        .strip_suffix("/v1/logs")
+        .unwrap_or(logs_endpoint)
+        .trim_end_matches('/');
+    format!("{base}/v1/{signal}")
+}
+
 impl Drop for OtelProvider {
     fn drop(&mut self) {
-        let _ = self.logger.shutdown();
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn http_signal_endpoint_replaces_logs_path() {
+        assert_eq!(
+            http_signal_endpoint("http://localhost:4318/v1/logs", "traces"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            http_signal_endpoint("http://localhost:4318/", "metrics"),
+            "http://localhost:4318/v1/metrics"
+        );
     }
 }
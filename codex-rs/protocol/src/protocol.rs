@@ -21,6 +21,9 @@ use crate::num_format::format_with_separators;
 use crate::parse_command::ParsedCommand;
 use crate::plan_tool::UpdatePlanArgs;
 use mcp_types::CallToolResult;
+use mcp_types::ElicitRequestParamsRequestedSchema;
+use mcp_types::Prompt as McpPrompt;
+use mcp_types::RequestId;
 use mcp_types::Resource as McpResource;
 use mcp_types::ResourceTemplate as McpResourceTemplate;
 use mcp_types::Tool as McpTool;
@@ -145,6 +148,21 @@ pub enum Op {
         decision: ReviewDecision,
     },
 
+    /// Answer an MCP elicitation request surfaced via
+    /// [`EventMsg::ElicitationRequest`].
+    ResolveElicitation {
+        /// Name of the MCP server that issued the request.
+        server_name: String,
+        /// Id of the request, as assigned by the server.
+        request_id: RequestId,
+        /// The user's decision in response to the request.
+        decision: ElicitationAction,
+        /// Values for the requested fields. Only sent to the server when
+        /// `decision` is [`ElicitationAction::Accept`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        content: Option<Value>,
+    },
+
     /// Append an entry to the persistent cross-session message history.
     ///
     /// Note the entry is not guaranteed to be logged if the user has
@@ -165,9 +183,22 @@ pub enum Op {
     /// Reply is delivered via `EventMsg::McpListToolsResponse`.
     ListMcpTools,
 
-    /// Request the list of available custom prompts.
+    /// Request the list of available custom prompts, including the prompts
+    /// exposed by connected MCP servers.
     ListCustomPrompts,
 
+    /// Fetch a prompt from an MCP server and submit its messages as the
+    /// user's input.
+    RunMcpPrompt {
+        /// Name of the MCP server that exposes the prompt.
+        server_name: String,
+        /// Name of the prompt, as listed by the server.
+        prompt_name: String,
+        /// Values for the prompt's declared arguments.
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        arguments: HashMap<String, String>,
+    },
+
     /// Request the agent to summarize the current conversation context.
     /// The agent will use its existing context (either conversation history or previous response id)
     /// to generate a summary which will be returned as an AgentMessage event.
@@ -176,6 +207,22 @@ pub enum Op {
     /// Request a code review from the agent.
     Review { review_request: ReviewRequest },
 
+    /// Capture the current state of the workspace as a checkpoint.
+    /// Reply is delivered via `EventMsg::CheckpointCreated`.
+    Checkpoint,
+
+    /// Request the list of checkpoints recorded for this session.
+    /// Reply is delivered via `EventMsg::ListCheckpointsResponse`.
+    ListCheckpoints,
+
+    /// Restore the workspace to a previously recorded checkpoint.
+    /// Reply is delivered via `EventMsg::CheckpointRestored`.
+    RestoreCheckpoint {
+        /// Id of the checkpoint to restore, as reported by
+        /// [`EventMsg::CheckpointCreated`].
+        checkpoint_id: String,
+    },
+
     /// Request to shut down codex instance.
     Shutdown,
 }
@@ -486,12 +533,20 @@ pub enum EventMsg {
 
     ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent),
 
+    /// An MCP server asked the user for structured input. Answer with
+    /// [`Op::ResolveElicitation`].
+    ElicitationRequest(ElicitationRequestEvent),
+
     BackgroundEvent(BackgroundEventEvent),
 
     /// Notification that a model stream experienced an error or disconnect
     /// and the system is handling it (e.g., retrying with backoff).
     StreamError(StreamErrorEvent),
 
+    /// Estimated spend crossed a soft `[budget]` limit. The session keeps
+    /// running; hard limits are reported with `Error` instead.
+    BudgetWarning(BudgetWarningEvent),
+
     /// Notification that the agent is about to apply a code patch. Mirrors
     /// `ExecCommandBegin` so front‑ends can show progress indicators.
     PatchApplyBegin(PatchApplyBeginEvent),
@@ -507,6 +562,13 @@ pub enum EventMsg {
     /// List of MCP tools available to the agent.
     McpListToolsResponse(McpListToolsResponseEvent),
 
+    /// An MCP server changed the tools, resources, or prompts it exposes.
+    McpListChanged(McpListChangedEvent),
+
+    /// The health of an MCP server changed, e.g. because it crashed and is
+    /// being restarted.
+    McpServerStatus(McpServerStatusEvent),
+
     /// List of custom prompts available to the agent.
     ListCustomPromptsResponse(ListCustomPromptsResponseEvent),
 
@@ -524,6 +586,15 @@ pub enum EventMsg {
 
     /// Exited review mode with an optional final result to apply.
     ExitedReviewMode(ExitedReviewModeEvent),
+
+    /// A workspace checkpoint was recorded.
+    CheckpointCreated(CheckpointCreatedEvent),
+
+    /// List of checkpoints recorded for this session.
+    ListCheckpointsResponse(ListCheckpointsResponseEvent),
+
+    /// The workspace was restored to a checkpoint.
+    CheckpointRestored(CheckpointRestoredEvent),
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
@@ -629,6 +700,20 @@ impl TokenUsageInfo {
 pub struct TokenCountEvent {
     pub info: Option<TokenUsageInfo>,
     pub rate_limits: Option<RateLimitSnapshot>,
+    /// Estimated spend; `None` when no price is known for the model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<CostSummary>,
+}
+
+/// Estimated spend in USD, derived from token usage and model pricing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize, TS)]
+pub struct CostSummary {
+    /// Spend of the current session.
+    pub session_usd: f64,
+    /// Spend recorded for the session's project across all sessions.
+    pub project_usd: f64,
+    /// Spend recorded today across all sessions.
+    pub daily_usd: f64,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
@@ -1034,6 +1119,10 @@ pub struct TurnContextItem {
     pub approval_policy: AskForApproval,
     pub sandbox_policy: SandboxPolicy,
     pub model: String,
+    /// Key of the provider the turn was sent to; changes when the session
+    /// switches to a `model_fallbacks` entry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_provider: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub effort: Option<ReasoningEffortConfig>,
     pub summary: ReasoningSummaryConfig,
@@ -1064,6 +1153,37 @@ pub struct GitInfo {
 pub struct ReviewRequest {
     pub prompt: String,
     pub user_facing_hint: String,
+    /// Changes to review. When set, core computes the diff itself and
+    /// includes it with the prompt instead of asking the model to find it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<ReviewTarget>,
+}
+
+/// The set of changes a review looks at.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReviewTarget {
+    /// Staged, unstaged, and untracked changes in the working tree.
+    UncommittedChanges,
+    /// The changes introduced by a single commit.
+    Commit { sha: String },
+    /// The changes between two revisions, i.e. `git diff base..head`.
+    Range { base: String, head: String },
+    /// The working tree against its merge-base with `branch` (PR style).
+    BaseBranch { branch: String },
+}
+
+impl fmt::Display for ReviewTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReviewTarget::UncommittedChanges => write!(f, "uncommitted changes"),
+            ReviewTarget::Commit { sha } => {
+                write!(f, "commit {}", sha.chars().take(7).collect::<String>())
+            }
+            ReviewTarget::Range { base, head } => write!(f, "{base}..{head}"),
+            ReviewTarget::BaseBranch { branch } => write!(f, "changes against '{branch}'"),
+        }
+    }
 }
 
 /// Structured review result produced by a child review session.
@@ -1096,6 +1216,31 @@ pub struct ReviewFinding {
     pub code_location: ReviewCodeLocation,
 }
 
+impl ReviewFinding {
+    /// Severity implied by the finding's `[P0]`..`[P3]` priority.
+    pub fn severity(&self) -> ReviewSeverity {
+        match self.priority {
+            i32::MIN..=0 => ReviewSeverity::Critical,
+            1 => ReviewSeverity::High,
+            2 => ReviewSeverity::Medium,
+            _ => ReviewSeverity::Low,
+        }
+    }
+}
+
+/// Severity of a review finding, ordered from least to most severe.
+#[derive(
+    Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash, TS, Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ReviewSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
 /// Location of the code related to a review finding.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, TS)]
 pub struct ReviewCodeLocation {
@@ -1196,6 +1341,31 @@ pub struct ApplyPatchApprovalRequestEvent {
     pub grant_root: Option<PathBuf>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct ElicitationRequestEvent {
+    /// Name of the MCP server that issued the request.
+    pub server_name: String,
+    /// Id of the request, echoed back in [`Op::ResolveElicitation`].
+    pub id: RequestId,
+    /// Human-readable explanation of what the server needs.
+    pub message: String,
+    /// Flat JSON schema describing the fields the server is asking for.
+    pub requested_schema: ElicitRequestParamsRequestedSchema,
+}
+
+/// User's decision in response to an ElicitationRequest.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Display, TS)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ElicitationAction {
+    /// User provided the requested values.
+    Accept,
+    /// User chose not to provide the values; the server may continue.
+    Decline,
+    /// User dismissed the request; the server should abort the operation.
+    Cancel,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
 pub struct BackgroundEventEvent {
     pub message: String,
@@ -1206,6 +1376,11 @@ pub struct StreamErrorEvent {
     pub message: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct BudgetWarningEvent {
+    pub message: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
 pub struct StreamInfoEvent {
     pub message: String,
@@ -1231,6 +1406,11 @@ pub struct PatchApplyEndEvent {
     pub stderr: String,
     /// Whether the patch was applied successfully.
     pub success: bool,
+    /// Files the patch changed. Patches are applied atomically, so this is
+    /// empty for a failed patch unless rolling it back also failed, in which
+    /// case it lists the files that were left modified.
+    #[serde(default)]
+    pub committed: Vec<PathBuf>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
@@ -1257,6 +1437,9 @@ pub struct McpListToolsResponseEvent {
     pub resource_templates: std::collections::HashMap<String, Vec<McpResourceTemplate>>,
     /// Authentication status for each configured MCP server.
     pub auth_statuses: std::collections::HashMap<String, McpAuthStatus>,
+    /// Health of each MCP server Codex tried to start.
+    #[serde(default)]
+    pub server_statuses: std::collections::HashMap<String, McpServerStatus>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
@@ -1281,10 +1464,111 @@ impl fmt::Display for McpAuthStatus {
     }
 }
 
+/// Health of an MCP server, as tracked by the supervisor that restarts it
+/// when its transport fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, TS)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum McpServerHealth {
+    Ready,
+    Restarting,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, TS)]
+pub struct McpServerStatus {
+    pub health: McpServerHealth,
+    /// Number of times the server was restarted in this session.
+    pub restarts: u32,
+    /// Why the server is not ready, if it is not.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct McpServerStatusEvent {
+    pub server_name: String,
+    pub status: McpServerStatus,
+}
+
+impl fmt::Display for McpServerStatusEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let server_name = &self.server_name;
+        match self.status.health {
+            McpServerHealth::Ready if self.status.restarts > 0 => {
+                write!(f, "MCP server `{server_name}` restarted")
+            }
+            McpServerHealth::Ready => write!(f, "MCP server `{server_name}` is ready"),
+            McpServerHealth::Restarting => {
+                write!(f, "MCP server `{server_name}` stopped; restarting")
+            }
+            McpServerHealth::Failed => write!(f, "MCP server `{server_name}` failed"),
+        }?;
+        if let Some(error) = &self.status.error {
+            write!(f, ": {error}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct McpListChangedEvent {
+    pub server_name: String,
+    pub kind: McpListKind,
+    /// Number of entries the server now exposes, when Codex re-listed them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+}
+
+/// Kind of list an MCP server can announce as changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, TS)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum McpListKind {
+    Tools,
+    Resources,
+    Prompts,
+}
+
+/// Snapshot of the workspace taken at a point in the conversation.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, TS)]
+pub struct Checkpoint {
+    /// Id of the ghost commit that captures the workspace.
+    pub id: String,
+    /// Commit `HEAD` pointed to when the checkpoint was taken, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// Submission id of the turn that followed the checkpoint.
+    pub turn_id: String,
+    /// Working directory the checkpoint was taken from.
+    pub cwd: PathBuf,
+    /// RFC 3339 timestamp of when the checkpoint was taken.
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct CheckpointCreatedEvent {
+    pub checkpoint: Checkpoint,
+}
+
+/// Response payload for `Op::ListCheckpoints`, oldest first.
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct ListCheckpointsResponseEvent {
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct CheckpointRestoredEvent {
+    pub checkpoint: Checkpoint,
+}
+
 /// Response payload for `Op::ListCustomPrompts`.
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
 pub struct ListCustomPromptsResponseEvent {
     pub custom_prompts: Vec<CustomPrompt>,
+    /// Prompts exposed by MCP servers, keyed by server name.
+    #[serde(default)]
+    pub mcp_prompts: HashMap<String, Vec<McpPrompt>>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, Serialize, TS)]
@@ -1340,6 +1624,20 @@ pub enum ReviewDecision {
 pub enum FileChange {
     Add {
         content: String,
+        /// Unix permission bits for the new file (e.g. `0o755`), if set.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        mode: Option<u32>,
+    },
+    /// A new file whose contents are not UTF-8 text.
+    AddBinary {
+        #[ts(type = "number")]
+        size: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        mode: Option<u32>,
+    },
+    /// A new symbolic link pointing at `target`.
+    AddSymlink {
+        target: PathBuf,
     },
     Delete {
         content: String,
@@ -1347,6 +1645,9 @@ pub enum FileChange {
     Update {
         unified_diff: String,
         move_path: Option<PathBuf>,
+        /// Unix permission bits the file will have after the update, if changed.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        mode: Option<u32>,
     },
 }
 
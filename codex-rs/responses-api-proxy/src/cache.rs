@@ -0,0 +1,327 @@
+//! On-disk cache of upstream responses, keyed by the request body, the
+//! client, and the headers that identify the caller.
+//!
+//! Each entry is stored as `<key>.json` (status and headers) next to
+//! `<key>.body` (the raw response body, usually an SSE stream). Only
+//! successful, fully streamed responses that end in `response.completed`
+//! are stored.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Condvar;
+use std::sync::Mutex;
+
+use anyhow::Context;
+use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use sha1::Digest;
+use sha1::Sha1;
+
+/// Request headers that select the account or API behaviour a request is
+/// served with, so requests that differ in them never share an entry.
+const KEYED_HEADERS: [&str; 5] = [
+    "authorization",
+    "chatgpt-account-id",
+    "openai-beta",
+    "openai-organization",
+    "openai-project",
+];
+
+/// Response headers that describe one particular upstream exchange and are
+/// dropped when an entry is replayed.
+const PER_REQUEST_HEADERS: [&str; 5] = [
+    "cf-ray",
+    "date",
+    "openai-processing-ms",
+    "set-cookie",
+    "x-request-id",
+];
+
+/// Prefixes of the upstream rate-limit headers, which are also specific to
+/// the exchange that produced them.
+const RATE_LIMIT_HEADER_PREFIXES: [&str; 3] =
+    ["x-ratelimit-", "x-codex-primary-", "x-codex-secondary-"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CachedResponse {
+    pub(crate) status: u16,
+    pub(crate) headers: Vec<(String, String)>,
+}
+
+pub(crate) struct ResponseCache {
+    dir: PathBuf,
+    /// Keys whose upstream request is in flight. Identical requests wait for
+    /// the first one to finish instead of going upstream themselves.
+    in_flight: Mutex<HashSet<String>>,
+    finished: Condvar,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(dir: PathBuf) -> Result<Self> {
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("failed to create cache dir {}", dir.display()))?;
+        Ok(Self {
+            dir,
+            in_flight: Mutex::new(HashSet::new()),
+            finished: Condvar::new(),
+        })
+    }
+
+    pub(crate) fn key(client: &str, headers: &[(String, String)], body: &[u8]) -> String {
+        let mut hasher = Sha1::new();
+        hasher.update(client.as_bytes());
+        hasher.update([0]);
+        for name in KEYED_HEADERS {
+            for (_, value) in headers
+                .iter()
+                .filter(|(header, _)| header.eq_ignore_ascii_case(name))
+            {
+                hasher.update(name.as_bytes());
+                hasher.update(b":");
+                hasher.update(value.as_bytes());
+                hasher.update([0]);
+            }
+        }
+        hasher.update(body);
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Returns the stored response for `key`, if any, without the headers
+    /// that only applied to the original exchange. Unreadable entries are
+    /// treated as misses.
+    pub(crate) fn lookup(&self, key: &str) -> Option<(CachedResponse, Vec<u8>)> {
+        let meta = fs::read(self.entry_path(key, "json")).ok()?;
+        let mut meta: CachedResponse = serde_json::from_slice(&meta).ok()?;
+        meta.headers
+            .retain(|(name, _)| !is_per_request_header(name));
+        let body = fs::read(self.entry_path(key, "body")).ok()?;
+        Some((meta, body))
+    }
+
+    pub(crate) fn store(&self, key: &str, response: &CachedResponse, body: &[u8]) -> Result<()> {
+        // The metadata is written last so a reader never sees it without the
+        // matching body.
+        write_atomically(&self.entry_path(key, "body"), body)?;
+        write_atomically(
+            &self.entry_path(key, "json"),
+            &serde_json::to_vec(response)?,
+        )
+    }
+
+    /// Blocks until no other request for `key` is in flight, then claims it
+    /// until the returned guard is dropped.
+    pub(crate) fn claim(self: &Arc<Self>, key: &str) -> CacheClaim {
+        let mut in_flight = self
+            .in_flight
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        while in_flight.contains(key) {
+            in_flight = self
+                .finished
+                .wait(in_flight)
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+        }
+        in_flight.insert(key.to_string());
+        CacheClaim {
+            cache: Arc::clone(self),
+            key: key.to_string(),
+        }
+    }
+
+    fn entry_path(&self, key: &str, extension: &str) -> PathBuf {
+        self.dir.join(format!("{key}.{extension}"))
+    }
+}
+
+/// Marks a cache key as in flight; see [`ResponseCache::claim`].
+pub(crate) struct CacheClaim {
+    cache: Arc<ResponseCache>,
+    key: String,
+}
+
+impl CacheClaim {
+    pub(crate) fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub(crate) fn store(&self, response: &CachedResponse, body: &[u8]) -> Result<()> {
+        self.cache.store(&self.key, response, body)
+    }
+}
+
+impl Drop for CacheClaim {
+    fn drop(&mut self) {
+        let mut in_flight = self
+            .cache
+            .in_flight
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        in_flight.remove(&self.key);
+        self.cache.finished.notify_all();
+    }
+}
+
+fn is_per_request_header(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    PER_REQUEST_HEADERS.contains(&name.as_str())
+        || RATE_LIMIT_HEADER_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+}
+
+/// Whether `body` is a finished response worth replaying: an SSE stream whose
+/// last event is `response.completed`, or a single JSON response with status
+/// `completed`. Streams ending in `response.failed` or `response.incomplete`
+/// arrive with a 2xx status but must not be cached.
+pub(crate) fn is_completed_response(body: &[u8]) -> bool {
+    let body = String::from_utf8_lossy(body);
+    if let Ok(response) = serde_json::from_str::<Value>(&body) {
+        return response.get("status").and_then(Value::as_str) == Some("completed");
+    }
+
+    body.lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .filter_map(|data| serde_json::from_str::<Value>(data.trim()).ok())
+        .filter_map(|event| event.get("type").and_then(Value::as_str).map(str::to_owned))
+        .last()
+        .is_some_and(|kind| kind == "response.completed")
+}
+
+/// Writes `contents` to a temporary file next to `path` and renames it into
+/// place, so readers never see a partially written file.
+pub(crate) fn write_atomically(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".tmp-{}", std::process::id()));
+    let tmp = PathBuf::from(tmp);
+    fs::write(&tmp, contents).with_context(|| format!("failed to write {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn stores_and_looks_up_entries() -> Result<()> {
+        let dir = TempDir::new()?;
+        let cache = ResponseCache::new(dir.path().join("cache"))?;
+        let key = ResponseCache::key("ci", &[], br#"{"model":"gpt-5"}"#);
+        assert_eq!(cache.lookup(&key), None);
+
+        let response = CachedResponse {
+            status: 200,
+            headers: vec![("content-type".to_string(), "text/event-stream".to_string())],
+        };
+        cache.store(&key, &response, b"data: {}\n\n")?;
+        assert_eq!(
+            cache.lookup(&key),
+            Some((response, b"data: {}\n\n".to_vec()))
+        );
+        assert_eq!(cache.lookup(&ResponseCache::key("ci", &[], b"{}")), None);
+        Ok(())
+    }
+
+    #[test]
+    fn only_completed_responses_are_cacheable() {
+        let sse = |events: &[&str]| {
+            events
+                .iter()
+                .map(|kind| format!("event: {kind}\ndata: {{\"type\":\"{kind}\"}}\n\n"))
+                .collect::<String>()
+        };
+
+        assert!(is_completed_response(
+            sse(&["response.created", "response.completed"]).as_bytes()
+        ));
+        assert!(!is_completed_response(
+            sse(&["response.created", "response.failed"]).as_bytes()
+        ));
+        assert!(!is_completed_response(
+            sse(&["response.created", "response.incomplete"]).as_bytes()
+        ));
+        assert!(!is_completed_response(
+            sse(&["response.created", "response.output_text.delta"]).as_bytes()
+        ));
+        assert!(is_completed_response(
+            br#"{"id":"resp_1","status":"completed"}"#
+        ));
+        assert!(!is_completed_response(
+            br#"{"id":"resp_1","status":"failed"}"#
+        ));
+    }
+
+    #[test]
+    fn key_separates_clients_and_credentials() {
+        let body = br#"{"model":"gpt-5"}"#;
+        let auth = |token: &str| vec![("Authorization".to_string(), format!("Bearer {token}"))];
+        let base = ResponseCache::key("ci", &auth("a"), body);
+
+        assert_eq!(base, ResponseCache::key("ci", &auth("a"), body));
+        assert_ne!(base, ResponseCache::key("dev", &auth("a"), body));
+        assert_ne!(base, ResponseCache::key("ci", &auth("b"), body));
+        // Headers that do not identify the caller do not split entries.
+        let mut with_user_agent = auth("a");
+        with_user_agent.push(("user-agent".to_string(), "codex".to_string()));
+        assert_eq!(base, ResponseCache::key("ci", &with_user_agent, body));
+    }
+
+    #[test]
+    fn lookup_drops_per_request_headers() -> Result<()> {
+        let dir = TempDir::new()?;
+        let cache = ResponseCache::new(dir.path().to_path_buf())?;
+        let header = |name: &str, value: &str| (name.to_string(), value.to_string());
+        let content_type = header("content-type", "text/event-stream");
+        cache.store(
+            "abc",
+            &CachedResponse {
+                status: 200,
+                headers: vec![
+                    content_type.clone(),
+                    header("x-request-id", "req_1"),
+                    header("X-RateLimit-Remaining-Requests", "99"),
+                    header("x-codex-primary-used-percent", "12"),
+                ],
+            },
+            b"ok",
+        )?;
+
+        let (cached, _) = cache.lookup("abc").expect("cached entry");
+        assert_eq!(cached.headers, vec![content_type]);
+        Ok(())
+    }
+
+    #[test]
+    fn claim_waits_for_identical_request() -> Result<()> {
+        let dir = TempDir::new()?;
+        let cache = Arc::new(ResponseCache::new(dir.path().to_path_buf())?);
+        let claim = cache.claim("abc");
+
+        let waiter = {
+            let cache = Arc::clone(&cache);
+            std::thread::spawn(move || {
+                let _claim = cache.claim("abc");
+                cache.lookup("abc").is_some()
+            })
+        };
+        std::thread::sleep(Duration::from_millis(50));
+        claim.store(
+            &CachedResponse {
+                status: 200,
+                headers: Vec::new(),
+            },
+            b"ok",
+        )?;
+        drop(claim);
+
+        assert!(waiter.join().expect("waiter thread panicked"));
+        Ok(())
+    }
+}
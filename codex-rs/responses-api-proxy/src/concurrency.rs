@@ -0,0 +1,91 @@
+//! Caps the number of requests forwarded upstream at the same time.
+
+use std::sync::Arc;
+use std::sync::Condvar;
+use std::sync::Mutex;
+
+pub(crate) struct ConcurrencyLimit {
+    max: usize,
+    active: Mutex<usize>,
+    released: Condvar,
+}
+
+impl ConcurrencyLimit {
+    pub(crate) fn new(max: usize) -> Self {
+        Self {
+            max,
+            active: Mutex::new(0),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Blocks until fewer than `max` permits are held, then takes one until
+    /// the returned guard is dropped.
+    pub(crate) fn acquire(self: &Arc<Self>) -> Permit {
+        let mut active = self
+            .active
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        while *active >= self.max {
+            active = self
+                .released
+                .wait(active)
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+        }
+        *active += 1;
+        Permit {
+            limit: Arc::clone(self),
+        }
+    }
+}
+
+pub(crate) struct Permit {
+    limit: Arc<ConcurrencyLimit>,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut active = self
+            .limit
+            .active
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        *active -= 1;
+        self.limit.released.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+
+    #[test]
+    fn never_exceeds_max_permits() {
+        let limit = Arc::new(ConcurrencyLimit::new(2));
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..6)
+            .map(|_| {
+                let limit = Arc::clone(&limit);
+                let running = Arc::clone(&running);
+                let peak = Arc::clone(&peak);
+                std::thread::spawn(move || {
+                    let _permit = limit.acquire();
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(20));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("worker thread panicked");
+        }
+
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+}
@@ -1,12 +1,16 @@
 use std::fs::File;
 use std::fs::{self};
+use std::io::Cursor;
+use std::io::Read;
 use std::io::Write;
 use std::net::SocketAddr;
 use std::net::TcpListener;
+use std::num::NonZeroUsize;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
+use std::time::Instant;
 
 use anyhow::Context;
 use anyhow::Result;
@@ -14,7 +18,6 @@ use anyhow::anyhow;
 use clap::Parser;
 use reqwest::blocking::Client;
 use reqwest::header::AUTHORIZATION;
-use reqwest::header::HOST;
 use reqwest::header::HeaderMap;
 use reqwest::header::HeaderName;
 use reqwest::header::HeaderValue;
@@ -26,8 +29,25 @@ use tiny_http::Response;
 use tiny_http::Server;
 use tiny_http::StatusCode;
 
+mod cache;
+mod concurrency;
 mod read_api_key;
+mod request_log;
+mod usage;
+use cache::CacheClaim;
+use cache::CachedResponse;
+use cache::ResponseCache;
+use cache::is_completed_response;
+use concurrency::ConcurrencyLimit;
+use concurrency::Permit;
 use read_api_key::read_auth_header_from_stdin;
+use request_log::CacheStatus;
+use request_log::LogEntry;
+use request_log::RequestLog;
+use usage::UsageTracker;
+use usage::parse_usage;
+
+const DEFAULT_UPSTREAM_URL: &str = "https://api.openai.com/v1/responses";
 
 /// CLI arguments for the proxy.
 #[derive(Debug, Clone, Parser)]
@@ -44,6 +64,120 @@ pub struct Args {
     /// Enable HTTP shutdown endpoint at GET /shutdown
     #[arg(long)]
     pub http_shutdown: bool,
+
+    /// URL that `POST /v1/responses` requests are forwarded to.
+    #[arg(long, value_name = "URL", default_value = DEFAULT_UPSTREAM_URL)]
+    pub upstream_url: String,
+
+    /// Directory for an on-disk cache of successful responses, keyed by the
+    /// request body, the client, and the caller's credentials. Identical requests that arrive while one is in flight
+    /// wait for it instead of going upstream.
+    #[arg(long, value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Path to a JSON file with per-client request and token totals,
+    /// rewritten after every request. Totals already in the file are kept.
+    #[arg(long, value_name = "FILE")]
+    pub usage_file: Option<PathBuf>,
+
+    /// Request header whose value identifies the client in usage totals and
+    /// the request log. Defaults to the client's IP address.
+    #[arg(long, value_name = "HEADER")]
+    pub client_id_header: Option<String>,
+
+    /// Path to a JSON-lines file that gets one entry per request. Credential
+    /// headers are always redacted.
+    #[arg(long, value_name = "FILE")]
+    pub request_log: Option<PathBuf>,
+
+    /// Additional header to redact in the request log. May be repeated.
+    #[arg(
+        long = "redact-header",
+        value_name = "HEADER",
+        requires = "request_log"
+    )]
+    pub redact_headers: Vec<String>,
+
+    /// Include request and response bodies in the request log.
+    #[arg(long, requires = "request_log")]
+    pub log_bodies: bool,
+
+    /// Maximum number of requests forwarded upstream at once. Additional
+    /// requests wait for a slot.
+    #[arg(long, value_name = "N")]
+    pub max_concurrent_requests: Option<NonZeroUsize>,
+}
+
+/// Shared state for all requests.
+struct Proxy {
+    client: Client,
+    auth_header: &'static str,
+    upstream_url: String,
+    cache: Option<Arc<ResponseCache>>,
+    usage: Option<UsageTracker>,
+    request_log: Option<RequestLog>,
+    concurrency_limit: Option<Arc<ConcurrencyLimit>>,
+    client_id_header: Option<String>,
+}
+
+impl Proxy {
+    fn new(args: &Args, auth_header: &'static str) -> Result<Self> {
+        let client = Client::builder()
+            // Disable reqwest's 30s default so long-lived response streams keep flowing.
+            .timeout(None::<Duration>)
+            .build()
+            .context("building reqwest client")?;
+        let cache = args
+            .cache_dir
+            .clone()
+            .map(ResponseCache::new)
+            .transpose()?
+            .map(Arc::new);
+        let request_log = args
+            .request_log
+            .as_deref()
+            .map(|path| RequestLog::open(path, &args.redact_headers, args.log_bodies))
+            .transpose()?;
+        Ok(Self {
+            client,
+            auth_header,
+            upstream_url: args.upstream_url.clone(),
+            cache,
+            usage: args
+                .usage_file
+                .clone()
+                .map(UsageTracker::load)
+                .transpose()?,
+            request_log,
+            concurrency_limit: args
+                .max_concurrent_requests
+                .map(|max| Arc::new(ConcurrencyLimit::new(max.get()))),
+            client_id_header: args.client_id_header.clone(),
+        })
+    }
+
+    fn client_id(&self, req: &Request) -> String {
+        self.client_id_header
+            .as_deref()
+            .and_then(|name| {
+                req.headers()
+                    .iter()
+                    .find(|header| header.field.as_str().as_str().eq_ignore_ascii_case(name))
+            })
+            .map(|header| header.value.as_str().to_string())
+            .or_else(|| req.remote_addr().map(|addr| addr.ip().to_string()))
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+
+    /// Whether response bodies need to be kept in memory while they stream.
+    fn captures_bodies(&self) -> bool {
+        self.cache.is_some()
+            || self.usage.is_some()
+            || self
+                .request_log
+                .as_ref()
+                .is_some_and(RequestLog::include_bodies)
+    }
 }
 
 #[derive(Serialize)]
@@ -62,26 +196,20 @@ pub fn run_main(args: Args) -> Result<()> {
     }
     let server = Server::from_listener(listener, None)
         .map_err(|err| anyhow!("creating HTTP server: {err}"))?;
-    let client = Arc::new(
-        Client::builder()
-            // Disable reqwest's 30s default so long-lived response streams keep flowing.
-            .timeout(None::<Duration>)
-            .build()
-            .context("building reqwest client")?,
-    );
+    let proxy = Arc::new(Proxy::new(&args, auth_header)?);
 
     eprintln!("responses-api-proxy listening on {bound_addr}");
 
     let http_shutdown = args.http_shutdown;
     for request in server.incoming_requests() {
-        let client = client.clone();
+        let proxy = proxy.clone();
         std::thread::spawn(move || {
             if http_shutdown && request.method() == &Method::Get && request.url() == "/shutdown" {
                 let _ = request.respond(Response::new_empty(StatusCode(200)));
                 std::process::exit(0);
             }
 
-            if let Err(e) = forward_request(&client, auth_header, request) {
+            if let Err(e) = forward_request(&proxy, request) {
                 eprintln!("forwarding error: {e}");
             }
         });
@@ -115,13 +243,40 @@ fn write_server_info(path: &Path, port: u16) -> Result<()> {
     Ok(())
 }
 
-fn forward_request(client: &Client, auth_header: &'static str, mut req: Request) -> Result<()> {
+fn forward_request(proxy: &Arc<Proxy>, mut req: Request) -> Result<()> {
     // Only allow POST /v1/responses exactly, no query string.
     let method = req.method().clone();
     let url_path = req.url().to_string();
     let allow = method == Method::Post && url_path == "/v1/responses";
 
+    let mut exchange = Exchange {
+        proxy: Arc::clone(proxy),
+        client: proxy.client_id(&req),
+        method: method.to_string(),
+        path: url_path,
+        started: Instant::now(),
+        request_headers: req
+            .headers()
+            .iter()
+            .map(|header| {
+                (
+                    header.field.as_str().as_str().to_string(),
+                    header.value.as_str().to_string(),
+                )
+            })
+            .collect(),
+        request_body: None,
+        cache: CacheStatus::Disabled,
+        claim: None,
+        permit: None,
+        status: None,
+        response_headers: Vec::new(),
+        error: None,
+    };
+
     if !allow {
+        exchange.status = Some(403);
+        exchange.finish(Vec::new(), true);
         let resp = Response::new_empty(StatusCode(403));
         let _ = req.respond(resp);
         return Ok(());
@@ -131,6 +286,49 @@ fn forward_request(client: &Client, auth_header: &'static str, mut req: Request)
     let mut body = Vec::new();
     let mut reader = req.as_reader();
     std::io::Read::read_to_end(&mut reader, &mut body)?;
+    if proxy
+        .request_log
+        .as_ref()
+        .is_some_and(RequestLog::include_bodies)
+    {
+        exchange.request_body = Some(String::from_utf8_lossy(&body).into_owned());
+    }
+
+    if let Some(cache) = proxy.cache.as_ref() {
+        // Claiming the key first means an identical request that is still
+        // streaming gets to fill the cache before we look.
+        let key = ResponseCache::key(&exchange.client, &exchange.request_headers, &body);
+        let claim = cache.claim(&key);
+        if let Some((cached, cached_body)) = cache.lookup(claim.key()) {
+            drop(claim);
+            exchange.cache = CacheStatus::Hit;
+            exchange.status = Some(cached.status);
+            exchange.response_headers = cached.headers.clone();
+            let headers = cached
+                .headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    Header::from_bytes(name.as_bytes(), value.as_bytes()).ok()
+                })
+                .collect();
+            let len = cached_body.len();
+            let response = Response::new(
+                StatusCode(cached.status),
+                headers,
+                ResponseTap::new(Cursor::new(cached_body), Some(len), exchange),
+                Some(len),
+                None,
+            );
+            let _ = req.respond(response);
+            return Ok(());
+        }
+        exchange.cache = CacheStatus::Miss;
+        exchange.claim = Some(claim);
+    }
+    exchange.permit = proxy
+        .concurrency_limit
+        .as_ref()
+        .map(ConcurrencyLimit::acquire);
 
     // Build headers for upstream, forwarding everything from the incoming
     // request except Authorization (we replace it below).
@@ -153,24 +351,31 @@ fn forward_request(client: &Client, auth_header: &'static str, mut req: Request)
 
     // As part of our effort to to keep `auth_header` secret, we use a
     // combination of `from_static()` and `set_sensitive(true)`.
-    let mut auth_header_value = HeaderValue::from_static(auth_header);
+    let mut auth_header_value = HeaderValue::from_static(proxy.auth_header);
     auth_header_value.set_sensitive(true);
     headers.insert(AUTHORIZATION, auth_header_value);
 
-    headers.insert(HOST, HeaderValue::from_static("api.openai.com"));
-
-    let upstream = "https://api.openai.com/v1/responses";
-    let upstream_resp = client
-        .post(upstream)
+    // `Host` is derived from the upstream URL.
+    let upstream_resp = match proxy
+        .client
+        .post(&proxy.upstream_url)
         .headers(headers)
         .body(body)
         .send()
-        .context("forwarding request to upstream")?;
+        .context("forwarding request to upstream")
+    {
+        Ok(resp) => resp,
+        Err(err) => {
+            exchange.error = Some(format!("{err:#}"));
+            exchange.finish(Vec::new(), false);
+            return Err(err);
+        }
+    };
 
     // We have to create an adapter between a `reqwest::blocking::Response`
     // and a `tiny_http::Response`. Fortunately, `reqwest::blocking::Response`
-    // implements `Read`, so we can use it directly as the body of the
-    // `tiny_http::Response`.
+    // implements `Read`, so we can use it directly (wrapped in a
+    // `ResponseTap`) as the body of the `tiny_http::Response`.
     let status = upstream_resp.status();
     let mut response_headers = Vec::new();
     for (name, value) in upstream_resp.headers().iter() {
@@ -185,7 +390,13 @@ fn forward_request(client: &Client, auth_header: &'static str, mut req: Request)
         if let Ok(header) = Header::from_bytes(name.as_str().as_bytes(), value.as_bytes()) {
             response_headers.push(header);
         }
+        if let Ok(value) = value.to_str() {
+            exchange
+                .response_headers
+                .push((name.as_str().to_string(), value.to_string()));
+        }
     }
+    exchange.status = Some(status.as_u16());
 
     let content_length = upstream_resp.content_length().and_then(|len| {
         if len <= usize::MAX as u64 {
@@ -198,7 +409,7 @@ fn forward_request(client: &Client, auth_header: &'static str, mut req: Request)
     let response = Response::new(
         StatusCode(status.as_u16()),
         response_headers,
-        upstream_resp,
+        ResponseTap::new(upstream_resp, content_length, exchange),
         content_length,
         None,
     );
@@ -206,3 +417,140 @@ fn forward_request(client: &Client, auth_header: &'static str, mut req: Request)
     let _ = req.respond(response);
     Ok(())
 }
+
+/// Everything known about one request, settled by [`Exchange::finish`] once
+/// the response body has been sent (or abandoned).
+struct Exchange {
+    proxy: Arc<Proxy>,
+    client: String,
+    method: String,
+    path: String,
+    started: Instant,
+    request_headers: Vec<(String, String)>,
+    request_body: Option<String>,
+    cache: CacheStatus,
+    /// Held until the response is stored so identical requests wait for it.
+    claim: Option<CacheClaim>,
+    /// Held until the upstream response has been fully relayed.
+    permit: Option<Permit>,
+    status: Option<u16>,
+    response_headers: Vec<(String, String)>,
+    error: Option<String>,
+}
+
+impl Exchange {
+    fn finish(self, body: Vec<u8>, complete: bool) {
+        let succeeded = complete
+            && self.error.is_none()
+            && self
+                .status
+                .is_some_and(|status| (200..300).contains(&status));
+        let usage = parse_usage(&body);
+
+        if succeeded
+            && is_completed_response(&body)
+            && let (Some(claim), Some(status)) = (self.claim.as_ref(), self.status)
+        {
+            let cached = CachedResponse {
+                status,
+                headers: self.response_headers.clone(),
+            };
+            if let Err(err) = claim.store(&cached, &body) {
+                eprintln!("failed to cache response: {err:#}");
+            }
+        }
+
+        if let Some(tracker) = self.proxy.usage.as_ref()
+            && let Err(err) = tracker.record(
+                &self.client,
+                usage,
+                self.cache == CacheStatus::Hit,
+                succeeded,
+            )
+        {
+            eprintln!("failed to write usage file: {err:#}");
+        }
+
+        if let Some(log) = self.proxy.request_log.as_ref() {
+            let request_headers = log.redact_headers(
+                self.request_headers
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_str())),
+            );
+            let response_headers = log.redact_headers(
+                self.response_headers
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_str())),
+            );
+            let response_body = (log.include_bodies() && self.status.is_some())
+                .then(|| String::from_utf8_lossy(&body).into_owned());
+            let entry = LogEntry {
+                client: self.client,
+                method: self.method,
+                path: self.path,
+                status: self.status,
+                cache: self.cache,
+                complete,
+                duration_ms: self.started.elapsed().as_millis() as u64,
+                request_headers,
+                response_headers,
+                usage,
+                error: self.error,
+                request_body: self.request_body,
+                response_body,
+            };
+            if let Err(err) = log.write(&entry) {
+                eprintln!("failed to write request log: {err:#}");
+            }
+        }
+    }
+}
+
+/// Relays a response body to the client while keeping a copy for the cache,
+/// usage accounting and request log. The exchange is finished when the tap
+/// is dropped, i.e. after tiny_http has written the response.
+struct ResponseTap<R> {
+    inner: R,
+    body: Vec<u8>,
+    capture: bool,
+    bytes_read: usize,
+    expected_len: Option<usize>,
+    eof: bool,
+    exchange: Option<Exchange>,
+}
+
+impl<R> ResponseTap<R> {
+    fn new(inner: R, expected_len: Option<usize>, exchange: Exchange) -> Self {
+        Self {
+            inner,
+            body: Vec::new(),
+            capture: exchange.proxy.captures_bodies(),
+            bytes_read: 0,
+            expected_len,
+            eof: false,
+            exchange: Some(exchange),
+        }
+    }
+}
+
+impl<R: Read> Read for ResponseTap<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n == 0 {
+            self.eof = true;
+        } else if self.capture {
+            self.body.extend_from_slice(&buf[..n]);
+        }
+        self.bytes_read += n;
+        Ok(n)
+    }
+}
+
+impl<R> Drop for ResponseTap<R> {
+    fn drop(&mut self) {
+        if let Some(exchange) = self.exchange.take() {
+            let complete = self.eof || Some(self.bytes_read) == self.expected_len;
+            exchange.finish(std::mem::take(&mut self.body), complete);
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! JSON-lines log of proxied requests with credentials redacted.
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::Context;
+use anyhow::Result;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::usage::TokenUsage;
+
+/// Headers whose values never appear in the log.
+const ALWAYS_REDACTED_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "api-key",
+    "x-api-key",
+    "openai-organization",
+    "openai-project",
+];
+
+const REDACTED: &str = "[REDACTED]";
+
+pub(crate) struct RequestLog {
+    file: Mutex<File>,
+    redacted_headers: Vec<String>,
+    include_bodies: bool,
+}
+
+/// How the response cache handled a request.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CacheStatus {
+    Hit,
+    Miss,
+    Disabled,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct LogEntry {
+    pub(crate) client: String,
+    pub(crate) method: String,
+    pub(crate) path: String,
+    /// `None` when no response was produced, e.g. the upstream was
+    /// unreachable.
+    pub(crate) status: Option<u16>,
+    pub(crate) cache: CacheStatus,
+    /// False when the client disconnected before the body was fully sent.
+    pub(crate) complete: bool,
+    pub(crate) duration_ms: u64,
+    pub(crate) request_headers: Vec<(String, String)>,
+    pub(crate) response_headers: Vec<(String, String)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) usage: Option<TokenUsage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) request_body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) response_body: Option<String>,
+}
+
+#[derive(Serialize)]
+struct TimestampedEntry<'a> {
+    timestamp: String,
+    #[serde(flatten)]
+    entry: &'a LogEntry,
+}
+
+impl RequestLog {
+    /// Opens `path` for appending. `extra_redacted_headers` are redacted in
+    /// addition to the credential headers in [`ALWAYS_REDACTED_HEADERS`].
+    pub(crate) fn open(
+        path: &Path,
+        extra_redacted_headers: &[String],
+        include_bodies: bool,
+    ) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("failed to open request log {}", path.display()))?;
+        let redacted_headers = ALWAYS_REDACTED_HEADERS
+            .iter()
+            .map(|name| (*name).to_string())
+            .chain(
+                extra_redacted_headers
+                    .iter()
+                    .map(|name| name.to_ascii_lowercase()),
+            )
+            .collect();
+        Ok(Self {
+            file: Mutex::new(file),
+            redacted_headers,
+            include_bodies,
+        })
+    }
+
+    pub(crate) fn include_bodies(&self) -> bool {
+        self.include_bodies
+    }
+
+    /// Returns `headers` with the values of sensitive headers replaced.
+    pub(crate) fn redact_headers<'a>(
+        &self,
+        headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Vec<(String, String)> {
+        headers
+            .into_iter()
+            .map(|(name, value)| {
+                let name = name.to_ascii_lowercase();
+                let value = if self.redacted_headers.contains(&name) {
+                    REDACTED.to_string()
+                } else {
+                    value.to_string()
+                };
+                (name, value)
+            })
+            .collect()
+    }
+
+    pub(crate) fn write(&self, entry: &LogEntry) -> Result<()> {
+        let mut line = serde_json::to_string(&TimestampedEntry {
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            entry,
+        })?;
+        line.push('\n');
+        let mut file = self
+            .file
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn redacts_credentials_and_configured_headers() -> Result<()> {
+        let dir = TempDir::new()?;
+        let log = RequestLog::open(
+            &dir.path().join("requests.jsonl"),
+            &["X-Session-Token".to_string()],
+            false,
+        )?;
+        assert_eq!(
+            log.redact_headers([
+                ("Authorization", "Bearer sk-secret"),
+                ("x-session-token", "abc"),
+                ("Content-Type", "application/json"),
+            ]),
+            vec![
+                ("authorization".to_string(), REDACTED.to_string()),
+                ("x-session-token".to_string(), REDACTED.to_string()),
+                ("content-type".to_string(), "application/json".to_string()),
+            ]
+        );
+        Ok(())
+    }
+}
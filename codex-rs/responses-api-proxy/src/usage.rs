@@ -0,0 +1,203 @@
+//! Per-client request and token accounting.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::Context;
+use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::cache::write_atomically;
+
+/// Token usage reported by the upstream for one response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TokenUsage {
+    pub(crate) input_tokens: u64,
+    pub(crate) cached_input_tokens: u64,
+    pub(crate) output_tokens: u64,
+    pub(crate) total_tokens: u64,
+}
+
+/// Running totals for one client.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ClientUsage {
+    pub(crate) requests: u64,
+    /// Requests answered from the response cache. Their tokens are not
+    /// counted again.
+    pub(crate) cache_hits: u64,
+    /// Requests that ended without a successful, complete response.
+    pub(crate) failed: u64,
+    #[serde(flatten)]
+    pub(crate) tokens: TokenUsage,
+}
+
+/// Accumulates [`ClientUsage`] and rewrites `path` as a JSON object keyed by
+/// client after every request.
+pub(crate) struct UsageTracker {
+    path: PathBuf,
+    clients: Mutex<BTreeMap<String, ClientUsage>>,
+}
+
+impl UsageTracker {
+    /// Starts from the totals already stored at `path`, if the file exists.
+    pub(crate) fn load(path: PathBuf) -> Result<Self> {
+        let clients = match std::fs::read(&path) {
+            Ok(data) => serde_json::from_slice(&data)
+                .with_context(|| format!("failed to parse usage file {}", path.display()))?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("failed to read usage file {}", path.display()));
+            }
+        };
+        Ok(Self {
+            path,
+            clients: Mutex::new(clients),
+        })
+    }
+
+    pub(crate) fn record(
+        &self,
+        client: &str,
+        usage: Option<TokenUsage>,
+        cache_hit: bool,
+        succeeded: bool,
+    ) -> Result<()> {
+        let mut clients = self
+            .clients
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let totals = clients.entry(client.to_string()).or_default();
+        totals.requests += 1;
+        if cache_hit {
+            totals.cache_hits += 1;
+        } else if let Some(usage) = usage {
+            totals.tokens.input_tokens += usage.input_tokens;
+            totals.tokens.cached_input_tokens += usage.cached_input_tokens;
+            totals.tokens.output_tokens += usage.output_tokens;
+            totals.tokens.total_tokens += usage.total_tokens;
+        }
+        if !succeeded {
+            totals.failed += 1;
+        }
+
+        let mut data = serde_json::to_string_pretty(&*clients)?;
+        data.push('\n');
+        write_atomically(&self.path, data.as_bytes())
+    }
+}
+
+/// Extracts the `usage` of a Responses API body, which is either an SSE
+/// stream ending in a `response.completed` event or a single JSON response.
+pub(crate) fn parse_usage(body: &[u8]) -> Option<TokenUsage> {
+    let body = String::from_utf8_lossy(body);
+    if let Ok(response) = serde_json::from_str::<Value>(&body) {
+        return usage_from_response(&response);
+    }
+
+    body.lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .filter_map(|data| serde_json::from_str::<Value>(data.trim()).ok())
+        .filter(|event| event.get("type").and_then(Value::as_str) == Some("response.completed"))
+        .find_map(|event| event.get("response").and_then(usage_from_response))
+}
+
+fn usage_from_response(response: &Value) -> Option<TokenUsage> {
+    let usage = response.get("usage")?;
+    let count = |value: Option<&Value>| value.and_then(Value::as_u64).unwrap_or_default();
+    Some(TokenUsage {
+        input_tokens: count(usage.get("input_tokens")),
+        cached_input_tokens: count(usage.pointer("/input_tokens_details/cached_tokens")),
+        output_tokens: count(usage.get("output_tokens")),
+        total_tokens: count(usage.get("total_tokens")),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn parses_usage_from_sse_and_json_bodies() {
+        let sse = concat!(
+            "event: response.created\n",
+            "data: {\"type\":\"response.created\",\"response\":{}}\n\n",
+            "event: response.completed\n",
+            "data: {\"type\":\"response.completed\",\"response\":{\"usage\":",
+            "{\"input_tokens\":10,\"input_tokens_details\":{\"cached_tokens\":4},",
+            "\"output_tokens\":5,\"total_tokens\":15}}}\n\n",
+        );
+        let expected = TokenUsage {
+            input_tokens: 10,
+            cached_input_tokens: 4,
+            output_tokens: 5,
+            total_tokens: 15,
+        };
+        assert_eq!(parse_usage(sse.as_bytes()), Some(expected));
+
+        let json =
+            br#"{"id":"resp_1","usage":{"input_tokens":10,"output_tokens":5,"total_tokens":15}}"#;
+        assert_eq!(
+            parse_usage(json),
+            Some(TokenUsage {
+                cached_input_tokens: 0,
+                ..expected
+            })
+        );
+        assert_eq!(parse_usage(b"data: [DONE]\n\n"), None);
+    }
+
+    #[test]
+    fn tracker_accumulates_per_client() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("usage.json");
+        let tracker = UsageTracker::load(path.clone())?;
+        let usage = TokenUsage {
+            input_tokens: 10,
+            cached_input_tokens: 0,
+            output_tokens: 5,
+            total_tokens: 15,
+        };
+        tracker.record("ci", Some(usage), false, true)?;
+        tracker.record("ci", Some(usage), true, true)?;
+        tracker.record("dev", None, false, false)?;
+
+        let written: BTreeMap<String, ClientUsage> = serde_json::from_slice(&std::fs::read(path)?)?;
+        assert_eq!(
+            written,
+            BTreeMap::from([
+                (
+                    "ci".to_string(),
+                    ClientUsage {
+                        requests: 2,
+                        cache_hits: 1,
+                        failed: 0,
+                        tokens: usage,
+                    },
+                ),
+                (
+                    "dev".to_string(),
+                    ClientUsage {
+                        requests: 1,
+                        cache_hits: 0,
+                        failed: 1,
+                        tokens: TokenUsage::default(),
+                    },
+                ),
+            ])
+        );
+
+        // A restarted proxy keeps counting from the stored totals.
+        let tracker = UsageTracker::load(path.clone())?;
+        tracker.record("dev", None, false, true)?;
+        let written: BTreeMap<String, ClientUsage> = serde_json::from_slice(&std::fs::read(path)?)?;
+        assert_eq!(written["ci"].requests, 2);
+        assert_eq!(written["dev"].requests, 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+#![allow(clippy::expect_used)]
+
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::process::Child;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use tempfile::TempDir;
+use tiny_http::Header;
+use tiny_http::Response;
+use tiny_http::Server;
+
+const SSE_BODY: &str = concat!(
+    "event: response.completed\n",
+    "data: {\"type\":\"response.completed\",\"response\":{\"id\":\"resp_1\",\"usage\":",
+    "{\"input_tokens\":12,\"output_tokens\":3,\"total_tokens\":15}}}\n\n",
+);
+
+/// Upstream stub that answers every request with [`SSE_BODY`] and remembers
+/// the `Authorization` header of each request it saw.
+fn start_upstream() -> (String, Arc<Mutex<Vec<String>>>) {
+    let server = Server::http("127.0.0.1:0").expect("bind upstream stub");
+    let port = server
+        .server_addr()
+        .to_ip()
+        .expect("upstream stub listens on TCP")
+        .port();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_by_server = Arc::clone(&seen);
+    std::thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let auth = request
+                .headers()
+                .iter()
+                .find(|header| header.field.equiv("authorization"))
+                .map(|header| header.value.to_string())
+                .unwrap_or_default();
+            seen_by_server.lock().expect("lock").push(auth);
+            let content_type =
+                Header::from_bytes("content-type", "text/event-stream").expect("valid header");
+            let _ = request.respond(Response::from_string(SSE_BODY).with_header(content_type));
+        }
+    });
+    (format!("http://127.0.0.1:{port}/v1/responses"), seen)
+}
+
+fn wait_for<T>(mut poll: impl FnMut() -> Option<T>) -> T {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        if let Some(value) = poll() {
+            return value;
+        }
+        assert!(Instant::now() < deadline, "timed out waiting for proxy");
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
+
+fn spawn_proxy(dir: &Path, upstream_url: &str) -> (Child, String) {
+    let server_info = dir.join("server-info.json");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_codex-responses-api-proxy"))
+        .arg("--http-shutdown")
+        .arg("--server-info")
+        .arg(&server_info)
+        .args(["--upstream-url", upstream_url])
+        .arg("--cache-dir")
+        .arg(dir.join("cache"))
+        .arg("--usage-file")
+        .arg(dir.join("usage.json"))
+        .args(["--client-id-header", "x-client-id"])
+        .arg("--request-log")
+        .arg(dir.join("requests.jsonl"))
+        .arg("--log-bodies")
+        .args(["--max-concurrent-requests", "2"])
+        .stdin(Stdio::piped())
+        .spawn()
+        .expect("spawn proxy");
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(b"sk-upstream")
+        .expect("write api key");
+
+    let port = wait_for(|| {
+        let info = std::fs::read_to_string(&server_info).ok()?;
+        let info: Value = serde_json::from_str(&info).ok()?;
+        info["port"].as_u64()
+    });
+    (child, format!("http://127.0.0.1:{port}"))
+}
+
+#[test]
+fn caches_identical_requests_and_accounts_usage() {
+    let dir = TempDir::new().expect("tempdir");
+    let (upstream_url, upstream_auth) = start_upstream();
+    let (mut child, proxy_url) = spawn_proxy(dir.path(), &upstream_url);
+
+    let client = reqwest::blocking::Client::new();
+    let request_body = json!({"model": "gpt-5", "input": "hi", "stream": true});
+    for _ in 0..2 {
+        let mut response = client
+            .post(format!("{proxy_url}/v1/responses"))
+            .header("authorization", "Bearer client-secret")
+            .header("x-client-id", "ci")
+            .json(&request_body)
+            .send()
+            .expect("request through proxy");
+        assert_eq!(response.status().as_u16(), 200);
+        let mut body = String::new();
+        response.read_to_string(&mut body).expect("read body");
+        assert_eq!(body, SSE_BODY);
+    }
+
+    // The second request was answered from the cache.
+    assert_eq!(
+        *upstream_auth.lock().expect("lock"),
+        vec!["Bearer sk-upstream".to_string()]
+    );
+
+    let usage = wait_for(|| {
+        let usage = std::fs::read_to_string(dir.path().join("usage.json")).ok()?;
+        let usage: Value = serde_json::from_str(&usage).ok()?;
+        (usage["ci"]["requests"] == 2).then_some(usage)
+    });
+    assert_eq!(
+        usage,
+        json!({
+            "ci": {
+                "requests": 2,
+                "cache_hits": 1,
+                "failed": 0,
+                "input_tokens": 12,
+                "cached_input_tokens": 0,
+                "output_tokens": 3,
+                "total_tokens": 15,
+            }
+        })
+    );
+
+    let log = wait_for(|| {
+        let log = std::fs::read_to_string(dir.path().join("requests.jsonl")).ok()?;
+        // A line may still be in the middle of being written.
+        let entries: Vec<Value> = log
+            .lines()
+            .map(|line| serde_json::from_str(line).ok())
+            .collect::<Option<_>>()?;
+        (entries.len() == 2).then_some(entries)
+    });
+    let caches: Vec<&str> = log
+        .iter()
+        .map(|entry| entry["cache"].as_str().unwrap_or_default())
+        .collect();
+    assert_eq!(caches, vec!["miss", "hit"]);
+    for entry in &log {
+        assert_eq!(entry["client"], "ci");
+        assert_eq!(entry["status"], 200);
+        assert_eq!(entry["response_body"], SSE_BODY);
+        let auth = entry["request_headers"]
+            .as_array()
+            .expect("headers array")
+            .iter()
+            .find(|header| header[0] == "authorization")
+            .expect("authorization header is logged");
+        assert_eq!(auth[1], "[REDACTED]");
+    }
+
+    let _ = client.get(format!("{proxy_url}/shutdown")).send();
+    let status = child.wait().expect("wait for proxy");
+    assert!(status.success());
+}
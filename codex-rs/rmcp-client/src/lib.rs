@@ -9,6 +9,9 @@ mod utils;
 pub use auth_status::determine_streamable_http_auth_status;
 pub use auth_status::supports_oauth_login;
 pub use codex_protocol::protocol::McpAuthStatus;
+pub use oauth::CredentialStoreError;
+pub use oauth::DefaultKeyringStore;
+pub use oauth::KeyringStore;
 pub use oauth::OAuthCredentialsStoreMode;
 pub use oauth::StoredOAuthTokens;
 pub use oauth::WrappedOAuthTokenResponse;
@@ -16,4 +19,8 @@ pub use oauth::delete_oauth_tokens;
 pub(crate) use oauth::load_oauth_tokens;
 pub use oauth::save_oauth_tokens;
 pub use perform_oauth_login::perform_oauth_login;
+pub use rmcp_client::CreateMessage;
+pub use rmcp_client::ListChangedKind;
+pub use rmcp_client::OnListChanged;
 pub use rmcp_client::RmcpClient;
+pub use rmcp_client::SendElicitation;
@@ -1,10 +1,21 @@
+use std::sync::Arc;
+
+use mcp_types::CreateMessageRequestParams;
+use mcp_types::CreateMessageResult;
+use mcp_types::ElicitRequestParams;
+use mcp_types::ElicitResult;
+use mcp_types::RequestId;
 use rmcp::ClientHandler;
 use rmcp::RoleClient;
 use rmcp::model::CancelledNotificationParam;
 use rmcp::model::ClientInfo;
 use rmcp::model::CreateElicitationRequestParam;
 use rmcp::model::CreateElicitationResult;
+use rmcp::model::CreateMessageRequestMethod;
+use rmcp::model::CreateMessageRequestParam;
+use rmcp::model::CreateMessageResult as RmcpCreateMessageResult;
 use rmcp::model::ElicitationAction;
+use rmcp::model::ErrorData;
 use rmcp::model::LoggingLevel;
 use rmcp::model::LoggingMessageNotificationParam;
 use rmcp::model::ProgressNotificationParam;
@@ -16,32 +27,91 @@ use tracing::error;
 use tracing::info;
 use tracing::warn;
 
-#[derive(Debug, Clone)]
+use crate::rmcp_client::CreateMessage;
+use crate::rmcp_client::ListChangedKind;
+use crate::rmcp_client::OnListChanged;
+use crate::rmcp_client::SendElicitation;
+use crate::utils::convert_to_mcp;
+use crate::utils::convert_to_rmcp;
+
+#[derive(Clone)]
 pub(crate) struct LoggingClientHandler {
     client_info: ClientInfo,
+    send_elicitation: Arc<SendElicitation>,
+    create_message: Option<Arc<CreateMessage>>,
+    on_list_changed: Arc<OnListChanged>,
 }
 
 impl LoggingClientHandler {
-    pub(crate) fn new(client_info: ClientInfo) -> Self {
-        Self { client_info }
+    pub(crate) fn new(
+        client_info: ClientInfo,
+        send_elicitation: SendElicitation,
+        create_message: Option<CreateMessage>,
+        on_list_changed: OnListChanged,
+    ) -> Self {
+        Self {
+            client_info,
+            send_elicitation: Arc::new(send_elicitation),
+            create_message: create_message.map(Arc::new),
+            on_list_changed: Arc::new(on_list_changed),
+        }
     }
 }
 
 impl ClientHandler for LoggingClientHandler {
-    // TODO (CODEX-3571): support elicitations.
     async fn create_elicitation(
         &self,
         request: CreateElicitationRequestParam,
-        _context: RequestContext<RoleClient>,
-    ) -> Result<CreateElicitationResult, rmcp::ErrorData> {
+        context: RequestContext<RoleClient>,
+    ) -> Result<CreateElicitationResult, ErrorData> {
+        info!("MCP server requested elicitation ({})", request.message);
+        let params: ElicitRequestParams = convert_to_mcp(request)
+            .map_err(|err| ErrorData::invalid_params(err.to_string(), None))?;
+        let id: RequestId = convert_to_mcp(context.id)
+            .map_err(|err| ErrorData::internal_error(err.to_string(), None))?;
+
+        // The server may give up on the request (e.g. because its own caller
+        // was cancelled) before the user answers.
+        let response: ElicitResult = tokio::select! {
+            response = (self.send_elicitation)(id, params) => {
+                response.map_err(|err| ErrorData::internal_error(err.to_string(), None))?
+            }
+            _ = context.ct.cancelled() => {
+                return Ok(CreateElicitationResult {
+                    action: ElicitationAction::Cancel,
+                    content: None,
+                });
+            }
+        };
+        convert_to_rmcp(response).map_err(|err| ErrorData::internal_error(err.to_string(), None))
+    }
+
+    async fn create_message(
+        &self,
+        params: CreateMessageRequestParam,
+        context: RequestContext<RoleClient>,
+    ) -> Result<RmcpCreateMessageResult, ErrorData> {
+        let Some(create_message) = &self.create_message else {
+            return Err(ErrorData::method_not_found::<CreateMessageRequestMethod>());
+        };
         info!(
-            "MCP server requested elicitation ({}). Elicitations are not supported yet. Declining.",
-            request.message
+            "MCP server requested sampling (max_tokens: {})",
+            params.max_tokens
         );
-        Ok(CreateElicitationResult {
-            action: ElicitationAction::Decline,
-            content: None,
-        })
+        let params: CreateMessageRequestParams = convert_to_mcp(params)
+            .map_err(|err| ErrorData::invalid_params(err.to_string(), None))?;
+        let id: RequestId = convert_to_mcp(context.id)
+            .map_err(|err| ErrorData::internal_error(err.to_string(), None))?;
+
+        let result: CreateMessageResult = tokio::select! {
+            result = create_message(id, params) => {
+                result.map_err(|err| ErrorData::internal_error(err.to_string(), None))?
+            }
+            _ = context.ct.cancelled() => {
+                return Err(ErrorData::internal_error("sampling request was cancelled", None));
+            }
+        };
+        convert_to_rmcp(result).map_err(|err| ErrorData::internal_error(err.to_string(), None))
     }
 
     async fn on_cancelled(
@@ -76,14 +146,17 @@ impl ClientHandler for LoggingClientHandler {
 
     async fn on_resource_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server resource list changed");
+        (self.on_list_changed)(ListChangedKind::Resources);
     }
 
     async fn on_tool_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server tool list changed");
+        (self.on_list_changed)(ListChangedKind::Tools);
     }
 
     async fn on_prompt_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server prompt list changed");
+        (self.on_list_changed)(ListChangedKind::Prompts);
     }
 
     fn get_info(&self) -> ClientInfo {
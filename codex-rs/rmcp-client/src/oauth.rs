@@ -73,19 +73,20 @@ pub enum OAuthCredentialsStoreMode {
     Keyring,
 }
 
+/// Error returned by a [`KeyringStore`] backend.
 #[derive(Debug)]
-struct CredentialStoreError(anyhow::Error);
+pub struct CredentialStoreError(anyhow::Error);
 
 impl CredentialStoreError {
-    fn new(error: impl Into<anyhow::Error>) -> Self {
+    pub fn new(error: impl Into<anyhow::Error>) -> Self {
         Self(error.into())
     }
 
-    fn message(&self) -> String {
+    pub fn message(&self) -> String {
         self.0.to_string()
     }
 
-    fn into_error(self) -> anyhow::Error {
+    pub fn into_error(self) -> anyhow::Error {
         self.0
     }
 }
@@ -98,13 +99,17 @@ impl fmt::Display for CredentialStoreError {
 
 impl std::error::Error for CredentialStoreError {}
 
-trait KeyringStore {
+/// Secret storage keyed by service and account, backed by the OS keyring in
+/// production. Also used by `codex-core` for the CLI credentials.
+pub trait KeyringStore {
     fn load(&self, service: &str, account: &str) -> Result<Option<String>, CredentialStoreError>;
     fn save(&self, service: &str, account: &str, value: &str) -> Result<(), CredentialStoreError>;
     fn delete(&self, service: &str, account: &str) -> Result<bool, CredentialStoreError>;
 }
 
-struct DefaultKeyringStore;
+/// [`KeyringStore`] backed by the platform keyring.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultKeyringStore;
 
 impl KeyringStore for DefaultKeyringStore {
     fn load(&self, service: &str, account: &str) -> Result<Option<String>, CredentialStoreError> {
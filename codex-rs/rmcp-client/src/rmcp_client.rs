@@ -9,10 +9,19 @@ use std::time::Duration;
 use anyhow::Result;
 use anyhow::anyhow;
 use futures::FutureExt;
+use futures::future::BoxFuture;
 use mcp_types::CallToolRequestParams;
 use mcp_types::CallToolResult;
+use mcp_types::CreateMessageRequestParams;
+use mcp_types::CreateMessageResult;
+use mcp_types::ElicitRequestParams;
+use mcp_types::ElicitResult;
+use mcp_types::GetPromptRequestParams;
+use mcp_types::GetPromptResult;
 use mcp_types::InitializeRequestParams;
 use mcp_types::InitializeResult;
+use mcp_types::ListPromptsRequestParams;
+use mcp_types::ListPromptsResult;
 use mcp_types::ListResourceTemplatesRequestParams;
 use mcp_types::ListResourceTemplatesResult;
 use mcp_types::ListResourcesRequestParams;
@@ -21,8 +30,11 @@ use mcp_types::ListToolsRequestParams;
 use mcp_types::ListToolsResult;
 use mcp_types::ReadResourceRequestParams;
 use mcp_types::ReadResourceResult;
+use mcp_types::RequestId;
+use mcp_types::ServerCapabilities;
 use reqwest::header::HeaderMap;
 use rmcp::model::CallToolRequestParam;
+use rmcp::model::GetPromptRequestParam;
 use rmcp::model::InitializeRequestParam;
 use rmcp::model::PaginatedRequestParam;
 use rmcp::model::ReadResourceRequestParam;
@@ -76,6 +88,35 @@ enum ClientState {
     },
 }
 
+/// Forwards an `elicitation/create` request from the server to the user and
+/// resolves with their answer.
+pub type SendElicitation = Box<
+    dyn Fn(RequestId, ElicitRequestParams) -> BoxFuture<'static, Result<ElicitResult>>
+        + Send
+        + Sync,
+>;
+
+/// Answers a `sampling/createMessage` request from the server with a
+/// completion from the client's model.
+pub type CreateMessage = Box<
+    dyn Fn(RequestId, CreateMessageRequestParams) -> BoxFuture<'static, Result<CreateMessageResult>>
+        + Send
+        + Sync,
+>;
+
+/// A server-side list announced as changed by a
+/// `notifications/*/list_changed` notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListChangedKind {
+    Tools,
+    Resources,
+    Prompts,
+}
+
+/// Invoked whenever the server sends a `notifications/*/list_changed`
+/// notification.
+pub type OnListChanged = Box<dyn Fn(ListChangedKind) + Send + Sync>;
+
 /// MCP client implemented on top of the official `rmcp` SDK.
 /// https://github.com/modelcontextprotocol/rust-sdk
 pub struct RmcpClient {
@@ -188,13 +229,26 @@ impl RmcpClient {
 
     /// Perform the initialization handshake with the MCP server.
     /// https://modelcontextprotocol.io/specification/2025-06-18/basic/lifecycle#initialization
+    ///
+    /// Elicitation requests the server sends over the lifetime of the
+    /// connection are answered through `send_elicitation`, sampling requests
+    /// through `create_message` (rejected when `None`), and list change
+    /// notifications are reported to `on_list_changed`.
     pub async fn initialize(
         &self,
         params: InitializeRequestParams,
         timeout: Option<Duration>,
+        send_elicitation: SendElicitation,
+        create_message: Option<CreateMessage>,
+        on_list_changed: OnListChanged,
     ) -> Result<InitializeResult> {
         let rmcp_params: InitializeRequestParam = convert_to_rmcp(params.clone())?;
-        let client_handler = LoggingClientHandler::new(rmcp_params);
+        let client_handler = LoggingClientHandler::new(
+            rmcp_params,
+            send_elicitation,
+            create_message,
+            on_list_changed,
+        );
 
         let (transport, oauth_persistor) = {
             let mut guard = self.state.lock().await;
@@ -305,6 +359,48 @@ impl RmcpClient {
         Ok(converted)
     }
 
+    /// Capabilities the server advertised in its `initialize` response.
+    pub async fn server_capabilities(&self) -> Result<ServerCapabilities> {
+        let service = self.service().await?;
+        let server_info = service
+            .peer()
+            .peer_info()
+            .ok_or_else(|| anyhow!("server info was missing"))?;
+        let initialize_result: InitializeResult = convert_to_mcp(server_info)?;
+        Ok(initialize_result.capabilities)
+    }
+
+    pub async fn list_prompts(
+        &self,
+        params: Option<ListPromptsRequestParams>,
+        timeout: Option<Duration>,
+    ) -> Result<ListPromptsResult> {
+        let service = self.service().await?;
+        let rmcp_params = params
+            .map(convert_to_rmcp::<_, PaginatedRequestParam>)
+            .transpose()?;
+
+        let fut = service.list_prompts(rmcp_params);
+        let result = run_with_timeout(fut, timeout, "prompts/list").await?;
+        let converted = convert_to_mcp(result)?;
+        self.persist_oauth_tokens().await;
+        Ok(converted)
+    }
+
+    pub async fn get_prompt(
+        &self,
+        params: GetPromptRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<GetPromptResult> {
+        let service = self.service().await?;
+        let rmcp_params: GetPromptRequestParam = convert_to_rmcp(params)?;
+        let fut = service.get_prompt(rmcp_params);
+        let result = run_with_timeout(fut, timeout, "prompts/get").await?;
+        let converted = convert_to_mcp(result)?;
+        self.persist_oauth_tokens().await;
+        Ok(converted)
+    }
+
     pub async fn read_resource(
         &self,
         params: ReadResourceRequestParams,
@@ -335,6 +431,16 @@ impl RmcpClient {
         Ok(converted)
     }
 
+    /// Returns true once the connection to the server is gone, e.g. because
+    /// the server process exited. A client that has not finished
+    /// initializing is not considered closed.
+    pub async fn is_transport_closed(&self) -> bool {
+        match &*self.state.lock().await {
+            ClientState::Ready { service, .. } => service.is_transport_closed(),
+            ClientState::Connecting { .. } => false,
+        }
+    }
+
     async fn service(&self) -> Result<Arc<RunningService<RoleClient, LoggingClientHandler>>> {
         let guard = self.state.lock().await;
         match &*guard {
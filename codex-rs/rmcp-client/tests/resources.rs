@@ -3,8 +3,11 @@ use std::path::PathBuf;
 use std::time::Duration;
 
 use codex_rmcp_client::RmcpClient;
+use codex_rmcp_client::SendElicitation;
 use escargot::CargoBuild;
+use futures::FutureExt;
 use mcp_types::ClientCapabilities;
+use mcp_types::ElicitResult;
 use mcp_types::Implementation;
 use mcp_types::InitializeRequestParams;
 use mcp_types::ListResourceTemplatesResult;
@@ -43,6 +46,18 @@ fn init_params() -> InitializeRequestParams {
     }
 }
 
+fn decline_elicitations() -> SendElicitation {
+    Box::new(|_, _| {
+        async {
+            Ok(ElicitResult {
+                action: "decline".to_string(),
+                content: None,
+            })
+        }
+        .boxed()
+    })
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn rmcp_client_can_list_and_read_resources() -> anyhow::Result<()> {
     let client = RmcpClient::new_stdio_client(
@@ -55,7 +70,13 @@ async fn rmcp_client_can_list_and_read_resources() -> anyhow::Result<()> {
     .await?;
 
     client
-        .initialize(init_params(), Some(Duration::from_secs(5)))
+        .initialize(
+            init_params(),
+            Some(Duration::from_secs(5)),
+            decline_elicitations(),
+            None,
+            Box::new(|_| {}),
+        )
         .await?;
 
     let list = client
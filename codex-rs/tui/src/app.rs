@@ -10,6 +10,9 @@ use crate::diff_render::DiffSummary;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::file_search::FileSearchManager;
 use crate::history_cell::HistoryCell;
+use crate::keymap::KeymapAction;
+use crate::keymap::KeymapContext;
+use crate::keymap::keymap;
 use crate::pager_overlay::Overlay;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::resume_picker::ResumeSelection;
@@ -21,7 +24,6 @@ use codex_core::ConversationManager;
 use codex_core::config::Config;
 use codex_core::config::persist_model_selection;
 use codex_core::config::set_hide_full_access_warning;
-use codex_core::model_family::find_family_for_model;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol::SessionSource;
 use codex_core::protocol::TokenUsage;
@@ -554,13 +556,22 @@ impl App {
             AppEvent::UpdateModel(model) => {
                 self.chat_widget.set_model(&model);
                 self.config.model = model.clone();
-                if let Some(family) = find_family_for_model(&model) {
+                if let Some(family) = self.config.find_model_family(&model) {
                     self.config.model_family = family;
                 }
             }
             AppEvent::OpenReasoningPopup { model, presets } => {
                 self.chat_widget.open_reasoning_popup(model, presets);
             }
+            AppEvent::OllamaModelsLoaded(choices) => {
+                self.chat_widget.open_ollama_model_popup(choices);
+            }
+            AppEvent::PullOllamaModel(model) => {
+                self.chat_widget.start_ollama_pull(model);
+            }
+            AppEvent::OllamaPullFinished { model, result } => {
+                self.chat_widget.on_ollama_pull_finished(model, result);
+            }
             AppEvent::OpenFullAccessConfirmation { preset } => {
                 self.chat_widget.open_full_access_confirmation(preset);
             }
@@ -1088,24 +1099,19 @@ impl App {
     }
 
     async fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) {
+        let action = if key_event.kind == KeyEventKind::Press {
+            keymap().action_for(KeymapContext::Global, &key_event)
+        } else {
+            None
+        };
         match key_event {
-            KeyEvent {
-                code: KeyCode::Char('t'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                ..
-            } => {
+            _ if action == Some(KeymapAction::ShowTranscript) => {
                 // Enter alternate screen and set viewport to full size.
                 let _ = tui.enter_alt_screen();
                 self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
                 tui.frame_requester().schedule_frame();
             }
-            KeyEvent {
-                code: KeyCode::Char('g'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                ..
-            } => {
+            _ if action == Some(KeymapAction::ShowGitGraph) => {
                 // Show git graph for current directory
                 match crate::git_graph_widget::create_git_graph_overlay(".") {
                     Ok(overlay) => {
@@ -1132,12 +1138,7 @@ impl App {
                     }
                 }
             }
-            KeyEvent {
-                code: KeyCode::Char(c),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                ..
-            } if matches!(c, 'x' | 'q') => {
+            _ if action == Some(KeymapAction::OpenSessionPicker) => {
                 self.open_or_refresh_session_picker(tui);
             }
             // Esc primes/advances backtracking only in normal (not working) mode
@@ -6,8 +6,11 @@ use crate::app::App;
 use crate::app_event::AppEvent;
 use crate::cxresume_picker_widget::SessionInfo;
 use crate::cxresume_picker_widget::{self};
+use crate::git_graph_widget::GitGraphAction;
 use crate::history_cell::CompositeHistoryCell;
 use crate::history_cell::UserHistoryCell;
+use crate::keymap::KeymapAction;
+use crate::keymap::keymap;
 use crate::pager_overlay::Overlay;
 use crate::tui;
 use crate::tui::TuiEvent;
@@ -17,7 +20,6 @@ use color_eyre::eyre::Result;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
-use crossterm::event::KeyModifiers;
 use tracing::warn;
 
 /// Aggregates all backtrack-related state used by the App.
@@ -45,18 +47,20 @@ impl App {
         tui: &mut tui::Tui,
         event: TuiEvent,
     ) -> Result<bool> {
-        if let TuiEvent::Key(KeyEvent {
-            code: KeyCode::Char(c),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            ..
-        }) = event
-            && matches!(c, 'x' | 'q')
+        if let TuiEvent::Key(key_event) = &event
+            && key_event.kind == KeyEventKind::Press
+            && keymap().is_match(KeymapAction::OpenSessionPicker, key_event)
         {
             self.open_or_refresh_session_picker(tui);
             return Ok(true);
         }
 
+        // The git graph uses Esc itself (collapse diff, clear filter, close).
+        if matches!(self.overlay, Some(Overlay::GitGraph(_))) {
+            self.overlay_forward_event(tui, event)?;
+            return Ok(true);
+        }
+
         if self.backtrack.overlay_preview_active {
             match event {
                 TuiEvent::Key(KeyEvent {
@@ -266,6 +270,7 @@ impl App {
                 id: Option<String>,
                 session: Option<SessionInfo>,
             },
+            GitGraph(Option<GitGraphAction>),
             Other,
         }
 
@@ -278,6 +283,9 @@ impl App {
                         id: overlay.get_selected_session_id(),
                         session: overlay.get_selected_session(),
                     },
+                    Overlay::GitGraph(_) => {
+                        OverlayCloseAction::GitGraph(overlay.take_git_graph_action())
+                    }
                     _ => OverlayCloseAction::Other,
                 });
             }
@@ -288,6 +296,18 @@ impl App {
                 OverlayCloseAction::Session { id, session } => {
                     self.close_session_picker(tui, id, session)?;
                 }
+                OverlayCloseAction::GitGraph(action) => {
+                    self.close_transcript_overlay(tui);
+                    match action {
+                        Some(GitGraphAction::AttachDiff(text)) => {
+                            self.chat_widget.handle_paste(text)
+                        }
+                        Some(GitGraphAction::Explain(prompt)) => {
+                            self.chat_widget.submit_text_message(prompt);
+                        }
+                        None => {}
+                    }
+                }
                 OverlayCloseAction::Other => self.close_transcript_overlay(tui),
             }
             tui.frame_requester().schedule_frame();
@@ -10,6 +10,7 @@ use codex_multi_agent::DelegateEvent;
 use crate::bottom_pane::ApprovalRequest;
 use crate::cxresume_picker_widget::PickerState;
 use crate::history_cell::HistoryCell;
+use crate::ollama_models::OllamaModelChoice;
 
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::SandboxPolicy;
@@ -77,6 +78,19 @@ pub(crate) enum AppEvent {
         effort: Option<ReasoningEffort>,
     },
 
+    /// Result of listing the local Ollama models for the `/model` picker.
+    OllamaModelsLoaded(Result<Vec<OllamaModelChoice>, String>),
+
+    /// Download an Ollama model that was picked in `/model` but is not
+    /// available locally yet.
+    PullOllamaModel(String),
+
+    /// A background Ollama pull ended.
+    OllamaPullFinished {
+        model: String,
+        result: Result<(), String>,
+    },
+
     /// Open the reasoning selection popup after picking a model.
     OpenReasoningPopup {
         model: String,
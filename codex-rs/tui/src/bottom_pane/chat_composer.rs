@@ -29,8 +29,11 @@ use super::footer::toggle_shortcut_mode;
 use super::paste_burst::CharDecision;
 use super::paste_burst::PasteBurst;
 use crate::bottom_pane::paste_burst::FlushResult;
+use crate::bottom_pane::prompt_args::McpPromptCommand;
+use crate::bottom_pane::prompt_args::command_with_arg_placeholders;
 use crate::bottom_pane::prompt_args::expand_custom_prompt;
 use crate::bottom_pane::prompt_args::expand_if_numeric_with_positional_args;
+use crate::bottom_pane::prompt_args::parse_mcp_prompt_invocation;
 use crate::bottom_pane::prompt_args::parse_slash_name;
 use crate::bottom_pane::prompt_args::prompt_argument_names;
 use crate::bottom_pane::prompt_args::prompt_command_with_arg_placeholders;
@@ -48,6 +51,9 @@ use crate::bottom_pane::textarea::TextAreaState;
 use crate::clipboard_paste::normalize_pasted_path;
 use crate::clipboard_paste::pasted_image_format;
 use crate::history_cell;
+use crate::keymap::KeymapAction;
+use crate::keymap::KeymapContext;
+use crate::keymap::keymap;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use codex_file_search::FileMatch;
 use std::cell::RefCell;
@@ -67,6 +73,14 @@ pub enum InputResult {
     Submitted(String),
     Command(SlashCommand),
     CommandWithArgs(SlashCommand, String),
+    /// An MCP prompt invoked as `/server:prompt key=value …`. `text` is the
+    /// line as typed by the user.
+    McpPrompt {
+        server_name: String,
+        prompt_name: String,
+        arguments: HashMap<String, String>,
+        text: String,
+    },
     None,
 }
 
@@ -107,6 +121,7 @@ pub(crate) struct ChatComposer {
     // When true, disables paste-burst logic and inserts characters immediately.
     disable_paste_burst: bool,
     custom_prompts: Vec<CustomPrompt>,
+    mcp_prompts: Vec<McpPromptCommand>,
     footer_mode: FooterMode,
     footer_hint_override: Option<Vec<(String, String)>>,
     context_window_percent: Option<u8>,
@@ -151,6 +166,7 @@ impl ChatComposer {
             paste_burst: PasteBurst::default(),
             disable_paste_burst: false,
             custom_prompts: Vec::new(),
+            mcp_prompts: Vec::new(),
             footer_mode: FooterMode::ShortcutSummary,
             footer_hint_override: None,
             context_window_percent: None,
@@ -495,6 +511,21 @@ impl ChatComposer {
                                 }
                             }
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some(prompt) = popup.mcp_prompt(idx) {
+                                let command = prompt.command();
+                                let starts_with_cmd = parse_slash_name(first_line)
+                                    .is_some_and(|(name, _)| name == command);
+                                if !starts_with_cmd {
+                                    let (text, cursor) = command_with_arg_placeholders(
+                                        &command,
+                                        &prompt.argument_names(),
+                                    );
+                                    self.textarea.set_text(&text);
+                                    cursor_target = Some(cursor);
+                                }
+                            }
+                        }
                     }
                     if let Some(pos) = cursor_target {
                         self.textarea.set_cursor(pos);
@@ -548,6 +579,28 @@ impl ChatComposer {
                             }
                             return (InputResult::None, true);
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some(prompt) = popup.mcp_prompt(idx) {
+                                let command = prompt.command();
+                                let args = prompt.argument_names();
+                                let starts_with_cmd = parse_slash_name(first_line)
+                                    .is_some_and(|(name, _)| name == command);
+                                if !starts_with_cmd {
+                                    if !args.is_empty() {
+                                        let (text, cursor) =
+                                            command_with_arg_placeholders(&command, &args);
+                                        self.textarea.set_text(&text);
+                                        self.textarea.set_cursor(cursor);
+                                        return (InputResult::None, true);
+                                    }
+                                    self.textarea.set_text(&format!("/{command}"));
+                                }
+                            }
+                            // Submit through the regular path, which validates
+                            // the arguments.
+                            self.active_popup = ActivePopup::None;
+                            return self.handle_key_event_without_popup(key_event);
+                        }
                     }
                 }
                 // Fallback to default newline handling if no command selected.
@@ -872,33 +925,33 @@ impl ChatComposer {
         } else {
             self.footer_mode = reset_mode_after_activity(self.footer_mode);
         }
+        let action = keymap().action_for(KeymapContext::Composer, &key_event);
         match key_event {
-            KeyEvent {
-                code: KeyCode::Char('d'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                ..
-            } if self.is_empty() => {
+            _ if action == Some(KeymapAction::Exit)
+                && key_event.kind == KeyEventKind::Press
+                && self.is_empty() =>
+            {
                 self.app_event_tx.send(AppEvent::ExitRequest);
                 (InputResult::None, true)
             }
             // -------------------------------------------------------------
-            // History navigation (Up / Down) – only when the composer is not
-            // empty or when the cursor is at the correct position, to avoid
-            // interfering with normal cursor movement.
+            // History navigation (Up / Down by default) – only when the
+            // composer is not empty or when the cursor is at the correct
+            // position, to avoid interfering with normal cursor movement.
             // -------------------------------------------------------------
-            KeyEvent {
-                code: KeyCode::Up | KeyCode::Down,
-                ..
-            } => {
+            _ if matches!(
+                action,
+                Some(KeymapAction::HistoryPrevious | KeymapAction::HistoryNext)
+            ) =>
+            {
                 if self
                     .history
                     .should_handle_navigation(self.textarea.text(), self.textarea.cursor())
                 {
-                    let replace_text = match key_event.code {
-                        KeyCode::Up => self.history.navigate_up(&self.app_event_tx),
-                        KeyCode::Down => self.history.navigate_down(&self.app_event_tx),
-                        _ => unreachable!(),
+                    let replace_text = if action == Some(KeymapAction::HistoryPrevious) {
+                        self.history.navigate_up(&self.app_event_tx)
+                    } else {
+                        self.history.navigate_down(&self.app_event_tx)
                     };
                     if let Some(text) = replace_text {
                         self.textarea.set_text(&text);
@@ -1017,7 +1070,11 @@ impl ChatComposer {
                                     .any(|prompt| prompt.name == prompt_name)
                             })
                             .unwrap_or(false);
-                        if !is_builtin && !is_known_prompt {
+                        let is_mcp_prompt = self
+                            .mcp_prompts
+                            .iter()
+                            .any(|prompt| prompt.command() == name);
+                        if !is_builtin && !is_known_prompt && !is_mcp_prompt {
                             let message = format!(
                                 r#"Unrecognized command '/{name}'. Type "/" for a list of supported commands."#
                             );
@@ -1031,6 +1088,28 @@ impl ChatComposer {
                     }
                 }
 
+                match parse_mcp_prompt_invocation(&text, &self.mcp_prompts) {
+                    Ok(Some((prompt, arguments))) => {
+                        let result = InputResult::McpPrompt {
+                            server_name: prompt.server_name.clone(),
+                            prompt_name: prompt.prompt.name.clone(),
+                            arguments,
+                            text: text.clone(),
+                        };
+                        self.history.record_local_submission(&text);
+                        return (result, true);
+                    }
+                    Ok(None) => {}
+                    Err(err) => {
+                        self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                            history_cell::new_error_event(err.user_message()),
+                        )));
+                        self.textarea.set_text(&original_input);
+                        self.textarea.set_cursor(original_input.len());
+                        return (InputResult::None, true);
+                    }
+                }
+
                 let expanded_prompt = match expand_custom_prompt(&text, &self.custom_prompts) {
                     Ok(expanded) => expanded,
                     Err(err) => {
@@ -1371,14 +1450,8 @@ impl ChatComposer {
             return false;
         }
 
-        let toggles = matches!(
-            key_event,
-            KeyEvent {
-                code: KeyCode::Char('?'),
-                modifiers: KeyModifiers::NONE,
-                ..
-            } if self.is_empty()
-        );
+        let toggles =
+            self.is_empty() && keymap().is_match(KeymapAction::ToggleShortcuts, key_event);
 
         if !toggles {
             return false;
@@ -1459,7 +1532,8 @@ impl ChatComposer {
             }
             _ => {
                 if is_editing_slash_command_name {
-                    let mut command_popup = CommandPopup::new(self.custom_prompts.clone());
+                    let mut command_popup =
+                        CommandPopup::new(self.custom_prompts.clone(), self.mcp_prompts.clone());
                     command_popup.on_composer_text_change(first_line.to_string());
                     self.active_popup = ActivePopup::Command(command_popup);
                 }
@@ -1474,6 +1548,13 @@ impl ChatComposer {
         }
     }
 
+    pub(crate) fn set_mcp_prompts(&mut self, prompts: Vec<McpPromptCommand>) {
+        self.mcp_prompts = prompts.clone();
+        if let ActivePopup::Command(popup) = &mut self.active_popup {
+            popup.set_mcp_prompts(prompts);
+        }
+    }
+
     /// Synchronize `self.file_search_popup` with the current text in the textarea.
     /// Note this is only called when self.active_popup is NOT Command.
     fn sync_file_search_popup(&mut self) {
@@ -2293,7 +2374,7 @@ mod tests {
                 Some(CommandItem::Builtin(cmd)) => {
                     assert_eq!(cmd.command(), "model")
                 }
-                Some(CommandItem::UserPrompt(_)) => {
+                Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                     panic!("unexpected prompt selected for '/mo'")
                 }
                 None => panic!("no selected command for '/mo'"),
@@ -2948,6 +3029,50 @@ mod tests {
         assert!(composer.textarea.is_empty());
     }
 
+    #[test]
+    fn mcp_prompt_submission_returns_arguments() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+
+        composer.set_mcp_prompts(vec![McpPromptCommand {
+            server_name: "github".to_string(),
+            prompt: mcp_types::Prompt {
+                arguments: Some(vec![mcp_types::PromptArgument {
+                    description: None,
+                    name: "pr".to_string(),
+                    required: Some(true),
+                    title: None,
+                }]),
+                description: None,
+                name: "review".to_string(),
+                title: None,
+            },
+        }]);
+
+        composer.textarea.set_text("/github:review pr=42");
+
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(
+            InputResult::McpPrompt {
+                server_name: "github".to_string(),
+                prompt_name: "review".to_string(),
+                arguments: HashMap::from([("pr".to_string(), "42".to_string())]),
+                text: "/github:review pr=42".to_string(),
+            },
+            result
+        );
+        assert!(composer.textarea.is_empty());
+    }
+
     #[test]
     fn custom_prompt_submission_accepts_quoted_values() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -3,6 +3,7 @@ use ratatui::layout::Rect;
 use ratatui::widgets::WidgetRef;
 
 use super::popup_consts::MAX_POPUP_ROWS;
+use super::prompt_args::McpPromptCommand;
 use super::scroll_state::ScrollState;
 use super::selection_popup_common::GenericDisplayRow;
 use super::selection_popup_common::render_rows;
@@ -15,23 +16,27 @@ use codex_protocol::custom_prompts::CustomPrompt;
 use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use std::collections::HashSet;
 
-/// A selectable item in the popup: either a built-in command or a user prompt.
+/// A selectable item in the popup: a built-in command, a user prompt or a
+/// prompt exposed by an MCP server.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum CommandItem {
     Builtin(SlashCommand),
     // Index into `prompts`
     UserPrompt(usize),
+    // Index into `mcp_prompts`
+    McpPrompt(usize),
 }
 
 pub(crate) struct CommandPopup {
     command_filter: String,
     builtins: Vec<(&'static str, SlashCommand)>,
     prompts: Vec<CustomPrompt>,
+    mcp_prompts: Vec<McpPromptCommand>,
     state: ScrollState,
 }
 
 impl CommandPopup {
-    pub(crate) fn new(mut prompts: Vec<CustomPrompt>) -> Self {
+    pub(crate) fn new(mut prompts: Vec<CustomPrompt>, mcp_prompts: Vec<McpPromptCommand>) -> Self {
         let builtins = built_in_slash_commands();
         // Exclude prompts that collide with builtin command names and sort by name.
         let exclude: HashSet<String> = builtins.iter().map(|(n, _)| (*n).to_string()).collect();
@@ -41,6 +46,7 @@ impl CommandPopup {
             command_filter: String::new(),
             builtins,
             prompts,
+            mcp_prompts,
             state: ScrollState::new(),
         }
     }
@@ -56,10 +62,18 @@ impl CommandPopup {
         self.prompts = prompts;
     }
 
+    pub(crate) fn set_mcp_prompts(&mut self, mcp_prompts: Vec<McpPromptCommand>) {
+        self.mcp_prompts = mcp_prompts;
+    }
+
     pub(crate) fn prompt(&self, idx: usize) -> Option<&CustomPrompt> {
         self.prompts.get(idx)
     }
 
+    pub(crate) fn mcp_prompt(&self, idx: usize) -> Option<&McpPromptCommand> {
+        self.mcp_prompts.get(idx)
+    }
+
     /// Update the filter string based on the current composer text. The text
     /// passed in is expected to start with a leading '/'. Everything after the
     /// *first* '/" on the *first* line becomes the active filter that is used
@@ -115,6 +129,10 @@ impl CommandPopup {
             for idx in 0..self.prompts.len() {
                 out.push((CommandItem::UserPrompt(idx), None, 0));
             }
+            // Then MCP prompts, already sorted by `server:prompt`.
+            for idx in 0..self.mcp_prompts.len() {
+                out.push((CommandItem::McpPrompt(idx), None, 0));
+            }
             return out;
         }
 
@@ -132,23 +150,27 @@ impl CommandPopup {
                 out.push((CommandItem::UserPrompt(idx), Some(indices), score));
             }
         }
+        for (idx, p) in self.mcp_prompts.iter().enumerate() {
+            if let Some((indices, score)) = fuzzy_match(&p.command(), filter) {
+                out.push((CommandItem::McpPrompt(idx), Some(indices), score));
+            }
+        }
         // When filtering, sort by ascending score and then by name for stability.
         out.sort_by(|a, b| {
-            a.2.cmp(&b.2).then_with(|| {
-                let an = match a.0 {
-                    CommandItem::Builtin(c) => c.command(),
-                    CommandItem::UserPrompt(i) => &self.prompts[i].name,
-                };
-                let bn = match b.0 {
-                    CommandItem::Builtin(c) => c.command(),
-                    CommandItem::UserPrompt(i) => &self.prompts[i].name,
-                };
-                an.cmp(bn)
-            })
+            a.2.cmp(&b.2)
+                .then_with(|| self.sort_name(a.0).cmp(&self.sort_name(b.0)))
         });
         out
     }
 
+    fn sort_name(&self, item: CommandItem) -> String {
+        match item {
+            CommandItem::Builtin(c) => c.command().to_string(),
+            CommandItem::UserPrompt(i) => self.prompts[i].name.clone(),
+            CommandItem::McpPrompt(i) => self.mcp_prompts[i].command(),
+        }
+    }
+
     fn filtered_items(&self) -> Vec<CommandItem> {
         self.filtered().into_iter().map(|(c, _, _)| c).collect()
     }
@@ -168,6 +190,17 @@ impl CommandPopup {
                         format!("/{PROMPTS_CMD_PREFIX}:{}", self.prompts[i].name),
                         "send saved prompt".to_string(),
                     ),
+                    CommandItem::McpPrompt(i) => {
+                        let prompt = &self.mcp_prompts[i];
+                        (
+                            format!("/{}", prompt.command()),
+                            prompt
+                                .prompt
+                                .description
+                                .clone()
+                                .unwrap_or_else(|| "send MCP prompt".to_string()),
+                        )
+                    }
                 };
                 GenericDisplayRow {
                     name,
@@ -224,7 +257,7 @@ mod tests {
 
     #[test]
     fn filter_includes_init_when_typing_prefix() {
-        let mut popup = CommandPopup::new(Vec::new());
+        let mut popup = CommandPopup::new(Vec::new(), Vec::new());
         // Simulate the composer line starting with '/in' so the popup filters
         // matching commands by prefix.
         popup.on_composer_text_change("/in".to_string());
@@ -234,7 +267,7 @@ mod tests {
         let matches = popup.filtered_items();
         let has_init = matches.iter().any(|item| match item {
             CommandItem::Builtin(cmd) => cmd.command() == "init",
-            CommandItem::UserPrompt(_) => false,
+            CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => false,
         });
         assert!(
             has_init,
@@ -244,7 +277,7 @@ mod tests {
 
     #[test]
     fn selecting_init_by_exact_match() {
-        let mut popup = CommandPopup::new(Vec::new());
+        let mut popup = CommandPopup::new(Vec::new(), Vec::new());
         popup.on_composer_text_change("/init".to_string());
 
         // When an exact match exists, the selected command should be that
@@ -252,19 +285,21 @@ mod tests {
         let selected = popup.selected_item();
         match selected {
             Some(CommandItem::Builtin(cmd)) => assert_eq!(cmd.command(), "init"),
-            Some(CommandItem::UserPrompt(_)) => panic!("unexpected prompt selected for '/init'"),
+            Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
+                panic!("unexpected prompt selected for '/init'")
+            }
             None => panic!("expected a selected command for exact match"),
         }
     }
 
     #[test]
     fn model_is_first_suggestion_for_mo() {
-        let mut popup = CommandPopup::new(Vec::new());
+        let mut popup = CommandPopup::new(Vec::new(), Vec::new());
         popup.on_composer_text_change("/mo".to_string());
         let matches = popup.filtered_items();
         match matches.first() {
             Some(CommandItem::Builtin(cmd)) => assert_eq!(cmd.command(), "model"),
-            Some(CommandItem::UserPrompt(_)) => {
+            Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                 panic!("unexpected prompt ranked before '/model' for '/mo'")
             }
             None => panic!("expected at least one match for '/mo'"),
@@ -289,7 +324,7 @@ mod tests {
                 argument_hint: None,
             },
         ];
-        let popup = CommandPopup::new(prompts);
+        let popup = CommandPopup::new(prompts, Vec::new());
         let items = popup.filtered_items();
         let mut prompt_names: Vec<String> = items
             .into_iter()
@@ -305,13 +340,16 @@ mod tests {
     #[test]
     fn prompt_name_collision_with_builtin_is_ignored() {
         // Create a prompt named like a builtin (e.g. "init").
-        let popup = CommandPopup::new(vec![CustomPrompt {
-            name: "init".to_string(),
-            path: "/tmp/init.md".to_string().into(),
-            content: "should be ignored".to_string(),
-            description: None,
-            argument_hint: None,
-        }]);
+        let popup = CommandPopup::new(
+            vec![CustomPrompt {
+                name: "init".to_string(),
+                path: "/tmp/init.md".to_string().into(),
+                content: "should be ignored".to_string(),
+                description: None,
+                argument_hint: None,
+            }],
+            Vec::new(),
+        );
         let items = popup.filtered_items();
         let has_collision_prompt = items.into_iter().any(|it| match it {
             CommandItem::UserPrompt(i) => popup.prompt(i).is_some_and(|p| p.name == "init"),
@@ -322,4 +360,28 @@ mod tests {
             "prompt with builtin name should be ignored"
         );
     }
+
+    #[test]
+    fn mcp_prompts_are_listed_as_server_commands() {
+        let mut popup = CommandPopup::new(
+            Vec::new(),
+            vec![McpPromptCommand {
+                server_name: "github".to_string(),
+                prompt: mcp_types::Prompt {
+                    arguments: None,
+                    description: Some("Review a pull request".to_string()),
+                    name: "review".to_string(),
+                    title: None,
+                },
+            }],
+        );
+        popup.on_composer_text_change("/github:rev".to_string());
+        match popup.selected_item() {
+            Some(CommandItem::McpPrompt(i)) => assert_eq!(
+                popup.mcp_prompt(i).map(McpPromptCommand::command),
+                Some("github:review".to_string())
+            ),
+            other => panic!("expected the MCP prompt to be selected, got {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,469 @@
+use codex_core::protocol::ElicitationAction;
+use codex_core::protocol::ElicitationRequestEvent;
+use codex_core::protocol::Op;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use mcp_types::RequestId;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+use serde_json::Map;
+use serde_json::Value;
+use unicode_width::UnicodeWidthStr;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::key_hint;
+use crate::render::renderable::Renderable;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+
+#[derive(Debug, Clone, PartialEq)]
+enum FieldKind {
+    Text,
+    Number { integer: bool },
+    Boolean,
+    Choice(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+struct FormField {
+    name: String,
+    title: String,
+    description: Option<String>,
+    required: bool,
+    kind: FieldKind,
+    /// Typed text for `Text` and `Number` fields.
+    text: String,
+    checked: bool,
+    choice: usize,
+}
+
+impl FormField {
+    fn from_schema(name: &str, schema: &Value, required: bool) -> Self {
+        let title = schema
+            .get("title")
+            .and_then(Value::as_str)
+            .unwrap_or(name)
+            .to_string();
+        let description = schema
+            .get("description")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let default = schema.get("default");
+        let choices: Option<Vec<String>> = schema.get("enum").and_then(Value::as_array).map(|v| {
+            v.iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        });
+        let kind = match (schema.get("type").and_then(Value::as_str), choices) {
+            (_, Some(choices)) if !choices.is_empty() => FieldKind::Choice(choices),
+            (Some("boolean"), _) => FieldKind::Boolean,
+            (Some("integer"), _) => FieldKind::Number { integer: true },
+            (Some("number"), _) => FieldKind::Number { integer: false },
+            _ => FieldKind::Text,
+        };
+        let choice = match (&kind, default.and_then(Value::as_str)) {
+            (FieldKind::Choice(choices), Some(default)) => {
+                choices.iter().position(|c| c == default).unwrap_or(0)
+            }
+            _ => 0,
+        };
+        let text = match default {
+            Some(Value::String(s)) if kind == FieldKind::Text => s.clone(),
+            Some(Value::Number(n)) => n.to_string(),
+            _ => String::new(),
+        };
+        Self {
+            name: name.to_string(),
+            title,
+            description,
+            required,
+            checked: default.and_then(Value::as_bool).unwrap_or(false),
+            kind,
+            text,
+            choice,
+        }
+    }
+
+    fn label(&self) -> String {
+        if self.required {
+            format!("{}*: ", self.title)
+        } else {
+            format!("{}: ", self.title)
+        }
+    }
+
+    fn display_value(&self) -> String {
+        match &self.kind {
+            FieldKind::Text | FieldKind::Number { .. } => self.text.clone(),
+            FieldKind::Boolean => if self.checked { "[x]" } else { "[ ]" }.to_string(),
+            FieldKind::Choice(choices) => format!("‹ {} ›", choices[self.choice]),
+        }
+    }
+
+    fn edit(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        match (&self.kind, code) {
+            (FieldKind::Boolean, KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right) => {
+                self.checked = !self.checked;
+            }
+            (FieldKind::Choice(choices), KeyCode::Left) => {
+                self.choice = (self.choice + choices.len() - 1) % choices.len();
+            }
+            (FieldKind::Choice(choices), KeyCode::Char(' ') | KeyCode::Right) => {
+                self.choice = (self.choice + 1) % choices.len();
+            }
+            (FieldKind::Text | FieldKind::Number { .. }, KeyCode::Backspace) => {
+                self.text.pop();
+            }
+            (FieldKind::Text | FieldKind::Number { .. }, KeyCode::Char(c))
+                if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.text.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn accepts_text(&self) -> bool {
+        matches!(self.kind, FieldKind::Text | FieldKind::Number { .. })
+    }
+
+    /// Convert the current input into a JSON value, or `None` when an
+    /// optional field was left empty.
+    fn value(&self) -> Result<Option<Value>, String> {
+        let text = self.text.trim();
+        match &self.kind {
+            FieldKind::Boolean => Ok(Some(Value::Bool(self.checked))),
+            FieldKind::Choice(choices) => Ok(Some(Value::String(choices[self.choice].clone()))),
+            FieldKind::Text | FieldKind::Number { .. } if text.is_empty() => {
+                if self.required {
+                    Err(format!("{} is required", self.title))
+                } else {
+                    Ok(None)
+                }
+            }
+            FieldKind::Text => Ok(Some(Value::String(self.text.clone()))),
+            FieldKind::Number { integer: true } => text
+                .parse::<i64>()
+                .map(|n| Some(Value::from(n)))
+                .map_err(|_| format!("{} must be an integer", self.title)),
+            FieldKind::Number { integer: false } => text
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(|n| Some(Value::Number(n)))
+                .ok_or_else(|| format!("{} must be a number", self.title)),
+        }
+    }
+}
+
+/// Form shown when an MCP server asks the user for structured input.
+pub(crate) struct ElicitationFormView {
+    server_name: String,
+    request_id: RequestId,
+    message: String,
+    fields: Vec<FormField>,
+    selected: usize,
+    error: Option<String>,
+    app_event_tx: AppEventSender,
+    complete: bool,
+}
+
+impl ElicitationFormView {
+    pub(crate) fn new(request: ElicitationRequestEvent, app_event_tx: AppEventSender) -> Self {
+        let schema = request.requested_schema;
+        let required = schema.required.unwrap_or_default();
+        let fields = schema
+            .properties
+            .as_object()
+            .map(|properties| {
+                properties
+                    .iter()
+                    .map(|(name, property)| {
+                        FormField::from_schema(name, property, required.contains(name))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            server_name: request.server_name,
+            request_id: request.id,
+            message: request.message,
+            fields,
+            selected: 0,
+            error: None,
+            app_event_tx,
+            complete: false,
+        }
+    }
+
+    fn resolve(&mut self, decision: ElicitationAction, content: Option<Value>) {
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::ResolveElicitation {
+                server_name: self.server_name.clone(),
+                request_id: self.request_id.clone(),
+                decision,
+                content,
+            }));
+        self.complete = true;
+    }
+
+    fn submit(&mut self) {
+        let mut content = Map::new();
+        for field in &self.fields {
+            match field.value() {
+                Ok(Some(value)) => {
+                    content.insert(field.name.clone(), value);
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    self.error = Some(err);
+                    return;
+                }
+            }
+        }
+        self.resolve(ElicitationAction::Accept, Some(Value::Object(content)));
+    }
+
+    fn move_selection(&mut self, forward: bool) {
+        let len = self.fields.len();
+        if len == 0 {
+            return;
+        }
+        self.selected = if forward {
+            (self.selected + 1) % len
+        } else {
+            (self.selected + len - 1) % len
+        };
+    }
+
+    /// Lines to render plus the cursor position (column, row) relative to the
+    /// view when the selected field takes typed input.
+    fn layout(&self, width: u16) -> (Vec<Line<'static>>, Option<(u16, u16)>) {
+        let mut lines: Vec<Line<'static>> = Vec::new();
+        lines.push(Line::from(vec![
+            gutter(),
+            format!("{} needs your input", self.server_name).bold(),
+        ]));
+        let wrap_width = usize::from(width.saturating_sub(2)).max(1);
+        for line in textwrap::wrap(&self.message, wrap_width) {
+            lines.push(Line::from(vec![gutter(), line.into_owned().into()]));
+        }
+        lines.push(Line::from(gutter()));
+
+        let mut cursor = None;
+        for (idx, field) in self.fields.iter().enumerate() {
+            let selected = idx == self.selected;
+            let marker: Span<'static> = if selected { "› ".cyan() } else { "  ".into() };
+            let label = field.label();
+            let value = field.display_value();
+            if selected && field.accepts_text() {
+                let col = 2 + 2 + label.width() + value.width();
+                cursor = Some((col as u16, lines.len() as u16));
+            }
+            let label: Span<'static> = if selected { label.cyan() } else { label.into() };
+            lines.push(Line::from(vec![gutter(), marker, label, value.into()]));
+            if selected && let Some(description) = &field.description {
+                lines.push(Line::from(vec![
+                    gutter(),
+                    "  ".into(),
+                    description.clone().dim(),
+                ]));
+            }
+        }
+
+        if let Some(error) = &self.error {
+            lines.push(Line::from(vec![gutter(), error.clone().red()]));
+        }
+        lines.push(Line::from(""));
+        lines.push(hint_line(self.fields.len() > 1));
+        (lines, cursor)
+    }
+}
+
+impl BottomPaneView for ElicitationFormView {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Esc => self.resolve(ElicitationAction::Decline, None),
+            KeyCode::Enter if self.selected + 1 < self.fields.len() => self.move_selection(true),
+            KeyCode::Enter => self.submit(),
+            KeyCode::Tab | KeyCode::Down => self.move_selection(true),
+            KeyCode::BackTab | KeyCode::Up => self.move_selection(false),
+            code => {
+                if let Some(field) = self.fields.get_mut(self.selected) {
+                    field.edit(code, key_event.modifiers);
+                    self.error = None;
+                }
+            }
+        }
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.resolve(ElicitationAction::Cancel, None);
+        CancellationEvent::Handled
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn handle_paste(&mut self, pasted: String) -> bool {
+        match self.fields.get_mut(self.selected) {
+            Some(field) if field.accepts_text() => {
+                field.text.push_str(&pasted.replace(['\r', '\n'], " "));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
+        let (_, cursor) = self.layout(area.width);
+        let (col, row) = cursor?;
+        (col < area.width && row < area.height).then_some((area.x + col, area.y + row))
+    }
+}
+
+impl Renderable for ElicitationFormView {
+    fn desired_height(&self, width: u16) -> u16 {
+        self.layout(width).0.len() as u16
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+        Clear.render(area, buf);
+        let (lines, _) = self.layout(area.width);
+        Paragraph::new(lines).render(area, buf);
+    }
+}
+
+fn hint_line(multiple_fields: bool) -> Line<'static> {
+    let mut spans: Vec<Span<'static>> = vec![
+        "Press ".into(),
+        key_hint::plain(KeyCode::Enter).into(),
+        " to submit".into(),
+    ];
+    if multiple_fields {
+        spans.extend([
+            ", ".into(),
+            key_hint::plain(KeyCode::Tab).into(),
+            " to move between fields".into(),
+        ]);
+    }
+    spans.extend([
+        ", ".into(),
+        key_hint::plain(KeyCode::Esc).into(),
+        " to decline".into(),
+    ]);
+    Line::from(spans)
+}
+
+fn gutter() -> Span<'static> {
+    "▌ ".cyan()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_event::AppEvent;
+    use mcp_types::ElicitRequestParamsRequestedSchema;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn make_view() -> (
+        ElicitationFormView,
+        tokio::sync::mpsc::UnboundedReceiver<AppEvent>,
+    ) {
+        let (tx, rx) = unbounded_channel::<AppEvent>();
+        let request = ElicitationRequestEvent {
+            server_name: "deploy".to_string(),
+            id: RequestId::Integer(1),
+            message: "Where should we deploy?".to_string(),
+            requested_schema: ElicitRequestParamsRequestedSchema {
+                properties: json!({
+                    "confirm": { "type": "boolean" },
+                    "region": { "type": "string", "enum": ["us", "eu"] },
+                    "replicas": { "type": "integer" },
+                }),
+                required: Some(vec!["replicas".to_string()]),
+                r#type: "object".to_string(),
+            },
+        };
+        (
+            ElicitationFormView::new(request, AppEventSender::new(tx)),
+            rx,
+        )
+    }
+
+    fn press(view: &mut ElicitationFormView, code: KeyCode) {
+        view.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn resolved_op(
+        rx: &mut tokio::sync::mpsc::UnboundedReceiver<AppEvent>,
+    ) -> (ElicitationAction, Option<Value>) {
+        while let Ok(event) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::ResolveElicitation {
+                decision, content, ..
+            }) = event
+            {
+                return (decision, content);
+            }
+        }
+        panic!("no ResolveElicitation op was sent");
+    }
+
+    #[test]
+    fn submits_typed_values() {
+        let (mut view, mut rx) = make_view();
+        press(&mut view, KeyCode::Char(' '));
+        press(&mut view, KeyCode::Tab);
+        press(&mut view, KeyCode::Right);
+        press(&mut view, KeyCode::Tab);
+        press(&mut view, KeyCode::Char('3'));
+        press(&mut view, KeyCode::Enter);
+
+        assert!(view.is_complete());
+        assert_eq!(
+            resolved_op(&mut rx),
+            (
+                ElicitationAction::Accept,
+                Some(json!({ "confirm": true, "region": "eu", "replicas": 3 }))
+            )
+        );
+    }
+
+    #[test]
+    fn missing_required_field_blocks_submit() {
+        let (mut view, mut rx) = make_view();
+        view.selected = 2;
+        press(&mut view, KeyCode::Enter);
+
+        assert!(!view.is_complete());
+        assert_eq!(view.error.as_deref(), Some("replicas is required"));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn esc_declines_and_ctrl_c_cancels() {
+        let (mut view, mut rx) = make_view();
+        press(&mut view, KeyCode::Esc);
+        assert_eq!(resolved_op(&mut rx), (ElicitationAction::Decline, None));
+
+        let (mut view, mut rx) = make_view();
+        assert_eq!(view.on_ctrl_c(), CancellationEvent::Handled);
+        assert_eq!(resolved_op(&mut rx), (ElicitationAction::Cancel, None));
+    }
+}
@@ -1,5 +1,7 @@
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::KeymapAction;
+use crate::keymap::keymap;
 use crate::render::line_utils::prefix_lines;
 use crate::ui_consts::FOOTER_INDENT_COLS;
 use crossterm::event::KeyCode;
@@ -91,11 +93,10 @@ fn footer_lines(props: &FooterProps) -> Vec<Line<'static>> {
             for span in context_line {
                 spans.push(span);
             }
-            spans.push(" · ".dim());
-            spans.extend(vec![
-                key_hint::plain(KeyCode::Char('?')).into(),
-                " for shortcuts".dim(),
-            ]);
+            if let Some(key) = keymap().key_binding(KeymapAction::ToggleShortcuts) {
+                spans.push(" · ".dim());
+                spans.extend(vec![key.into(), " for shortcuts".dim()]);
+            }
             vec![Line::from(spans)]
         }
         FooterMode::ShortcutOverlay => shortcut_overlay_lines(ShortcutsState {
@@ -289,6 +290,9 @@ impl DisplayCondition {
 struct ShortcutDescriptor {
     id: ShortcutId,
     bindings: &'static [ShortcutBinding],
+    /// When set, the key comes from the effective keymap instead of
+    /// `bindings`, and the entry is hidden if the action is unbound.
+    action: Option<KeymapAction>,
     prefix: &'static str,
     label: &'static str,
 }
@@ -298,9 +302,16 @@ impl ShortcutDescriptor {
         self.bindings.iter().find(|binding| binding.matches(state))
     }
 
+    fn key_for(&self, state: ShortcutsState) -> Option<KeyBinding> {
+        match self.action {
+            Some(action) => keymap().key_binding(action),
+            None => self.binding_for(state).map(|binding| binding.key),
+        }
+    }
+
     fn overlay_entry(&self, state: ShortcutsState) -> Option<Line<'static>> {
-        let binding = self.binding_for(state)?;
-        let mut line = Line::from(vec![self.prefix.into(), binding.key.into()]);
+        let key = self.key_for(state)?;
+        let mut line = Line::from(vec![self.prefix.into(), key.into()]);
         match self.id {
             ShortcutId::EditPrevious => {
                 if state.esc_backtrack_hint {
@@ -326,6 +337,7 @@ const SHORTCUTS: &[ShortcutDescriptor] = &[
             key: key_hint::plain(KeyCode::Char('/')),
             condition: DisplayCondition::Always,
         }],
+        action: None,
         prefix: "",
         label: " for commands",
     },
@@ -341,6 +353,7 @@ const SHORTCUTS: &[ShortcutDescriptor] = &[
                 condition: DisplayCondition::WhenNotShiftEnterHint,
             },
         ],
+        action: None,
         prefix: "",
         label: " for newline",
     },
@@ -350,6 +363,7 @@ const SHORTCUTS: &[ShortcutDescriptor] = &[
             key: key_hint::plain(KeyCode::Char('@')),
             condition: DisplayCondition::Always,
         }],
+        action: None,
         prefix: "",
         label: " for file paths",
     },
@@ -359,6 +373,7 @@ const SHORTCUTS: &[ShortcutDescriptor] = &[
             key: key_hint::ctrl(KeyCode::Char('v')),
             condition: DisplayCondition::Always,
         }],
+        action: None,
         prefix: "",
         label: " to paste images",
     },
@@ -368,6 +383,7 @@ const SHORTCUTS: &[ShortcutDescriptor] = &[
             key: key_hint::plain(KeyCode::Esc),
             condition: DisplayCondition::Always,
         }],
+        action: None,
         prefix: "",
         label: "",
     },
@@ -377,15 +393,14 @@ const SHORTCUTS: &[ShortcutDescriptor] = &[
             key: key_hint::ctrl(KeyCode::Char('c')),
             condition: DisplayCondition::Always,
         }],
+        action: None,
         prefix: "",
         label: " to exit",
     },
     ShortcutDescriptor {
         id: ShortcutId::ShowTranscript,
-        bindings: &[ShortcutBinding {
-            key: key_hint::ctrl(KeyCode::Char('t')),
-            condition: DisplayCondition::Always,
-        }],
+        bindings: &[],
+        action: Some(KeymapAction::ShowTranscript),
         prefix: "",
         label: " to view transcript",
     },
@@ -1,4 +1,5 @@
 //! Bottom pane: shows the ChatComposer or a BottomPaneView, if one is active.
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::app_event_sender::AppEventSender;
@@ -22,9 +23,11 @@ mod chat_composer;
 mod chat_composer_history;
 mod command_popup;
 pub mod custom_prompt_view;
+mod elicitation_form_view;
 mod file_search_popup;
 mod footer;
 mod list_selection_view;
+mod ollama_pull_view;
 mod prompt_args;
 pub(crate) use list_selection_view::SelectionViewParams;
 mod feedback_view;
@@ -33,7 +36,9 @@ pub mod popup_consts;
 mod scroll_state;
 mod selection_popup_common;
 mod textarea;
+pub(crate) use elicitation_form_view::ElicitationFormView;
 pub(crate) use feedback_view::FeedbackView;
+pub(crate) use ollama_pull_view::OllamaPullView;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum CancellationEvent {
@@ -44,6 +49,7 @@ pub(crate) enum CancellationEvent {
 pub(crate) use chat_composer::ChatComposer;
 pub(crate) use chat_composer::InputResult;
 use codex_protocol::custom_prompts::CustomPrompt;
+use mcp_types::Prompt;
 
 use crate::status_indicator_widget::StatusIndicatorWidget;
 pub(crate) use list_selection_view::SelectionAction;
@@ -400,6 +406,13 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Update the MCP prompts offered as `/server:prompt` commands.
+    pub(crate) fn set_mcp_prompts(&mut self, prompts: HashMap<String, Vec<Prompt>>) {
+        self.composer
+            .set_mcp_prompts(prompt_args::mcp_prompt_commands(prompts));
+        self.request_redraw();
+    }
+
     pub(crate) fn composer_is_empty(&self) -> bool {
         self.composer.is_empty()
     }
@@ -415,6 +428,20 @@ impl BottomPane {
         !self.is_task_running && self.view_stack.is_empty() && !self.composer.popup_active()
     }
 
+    /// Remove the active view if it finished on its own, e.g. after the
+    /// background work it was reporting on ended.
+    pub(crate) fn dismiss_completed_view(&mut self) {
+        if self
+            .view_stack
+            .last()
+            .is_some_and(|view| view.is_complete())
+        {
+            self.view_stack.pop();
+            self.on_active_view_complete();
+            self.request_redraw();
+        }
+    }
+
     pub(crate) fn show_view(&mut self, view: Box<dyn BottomPaneView>) {
         self.push_view(view);
     }
@@ -0,0 +1,160 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use codex_ollama::format_model_size;
+use crossterm::event::KeyCode;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+
+use crate::key_hint;
+use crate::ollama_models::PullProgress;
+use crate::render::renderable::Renderable;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+
+/// Shows the progress of an Ollama model pull. Esc hides the view while the
+/// pull keeps running in the background.
+pub(crate) struct OllamaPullView {
+    model: String,
+    progress: Arc<Mutex<PullProgress>>,
+    hidden: bool,
+}
+
+impl OllamaPullView {
+    pub(crate) fn new(model: String, progress: Arc<Mutex<PullProgress>>) -> Self {
+        Self {
+            model,
+            progress,
+            hidden: false,
+        }
+    }
+
+    fn lines(&self, width: u16) -> Vec<Line<'static>> {
+        let (status, (completed, total)) = match self.progress.lock() {
+            Ok(progress) => (progress.status().map(str::to_string), progress.bytes()),
+            Err(_) => (None, (0, 0)),
+        };
+
+        let mut lines = vec![Line::from(format!("Pulling {}", self.model).bold())];
+        if total > 0 {
+            let label = format!(
+                " {:>3}%  {}/{}",
+                completed.saturating_mul(100) / total,
+                format_model_size(completed),
+                format_model_size(total)
+            );
+            let bar_width = usize::from(width).saturating_sub(label.len() + 2).min(40);
+            lines.push(Line::from(vec![
+                progress_bar(completed, total, bar_width).cyan(),
+                label.into(),
+            ]));
+        }
+        lines.push(Line::from(status.unwrap_or_else(|| "starting…".to_string())).dim());
+        lines.push(Line::from(vec![
+            "Press ".into(),
+            key_hint::plain(KeyCode::Esc).into(),
+            " to hide; the download continues in the background.".into(),
+        ]));
+        lines
+    }
+}
+
+impl BottomPaneView for OllamaPullView {
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.hidden = true;
+        CancellationEvent::Handled
+    }
+
+    fn is_complete(&self) -> bool {
+        self.hidden
+            || self
+                .progress
+                .lock()
+                .map(|progress| progress.is_finished())
+                .unwrap_or(true)
+    }
+}
+
+impl Renderable for OllamaPullView {
+    fn desired_height(&self, width: u16) -> u16 {
+        self.lines(width).len() as u16
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+        Clear.render(area, buf);
+        Paragraph::new(self.lines(area.width)).render(area, buf);
+    }
+}
+
+fn progress_bar(completed: u64, total: u64, width: usize) -> String {
+    let filled = if total == 0 {
+        0
+    } else {
+        (completed.min(total) as f64 / total as f64 * width as f64).round() as usize
+    };
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_ollama::PullEvent;
+    use pretty_assertions::assert_eq;
+
+    fn rendered(view: &OllamaPullView, width: u16) -> Vec<String> {
+        view.lines(width)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn renders_progress_bar_and_hides_on_esc() {
+        let progress = Arc::new(Mutex::new(PullProgress::default()));
+        let mut view = OllamaPullView::new("gpt-oss:20b".to_string(), Arc::clone(&progress));
+        assert_eq!(
+            rendered(&view, 80),
+            vec![
+                "Pulling gpt-oss:20b",
+                "starting…",
+                "Press esc to hide; the download continues in the background.",
+            ]
+        );
+
+        if let Ok(mut progress) = progress.lock() {
+            progress.apply(&PullEvent::Status("pulling 3a5b".to_string()));
+            progress.apply(&PullEvent::ChunkProgress {
+                digest: "sha256:3a5b".to_string(),
+                total: Some(4_000_000_000),
+                completed: Some(1_000_000_000),
+            });
+        }
+        assert_eq!(
+            rendered(&view, 30),
+            vec![
+                "Pulling gpt-oss:20b".to_string(),
+                format!("{}{}  25%  1.0 GB/4.0 GB", "██", "░".repeat(6)),
+                "pulling 3a5b".to_string(),
+                "Press esc to hide; the download continues in the background.".to_string(),
+            ]
+        );
+
+        assert!(!view.is_complete());
+        assert_eq!(view.on_ctrl_c(), CancellationEvent::Handled);
+        assert!(view.is_complete());
+    }
+}
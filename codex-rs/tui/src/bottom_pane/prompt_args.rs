@@ -1,6 +1,7 @@
 use codex_protocol::custom_prompts::CustomPrompt;
 use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use lazy_static::lazy_static;
+use mcp_types::Prompt;
 use regex_lite::Regex;
 use shlex::Shlex;
 use std::collections::HashMap;
@@ -56,6 +57,92 @@ impl PromptExpansionError {
     }
 }
 
+/// A prompt exposed by an MCP server, invoked as `/server:prompt`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct McpPromptCommand {
+    pub server_name: String,
+    pub prompt: Prompt,
+}
+
+impl McpPromptCommand {
+    /// The slash command name without the leading `/`, e.g. `server:prompt`.
+    pub fn command(&self) -> String {
+        format!("{}:{}", self.server_name, self.prompt.name)
+    }
+
+    /// Names of all arguments declared by the prompt, in declaration order.
+    pub fn argument_names(&self) -> Vec<String> {
+        self.prompt
+            .arguments
+            .iter()
+            .flatten()
+            .map(|arg| arg.name.clone())
+            .collect()
+    }
+
+    fn required_argument_names(&self) -> Vec<String> {
+        self.prompt
+            .arguments
+            .iter()
+            .flatten()
+            .filter(|arg| arg.required.unwrap_or(false))
+            .map(|arg| arg.name.clone())
+            .collect()
+    }
+}
+
+/// Flattens the per-server prompt listing into commands sorted by
+/// `server:prompt`. Servers named like the custom prompts prefix are skipped
+/// so that `/prompts:name` always refers to a local prompt.
+pub fn mcp_prompt_commands(prompts: HashMap<String, Vec<Prompt>>) -> Vec<McpPromptCommand> {
+    let mut commands: Vec<McpPromptCommand> = prompts
+        .into_iter()
+        .filter(|(server_name, _)| server_name != PROMPTS_CMD_PREFIX)
+        .flat_map(|(server_name, prompts)| {
+            prompts.into_iter().map(move |prompt| McpPromptCommand {
+                server_name: server_name.clone(),
+                prompt,
+            })
+        })
+        .collect();
+    commands.sort_by_key(McpPromptCommand::command);
+    commands
+}
+
+/// Parses a message of the form `/server:prompt key=value …` that names one of
+/// `commands`.
+///
+/// Returns `Ok(None)` if the text does not name a known MCP prompt. On success
+/// it returns the matching command together with its arguments; otherwise a
+/// descriptive error, e.g. when a required argument is missing.
+pub fn parse_mcp_prompt_invocation<'a>(
+    text: &str,
+    commands: &'a [McpPromptCommand],
+) -> Result<Option<(&'a McpPromptCommand, HashMap<String, String>)>, PromptExpansionError> {
+    let Some((name, rest)) = parse_slash_name(text) else {
+        return Ok(None);
+    };
+    let Some(command) = commands.iter().find(|command| command.command() == name) else {
+        return Ok(None);
+    };
+    let inputs = parse_prompt_inputs(rest).map_err(|error| PromptExpansionError::Args {
+        command: format!("/{name}"),
+        error,
+    })?;
+    let missing: Vec<String> = command
+        .required_argument_names()
+        .into_iter()
+        .filter(|k| !inputs.contains_key(k))
+        .collect();
+    if !missing.is_empty() {
+        return Err(PromptExpansionError::MissingArgs {
+            command: format!("/{name}"),
+            missing,
+        });
+    }
+    Ok(Some((command, inputs)))
+}
+
 /// Parse a first-line slash command of the form `/name <rest>`.
 /// Returns `(name, rest_after_name)` if the line begins with `/` and contains
 /// a non-empty name; otherwise returns `None`.
@@ -299,7 +386,14 @@ pub fn expand_numeric_placeholders(content: &str, args: &[String]) -> String {
 /// Constructs a command text for a custom prompt with arguments.
 /// Returns the text and the cursor position (inside the first double quote).
 pub fn prompt_command_with_arg_placeholders(name: &str, args: &[String]) -> (String, usize) {
-    let mut text = format!("/{PROMPTS_CMD_PREFIX}:{name}");
+    command_with_arg_placeholders(&format!("{PROMPTS_CMD_PREFIX}:{name}"), args)
+}
+
+/// Constructs the text for the slash command `command` (without the leading
+/// `/`) followed by empty `key=""` pairs for `args`.
+/// Returns the text and the cursor position (inside the first double quote).
+pub fn command_with_arg_placeholders(command: &str, args: &[String]) -> (String, usize) {
+    let mut text = format!("/{command}");
     let mut cursor: usize = text.len();
     for (i, arg) in args.iter().enumerate() {
         text.push_str(format!(" {arg}=\"\"").as_str());
@@ -403,4 +497,63 @@ mod tests {
         let out = expand_custom_prompt("/prompts:my-prompt", &prompts).unwrap();
         assert_eq!(out, Some("literal $$USER".to_string()));
     }
+
+    fn review_command() -> McpPromptCommand {
+        McpPromptCommand {
+            server_name: "github".to_string(),
+            prompt: Prompt {
+                arguments: Some(vec![
+                    mcp_types::PromptArgument {
+                        description: None,
+                        name: "pr".to_string(),
+                        required: Some(true),
+                        title: None,
+                    },
+                    mcp_types::PromptArgument {
+                        description: None,
+                        name: "focus".to_string(),
+                        required: None,
+                        title: None,
+                    },
+                ]),
+                description: Some("Review a pull request".to_string()),
+                name: "review".to_string(),
+                title: None,
+            },
+        }
+    }
+
+    #[test]
+    fn mcp_prompt_invocation_parses_arguments() {
+        let commands = vec![review_command()];
+
+        let (command, args) =
+            parse_mcp_prompt_invocation("/github:review pr=42 focus=\"error handling\"", &commands)
+                .unwrap()
+                .unwrap();
+        assert_eq!(command.command(), "github:review");
+        assert_eq!(
+            args,
+            HashMap::from([
+                ("pr".to_string(), "42".to_string()),
+                ("focus".to_string(), "error handling".to_string()),
+            ])
+        );
+
+        assert!(
+            parse_mcp_prompt_invocation("/github:unknown", &commands)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn mcp_prompt_missing_required_args_reports_error() {
+        let commands = vec![review_command()];
+        let err = parse_mcp_prompt_invocation("/github:review focus=tests", &commands)
+            .unwrap_err()
+            .user_message();
+        assert!(err.contains("/github:review"));
+        assert!(err.contains("pr"));
+    }
 }
@@ -3,6 +3,7 @@ use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use codex_core::config::Config;
 use codex_core::config_types::Notifications;
@@ -17,6 +18,12 @@ use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::BudgetWarningEvent;
+use codex_core::protocol::Checkpoint;
+use codex_core::protocol::CheckpointCreatedEvent;
+use codex_core::protocol::CheckpointRestoredEvent;
+use codex_core::protocol::CostSummary;
+use codex_core::protocol::ElicitationRequestEvent;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
@@ -27,13 +34,18 @@ use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::InputItem;
 use codex_core::protocol::InputMessageKind;
 use codex_core::protocol::ListCustomPromptsResponseEvent;
+use codex_core::protocol::McpListChangedEvent;
+use codex_core::protocol::McpListKind;
 use codex_core::protocol::McpListToolsResponseEvent;
+use codex_core::protocol::McpServerHealth;
+use codex_core::protocol::McpServerStatusEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_core::protocol::ReviewRequest;
+use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_core::protocol::TokenUsage;
@@ -71,7 +83,9 @@ use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::BottomPane;
 use crate::bottom_pane::BottomPaneParams;
 use crate::bottom_pane::CancellationEvent;
+use crate::bottom_pane::ElicitationFormView;
 use crate::bottom_pane::InputResult;
+use crate::bottom_pane::OllamaPullView;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
@@ -88,6 +102,10 @@ use crate::history_cell::AgentMessageCell;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::McpToolCallCell;
 use crate::markdown::append_markdown;
+use crate::ollama_models::OllamaModelChoice;
+use crate::ollama_models::PullProgress;
+use crate::ollama_models::load_model_choices;
+use crate::ollama_models::spawn_pull;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
 use crate::slash_command::SlashCommand;
@@ -115,26 +133,21 @@ use codex_common::approval_presets::builtin_approval_presets;
 use codex_common::model_presets::ModelPreset;
 use codex_common::model_presets::builtin_model_presets;
 use codex_core::AuthManager;
+use codex_core::BUILT_IN_OSS_MODEL_PROVIDER_ID;
 use codex_core::ConversationManager;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol_config_types::ReasoningEffort as ReasoningEffortConfig;
 use codex_file_search::FileMatch;
-use codex_git_tooling::CreateGhostCommitOptions;
-use codex_git_tooling::GhostCommit;
-use codex_git_tooling::GitToolingError;
-use codex_git_tooling::create_ghost_commit;
-use codex_git_tooling::restore_ghost_commit;
 use codex_multi_agent::AgentId;
 use codex_multi_agent::DelegateSessionMode;
 use codex_multi_agent::DelegateSessionSummary;
 use codex_multi_agent::DetachedRunStatusSummary;
 use codex_multi_agent::DetachedRunSummary;
+use codex_ollama::format_model_size;
 use codex_protocol::plan_tool::UpdatePlanArgs;
 use strum::IntoEnumIterator;
 
-const MAX_TRACKED_GHOST_COMMITS: usize = 20;
-
 // Track information about an in-flight exec command.
 struct RunningCommand {
     command: Vec<String>,
@@ -260,6 +273,7 @@ pub(crate) struct ChatWidget {
     session_header: SessionHeader,
     initial_user_message: Option<UserMessage>,
     token_info: Option<TokenUsageInfo>,
+    cost_summary: Option<CostSummary>,
     rate_limit_snapshot: Option<RateLimitSnapshotDisplay>,
     rate_limit_warnings: RateLimitWarningState,
     // Stream lifecycle controller
@@ -289,9 +303,8 @@ pub(crate) struct ChatWidget {
     pending_notification: Option<Notification>,
     // Simple review mode flag; used to adjust layout and banners.
     is_review_mode: bool,
-    // List of ghost commits corresponding to each turn.
-    ghost_snapshots: Vec<GhostCommit>,
-    ghost_snapshots_disabled: bool,
+    // Workspace checkpoints reported by core, oldest first.
+    checkpoints: Vec<Checkpoint>,
     // Whether to add a final message separator after the last message
     needs_final_message_separator: bool,
 
@@ -681,6 +694,13 @@ impl ChatWidget {
         );
     }
 
+    fn on_elicitation_request(&mut self, ev: ElicitationRequestEvent) {
+        self.flush_answer_stream_with_separator();
+        let view = ElicitationFormView::new(ev, self.app_event_tx.clone());
+        self.bottom_pane.show_view(Box::new(view));
+        self.request_redraw();
+    }
+
     fn on_exec_command_begin(&mut self, ev: ExecCommandBeginEvent) {
         self.flush_answer_stream_with_separator();
         let ev2 = ev.clone();
@@ -766,6 +786,49 @@ impl ChatWidget {
         debug!("TurnDiffEvent: {unified_diff}");
     }
 
+    fn on_mcp_list_changed(&mut self, ev: McpListChangedEvent) {
+        let McpListChangedEvent {
+            server_name,
+            kind,
+            count,
+        } = ev;
+        let message = match count {
+            Some(count) => {
+                let noun = match (kind, count) {
+                    (McpListKind::Tools, 1) => "tool",
+                    (McpListKind::Tools, _) => "tools",
+                    (McpListKind::Resources, 1) => "resource",
+                    (McpListKind::Resources, _) => "resources",
+                    (McpListKind::Prompts, 1) => "prompt",
+                    (McpListKind::Prompts, _) => "prompts",
+                };
+                format!("MCP server `{server_name}` now exposes {count} {noun}")
+            }
+            None => format!("MCP server `{server_name}` updated its {kind}"),
+        };
+        self.add_to_history(history_cell::new_info_event(message, None));
+        if kind == McpListKind::Prompts {
+            self.submit_op(Op::ListCustomPrompts);
+        }
+        self.request_redraw();
+    }
+
+    fn on_mcp_server_status(&mut self, ev: McpServerStatusEvent) {
+        let message = ev.to_string();
+        match ev.status.health {
+            McpServerHealth::Ready => {
+                self.add_to_history(history_cell::new_info_event(message, None));
+            }
+            McpServerHealth::Restarting => {
+                self.add_to_history(history_cell::new_warning_event(message));
+            }
+            McpServerHealth::Failed => {
+                self.add_to_history(history_cell::new_error_event(message));
+            }
+        }
+        self.request_redraw();
+    }
+
     fn on_background_event(&mut self, message: String) {
         debug!("BackgroundEvent: {message}");
     }
@@ -1072,6 +1135,7 @@ impl ChatWidget {
                 initial_images,
             ),
             token_info: None,
+            cost_summary: None,
             rate_limit_snapshot: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             stream_controller: None,
@@ -1088,8 +1152,7 @@ impl ChatWidget {
             suppress_session_configured_redraw: false,
             pending_notification: None,
             is_review_mode: false,
-            ghost_snapshots: Vec::new(),
-            ghost_snapshots_disabled: true,
+            checkpoints: Vec::new(),
             needs_final_message_separator: false,
             delegate_run: None,
             delegate_runs_with_stream: HashSet::new(),
@@ -1147,6 +1210,7 @@ impl ChatWidget {
                 initial_images,
             ),
             token_info: None,
+            cost_summary: None,
             rate_limit_snapshot: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             stream_controller: None,
@@ -1163,8 +1227,7 @@ impl ChatWidget {
             suppress_session_configured_redraw: true,
             pending_notification: None,
             is_review_mode: false,
-            ghost_snapshots: Vec::new(),
-            ghost_snapshots_disabled: true,
+            checkpoints: Vec::new(),
             needs_final_message_separator: false,
             delegate_run: None,
             delegate_runs_with_stream: HashSet::new(),
@@ -1250,6 +1313,14 @@ impl ChatWidget {
                     InputResult::CommandWithArgs(cmd, args) => {
                         self.dispatch_command(cmd, Some(args));
                     }
+                    InputResult::McpPrompt {
+                        server_name,
+                        prompt_name,
+                        arguments,
+                        text,
+                    } => {
+                        self.submit_mcp_prompt(server_name, prompt_name, arguments, text);
+                    }
                     InputResult::None => {}
                 }
             }
@@ -1339,7 +1410,11 @@ impl ChatWidget {
                 self.app_event_tx.send(AppEvent::ExitRequest);
             }
             SlashCommand::Logout => {
-                if let Err(e) = codex_core::auth::logout(&self.config.codex_home) {
+                if let Err(e) = codex_core::auth::logout_profile(
+                    &self.config.codex_home,
+                    self.config.auth_profile.as_deref(),
+                    self.config.cli_auth_credentials_store_mode,
+                ) {
                     tracing::error!("failed to logout: {e}");
                 }
                 self.app_event_tx.send(AppEvent::ExitRequest);
@@ -1399,6 +1474,7 @@ impl ChatWidget {
                                 PathBuf::from("/tmp/test.txt"),
                                 FileChange::Add {
                                     content: "test".to_string(),
+                                    mode: None,
                                 },
                             ),
                             (
@@ -1406,6 +1482,7 @@ impl ChatWidget {
                                 FileChange::Update {
                                     unified_diff: "+test\n-test2".to_string(),
                                     move_path: None,
+                                    mode: None,
                                 },
                             ),
                         ]),
@@ -1484,8 +1561,6 @@ impl ChatWidget {
             return;
         }
 
-        self.capture_ghost_snapshot();
-
         if self.delegate_context.is_none()
             && !self.pending_delegate_context.is_empty()
             && !text.trim().is_empty()
@@ -1530,54 +1605,49 @@ impl ChatWidget {
         self.needs_final_message_separator = false;
     }
 
-    fn capture_ghost_snapshot(&mut self) {
-        if self.ghost_snapshots_disabled {
-            return;
-        }
-
-        let options = CreateGhostCommitOptions::new(&self.config.cwd);
-        match create_ghost_commit(&options) {
-            Ok(commit) => {
-                self.ghost_snapshots.push(commit);
-                if self.ghost_snapshots.len() > MAX_TRACKED_GHOST_COMMITS {
-                    self.ghost_snapshots.remove(0);
-                }
-            }
-            Err(err) => {
-                self.ghost_snapshots_disabled = true;
-                let (message, hint) = match &err {
-                    GitToolingError::NotAGitRepository { .. } => (
-                        "Snapshots disabled: current directory is not a Git repository."
-                            .to_string(),
-                        None,
-                    ),
-                    _ => (
-                        format!("Snapshots disabled after error: {err}"),
-                        Some(
-                            "Restart Codex after resolving the issue to re-enable snapshots."
-                                .to_string(),
-                        ),
-                    ),
-                };
-                self.add_info_message(message, hint);
-                tracing::warn!("failed to create ghost snapshot: {err}");
-            }
-        }
+    /// Ask core to expand an MCP prompt; its messages become the user turn.
+    fn submit_mcp_prompt(
+        &mut self,
+        server_name: String,
+        prompt_name: String,
+        arguments: HashMap<String, String>,
+        text: String,
+    ) {
+        self.submit_op(Op::RunMcpPrompt {
+            server_name,
+            prompt_name,
+            arguments,
+        });
+        self.submit_op(Op::AddToHistory { text: text.clone() });
+        self.add_to_history(history_cell::new_user_prompt(text));
+        self.needs_final_message_separator = false;
     }
 
     fn undo_last_snapshot(&mut self) {
-        let Some(commit) = self.ghost_snapshots.pop() else {
+        let Some(checkpoint) = self.checkpoints.last() else {
             self.add_info_message("No snapshot available to undo.".to_string(), None);
             return;
         };
 
-        if let Err(err) = restore_ghost_commit(&self.config.cwd, &commit) {
-            self.add_error_message(format!("Failed to restore snapshot: {err}"));
-            self.ghost_snapshots.push(commit);
+        self.submit_op(Op::RestoreCheckpoint {
+            checkpoint_id: checkpoint.id.clone(),
+        });
+    }
+
+    fn on_checkpoint_created(&mut self, checkpoint: Checkpoint) {
+        self.checkpoints.push(checkpoint);
+    }
+
+    fn on_checkpoint_restored(&mut self, checkpoint: Checkpoint, from_replay: bool) {
+        // Mirror core: the restored checkpoint and later ones describe states
+        // that were just discarded.
+        if let Some(pos) = self.checkpoints.iter().position(|c| c.id == checkpoint.id) {
+            self.checkpoints.truncate(pos);
+        }
+        if from_replay {
             return;
         }
-
-        let short_id: String = commit.id().chars().take(8).collect();
+        let short_id: String = checkpoint.id.chars().take(8).collect();
         self.add_info_message(format!("Restored workspace to snapshot {short_id}"), None);
     }
 
@@ -1639,6 +1709,12 @@ impl ChatWidget {
             EventMsg::TokenCount(ev) => {
                 self.set_token_info(ev.info);
                 self.on_rate_limit_snapshot(ev.rate_limits);
+                if ev.cost.is_some() {
+                    self.cost_summary = ev.cost;
+                }
+            }
+            EventMsg::BudgetWarning(BudgetWarningEvent { message }) => {
+                self.add_to_history(history_cell::new_warning_event(message));
             }
             EventMsg::Error(ErrorEvent { message }) => self.on_error(message),
             EventMsg::TurnAborted(ev) => match ev.reason {
@@ -1660,6 +1736,7 @@ impl ChatWidget {
             EventMsg::ApplyPatchApprovalRequest(ev) => {
                 self.on_apply_patch_approval_request(id.unwrap_or_default(), ev)
             }
+            EventMsg::ElicitationRequest(ev) => self.on_elicitation_request(ev),
             EventMsg::ExecCommandBegin(ev) => self.on_exec_command_begin(ev),
             EventMsg::ExecCommandOutputDelta(delta) => self.on_exec_command_output_delta(delta),
             EventMsg::PatchApplyBegin(ev) => self.on_patch_apply_begin(ev),
@@ -1672,6 +1749,8 @@ impl ChatWidget {
             EventMsg::WebSearchEnd(ev) => self.on_web_search_end(ev),
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
+            EventMsg::McpListChanged(ev) => self.on_mcp_list_changed(ev),
+            EventMsg::McpServerStatus(ev) => self.on_mcp_server_status(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
             EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => self.on_turn_diff(unified_diff),
@@ -1692,6 +1771,13 @@ impl ChatWidget {
                 self.on_entered_review_mode(review_request)
             }
             EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review),
+            EventMsg::CheckpointCreated(CheckpointCreatedEvent { checkpoint }) => {
+                self.on_checkpoint_created(checkpoint)
+            }
+            EventMsg::CheckpointRestored(CheckpointRestoredEvent { checkpoint }) => {
+                self.on_checkpoint_restored(checkpoint, from_replay)
+            }
+            EventMsg::ListCheckpointsResponse(_) => {}
         }
     }
 
@@ -1850,12 +1936,26 @@ impl ChatWidget {
             context_usage,
             &self.conversation_id,
             self.rate_limit_snapshot.as_ref(),
+            self.cost_summary.as_ref(),
         ));
     }
 
     /// Open a popup to choose the model (stage 1). After selecting a model,
     /// a second popup is shown to choose the reasoning effort.
     pub(crate) fn open_model_popup(&mut self) {
+        if self.config.model_provider_id == BUILT_IN_OSS_MODEL_PROVIDER_ID {
+            // Local models come from the running Ollama instance, not the presets.
+            let config = self.config.clone();
+            let tx = self.app_event_tx.clone();
+            tokio::spawn(async move {
+                let result = load_model_choices(&config)
+                    .await
+                    .map_err(|err| err.to_string());
+                tx.send(AppEvent::OllamaModelsLoaded(result));
+            });
+            return;
+        }
+
         let current_model = self.config.model.clone();
         let auth_mode = self.auth_manager.auth().map(|auth| auth.mode);
         let presets: Vec<ModelPreset> = builtin_model_presets(auth_mode);
@@ -1910,6 +2010,97 @@ impl ChatWidget {
         });
     }
 
+    /// Open the `/model` picker for the built-in `oss` provider. Models that
+    /// are not downloaded yet are pulled when selected.
+    pub(crate) fn open_ollama_model_popup(
+        &mut self,
+        choices: Result<Vec<OllamaModelChoice>, String>,
+    ) {
+        let choices = match choices {
+            Ok(choices) => choices,
+            Err(err) => {
+                self.add_error_message(format!("Failed to list Ollama models: {err}"));
+                return;
+            }
+        };
+
+        let current_model = self.config.model.clone();
+        let effort = self.config.model_reasoning_effort;
+        let mut items: Vec<SelectionItem> = Vec::new();
+        for choice in choices {
+            let description = if choice.downloaded {
+                let mut parts = Vec::new();
+                if let Some(size) = choice.size {
+                    parts.push(format_model_size(size));
+                }
+                if let Some(context_length) = choice.context_length {
+                    parts.push(format!("{context_length} context"));
+                }
+                (!parts.is_empty()).then(|| parts.join(" · "))
+            } else {
+                Some("not downloaded — select to pull".to_string())
+            };
+            let model = choice.name.clone();
+            let actions: Vec<SelectionAction> = if choice.downloaded {
+                vec![Box::new(move |tx| {
+                    send_model_switch(tx, model.clone(), effort);
+                })]
+            } else {
+                vec![Box::new(move |tx| {
+                    tx.send(AppEvent::PullOllamaModel(model.clone()));
+                })]
+            };
+            items.push(SelectionItem {
+                is_current: choice.name == current_model,
+                name: choice.name,
+                description,
+                actions,
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Select Ollama Model".to_string()),
+            subtitle: Some("Models available to the local Ollama instance".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Start pulling `model` and show its progress in the bottom pane.
+    pub(crate) fn start_ollama_pull(&mut self, model: String) {
+        let progress = Arc::new(Mutex::new(PullProgress::default()));
+        self.bottom_pane.show_view(Box::new(OllamaPullView::new(
+            model.clone(),
+            Arc::clone(&progress),
+        )));
+        spawn_pull(
+            self.config.clone(),
+            model,
+            progress,
+            self.frame_requester.clone(),
+            self.app_event_tx.clone(),
+        );
+    }
+
+    /// Switch to a freshly pulled model, or report why the pull failed.
+    pub(crate) fn on_ollama_pull_finished(&mut self, model: String, result: Result<(), String>) {
+        self.bottom_pane.dismiss_completed_view();
+        match result {
+            Ok(()) => {
+                self.add_info_message(format!("Pulled {model}; switching to it."), None);
+                send_model_switch(
+                    &self.app_event_tx,
+                    model,
+                    self.config.model_reasoning_effort,
+                );
+            }
+            Err(err) => self.add_error_message(format!("Failed to pull {model}: {err}")),
+        }
+    }
+
     /// Open a popup to choose the reasoning effort (stage 2) for the given model.
     pub(crate) fn open_reasoning_popup(&mut self, model_slug: String, presets: Vec<ModelPreset>) {
         let default_effort = ReasoningEffortConfig::default();
@@ -2534,6 +2725,7 @@ impl ChatWidget {
             ev.resources,
             ev.resource_templates,
             &ev.auth_statuses,
+            &ev.server_statuses,
         ));
     }
 
@@ -2542,6 +2734,7 @@ impl ChatWidget {
         debug!("received {len} custom prompts");
         // Forward to bottom pane so the slash popup can show them now.
         self.bottom_pane.set_custom_prompts(ev.custom_prompts);
+        self.bottom_pane.set_mcp_prompts(ev.mcp_prompts);
     }
 
     pub(crate) fn open_review_popup(&mut self) {
@@ -2568,6 +2761,7 @@ impl ChatWidget {
                         review_request: ReviewRequest {
                             prompt: "Review the current code changes (staged, unstaged, and untracked files) and provide prioritized findings.".to_string(),
                             user_facing_hint: "current changes".to_string(),
+                            target: Some(ReviewTarget::UncommittedChanges),
                         },
                     }));
                 },
@@ -2621,9 +2815,12 @@ impl ChatWidget {
                     tx3.send(AppEvent::CodexOp(Op::Review {
                         review_request: ReviewRequest {
                             prompt: format!(
-                                "Review the code changes against the base branch '{branch}'. Start by finding the merge diff between the current branch and {branch}'s upstream e.g. (`git merge-base HEAD \"$(git rev-parse --abbrev-ref \"{branch}@{{upstream}}\")\"`), then run `git diff` against that SHA to see what changes we would merge into the {branch} branch. Provide prioritized, actionable findings."
+                                "Review the code changes against the base branch '{branch}', i.e. what we would merge into the {branch} branch. Provide prioritized, actionable findings."
                             ),
                             user_facing_hint: format!("changes against '{branch}'"),
+                            target: Some(ReviewTarget::BaseBranch {
+                                branch: branch.clone(),
+                            }),
                         },
                     }));
                 })],
@@ -2664,6 +2861,7 @@ impl ChatWidget {
                         review_request: ReviewRequest {
                             prompt,
                             user_facing_hint: hint,
+                            target: Some(ReviewTarget::Commit { sha: sha.clone() }),
                         },
                     }));
                 })],
@@ -2698,6 +2896,7 @@ impl ChatWidget {
                     review_request: ReviewRequest {
                         prompt: trimmed.clone(),
                         user_facing_hint: trimmed,
+                        target: None,
                     },
                 }));
             }),
@@ -2919,6 +3118,7 @@ impl ChatWidget {
 
     pub(crate) fn clear_token_usage(&mut self) {
         self.token_info = None;
+        self.cost_summary = None;
     }
 
     pub fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
@@ -3031,6 +3231,20 @@ const EXAMPLE_PROMPTS: [&str; 6] = [
     "Improve documentation in @filename",
 ];
 
+/// Switch the session to a local Ollama model and remember the choice.
+fn send_model_switch(tx: &AppEventSender, model: String, effort: Option<ReasoningEffortConfig>) {
+    tx.send(AppEvent::CodexOp(Op::OverrideTurnContext {
+        cwd: None,
+        approval_policy: None,
+        sandbox_policy: None,
+        model: Some(model.clone()),
+        effort: None,
+        summary: None,
+    }));
+    tx.send(AppEvent::UpdateModel(model.clone()));
+    tx.send(AppEvent::PersistModelSelection { model, effort });
+}
+
 // Extract the first bold (Markdown) element in the form **...** from `s`.
 // Returns the inner text if found; otherwise `None`.
 fn extract_first_bold(s: &str) -> Option<String> {
@@ -3084,6 +3298,7 @@ pub(crate) fn show_review_commit_picker_with_entries(
                     review_request: ReviewRequest {
                         prompt,
                         user_facing_hint: hint,
+                        target: Some(ReviewTarget::Commit { sha: sha.clone() }),
                     },
                 }));
             })],
@@ -154,6 +154,7 @@ fn entered_review_mode_uses_request_hint() {
         msg: EventMsg::EnteredReviewMode(ReviewRequest {
             prompt: "Review the latest changes".to_string(),
             user_facing_hint: "feature branch".to_string(),
+            target: None,
         }),
     });
 
@@ -173,6 +174,7 @@ fn entered_review_mode_defaults_to_current_changes_banner() {
         msg: EventMsg::EnteredReviewMode(ReviewRequest {
             prompt: "Review the current changes".to_string(),
             user_facing_hint: "current changes".to_string(),
+            target: None,
         }),
     });
 
@@ -274,6 +276,7 @@ fn make_chatwidget_manual() -> (
         session_header: SessionHeader::new(cfg.model),
         initial_user_message: None,
         token_info: None,
+        cost_summary: None,
         rate_limit_snapshot: None,
         rate_limit_warnings: RateLimitWarningState::default(),
         stream_controller: None,
@@ -291,8 +294,7 @@ fn make_chatwidget_manual() -> (
         suppress_session_configured_redraw: false,
         pending_notification: None,
         is_review_mode: false,
-        ghost_snapshots: Vec::new(),
-        ghost_snapshots_disabled: false,
+        checkpoints: Vec::new(),
         needs_final_message_separator: false,
         delegate_run: None,
         delegate_runs_with_stream: HashSet::new(),
@@ -1595,6 +1597,7 @@ fn approval_modal_patch_snapshot() {
         PathBuf::from("README.md"),
         FileChange::Add {
             content: "hello\nworld\n".into(),
+            mode: None,
         },
     );
     let ev = ApplyPatchApprovalRequestEvent {
@@ -1829,6 +1832,7 @@ fn apply_patch_events_emit_history_cells() {
         PathBuf::from("foo.txt"),
         FileChange::Add {
             content: "hello\n".to_string(),
+            mode: None,
         },
     );
     let ev = ApplyPatchApprovalRequestEvent {
@@ -1869,6 +1873,7 @@ fn apply_patch_events_emit_history_cells() {
         PathBuf::from("foo.txt"),
         FileChange::Add {
             content: "hello\n".to_string(),
+            mode: None,
         },
     );
     let begin = PatchApplyBeginEvent {
@@ -1894,6 +1899,7 @@ fn apply_patch_events_emit_history_cells() {
         stdout: "ok\n".into(),
         stderr: String::new(),
         success: true,
+        committed: vec![PathBuf::from("foo.txt")],
     };
     chat.handle_codex_event(Event {
         id: "s1".into(),
@@ -1915,6 +1921,7 @@ fn apply_patch_manual_approval_adjusts_header() {
         PathBuf::from("foo.txt"),
         FileChange::Add {
             content: "hello\n".to_string(),
+            mode: None,
         },
     );
     chat.handle_codex_event(Event {
@@ -1933,6 +1940,7 @@ fn apply_patch_manual_approval_adjusts_header() {
         PathBuf::from("foo.txt"),
         FileChange::Add {
             content: "hello\n".to_string(),
+            mode: None,
         },
     );
     chat.handle_codex_event(Event {
@@ -1962,6 +1970,7 @@ fn apply_patch_manual_flow_snapshot() {
         PathBuf::from("foo.txt"),
         FileChange::Add {
             content: "hello\n".to_string(),
+            mode: None,
         },
     );
     chat.handle_codex_event(Event {
@@ -1984,6 +1993,7 @@ fn apply_patch_manual_flow_snapshot() {
         PathBuf::from("foo.txt"),
         FileChange::Add {
             content: "hello\n".to_string(),
+            mode: None,
         },
     );
     chat.handle_codex_event(Event {
@@ -2013,6 +2023,7 @@ fn apply_patch_approval_sends_op_with_submission_id() {
         PathBuf::from("file.rs"),
         FileChange::Add {
             content: "fn main(){}\n".into(),
+            mode: None,
         },
     );
     let ev = ApplyPatchApprovalRequestEvent {
@@ -2050,7 +2061,10 @@ fn apply_patch_full_flow_integration_like() {
     let mut changes = HashMap::new();
     changes.insert(
         PathBuf::from("pkg.rs"),
-        FileChange::Add { content: "".into() },
+        FileChange::Add {
+            content: "".into(),
+            mode: None,
+        },
     );
     chat.handle_codex_event(Event {
         id: "sub-xyz".into(),
@@ -2090,7 +2104,10 @@ fn apply_patch_full_flow_integration_like() {
     let mut changes2 = HashMap::new();
     changes2.insert(
         PathBuf::from("pkg.rs"),
-        FileChange::Add { content: "".into() },
+        FileChange::Add {
+            content: "".into(),
+            mode: None,
+        },
     );
     chat.handle_codex_event(Event {
         id: "sub-xyz".into(),
@@ -2107,6 +2124,7 @@ fn apply_patch_full_flow_integration_like() {
             stdout: String::from("ok"),
             stderr: String::new(),
             success: true,
+            committed: Vec::new(),
         }),
     });
 }
@@ -2121,7 +2139,10 @@ fn apply_patch_untrusted_shows_approval_modal() {
     let mut changes = HashMap::new();
     changes.insert(
         PathBuf::from("a.rs"),
-        FileChange::Add { content: "".into() },
+        FileChange::Add {
+            content: "".into(),
+            mode: None,
+        },
     );
     chat.handle_codex_event(Event {
         id: "sub-1".into(),
@@ -2169,6 +2190,7 @@ fn apply_patch_request_shows_diff_summary() {
         FileChange::Add {
             // Two lines (no trailing empty line counted)
             content: "line one\nline two\n".into(),
+            mode: None,
         },
     );
     chat.handle_codex_event(Event {
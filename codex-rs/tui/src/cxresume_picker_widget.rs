@@ -1,3 +1,6 @@
+use crate::keymap::KeymapAction;
+use crate::keymap::KeymapContext;
+use crate::keymap::keymap;
 use crate::pager_overlay::Overlay;
 use crate::render::line_utils;
 use codex_ansi_escape::ansi_escape_line;
@@ -1053,37 +1056,39 @@ impl PickerState {
     }
 
     /// Convert KeyEvent to PickerEvent (for integration with Overlay)
-    pub fn key_to_event(&self, key_code: crossterm::event::KeyCode) -> Option<PickerEvent> {
+    pub fn key_to_event(&self, key_event: &crossterm::event::KeyEvent) -> Option<PickerEvent> {
         use crossterm::event::KeyCode;
 
         if self.modal_active {
-            return match key_code {
+            return match key_event.code {
                 KeyCode::Char('y') | KeyCode::Enter => Some(PickerEvent::ConfirmAction),
                 KeyCode::Char('n') | KeyCode::Esc => Some(PickerEvent::CancelAction),
                 _ => None,
             };
         }
 
-        match key_code {
-            KeyCode::Up => Some(PickerEvent::SelectPrev),
-            KeyCode::Down => Some(PickerEvent::SelectNext),
-            KeyCode::Home => Some(PickerEvent::SelectFirst),
-            KeyCode::End => Some(PickerEvent::SelectLast),
-            KeyCode::PageUp => Some(PickerEvent::PagePrev),
-            KeyCode::PageDown => Some(PickerEvent::PageNext),
+        if let Some(action) = keymap().action_for(KeymapContext::Picker, key_event) {
+            return match action {
+                KeymapAction::PickerUp => Some(PickerEvent::SelectPrev),
+                KeymapAction::PickerDown => Some(PickerEvent::SelectNext),
+                KeymapAction::PickerFirst => Some(PickerEvent::SelectFirst),
+                KeymapAction::PickerLast => Some(PickerEvent::SelectLast),
+                KeymapAction::PickerPageUp => Some(PickerEvent::PagePrev),
+                KeymapAction::PickerPageDown => Some(PickerEvent::PageNext),
+                KeymapAction::PickerResume => Some(PickerEvent::Resume),
+                KeymapAction::PickerDelete => Some(PickerEvent::Delete),
+                KeymapAction::PickerCycleView => Some(PickerEvent::CycleViewMode),
+                KeymapAction::PickerCopyId => Some(PickerEvent::CopySessionId),
+                KeymapAction::PickerNewSession => Some(PickerEvent::NewSession),
+                KeymapAction::PickerRefresh => Some(PickerEvent::Refresh),
+                KeymapAction::PickerClose => Some(PickerEvent::Exit),
+                _ => None,
+            };
+        }
+
+        match key_event.code {
             KeyCode::Left => Some(PickerEvent::FocusLeft),
             KeyCode::Right => Some(PickerEvent::FocusRight),
-
-            KeyCode::Enter => Some(PickerEvent::Resume),
-            KeyCode::Char('d') => Some(PickerEvent::Delete),
-            KeyCode::Char('f') => Some(PickerEvent::CycleViewMode),
-            KeyCode::Char('c') => Some(PickerEvent::CopySessionId),
-            KeyCode::Char('n') => Some(PickerEvent::NewSession),
-            KeyCode::Char('r') => Some(PickerEvent::Refresh),
-
-            KeyCode::Char('q') => Some(PickerEvent::Exit),
-            KeyCode::Esc => Some(PickerEvent::Exit),
-
             _ => None,
         }
     }
@@ -2601,15 +2606,52 @@ fn build_footer_lines(state: &PickerState) -> Vec<Line<'static>> {
     lines
         .push(Line::from("────────────────────────────────────────────────────────────────").dim());
     lines.push(Line::from("Keyboard Shortcuts:").bold());
-    lines.push(ansi_escape_line(
-        "  ↑↓      Navigate sessions       Page↑/↓  Page jump       ←→      Switch focus",
-    ));
-    lines.push(ansi_escape_line(
-        "  Enter   Resume session         d      Delete              f        Full preview",
-    ));
-    lines.push(ansi_escape_line(
-        "  n       New session            c      Copy ID             q/Esc    Close",
-    ));
+    lines.extend(picker_shortcut_lines());
     lines.push(Line::from(""));
     lines
 }
+
+/// The shortcut legend under the picker, with keys from the effective keymap.
+fn picker_shortcut_lines() -> Vec<Line<'static>> {
+    let keymap = keymap();
+    let keys = |actions: &[KeymapAction]| {
+        actions
+            .iter()
+            .map(|&action| keymap.label(action))
+            .filter(|label| !label.is_empty())
+            .collect::<Vec<_>>()
+            .join("/")
+    };
+    let rows = [
+        [
+            (
+                keys(&[KeymapAction::PickerUp, KeymapAction::PickerDown]),
+                "Navigate sessions",
+            ),
+            (
+                keys(&[KeymapAction::PickerPageUp, KeymapAction::PickerPageDown]),
+                "Page jump",
+            ),
+            ("←/→".to_string(), "Switch focus"),
+        ],
+        [
+            (keys(&[KeymapAction::PickerResume]), "Resume session"),
+            (keys(&[KeymapAction::PickerDelete]), "Delete"),
+            (keys(&[KeymapAction::PickerCycleView]), "Full preview"),
+        ],
+        [
+            (keys(&[KeymapAction::PickerNewSession]), "New session"),
+            (keys(&[KeymapAction::PickerCopyId]), "Copy ID"),
+            (keys(&[KeymapAction::PickerClose]), "Close"),
+        ],
+    ];
+    rows.iter()
+        .map(|row| {
+            let text: String = row
+                .iter()
+                .map(|(key, desc)| format!("{key:<10}{desc:<21}"))
+                .collect();
+            ansi_escape_line(&format!("  {}", text.trim_end()))
+        })
+        .collect()
+}
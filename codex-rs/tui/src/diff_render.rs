@@ -100,7 +100,8 @@ fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
     let mut rows: Vec<Row> = Vec::new();
     for (path, change) in changes.iter() {
         let (added, removed) = match change {
-            FileChange::Add { content } => (content.lines().count(), 0),
+            FileChange::Add { content, .. } => (content.lines().count(), 0),
+            FileChange::AddBinary { .. } | FileChange::AddSymlink { .. } => (0, 0),
             FileChange::Delete { content } => (0, content.lines().count()),
             FileChange::Update { unified_diff, .. } => calculate_add_remove_from_diff(unified_diff),
         };
@@ -153,7 +154,9 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
     let mut header_spans: Vec<RtSpan<'static>> = vec!["• ".dim()];
     if let [row] = &rows[..] {
         let verb = match &row.change {
-            FileChange::Add { .. } => "Added",
+            FileChange::Add { .. }
+            | FileChange::AddBinary { .. }
+            | FileChange::AddSymlink { .. } => "Added",
             FileChange::Delete { .. } => "Deleted",
             _ => "Edited",
         };
@@ -193,9 +196,31 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
     out
 }
 
+/// Describes the parts of a change that a line diff cannot show.
+fn change_note(change: &FileChange) -> Option<String> {
+    match change {
+        FileChange::Add {
+            mode: Some(mode), ..
+        } => Some(format!("mode {mode:o}")),
+        FileChange::AddBinary { size, mode } => Some(match mode {
+            Some(mode) => format!("binary file, {size} bytes, mode {mode:o}"),
+            None => format!("binary file, {size} bytes"),
+        }),
+        FileChange::AddSymlink { target } => Some(format!("symlink to {}", target.display())),
+        FileChange::Update {
+            mode: Some(mode), ..
+        } => Some(format!("mode → {mode:o}")),
+        _ => None,
+    }
+}
+
 fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usize) {
+    if let Some(note) = change_note(change) {
+        out.push(RtLine::from(note.dim()));
+    }
     match change {
-        FileChange::Add { content } => {
+        FileChange::AddBinary { .. } | FileChange::AddSymlink { .. } => {}
+        FileChange::Add { content, .. } => {
             let line_number_width = line_number_width(content.lines().count());
             for (i, raw) in content.lines().enumerate() {
                 out.extend(push_wrapped_diff_line(
@@ -484,6 +509,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: None,
+                mode: None,
             },
         );
 
@@ -504,6 +530,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: Some(PathBuf::from("new_name.rs")),
+                mode: None,
             },
         );
 
@@ -524,6 +551,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch_a,
                 move_path: None,
+                mode: None,
             },
         );
 
@@ -532,6 +560,7 @@ mod tests {
             PathBuf::from("b.txt"),
             FileChange::Add {
                 content: "new\n".to_string(),
+                mode: None,
             },
         );
 
@@ -547,6 +576,7 @@ mod tests {
             PathBuf::from("new_file.txt"),
             FileChange::Add {
                 content: "alpha\nbeta\n".to_string(),
+                mode: None,
             },
         );
 
@@ -590,6 +620,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: None,
+                mode: None,
             },
         );
 
@@ -613,6 +644,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: None,
+                mode: None,
             },
         );
 
@@ -640,6 +672,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: None,
+                mode: None,
             },
         );
 
@@ -663,6 +696,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: Some(abs_new),
+                mode: None,
             },
         );
 
@@ -1,73 +1,963 @@
-use crate::pager_overlay::Overlay;
-use codex_ansi_escape::ansi_escape_line;
-use ratatui::style::Stylize;
-use ratatui::text::Line;
+//! The Ctrl+G git graph overlay.
+//!
+//! Layout comes from the embedded `git_graph` crate: [`GitGraph`] assigns
+//! commits to branch lanes and `print_unicode` draws the lanes with rounded
+//! connectors. Next to each lane this module keeps the commit's metadata so
+//! the overlay can move a cursor between commits, expand a commit's diff,
+//! filter by branch or author, jump between TUMIX `round1-*` branches and
+//! hand a commit to the composer. Repositories the library cannot read
+//! (e.g. shallow clones) fall back to a static `git log --graph` pager.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io;
 use std::path::Path;
+use std::path::PathBuf;
 use std::process::Command;
 
-// Prefer the embedded git-graph library (round Unicode style) when available.
-// This produces rounded connectors (╭╮╯╰) and colored branches. If it fails
-// for any reason, we fall back to `git log --graph`.
-#[allow(unused_imports)]
+use codex_ansi_escape::ansi_escape_line;
+use codex_core::protocol::FileChange;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
 use git_graph::config::get_model_name;
-#[allow(unused_imports)]
 use git_graph::get_repo;
-#[allow(unused_imports)]
 use git_graph::graph::GitGraph;
-#[allow(unused_imports)]
 use git_graph::print::format::CommitFormat;
-#[allow(unused_imports)]
 use git_graph::print::unicode::print_unicode;
-#[allow(unused_imports)]
 use git_graph::settings::BranchOrder;
-#[allow(unused_imports)]
 use git_graph::settings::BranchSettings;
-#[allow(unused_imports)]
 use git_graph::settings::BranchSettingsDef;
-#[allow(unused_imports)]
 use git_graph::settings::Characters;
-#[allow(unused_imports)]
 use git_graph::settings::MergePatterns;
-#[allow(unused_imports)]
 use git_graph::settings::Settings;
+use git2::DiffFormat;
+use git2::Oid;
+use git2::Repository;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+use ratatui::widgets::WidgetRef;
 
-/// Convert ASCII art to round/Unicode style
-fn convert_to_round_style(line: &str) -> String {
-    let mut result = line.to_string();
-
-    // Replace ASCII graph characters with round Unicode equivalents
-    result = result.replace('*', "●"); // Replace asterisk with bullet
-    result = result.replace('|', "│"); // Replace pipe with box drawing
-    result = result.replace('\\', "╲"); // Replace backslash with diagonal
-    result = result.replace('/', "╱"); // Replace forward slash with diagonal
-    result = result.replace('-', "─"); // Replace dash with horizontal line
-
-    // Handle merge patterns
-    result = result.replace("│╲", "│╲");
-    result = result.replace("│╱", "│╱");
-    result = result.replace("╲│", "╲│");
-    result = result.replace("╱│", "╱│");
-
-    // Make some visual improvements
-    result = result.replace("  ●", "  ●"); // Keep bullet spacing
-    result = result.replace("● ", "● "); // Keep bullet spacing
-
-    result
+use crate::diff_render::create_diff_summary;
+use crate::key_hint::KeyBinding;
+use crate::keymap::KeymapAction;
+use crate::keymap::KeymapContext;
+use crate::keymap::Resolved;
+use crate::keymap::keymap;
+use crate::pager_overlay::Overlay;
+use crate::pager_overlay::render_key_hints;
+use crate::tui;
+use crate::tui::TuiEvent;
+
+const TITLE: &str = "G I T   G R A P H";
+
+/// Prefix of the branches TUMIX creates for its first-round agents.
+const TUMIX_BRANCH_PREFIX: &str = "round1-";
+
+/// Create the git graph overlay for the repository containing `repo_path`.
+pub fn create_git_graph_overlay<P: AsRef<Path>>(repo_path: P) -> Result<Overlay, String> {
+    let repo_path = repo_path.as_ref();
+    match GitGraphOverlay::new(repo_path) {
+        Ok(overlay) => Ok(Overlay::GitGraph(overlay)),
+        Err(err) => {
+            tracing::debug!("git-graph unavailable ({err}); falling back to git log --graph");
+            let lines = generate_git_log_graph(repo_path)?;
+            let path = repo_path.to_path_buf();
+            let keymap = keymap();
+            let label = |action| keymap.label(action);
+            let reopen: Vec<String> = label(KeymapAction::ShowGitGraph)
+                .chars()
+                .map(String::from)
+                .collect();
+            Ok(Overlay::new_static_with_title_no_wrap_refresh(
+                lines,
+                format!(
+                    "{TITLE}   │   {}/{}:scroll   {}:refresh   {}:close   │   {}",
+                    label(KeymapAction::PagerScrollUp),
+                    label(KeymapAction::PagerScrollDown),
+                    label(KeymapAction::GitGraphRefresh),
+                    label(KeymapAction::PagerClose),
+                    reopen.join(" "),
+                ),
+                Box::new(move || generate_git_log_graph(&path)),
+            ))
+        }
+    }
+}
+
+/// What the user asked to do with a commit. `App` carries it out once the
+/// overlay has closed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum GitGraphAction {
+    /// Paste the commit's patch into the composer.
+    AttachDiff(String),
+    /// Submit a prompt asking Codex to explain the commit.
+    Explain(String),
+}
+
+/// Commit metadata shown next to the graph.
+#[derive(Debug, Clone)]
+struct CommitEntry {
+    oid: String,
+    summary: String,
+    author_name: String,
+    author_email: String,
+    /// HEAD, branches and tags pointing at the commit.
+    refs: Vec<String>,
+    /// Indices of the parents in [`GraphModel::commits`].
+    parents: Vec<usize>,
+}
+
+impl CommitEntry {
+    fn short_id(&self) -> &str {
+        &self.oid[..self.oid.len().min(7)]
+    }
+
+    fn has_tumix_branch(&self) -> bool {
+        self.refs.iter().any(|name| {
+            name.rsplit('/')
+                .next()
+                .is_some_and(|name| name.starts_with(TUMIX_BRANCH_PREFIX))
+        })
+    }
+}
+
+/// One screen row of the graph; rows that only carry connectors have no
+/// commit.
+struct GraphRow {
+    graph: Line<'static>,
+    commit: Option<usize>,
+}
+
+struct GraphModel {
+    rows: Vec<GraphRow>,
+    commits: Vec<CommitEntry>,
+    repo: Option<Repository>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum GraphFilter {
+    /// Commits reachable from the branches whose name contains the text.
+    Branch(String),
+    /// Commits whose author name or email contains the text.
+    Author(String),
 }
 
-/// Generate git graph lines for display in the TUI overlay.
-pub fn generate_git_graph<P: AsRef<Path>>(repo_path: P) -> Result<Vec<Line<'static>>, String> {
-    // First try: high-quality round Unicode graph via git-graph library.
-    if let Ok(lines) = generate_with_git_graph(repo_path.as_ref()) {
-        return Ok(lines);
+impl GraphFilter {
+    fn describe(&self) -> String {
+        match self {
+            GraphFilter::Branch(name) => format!("branch ~ {name}"),
+            GraphFilter::Author(name) => format!("author ~ {name}"),
+        }
+    }
+}
+
+/// Files changed by a commit, relative to its first parent.
+struct CommitDiff {
+    changes: HashMap<PathBuf, FileChange>,
+    patch: String,
+}
+
+impl GraphModel {
+    fn load(repo_path: &Path) -> Result<Self, String> {
+        // Allow owner validation to be skipped to avoid platform-specific
+        // errors in embedded environments.
+        let repo =
+            get_repo(repo_path, true).map_err(|e| format!("libgit2 error: {}", e.message()))?;
+
+        let model_name = get_model_name(&repo, "git-graph.toml").unwrap_or(None);
+        let model_def = match model_name.as_deref() {
+            Some("git-flow") => BranchSettingsDef::git_flow(),
+            Some("none") => BranchSettingsDef::none(),
+            _ => BranchSettingsDef::simple(),
+        };
+        let branches =
+            BranchSettings::from(model_def).map_err(|e| format!("settings error: {e}"))?;
+        let settings = Settings {
+            reverse_commit_order: false,
+            debug: false,
+            compact: true,
+            colored: true,
+            include_remote: true,
+            format: CommitFormat::Short,
+            wrapping: None,
+            characters: Characters::round(),
+            branch_order: BranchOrder::ShortestFirst(true),
+            branches,
+            merge_patterns: MergePatterns::default(),
+        };
+
+        let graph = GitGraph::new(repo, &settings, None)?;
+        // The text column is rebuilt from `commits` so it can be styled and
+        // filtered; only the lanes are taken from the printer.
+        let (graph_lines, _text_lines, start_rows) = print_unicode(&graph, &settings)?;
+
+        let mut commits = Vec::with_capacity(graph.commits.len());
+        for info in &graph.commits {
+            let commit = graph
+                .repository
+                .find_commit(info.oid)
+                .map_err(|e| e.message().to_string())?;
+            let author = commit.author();
+            let mut refs: Vec<String> = info
+                .branches
+                .iter()
+                .chain(&info.tags)
+                .filter_map(|&idx| graph.all_branches.get(idx))
+                .map(|branch| branch.name.clone())
+                .collect();
+            if info.oid == graph.head.oid {
+                refs.insert(0, "HEAD".to_string());
+            }
+            commits.push(CommitEntry {
+                oid: info.oid.to_string(),
+                summary: commit.summary().unwrap_or_default().to_string(),
+                author_name: author.name().unwrap_or_default().to_string(),
+                author_email: author.email().unwrap_or_default().to_string(),
+                refs,
+                parents: info
+                    .parents
+                    .iter()
+                    .flatten()
+                    .filter_map(|parent| graph.indices.get(parent).copied())
+                    .collect(),
+            });
+        }
+
+        let mut row_commits = vec![None; graph_lines.len()];
+        for (idx, &row) in start_rows.iter().enumerate() {
+            if let Some(slot) = row_commits.get_mut(row) {
+                *slot = Some(idx);
+            }
+        }
+        let rows = graph_lines
+            .iter()
+            .zip(row_commits)
+            .map(|(graph, commit)| GraphRow {
+                graph: ansi_escape_line(graph),
+                commit,
+            })
+            .collect();
+
+        Ok(Self {
+            rows,
+            commits,
+            repo: Some(graph.take_repository()),
+        })
     }
 
-    // Fallback: use `git log --graph` (ASCII) and do a best-effort conversion
-    // to Unicode line drawing characters.
-    // Try git log with detailed formatting and more commits for scrolling
-    // Show the full history (no artificial commit limit); the pager overlay
-    // handles scrolling efficiently. This avoids surprising truncation in
-    // larger repos where 50 commits isn't enough.
+    /// Indices of the commits that pass `filter`.
+    fn matching(&self, filter: &GraphFilter) -> HashSet<usize> {
+        match filter {
+            GraphFilter::Author(needle) => {
+                let needle = needle.to_lowercase();
+                self.commits
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, commit)| {
+                        commit.author_name.to_lowercase().contains(&needle)
+                            || commit.author_email.to_lowercase().contains(&needle)
+                    })
+                    .map(|(idx, _)| idx)
+                    .collect()
+            }
+            GraphFilter::Branch(needle) => {
+                let needle = needle.to_lowercase();
+                let mut stack: Vec<usize> = self
+                    .commits
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, commit)| {
+                        commit
+                            .refs
+                            .iter()
+                            .any(|name| name.to_lowercase().contains(&needle))
+                    })
+                    .map(|(idx, _)| idx)
+                    .collect();
+                let mut reachable = HashSet::new();
+                while let Some(idx) = stack.pop() {
+                    if reachable.insert(idx) {
+                        stack.extend(&self.commits[idx].parents);
+                    }
+                }
+                reachable
+            }
+        }
+    }
+
+    fn commit_diff(&self, commit: usize) -> Result<CommitDiff, String> {
+        let repo = self
+            .repo
+            .as_ref()
+            .ok_or_else(|| "repository is not available".to_string())?;
+        let entry = &self.commits[commit];
+        commit_diff(repo, &entry.oid).map_err(|e| e.message().to_string())
+    }
+}
+
+fn commit_diff(repo: &Repository, oid: &str) -> Result<CommitDiff, git2::Error> {
+    let commit = repo.find_commit(Oid::from_str(oid)?)?;
+    let tree = commit.tree()?;
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+    let mut diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+    diff.find_similar(None)?;
+
+    let root = repo.workdir().unwrap_or_else(|| repo.path()).to_path_buf();
+    let mut changes = HashMap::new();
+    for delta in diff.deltas() {
+        let old_path = delta.old_file().path().map(|path| root.join(path));
+        let new_path = delta.new_file().path().map(|path| root.join(path));
+        let change = match delta.status() {
+            git2::Delta::Added => {
+                let blob = repo.find_blob(delta.new_file().id())?;
+                if blob.is_binary() {
+                    FileChange::AddBinary {
+                        size: blob.size() as u64,
+                        mode: None,
+                    }
+                } else {
+                    FileChange::Add {
+                        content: String::from_utf8_lossy(blob.content()).into_owned(),
+                        mode: None,
+                    }
+                }
+            }
+            git2::Delta::Deleted => {
+                let blob = repo.find_blob(delta.old_file().id())?;
+                FileChange::Delete {
+                    content: String::from_utf8_lossy(blob.content()).into_owned(),
+                }
+            }
+            _ => FileChange::Update {
+                unified_diff: String::new(),
+                move_path: (old_path != new_path).then(|| new_path.clone()).flatten(),
+                mode: None,
+            },
+        };
+        if let Some(path) = old_path.or(new_path) {
+            changes.insert(path, change);
+        }
+    }
+
+    // Fill in the hunks of modified files and collect the full patch.
+    let mut patch = String::new();
+    let mut hunks: HashMap<PathBuf, String> = HashMap::new();
+    diff.print(DiffFormat::Patch, |delta, _hunk, line| {
+        let content = String::from_utf8_lossy(line.content());
+        let origin = line.origin();
+        if matches!(origin, '+' | '-' | ' ') {
+            patch.push(origin);
+        }
+        patch.push_str(&content);
+
+        if let Some(path) = delta.old_file().path().or(delta.new_file().path()) {
+            let hunk_text = hunks.entry(root.join(path)).or_default();
+            match origin {
+                'H' => hunk_text.push_str(&content),
+                '+' | '-' | ' ' => {
+                    hunk_text.push(origin);
+                    hunk_text.push_str(&content);
+                    if !content.ends_with('\n') {
+                        hunk_text.push('\n');
+                    }
+                }
+                _ => {}
+            }
+        }
+        true
+    })?;
+    for (path, change) in &mut changes {
+        if let FileChange::Update { unified_diff, .. } = change
+            && let Some(hunk_text) = hunks.remove(path)
+        {
+            *unified_diff = format!("--- a\n+++ b\n{hunk_text}");
+        }
+    }
+
+    Ok(CommitDiff { changes, patch })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FilterKind {
+    Branch,
+    Author,
+}
+
+/// Text being typed for a branch or author filter.
+struct FilterPrompt {
+    kind: FilterKind,
+    input: String,
+}
+
+struct ExpandedCommit {
+    commit: usize,
+    diff: CommitDiff,
+    /// Diff lines rendered for the given width.
+    rendered: Option<(u16, Vec<Line<'static>>)>,
+}
+
+/// Navigable view over a [`GraphModel`].
+pub(crate) struct GitGraphOverlay {
+    repo_path: PathBuf,
+    model: GraphModel,
+    /// Indices into `model.rows` that pass the current filter.
+    visible: Vec<usize>,
+    /// Row (index into `model.rows`) of the selected commit.
+    cursor: Option<usize>,
+    scroll_offset: usize,
+    /// Scroll the cursor into view on the next render.
+    follow_cursor: bool,
+    last_content_height: usize,
+    filter: Option<GraphFilter>,
+    prompt: Option<FilterPrompt>,
+    expanded: Option<ExpandedCommit>,
+    /// One-off feedback shown in the status line.
+    message: Option<String>,
+    /// First key of a two-key binding waiting for its second key.
+    pending_chord: Option<KeyBinding>,
+    action: Option<GitGraphAction>,
+    is_done: bool,
+}
+
+impl GitGraphOverlay {
+    pub(crate) fn new(repo_path: &Path) -> Result<Self, String> {
+        let model = GraphModel::load(repo_path)?;
+        Ok(Self::with_model(repo_path.to_path_buf(), model))
+    }
+
+    fn with_model(repo_path: PathBuf, model: GraphModel) -> Self {
+        let mut overlay = Self {
+            repo_path,
+            model,
+            visible: Vec::new(),
+            cursor: None,
+            scroll_offset: 0,
+            follow_cursor: true,
+            last_content_height: 0,
+            filter: None,
+            prompt: None,
+            expanded: None,
+            message: None,
+            pending_chord: None,
+            action: None,
+            is_done: false,
+        };
+        overlay.apply_filter();
+        overlay
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done || self.action.is_some()
+    }
+
+    pub(crate) fn take_action(&mut self) -> Option<GitGraphAction> {
+        self.action.take()
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> io::Result<()> {
+        match event {
+            TuiEvent::Key(key_event) => {
+                self.handle_key_event(key_event);
+                tui.frame_requester().schedule_frame();
+                Ok(())
+            }
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if !matches!(key_event.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+            return;
+        }
+        if self.prompt.is_some() {
+            self.handle_prompt_key(key_event);
+            return;
+        }
+        self.message = None;
+
+        let keymap = keymap();
+        let action = match keymap.resolve(KeymapContext::Pager, &mut self.pending_chord, &key_event)
+        {
+            Resolved::Action(action) => action,
+            Resolved::Pending => return,
+            Resolved::Unbound => match keymap.action_for(KeymapContext::GitGraph, &key_event) {
+                Some(action) => action,
+                None => return,
+            },
+        };
+
+        match action {
+            KeymapAction::PagerScrollUp => self.move_cursor(-1),
+            KeymapAction::PagerScrollDown => self.move_cursor(1),
+            KeymapAction::PagerHome | KeymapAction::PagerFirstCommit => {
+                self.move_cursor(isize::MIN)
+            }
+            KeymapAction::PagerEnd | KeymapAction::PagerLastCommit => self.move_cursor(isize::MAX),
+            KeymapAction::PagerPageUp => {
+                self.scroll_offset = self
+                    .scroll_offset
+                    .saturating_sub(self.last_content_height.max(1));
+            }
+            KeymapAction::PagerPageDown => {
+                self.scroll_offset = self
+                    .scroll_offset
+                    .saturating_add(self.last_content_height.max(1));
+            }
+            KeymapAction::PagerClose => self.is_done = true,
+            KeymapAction::GitGraphBack => {
+                if self.expanded.is_some() {
+                    self.expanded = None;
+                    self.follow_cursor = true;
+                } else if self.filter.is_some() {
+                    self.filter = None;
+                    self.apply_filter();
+                } else {
+                    self.is_done = true;
+                }
+            }
+            KeymapAction::GitGraphToggleDiff => self.toggle_expanded(),
+            KeymapAction::GitGraphFilterBranch => self.open_prompt(FilterKind::Branch),
+            KeymapAction::GitGraphFilterAuthor => self.open_prompt(FilterKind::Author),
+            KeymapAction::GitGraphNextBranch => self.jump_to_tumix_branch(true),
+            KeymapAction::GitGraphPreviousBranch => self.jump_to_tumix_branch(false),
+            KeymapAction::GitGraphAttachDiff => self.attach_diff(),
+            KeymapAction::GitGraphExplain => self.explain_commit(),
+            KeymapAction::GitGraphRefresh => self.refresh(),
+            _ => {}
+        }
+    }
+
+    fn handle_prompt_key(&mut self, key_event: KeyEvent) {
+        let Some(prompt) = self.prompt.as_mut() else {
+            return;
+        };
+        match key_event.code {
+            KeyCode::Esc => self.prompt = None,
+            KeyCode::Backspace => {
+                prompt.input.pop();
+            }
+            KeyCode::Char(c)
+                if !key_event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                prompt.input.push(c);
+            }
+            KeyCode::Enter => {
+                let kind = prompt.kind;
+                let input = prompt.input.trim().to_string();
+                self.prompt = None;
+                if input.is_empty() {
+                    self.filter = None;
+                    self.apply_filter();
+                    return;
+                }
+                let filter = match kind {
+                    FilterKind::Branch => GraphFilter::Branch(input),
+                    FilterKind::Author => GraphFilter::Author(input),
+                };
+                if self.model.matching(&filter).is_empty() {
+                    self.message = Some(format!("No commits match {}", filter.describe()));
+                } else {
+                    self.filter = Some(filter);
+                    self.apply_filter();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn open_prompt(&mut self, kind: FilterKind) {
+        let input = match (&self.filter, kind) {
+            (Some(GraphFilter::Branch(name)), FilterKind::Branch)
+            | (Some(GraphFilter::Author(name)), FilterKind::Author) => name.clone(),
+            _ => String::new(),
+        };
+        self.prompt = Some(FilterPrompt { kind, input });
+    }
+
+    fn apply_filter(&mut self) {
+        let matching = self
+            .filter
+            .as_ref()
+            .map(|filter| self.model.matching(filter));
+        self.visible = self
+            .model
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| match &matching {
+                None => true,
+                Some(matching) => row.commit.is_some_and(|c| matching.contains(&c)),
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+        if !self.cursor.is_some_and(|row| self.visible.contains(&row)) {
+            self.cursor = self.commit_rows().first().copied();
+        }
+        if self
+            .expanded
+            .as_ref()
+            .is_some_and(|expanded| Some(expanded.commit) != self.selected_commit())
+        {
+            self.expanded = None;
+        }
+        self.scroll_offset = 0;
+        self.follow_cursor = true;
+    }
+
+    /// Visible rows that hold a commit, in display order.
+    fn commit_rows(&self) -> Vec<usize> {
+        self.visible
+            .iter()
+            .copied()
+            .filter(|&row| self.model.rows[row].commit.is_some())
+            .collect()
+    }
+
+    fn selected_commit(&self) -> Option<usize> {
+        self.cursor.and_then(|row| self.model.rows[row].commit)
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        let rows = self.commit_rows();
+        if rows.is_empty() {
+            return;
+        }
+        let current = self
+            .cursor
+            .and_then(|row| rows.iter().position(|&r| r == row))
+            .unwrap_or(0);
+        let target = current.saturating_add_signed(delta).min(rows.len() - 1);
+        self.cursor = Some(rows[target]);
+        self.follow_cursor = true;
+    }
+
+    fn jump_to_tumix_branch(&mut self, forward: bool) {
+        let rows = self.commit_rows();
+        let current = self
+            .cursor
+            .and_then(|row| rows.iter().position(|&r| r == row))
+            .unwrap_or(0);
+        let len = rows.len();
+        let found = (1..=len)
+            .map(|step| {
+                if forward {
+                    (current + step) % len
+                } else {
+                    (current + len - step % len) % len
+                }
+            })
+            .find(|&pos| {
+                self.model.rows[rows[pos]]
+                    .commit
+                    .is_some_and(|commit| self.model.commits[commit].has_tumix_branch())
+            });
+        match found {
+            Some(pos) => {
+                self.cursor = Some(rows[pos]);
+                self.follow_cursor = true;
+            }
+            None => {
+                self.message = Some(format!("No {TUMIX_BRANCH_PREFIX}* branches in view"));
+            }
+        }
+    }
+
+    fn toggle_expanded(&mut self) {
+        let Some(commit) = self.selected_commit() else {
+            return;
+        };
+        if let Some(expanded) = self.expanded.take()
+            && expanded.commit == commit
+        {
+            self.follow_cursor = true;
+            return;
+        }
+        match self.model.commit_diff(commit) {
+            Ok(diff) => {
+                self.expanded = Some(ExpandedCommit {
+                    commit,
+                    diff,
+                    rendered: None,
+                });
+                self.follow_cursor = true;
+            }
+            Err(err) => self.message = Some(format!("Failed to load diff: {err}")),
+        }
+    }
+
+    fn attach_diff(&mut self) {
+        let Some(commit) = self.selected_commit() else {
+            return;
+        };
+        let patch = match &self.expanded {
+            Some(expanded) if expanded.commit == commit => expanded.diff.patch.clone(),
+            _ => match self.model.commit_diff(commit) {
+                Ok(diff) => diff.patch,
+                Err(err) => {
+                    self.message = Some(format!("Failed to load diff: {err}"));
+                    return;
+                }
+            },
+        };
+        let entry = &self.model.commits[commit];
+        let mut text = format!(
+            "Diff of commit {} \"{}\":\n\n```diff\n{patch}",
+            entry.short_id(),
+            entry.summary
+        );
+        if !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str("```\n");
+        self.action = Some(GitGraphAction::AttachDiff(text));
+    }
+
+    fn explain_commit(&mut self) {
+        let Some(commit) = self.selected_commit() else {
+            return;
+        };
+        let entry = &self.model.commits[commit];
+        self.action = Some(GitGraphAction::Explain(format!(
+            "Explain commit {} (\"{}\" by {}): what it changes and why. Run `git show {}` to read the diff.",
+            entry.short_id(),
+            entry.summary,
+            entry.author_name,
+            entry.oid
+        )));
+    }
+
+    fn refresh(&mut self) {
+        let selected_oid = self
+            .selected_commit()
+            .map(|commit| self.model.commits[commit].oid.clone());
+        match GraphModel::load(&self.repo_path) {
+            Ok(model) => {
+                self.model = model;
+                self.expanded = None;
+                self.cursor = selected_oid.and_then(|oid| {
+                    self.model.rows.iter().position(|row| {
+                        row.commit.is_some_and(|c| self.model.commits[c].oid == oid)
+                    })
+                });
+                self.apply_filter();
+            }
+            Err(err) => self.message = Some(format!("Failed to refresh: {err}")),
+        }
+    }
+
+    fn commit_line(&self, row: &GraphRow, selected: bool) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> =
+            vec![if selected { "▸ ".cyan() } else { "  ".into() }];
+        spans.extend(row.graph.spans.iter().cloned());
+        let Some(commit) = row.commit else {
+            return Line::from(spans);
+        };
+        let entry = &self.model.commits[commit];
+        spans.push("  ".into());
+        spans.push(entry.short_id().to_string().dim());
+        spans.push(" ".into());
+        if !entry.refs.is_empty() {
+            let refs = entry.refs.join(", ");
+            spans.push(if entry.has_tumix_branch() {
+                format!("({refs})").magenta()
+            } else {
+                format!("({refs})").cyan()
+            });
+            spans.push(" ".into());
+        }
+        spans.push(if selected {
+            entry.summary.clone().bold()
+        } else {
+            entry.summary.clone().into()
+        });
+        spans.push(format!(" <{}>", entry.author_name).dim());
+        Line::from(spans)
+    }
+
+    /// All lines of the view and the index of the selected commit's line.
+    fn display_lines(&mut self, width: u16) -> (Vec<Line<'static>>, Option<usize>) {
+        if let Some(expanded) = self.expanded.as_mut()
+            && expanded.rendered.as_ref().is_none_or(|(w, _)| *w != width)
+        {
+            let cwd = self.model.repo.as_ref().and_then(Repository::workdir);
+            let cwd = cwd.unwrap_or(self.repo_path.as_path());
+            let wrap_cols = usize::from(width.saturating_sub(4)).max(20);
+            let lines = create_diff_summary(&expanded.diff.changes, cwd, wrap_cols);
+            expanded.rendered = Some((width, lines));
+        }
+
+        let mut lines = Vec::with_capacity(self.visible.len());
+        let mut cursor_line = None;
+        for &row_idx in &self.visible {
+            let row = &self.model.rows[row_idx];
+            let selected = self.cursor == Some(row_idx);
+            if selected {
+                cursor_line = Some(lines.len());
+            }
+            lines.push(self.commit_line(row, selected));
+            if let Some(expanded) = &self.expanded
+                && row.commit == Some(expanded.commit)
+                && let Some((_, diff_lines)) = &expanded.rendered
+            {
+                if expanded.diff.changes.is_empty() {
+                    lines.push(Line::from("    (no changes)".dim()));
+                }
+                for line in diff_lines {
+                    let mut spans = vec![Span::from("    ")];
+                    spans.extend(line.spans.iter().cloned());
+                    lines.push(Line::from(spans).style(line.style));
+                }
+            }
+        }
+        (lines, cursor_line)
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        if area.height < 5 {
+            return;
+        }
+        let header = Rect::new(area.x, area.y, area.width, 1);
+        let content = Rect::new(area.x, area.y + 1, area.width, area.height - 4);
+        let status = Rect::new(area.x, content.bottom(), area.width, 1);
+        let hints = Rect::new(area.x, status.bottom(), area.width, 2);
+
+        let mut title = format!("/ {TITLE}");
+        if let Some(filter) = &self.filter {
+            title.push_str(&format!("   │   {}", filter.describe()));
+        }
+        Span::from("/ ".repeat(area.width as usize / 2))
+            .dim()
+            .render_ref(header, buf);
+        title.dim().render_ref(header, buf);
+
+        let (lines, cursor_line) = self.display_lines(content.width);
+        let height = usize::from(content.height);
+        self.last_content_height = height;
+        if self.follow_cursor {
+            self.follow_cursor = false;
+            if let Some(cursor_line) = cursor_line {
+                if cursor_line < self.scroll_offset {
+                    self.scroll_offset = cursor_line;
+                } else if cursor_line >= self.scroll_offset + height {
+                    self.scroll_offset = cursor_line + 1 - height;
+                }
+                // Show as much of an expanded diff as fits below its commit.
+                if let Some(expanded) = &self.expanded
+                    && self.selected_commit() == Some(expanded.commit)
+                {
+                    let diff_len = expanded.rendered.as_ref().map_or(0, |(_, l)| l.len());
+                    let bottom = cursor_line + 1 + diff_len;
+                    if bottom > self.scroll_offset + height {
+                        self.scroll_offset = (bottom - height).min(cursor_line);
+                    }
+                }
+            }
+        }
+        self.scroll_offset = self.scroll_offset.min(lines.len().saturating_sub(height));
+        let page: Vec<Line<'static>> = lines
+            .into_iter()
+            .skip(self.scroll_offset)
+            .take(height)
+            .collect();
+        if page.is_empty() {
+            Paragraph::new(Line::from("  No commits to show.".dim())).render(content, buf);
+        } else {
+            Paragraph::new(page).render(content, buf);
+        }
+
+        let status_line: Line<'static> = if let Some(prompt) = &self.prompt {
+            let label = match prompt.kind {
+                FilterKind::Branch => "Filter by branch",
+                FilterKind::Author => "Filter by author",
+            };
+            Line::from(vec![
+                format!(" {label}: ").cyan(),
+                prompt.input.clone().into(),
+                "▏".cyan(),
+                "   Enter:apply  Esc:cancel  (empty clears)".dim(),
+            ])
+        } else if let Some(message) = &self.message {
+            Line::from(format!(" {message}").magenta())
+        } else {
+            Line::from("─".repeat(area.width as usize).dim())
+        };
+        Paragraph::new(status_line).render(status, buf);
+
+        let line1 = Rect::new(hints.x, hints.y, hints.width, 1);
+        let line2 = Rect::new(hints.x, hints.y + 1, hints.width, 1);
+        let keymap = keymap();
+        let pair = |first, second| format!("{}/{}", keymap.label(first), keymap.label(second));
+        let select = pair(KeymapAction::PagerScrollUp, KeymapAction::PagerScrollDown);
+        let scroll = pair(KeymapAction::PagerPageUp, KeymapAction::PagerPageDown);
+        let branches = pair(
+            KeymapAction::GitGraphNextBranch,
+            KeymapAction::GitGraphPreviousBranch,
+        );
+        let label = |action| keymap.label(action);
+        let toggle_diff = label(KeymapAction::GitGraphToggleDiff);
+        let filter_branch = label(KeymapAction::GitGraphFilterBranch);
+        let filter_author = label(KeymapAction::GitGraphFilterAuthor);
+        let attach_diff = label(KeymapAction::GitGraphAttachDiff);
+        let explain = label(KeymapAction::GitGraphExplain);
+        let refresh = label(KeymapAction::GitGraphRefresh);
+        let close = label(KeymapAction::PagerClose);
+        render_key_hints(
+            line1,
+            buf,
+            &[
+                (select.as_str(), "select"),
+                (toggle_diff.as_str(), "diff"),
+                (scroll.as_str(), "scroll"),
+                (branches.as_str(), "round1-* branches"),
+            ],
+        );
+        render_key_hints(
+            line2,
+            buf,
+            &[
+                (filter_branch.as_str(), "branch"),
+                (filter_author.as_str(), "author"),
+                (attach_diff.as_str(), "attach diff"),
+                (explain.as_str(), "explain"),
+                (refresh.as_str(), "refresh"),
+                (close.as_str(), "quit"),
+            ],
+        );
+    }
+}
+
+/// Convert ASCII art to round/Unicode style
+fn convert_to_round_style(line: &str) -> String {
+    line.replace('*', "●")
+        .replace('|', "│")
+        .replace('\\', "╲")
+        .replace('/', "╱")
+        .replace('-', "─")
+}
+
+/// `git log --graph` lines for repositories the git-graph library cannot
+/// read, with a best-effort conversion to Unicode line drawing characters.
+fn generate_git_log_graph(repo_path: &Path) -> Result<Vec<Line<'static>>, String> {
     let output = Command::new("git")
         .args([
             "log",
@@ -80,219 +970,248 @@ pub fn generate_git_graph<P: AsRef<Path>>(repo_path: P) -> Result<Vec<Line<'stat
             "--color=always",
             "--abbrev-commit",
         ])
-        .current_dir(&repo_path)
+        .current_dir(repo_path)
         .output()
         .map_err(|e| format!("Failed to execute git log: {e}"))?;
 
-    if !output.status.success() {
+    let output = if output.status.success() {
+        output
+    } else {
         // Fallback to simpler git log if the above fails
         let fallback_output = Command::new("git")
             .args(["log", "--graph", "--oneline", "--all", "--color=always"])
-            .current_dir(&repo_path)
+            .current_dir(repo_path)
             .output()
             .map_err(|e| format!("Failed to execute fallback git log: {e}"))?;
-
         if !fallback_output.status.success() {
             return Err(format!(
                 "Git command failed: {}",
                 String::from_utf8_lossy(&fallback_output.stderr)
             ));
         }
-
-        let output_str = String::from_utf8_lossy(&fallback_output.stdout);
-        return if output_str.trim().is_empty() {
-            Ok(vec!["No git history found.".dim().into()])
-        } else {
-            // Convert to round style and process ANSI
-            let lines: Vec<Line<'static>> = output_str
-                .lines()
-                .map(|line| {
-                    let round_line = convert_to_round_style(line);
-                    ansi_escape_line(&round_line)
-                })
-                .collect();
-            Ok(lines)
-        };
-    }
+        fallback_output
+    };
 
     let output_str = String::from_utf8_lossy(&output.stdout);
-
     if output_str.trim().is_empty() {
-        Ok(vec!["No git history found.".dim().into()])
-    } else {
-        // Convert each line to round style, then process ANSI escapes
-        let lines: Vec<Line<'static>> = output_str
-            .lines()
-            .map(|line| {
-                let round_line = convert_to_round_style(line);
-                ansi_escape_line(&round_line)
-            })
-            .collect();
-        Ok(lines)
+        return Ok(vec!["No git history found.".dim().into()]);
     }
+    Ok(output_str
+        .lines()
+        .map(|line| ansi_escape_line(&convert_to_round_style(line)))
+        .collect())
 }
 
-/// Create a new git graph overlay for the TUI with enhanced title.
-pub fn create_git_graph_overlay<P: AsRef<Path>>(repo_path: P) -> Result<Overlay, String> {
-    let path = repo_path.as_ref().to_path_buf();
-    let lines = generate_git_graph(&path)?;
-
-    // Create a refresh callback that regenerates the git graph
-    let refresh_callback = Box::new(move || generate_git_graph(&path));
-
-    Ok(Overlay::new_static_with_title_no_wrap_refresh(
-        lines,
-        "G I T   G R A P H   │   j/k:scroll   r:refresh   q/Esc:close   │   C t r l + G"
-            .to_string(),
-        refresh_callback,
-    ))
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
 
-// Build lines using the embedded git-graph library with a "round" style.
-fn generate_with_git_graph<P: AsRef<Path>>(repo_path: P) -> Result<Vec<Line<'static>>, String> {
-    // Discover the repository; allow owner validation to be skipped to avoid
-    // platform-specific errors in embedded environments.
-    let repo = get_repo(repo_path, true).map_err(|e| format!("libgit2 error: {}", e.message()))?;
-
-    // Load model preference if present; otherwise use a reasonable default.
-    let model_name = get_model_name(&repo, "git-graph.toml").unwrap_or(None);
-    let model_def = match model_name.as_deref() {
-        Some("git-flow") => BranchSettingsDef::git_flow(),
-        Some("simple") => BranchSettingsDef::simple(),
-        Some("none") => BranchSettingsDef::none(),
-        _ => BranchSettingsDef::simple(),
-    };
-    let branches = BranchSettings::from(model_def).map_err(|e| format!("settings error: {e}"))?;
-
-    // Use rounded characters, include remotes, and colored output like `--all --color`.
-    let settings = Settings {
-        reverse_commit_order: false,
-        debug: false,
-        compact: true,
-        colored: true,
-        include_remote: true,
-        // Compact commit summary similar to `--oneline`.
-        format: CommitFormat::Short,
-        // Let our TUI pager handle wrapping.
-        wrapping: None,
-        characters: Characters::round(),
-        branch_order: BranchOrder::ShortestFirst(true),
-        branches,
-        merge_patterns: MergePatterns::default(),
-    };
+    fn commit(oid: &str, author: &str, refs: &[&str], parents: &[usize]) -> CommitEntry {
+        CommitEntry {
+            oid: oid.to_string(),
+            summary: format!("commit {oid}"),
+            author_name: author.to_string(),
+            author_email: format!("{}@example.com", author.to_lowercase()),
+            refs: refs.iter().map(|name| (*name).to_string()).collect(),
+            parents: parents.to_vec(),
+        }
+    }
 
-    // No artificial limit: let the pager scroll the full history.
-    let graph = GitGraph::new(repo, &settings, None)?;
-    let (g_lines, t_lines, _indices) = print_unicode(&graph, &settings)?;
-
-    // Join graph and text columns, then parse ANSI into ratatui Line.
-    // For the embedded printer path, post-process the text column to dim the
-    // commit hash (first token) and the author section (e.g., "<name>") so
-    // the commit subject stands out.
-    #[allow(dead_code)]
-    fn dim_hash_and_author(s: &str) -> String {
-        // Insert dim (ESC[2m) at the first visible character (skipping any
-        // leading ANSI), and end dim (ESC[22m) before the first following
-        // visible whitespace. Then, if present, also wrap the first
-        // angle-bracketed author section with dim toggles.
-        let bytes = s.as_bytes();
-        let mut i = 0;
-        let len = bytes.len();
-        // Helper to skip a CSI sequence starting at ESC
-        let skip_ansi = |idx: &mut usize| {
-            if *idx < len && bytes[*idx] == 0x1b {
-                *idx += 1;
-                if *idx < len && bytes[*idx] == b'[' {
-                    *idx += 1;
-                    while *idx < len && bytes[*idx] != b'm' {
-                        *idx += 1;
-                    }
-                    if *idx < len {
-                        *idx += 1;
-                    }
-                }
-            }
+    /// main: c0 <- c1 <- c3 (merge of c2); round1 branch: c2 <- c1.
+    fn overlay() -> GitGraphOverlay {
+        let commits = vec![
+            commit("c3aaaaaaaa", "Ann", &["HEAD", "main"], &[1, 2]),
+            commit("c2bbbbbbbb", "Bob", &["round1-run-agent-1"], &[2]),
+            commit("c1cccccccc", "Ann", &[], &[3]),
+            commit("c0dddddddd", "Cid", &["v1.0"], &[]),
+        ];
+        let row = |commit| GraphRow {
+            graph: Line::from("●"),
+            commit,
         };
+        let rows = vec![
+            row(Some(0)),
+            row(None),
+            row(Some(1)),
+            row(None),
+            row(Some(2)),
+            row(Some(3)),
+        ];
+        let model = GraphModel {
+            rows,
+            commits,
+            repo: None,
+        };
+        GitGraphOverlay::with_model(PathBuf::from("."), model)
+    }
 
-        // Find first visible, non-space character using char boundaries
-        let mut char_iter = s.char_indices();
-        let mut start_idx = 0;
+    fn press(overlay: &mut GitGraphOverlay, code: KeyCode) {
+        overlay.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+    }
 
-        while i < len {
-            if bytes[i] == 0x1b {
-                skip_ansi(&mut i);
-                continue;
-            }
-            if let Some((idx, ch)) = char_iter.next() {
-                i = idx + ch.len_utf8();
-                if !ch.is_whitespace() {
-                    start_idx = idx;
-                    break;
-                }
-            } else {
-                break;
-            }
+    fn type_text(overlay: &mut GitGraphOverlay, text: &str) {
+        for c in text.chars() {
+            press(overlay, KeyCode::Char(c));
         }
+    }
 
-        // If nothing visible, return original
-        if start_idx >= s.len() {
-            return s.to_string();
-        }
+    #[test]
+    fn cursor_moves_between_commits_only() {
+        let mut overlay = overlay();
+        assert_eq!(overlay.selected_commit(), Some(0));
+        press(&mut overlay, KeyCode::Down);
+        assert_eq!(overlay.selected_commit(), Some(1));
+        press(&mut overlay, KeyCode::Char('j'));
+        assert_eq!(overlay.selected_commit(), Some(2));
+        press(&mut overlay, KeyCode::End);
+        assert_eq!(overlay.selected_commit(), Some(3));
+        press(&mut overlay, KeyCode::Down);
+        assert_eq!(overlay.selected_commit(), Some(3));
+        press(&mut overlay, KeyCode::Char('g'));
+        assert_eq!(overlay.selected_commit(), Some(3));
+        press(&mut overlay, KeyCode::Char('g'));
+        assert_eq!(overlay.selected_commit(), Some(0));
+    }
 
-        // Find end of the first token using char boundaries
-        let mut end_idx = start_idx;
-        for (idx, ch) in s[start_idx..].char_indices() {
-            if ch.is_whitespace() {
-                end_idx = start_idx + idx;
-                break;
-            }
-            end_idx = start_idx + idx + ch.len_utf8();
-        }
+    #[test]
+    fn filters_by_branch_and_author() {
+        let mut overlay = overlay();
+        press(&mut overlay, KeyCode::Char('b'));
+        type_text(&mut overlay, "round1");
+        press(&mut overlay, KeyCode::Enter);
+        assert_eq!(
+            overlay.filter,
+            Some(GraphFilter::Branch("round1".to_string()))
+        );
+        // The branch tip and its ancestors; connector rows are hidden.
+        assert_eq!(overlay.visible, vec![2, 4, 5]);
+        assert_eq!(overlay.selected_commit(), Some(1));
 
-        // Ensure we don't have ANSI codes in our indices
-        // Skip if start or end is in middle of ANSI sequence
-        if s.as_bytes().get(start_idx) == Some(&0x1b) || s.as_bytes().get(end_idx) == Some(&0x1b) {
-            return s.to_string();
-        }
+        press(&mut overlay, KeyCode::Char('a'));
+        // The prompt starts empty for a different filter kind.
+        type_text(&mut overlay, "ann");
+        press(&mut overlay, KeyCode::Enter);
+        assert_eq!(overlay.visible, vec![0, 4]);
 
-        let start = start_idx;
-        let end = end_idx;
+        press(&mut overlay, KeyCode::Char('a'));
+        type_text(&mut overlay, "x");
+        press(&mut overlay, KeyCode::Enter);
+        assert_eq!(
+            overlay.message.as_deref(),
+            Some("No commits match author ~ annx")
+        );
+        assert_eq!(overlay.visible, vec![0, 4]);
 
-        // Compose with dim around [start, end)
-        let mut out = String::with_capacity(s.len() + 16);
-        out.push_str(&s[..start]);
-        out.push_str("\x1b[2m");
-        out.push_str(&s[start..end]);
-        out.push_str("\x1b[22m");
-        out.push_str(&s[end..]);
+        press(&mut overlay, KeyCode::Esc);
+        assert_eq!(overlay.filter, None);
+        assert_eq!(overlay.visible.len(), 6);
+        assert!(!overlay.is_done());
+        press(&mut overlay, KeyCode::Esc);
+        assert!(overlay.is_done());
+    }
 
-        // Now dim the first "<...>" block if present.
-        if let Some(a_start) = out.find('<')
-            && let Some(a_end_rel) = out[a_start..].find('>')
-        {
-            let a_end = a_start + a_end_rel;
-            if a_end > a_start {
-                let mut final_out = String::with_capacity(out.len() + 16);
-                final_out.push_str(&out[..a_start]);
-                final_out.push_str("\x1b[2m");
-                final_out.push_str(&out[a_start..=a_end]);
-                final_out.push_str("\x1b[22m");
-                final_out.push_str(&out[a_end + 1..]);
-                return final_out;
+    #[test]
+    fn jumps_to_tumix_branches_and_builds_actions() {
+        let mut overlay = overlay();
+        press(&mut overlay, KeyCode::Char('t'));
+        assert_eq!(overlay.selected_commit(), Some(1));
+        // Wraps around to the only round1 branch again.
+        press(&mut overlay, KeyCode::Char('T'));
+        assert_eq!(overlay.selected_commit(), Some(1));
+
+        press(&mut overlay, KeyCode::Char('e'));
+        assert!(overlay.is_done());
+        assert_eq!(
+            overlay.take_action(),
+            Some(GitGraphAction::Explain(
+                "Explain commit c2bbbbb (\"commit c2bbbbbbbb\" by Bob): what it changes and why. Run `git show c2bbbbbbbb` to read the diff."
+                    .to_string()
+            ))
+        );
+
+        press(&mut overlay, KeyCode::Char('d'));
+        assert_eq!(
+            overlay.message.as_deref(),
+            Some("Failed to load diff: repository is not available")
+        );
+        assert_eq!(overlay.take_action(), None);
+    }
+
+    fn write_commit(repo: &Repository, file: &str, contents: &str, message: &str) -> Oid {
+        let workdir = repo.workdir().expect("workdir");
+        std::fs::write(workdir.join(file), contents).expect("write file");
+        let mut index = repo.index().expect("index");
+        index.add_path(Path::new(file)).expect("add");
+        index.write().expect("write index");
+        let tree = repo
+            .find_tree(index.write_tree().expect("tree"))
+            .expect("find tree");
+        let signature = Signature::now("Ann", "ann@example.com").expect("signature");
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .expect("commit")
+    }
+
+    #[test]
+    fn loads_repository_and_renders_commit_diff() {
+        let dir = TempDir::new().expect("tempdir");
+        let repo = Repository::init(dir.path()).expect("init");
+        write_commit(&repo, "notes.txt", "one\n", "Add notes");
+        let second = write_commit(&repo, "notes.txt", "one\ntwo\n", "Extend notes");
+        repo.branch(
+            "round1-run-agent-1",
+            &repo.find_commit(second).expect("commit"),
+            false,
+        )
+        .expect("branch");
+
+        let mut overlay = GitGraphOverlay::new(dir.path()).expect("overlay");
+        let summaries: Vec<&str> = overlay
+            .model
+            .commits
+            .iter()
+            .map(|commit| commit.summary.as_str())
+            .collect();
+        assert_eq!(summaries, vec!["Extend notes", "Add notes"]);
+        assert!(overlay.model.commits[0].has_tumix_branch());
+        assert!(overlay.model.commits[0].refs.contains(&"HEAD".to_string()));
+
+        press(&mut overlay, KeyCode::Enter);
+        let expanded = overlay.expanded.as_ref().expect("expanded diff");
+        let change = expanded.diff.changes.values().next().expect("changed file");
+        match change {
+            FileChange::Update { unified_diff, .. } => {
+                assert!(unified_diff.contains("+two\n"), "{unified_diff}");
             }
+            other => panic!("unexpected change: {other:?}"),
         }
 
-        out
-    }
+        let (lines, cursor_line) = overlay.display_lines(80);
+        assert_eq!(cursor_line, Some(0));
+        let text: Vec<String> = lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(text.iter().any(|line| line.contains("two")), "{text:?}");
 
-    let lines: Vec<Line<'static>> = g_lines
-        .into_iter()
-        .zip(t_lines)
-        .map(|(g, t)| {
-            // Skip dim_hash_and_author for non-ASCII commits (e.g., Chinese)
-            // to avoid UTF-8 boundary issues
-            ansi_escape_line(&format!(" {g}  {t}"))
-        })
-        .collect();
-    Ok(lines)
+        press(&mut overlay, KeyCode::Char('d'));
+        match overlay.take_action() {
+            Some(GitGraphAction::AttachDiff(text)) => {
+                assert!(text.contains("+two"), "{text}");
+                assert!(text.ends_with("```\n"), "{text}");
+            }
+            other => panic!("unexpected action: {other:?}"),
+        }
+    }
 }
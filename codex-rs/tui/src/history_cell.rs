@@ -27,6 +27,8 @@ use codex_core::config_types::ReasoningSummaryFormat;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpAuthStatus;
 use codex_core::protocol::McpInvocation;
+use codex_core::protocol::McpServerHealth;
+use codex_core::protocol::McpServerStatus;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol_config_types::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::plan_tool::PlanItemArg;
@@ -986,6 +988,7 @@ pub(crate) fn new_mcp_tools_output(
     resources: HashMap<String, Vec<Resource>>,
     resource_templates: HashMap<String, Vec<ResourceTemplate>>,
     auth_statuses: &HashMap<String, McpAuthStatus>,
+    server_statuses: &HashMap<String, McpServerStatus>,
 ) -> PlainHistoryCell {
     let mut lines: Vec<Line<'static>> = vec![
         "/mcp".magenta().into(),
@@ -1020,6 +1023,22 @@ pub(crate) fn new_mcp_tools_output(
             vec!["    • Status: ".into(), "disabled".red()].into()
         };
         lines.push(status_line);
+        if let Some(server_status) = server_statuses.get(server.as_str()) {
+            let health = server_status.health.to_string();
+            let health = match server_status.health {
+                McpServerHealth::Ready => health.green(),
+                McpServerHealth::Restarting => health.cyan(),
+                McpServerHealth::Failed => health.red(),
+            };
+            let mut spans = vec!["    • Health: ".into(), health];
+            if server_status.restarts > 0 {
+                spans.push(format!(" (restarted {}x)", server_status.restarts).dim());
+            }
+            if let Some(error) = server_status.error.as_ref() {
+                spans.push(format!(": {error}").dim());
+            }
+            lines.push(spans.into());
+        }
         lines.push(vec!["    • Auth: ".into(), status.to_string().into()].into());
 
         match &cfg.transport {
@@ -26,6 +26,55 @@ impl KeyBinding {
             && self.modifiers == event.modifiers
             && (event.kind == KeyEventKind::Press || event.kind == KeyEventKind::Repeat)
     }
+
+    /// Like [`KeyBinding::is_press`], but ignores Shift on characters and
+    /// Shift+Tab: terminals disagree on whether they report it, and the
+    /// character itself already says whether Shift was held.
+    pub(crate) fn matches(&self, event: &KeyEvent) -> bool {
+        let modifiers = match event.code {
+            KeyCode::Char(_) | KeyCode::BackTab => event.modifiers - KeyModifiers::SHIFT,
+            _ => event.modifiers,
+        };
+        self.key == event.code
+            && self.modifiers == modifiers
+            && (event.kind == KeyEventKind::Press || event.kind == KeyEventKind::Repeat)
+    }
+
+    pub(crate) fn key(&self) -> KeyCode {
+        self.key
+    }
+
+    pub(crate) fn modifiers(&self) -> KeyModifiers {
+        self.modifiers
+    }
+
+    /// Short label for pager-style hint bars, e.g. `Ctrl+t`, `PgUp` or `↑`.
+    pub(crate) fn compact_label(&self) -> String {
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            label.push_str("Shift+");
+        }
+        match self.key {
+            KeyCode::Up => label.push('↑'),
+            KeyCode::Down => label.push('↓'),
+            KeyCode::Left => label.push('←'),
+            KeyCode::Right => label.push('→'),
+            KeyCode::Enter => label.push('⏎'),
+            KeyCode::PageUp => label.push_str("PgUp"),
+            KeyCode::PageDown => label.push_str("PgDn"),
+            KeyCode::BackTab => label.push_str("Shift+Tab"),
+            KeyCode::Char(' ') => label.push_str("Space"),
+            KeyCode::Char(c) => label.push(c),
+            key => label.push_str(&key.to_string()),
+        }
+        label
+    }
 }
 
 pub(crate) const fn plain(key: KeyCode) -> KeyBinding {
@@ -0,0 +1,754 @@
+//! Named TUI actions and the key chords bound to them.
+//!
+//! The effective keymap is a preset (`default`, `vim` or `emacs`) with the
+//! `[tui.keymap]` overrides from config.toml applied on top. It is validated
+//! once at startup and then read through [`keymap()`] by the key handlers and
+//! by the footer and overlay hints, so the hints always show what is bound.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use codex_core::config_types::KeymapPreset;
+use codex_core::config_types::TuiKeymap;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+
+use crate::key_hint::KeyBinding;
+
+static KEYMAP: OnceLock<Keymap> = OnceLock::new();
+
+/// Install the effective keymap. Only the first call has an effect.
+pub(crate) fn init(keymap: Keymap) {
+    let _ = KEYMAP.set(keymap);
+}
+
+/// The effective keymap; the default preset until [`init`] has run.
+pub(crate) fn keymap() -> &'static Keymap {
+    KEYMAP.get_or_init(|| Keymap::preset(KeymapPreset::Default))
+}
+
+/// Where an action is handled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum KeymapContext {
+    /// Handled by `App` before the composer sees the key.
+    Global,
+    /// The chat composer.
+    Composer,
+    /// Transcript and static pager overlays.
+    Pager,
+    /// The git graph overlay, which also handles the pager actions.
+    GitGraph,
+    /// The session picker.
+    Picker,
+}
+
+impl KeymapContext {
+    /// Global keys are intercepted before the composer, and the git graph
+    /// sees the pager's keys as well as its own, so each pair shares one set
+    /// of chords for conflict detection.
+    fn conflicts_with(self, other: KeymapContext) -> bool {
+        use KeymapContext::*;
+        matches!(
+            (self, other),
+            (Global | Composer, Global | Composer)
+                | (Pager | GitGraph, Pager | GitGraph)
+                | (Picker, Picker)
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum KeymapAction {
+    ShowTranscript,
+    ShowGitGraph,
+    OpenSessionPicker,
+    ToggleShortcuts,
+    HistoryPrevious,
+    HistoryNext,
+    Exit,
+    PagerScrollUp,
+    PagerScrollDown,
+    PagerPageUp,
+    PagerPageDown,
+    PagerHome,
+    PagerEnd,
+    PagerFirstCommit,
+    PagerLastCommit,
+    PagerSearch,
+    PagerNextMatch,
+    PagerPreviousMatch,
+    PagerClose,
+    GitGraphToggleDiff,
+    GitGraphFilterBranch,
+    GitGraphFilterAuthor,
+    GitGraphNextBranch,
+    GitGraphPreviousBranch,
+    GitGraphAttachDiff,
+    GitGraphExplain,
+    GitGraphRefresh,
+    GitGraphBack,
+    PickerUp,
+    PickerDown,
+    PickerFirst,
+    PickerLast,
+    PickerPageUp,
+    PickerPageDown,
+    PickerResume,
+    PickerDelete,
+    PickerCycleView,
+    PickerCopyId,
+    PickerNewSession,
+    PickerRefresh,
+    PickerClose,
+}
+
+/// Every action, in the order conflicts and hints are reported.
+const ACTIONS: &[KeymapAction] = &[
+    KeymapAction::ShowTranscript,
+    KeymapAction::ShowGitGraph,
+    KeymapAction::OpenSessionPicker,
+    KeymapAction::ToggleShortcuts,
+    KeymapAction::HistoryPrevious,
+    KeymapAction::HistoryNext,
+    KeymapAction::Exit,
+    KeymapAction::PagerScrollUp,
+    KeymapAction::PagerScrollDown,
+    KeymapAction::PagerPageUp,
+    KeymapAction::PagerPageDown,
+    KeymapAction::PagerHome,
+    KeymapAction::PagerEnd,
+    KeymapAction::PagerFirstCommit,
+    KeymapAction::PagerLastCommit,
+    KeymapAction::PagerSearch,
+    KeymapAction::PagerNextMatch,
+    KeymapAction::PagerPreviousMatch,
+    KeymapAction::PagerClose,
+    KeymapAction::GitGraphToggleDiff,
+    KeymapAction::GitGraphFilterBranch,
+    KeymapAction::GitGraphFilterAuthor,
+    KeymapAction::GitGraphNextBranch,
+    KeymapAction::GitGraphPreviousBranch,
+    KeymapAction::GitGraphAttachDiff,
+    KeymapAction::GitGraphExplain,
+    KeymapAction::GitGraphRefresh,
+    KeymapAction::GitGraphBack,
+    KeymapAction::PickerUp,
+    KeymapAction::PickerDown,
+    KeymapAction::PickerFirst,
+    KeymapAction::PickerLast,
+    KeymapAction::PickerPageUp,
+    KeymapAction::PickerPageDown,
+    KeymapAction::PickerResume,
+    KeymapAction::PickerDelete,
+    KeymapAction::PickerCycleView,
+    KeymapAction::PickerCopyId,
+    KeymapAction::PickerNewSession,
+    KeymapAction::PickerRefresh,
+    KeymapAction::PickerClose,
+];
+
+impl KeymapAction {
+    /// The name used in `[tui.keymap]`.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            KeymapAction::ShowTranscript => "show_transcript",
+            KeymapAction::ShowGitGraph => "show_git_graph",
+            KeymapAction::OpenSessionPicker => "open_session_picker",
+            KeymapAction::ToggleShortcuts => "toggle_shortcuts",
+            KeymapAction::HistoryPrevious => "history_previous",
+            KeymapAction::HistoryNext => "history_next",
+            KeymapAction::Exit => "exit",
+            KeymapAction::PagerScrollUp => "pager_scroll_up",
+            KeymapAction::PagerScrollDown => "pager_scroll_down",
+            KeymapAction::PagerPageUp => "pager_page_up",
+            KeymapAction::PagerPageDown => "pager_page_down",
+            KeymapAction::PagerHome => "pager_home",
+            KeymapAction::PagerEnd => "pager_end",
+            KeymapAction::PagerFirstCommit => "pager_first_commit",
+            KeymapAction::PagerLastCommit => "pager_last_commit",
+            KeymapAction::PagerSearch => "pager_search",
+            KeymapAction::PagerNextMatch => "pager_next_match",
+            KeymapAction::PagerPreviousMatch => "pager_previous_match",
+            KeymapAction::PagerClose => "pager_close",
+            KeymapAction::GitGraphToggleDiff => "git_graph_toggle_diff",
+            KeymapAction::GitGraphFilterBranch => "git_graph_filter_branch",
+            KeymapAction::GitGraphFilterAuthor => "git_graph_filter_author",
+            KeymapAction::GitGraphNextBranch => "git_graph_next_branch",
+            KeymapAction::GitGraphPreviousBranch => "git_graph_previous_branch",
+            KeymapAction::GitGraphAttachDiff => "git_graph_attach_diff",
+            KeymapAction::GitGraphExplain => "git_graph_explain",
+            KeymapAction::GitGraphRefresh => "git_graph_refresh",
+            KeymapAction::GitGraphBack => "git_graph_back",
+            KeymapAction::PickerUp => "picker_up",
+            KeymapAction::PickerDown => "picker_down",
+            KeymapAction::PickerFirst => "picker_first",
+            KeymapAction::PickerLast => "picker_last",
+            KeymapAction::PickerPageUp => "picker_page_up",
+            KeymapAction::PickerPageDown => "picker_page_down",
+            KeymapAction::PickerResume => "picker_resume",
+            KeymapAction::PickerDelete => "picker_delete",
+            KeymapAction::PickerCycleView => "picker_cycle_view",
+            KeymapAction::PickerCopyId => "picker_copy_id",
+            KeymapAction::PickerNewSession => "picker_new_session",
+            KeymapAction::PickerRefresh => "picker_refresh",
+            KeymapAction::PickerClose => "picker_close",
+        }
+    }
+
+    pub(crate) fn context(self) -> KeymapContext {
+        match self {
+            KeymapAction::ShowTranscript
+            | KeymapAction::ShowGitGraph
+            | KeymapAction::OpenSessionPicker => KeymapContext::Global,
+            KeymapAction::ToggleShortcuts
+            | KeymapAction::HistoryPrevious
+            | KeymapAction::HistoryNext
+            | KeymapAction::Exit => KeymapContext::Composer,
+            KeymapAction::PagerScrollUp
+            | KeymapAction::PagerScrollDown
+            | KeymapAction::PagerPageUp
+            | KeymapAction::PagerPageDown
+            | KeymapAction::PagerHome
+            | KeymapAction::PagerEnd
+            | KeymapAction::PagerFirstCommit
+            | KeymapAction::PagerLastCommit
+            | KeymapAction::PagerSearch
+            | KeymapAction::PagerNextMatch
+            | KeymapAction::PagerPreviousMatch
+            | KeymapAction::PagerClose => KeymapContext::Pager,
+            KeymapAction::GitGraphToggleDiff
+            | KeymapAction::GitGraphFilterBranch
+            | KeymapAction::GitGraphFilterAuthor
+            | KeymapAction::GitGraphNextBranch
+            | KeymapAction::GitGraphPreviousBranch
+            | KeymapAction::GitGraphAttachDiff
+            | KeymapAction::GitGraphExplain
+            | KeymapAction::GitGraphRefresh
+            | KeymapAction::GitGraphBack => KeymapContext::GitGraph,
+            KeymapAction::PickerUp
+            | KeymapAction::PickerDown
+            | KeymapAction::PickerFirst
+            | KeymapAction::PickerLast
+            | KeymapAction::PickerPageUp
+            | KeymapAction::PickerPageDown
+            | KeymapAction::PickerResume
+            | KeymapAction::PickerDelete
+            | KeymapAction::PickerCycleView
+            | KeymapAction::PickerCopyId
+            | KeymapAction::PickerNewSession
+            | KeymapAction::PickerRefresh
+            | KeymapAction::PickerClose => KeymapContext::Picker,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        ACTIONS.iter().copied().find(|action| action.name() == name)
+    }
+
+    /// Chords bound to the action by `preset`.
+    fn preset_chords(self, preset: KeymapPreset) -> &'static [&'static str] {
+        use KeymapAction::*;
+        use KeymapPreset::Emacs;
+        use KeymapPreset::Vim;
+        match (self, preset) {
+            (ShowTranscript, _) => &["ctrl-t"],
+            (ShowGitGraph, _) => &["ctrl-g"],
+            (OpenSessionPicker, _) => &["ctrl-x", "ctrl-q"],
+            (ToggleShortcuts, _) => &["?"],
+            (HistoryPrevious, Emacs) => &["up", "ctrl-p"],
+            (HistoryPrevious, _) => &["up"],
+            (HistoryNext, Emacs) => &["down", "ctrl-n"],
+            (HistoryNext, _) => &["down"],
+            (Exit, _) => &["ctrl-d"],
+            (PagerScrollUp, Emacs) => &["up", "ctrl-p"],
+            (PagerScrollUp, _) => &["up", "k"],
+            (PagerScrollDown, Emacs) => &["down", "ctrl-n"],
+            (PagerScrollDown, _) => &["down", "j"],
+            (PagerPageUp, Vim) => &["pgup", "ctrl-b", "ctrl-u"],
+            (PagerPageUp, Emacs) => &["pgup", "alt-v"],
+            (PagerPageUp, _) => &["pgup"],
+            (PagerPageDown, Vim) => &["pgdn", "space", "ctrl-f", "ctrl-d"],
+            (PagerPageDown, Emacs) => &["pgdn", "space", "ctrl-v"],
+            (PagerPageDown, _) => &["pgdn", "space"],
+            (PagerHome, Emacs) => &["home", "alt-<"],
+            (PagerHome, _) => &["home"],
+            (PagerEnd, Emacs) => &["end", "alt->"],
+            (PagerEnd, _) => &["end"],
+            (PagerFirstCommit, _) => &["g g"],
+            (PagerLastCommit, _) => &["G"],
+            (PagerSearch, Emacs) => &["/", "ctrl-s"],
+            (PagerSearch, _) => &["/"],
+            (PagerNextMatch, _) => &["n"],
+            (PagerPreviousMatch, Emacs) => &["N", "ctrl-r"],
+            (PagerPreviousMatch, _) => &["N"],
+            (PagerClose, Emacs) => &["q", "ctrl-c", "ctrl-g"],
+            (PagerClose, _) => &["q", "ctrl-c"],
+            (GitGraphToggleDiff, _) => &["enter"],
+            (GitGraphFilterBranch, _) => &["b"],
+            (GitGraphFilterAuthor, _) => &["a"],
+            (GitGraphNextBranch, _) => &["t"],
+            (GitGraphPreviousBranch, _) => &["T"],
+            (GitGraphAttachDiff, _) => &["d"],
+            (GitGraphExplain, _) => &["e"],
+            (GitGraphRefresh, _) => &["r"],
+            (GitGraphBack, _) => &["esc"],
+            (PickerUp, Vim) => &["up", "k"],
+            (PickerUp, Emacs) => &["up", "ctrl-p"],
+            (PickerUp, _) => &["up"],
+            (PickerDown, Vim) => &["down", "j"],
+            (PickerDown, Emacs) => &["down", "ctrl-n"],
+            (PickerDown, _) => &["down"],
+            (PickerFirst, Vim) => &["home", "g"],
+            (PickerFirst, Emacs) => &["home", "alt-<"],
+            (PickerFirst, _) => &["home"],
+            (PickerLast, Vim) => &["end", "G"],
+            (PickerLast, Emacs) => &["end", "alt->"],
+            (PickerLast, _) => &["end"],
+            (PickerPageUp, Vim) => &["pgup", "ctrl-b", "ctrl-u"],
+            (PickerPageUp, Emacs) => &["pgup", "alt-v"],
+            (PickerPageUp, _) => &["pgup"],
+            (PickerPageDown, Vim) => &["pgdn", "ctrl-f", "ctrl-d"],
+            (PickerPageDown, Emacs) => &["pgdn", "ctrl-v"],
+            (PickerPageDown, _) => &["pgdn"],
+            (PickerResume, _) => &["enter"],
+            (PickerDelete, _) => &["d"],
+            (PickerCycleView, _) => &["f"],
+            (PickerCopyId, _) => &["c"],
+            (PickerNewSession, _) => &["n"],
+            (PickerRefresh, _) => &["r"],
+            (PickerClose, Emacs) => &["q", "esc", "ctrl-g"],
+            (PickerClose, _) => &["q", "esc"],
+        }
+    }
+}
+
+/// One chord, or two pressed in order (`"g g"`). Sequences are only
+/// supported in the pager.
+type KeySequence = Vec<KeyBinding>;
+
+/// Result of feeding a key to [`Keymap::resolve`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Resolved {
+    Action(KeymapAction),
+    /// The key starts a sequence; wait for the next one.
+    Pending,
+    Unbound,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Keymap {
+    bindings: BTreeMap<KeymapAction, Vec<KeySequence>>,
+}
+
+impl Keymap {
+    pub(crate) fn preset(preset: KeymapPreset) -> Self {
+        let bindings = ACTIONS
+            .iter()
+            .map(|&action| {
+                let sequences = action
+                    .preset_chords(preset)
+                    .iter()
+                    .filter_map(|chord| parse_sequence(chord).ok())
+                    .collect();
+                (action, sequences)
+            })
+            .collect();
+        Self { bindings }
+    }
+
+    /// Apply the `[tui.keymap]` overrides to their preset and check the
+    /// result for conflicts. Every problem found is reported, one per line.
+    pub(crate) fn from_config(config: &TuiKeymap) -> Result<Self, String> {
+        let mut keymap = Self::preset(config.preset);
+        let mut errors = Vec::new();
+
+        for (name, chords) in &config.bindings {
+            let Some(action) = KeymapAction::from_name(name) else {
+                errors.push(format!("unknown action `{name}`"));
+                continue;
+            };
+            let mut sequences = Vec::new();
+            for chord in chords.chords() {
+                match parse_sequence(chord) {
+                    Ok(sequence)
+                        if sequence.len() > 1 && action.context() != KeymapContext::Pager =>
+                    {
+                        errors.push(format!(
+                            "`{name}`: key sequences like `{chord}` are only supported for pager actions"
+                        ));
+                    }
+                    Ok(sequence) => sequences.push(sequence),
+                    Err(err) => errors.push(format!("`{name}`: {err}")),
+                }
+            }
+            keymap.bindings.insert(action, sequences);
+        }
+
+        errors.extend(keymap.conflicts());
+        if errors.is_empty() {
+            Ok(keymap)
+        } else {
+            Err(errors.join("\n"))
+        }
+    }
+
+    /// Chords bound to two actions that can see the same key, including a
+    /// chord that is also the start of a sequence.
+    fn conflicts(&self) -> Vec<String> {
+        let mut conflicts = Vec::new();
+        let entries: Vec<(KeymapAction, &KeySequence)> = self
+            .bindings
+            .iter()
+            .flat_map(|(&action, sequences)| sequences.iter().map(move |seq| (action, seq)))
+            .collect();
+        for (i, &(action, sequence)) in entries.iter().enumerate() {
+            for &(other, other_sequence) in &entries[i + 1..] {
+                if action == other || !action.context().conflicts_with(other.context()) {
+                    continue;
+                }
+                let shorter = sequence.len().min(other_sequence.len());
+                if sequence[..shorter] == other_sequence[..shorter] {
+                    conflicts.push(format!(
+                        "`{}` is bound to both `{}` and `{}`",
+                        sequence_label(&sequence[..shorter]),
+                        action.name(),
+                        other.name()
+                    ));
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Whether `event` is a single-chord binding of `action`.
+    pub(crate) fn is_match(&self, action: KeymapAction, event: &KeyEvent) -> bool {
+        self.bindings.get(&action).is_some_and(|sequences| {
+            sequences
+                .iter()
+                .any(|sequence| sequence.len() == 1 && sequence[0].matches(event))
+        })
+    }
+
+    /// The action in `context` bound to `event` as a single chord.
+    pub(crate) fn action_for(
+        &self,
+        context: KeymapContext,
+        event: &KeyEvent,
+    ) -> Option<KeymapAction> {
+        ACTIONS
+            .iter()
+            .copied()
+            .filter(|action| action.context() == context)
+            .find(|&action| self.is_match(action, event))
+    }
+
+    /// Like [`Keymap::action_for`], but also completes sequences. `pending`
+    /// holds the first chord of a sequence between calls.
+    pub(crate) fn resolve(
+        &self,
+        context: KeymapContext,
+        pending: &mut Option<KeyBinding>,
+        event: &KeyEvent,
+    ) -> Resolved {
+        let in_context = || {
+            self.bindings
+                .iter()
+                .filter(move |(action, _)| action.context() == context)
+                .flat_map(|(&action, sequences)| sequences.iter().map(move |seq| (action, seq)))
+        };
+
+        if let Some(first) = pending.take()
+            && let Some((action, _)) = in_context().find(|(_, sequence)| {
+                sequence.len() == 2 && sequence[0] == first && sequence[1].matches(event)
+            })
+        {
+            return Resolved::Action(action);
+        }
+        if let Some(action) = self.action_for(context, event) {
+            return Resolved::Action(action);
+        }
+        if let Some((_, sequence)) =
+            in_context().find(|(_, sequence)| sequence.len() == 2 && sequence[0].matches(event))
+        {
+            *pending = Some(sequence[0]);
+            return Resolved::Pending;
+        }
+        Resolved::Unbound
+    }
+
+    /// First single-chord binding of `action`, for hints rendered with
+    /// [`KeyBinding`]'s own style.
+    pub(crate) fn key_binding(&self, action: KeymapAction) -> Option<KeyBinding> {
+        self.bindings
+            .get(&action)?
+            .iter()
+            .find(|sequence| sequence.len() == 1)
+            .map(|sequence| sequence[0])
+    }
+
+    /// Compact label of the first binding of `action`, or an empty string
+    /// when it is unbound.
+    pub(crate) fn label(&self, action: KeymapAction) -> String {
+        self.bindings
+            .get(&action)
+            .and_then(|sequences| sequences.first())
+            .map(|sequence| sequence_label(&sequence[..]))
+            .unwrap_or_default()
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset(KeymapPreset::Default)
+    }
+}
+
+fn sequence_label(sequence: &[KeyBinding]) -> String {
+    let plain_chars = sequence.iter().all(|binding| {
+        binding.modifiers() == KeyModifiers::NONE
+            && matches!(binding.key(), KeyCode::Char(c) if c != ' ')
+    });
+    let labels: Vec<String> = sequence.iter().map(KeyBinding::compact_label).collect();
+    if plain_chars {
+        labels.concat()
+    } else {
+        labels.join(" ")
+    }
+}
+
+/// Parse a chord (`"ctrl-t"`) or a sequence of two (`"g g"`).
+fn parse_sequence(text: &str) -> Result<KeySequence, String> {
+    let sequence = text
+        .split_whitespace()
+        .map(parse_chord)
+        .collect::<Result<Vec<_>, _>>()?;
+    match sequence.len() {
+        0 => Err("empty key chord".to_string()),
+        1 | 2 => Ok(sequence),
+        _ => Err(format!("`{text}`: sequences are limited to two chords")),
+    }
+}
+
+/// Parse `[modifier-]*key`, e.g. `ctrl-t`, `alt+v`, `C-x`, `G`, `pgdn`.
+/// Modifiers are separated by `-` or `+`; a lone `-` or `+` is the key.
+fn parse_chord(text: &str) -> Result<KeyBinding, String> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = text;
+    while let Some(idx) = rest.find(['-', '+'])
+        && idx > 0
+        && idx + 1 < rest.len()
+    {
+        let modifier = match &rest[..idx] {
+            "C" => KeyModifiers::CONTROL,
+            "M" => KeyModifiers::ALT,
+            "S" => KeyModifiers::SHIFT,
+            name => match name.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" | "option" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier `{name}` in `{text}`")),
+            },
+        };
+        modifiers |= modifier;
+        rest = &rest[idx + 1..];
+    }
+
+    let mut chars = rest.chars();
+    let key = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match rest.to_ascii_lowercase().as_str() {
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pgup" | "pageup" => KeyCode::PageUp,
+            "pgdn" | "pagedown" => KeyCode::PageDown,
+            name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                Some(n @ 1..=12) => KeyCode::F(n),
+                _ => return Err(format!("unknown key `{rest}` in `{text}`")),
+            },
+        },
+    };
+
+    // Shift is folded into the key itself so bindings compare equal no
+    // matter how the terminal reports it (see `KeyBinding::matches`).
+    let key = match key {
+        KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+            modifiers -= KeyModifiers::SHIFT;
+            KeyCode::Char(c.to_ascii_uppercase())
+        }
+        KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+            modifiers -= KeyModifiers::SHIFT;
+            KeyCode::BackTab
+        }
+        key => key,
+    };
+    Ok(KeyBinding::new(key, modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::config_types::KeymapBindings;
+    use pretty_assertions::assert_eq;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn config(preset: KeymapPreset, bindings: &[(&str, &[&str])]) -> TuiKeymap {
+        TuiKeymap {
+            preset,
+            bindings: bindings
+                .iter()
+                .map(|(name, chords)| {
+                    (
+                        (*name).to_string(),
+                        KeymapBindings::Many(chords.iter().map(|c| (*c).to_string()).collect()),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn presets_have_no_conflicts() {
+        for preset in [
+            KeymapPreset::Default,
+            KeymapPreset::Vim,
+            KeymapPreset::Emacs,
+        ] {
+            let keymap = Keymap::preset(preset);
+            assert_eq!(keymap.conflicts(), Vec::<String>::new(), "{preset:?}");
+            for action in ACTIONS {
+                assert_eq!(
+                    keymap.bindings[action].len(),
+                    action.preset_chords(preset).len(),
+                    "{preset:?} {action:?} has an unparsable chord"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn parses_chords() {
+        let parse = |text| parse_chord(text).map(|b| (b.key(), b.modifiers()));
+        assert_eq!(
+            parse("ctrl-t"),
+            Ok((KeyCode::Char('t'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(parse("M-v"), Ok((KeyCode::Char('v'), KeyModifiers::ALT)));
+        assert_eq!(
+            parse("shift+g"),
+            Ok((KeyCode::Char('G'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            parse("shift-tab"),
+            Ok((KeyCode::BackTab, KeyModifiers::NONE))
+        );
+        assert_eq!(parse("alt--"), Ok((KeyCode::Char('-'), KeyModifiers::ALT)));
+        assert_eq!(parse("PgDn"), Ok((KeyCode::PageDown, KeyModifiers::NONE)));
+        assert_eq!(parse("f5"), Ok((KeyCode::F(5), KeyModifiers::NONE)));
+        assert_eq!(
+            parse("hyper-x"),
+            Err("unknown modifier `hyper` in `hyper-x`".to_string())
+        );
+        assert_eq!(
+            parse("ctrl-foo"),
+            Err("unknown key `foo` in `ctrl-foo`".to_string())
+        );
+    }
+
+    #[test]
+    fn overrides_replace_preset_bindings() {
+        let keymap = Keymap::from_config(&config(
+            KeymapPreset::Vim,
+            &[("show_git_graph", &["alt-g"]), ("pager_search", &[])],
+        ))
+        .expect("valid keymap");
+
+        let ctrl_g = key(KeyCode::Char('g'), KeyModifiers::CONTROL);
+        let alt_g = key(KeyCode::Char('g'), KeyModifiers::ALT);
+        assert_eq!(keymap.action_for(KeymapContext::Global, &ctrl_g), None);
+        assert_eq!(
+            keymap.action_for(KeymapContext::Global, &alt_g),
+            Some(KeymapAction::ShowGitGraph)
+        );
+        assert_eq!(keymap.label(KeymapAction::ShowGitGraph), "Alt+g");
+        assert_eq!(keymap.label(KeymapAction::PagerSearch), "");
+        // Vim preset bindings that were not overridden remain.
+        assert!(keymap.is_match(
+            KeymapAction::PagerPageDown,
+            &key(KeyCode::Char('d'), KeyModifiers::CONTROL)
+        ));
+    }
+
+    #[test]
+    fn reports_conflicts_and_invalid_entries() {
+        let err = Keymap::from_config(&config(
+            KeymapPreset::Default,
+            &[
+                ("show_transcript", &["?"]),
+                ("pager_close", &["g"]),
+                ("pager_scroll_down", &["b"]),
+                ("picker_up", &["k k"]),
+                ("scroll_sideways", &["h"]),
+            ],
+        ))
+        .expect_err("conflicting keymap");
+
+        assert_eq!(
+            err,
+            [
+                "`picker_up`: key sequences like `k k` are only supported for pager actions",
+                "unknown action `scroll_sideways`",
+                "`?` is bound to both `show_transcript` and `toggle_shortcuts`",
+                "`b` is bound to both `pager_scroll_down` and `git_graph_filter_branch`",
+                "`g` is bound to both `pager_first_commit` and `pager_close`",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn resolves_sequences() {
+        let keymap = Keymap::default();
+        let g = key(KeyCode::Char('g'), KeyModifiers::NONE);
+        let j = key(KeyCode::Char('j'), KeyModifiers::NONE);
+        let shift_g = key(KeyCode::Char('G'), KeyModifiers::SHIFT);
+        let mut pending = None;
+
+        assert_eq!(
+            keymap.resolve(KeymapContext::Pager, &mut pending, &g),
+            Resolved::Pending
+        );
+        assert_eq!(
+            keymap.resolve(KeymapContext::Pager, &mut pending, &g),
+            Resolved::Action(KeymapAction::PagerFirstCommit)
+        );
+        // An unrelated key drops the pending chord and is handled on its own.
+        keymap.resolve(KeymapContext::Pager, &mut pending, &g);
+        assert_eq!(
+            keymap.resolve(KeymapContext::Pager, &mut pending, &j),
+            Resolved::Action(KeymapAction::PagerScrollDown)
+        );
+        assert_eq!(pending, None);
+        assert_eq!(
+            keymap.resolve(KeymapContext::Pager, &mut pending, &shift_g),
+            Resolved::Action(KeymapAction::PagerLastCommit)
+        );
+        assert_eq!(keymap.label(KeymapAction::PagerFirstCommit), "gg");
+        assert_eq!(keymap.label(KeymapAction::PagerPageUp), "PgUp");
+    }
+}
@@ -56,10 +56,12 @@ mod git_graph_widget;
 mod history_cell;
 pub mod insert_history;
 mod key_hint;
+mod keymap;
 pub mod live_wrap;
 mod markdown;
 mod markdown_render;
 mod markdown_stream;
+mod ollama_models;
 pub mod onboarding;
 mod pager_overlay;
 pub mod public_widgets;
@@ -269,6 +271,15 @@ pub async fn run_main(
     let global_codex_home = agent_context.global_codex_home().to_path_buf();
     let config = agent_context.into_config();
 
+    match keymap::Keymap::from_config(&config.tui_keymap) {
+        Ok(keymap) => keymap::init(keymap),
+        #[allow(clippy::print_stderr)]
+        Err(err) => {
+            eprintln!("Error in [tui.keymap]:\n{err}");
+            std::process::exit(1);
+        }
+    }
+
     let active_profile = config.active_profile.clone();
     let log_dir = codex_core::config::log_dir(&config)?;
     std::fs::create_dir_all(&log_dir)?;
@@ -334,11 +345,18 @@ pub async fn run_main(
         let otel_layer = OpenTelemetryTracingBridge::new(&provider.logger).with_filter(
             tracing_subscriber::filter::filter_fn(codex_core::otel_init::codex_export_filter),
         );
+        let trace_layer =
+            provider
+                .tracing_layer()
+                .with_filter(tracing_subscriber::filter::filter_fn(
+                    codex_core::otel_init::codex_trace_filter,
+                ));
 
         let _ = tracing_subscriber::registry()
             .with(file_layer)
             .with(feedback_layer)
             .with(otel_layer)
+            .with(trace_layer)
             .try_init();
     } else {
         let _ = tracing_subscriber::registry()
@@ -464,7 +482,12 @@ async fn run_ratatui_app(
     // Initialize high-fidelity session event logging if enabled.
     session_log::maybe_init(&initial_config);
 
-    let mut auth_manager = AuthManager::shared(global_codex_home.clone(), false);
+    let mut auth_manager = AuthManager::shared_with_profile(
+        global_codex_home.clone(),
+        initial_config.auth_profile.clone(),
+        initial_config.cli_auth_credentials_store_mode,
+        false,
+    );
     let login_status = get_login_status(&initial_config, &global_codex_home);
     let should_show_trust_screen = should_show_trust_screen(&initial_config);
     let should_show_windows_wsl_screen =
@@ -517,8 +540,14 @@ async fn run_ratatui_app(
             .await;
             allowed_agents = context.allowed_agents().to_vec();
             global_codex_home = context.global_codex_home().to_path_buf();
-            auth_manager = AuthManager::shared(global_codex_home.clone(), false);
-            context.into_config()
+            let config = context.into_config();
+            auth_manager = AuthManager::shared_with_profile(
+                global_codex_home.clone(),
+                config.auth_profile.clone(),
+                config.cli_auth_credentials_store_mode,
+                false,
+            );
+            config
         } else {
             initial_config
         }
@@ -663,7 +692,11 @@ fn get_login_status(config: &Config, auth_codex_home: &Path) -> LoginStatus {
     if config.model_provider.requires_openai_auth {
         // Reading the OpenAI API key is an async operation because it may need
         // to refresh the token. Block on it.
-        match CodexAuth::from_codex_home(auth_codex_home) {
+        match CodexAuth::from_codex_home_for_profile(
+            auth_codex_home,
+            config.auth_profile.as_deref(),
+            config.cli_auth_credentials_store_mode,
+        ) {
             Ok(Some(auth)) => LoginStatus::AuthMode(auth.mode),
             Ok(None) => LoginStatus::NotAuthenticated,
             Err(err) => {
@@ -0,0 +1,192 @@
+//! Local Ollama models for the `/model` picker when the session runs on the
+//! built-in `oss` provider, and pulling the ones that are not downloaded yet.
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use codex_core::config::Config;
+use codex_ollama::DEFAULT_OSS_MODEL;
+use codex_ollama::OllamaClient;
+use codex_ollama::PullEvent;
+use codex_ollama::PullProgressReporter;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::tui::FrameRequester;
+
+/// Models offered in the picker even when they are not downloaded yet.
+const SUGGESTED_MODELS: &[&str] = &[DEFAULT_OSS_MODEL, "gpt-oss:120b"];
+
+/// One entry of the Ollama `/model` picker.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct OllamaModelChoice {
+    pub(crate) name: String,
+    /// Size on disk in bytes; `None` when not downloaded.
+    pub(crate) size: Option<u64>,
+    pub(crate) context_length: Option<u64>,
+    pub(crate) downloaded: bool,
+}
+
+/// Lists the downloaded models with their context length, followed by the
+/// suggested models that are not downloaded yet.
+pub(crate) async fn load_model_choices(config: &Config) -> io::Result<Vec<OllamaModelChoice>> {
+    let client = OllamaClient::try_from_oss_provider(config).await?;
+    let mut models = client.list_models().await?;
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut choices = Vec::with_capacity(models.len() + SUGGESTED_MODELS.len());
+    for model in models {
+        // Context length is informational; keep the model if `show` fails.
+        let context_length = match client.show_model(&model.name).await {
+            Ok(details) => details.context_length,
+            Err(err) => {
+                tracing::warn!("failed to show Ollama model {}: {err}", model.name);
+                None
+            }
+        };
+        choices.push(OllamaModelChoice {
+            name: model.name,
+            size: model.size,
+            context_length,
+            downloaded: true,
+        });
+    }
+    for suggested in SUGGESTED_MODELS {
+        if !choices.iter().any(|choice| choice.name == *suggested) {
+            choices.push(OllamaModelChoice {
+                name: (*suggested).to_string(),
+                size: None,
+                context_length: None,
+                downloaded: false,
+            });
+        }
+    }
+    Ok(choices)
+}
+
+/// Progress of a model pull, shared between the reporter and the view that
+/// renders it.
+#[derive(Debug, Default)]
+pub(crate) struct PullProgress {
+    status: Option<String>,
+    totals_by_digest: HashMap<String, (u64, u64)>,
+    finished: bool,
+}
+
+impl PullProgress {
+    pub(crate) fn apply(&mut self, event: &PullEvent) {
+        match event {
+            PullEvent::Status(status) => self.status = Some(status.clone()),
+            PullEvent::ChunkProgress {
+                digest,
+                total,
+                completed,
+            } => {
+                let entry = self.totals_by_digest.entry(digest.clone()).or_default();
+                if let Some(total) = total {
+                    entry.0 = *total;
+                }
+                if let Some(completed) = completed {
+                    entry.1 = *completed;
+                }
+            }
+            PullEvent::Success | PullEvent::Error(_) => self.finished = true,
+        }
+    }
+
+    pub(crate) fn status(&self) -> Option<&str> {
+        self.status.as_deref()
+    }
+
+    /// Bytes downloaded and total bytes across all layers seen so far.
+    pub(crate) fn bytes(&self) -> (u64, u64) {
+        self.totals_by_digest
+            .values()
+            .fold((0, 0), |(completed, total), (t, c)| {
+                (completed + c, total + t)
+            })
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+/// Feeds pull events into a shared [`PullProgress`] and schedules a redraw.
+struct TuiPullReporter {
+    progress: Arc<Mutex<PullProgress>>,
+    frame_requester: FrameRequester,
+}
+
+impl PullProgressReporter for TuiPullReporter {
+    fn on_event(&mut self, event: &PullEvent) -> io::Result<()> {
+        if let Ok(mut progress) = self.progress.lock() {
+            progress.apply(event);
+        }
+        self.frame_requester.schedule_frame();
+        Ok(())
+    }
+}
+
+/// Pulls `model` in the background and reports the outcome with
+/// [`AppEvent::OllamaPullFinished`].
+pub(crate) fn spawn_pull(
+    config: Config,
+    model: String,
+    progress: Arc<Mutex<PullProgress>>,
+    frame_requester: FrameRequester,
+    app_event_tx: AppEventSender,
+) {
+    tokio::spawn(async move {
+        let mut reporter = TuiPullReporter {
+            progress: Arc::clone(&progress),
+            frame_requester,
+        };
+        let result = match OllamaClient::try_from_oss_provider(&config).await {
+            Ok(client) => client.pull_with_reporter(&model, &mut reporter).await,
+            Err(err) => Err(err),
+        };
+        if let Ok(mut progress) = progress.lock() {
+            progress.finished = true;
+        }
+        app_event_tx.send(AppEvent::OllamaPullFinished {
+            model,
+            result: result.map_err(|err| err.to_string()),
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn progress_sums_layers() {
+        let mut progress = PullProgress::default();
+        progress.apply(&PullEvent::Status("pulling manifest".to_string()));
+        progress.apply(&PullEvent::ChunkProgress {
+            digest: "sha256:a".to_string(),
+            total: Some(100),
+            completed: None,
+        });
+        progress.apply(&PullEvent::ChunkProgress {
+            digest: "sha256:b".to_string(),
+            total: Some(50),
+            completed: Some(50),
+        });
+        progress.apply(&PullEvent::ChunkProgress {
+            digest: "sha256:a".to_string(),
+            total: None,
+            completed: Some(25),
+        });
+
+        assert_eq!(progress.bytes(), (75, 150));
+        assert_eq!(progress.status(), Some("pulling manifest"));
+        assert!(!progress.is_finished());
+        progress.apply(&PullEvent::Success);
+        assert!(progress.is_finished());
+    }
+}
@@ -2,7 +2,7 @@
 
 use codex_core::AuthManager;
 use codex_core::auth::CLIENT_ID;
-use codex_core::auth::login_with_api_key;
+use codex_core::auth::login_with_api_key_for_profile;
 use codex_core::auth::read_openai_api_key_from_env;
 use codex_login::ServerOptions;
 use codex_login::ShutdownHandle;
@@ -454,7 +454,12 @@ impl AuthModeWidget {
     }
 
     fn save_api_key(&mut self, api_key: String) {
-        match login_with_api_key(&self.codex_home, &api_key) {
+        match login_with_api_key_for_profile(
+            &self.codex_home,
+            self.auth_manager.auth_profile(),
+            self.auth_manager.credentials_store_mode(),
+            &api_key,
+        ) {
             Ok(()) => {
                 self.error = None;
                 self.login_status = LoginStatus::AuthMode(AuthMode::ApiKey);
@@ -491,7 +496,9 @@ impl AuthModeWidget {
         }
 
         self.error = None;
-        let opts = ServerOptions::new(self.codex_home.clone(), CLIENT_ID.to_string());
+        let mut opts = ServerOptions::new(self.codex_home.clone(), CLIENT_ID.to_string());
+        opts.auth_profile = self.auth_manager.auth_profile().map(str::to_string);
+        opts.cli_auth_credentials_store_mode = self.auth_manager.credentials_store_mode();
         match run_login_server(opts) {
             Ok(child) => {
                 let sign_in_state = self.sign_in_state.clone();
@@ -4,13 +4,21 @@ use std::time::Duration;
 use std::time::Instant;
 
 use crate::cxresume_picker_widget::SessionInfo;
+use crate::git_graph_widget::GitGraphAction;
+use crate::git_graph_widget::GitGraphOverlay;
 use crate::history_cell::HistoryCell;
+use crate::key_hint::KeyBinding;
+use crate::keymap::KeymapAction;
+use crate::keymap::KeymapContext;
+use crate::keymap::Resolved;
+use crate::keymap::keymap;
 use crate::render::line_utils::push_owned_lines;
 use crate::tui;
 use crate::tui::TuiEvent;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Color;
@@ -29,6 +37,7 @@ pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
     SessionPicker(SessionPickerOverlay),
+    GitGraph(GitGraphOverlay),
 }
 
 /// Session picker overlay integrating PickerState for interactive navigation
@@ -139,6 +148,7 @@ impl Overlay {
             Overlay::Transcript(o) => o.handle_event(tui, event),
             Overlay::Static(o) => o.handle_event(tui, event),
             Overlay::SessionPicker(o) => o.handle_event(tui, event),
+            Overlay::GitGraph(o) => o.handle_event(tui, event),
         }
     }
 
@@ -147,6 +157,15 @@ impl Overlay {
             Overlay::Transcript(o) => o.is_done(),
             Overlay::Static(o) => o.is_done(),
             Overlay::SessionPicker(o) => o.is_done(),
+            Overlay::GitGraph(o) => o.is_done(),
+        }
+    }
+
+    /// Take the commit action chosen in the git graph overlay, if any.
+    pub(crate) fn take_git_graph_action(&mut self) -> Option<GitGraphAction> {
+        match self {
+            Overlay::GitGraph(o) => o.take_action(),
+            _ => None,
         }
     }
 
@@ -175,15 +194,38 @@ impl Overlay {
     }
 }
 
-// Common pager navigation hints rendered on the first line
-const PAGER_KEY_HINTS: &[(&str, &str)] = &[
-    ("↑/↓", "scroll"),
-    ("PgUp/PgDn", "page"),
-    ("Home/End", "jump"),
-];
+// Common pager navigation hints rendered on the first line, taken from the
+// effective keymap.
+fn pager_key_hints() -> Vec<(String, &'static str)> {
+    let keymap = keymap();
+    let pair = |first, second| format!("{}/{}", keymap.label(first), keymap.label(second));
+    vec![
+        (
+            pair(KeymapAction::PagerScrollUp, KeymapAction::PagerScrollDown),
+            "scroll",
+        ),
+        (
+            pair(KeymapAction::PagerPageUp, KeymapAction::PagerPageDown),
+            "page",
+        ),
+        (
+            pair(KeymapAction::PagerHome, KeymapAction::PagerEnd),
+            "jump",
+        ),
+    ]
+}
+
+fn render_pager_key_hints(area: Rect, buf: &mut Buffer) {
+    let hints = pager_key_hints();
+    let pairs: Vec<(&str, &str)> = hints
+        .iter()
+        .map(|(key, desc)| (key.as_str(), *desc))
+        .collect();
+    render_key_hints(area, buf, &pairs);
+}
 
 // Render a single line of key hints from (key, description) pairs.
-fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&str, &str)]) {
+pub(crate) fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&str, &str)]) {
     let key_hint_style = Style::default().fg(Color::Cyan);
     let mut spans: Vec<Span<'static>> = vec![" ".into()];
     let mut first = true;
@@ -220,7 +262,8 @@ struct PagerView {
     search_input: Option<String>,
     last_search: Option<String>,
     last_match_idx: Option<usize>,
-    g_pending: bool,
+    /// First chord of a key sequence such as `g g`.
+    pending_chord: Option<KeyBinding>,
     /// Wrapping behavior for rendering: soft wrap or no-wrap.
     wrap_mode: WrapMode,
     /// Horizontal scroll offset (columns) used when `wrap_mode` is NoWrap.
@@ -247,7 +290,7 @@ impl PagerView {
             search_input: None,
             last_search: None,
             last_match_idx: None,
-            g_pending: false,
+            pending_chord: None,
             wrap_mode: WrapMode::WordWrap,
             horiz_offset: 0,
             show_cursor_gutter: false,
@@ -485,25 +528,21 @@ impl PagerView {
             }
         }
 
-        match key_event {
-            // Ignore Enter/Esc for mode toggling; commit mode is active by default in Git Graph overlay
-            KeyEvent {
-                code: KeyCode::Enter | KeyCode::Esc,
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
-                return Ok(());
-            }
-            KeyEvent {
-                code: KeyCode::Up,
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            }
-            | KeyEvent {
-                code: KeyCode::Char('k'),
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+        let action =
+            match keymap().resolve(KeymapContext::Pager, &mut self.pending_chord, &key_event) {
+                Resolved::Action(action) => action,
+                Resolved::Pending => return Ok(()),
+                Resolved::Unbound => {
+                    if self.handle_unbound_key(key_event, area) {
+                        tui.frame_requester()
+                            .schedule_frame_in(Duration::from_millis(16));
+                    }
+                    return Ok(());
+                }
+            };
+
+        match action {
+            KeymapAction::PagerScrollUp => {
                 if self.commit_mode {
                     self.move_commit_vertical(-1, area.width, area.height);
                 } else {
@@ -512,18 +551,8 @@ impl PagerView {
                     }
                     self.ensure_cursor_visible(area.height as usize);
                 }
-                self.g_pending = false;
             }
-            KeyEvent {
-                code: KeyCode::Down,
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            }
-            | KeyEvent {
-                code: KeyCode::Char('j'),
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            KeymapAction::PagerScrollDown => {
                 if self.commit_mode {
                     self.move_commit_vertical(1, area.width, area.height);
                 } else {
@@ -534,76 +563,30 @@ impl PagerView {
                     }
                     self.ensure_cursor_visible(area.height as usize);
                 }
-                self.g_pending = false;
             }
-            // Horizontal scroll or commit-branch navigation
-            KeyEvent {
-                code: KeyCode::Char('h'),
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
-                if self.commit_mode {
-                    self.move_commit_horizontal(-1, area.width, area.height);
-                } else {
-                    self.horiz_offset = self.horiz_offset.saturating_sub(1);
-                }
-            }
-            KeyEvent {
-                code: KeyCode::Char('l'),
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
-                if self.commit_mode {
-                    self.move_commit_horizontal(1, area.width, area.height);
-                } else {
-                    self.horiz_offset = self.horiz_offset.saturating_add(1);
-                }
-            }
-            KeyEvent {
-                code: KeyCode::Char('0'),
-                kind: KeyEventKind::Press,
-                ..
-            } => {
-                self.horiz_offset = 0;
-            }
-            // Vim-like jumps: gg to top, G to bottom
-            KeyEvent {
-                code: KeyCode::Char('g'),
-                kind: KeyEventKind::Press,
-                ..
-            } => {
-                if self.g_pending {
-                    self.g_pending = false;
-                    // Auto-activate commit mode and jump to first commit
-                    if let Some(cache) = self.wrap_cache.as_ref() {
-                        // Find first line with commits
-                        if let Some((first_line, cols)) = cache
-                            .commit_cols
-                            .iter()
-                            .enumerate()
-                            .find(|(_, cols)| !cols.is_empty())
-                        {
-                            self.commit_mode = true; // Activate commit mode
-                            self.commit_cursor_line = Some(first_line);
-                            self.commit_cursor_col = cols[0];
-                            self.ensure_commit_visible(area.width, area.height);
-                        } else {
-                            // No commits found, fallback to normal mode
-                            self.cursor_idx = 0;
-                            self.ensure_cursor_visible(area.height as usize);
-                            self.last_match_idx = Some(0);
-                        }
+            KeymapAction::PagerFirstCommit => {
+                // Auto-activate commit mode and jump to first commit
+                if let Some(cache) = self.wrap_cache.as_ref() {
+                    // Find first line with commits
+                    if let Some((first_line, cols)) = cache
+                        .commit_cols
+                        .iter()
+                        .enumerate()
+                        .find(|(_, cols)| !cols.is_empty())
+                    {
+                        self.commit_mode = true; // Activate commit mode
+                        self.commit_cursor_line = Some(first_line);
+                        self.commit_cursor_col = cols[0];
+                        self.ensure_commit_visible(area.width, area.height);
+                    } else {
+                        // No commits found, fallback to normal mode
+                        self.cursor_idx = 0;
+                        self.ensure_cursor_visible(area.height as usize);
+                        self.last_match_idx = Some(0);
                     }
-                } else {
-                    self.g_pending = true;
                 }
             }
-            KeyEvent {
-                code: KeyCode::Char('G'),
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
-                self.g_pending = false;
+            KeymapAction::PagerLastCommit => {
                 if let Some(cache) = self.wrap_cache.as_ref()
                     && !cache.wrapped.is_empty()
                 {
@@ -628,11 +611,7 @@ impl PagerView {
                     }
                 }
             }
-            KeyEvent {
-                code: KeyCode::PageUp,
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            KeymapAction::PagerPageUp => {
                 let page = area.height as usize;
                 if self.commit_mode {
                     self.move_to_nearby_commit(-(page as isize) as i32, 0, area.width, area.height);
@@ -640,13 +619,8 @@ impl PagerView {
                     self.cursor_idx = self.cursor_idx.saturating_sub(page);
                     self.ensure_cursor_visible(page);
                 }
-                self.g_pending = false;
             }
-            KeyEvent {
-                code: KeyCode::PageDown | KeyCode::Char(' '),
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            KeymapAction::PagerPageDown => {
                 if let Some(cache) = self.wrap_cache.as_ref() {
                     let page = area.height as usize;
                     let last = cache.wrapped.len().saturating_sub(1);
@@ -657,24 +631,14 @@ impl PagerView {
                         self.ensure_cursor_visible(page);
                     }
                 }
-                self.g_pending = false;
             }
-            KeyEvent {
-                code: KeyCode::Home,
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            KeymapAction::PagerHome => {
                 self.cursor_idx = 0;
                 self.commit_cursor_line = None;
                 self.commit_mode = false;
                 self.ensure_cursor_visible(area.height as usize);
-                self.g_pending = false;
             }
-            KeyEvent {
-                code: KeyCode::End,
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            KeymapAction::PagerEnd => {
                 if let Some(cache) = self.wrap_cache.as_ref()
                     && !cache.wrapped.is_empty()
                 {
@@ -683,22 +647,12 @@ impl PagerView {
                     self.commit_mode = false;
                     self.ensure_cursor_visible(area.height as usize);
                 }
-                self.g_pending = false;
             }
-            // Enter search mode with '/'; then 'Enter' to confirm; 'n'/'N' to navigate.
-            KeyEvent {
-                code: KeyCode::Char('/'),
-                kind: KeyEventKind::Press,
-                ..
-            } => {
-                self.g_pending = false;
+            // Enter search mode; then 'Enter' to confirm and next/previous match to navigate.
+            KeymapAction::PagerSearch => {
                 self.search_input = Some(String::new());
             }
-            KeyEvent {
-                code: KeyCode::Char('n'),
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            KeymapAction::PagerNextMatch => {
                 if let Some(q) = self.last_search.clone() {
                     let start = self
                         .last_match_idx
@@ -710,13 +664,8 @@ impl PagerView {
                         self.last_match_idx = Some(idx);
                     }
                 }
-                self.g_pending = false;
             }
-            KeyEvent {
-                code: KeyCode::Char('N'),
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            KeymapAction::PagerPreviousMatch => {
                 if let Some(q) = self.last_search.clone() {
                     let start = self.last_match_idx.unwrap_or(self.scroll_offset);
                     if let Some(idx) = self.find_prev_match(&q, start) {
@@ -725,18 +674,54 @@ impl PagerView {
                         self.last_match_idx = Some(idx);
                     }
                 }
-                self.g_pending = false;
-            }
-            _ => {
-                self.g_pending = false;
-                return Ok(());
             }
+            // Closing is handled by the overlay that owns the view.
+            _ => return Ok(()),
         }
         tui.frame_requester()
             .schedule_frame_in(Duration::from_millis(16));
         Ok(())
     }
 
+    /// Keys outside the keymap: horizontal scrolling and commit-branch
+    /// navigation. Returns whether the key was handled.
+    fn handle_unbound_key(&mut self, key_event: KeyEvent, area: Rect) -> bool {
+        if !matches!(key_event.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+            return false;
+        }
+        match key_event.code {
+            KeyCode::Char('h') => {
+                if self.commit_mode {
+                    self.move_commit_horizontal(-1, area.width, area.height);
+                } else {
+                    self.horiz_offset = self.horiz_offset.saturating_sub(1);
+                }
+            }
+            KeyCode::Char('l') => {
+                if self.commit_mode {
+                    self.move_commit_horizontal(1, area.width, area.height);
+                } else {
+                    self.horiz_offset = self.horiz_offset.saturating_add(1);
+                }
+            }
+            KeyCode::Char('0') => self.horiz_offset = 0,
+            // Enter and Esc are ignored; commit mode is active by default in
+            // the git graph overlay.
+            _ => return false,
+        }
+        true
+    }
+
+    /// Whether `key_event` is text for the `/` search prompt rather than a
+    /// command, so plain keys like `q` can be searched for.
+    fn types_into_search(&self, key_event: &KeyEvent) -> bool {
+        self.search_input.is_some()
+            && matches!(key_event.code, KeyCode::Char(_))
+            && !key_event
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+
     fn update_last_content_height(&mut self, height: u16) {
         self.last_content_height = Some(height as usize);
     }
@@ -1557,8 +1542,9 @@ impl TranscriptOverlay {
     fn render_hints(&self, area: Rect, buf: &mut Buffer) {
         let line1 = Rect::new(area.x, area.y, area.width, 1);
         let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
-        render_key_hints(line1, buf, PAGER_KEY_HINTS);
-        let mut pairs: Vec<(&str, &str)> = vec![("q", "quit"), ("Esc", "edit prev")];
+        render_pager_key_hints(line1, buf);
+        let close = keymap().label(KeymapAction::PagerClose);
+        let mut pairs: Vec<(&str, &str)> = vec![(close.as_str(), "quit"), ("Esc", "edit prev")];
         if self.highlight_cell.is_some() {
             pairs.push(("⏎", "edit message"));
         }
@@ -1577,41 +1563,16 @@ impl TranscriptOverlay {
 impl TranscriptOverlay {
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match event {
-            TuiEvent::Key(key_event) => match key_event {
-                KeyEvent {
-                    code: KeyCode::Char('q'),
-                    kind: KeyEventKind::Press,
-                    ..
-                }
-                | KeyEvent {
-                    code: KeyCode::Char('t'),
-                    modifiers: crossterm::event::KeyModifiers::CONTROL,
-                    kind: KeyEventKind::Press,
-                    ..
-                }
-                | KeyEvent {
-                    code: KeyCode::Char('c'),
-                    modifiers: crossterm::event::KeyModifiers::CONTROL,
-                    kind: KeyEventKind::Press,
-                    ..
-                } => {
-                    // Don't treat plain 'q' as quit when in search input mode; forward to view.
-                    if matches!(
-                        key_event,
-                        KeyEvent {
-                            code: KeyCode::Char('q'),
-                            ..
-                        }
-                    ) && self.view.search_input.is_some()
-                    {
-                        self.view.handle_key_event(tui, key_event)
-                    } else {
-                        self.is_done = true;
-                        Ok(())
-                    }
+            TuiEvent::Key(key_event) => {
+                let closes = keymap().is_match(KeymapAction::PagerClose, &key_event)
+                    || keymap().is_match(KeymapAction::ShowTranscript, &key_event);
+                if closes && !self.view.types_into_search(&key_event) {
+                    self.is_done = true;
+                    Ok(())
+                } else {
+                    self.view.handle_key_event(tui, key_event)
                 }
-                other => self.view.handle_key_event(tui, other),
-            },
+            }
             TuiEvent::Draw => {
                 tui.draw(u16::MAX, |frame| {
                     self.render(frame.area(), frame.buffer);
@@ -1768,11 +1729,13 @@ impl StaticOverlay {
     fn render_hints(&self, area: Rect, buf: &mut Buffer) {
         let line1 = Rect::new(area.x, area.y, area.width, 1);
         let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
-        render_key_hints(line1, buf, PAGER_KEY_HINTS);
+        render_pager_key_hints(line1, buf);
+        let close = keymap().label(KeymapAction::PagerClose);
+        let refresh = keymap().label(KeymapAction::GitGraphRefresh);
         let pairs = if self.refresh_callback.is_some() {
-            [("r", "refresh"), ("q", "quit")]
+            [(refresh.as_str(), "refresh"), (close.as_str(), "quit")]
         } else {
-            [("q", "quit"), ("", "")]
+            [(close.as_str(), "quit"), ("", "")]
         };
         render_key_hints(line2, buf, &pairs);
     }
@@ -1791,10 +1754,9 @@ impl StaticOverlay {
         match event {
             TuiEvent::Key(key_event) => match key_event {
                 KeyEvent {
-                    code: KeyCode::Char('r'),
                     kind: KeyEventKind::Press,
                     ..
-                } => {
+                } if keymap().is_match(KeymapAction::GitGraphRefresh, &key_event) => {
                     // Refresh the content if callback is available
                     if self.view.search_input.is_none() && self.refresh_callback.is_some() {
                         if self.can_refresh() {
@@ -1807,31 +1769,11 @@ impl StaticOverlay {
                     }
                     Ok(())
                 }
-                KeyEvent {
-                    code: KeyCode::Char('q'),
-                    kind: KeyEventKind::Press,
-                    ..
-                }
-                | KeyEvent {
-                    code: KeyCode::Char('c'),
-                    modifiers: crossterm::event::KeyModifiers::CONTROL,
-                    kind: KeyEventKind::Press,
-                    ..
-                } => {
-                    // When search input is active, treat 'q' as input, not quit.
-                    if matches!(
-                        key_event,
-                        KeyEvent {
-                            code: KeyCode::Char('q'),
-                            ..
-                        }
-                    ) && self.view.search_input.is_some()
-                    {
-                        self.view.handle_key_event(tui, key_event)
-                    } else {
-                        self.is_done = true;
-                        Ok(())
-                    }
+                _ if keymap().is_match(KeymapAction::PagerClose, &key_event)
+                    && !self.view.types_into_search(&key_event) =>
+                {
+                    self.is_done = true;
+                    Ok(())
                 }
                 other => self.view.handle_key_event(tui, other),
             },
@@ -1874,7 +1816,7 @@ impl SessionPickerOverlay {
         match event {
             TuiEvent::Key(key_event) => {
                 // Convert KeyCode to PickerEvent using the static method
-                if let Some(picker_event) = self.picker_state.key_to_event(key_event.code) {
+                if let Some(picker_event) = self.picker_state.key_to_event(&key_event) {
                     // Handle the picker event and get optional session ID
                     if let Some(session_id) = self.picker_state.handle_event(picker_event) {
                         if session_id.is_empty() {
@@ -2038,6 +1980,7 @@ mod tests {
             PathBuf::from("foo.txt"),
             FileChange::Add {
                 content: "hello\nworld\n".to_string(),
+                mode: None,
             },
         );
         let approval_cell: Arc<dyn HistoryCell> = Arc::new(new_patch_event(approval_changes, &cwd));
@@ -2048,6 +1991,7 @@ mod tests {
             PathBuf::from("foo.txt"),
             FileChange::Add {
                 content: "hello\nworld\n".to_string(),
+                mode: None,
             },
         );
         let apply_begin_cell: Arc<dyn HistoryCell> = Arc::new(new_patch_event(apply_changes, &cwd));
@@ -5,6 +5,7 @@ use crate::history_cell::with_border_with_inner_width;
 use crate::version::CODEX_CLI_VERSION;
 use codex_common::create_config_summary_entries;
 use codex_core::config::Config;
+use codex_core::protocol::CostSummary;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::TokenUsage;
 use codex_protocol::ConversationId;
@@ -21,6 +22,7 @@ use super::format::truncate_line_to_width;
 use super::helpers::compose_account_display;
 use super::helpers::compose_agents_summary;
 use super::helpers::compose_model_display;
+use super::helpers::compose_model_family_display;
 use super::helpers::format_directory_display;
 use super::helpers::format_tokens_compact;
 use super::rate_limits::RateLimitSnapshotDisplay;
@@ -48,13 +50,16 @@ pub(crate) struct StatusTokenUsageData {
 struct StatusHistoryCell {
     model_name: String,
     model_details: Vec<String>,
+    model_family: Option<(String, String)>,
     directory: PathBuf,
     approval: String,
     sandbox: String,
     agents_summary: String,
     account: Option<StatusAccountDisplay>,
+    auth_profile: Option<String>,
     session_id: Option<String>,
     token_usage: StatusTokenUsageData,
+    cost: Option<CostSummary>,
     rate_limits: StatusRateLimitData,
 }
 
@@ -64,9 +69,17 @@ pub(crate) fn new_status_output(
     context_usage: Option<&TokenUsage>,
     session_id: &Option<ConversationId>,
     rate_limits: Option<&RateLimitSnapshotDisplay>,
+    cost: Option<&CostSummary>,
 ) -> CompositeHistoryCell {
     let command = PlainHistoryCell::new(vec!["/status".magenta().into()]);
-    let card = StatusHistoryCell::new(config, total_usage, context_usage, session_id, rate_limits);
+    let card = StatusHistoryCell::new(
+        config,
+        total_usage,
+        context_usage,
+        session_id,
+        rate_limits,
+        cost,
+    );
 
     CompositeHistoryCell::new(vec![Box::new(command), Box::new(card)])
 }
@@ -78,9 +91,11 @@ impl StatusHistoryCell {
         context_usage: Option<&TokenUsage>,
         session_id: &Option<ConversationId>,
         rate_limits: Option<&RateLimitSnapshotDisplay>,
+        cost: Option<&CostSummary>,
     ) -> Self {
         let config_entries = create_config_summary_entries(config);
         let (model_name, model_details) = compose_model_display(config, &config_entries);
+        let model_family = compose_model_family_display(config);
         let approval = config_entries
             .iter()
             .find(|(k, _)| *k == "approval")
@@ -113,13 +128,16 @@ impl StatusHistoryCell {
         Self {
             model_name,
             model_details,
+            model_family,
             directory: config.cwd.clone(),
             approval,
             sandbox,
             agents_summary,
             account,
+            auth_profile: config.auth_profile.clone(),
             session_id,
             token_usage,
+            cost: cost.copied(),
             rate_limits,
         }
     }
@@ -142,6 +160,20 @@ impl StatusHistoryCell {
         ]
     }
 
+    fn cost_spans(&self) -> Option<Vec<Span<'static>>> {
+        let cost = self.cost.as_ref()?;
+        Some(vec![
+            Span::from(format!("${:.2}", cost.session_usd)),
+            Span::from(" session").dim(),
+            Span::from(" · ").dim(),
+            Span::from(format!("${:.2}", cost.project_usd)).dim(),
+            Span::from(" project").dim(),
+            Span::from(" · ").dim(),
+            Span::from(format!("${:.2}", cost.daily_usd)).dim(),
+            Span::from(" today").dim(),
+        ])
+    }
+
     fn context_window_spans(&self) -> Option<Vec<Span<'static>>> {
         let context = self.token_usage.context_window.as_ref()?;
         let percent = context.percent_remaining;
@@ -255,6 +287,10 @@ impl HistoryCell for StatusHistoryCell {
                 "API key configured (run codex login to use ChatGPT)".to_string()
             }
         });
+        let account_value = account_value.map(|value| match &self.auth_profile {
+            Some(name) => format!("{value} · profile {name}"),
+            None => value,
+        });
 
         let mut labels: Vec<String> =
             vec!["Model", "Directory", "Approval", "Sandbox", "Agents.md"]
@@ -263,6 +299,9 @@ impl HistoryCell for StatusHistoryCell {
                 .collect();
         let mut seen: BTreeSet<String> = labels.iter().cloned().collect();
 
+        if self.model_family.is_some() {
+            push_label(&mut labels, &mut seen, "Model family");
+        }
         if account_value.is_some() {
             push_label(&mut labels, &mut seen, "Account");
         }
@@ -270,6 +309,9 @@ impl HistoryCell for StatusHistoryCell {
             push_label(&mut labels, &mut seen, "Session");
         }
         push_label(&mut labels, &mut seen, "Token usage");
+        if self.cost.is_some() {
+            push_label(&mut labels, &mut seen, "Estimated cost");
+        }
         if self.token_usage.context_window.is_some() {
             push_label(&mut labels, &mut seen, "Context window");
         }
@@ -288,6 +330,17 @@ impl HistoryCell for StatusHistoryCell {
         let directory_value = format_directory_display(&self.directory, Some(value_width));
 
         lines.push(formatter.line("Model", model_spans));
+        if let Some((family, overrides)) = self.model_family.as_ref() {
+            lines.push(formatter.line(
+                "Model family",
+                vec![
+                    Span::from(family.clone()),
+                    Span::from(" (").dim(),
+                    Span::from(overrides.clone()).dim(),
+                    Span::from(")").dim(),
+                ],
+            ));
+        }
         lines.push(formatter.line("Directory", vec![Span::from(directory_value)]));
         lines.push(formatter.line("Approval", vec![Span::from(self.approval.clone())]));
         lines.push(formatter.line("Sandbox", vec![Span::from(self.sandbox.clone())]));
@@ -305,6 +358,9 @@ impl HistoryCell for StatusHistoryCell {
         // Hide token usage only for ChatGPT subscribers
         if !matches!(self.account, Some(StatusAccountDisplay::ChatGpt { .. })) {
             lines.push(formatter.line("Token usage", self.token_usage_spans()));
+            if let Some(spans) = self.cost_spans() {
+                lines.push(formatter.line("Estimated cost", spans));
+            }
         }
 
         if let Some(spans) = self.context_window_spans() {
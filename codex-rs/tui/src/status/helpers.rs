@@ -2,8 +2,7 @@ use crate::exec_command::relativize_to_home;
 use crate::text_formatting;
 use chrono::DateTime;
 use chrono::Local;
-use codex_core::auth::get_auth_file;
-use codex_core::auth::try_read_auth_json;
+use codex_core::auth::load_auth_dot_json;
 use codex_core::config::Config;
 use codex_core::project_doc::discover_project_doc_paths;
 use std::path::Path;
@@ -35,6 +34,17 @@ pub(crate) fn compose_model_display(
     (config.model.clone(), details)
 }
 
+/// Family name and the fields set by the `[model_families]` table that applies
+/// to the session's model, if any.
+pub(crate) fn compose_model_family_display(config: &Config) -> Option<(String, String)> {
+    let (key, family_override) = config.model_family_override()?;
+    let fields = family_override.overridden_fields().join(", ");
+    Some((
+        config.model_family.family.clone(),
+        format!("[model_families.{key}]: {fields}"),
+    ))
+}
+
 pub(crate) fn compose_agents_summary(config: &Config) -> String {
     match discover_project_doc_paths(config) {
         Ok(paths) => {
@@ -84,8 +94,12 @@ pub(crate) fn compose_agents_summary(config: &Config) -> String {
 }
 
 pub(crate) fn compose_account_display(config: &Config) -> Option<StatusAccountDisplay> {
-    let auth_file = get_auth_file(&config.codex_home);
-    let auth = try_read_auth_json(&auth_file).ok()?;
+    let auth = load_auth_dot_json(
+        &config.codex_home,
+        config.auth_profile.as_deref(),
+        config.cli_auth_credentials_store_mode,
+    )
+    .ok()??;
 
     if let Some(tokens) = auth.tokens.as_ref() {
         let info = &tokens.id_token;
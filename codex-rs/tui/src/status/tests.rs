@@ -5,6 +5,9 @@ use chrono::TimeZone;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 use codex_core::config::ConfigToml;
+use codex_core::config_types::ModelFamilyToml;
+use codex_core::model_family::ModelFamilyOverride;
+use codex_core::protocol::CostSummary;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_core::protocol::RateLimitWindow;
 use codex_core::protocol::SandboxPolicy;
@@ -103,7 +106,14 @@ fn status_snapshot_includes_reasoning_details() {
         .expect("timestamp");
     let rate_display = rate_limit_snapshot_display(&snapshot, captured_at);
 
-    let composite = new_status_output(&config, &usage, Some(&usage), &None, Some(&rate_display));
+    let composite = new_status_output(
+        &config,
+        &usage,
+        Some(&usage),
+        &None,
+        Some(&rate_display),
+        None,
+    );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
         for line in &mut rendered_lines {
@@ -144,7 +154,14 @@ fn status_snapshot_includes_monthly_limit() {
         .expect("timestamp");
     let rate_display = rate_limit_snapshot_display(&snapshot, captured_at);
 
-    let composite = new_status_output(&config, &usage, Some(&usage), &None, Some(&rate_display));
+    let composite = new_status_output(
+        &config,
+        &usage,
+        Some(&usage),
+        &None,
+        Some(&rate_display),
+        None,
+    );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
         for line in &mut rendered_lines {
@@ -170,7 +187,7 @@ fn status_card_token_usage_excludes_cached_tokens() {
         total_tokens: 2_100,
     };
 
-    let composite = new_status_output(&config, &usage, Some(&usage), &None, None);
+    let composite = new_status_output(&config, &usage, Some(&usage), &None, None, None);
     let rendered = render_lines(&composite.display_lines(120));
 
     assert!(
@@ -211,7 +228,14 @@ fn status_snapshot_truncates_in_narrow_terminal() {
         .expect("timestamp");
     let rate_display = rate_limit_snapshot_display(&snapshot, captured_at);
 
-    let composite = new_status_output(&config, &usage, Some(&usage), &None, Some(&rate_display));
+    let composite = new_status_output(
+        &config,
+        &usage,
+        Some(&usage),
+        &None,
+        Some(&rate_display),
+        None,
+    );
     let mut rendered_lines = render_lines(&composite.display_lines(46));
     if cfg!(windows) {
         for line in &mut rendered_lines {
@@ -238,7 +262,7 @@ fn status_snapshot_shows_missing_limits_message() {
         total_tokens: 750,
     };
 
-    let composite = new_status_output(&config, &usage, Some(&usage), &None, None);
+    let composite = new_status_output(&config, &usage, Some(&usage), &None, None, None);
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
         for line in &mut rendered_lines {
@@ -274,7 +298,14 @@ fn status_snapshot_shows_empty_limits_message() {
         .expect("timestamp");
     let rate_display = rate_limit_snapshot_display(&snapshot, captured_at);
 
-    let composite = new_status_output(&config, &usage, Some(&usage), &None, Some(&rate_display));
+    let composite = new_status_output(
+        &config,
+        &usage,
+        Some(&usage),
+        &None,
+        Some(&rate_display),
+        None,
+    );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
         for line in &mut rendered_lines {
@@ -306,7 +337,7 @@ fn status_context_window_uses_last_usage() {
         total_tokens: 13_679,
     };
 
-    let composite = new_status_output(&config, &total_usage, Some(&last_usage), &None, None);
+    let composite = new_status_output(&config, &total_usage, Some(&last_usage), &None, None, None);
     let rendered_lines = render_lines(&composite.display_lines(80));
     let context_line = rendered_lines
         .into_iter()
@@ -322,3 +353,90 @@ fn status_context_window_uses_last_usage() {
         "context line should not use total aggregated tokens, got: {context_line}"
     );
 }
+
+#[test]
+fn status_shows_model_family_override() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home);
+    config.model = "llama-3.3-70b".to_string();
+    config.model_family.family = "llama-3".to_string();
+    config.model_families.insert(
+        "llama-3".to_string(),
+        ModelFamilyOverride {
+            settings: ModelFamilyToml {
+                family: Some("llama-3".to_string()),
+                context_window: Some(128_000),
+                ..Default::default()
+            },
+            base_instructions: None,
+        },
+    );
+
+    let composite = new_status_output(&config, &TokenUsage::default(), None, &None, None, None);
+    let rendered_lines = render_lines(&composite.display_lines(120));
+    let family_line = rendered_lines
+        .into_iter()
+        .find(|line| line.contains("Model family"))
+        .expect("model family line");
+
+    assert!(
+        family_line.contains("llama-3 ([model_families.llama-3]: family, context_window)"),
+        "unexpected model family line: {family_line}"
+    );
+}
+
+#[test]
+fn status_shows_estimated_cost() {
+    let temp_home = TempDir::new().expect("temp home");
+    let config = test_config(&temp_home);
+    let cost = CostSummary {
+        session_usd: 0.4213,
+        project_usd: 12.5,
+        daily_usd: 3.0,
+    };
+
+    let composite = new_status_output(
+        &config,
+        &TokenUsage::default(),
+        None,
+        &None,
+        None,
+        Some(&cost),
+    );
+    let rendered_lines = render_lines(&composite.display_lines(120));
+    let cost_line = rendered_lines
+        .into_iter()
+        .find(|line| line.contains("Estimated cost"))
+        .expect("cost line");
+
+    assert!(
+        cost_line.contains("$0.42 session · $12.50 project · $3.00 today"),
+        "unexpected cost line: {cost_line}"
+    );
+}
+
+#[test]
+fn status_shows_active_auth_profile() {
+    let temp_home = TempDir::new().expect("temp home");
+    codex_core::auth::login_with_api_key_for_profile(
+        temp_home.path(),
+        Some("work"),
+        codex_core::auth::AuthCredentialsStoreMode::File,
+        "sk-work",
+    )
+    .expect("store work credentials");
+    let mut config = test_config(&temp_home);
+    config.auth_profile = Some("work".to_string());
+
+    let composite = new_status_output(&config, &TokenUsage::default(), None, &None, None, None);
+    let rendered_lines = render_lines(&composite.display_lines(120));
+    let account_line = rendered_lines
+        .into_iter()
+        .find(|line| line.contains("Account"))
+        .expect("account line");
+
+    assert!(
+        account_line.contains("API key configured (run codex login to use ChatGPT) · profile work"),
+        "unexpected account line: {account_line}"
+    );
+}